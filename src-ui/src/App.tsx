@@ -14,6 +14,7 @@ import { InputBar } from './components/chat/InputBar';
 import { StatusCapsule } from './components/shared/StatusCapsule';
 import { SettingsModal } from './components/settings/SettingsModal';
 import type {
+  ReasoningChunk,
   StreamChunk,
   AgentStatePayload,
   ApprovalRequest,
@@ -21,6 +22,7 @@ import type {
   TodoListPayload,
   StepStartedPayload,
   StepCompletedPayload,
+  QueuedTask,
 } from './types/agent';
 
 // ── Window controls ───────────────────────────────────────────────────────────
@@ -89,9 +91,19 @@ const AppInner = observer(() => {
   }, []);
   useTauriEvent<StreamChunk>('llm_stream_chunk', handleStreamChunk);
 
+  const handleReasoningChunk = useCallback((chunk: ReasoningChunk) => {
+    agentStore.handleReasoningChunk(chunk);
+  }, []);
+  useTauriEvent<ReasoningChunk>('llm_reasoning_chunk', handleReasoningChunk);
+
   const handleStateChange = useCallback((payload: AgentStatePayload) => {
     // Extract terminal message from payload (error message or completion summary)
     const terminalMessage = payload.message || payload.summary;
+    // A queued or restored goal starting on its own — show it like one typed now.
+    if (payload.state === 'routing' && (payload.from_queue || payload.restored) && payload.goal) {
+      agentStore.startNewTask();
+      agentStore.addUserMessage(payload.goal);
+    }
     agentStore.setState(payload.state, terminalMessage);
     // Pre-open an assistant message bubble for states that will stream LLM content,
     // so the "thinking" indicator appears immediately without waiting for the first chunk.
@@ -102,6 +114,14 @@ const AppInner = observer(() => {
   }, []);
   useTauriEvent<AgentStatePayload>('agent_state_changed', handleStateChange);
 
+  /** Goal typed into the tray's quick-goal prompt — show it like one typed here. */
+  const handleQuickGoal = useCallback((payload: { task: string }) => {
+    agentStore.startNewTask();
+    agentStore.addUserMessage(payload.task);
+    agentStore.setState('routing');
+  }, []);
+  useTauriEvent<{ task: string }>('quick_goal_submitted', handleQuickGoal);
+
   const handleApprovalRequest = useCallback((req: ApprovalRequest) => {
     if (settingsStore.permanentlyAllowed.includes(req.action.type)) {
       invoke('confirm_action', { approved: true });
@@ -111,6 +131,14 @@ const AppInner = observer(() => {
   }, []);
   useTauriEvent<ApprovalRequest>('action_required', handleApprovalRequest);
 
+  // A window opened from the tray after the request was emitted (background
+  // mode) would otherwise never see it.
+  useEffect(() => {
+    invoke<ApprovalRequest | null>('get_pending_approval').then((req) => {
+      if (req) handleApprovalRequest(req);
+    });
+  }, [handleApprovalRequest]);
+
   const handleViewportCaptured = useCallback((payload: ViewportCapturedPayload) => {
     agentStore.handleViewportCaptured(payload);
   }, []);
@@ -122,6 +150,17 @@ const AppInner = observer(() => {
   }, []);
   useTauriEvent<{ text: string }>('agent_activity', handleActivity);
 
+  const handleQueueUpdated = useCallback((payload: { items: QueuedTask[] }) => {
+    agentStore.setQueue(payload.items);
+  }, []);
+  useTauriEvent<{ items: QueuedTask[] }>('queue_updated', handleQueueUpdated);
+
+  /** Live output lines from execute_terminal */
+  const handleTerminalOutput = useCallback((payload: { stream: 'stdout' | 'stderr'; line: string }) => {
+    if (payload.line.trim()) agentStore.setActivity(`$ ${payload.line}`);
+  }, []);
+  useTauriEvent<{ stream: 'stdout' | 'stderr'; line: string }>('terminal_output', handleTerminalOutput);
+
   /** TodoList events from planner / step engine */
   const handleTodoList = useCallback((payload: TodoListPayload) => {
     agentStore.setTodoList(payload);
@@ -144,6 +183,12 @@ const AppInner = observer(() => {
   }, []);
   useTauriEvent<Record<string, unknown>>('config_updated', handleConfigUpdated);
 
+  // A hand edit to config.toml that doesn't parse or validate is not applied
+  const handleConfigReloadFailed = useCallback((payload: { message: string }) => {
+    console.warn('config.toml not reloaded:', payload.message);
+  }, []);
+  useTauriEvent<{ message: string }>('config_reload_failed', handleConfigReloadFailed);
+
   const toggleTheme = () => {
     const next = mode === 'dark' ? 'light' : 'dark';
     setMode(next);
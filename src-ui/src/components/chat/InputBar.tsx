@@ -3,20 +3,32 @@ import { observer } from 'mobx-react-lite';
 import Box from '@mui/joy/Box';
 import Textarea from '@mui/joy/Textarea';
 import IconButton from '@mui/joy/IconButton';
-import { ArrowUp, Square } from 'lucide-react';
+import Chip from '@mui/joy/Chip';
+import ChipDelete from '@mui/joy/ChipDelete';
+import { ArrowUp, ListPlus, Pause, Play, Square } from 'lucide-react';
 import { invoke } from '@tauri-apps/api/core';
 import { agentStore } from '../../store/AgentStore';
 
 export const InputBar = observer(() => {
   const [value, setValue] = useState('');
   const textareaRef = useRef<HTMLTextAreaElement>(null);
-  const { isRunning } = agentStore;
+  const { isRunning, queue } = agentStore;
+  const isPaused = agentStore.state === 'paused';
 
   const handleSubmit = useCallback(async () => {
     const task = value.trim();
-    if (!task || isRunning) return;
+    if (!task) return;
 
     setValue('');
+    // While a task runs, the backend queues the goal behind it.
+    if (isRunning) {
+      try {
+        await invoke('start_task', { task });
+      } catch (err) {
+        console.error('start_task (queue) failed:', err);
+      }
+      return;
+    }
     agentStore.startNewTask();
     agentStore.addUserMessage(task);
     agentStore.setState('routing');
@@ -38,6 +50,22 @@ export const InputBar = observer(() => {
     }
   }, []);
 
+  const handleCancelQueued = useCallback(async (id: number) => {
+    try {
+      await invoke('cancel_queued', { id });
+    } catch (err) {
+      console.error('cancel_queued failed:', err);
+    }
+  }, []);
+
+  const handlePauseResume = useCallback(async () => {
+    try {
+      await invoke(isPaused ? 'resume_task' : 'pause_task');
+    } catch (err) {
+      console.error('pause/resume failed:', err);
+    }
+  }, [isPaused]);
+
   const handleKeyDown = (e: React.KeyboardEvent) => {
     if (e.key === 'Enter' && !e.shiftKey) {
       e.preventDefault();
@@ -56,6 +84,23 @@ export const InputBar = observer(() => {
       }}
     >
       <Box sx={{ maxWidth: 740, mx: 'auto' }}>
+        {/* Goals queued behind the running task */}
+        {queue.length > 0 && (
+          <Box sx={{ display: 'flex', flexWrap: 'wrap', gap: 0.75, mb: 1 }}>
+            {queue.map((t, i) => (
+              <Chip
+                key={t.id}
+                size="sm"
+                variant="soft"
+                title={t.goal}
+                endDecorator={<ChipDelete onDelete={() => handleCancelQueued(t.id)} />}
+                sx={{ maxWidth: 240 }}
+              >
+                {i + 1}. {t.goal}
+              </Chip>
+            ))}
+          </Box>
+        )}
         {/* Gemini-style floating input card */}
         <Box
           sx={{
@@ -92,8 +137,9 @@ export const InputBar = observer(() => {
               value={value}
               onChange={(e) => setValue(e.target.value)}
               onKeyDown={handleKeyDown}
-              placeholder="描述你要完成的任务（Enter 发送，Shift+Enter 换行）"
-              disabled={isRunning}
+              placeholder={isRunning
+                ? '任务进行中，新任务将排队执行（Enter 加入队列）'
+                : '描述你要完成的任务（Enter 发送，Shift+Enter 换行）'}
               minRows={1}
               maxRows={6}
               variant="plain"
@@ -111,7 +157,31 @@ export const InputBar = observer(() => {
               }}
             />
 
-            {/* Send / Stop button */}
+            {/* Pause / Resume, Send / Stop buttons */}
+            {isRunning && (
+              <IconButton
+                variant="soft"
+                color="warning"
+                size="sm"
+                onClick={handlePauseResume}
+                title={isPaused ? '继续' : '暂停'}
+                sx={{ borderRadius: '50%', flexShrink: 0 }}
+              >
+                {isPaused ? <Play size={15} /> : <Pause size={15} />}
+              </IconButton>
+            )}
+            {isRunning && value.trim() && (
+              <IconButton
+                variant="soft"
+                color="neutral"
+                size="sm"
+                onClick={handleSubmit}
+                title="加入队列"
+                sx={{ borderRadius: '50%', flexShrink: 0 }}
+              >
+                <ListPlus size={15} />
+              </IconButton>
+            )}
             {isRunning ? (
               <IconButton
                 variant="solid"
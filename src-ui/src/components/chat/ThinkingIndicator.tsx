@@ -20,6 +20,8 @@ const STATE_ACTIVITY: Record<string, string> = {
   executing: '正在执行操作…',
   evaluating: '正在评估进度…',
   waiting_for_user: '等待您的确认…',
+  waiting_for_stability: '等待屏幕稳定…',
+  paused: '已暂停，您可以操作电脑，完成后点击继续',
 };
 
 function labelForState(state: AgentStateKind, activity: string | null): string {
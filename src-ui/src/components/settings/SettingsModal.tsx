@@ -8,6 +8,7 @@ import Typography from '@mui/joy/Typography';
 import FormControl from '@mui/joy/FormControl';
 import FormLabel from '@mui/joy/FormLabel';
 import Input from '@mui/joy/Input';
+import Textarea from '@mui/joy/Textarea';
 import Switch from '@mui/joy/Switch';
 import Checkbox from '@mui/joy/Checkbox';
 import RadioGroup from '@mui/joy/RadioGroup';
@@ -25,6 +26,7 @@ import Select from '@mui/joy/Select';
 import Option from '@mui/joy/Option';
 import { X, ChevronDown, KeyRound, AlertCircle } from 'lucide-react';
 import { settingsStore } from '../../store/SettingsStore';
+import type { LoopMode, ProfileList } from '../../types/settings';
 import presets from '../../data/provider-presets.json';
 
 // ── Types ─────────────────────────────────────────────────────────────────────
@@ -37,6 +39,12 @@ interface LocalProviderConfig {
   model: string;
   temperature: number;
   hasApiKey: boolean;
+  /** Wire protocol: null = OpenAI-compatible, 'gemini' = native Gemini API. */
+  adapter?: string | null;
+  /** Retry policy table, edited in config.toml and passed through unchanged. */
+  retry?: Record<string, unknown>;
+  /** Supports response_format json_schema; edited in config.toml. */
+  structuredOutput?: boolean;
 }
 
 interface RoleConfig {
@@ -44,8 +52,18 @@ interface RoleConfig {
   model: string;
   stream: boolean;
   temperature?: number;
+  /** Failover chain (provider ids or "provider:model"), edited in config.toml. */
+  fallback?: string[];
+  /** Model context window in tokens, edited in config.toml. */
+  contextTokens?: number;
+  timeoutSecs?: number;
+  /** Fields the form doesn't edit (max_tokens, top_p, extra_body, …), kept as loaded */
+  rest?: Record<string, unknown>;
 }
 
+/** Role keys the settings form maps to its own fields. */
+const EDITED_ROLE_KEYS = ['provider', 'model', 'stream', 'temperature', 'fallback', 'context_tokens', 'timeout_secs'];
+
 interface McpServer {
   name: string;
   command: string;
@@ -59,6 +77,16 @@ interface LocalSafety {
   requireApprovalFor: string[];
   maxConsecutiveFailures: number;
   maxLoopDurationMinutes: number;
+  loopMode: LoopMode;
+}
+
+interface LocalPreferences {
+  /** Free-text rules the planner must follow. */
+  constraints: string[];
+  /** Folders files may be saved to (enforced). */
+  savePaths: string[];
+  /** Language of agent messages: follow each goal, or always en / zh. */
+  language: 'auto' | 'en' | 'zh';
 }
 
 interface LocalConfig {
@@ -71,8 +99,13 @@ interface LocalConfig {
     vision?: RoleConfig;
   };
   safety: LocalSafety;
+  preferences: LocalPreferences;
   theme: 'light' | 'dark' | 'system';
   mcpServers: McpServer[];
+  /** Action types with an approval toggle, as reported by the backend */
+  approvalActions: string[];
+  /** Keep API keys in the OS credential store instead of config.toml */
+  keyring: boolean;
 }
 
 type PresetsMap = Record<string, { displayName: string; models: string[] }>;
@@ -80,11 +113,12 @@ const PRESETS: PresetsMap = presets as PresetsMap;
 
 // ── Constants ─────────────────────────────────────────────────────────────────
 
+/** Fallback when the backend payload has no `approval_actions`. */
 const BUILTIN_TOOLS: string[] = [
-  'mouse_click', 'mouse_double_click', 'mouse_right_click', 'scroll',
-  'type_text', 'hotkey', 'key_press', 'get_viewport',
-  'execute_terminal', 'mcp_call', 'invoke_skill', 'wait',
-  'finish_task', 'report_failure',
+  'mouse_click', 'mouse_double_click', 'mouse_right_click', 'mouse_click_at', 'scroll',
+  'type_text', 'hotkey', 'key_press', 'keyboard_navigate', 'select_monitor',
+  'clipboard_read', 'clipboard_write', 'open_app', 'read_file', 'list_dir',
+  'write_file', 'move_file', 'execute_terminal', 'mcp_call', 'invoke_skill',
 ];
 
 const ROLES: Array<{ key: keyof LocalConfig['roles']; label: string }> = [
@@ -104,23 +138,35 @@ const DEFAULT_CONFIG: LocalConfig = {
     requireApprovalFor: ['execute_terminal', 'mcp_call'],
     maxConsecutiveFailures: 5,
     maxLoopDurationMinutes: 0,
+    loopMode: 'until_done',
   },
+  preferences: { constraints: [], savePaths: [], language: 'auto' },
   theme: 'system',
   mcpServers: [],
+  approvalActions: BUILTIN_TOOLS,
+  keyring: false,
 };
 
 // ── Helpers ───────────────────────────────────────────────────────────────────
 
 /** A provider is "configured" when it has a key set (either in config or env). */
 function isProviderConfigured(p: LocalProviderConfig) {
+  // Local Ollama servers need no API key.
+  if (p.adapter === 'ollama') return true;
   return p.hasApiKey || (p.apiKey && p.apiKey.trim() !== '' && p.apiKey !== '***');
 }
 
+/** Textarea value → list, keeping the line being typed. */
+function splitLines(value: string): string[] {
+  return value.split('\n').map((l) => l.trimStart());
+}
+
 // ── Config mappings ───────────────────────────────────────────────────────────
 
 function mapBackendConfig(raw: Record<string, unknown>): LocalConfig {
   const llm = (raw.llm as Record<string, unknown>) ?? {};
   const safety = (raw.safety as Record<string, unknown>) ?? {};
+  const preferences = (raw.preferences as Record<string, unknown>) ?? {};
   const mcp = (raw.mcp as Record<string, unknown>) ?? {};
   const activeProvider = (llm.active_provider as string) ?? 'zhipu';
   const rawProviders = (llm.providers as Record<string, unknown>) ?? {};
@@ -135,6 +181,9 @@ function mapBackendConfig(raw: Record<string, unknown>): LocalConfig {
       model: (p.model as string) ?? '',
       temperature: (p.temperature as number) ?? 0.1,
       hasApiKey: Boolean(p.api_key),
+      adapter: (p.adapter as string) ?? null,
+      retry: p.retry as Record<string, unknown> | undefined,
+      structuredOutput: (p.structured_output as boolean) ?? false,
     };
   });
 
@@ -144,10 +193,14 @@ function mapBackendConfig(raw: Record<string, unknown>): LocalConfig {
     const r = rawRoles[key] as Record<string, unknown> | undefined;
     if (r) {
       roles[key] = {
+        rest: Object.fromEntries(Object.entries(r).filter(([k]) => !EDITED_ROLE_KEYS.includes(k))),
         provider: (r.provider as string) ?? activeProvider,
         model: (r.model as string) ?? '',
         stream: (r.stream as boolean) ?? false,
         temperature: r.temperature as number | undefined,
+        fallback: (r.fallback as string[]) ?? [],
+        contextTokens: r.context_tokens as number | undefined,
+        timeoutSecs: r.timeout_secs as number | undefined,
       };
     }
   }
@@ -163,6 +216,12 @@ function mapBackendConfig(raw: Record<string, unknown>): LocalConfig {
       requireApprovalFor: (safety.require_approval_for as string[]) ?? [],
       maxConsecutiveFailures: (safety.max_consecutive_failures as number) ?? 5,
       maxLoopDurationMinutes: (safety.max_loop_duration_minutes as number) ?? 0,
+      loopMode: (safety.loop_mode as LoopMode) ?? 'until_done',
+    },
+    preferences: {
+      constraints: (preferences.constraints as string[]) ?? [],
+      savePaths: (preferences.save_paths as string[]) ?? [],
+      language: (preferences.language as LocalPreferences['language']) ?? 'auto',
     },
     theme: (raw.theme as 'light' | 'dark' | 'system') ?? 'system',
     mcpServers: rawServers.map((s) => ({
@@ -171,6 +230,8 @@ function mapBackendConfig(raw: Record<string, unknown>): LocalConfig {
       args: (s.args as string[]) ?? [],
       enabled: (s.enabled as boolean) ?? false,
     })),
+    approvalActions: (raw.approval_actions as string[]) ?? BUILTIN_TOOLS,
+    keyring: (llm.keyring as boolean) ?? false,
   };
 }
 
@@ -183,30 +244,41 @@ function mapLocalToBackend(local: LocalConfig): Record<string, unknown> {
       model: p.model,
       temperature: p.temperature,
       api_key: p.apiKey ?? null,
-      adapter: null,
+      adapter: p.adapter ?? null,
+      ...(p.retry ? { retry: p.retry } : {}),
+      structured_output: p.structuredOutput ?? false,
     };
   }
   const roles: Record<string, unknown> = {};
   for (const [key, role] of Object.entries(local.roles)) {
     if (role) {
       roles[key] = {
+        ...(role.rest ?? {}),
         provider: role.provider,
         model: role.model,
         stream: role.stream,
         temperature: role.temperature ?? null,
+        fallback: role.fallback ?? [],
+        context_tokens: role.contextTokens ?? null,
+        timeout_secs: role.timeoutSecs ?? null,
       };
     }
   }
   return {
-    llm: { active_provider: local.activeProvider, providers, roles },
+    llm: { active_provider: local.activeProvider, providers, roles, keyring: local.keyring },
     safety: {
       allow_terminal_commands: local.safety.allowTerminalCommands,
       allow_file_operations: local.safety.allowFileOperations,
       require_approval_for: local.safety.requireApprovalFor,
       max_consecutive_failures: local.safety.maxConsecutiveFailures,
       max_loop_duration_minutes: local.safety.maxLoopDurationMinutes,
+      loop_mode: local.safety.loopMode,
+    },
+    preferences: {
+      constraints: local.preferences.constraints.filter((l) => l.trim() !== ''),
+      save_paths: local.preferences.savePaths.filter((l) => l.trim() !== ''),
+      language: local.preferences.language,
     },
-    prompts: { tools_file: '', system_template: '', experience_summary_template: '' },
     mcp: {
       servers: local.mcpServers.map((s) => ({
         name: s.name, command: s.command, args: s.args, enabled: s.enabled,
@@ -263,6 +335,8 @@ const SettingsModal: React.FC = observer(() => {
   const [loadError, setLoadError] = useState<string | null>(null);
   const [saving, setSaving] = useState(false);
   const [toast, setToast] = useState<{ msg: string; ok: boolean } | null>(null);
+  /** Models installed on local Ollama servers, keyed by provider id. */
+  const [localModels, setLocalModels] = useState<Record<string, string[]>>({});
 
   const isOpen = settingsStore.isSettingsOpen;
 
@@ -277,6 +351,21 @@ const SettingsModal: React.FC = observer(() => {
       .finally(() => setLoading(false));
   }, [isOpen]);
 
+  const ollamaBases = config.providers
+    .filter((p) => p.adapter === 'ollama')
+    .map((p) => `${p.id}=${p.apiBase}`)
+    .join('|');
+
+  useEffect(() => {
+    if (!isOpen) return;
+    for (const p of config.providers.filter((p) => p.adapter === 'ollama')) {
+      invoke<Array<{ name: string }>>('list_local_models', { apiBase: p.apiBase || null })
+        .then((models) => setLocalModels((prev) => ({ ...prev, [p.id]: models.map((m) => m.name) })))
+        .catch(() => setLocalModels((prev) => ({ ...prev, [p.id]: [] })));
+    }
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [isOpen, ollamaBases]);
+
   useEffect(() => {
     if (!toast) return;
     const id = setTimeout(() => setToast(null), 2800);
@@ -320,6 +409,9 @@ const SettingsModal: React.FC = observer(() => {
   const updateSafety = (field: keyof LocalSafety, value: unknown) =>
     setConfig((prev) => ({ ...prev, safety: { ...prev.safety, [field]: value } }));
 
+  const updatePreferences = (field: 'constraints' | 'savePaths', value: string[]) =>
+    setConfig((prev) => ({ ...prev, preferences: { ...prev.preferences, [field]: value } }));
+
   const toggleApproval = (tool: string) => {
     const cur = config.safety.requireApprovalFor;
     updateSafety('requireApprovalFor', cur.includes(tool) ? cur.filter((t) => t !== tool) : [...cur, tool]);
@@ -439,6 +531,7 @@ const SettingsModal: React.FC = observer(() => {
                           <AdvancedTab
                             config={config}
                             updateSafety={updateSafety}
+                            updatePreferences={updatePreferences}
                             toggleApproval={toggleApproval}
                             updateMcpServer={updateMcpServer}
                           />
@@ -500,6 +593,45 @@ const SettingsModal: React.FC = observer(() => {
 
 // ── BasicTab ──────────────────────────────────────────────────────────────────
 
+// ── ProfileSection ────────────────────────────────────────────────────────────
+
+/** Config profile picker; switching applies at once to the next task. Hidden
+ *  when config.toml defines no `[profiles]`. */
+function ProfileSection() {
+  const [list, setList] = useState<ProfileList | null>(null);
+
+  useEffect(() => {
+    invoke<ProfileList>('list_profiles')
+      .then(setList)
+      .catch((err) => console.error('list_profiles failed:', err));
+  }, []);
+
+  if (!list || list.profiles.length === 0) return null;
+
+  const select = async (name: string) => {
+    try {
+      await invoke('set_active_profile', { name });
+      setList({ ...list, active: name });
+    } catch (err) {
+      console.error('set_active_profile failed:', err);
+    }
+  };
+
+  return (
+    <Box sx={{ p: 3 }}>
+      <Box sx={{ display: 'flex', alignItems: 'center', justifyContent: 'space-between' }}>
+        <Typography level="title-sm">配置方案</Typography>
+        <Select size="sm" value={list.active} onChange={(_, v) => v !== null && select(v)} sx={{ minWidth: 160 }}>
+          <Option value="">默认</Option>
+          {list.profiles.map((p) => (
+            <Option key={p} value={p}>{p}</Option>
+          ))}
+        </Select>
+      </Box>
+    </Box>
+  );
+}
+
 function BasicTab({
   config, setConfig, updateProvider, updateRole,
 }: {
@@ -514,11 +646,17 @@ function BasicTab({
 
   return (
     <Stack spacing={0} divider={<Divider />}>
+      <ProfileSection />
       {/* Section A: Provider */}
       <Box sx={{ p: 3 }}>
         <Typography level="title-sm" sx={{ mb: 1.5 }}>
           模型提供商
         </Typography>
+        <Box sx={{ display: 'flex', alignItems: 'center', justifyContent: 'space-between', mb: 1.5 }}>
+          <Typography level="body-sm">API Key 保存到系统凭据管理器（不写入 config.toml）</Typography>
+          <Switch size="sm" checked={config.keyring}
+            onChange={(e) => setConfig((prev) => ({ ...prev, keyring: e.target.checked }))} />
+        </Box>
 
         {!hasAnyProvider ? (
           // No providers in config at all
@@ -541,7 +679,9 @@ function BasicTab({
             <AccordionGroup variant="outlined" sx={{ borderRadius: 'md' }}>
               {config.providers.map((provider) => {
                 const preset = PRESETS[provider.id];
-                const presetModels: string[] = preset?.models ?? [];
+                const presetModels: string[] = localModels[provider.id]?.length
+                  ? localModels[provider.id]
+                  : preset?.models ?? [];
                 const listId = `models-${provider.id}`;
                 const configured = isProviderConfigured(provider);
                 return (
@@ -664,7 +804,9 @@ function BasicTab({
                 stream: false,
               };
               // Only show configured providers in the dropdown
-              const rolePresetModels = PRESETS[role.provider]?.models ?? [];
+              const rolePresetModels = localModels[role.provider]?.length
+                ? localModels[role.provider]
+                : PRESETS[role.provider]?.models ?? [];
               const roleListId = `role-models-${key}`;
               return (
                 <Box key={key} sx={{ display: 'flex', alignItems: 'center', gap: 1.5 }}>
@@ -737,10 +879,11 @@ function BasicTab({
 // ── AdvancedTab ───────────────────────────────────────────────────────────────
 
 function AdvancedTab({
-  config, updateSafety, toggleApproval, updateMcpServer,
+  config, updateSafety, updatePreferences, toggleApproval, updateMcpServer,
 }: {
   config: LocalConfig;
   updateSafety: (field: keyof LocalSafety, value: unknown) => void;
+  updatePreferences: (field: keyof LocalPreferences, value: string[]) => void;
   toggleApproval: (tool: string) => void;
   updateMcpServer: (idx: number, field: keyof McpServer, value: unknown) => void;
 }) {
@@ -750,7 +893,7 @@ function AdvancedTab({
         <Typography level="title-sm" sx={{ mb: 1.5 }}>安全与权限</Typography>
         <Stack spacing={1.5}>
           <Box sx={{ display: 'flex', alignItems: 'center', justifyContent: 'space-between' }}>
-            <Typography level="body-sm">允许终端命令（无需确认）</Typography>
+            <Typography level="body-sm">允许终端命令（按风险等级确认）</Typography>
             <Switch size="sm" checked={config.safety.allowTerminalCommands}
               onChange={(e) => updateSafety('allowTerminalCommands', e.target.checked)} />
           </Box>
@@ -771,16 +914,52 @@ function AdvancedTab({
               onChange={(e) => updateSafety('maxLoopDurationMinutes', parseInt(e.target.value, 10) || 0)}
               slotProps={{ input: { min: 0 } }} sx={{ width: 80 }} />
           </Box>
+          <Box sx={{ display: 'flex', alignItems: 'center', justifyContent: 'space-between' }}>
+            <Typography level="body-sm">任务结束条件</Typography>
+            <Select size="sm" value={config.safety.loopMode}
+              onChange={(_, val) => val && updateSafety('loopMode', val)} sx={{ width: 160 }}>
+              <Option value="until_done">直到完成</Option>
+              <Option value="timed">达到运行时间</Option>
+              <Option value="failure_limit">达到连续失败次数</Option>
+            </Select>
+          </Box>
         </Stack>
       </Box>
 
+      <Box sx={{ p: 3 }}>
+        <Typography level="title-sm" sx={{ mb: 0.5 }}>用户约束</Typography>
+        <Typography level="body-xs" color="neutral" sx={{ mb: 1.5 }}>
+          每行一条规则，规划时作为硬性约束（例如「发送邮件前先给我看草稿」）
+        </Typography>
+        <Textarea size="sm" minRows={2}
+          value={config.preferences.constraints.join('\n')}
+          onChange={(e) => updatePreferences('constraints', splitLines(e.target.value))} />
+        <Typography level="body-xs" color="neutral" sx={{ mt: 1.5, mb: 1 }}>
+          允许保存文件的目录（每行一个，留空表示不限制；超出范围的写入会被拦截）
+        </Typography>
+        <Textarea size="sm" minRows={1}
+          value={config.preferences.savePaths.join('\n')}
+          onChange={(e) => updatePreferences('savePaths', splitLines(e.target.value))}
+          sx={{ fontFamily: 'code' }} />
+        <Box sx={{ display: 'flex', alignItems: 'center', justifyContent: 'space-between', mt: 1.5 }}>
+          <Typography level="body-sm">智能体消息语言</Typography>
+          <Select size="sm" value={config.preferences.language}
+            onChange={(_, val) => val && setConfig((prev) => ({ ...prev, preferences: { ...prev.preferences, language: val } }))}
+            sx={{ width: 160 }}>
+            <Option value="auto">跟随任务语言</Option>
+            <Option value="zh">中文</Option>
+            <Option value="en">English</Option>
+          </Select>
+        </Box>
+      </Box>
+
       <Box sx={{ p: 3 }}>
         <Typography level="title-sm" sx={{ mb: 0.5 }}>高危操作审批列表</Typography>
         <Typography level="body-xs" color="neutral" sx={{ mb: 1.5 }}>
-          选中的操作在执行前需要用户手动确认
+          选中的操作在执行前需要用户手动确认（点击、输入等也可勾选）；终端命令中的只读命令无需确认，高风险命令始终需要确认
         </Typography>
         <Box sx={{ display: 'grid', gridTemplateColumns: 'repeat(2, 1fr)', gap: 0.75 }}>
-          {BUILTIN_TOOLS.map((tool) => (
+          {config.approvalActions.map((tool) => (
             <Checkbox key={tool} size="sm"
               checked={config.safety.requireApprovalFor.includes(tool)}
               onChange={() => toggleApproval(tool)} label={tool} sx={{ fontFamily: 'code' }} />
@@ -12,10 +12,12 @@ const ACTION_LABELS: Record<string, string> = {
   mouse_click: '点击',
   mouse_double_click: '双击',
   mouse_right_click: '右键',
+  mouse_click_at: '坐标点击',
   scroll: '滚动',
   type_text: '输入',
   hotkey: '快捷键',
   key_press: '按键',
+  keyboard_navigate: '键盘定位',
   get_viewport: '截屏',
   execute_terminal: '终端',
   mcp_call: 'MCP',
@@ -40,7 +40,7 @@ export const ApprovalCard = observer(() => {
         >
           <Card
             variant="outlined"
-            color="warning"
+            color={pendingApproval.risk === 'high' ? 'danger' : 'warning'}
             sx={{ mb: 2, borderWidth: 2 }}
           >
             <Typography level="title-sm" color="warning">
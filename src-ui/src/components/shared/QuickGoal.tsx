@@ -0,0 +1,64 @@
+import { useState, useEffect, useRef, useCallback } from 'react';
+import Box from '@mui/joy/Box';
+import Input from '@mui/joy/Input';
+import { ArrowUp } from 'lucide-react';
+import { invoke } from '@tauri-apps/api/core';
+import { emit } from '@tauri-apps/api/event';
+import { getCurrentWindow } from '@tauri-apps/api/window';
+
+/**
+ * Quick-goal prompt shown in the tray's always-on-top mini window.
+ * Enter submits the goal to the agent and closes the window; Escape or
+ * losing focus just closes it.
+ */
+export function QuickGoal() {
+  const [value, setValue] = useState('');
+  const inputRef = useRef<HTMLInputElement>(null);
+
+  useEffect(() => {
+    inputRef.current?.focus();
+    const win = getCurrentWindow();
+    const unlisten = win.onFocusChanged(({ payload: focused }) => {
+      if (!focused) win.close();
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const submit = useCallback(async () => {
+    const task = value.trim();
+    if (!task) return;
+    try {
+      // Let the main window show the goal as a user message.
+      await emit('quick_goal_submitted', { task });
+      await invoke('start_task', { task });
+    } catch (err) {
+      console.error('quick goal: start_task failed:', err);
+    }
+    await getCurrentWindow().close();
+  }, [value]);
+
+  const handleKeyDown = (e: React.KeyboardEvent) => {
+    if (e.key === 'Enter') {
+      e.preventDefault();
+      submit();
+    } else if (e.key === 'Escape') {
+      getCurrentWindow().close();
+    }
+  };
+
+  return (
+    <Box sx={{ p: 1, height: '100vh', bgcolor: 'transparent' }}>
+      <Input
+        slotProps={{ input: { ref: inputRef } }}
+        value={value}
+        onChange={(e) => setValue(e.target.value)}
+        onKeyDown={handleKeyDown}
+        placeholder="让 SeeClaw 做什么？ / What should SeeClaw do?"
+        endDecorator={<ArrowUp size={16} />}
+        sx={{ borderRadius: '22px', height: 48, boxShadow: 'md' }}
+      />
+    </Box>
+  );
+}
@@ -13,6 +13,8 @@ const STATE_LABELS: Record<string, string> = {
   planning: '规划中',
   executing: '执行中',
   waiting_for_user: '等待确认',
+  waiting_for_stability: '等待稳定',
+  paused: '已暂停',
   evaluating: '评估中',
   error: '出错',
   done: '完成',
@@ -25,6 +27,8 @@ const STATE_COLORS: Record<string, 'neutral' | 'primary' | 'success' | 'warning'
   planning: 'primary',
   executing: 'warning',
   waiting_for_user: 'warning',
+  waiting_for_stability: 'primary',
+  paused: 'warning',
   evaluating: 'primary',
   error: 'danger',
   done: 'success',
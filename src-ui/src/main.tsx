@@ -4,13 +4,21 @@ import './index.css';
 import { CssVarsProvider } from '@mui/joy/styles';
 import CssBaseline from '@mui/joy/CssBaseline';
 import App from './App';
+import { QuickGoal } from './components/shared/QuickGoal';
 import { theme } from './theme';
 
+declare global {
+  interface Window {
+    /** Set by the Rust side for secondary windows (e.g. the tray's quick-goal prompt). */
+    __SEECLAW_VIEW__?: string;
+  }
+}
+
 ReactDOM.createRoot(document.getElementById('root')!).render(
   <React.StrictMode>
     <CssVarsProvider theme={theme} defaultMode="system">
       <CssBaseline />
-      <App />
+      {window.__SEECLAW_VIEW__ === 'quick' ? <QuickGoal /> : <App />}
     </CssVarsProvider>
   </React.StrictMode>,
 );
@@ -4,6 +4,7 @@ import type {
   ActionCard,
   LoopConfig,
   Message,
+  ReasoningChunk,
   StreamChunk,
   ApprovalRequest,
   ViewportCapturedPayload,
@@ -11,6 +12,7 @@ import type {
   TodoListPayload,
   StepStartedPayload,
   StepCompletedPayload,
+  QueuedTask,
 } from '../types/agent';
 
 class AgentStore {
@@ -21,6 +23,8 @@ class AgentStore {
   elapsedMs = 0;
   loopConfig: LoopConfig = { mode: 'until_done' };
   pendingApproval: ApprovalRequest | null = null;
+  /** Goals queued behind the running task */
+  queue: QueuedTask[] = [];
   /** Fine-grained activity label emitted by the engine (e.g. "正在截取屏幕…"). */
   latestActivity: string | null = null;
   /** TodoList steps from the planner (global reference kept for compat) */
@@ -124,14 +128,18 @@ class AgentStore {
   handleStreamChunk(chunk: StreamChunk): void {
     // Auto-start a message slot if state event arrived late.
     if (!this.currentStreamingId) {
-      if (chunk.kind === 'done' || chunk.kind === 'error') return;
+      if (chunk.kind === 'done' || chunk.kind === 'error' || chunk.kind === 'restart') return;
       this.startAssistantMessage();
     }
     const msg = this.messages.find((m) => m.id === this.currentStreamingId);
     if (!msg) return;
 
     runInAction(() => {
-      if (chunk.kind === 'reasoning') {
+      if (chunk.kind === 'restart') {
+        // Another provider took over mid-answer and streams it from the start.
+        msg.content = '';
+        msg.reasoningContent = undefined;
+      } else if (chunk.kind === 'reasoning') {
         if (this.streamStartedAt === null) this.streamStartedAt = Date.now();
         msg.reasoningContent = (msg.reasoningContent ?? '') + chunk.content;
       } else if (chunk.kind === 'content') {
@@ -158,6 +166,33 @@ class AgentStore {
     });
   }
 
+  handleReasoningChunk(chunk: ReasoningChunk): void {
+    if (!this.currentStreamingId) {
+      if (chunk.done) return;
+      this.startAssistantMessage();
+    }
+    const msg = this.messages.find((m) => m.id === this.currentStreamingId);
+    if (!msg) return;
+
+    runInAction(() => {
+      if (!chunk.done) {
+        if (this.streamStartedAt === null) this.streamStartedAt = Date.now();
+        msg.reasoningContent = (msg.reasoningContent ?? '') + chunk.content;
+        return;
+      }
+      // Silent calls send no stream `done`; close a message that only
+      // holds reasoning so the next reply starts its own.
+      if (!msg.content) {
+        if (this.streamStartedAt !== null) {
+          msg.durationMs = Date.now() - this.streamStartedAt;
+          this.streamStartedAt = null;
+        }
+        msg.isStreaming = false;
+        this.currentStreamingId = null;
+      }
+    });
+  }
+
   addActionCard(card: ActionCard): void {
     if (!this.currentStreamingId) return;
     const msg = this.messages.find((m) => m.id === this.currentStreamingId);
@@ -249,6 +284,10 @@ class AgentStore {
     this.latestActivity = text;
   }
 
+  setQueue(items: QueuedTask[]): void {
+    this.queue = items;
+  }
+
   // ── TodoList management (task-scoped inline messages) ────────────────
 
   /** Called when backend emits `todolist_updated` — creates or updates an inline plan message */
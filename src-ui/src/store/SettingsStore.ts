@@ -1,5 +1,5 @@
 import { makeAutoObservable } from 'mobx';
-import type { AppSettings, ProviderConfig } from '../types/settings';
+import type { AppSettings, LoopMode, ProviderConfig } from '../types/settings';
 
 const DEFAULT_SETTINGS: AppSettings = {
   activeProvider: 'zhipu',
@@ -10,6 +10,7 @@ const DEFAULT_SETTINGS: AppSettings = {
     requireApprovalFor: ['execute_terminal', 'mcp_call'],
     maxConsecutiveFailures: 5,
     maxLoopDurationMinutes: 0,
+    loopMode: 'until_done',
   },
   loopDefaults: {
     mode: 'until_done',
@@ -83,6 +84,7 @@ class SettingsStore {
         requireApprovalFor: (safety.require_approval_for as string[]) ?? [],
         maxConsecutiveFailures: (safety.max_consecutive_failures as number) ?? 5,
         maxLoopDurationMinutes: (safety.max_loop_duration_minutes as number) ?? 0,
+        loopMode: (safety.loop_mode as LoopMode) ?? 'until_done',
       },
     };
   }
@@ -5,7 +5,10 @@ export type AgentStateKind =
   | 'planning'
   | 'executing'
   | 'waiting_for_user'
+  | 'waiting_for_stability'
+  | 'paused'
   | 'evaluating'
+  | 'deferred'
   | 'error'
   | 'done';
 
@@ -47,10 +50,18 @@ export interface LoopConfig {
 }
 
 export interface StreamChunk {
-  kind: 'reasoning' | 'content' | 'tool_call' | 'done' | 'error';
+  /** `restart`: drop what this call streamed so far; it is sent again. */
+  kind: 'reasoning' | 'content' | 'tool_call' | 'done' | 'error' | 'restart';
   content: string;
 }
 
+/** `llm_reasoning_chunk`: the model's thinking, streamed apart from its reply. */
+export interface ReasoningChunk {
+  content: string;
+  /** Set once after the call; `content` is empty. */
+  done: boolean;
+}
+
 export interface Message {
   id: string;
   role: 'user' | 'assistant' | 'tool';
@@ -87,6 +98,10 @@ export interface ApprovalRequest {
   id: string;
   action: AgentAction;
   reason: string;
+  /** Grounding confidence when a low-confidence click asks for approval */
+  confidence?: number | null;
+  /** Risk class of a terminal command */
+  risk?: 'low' | 'medium' | 'high' | null;
   timestamp: string;
 }
 
@@ -97,6 +112,44 @@ export interface AgentStatePayload {
   summary?: string;
   /** Error message when state is 'error' */
   message?: string;
+  /** Error category when the task failed or was stopped (matches Rust ErrorCategory) */
+  category?: ErrorCategory | null;
+  /** Goal of a task that is starting ('routing') */
+  goal?: string;
+  /** True when the starting task was waiting in the task queue */
+  from_queue?: boolean;
+  /** True when the starting task was restored from a saved session */
+  restored?: boolean;
+}
+
+/** A goal waiting for the running task to finish */
+export interface QueuedTask {
+  id: number;
+  goal: string;
+  queued_at: string;
+}
+
+/** Error taxonomy from the backend — drives targeted recovery actions in the UI. */
+export type ErrorCategory =
+  | 'grounding_failed'
+  | 'provider_timeout'
+  | 'provider_error'
+  | 'perception_failed'
+  | 'executor_failed'
+  | 'executor_denied'
+  | 'tool_failed'
+  | 'task_failed'
+  | 'user_stop'
+  | 'budget_exhausted'
+  | 'config'
+  | 'internal';
+
+/** Emitted after every executed (or rejected) action. */
+export interface ActionResultPayload {
+  action: string;
+  ok: boolean;
+  message: string;
+  category: ErrorCategory | null;
 }
 
 // ── TodoList types (from backend plan_task) ────────────────────────────────
@@ -106,7 +159,7 @@ export type StepStatus = 'Pending' | 'InProgress' | 'Completed' | 'Failed' | 'Sk
  * Execution mode for a step — matches Rust StepMode (snake_case serde).
  * Previous values ('Direct' | 'VisualLocate' | 'VisualAct') were from the old arch.
  */
-export type StepMode = 'combo' | 'chat' | 'vlm';
+export type StepMode = 'combo' | 'chat' | 'vlm' | 'explore';
 
 export interface TodoStep {
   index: number;
@@ -115,6 +168,8 @@ export interface TodoStep {
   mode: StepMode;
   /** Planner's recommended mode hint */
   recommended_mode?: StepMode;
+  /** Parallel group; steps sharing one may run at the same time */
+  group?: string;
   status: StepStatus;
 }
 
@@ -132,6 +187,22 @@ export interface StepStartedPayload {
   recommended_mode?: StepMode;
 }
 
+/** `agent_metrics` event — one timing record (see `agent_engine/metrics.rs`). */
+export type AgentMetricPayload = { ts: number } & (
+  | { kind: 'step_started'; index: number; description: string }
+  | { kind: 'perception_done'; ms: number; elements: number; parsers: [string, number][] }
+  | {
+      kind: 'llm_call_done';
+      ms: number;
+      provider: string;
+      model: string;
+      ok: boolean;
+      prompt_tokens: number;
+      completion_tokens: number;
+    }
+  | { kind: 'action_done'; ms: number; action: string; ok: boolean }
+);
+
 export interface StepCompletedPayload {
   index: number;
   status: StepStatus;
@@ -7,16 +7,40 @@ export interface ProviderConfig {
   hasApiKey: boolean;
 }
 
+/** Result of the `list_profiles` command; `active` is '' when none applies. */
+export interface ProfileList {
+  active: string;
+  profiles: string[];
+}
+
+/** Result of the `test_provider` command. */
+export interface ProviderTestReport {
+  provider: string;
+  role: string | null;
+  model: string;
+  ok: boolean;
+  latency_ms: number;
+  reply: string | null;
+  error: string | null;
+  category: string | null;
+  models: string[];
+  models_error: string | null;
+  model_not_listed: boolean;
+}
+
 export interface SafetyConfig {
   allowTerminalCommands: boolean;
   allowFileOperations: boolean;
   requireApprovalFor: string[];
   maxConsecutiveFailures: number;
   maxLoopDurationMinutes: number;
+  loopMode: LoopMode;
 }
 
+export type LoopMode = 'until_done' | 'timed' | 'failure_limit';
+
 export interface LoopDefaults {
-  mode: 'until_done' | 'timed' | 'failure_limit';
+  mode: LoopMode;
   maxDurationMinutes: number;
   maxFailures: number;
 }
@@ -0,0 +1,217 @@
+//! Embeddable agent facade — run SeeClaw inside another Rust application.
+//!
+//! The desktop app drives the agent through Tauri commands; embedders use
+//! [`Agent`] instead. Events the UI would receive ("agent_state_changed",
+//! "llm_stream_chunk", "action_required", …) are delivered to an
+//! [`EventSink`](crate::events::EventSink) of your choice.
+//!
+//! ```no_run
+//! # async fn demo() -> Result<(), Box<dyn std::error::Error>> {
+//! use std::sync::Arc;
+//! use seeclaw_lib::events::ChannelSink;
+//!
+//! let (sink, mut events) = ChannelSink::new(256);
+//! let agent = seeclaw_lib::Agent::builder()
+//!     .config(seeclaw_lib::config::load_config()?)
+//!     .event_sink(Arc::new(sink))
+//!     .build();
+//!
+//! agent.submit_goal("open notepad and type hello").await?;
+//! while let Ok(msg) = events.recv().await {
+//!     if msg.event == "action_required" {
+//!         agent.respond_to_approval(true).await?;
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::sync::Arc;
+
+use tokio::sync::{broadcast, mpsc, Mutex};
+
+use crate::agent_engine::event_bus::{AgentMessage, EventBus};
+use crate::agent_engine::node::StopSignal;
+use crate::agent_engine::state::{AgentEvent, LoopConfig, LoopOverrides};
+use crate::config::{AppConfig, LlmConfig, PerceptionConfig, SafetyConfig};
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::events::{NullSink, SharedEventSink};
+use crate::llm::registry::ProviderRegistry;
+use crate::perception::pipeline::PerceptionChain;
+use crate::perception::traits::VisionParser;
+use crate::perception::yolo_detector::YoloDetector;
+use crate::AgentHandle;
+
+/// Builder for [`Agent`]. Start from [`Agent::builder`].
+pub struct AgentBuilder {
+    config: AppConfig,
+    sink: Option<SharedEventSink>,
+    loop_config: LoopConfig,
+    extra_parsers: Vec<Arc<dyn VisionParser>>,
+}
+
+impl AgentBuilder {
+    fn new() -> Self {
+        Self {
+            config: AppConfig::default(),
+            sink: None,
+            loop_config: LoopConfig::from(&SafetyConfig::default()),
+            extra_parsers: Vec::new(),
+        }
+    }
+
+    /// Use a complete config (e.g. from [`crate::config::load_config`]).
+    pub fn config(mut self, config: AppConfig) -> Self {
+        self.loop_config = LoopConfig::from(&config.safety);
+        self.config = config;
+        self
+    }
+
+    /// Providers and role mapping.
+    pub fn providers(mut self, llm: LlmConfig) -> Self {
+        self.config.llm = llm;
+        self
+    }
+
+    pub fn perception(mut self, perception: PerceptionConfig) -> Self {
+        self.config.perception = perception;
+        self
+    }
+
+    pub fn safety(mut self, safety: SafetyConfig) -> Self {
+        self.loop_config = LoopConfig::from(&safety);
+        self.config.safety = safety;
+        self
+    }
+
+    /// Loop mode and limits for every goal. Defaults to the `[safety]`
+    /// values; set after [`Self::config`] / [`Self::safety`], which reset it.
+    pub fn loop_config(mut self, loop_config: LoopConfig) -> Self {
+        self.loop_config = loop_config;
+        self
+    }
+
+    /// Where agent events go. Defaults to discarding them.
+    pub fn event_sink(mut self, sink: SharedEventSink) -> Self {
+        self.sink = Some(sink);
+        self
+    }
+
+    /// Append a custom vision parser after the configured chain.
+    pub fn vision_parser(mut self, parser: Arc<dyn VisionParser>) -> Self {
+        self.extra_parsers.push(parser);
+        self
+    }
+
+    /// Start the agent loop on the current Tokio runtime.
+    pub fn build(mut self) -> Agent {
+        crate::secrets::configure(self.config.llm.keyring);
+        match crate::profiles::effective(&self.config) {
+            Ok(cfg) => self.config = cfg,
+            Err(e) => tracing::warn!(error = %e, "active_profile not applied; using the base config"),
+        }
+        let registry = Arc::new(Mutex::new(ProviderRegistry::from_config(&self.config)));
+
+        crate::perception::exclusion::set_zones(self.config.perception.exclusion_zones.clone());
+        crate::perception::screenshot::set_encoding(&self.config.perception);
+        crate::executor::pacing::set_pacing(&self.config.executor);
+        crate::prompts::configure(&self.config.prompts);
+        crate::agent_engine::lang::set_preference(self.config.preferences.language);
+        let yolo = YoloDetector::from_config(&self.config.perception);
+        let mut perception = PerceptionChain::from_config(&self.config.perception, yolo);
+        for parser in self.extra_parsers {
+            perception.push(parser);
+        }
+
+        let (tx, rx) = mpsc::channel::<AgentEvent>(32);
+        let stop_flag = Arc::new(StopSignal::new());
+        let handle = Arc::new(AgentHandle { tx, stop_flag: stop_flag.clone() });
+        let sink = self.sink.unwrap_or_else(|| Arc::new(NullSink));
+        let bus = EventBus::new();
+
+        let task = tokio::spawn(crate::agent_loop(
+            sink,
+            rx,
+            registry.clone(),
+            self.config,
+            perception,
+            self.loop_config,
+            stop_flag,
+            bus.clone(),
+        ));
+
+        Agent { handle, registry, bus, task }
+    }
+}
+
+/// A running agent. Dropping it aborts the agent loop.
+pub struct Agent {
+    handle: Arc<AgentHandle>,
+    registry: Arc<Mutex<ProviderRegistry>>,
+    bus: EventBus,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl Agent {
+    pub fn builder() -> AgentBuilder {
+        AgentBuilder::new()
+    }
+
+    /// Submit a goal. If a task is running it is queued and starts when the
+    /// running one ends, matching the desktop app's behaviour.
+    pub async fn submit_goal(&self, goal: impl Into<String>) -> SeeClawResult<()> {
+        self.send(AgentEvent::GoalReceived(goal.into())).await
+    }
+
+    /// Like [`Self::submit_goal`], with loop mode / limits for this goal only.
+    pub async fn submit_goal_with_loop(&self, goal: impl Into<String>, overrides: LoopOverrides) -> SeeClawResult<()> {
+        self.send(AgentEvent::GoalWithLoop(goal.into(), overrides)).await
+    }
+
+    /// Stop the current task.
+    pub async fn stop(&self) -> SeeClawResult<()> {
+        self.handle.stop_flag.set();
+        self.send(AgentEvent::Stop).await
+    }
+
+    /// Hold the current task before its next node.
+    pub async fn pause(&self) -> SeeClawResult<()> {
+        self.send(AgentEvent::Pause).await
+    }
+
+    /// Continue a paused task.
+    pub async fn resume(&self) -> SeeClawResult<()> {
+        self.send(AgentEvent::Resume).await
+    }
+
+    /// Answer a pending "action_required" event.
+    pub async fn respond_to_approval(&self, approved: bool) -> SeeClawResult<()> {
+        let event = if approved { AgentEvent::UserApproved } else { AgentEvent::UserRejected };
+        self.send(event).await
+    }
+
+    /// Provider registry, e.g. to hot-swap providers after a config change.
+    pub fn registry(&self) -> Arc<Mutex<ProviderRegistry>> {
+        self.registry.clone()
+    }
+
+    /// Typed engine milestones (perception, stability, plan, action) — a
+    /// lighter alternative to filtering the event sink.
+    pub fn messages(&self) -> broadcast::Receiver<AgentMessage> {
+        self.bus.subscribe()
+    }
+
+    async fn send(&self, event: AgentEvent) -> SeeClawResult<()> {
+        self.handle
+            .tx
+            .send(event)
+            .await
+            .map_err(|e| SeeClawError::Agent(format!("agent loop is not running: {e}")))
+    }
+}
+
+impl Drop for Agent {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
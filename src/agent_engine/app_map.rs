@@ -0,0 +1,53 @@
+//! App map — menu commands discovered by an `explore` step.
+//!
+//! Lives for the current task only (in `SharedState`). Loop agents and the
+//! verifier see it as prompt context, so later steps can go straight to a
+//! known menu command instead of searching the UI again.
+
+/// Commands found under the top-level menus of one application.
+#[derive(Debug, Clone, Default)]
+pub struct AppMap {
+    pub app: String,
+    /// `(menu name, commands in that menu)` in menu-bar order.
+    pub menus: Vec<(String, Vec<String>)>,
+}
+
+impl AppMap {
+    pub fn is_empty(&self) -> bool {
+        self.menus.is_empty()
+    }
+
+    /// One line per menu: `File: New, Open…, Save`.
+    pub fn render(&self) -> String {
+        let mut out = format!("### {}\n", self.app);
+        for (menu, commands) in &self.menus {
+            if commands.is_empty() {
+                out.push_str(&format!("- {menu}: (no items read)\n"));
+            } else {
+                out.push_str(&format!("- {menu}: {}\n", commands.join(", ")));
+            }
+        }
+        out
+    }
+}
+
+/// Replace any earlier map for the same app.
+pub fn upsert(maps: &mut Vec<AppMap>, map: AppMap) {
+    maps.retain(|m| m.app != map.app);
+    maps.push(map);
+}
+
+/// Prompt section listing every explored app, or `None` when nothing was explored.
+pub fn prompt_section(maps: &[AppMap]) -> Option<String> {
+    let maps: Vec<&AppMap> = maps.iter().filter(|m| !m.is_empty()).collect();
+    if maps.is_empty() {
+        return None;
+    }
+    let mut out = String::from(
+        "## Explored app menus\nMenu commands discovered earlier in this task (open the menu, then pick the command):\n",
+    );
+    for map in maps {
+        out.push_str(&map.render());
+    }
+    Some(out)
+}
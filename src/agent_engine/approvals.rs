@@ -0,0 +1,29 @@
+//! Pending approval — the `action_required` request the agent is blocked on.
+//!
+//! In background mode no window may be listening when `UserConfirmNode`
+//! emits the request, so it is kept here until answered. A UI that attaches
+//! later fetches it with the `get_pending_approval` command and shows the
+//! card as if it had received the event.
+
+use std::sync::{Mutex, OnceLock};
+
+use serde_json::Value;
+
+fn slot() -> &'static Mutex<Option<Value>> {
+    static SLOT: OnceLock<Mutex<Option<Value>>> = OnceLock::new();
+    SLOT.get_or_init(|| Mutex::new(None))
+}
+
+/// Remember `request` as the one awaiting an answer.
+pub fn set(request: Value) {
+    *slot().lock().unwrap_or_else(|e| e.into_inner()) = Some(request);
+}
+
+/// The request awaiting an answer, if any.
+pub fn pending() -> Option<Value> {
+    slot().lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+pub fn clear() {
+    *slot().lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
@@ -0,0 +1,77 @@
+//! Alternative click targets offered by the vision model.
+//!
+//! `mouse_click` and its double/right variants accept `alternatives` — up to
+//! two more element ids or grid cells the model had in mind — and a short
+//! `target` description. When the pick is rated low, or an alternative is
+//! rated close to it, VlmActNode does not click the first guess: the
+//! focus-crop pass chooses among the candidates, or the model is asked to
+//! name the target more precisely.
+
+use serde::{Deserialize, Serialize};
+
+/// The model's pick plus at most two alternatives.
+pub const MAX_CANDIDATES: usize = 3;
+/// A pick rated below this gets a second look before it is clicked.
+const LOW_CONFIDENCE: f32 = 0.5;
+/// An alternative rated within this margin of the pick is a real contender.
+const CLOSE_MARGIN: f32 = 0.15;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClickCandidate {
+    pub element_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+}
+
+/// Candidates from click tool arguments: `element_id` first, then the
+/// distinct `alternatives`. Empty when there is no `element_id`.
+pub fn parse(args: &serde_json::Value) -> Vec<ClickCandidate> {
+    let Some(pick) = args["element_id"].as_str().map(str::trim).filter(|id| !id.is_empty()) else {
+        return Vec::new();
+    };
+    let mut candidates = vec![ClickCandidate {
+        element_id: pick.to_string(),
+        confidence: args["confidence"].as_f64().map(|c| c as f32),
+    }];
+    for alt in args["alternatives"].as_array().into_iter().flatten() {
+        // Tolerate a bare list of ids.
+        let (id, confidence) = match alt {
+            serde_json::Value::String(id) => (id.as_str(), None),
+            _ => (alt["element_id"].as_str().unwrap_or_default(), alt["confidence"].as_f64().map(|c| c as f32)),
+        };
+        let id = id.trim();
+        if id.is_empty() || candidates.iter().any(|c| c.element_id == id) {
+            continue;
+        }
+        candidates.push(ClickCandidate { element_id: id.to_string(), confidence });
+        if candidates.len() == MAX_CANDIDATES {
+            break;
+        }
+    }
+    candidates
+}
+
+/// The pick is too uncertain to click as is.
+pub fn is_ambiguous(candidates: &[ClickCandidate]) -> bool {
+    let Some(pick) = candidates.first() else { return false };
+    if pick.confidence.is_some_and(|c| c < LOW_CONFIDENCE) {
+        return true;
+    }
+    candidates[1..].iter().any(|alt| match (pick.confidence, alt.confidence) {
+        (Some(top), Some(c)) => c >= top - CLOSE_MARGIN,
+        // An unrated alternative can't be ruled out.
+        _ => true,
+    })
+}
+
+/// "UI_3 (0.55), UI_9 (0.50)" for prompts and logs.
+pub fn describe(candidates: &[ClickCandidate]) -> String {
+    candidates
+        .iter()
+        .map(|c| match c.confidence {
+            Some(conf) => format!("{} ({conf:.2})", c.element_id),
+            None => c.element_id.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,165 @@
+//! Task checkpoints — crash recovery for the task in flight.
+//!
+//! After every node the graph writes the task's durable state (goal, plan,
+//! current step, conversation without screenshots, execution log) to
+//! `sessions/<session id>/checkpoint.json`. The file is removed when the task
+//! ends, stopped or not, so one that survives an app restart belongs to a task
+//! that never finished. `resume_session` loads it and starts the task again at
+//! the step it was on; anything tied to the old screen (elements, frames,
+//! pending actions) is rebuilt by the next perception pass.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::agent_engine::history::{current_session_id, data_dir_or_cwd, validate_session_id};
+use crate::agent_engine::state::{RouteType, SharedState, StepStatus, TodoStep};
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::llm::types::{ChatMessage, ContentPart, MessageContent};
+
+const CHECKPOINT_FILE: &str = "checkpoint.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub session_id: String,
+    pub goal: String,
+    pub route_type: RouteType,
+    pub plan_summary: String,
+    pub final_goal: String,
+    pub todo_steps: Vec<TodoStep>,
+    pub current_step_idx: usize,
+    /// Planner conversation; screenshots are replaced by a placeholder.
+    pub conv_messages: Vec<ChatMessage>,
+    pub steps_log: Vec<String>,
+    pub cycle_count: u32,
+    /// Node that was about to run.
+    pub node: String,
+    /// RFC 3339 time of the write.
+    pub saved_at: String,
+}
+
+impl Checkpoint {
+    pub fn capture(state: &SharedState, node: &str) -> Self {
+        Self {
+            session_id: current_session_id().to_string(),
+            goal: state.goal.clone(),
+            route_type: state.route_type.clone(),
+            plan_summary: state.plan_summary.clone(),
+            final_goal: state.final_goal.clone(),
+            todo_steps: state.todo_steps.clone(),
+            current_step_idx: state.current_step_idx,
+            conv_messages: state.conv_messages.iter().map(without_images).collect(),
+            steps_log: state.steps_log.clone(),
+            cycle_count: state.cycle_count,
+            node: node.to_string(),
+            saved_at: chrono::Local::now().to_rfc3339(),
+        }
+    }
+
+    /// Put the saved task back into a fresh `state`; returns the node to
+    /// start at. A task with unfinished plan steps resumes at its current
+    /// step, anything else is routed again from the goal.
+    pub fn restore(self, state: &mut SharedState) -> &'static str {
+        state.goal = self.goal;
+        state.route_type = self.route_type;
+        state.plan_summary = self.plan_summary;
+        state.final_goal = self.final_goal;
+        state.current_step_idx = self.current_step_idx;
+        state.steps_log = self.steps_log;
+        state.cycle_count = self.cycle_count;
+        state.conv_messages = self.conv_messages;
+        drop_unanswered_tool_calls(&mut state.conv_messages);
+        state.todo_steps = self.todo_steps;
+        for step in &mut state.todo_steps {
+            // The step that was running starts over.
+            if step.status == StepStatus::InProgress {
+                step.status = StepStatus::Pending;
+            }
+        }
+        if state.current_step_idx < state.todo_steps.len() {
+            "step_router"
+        } else {
+            state.conv_messages.clear();
+            "router"
+        }
+    }
+}
+
+/// Write `state` as the current session's checkpoint. Failures are logged;
+/// a missing checkpoint only costs recoverability.
+pub fn save(state: &SharedState, node: &str) {
+    // A subtask resumes as part of its parent's step, not on its own.
+    if state.subtask_depth > 0 {
+        return;
+    }
+    let checkpoint = Checkpoint::capture(state, node);
+    let path = checkpoint_path(current_session_id());
+    let result = serde_json::to_vec(&checkpoint).map_err(SeeClawError::from).and_then(|bytes| {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        // Write-then-rename so a crash mid-write leaves the previous checkpoint.
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, bytes)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    });
+    if let Err(e) = result {
+        tracing::warn!(error = %e, "checkpoint: save failed");
+    }
+}
+
+/// Remove the current session's checkpoint (the task ended).
+pub fn clear() {
+    remove(current_session_id());
+}
+
+/// Remove `session_id`'s checkpoint, e.g. once it has been resumed.
+pub fn remove(session_id: &str) {
+    let _ = std::fs::remove_file(checkpoint_path(session_id));
+}
+
+/// The checkpoint an unfinished task left in `session_id`.
+pub fn load(session_id: &str) -> SeeClawResult<Checkpoint> {
+    validate_session_id(session_id)?;
+    let path = checkpoint_path(session_id);
+    let text = std::fs::read_to_string(&path).map_err(|e| {
+        SeeClawError::Agent(format!("session {session_id} has no unfinished task to resume ({e})"))
+    })?;
+    Ok(serde_json::from_str(&text)?)
+}
+
+/// Whether `session_id` left an unfinished task behind.
+pub fn exists(session_id: &str) -> bool {
+    checkpoint_path(session_id).is_file()
+}
+
+fn checkpoint_path(session_id: &str) -> PathBuf {
+    data_dir_or_cwd("sessions").join(session_id).join(CHECKPOINT_FILE)
+}
+
+fn without_images(msg: &ChatMessage) -> ChatMessage {
+    let mut msg = msg.clone();
+    if let MessageContent::Parts(parts) = &mut msg.content {
+        for part in parts.iter_mut() {
+            if matches!(part, ContentPart::ImageUrl { .. }) {
+                *part = ContentPart::Text { text: "[Screenshot — not saved]".to_string() };
+            }
+        }
+    }
+    msg
+}
+
+/// Cut the conversation before an assistant turn whose tool calls did not all
+/// get a result (the app went down mid-action); providers reject it otherwise.
+fn drop_unanswered_tool_calls(messages: &mut Vec<ChatMessage>) {
+    let Some(pos) = messages.iter().rposition(|m| m.tool_calls.as_ref().is_some_and(|c| !c.is_empty())) else {
+        return;
+    };
+    let answered = messages[pos].tool_calls.iter().flatten().all(|tc| {
+        messages[pos + 1..].iter().any(|m| m.tool_call_id.as_deref() == Some(tc.id.as_str()))
+    });
+    if !answered {
+        messages.truncate(pos);
+    }
+}
@@ -0,0 +1,132 @@
+//! Conversation compaction — folds old planner turns into one progress note.
+//!
+//! When `conv_messages` grows past `[llm] compact_after_tokens`, everything
+//! between the pinned head (system prompt + goal) and the most recent turns is
+//! rendered as a plain transcript and summarised by the cheap `routing` model.
+//! The detailed messages are replaced by a single assistant note, so the
+//! planner keeps the same message shape and never sees the difference.
+//!
+//! Compaction is best-effort: on any failure the conversation is left as is
+//! and `context_window::trim_to_budget` remains the hard limit.
+
+use crate::agent_engine::context::NodeContext;
+use crate::agent_engine::context_window::{KEEP_RECENT, PINNED};
+use crate::agent_engine::state::SharedState;
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::llm::tokens::estimate_messages;
+use crate::llm::types::{ChatMessage, ContentPart, MessageContent};
+use crate::prompts;
+
+const COMPACTION_PROMPT: &str = include_str!("../../prompts/system/compaction.md");
+/// Prefix of the note that replaces compacted messages.
+const NOTE_PREFIX: &str = "[Summary of earlier progress]";
+/// Characters of one message kept in the transcript sent for summarising.
+const TRANSCRIPT_MESSAGE_CHARS: usize = 1_500;
+
+/// Result of a compaction pass, for logging.
+#[derive(Debug, Clone, Copy)]
+pub struct CompactReport {
+    pub before: usize,
+    pub after: usize,
+    pub messages_folded: usize,
+}
+
+/// Compact `state.conv_messages` if it exceeds `threshold` tokens.
+/// `Ok(None)` when below the threshold, disabled (`0`) or nothing to fold.
+pub async fn compact_if_needed(
+    state: &mut SharedState,
+    ctx: &NodeContext,
+    threshold: usize,
+) -> SeeClawResult<Option<CompactReport>> {
+    let before = estimate_messages(&state.conv_messages);
+    if threshold == 0 || before <= threshold {
+        return Ok(None);
+    }
+    let Some(end) = fold_end(&state.conv_messages) else {
+        return Ok(None);
+    };
+    let folded = &state.conv_messages[PINNED..end];
+
+    let prompt = prompts::get("compaction.md", COMPACTION_PROMPT)
+        .replace("{goal}", &state.goal)
+        .replace("{transcript}", &render_transcript(folded));
+    let messages = vec![ChatMessage {
+        role: "system".into(),
+        content: MessageContent::Text(prompt),
+        tool_call_id: None,
+        tool_calls: None,
+    }];
+
+    let (provider, mut cfg) = {
+        let reg = ctx.registry.lock().await;
+        reg.call_config_for_role("routing")?
+    };
+    cfg.silent = true;
+    cfg.stream = false;
+    cfg.cancel = state.cancel.clone();
+
+    let _ = ctx.app.emit("agent_activity", serde_json::json!({
+        "text": state.lang.pick("正在压缩对话历史…", "Compacting conversation history…")
+    }));
+    let response = provider.chat(messages, vec![], &cfg, &ctx.app).await?;
+    let note = response.content.trim();
+    if note.is_empty() {
+        return Err(SeeClawError::LlmProvider("compaction returned an empty summary".into()));
+    }
+
+    let messages_folded = end - PINNED;
+    state.conv_messages.splice(
+        PINNED..end,
+        [ChatMessage {
+            role: "assistant".into(),
+            content: MessageContent::Text(format!("{NOTE_PREFIX}\n{note}")),
+            tool_call_id: None,
+            tool_calls: None,
+        }],
+    );
+    Ok(Some(CompactReport {
+        before,
+        after: estimate_messages(&state.conv_messages),
+        messages_folded,
+    }))
+}
+
+/// End (exclusive) of the range to fold, chosen so the kept tail does not
+/// start with a tool result whose call would be folded away. `None` when
+/// fewer than two messages would be folded.
+fn fold_end(messages: &[ChatMessage]) -> Option<usize> {
+    let mut end = messages.len().saturating_sub(KEEP_RECENT);
+    while end > PINNED && messages[end].role == "tool" {
+        end -= 1;
+    }
+    (end >= PINNED + 2).then_some(end)
+}
+
+/// Plain-text transcript of the folded messages; images become a marker.
+fn render_transcript(messages: &[ChatMessage]) -> String {
+    let mut out = String::new();
+    for msg in messages {
+        let text = match &msg.content {
+            MessageContent::Text(t) => t.clone(),
+            MessageContent::Parts(parts) => parts
+                .iter()
+                .map(|p| match p {
+                    ContentPart::Text { text } => text.clone(),
+                    ContentPart::ImageUrl { .. } => "[screenshot]".to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+        };
+        let mut line = text.trim().to_string();
+        for tc in msg.tool_calls.iter().flatten() {
+            line.push_str(&format!(" → {}({})", tc.function.name, tc.function.arguments));
+        }
+        if line.chars().count() > TRANSCRIPT_MESSAGE_CHARS {
+            line = format!("{}…", line.chars().take(TRANSCRIPT_MESSAGE_CHARS).collect::<String>());
+        }
+        if !line.is_empty() {
+            out.push_str(&format!("[{}] {}\n", msg.role, line));
+        }
+    }
+    out
+}
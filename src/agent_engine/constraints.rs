@@ -0,0 +1,186 @@
+//! User constraints — standing rules from `[preferences]` in config.toml.
+//!
+//! Free-text `constraints` ("never send emails without showing me first")
+//! are injected into every planner prompt as hard rules. `save_paths` is the
+//! machine-checkable part: when set, [`check_action`] refuses actions that
+//! would write outside those folders — a path typed into a dialog, the
+//! target of a shell redirect / `Out-File` / `Set-Content` in a terminal
+//! command, or the destination of `write_file` / `move_file`.
+
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::agent_engine::state::AgentAction;
+use crate::config::PreferencesConfig;
+
+/// Planner prompt section listing the user's rules, or `None` when there are none.
+pub fn prompt_section(prefs: &PreferencesConfig) -> Option<String> {
+    let rules: Vec<&str> = prefs
+        .constraints
+        .iter()
+        .map(|c| c.trim())
+        .filter(|c| !c.is_empty())
+        .collect();
+    let save_paths = allowed_paths(prefs);
+    if rules.is_empty() && save_paths.is_empty() {
+        return None;
+    }
+    let mut out = String::from(
+        "## User constraints (hard rules)\nThe user set these rules. Every plan and action MUST respect them; if the goal cannot be met without breaking one, report failure instead.\n",
+    );
+    for rule in rules {
+        out.push_str(&format!("- {rule}\n"));
+    }
+    if !save_paths.is_empty() {
+        out.push_str(&format!(
+            "- Files may only be saved under: {} (enforced — writes elsewhere are blocked)\n",
+            save_paths.join(", ")
+        ));
+    }
+    Some(out)
+}
+
+/// `Err(reason)` if `action` would write outside the allowed save paths.
+pub fn check_action(action: &AgentAction, prefs: &PreferencesConfig) -> Result<(), String> {
+    let save_paths = allowed_paths(prefs);
+    if save_paths.is_empty() {
+        return Ok(());
+    }
+    let targets: Vec<String> = match action {
+        // A bare absolute path typed into a field is almost always a
+        // save-dialog file name.
+        AgentAction::TypeText { text, .. } => {
+            let text = text.trim().trim_matches('"');
+            if is_absolute(text) { vec![text.to_string()] } else { Vec::new() }
+        }
+        AgentAction::ExecuteTerminal { command, .. } => write_targets(command),
+        AgentAction::WriteFile { path, .. } => vec![path.trim().to_string()],
+        AgentAction::MoveFile { to, .. } => vec![to.trim().to_string()],
+        _ => Vec::new(),
+    };
+    match targets.into_iter().find(|t| !is_allowed(t, &save_paths)) {
+        Some(path) => Err(format!(
+            "'{path}' is outside the folders the user allows saving to ({}). Save under one of them instead.",
+            save_paths.join(", ")
+        )),
+        None => Ok(()),
+    }
+}
+
+fn allowed_paths(prefs: &PreferencesConfig) -> Vec<&str> {
+    prefs.save_paths.iter().map(|p| p.trim()).filter(|p| !p.is_empty()).collect()
+}
+
+/// Absolute paths a shell command writes to.
+fn write_targets(command: &str) -> Vec<String> {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| {
+        Regex::new(
+            r#"(?i)(?:>>?|\b(?:out-file|set-content|add-content)\b(?:\s+-(?:file)?path)?|-outfile)\s*(?:"([^"]+)"|'([^']+)'|([^\s"'<>|;&]+))"#,
+        )
+        .expect("valid write target regex")
+    });
+    re.captures_iter(command)
+        .filter_map(|c| c.get(1).or_else(|| c.get(2)).or_else(|| c.get(3)))
+        .map(|m| m.as_str().trim().to_string())
+        .filter(|p| is_absolute(p) && !p.starts_with("/dev/"))
+        .collect()
+}
+
+fn is_absolute(text: &str) -> bool {
+    let b = text.as_bytes();
+    let windows = b.len() >= 3 && b[0].is_ascii_alphabetic() && b[1] == b':' && (b[2] == b'\\' || b[2] == b'/');
+    let unix = text.starts_with('/') && !text.contains(char::is_whitespace);
+    (windows || unix) && !text.contains('\n')
+}
+
+/// `path` equals or lies inside one of `allowed` (case-insensitive, either slash).
+fn is_allowed(path: &str, allowed: &[&str]) -> bool {
+    let Some(path) = normalize(path) else {
+        return false;
+    };
+    allowed.iter().filter_map(|a| normalize(a)).filter(|a| !a.is_empty()).any(|a| {
+        path == a || path.strip_prefix(&a).is_some_and(|rest| rest.starts_with('/'))
+    })
+}
+
+/// Lowercase, forward slashes, no trailing slash. `None` for paths with `..`
+/// segments, which could climb out of an allowed folder.
+fn normalize(path: &str) -> Option<String> {
+    let p = path.trim().replace('\\', "/").to_lowercase();
+    if p.split('/').any(|seg| seg == "..") {
+        return None;
+    }
+    Some(p.trim_end_matches('/').to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prefs(save_paths: &[&str]) -> PreferencesConfig {
+        PreferencesConfig {
+            save_paths: save_paths.iter().map(|p| p.to_string()).collect(),
+            ..PreferencesConfig::default()
+        }
+    }
+
+    fn terminal(command: &str) -> AgentAction {
+        AgentAction::ExecuteTerminal {
+            command: command.into(),
+            reason: String::new(),
+            cwd: None,
+            env: Default::default(),
+            timeout_secs: None,
+        }
+    }
+
+    fn write(path: &str) -> AgentAction {
+        AgentAction::WriteFile { path: path.into(), content: String::new(), append: false }
+    }
+
+    #[test]
+    fn test_no_save_paths_allows_everything() {
+        assert!(check_action(&write("/etc/passwd"), &prefs(&[])).is_ok());
+        assert!(check_action(&write("/etc/passwd"), &prefs(&["  "])).is_ok());
+    }
+
+    #[test]
+    fn test_write_file_and_move_file_targets() {
+        let p = prefs(&[r"C:\Users\me\Documents"]);
+        assert!(check_action(&write(r"c:\users\me\documents\a.txt"), &p).is_ok());
+        assert!(check_action(&write("C:/Users/me/Documents/sub/b.txt"), &p).is_ok());
+        assert!(check_action(&write(r"C:\Users\me\Desktop\a.txt"), &p).is_err());
+        // A sibling folder sharing the prefix is not inside.
+        assert!(check_action(&write(r"C:\Users\me\Documents2\a.txt"), &p).is_err());
+        let mv = AgentAction::MoveFile { from: r"C:\tmp\a.txt".into(), to: r"C:\Windows\a.txt".into() };
+        assert!(check_action(&mv, &p).is_err());
+    }
+
+    #[test]
+    fn test_parent_segments_are_rejected() {
+        let p = prefs(&["/home/me/out"]);
+        assert!(check_action(&write("/home/me/out/../.bashrc"), &p).is_err());
+    }
+
+    #[test]
+    fn test_terminal_redirects() {
+        let p = prefs(&["/home/me/out"]);
+        assert!(check_action(&terminal("ls > /home/me/out/list.txt"), &p).is_ok());
+        assert!(check_action(&terminal("echo hi >> /tmp/x"), &p).is_err());
+        assert!(check_action(&terminal("Get-Process | Out-File -FilePath '/tmp/p.txt'"), &p).is_err());
+        assert!(check_action(&terminal("Invoke-WebRequest u -OutFile \"/tmp/f\""), &p).is_err());
+        assert!(check_action(&terminal("make 2> /dev/null"), &p).is_ok());
+        assert!(check_action(&terminal("ls -la"), &p).is_ok());
+    }
+
+    #[test]
+    fn test_typed_absolute_path() {
+        let p = prefs(&[r"D:\Saves"]);
+        let typed = |text: &str| AgentAction::TypeText { text: text.into(), clear_first: false };
+        assert!(check_action(&typed(r"D:\Saves\report.docx"), &p).is_ok());
+        assert!(check_action(&typed(r#""C:\report.docx""#), &p).is_err());
+        assert!(check_action(&typed("hello world"), &p).is_ok());
+    }
+}
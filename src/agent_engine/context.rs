@@ -1,68 +1,162 @@
 //! Immutable resource context shared by all nodes.
 //!
 //! `NodeContext` holds references to long-lived resources that do NOT change
-//! across node executions: the Tauri app handle, the LLM provider registry,
-//! perception config, YOLO detector, safety config, etc.
+//! across node executions: the event sink, the LLM provider registry,
+//! perception config, vision parser chain, safety config, etc.
 //!
 //! Nodes receive `&NodeContext` (immutable borrow) — they can read resources
 //! but not mutate the context itself.
 
 use std::sync::Arc;
 
-use tauri::{AppHandle, Wry};
 use tokio::sync::Mutex;
 
+use crate::agent_engine::event_bus::EventBus;
+use crate::agent_engine::grounding::{ElementThenGridGrounding, GroundingStrategy};
 use crate::agent_engine::history::SessionHistory;
 use crate::agent_engine::loop_control::LoopController;
-use crate::config::PerceptionConfig;
+use crate::config::{AppConfig, ExecutorConfig, PerceptionConfig, PreferencesConfig, SafetyConfig, StabilityWaitConfig};
+use crate::events::SharedEventSink;
 use crate::llm::registry::ProviderRegistry;
+use crate::mcp::manager::McpManager;
+use crate::perception::pipeline::PerceptionChain;
 use crate::perception::yolo_detector::YoloDetector;
-use crate::skills::SkillRegistry;
+use crate::skills::manager::load_skill_registry;
+use crate::skills::{SkillRegistry, SKILLS_DIR};
 
 /// Immutable resource container passed to every node.
 pub struct NodeContext {
-    /// Tauri application handle — used for emitting frontend events.
-    pub app: AppHandle<Wry>,
+    /// Event sink — the Tauri app handle in the desktop app, or any
+    /// embedder-provided sink.
+    pub app: SharedEventSink,
     /// LLM provider registry (behind Mutex because providers are shared).
     pub registry: Arc<Mutex<ProviderRegistry>>,
     /// Perception configuration (grid size, YOLO paths, UIA flags, etc.).
     pub perception_cfg: PerceptionConfig,
+    /// Safety configuration (terminal/file permissions, approval list).
+    pub safety: SafetyConfig,
+    /// User constraints injected into planner prompts and checked before actions.
+    pub preferences: PreferencesConfig,
+    /// Input execution settings (keyboard batching).
+    pub executor: ExecutorConfig,
+    /// How long to wait for the screen to settle, per action kind.
+    pub stability: StabilityWaitConfig,
     /// Grid resolution loaded from config (rows = cols = grid_n).
     pub grid_n: u32,
-    /// YOLO detector instance (None if model file missing or disabled).
-    pub yolo_detector: Arc<Mutex<Option<YoloDetector>>>,
+    /// Ordered vision parser chain (YOLO → UIA → SoM grid by default).
+    pub perception: Arc<PerceptionChain>,
+    /// Resolves element IDs / grid labels into click coordinates.
+    pub grounding: Arc<dyn GroundingStrategy>,
     /// Loop controller for timeout / failure limits.
     pub loop_ctrl: Arc<Mutex<LoopController>>,
     /// Session history writer (JSONL).
     pub history: Arc<Mutex<SessionHistory>>,
     /// Skill registry with manifests (for Planner) and combos (for ComboExec).
     pub skill_registry: Arc<SkillRegistry>,
-    /// Pre-computed skills context string to inject into planner prompts.
-    /// (Derived from `skill_registry.manifest_summary_for_planner()`)
-    pub skills_context: String,
+    /// Connected MCP servers (empty when none are configured/enabled).
+    pub mcp: Arc<McpManager>,
+    /// Engine milestones (perception, stability, plan, action) for subscribers.
+    pub bus: EventBus,
 }
 
 impl NodeContext {
     pub fn new(
-        app: AppHandle<Wry>,
+        app: SharedEventSink,
         registry: Arc<Mutex<ProviderRegistry>>,
-        perception_cfg: PerceptionConfig,
-        yolo_detector: Option<YoloDetector>,
+        config: &AppConfig,
+        perception: PerceptionChain,
         loop_ctrl: LoopController,
         skill_registry: SkillRegistry,
+        mcp: Arc<McpManager>,
+        bus: EventBus,
     ) -> Self {
+        let perception_cfg = config.perception.clone();
+        let safety = config.safety.clone();
         let grid_n = perception_cfg.grid_n.clamp(4, 26);
-        let skills_context = skill_registry.manifest_summary_for_planner();
         Self {
             app,
             registry,
             perception_cfg,
+            safety,
+            preferences: config.preferences.clone(),
+            executor: config.executor.clone(),
+            stability: config.stability.clone(),
             grid_n,
-            yolo_detector: Arc::new(Mutex::new(yolo_detector)),
+            perception: Arc::new(perception),
+            grounding: Arc::new(ElementThenGridGrounding),
             loop_ctrl: Arc::new(Mutex::new(loop_ctrl)),
-            history: Arc::new(Mutex::new(SessionHistory::new())),
+            history: Arc::new(Mutex::new(SessionHistory::new().with_thumbnails(config.history.thumbnails))),
             skill_registry: Arc::new(skill_registry),
-            skills_context,
+            mcp,
+            bus,
+        }
+    }
+
+    /// Same resources with another event sink and loop controller (a
+    /// delegated subtask).
+    pub(crate) fn scoped(&self, app: SharedEventSink, loop_ctrl: Arc<Mutex<LoopController>>) -> Self {
+        Self {
+            app,
+            registry: self.registry.clone(),
+            perception_cfg: self.perception_cfg.clone(),
+            safety: self.safety.clone(),
+            preferences: self.preferences.clone(),
+            executor: self.executor.clone(),
+            stability: self.stability.clone(),
+            grid_n: self.grid_n,
+            perception: self.perception.clone(),
+            grounding: self.grounding.clone(),
+            loop_ctrl,
+            history: self.history.clone(),
+            skill_registry: self.skill_registry.clone(),
+            mcp: self.mcp.clone(),
+            bus: self.bus.clone(),
+        }
+    }
+
+    /// Same long-lived resources (history, loop controller, MCP, bus) with
+    /// the settings of `config` — a config profile. The perception chain is
+    /// rebuilt only when `[perception]` differs, which drops parsers added
+    /// through `AgentBuilder::vision_parser`; skills are reloaded only when
+    /// `[safety]` differs, since it decides which of them are enabled.
+    pub(crate) async fn with_config(&self, config: &AppConfig, registry: Arc<Mutex<ProviderRegistry>>) -> Self {
+        let same_perception =
+            serde_json::to_value(&config.perception).ok() == serde_json::to_value(&self.perception_cfg).ok();
+        let perception = if same_perception {
+            self.perception.clone()
+        } else {
+            // Loading the YOLO model reads and parses a large file.
+            let perception_cfg = config.perception.clone();
+            let yolo = tokio::task::spawn_blocking(move || YoloDetector::from_config(&perception_cfg))
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!(error = %e, "YOLO detector load panicked; continuing without it");
+                    None
+                });
+            Arc::new(PerceptionChain::from_config(&config.perception, yolo))
+        };
+        let same_safety = serde_json::to_value(&config.safety).ok() == serde_json::to_value(&self.safety).ok();
+        let skill_registry = if same_safety {
+            self.skill_registry.clone()
+        } else {
+            Arc::new(load_skill_registry(SKILLS_DIR, &config.safety).await)
+        };
+        Self {
+            app: self.app.clone(),
+            registry,
+            perception_cfg: config.perception.clone(),
+            safety: config.safety.clone(),
+            preferences: config.preferences.clone(),
+            executor: config.executor.clone(),
+            stability: config.stability.clone(),
+            grid_n: config.perception.grid_n.clamp(4, 26),
+            perception,
+            grounding: self.grounding.clone(),
+            loop_ctrl: self.loop_ctrl.clone(),
+            history: self.history.clone(),
+            skill_registry,
+            mcp: self.mcp.clone(),
+            bus: self.bus.clone(),
         }
     }
 }
@@ -0,0 +1,191 @@
+//! Context window management for the planner conversation.
+//!
+//! `SharedState::conv_messages` grows with every replanning cycle: tool
+//! results, verifier feedback and full-resolution screenshots. Before each
+//! planner call, [`trim_to_budget`] shrinks it in place, cheapest loss first:
+//!
+//! 1. Screenshots other than the most recent one become a text placeholder.
+//! 2. Old tool results are cut to a short head.
+//! 3. Whole old turns (assistant call + its tool results) are dropped.
+//!
+//! The system prompt and the original goal message are never dropped, and
+//! the newest [`KEEP_RECENT`] messages keep their full text.
+
+use crate::llm::tokens::{estimate_message, estimate_messages};
+use crate::llm::types::{ChatMessage, ContentPart, MessageContent};
+
+/// Leading messages that are never trimmed: system prompt + goal.
+pub(crate) const PINNED: usize = 2;
+/// Most recent messages kept verbatim by steps 2 and 3.
+pub(crate) const KEEP_RECENT: usize = 4;
+/// Characters kept from a shortened tool result.
+const TOOL_RESULT_HEAD: usize = 200;
+const IMAGE_PLACEHOLDER: &str = "[earlier screenshot removed to save context]";
+
+/// What a trim pass did, for logging.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TrimReport {
+    pub before: usize,
+    pub after: usize,
+    pub images_stripped: usize,
+    pub results_shortened: usize,
+    pub messages_dropped: usize,
+}
+
+impl TrimReport {
+    pub fn changed(&self) -> bool {
+        self.images_stripped + self.results_shortened + self.messages_dropped > 0
+    }
+}
+
+/// Shrink `messages` until their estimate fits in `budget` tokens, or nothing
+/// more can be removed. Stale screenshots are stripped even when under budget.
+pub fn trim_to_budget(messages: &mut Vec<ChatMessage>, budget: usize) -> TrimReport {
+    let mut report = TrimReport { before: estimate_messages(messages), ..Default::default() };
+
+    report.images_stripped = strip_stale_images(messages);
+    let mut total = estimate_messages(messages);
+
+    let recent_start = messages.len().saturating_sub(KEEP_RECENT).max(PINNED).min(messages.len());
+    let start = PINNED.min(recent_start);
+    for msg in messages[start..recent_start].iter_mut() {
+        if total <= budget {
+            break;
+        }
+        if msg.role != "tool" {
+            continue;
+        }
+        if let MessageContent::Text(text) = &msg.content {
+            if text.chars().count() > TOOL_RESULT_HEAD {
+                let before = estimate_message(msg);
+                let head: String = text.chars().take(TOOL_RESULT_HEAD).collect();
+                msg.content = MessageContent::Text(format!("{head}… [truncated]"));
+                total = total.saturating_sub(before - estimate_message(msg));
+                report.results_shortened += 1;
+            }
+        }
+    }
+
+    while total > budget && messages.len() > PINNED + KEEP_RECENT {
+        // Drop one turn: the oldest unpinned message plus any tool results
+        // answering it, so no tool message is left without its call.
+        let mut end = PINNED + 1;
+        while end < messages.len() && messages[end].role == "tool" {
+            end += 1;
+        }
+        let removed: usize = messages.drain(PINNED..end).map(|m| estimate_message(&m)).sum();
+        total = total.saturating_sub(removed);
+        report.messages_dropped += end - PINNED;
+    }
+
+    report.after = total;
+    report
+}
+
+/// Replace every image except the last one with a placeholder. Returns the
+/// number of images removed.
+fn strip_stale_images(messages: &mut [ChatMessage]) -> usize {
+    let Some(last_with_image) = messages.iter().rposition(has_image) else {
+        return 0;
+    };
+    let mut stripped = 0;
+    for msg in messages[..last_with_image].iter_mut() {
+        if let MessageContent::Parts(parts) = &mut msg.content {
+            for part in parts.iter_mut() {
+                if matches!(part, ContentPart::ImageUrl { .. }) {
+                    *part = ContentPart::Text { text: IMAGE_PLACEHOLDER.to_string() };
+                    stripped += 1;
+                }
+            }
+        }
+    }
+    stripped
+}
+
+fn has_image(msg: &ChatMessage) -> bool {
+    matches!(&msg.content, MessageContent::Parts(parts)
+        if parts.iter().any(|p| matches!(p, ContentPart::ImageUrl { .. })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::types::ImageUrl;
+
+    fn msg(role: &str, text: &str) -> ChatMessage {
+        ChatMessage {
+            role: role.into(),
+            content: MessageContent::Text(text.into()),
+            tool_call_id: None,
+            tool_calls: None,
+        }
+    }
+
+    fn screenshot(text: &str) -> ChatMessage {
+        ChatMessage {
+            role: "user".into(),
+            content: MessageContent::Parts(vec![
+                ContentPart::Text { text: text.into() },
+                ContentPart::ImageUrl { image_url: ImageUrl { url: "data:image/png;base64,AAAA".into() } },
+            ]),
+            tool_call_id: None,
+            tool_calls: None,
+        }
+    }
+
+    fn pinned() -> Vec<ChatMessage> {
+        vec![msg("system", "You are a planner."), msg("user", "Open the report")]
+    }
+
+    #[test]
+    fn test_strips_all_but_latest_screenshot() {
+        let mut messages = pinned();
+        messages.extend([screenshot("step 1"), msg("assistant", "ok"), screenshot("step 2")]);
+        let report = trim_to_budget(&mut messages, usize::MAX);
+        assert_eq!(report.images_stripped, 1);
+        assert!(!has_image(&messages[2]));
+        assert!(has_image(&messages[4]));
+        assert_eq!(report.messages_dropped, 0);
+    }
+
+    #[test]
+    fn test_shortens_old_tool_results_first() {
+        let long = "x".repeat(4_000);
+        let mut messages = pinned();
+        messages.extend([msg("assistant", "read"), msg("tool", &long)]);
+        messages.extend((0..KEEP_RECENT).map(|_| msg("tool", &long)));
+        let budget = estimate_messages(&messages) - 500;
+        let report = trim_to_budget(&mut messages, budget);
+        assert_eq!(report.results_shortened, 1);
+        assert_eq!(report.messages_dropped, 0);
+        assert!(report.after <= budget);
+        assert!(matches!(&messages[3].content, MessageContent::Text(t) if t.ends_with("[truncated]")));
+        for m in &messages[messages.len() - KEEP_RECENT..] {
+            assert!(matches!(&m.content, MessageContent::Text(t) if t.len() == long.len()));
+        }
+    }
+
+    #[test]
+    fn test_drops_whole_turns_and_keeps_pinned() {
+        let mut messages = pinned();
+        for i in 0..6 {
+            messages.push(msg("assistant", &format!("call {i} {}", "y".repeat(400))));
+            messages.push(msg("tool", "done"));
+        }
+        let report = trim_to_budget(&mut messages, 200);
+        assert!(report.messages_dropped > 0);
+        assert_eq!(messages[0].role, "system");
+        assert_eq!(messages[1].role, "user");
+        // No tool result is left without the call it answers.
+        assert_ne!(messages[PINNED].role, "tool");
+        assert!(messages.len() >= PINNED + KEEP_RECENT);
+    }
+
+    #[test]
+    fn test_short_conversation_is_left_alone() {
+        let mut messages = vec![msg("system", &"s".repeat(2_000)), msg("tool", &"t".repeat(2_000))];
+        let report = trim_to_budget(&mut messages, 10);
+        assert!(!report.changed());
+        assert_eq!(messages.len(), 2);
+    }
+}
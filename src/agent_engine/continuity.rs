@@ -0,0 +1,95 @@
+//! Follow-up continuity — carries a short summary of the previous task into
+//! the next goal so "now email that file to Bob" can resolve "that file".
+//!
+//! The carry-over is deliberately short-lived: after [`CARRYOVER_TTL`] a new
+//! goal is treated as unrelated and starts from a blank conversation.
+
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use regex::Regex;
+
+use crate::agent_engine::state::SharedState;
+
+/// How long a finished task stays referenceable.
+pub const CARRYOVER_TTL: Duration = Duration::from_secs(10 * 60);
+/// Execution-log lines kept for context.
+const MAX_RECENT_STEPS: usize = 6;
+const MAX_ARTIFACTS: usize = 8;
+
+/// What the next goal may refer back to.
+#[derive(Debug, Clone)]
+pub struct TaskCarryover {
+    pub goal: String,
+    pub summary: String,
+    /// File paths and URLs mentioned in the log or summary, most recent last.
+    pub artifacts: Vec<String>,
+    pub recent_steps: Vec<String>,
+    finished_at: Instant,
+}
+
+impl TaskCarryover {
+    pub fn from_state(state: &SharedState, summary: &str) -> Self {
+        let mut artifacts = Vec::new();
+        for text in state.steps_log.iter().map(String::as_str).chain(std::iter::once(summary)) {
+            for m in artifact_regex().find_iter(text) {
+                let a = m.as_str().trim_end_matches(['.', ',', ')', '」', '。']).to_string();
+                artifacts.retain(|x| x != &a);
+                artifacts.push(a);
+            }
+        }
+        let skip = artifacts.len().saturating_sub(MAX_ARTIFACTS);
+        artifacts.drain(..skip);
+
+        let skip = state.steps_log.len().saturating_sub(MAX_RECENT_STEPS);
+        Self {
+            goal: state.goal.clone(),
+            summary: summary.to_string(),
+            artifacts,
+            recent_steps: state.steps_log[skip..].to_vec(),
+            finished_at: Instant::now(),
+        }
+    }
+
+    pub fn is_fresh(&self) -> bool {
+        self.finished_at.elapsed() < CARRYOVER_TTL
+    }
+
+    /// Prompt section describing the previous task.
+    pub fn to_prompt(&self) -> String {
+        let mut out = format!(
+            "## Previous Task (finished {}s ago)\nThe new request may refer to this (\"that file\", \"it\", \"also\"). Use it only if it does.\n- Goal: {}\n- Outcome: {}\n",
+            self.finished_at.elapsed().as_secs(),
+            self.goal,
+            self.summary.trim(),
+        );
+        if !self.artifacts.is_empty() {
+            out.push_str(&format!("- Artifacts: {}\n", self.artifacts.join(", ")));
+        }
+        if !self.recent_steps.is_empty() {
+            out.push_str("- Last steps:\n");
+            for s in &self.recent_steps {
+                out.push_str(&format!("  - {s}\n"));
+            }
+        }
+        out.trim_end().to_string()
+    }
+}
+
+/// Append the previous-task section to a system prompt, if there is one.
+pub fn with_carryover(system: &str, carryover: &str) -> String {
+    if carryover.is_empty() {
+        system.to_string()
+    } else {
+        format!("{system}\n\n{carryover}")
+    }
+}
+
+/// URLs, Windows paths and Unix paths with a file extension.
+fn artifact_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"https?://[^\s"'<>]+|[A-Za-z]:\\[^\s"'<>|*?]+\.\w{1,8}|(?:~|/)[\w./-]+\.\w{1,8}"#)
+            .expect("valid artifact regex")
+    })
+}
@@ -0,0 +1,118 @@
+//! Element cooldown — stops the agent from clicking the same unreachable target forever.
+//!
+//! A click counts as failed when grounding/input fails or when the screen does
+//! not change afterwards. After `MAX_CLICK_FAILURES` failures the element is put
+//! on cooldown for the rest of the task: `ActionExecNode` refuses further clicks
+//! on it, and the VLM / planner are told to find another route (keyboard
+//! navigation, menus, search).
+//!
+//! Detector IDs are renumbered on every screenshot, so elements are keyed by
+//! role, label and rounded box centre instead; the same control on the next
+//! frame maps to the same key whatever ID it was given. Targets that are not
+//! detected elements (SoM grid cells such as "C4") are keyed by their ID,
+//! which the grid keeps fixed.
+
+use std::collections::HashMap;
+
+use crate::perception::types::UIElement;
+
+/// Failed clicks on one element before it is put on cooldown.
+pub const MAX_CLICK_FAILURES: u32 = 2;
+/// Box centres are rounded to this many steps per screen axis.
+const CENTRE_STEPS: f32 = 50.0;
+
+#[derive(Debug, Clone)]
+struct Cooled {
+    key: String,
+    /// Label (or ID) shown when the element is not on the current frame.
+    label: String,
+}
+
+#[derive(Debug, Default)]
+pub struct ElementCooldown {
+    failures: HashMap<String, u32>,
+    cooling: Vec<Cooled>,
+}
+
+impl ElementCooldown {
+    /// Whether `element_id` on the frame described by `elements` is cooling.
+    pub fn is_cooling(&self, element_id: &str, elements: &[UIElement]) -> bool {
+        let key = key_for(element_id, elements);
+        self.cooling.iter().any(|c| c.key == key)
+    }
+
+    /// Count a failed click. Returns `true` when this failure put the element
+    /// on cooldown.
+    pub fn record_failure(&mut self, element_id: &str, elements: &[UIElement]) -> bool {
+        let key = key_for(element_id, elements);
+        if self.cooling.iter().any(|c| c.key == key) {
+            return false;
+        }
+        let count = self.failures.entry(key.clone()).or_insert(0);
+        *count += 1;
+        if *count >= MAX_CLICK_FAILURES {
+            tracing::warn!(element_id, key = %key, failures = *count, "element put on cooldown");
+            let label = find(element_id, elements)
+                .and_then(|e| e.content.as_deref())
+                .map(|c| format!("'{}'", c.trim()))
+                .unwrap_or_else(|| element_id.trim().to_uppercase());
+            self.cooling.push(Cooled { key, label });
+            return true;
+        }
+        false
+    }
+
+    /// A click on this element had a visible effect — forget earlier failures.
+    pub fn record_success(&mut self, element_id: &str, elements: &[UIElement]) {
+        self.failures.remove(&key_for(element_id, elements));
+    }
+
+    /// Prompt note for the planner / VLM, or `None` when nothing is cooling.
+    /// Cooled elements visible in `elements` are named by their current ID.
+    pub fn prompt_note(&self, elements: &[UIElement]) -> Option<String> {
+        if self.cooling.is_empty() {
+            return None;
+        }
+        let names = self
+            .cooling
+            .iter()
+            .map(|c| match elements.iter().find(|e| element_key(e) == c.key) {
+                Some(e) if c.label.starts_with('\'') => format!("{} {}", e.id, c.label),
+                Some(e) => e.id.clone(),
+                None => c.label.clone(),
+            })
+            .collect::<Vec<_>>();
+        Some(format!(
+            "Unreachable elements (clicking them failed {MAX_CLICK_FAILURES}+ times, further clicks will be refused): {}.\n\
+             Reach the target another way: `keyboard_navigate` to the control by its name, \
+             menu or shortcut routes, or a search box.",
+            names.join(", ")
+        ))
+    }
+}
+
+fn find<'a>(element_id: &str, elements: &'a [UIElement]) -> Option<&'a UIElement> {
+    elements.iter().find(|e| e.id.eq_ignore_ascii_case(element_id.trim()))
+}
+
+fn key_for(element_id: &str, elements: &[UIElement]) -> String {
+    match find(element_id, elements) {
+        Some(e) => element_key(e),
+        None => format!("id:{}", element_id.trim().to_uppercase()),
+    }
+}
+
+/// Role, label and rounded centre — stable across screenshots.
+fn element_key(e: &UIElement) -> String {
+    let label = e
+        .content
+        .as_deref()
+        .unwrap_or("")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    let cx = ((e.bbox[0] + e.bbox[2]) / 2.0 * CENTRE_STEPS).round() as i32;
+    let cy = ((e.bbox[1] + e.bbox[3]) / 2.0 * CENTRE_STEPS).round() as i32;
+    format!("{:?}|{label}|{cx},{cy}", e.node_type)
+}
@@ -0,0 +1,74 @@
+//! In-process message bus for engine milestones.
+//!
+//! The graph still decides which node runs next. The bus carries *what
+//! happened* — a screenshot was perceived, the screen settled, a plan was
+//! accepted, an action finished — as typed messages, so components such as
+//! the trace logger, metrics or an embedder can follow the loop without
+//! hooking into node code or parsing UI event payloads.
+//!
+//! Unlike [`EventSink`](crate::events::EventSink) events, these are not
+//! meant for the frontend and carry no display text.
+
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+/// Messages slower subscribers may fall behind by before losing the oldest.
+const BUS_CAPACITY: usize = 256;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AgentMessage {
+    /// The perception chain finished on a fresh screenshot.
+    PerceptionReady { elements: usize, elapsed_ms: u64 },
+    /// The stability wait after an action ended; `stable` is false on timeout.
+    VisualStable { stable: bool, waited_ms: u64 },
+    /// The planner accepted a plan.
+    PlanGenerated { final_goal: String, steps: Vec<String> },
+    /// An action (or keyboard batch) was executed.
+    ActionCompleted { action: String, ok: bool, error: Option<String> },
+    /// The graph saw the stop flag and is terminating the task.
+    StopRequested,
+}
+
+/// Cheap to clone; every clone publishes to the same subscribers.
+#[derive(Clone)]
+pub struct EventBus {
+    tx: broadcast::Sender<AgentMessage>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self { tx: broadcast::channel(BUS_CAPACITY).0 }
+    }
+
+    pub fn publish(&self, msg: AgentMessage) {
+        // No subscribers is not an error — the message is dropped.
+        let _ = self.tx.send(msg);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<AgentMessage> {
+        self.tx.subscribe()
+    }
+
+    /// Log every message at debug level (`RUST_LOG=seeclaw_lib::agent_engine::event_bus=debug`).
+    pub fn spawn_logger(&self) {
+        let mut rx = self.subscribe();
+        tokio::spawn(async move {
+            loop {
+                match rx.recv().await {
+                    Ok(msg) => tracing::debug!(?msg, "bus"),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::debug!(skipped, "bus: logger lagged");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+}
@@ -5,7 +5,7 @@
 
 use crate::agent_engine::graph::Graph;
 use crate::agent_engine::nodes;
-use crate::agent_engine::state::{RouteType, StepStatus};
+use crate::agent_engine::state::{RouteType, SharedState, StepStatus};
 
 /// Build the default agent graph with all nodes and edges.
 ///
@@ -25,6 +25,7 @@ use crate::agent_engine::state::{RouteType, StepStatus};
 ///                               └──────┬───────┘
 ///                                 ┌────┤
 ///                                 │    ├─ Combo ──→ combo_exec → step_advance
+///                                 │    ├─ Group ──→ parallel_exec → next step
 ///                                 │    ├─ Chat  ──→ chat_agent ──┐
 ///                                 │    └─ Vlm   ──→ vlm_act ────┘
 ///                                 │                       │
@@ -43,7 +44,8 @@ use crate::agent_engine::state::{RouteType, StepStatus};
 ///                                 │    ▼                  ▼
 ///                                 │  step_advance    chat_agent / vlm_act
 ///                                 │    │
-///                                 │    │ conditional: more steps?
+///                                 │    │ conditional: step failed? more steps?
+///                                 │    ├─ failed → reflect (patch plan) → …
 ///                                 │    ├─ yes → step_router (loop)
 ///                                 │    └─ no  → verifier
 ///                                 │                │
@@ -68,6 +70,7 @@ pub fn build_default_flow() -> Graph {
         match state.route_type {
             RouteType::Chat => "simple_chat".to_string(),
             RouteType::Simple => "simple_exec".to_string(),
+            RouteType::Terminal => "simple_exec".to_string(),
             RouteType::Complex => "planner".to_string(),
             RouteType::ComplexVisual => "planner".to_string(),
         }
@@ -102,6 +105,9 @@ pub fn build_default_flow() -> Graph {
     graph.add_conditional_edge("action_exec", |state| {
         if state.needs_approval {
             "user_confirm".to_string()
+        } else if !state.queued_tool_calls.is_empty() {
+            // More calls from the same planner message: settle, then run the next.
+            if state.needs_stability { "stability".to_string() } else { "planner".to_string() }
         } else if state.todo_steps.is_empty() {
             // Simple route or direct action from planner: no todo_steps → go to summarizer
             "summarizer".to_string()
@@ -115,32 +121,40 @@ pub fn build_default_flow() -> Graph {
     // ── UserConfirm → action_exec (node uses GoTo) ─────────────────────
     graph.add_edge("user_confirm", "action_exec");
 
-    // ── Stability → step_evaluate ───────────────────────────────────────
-    graph.add_edge("stability", "step_evaluate");
+    // ── Stability → planner (queued tool calls) or step_evaluate ────────
+    graph.add_conditional_edge("stability", |state| {
+        if state.queued_tool_calls.is_empty() {
+            "step_evaluate".to_string()
+        } else {
+            "planner".to_string()
+        }
+    });
 
     // ── StepEvaluate → conditional: loop back or advance ────────────────
     // StepEvaluateNode uses GoTo() for all routing. Fallback:
     graph.add_edge("step_evaluate", "step_advance");
 
-    // ── StepAdvance → conditional: more steps, verifier, or skip verifier ──
+    // ── StepAdvance → reflect (step failed) or the next step ────────────
     graph.add_conditional_edge("step_advance", |state| {
-        if state.current_step_idx < state.todo_steps.len() {
-            "step_router".to_string()
+        if nodes::reflect::should_reflect(state) {
+            "reflect".to_string()
+        } else {
+            after_step(state)
+        }
+    });
+
+    // ── ParallelExec → like step_advance, for the whole group ───────────
+    graph.add_conditional_edge("parallel_exec", |state| {
+        if nodes::reflect::should_reflect(state) {
+            "reflect".to_string()
         } else {
-            // All steps done — check if any failed.
-            // If all succeeded, skip verifier (saves one VLM call + screenshot).
-            let has_failure = state.todo_steps.iter().any(|s| {
-                matches!(s.status, StepStatus::Failed | StepStatus::Skipped)
-            });
-            if has_failure {
-                "verifier".to_string()
-            } else {
-                tracing::info!("[StepAdvance] all steps succeeded → skip verifier → summarizer");
-                "summarizer".to_string()
-            }
+            after_step(state)
         }
     });
 
+    // ── Reflect → the next step of the (possibly patched) plan ──────────
+    graph.add_conditional_edge("reflect", after_step);
+
     // ── Verifier → summarizer (pass) or planner (fail) ──────────────────
     graph.add_edge("verifier", "summarizer");
 
@@ -149,3 +163,23 @@ pub fn build_default_flow() -> Graph {
 
     graph
 }
+
+/// Where to go once a step is finished: the next step, the verifier, or —
+/// when every step succeeded — straight to the summarizer.
+fn after_step(state: &SharedState) -> String {
+    if state.current_step_idx < state.todo_steps.len() {
+        "step_router".to_string()
+    } else {
+        // All steps done — check if any failed.
+        // If all succeeded, skip verifier (saves one VLM call + screenshot).
+        let has_failure = state.todo_steps.iter().any(|s| {
+            matches!(s.status, StepStatus::Failed | StepStatus::Skipped)
+        });
+        if has_failure {
+            "verifier".to_string()
+        } else {
+            tracing::info!("[StepAdvance] all steps succeeded → skip verifier → summarizer");
+            "summarizer".to_string()
+        }
+    }
+}
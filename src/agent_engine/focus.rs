@@ -0,0 +1,276 @@
+//! Focus-crop second pass — sharpen a click the VLM chose on a crowded or
+//! coarse target.
+//!
+//! When `[perception] enable_focus_crop` is on and the chosen target is a
+//! small, low-confidence or overlapped element, or a bare SoM grid cell, the
+//! area around it is cropped from the screenshot, upscaled and shown to the
+//! vision model again, which answers with a point inside the crop. That
+//! point is mapped back to desktop pixels and clicked instead of the element
+//! centre. Best effort: any failure keeps the normal grounding.
+//!
+//! The same pass settles an uncertain pick: the crop then spans every
+//! candidate the model offered (see [`candidates`](super::candidates)) and
+//! the point it returns decides which one is clicked.
+//!
+//! With `[perception] verify_clicks`, [`verify_click`] crops the screen
+//! around a click once it has settled and asks whether the target responded.
+
+use crate::agent_engine::candidates::ClickCandidate;
+use crate::agent_engine::context::NodeContext;
+use crate::agent_engine::grounding::GroundedPoint;
+use crate::agent_engine::state::SharedState;
+use crate::llm::structured::{click_check_schema, crop_point_schema, parse_reply, ClickCheck, CropPoint};
+use crate::llm::types::{ChatMessage, ContentPart, ImageUrl, MessageContent};
+use crate::perception::focus_crop::{crop_bbox, crop_to_desktop};
+use crate::perception::screenshot::{data_url, ScreenshotResult};
+use crate::perception::som_grid::parse_grid_label;
+use crate::perception::types::{ScreenshotMeta, UIElement};
+use crate::prompts;
+
+const FOCUS_PROMPT: &str = include_str!("../../prompts/system/focus_crop.md");
+const CLICK_CHECK_PROMPT: &str = include_str!("../../prompts/system/click_check.md");
+/// Elements narrower or shorter than this (physical pixels) are refined.
+const SMALL_TARGET_PX: f32 = 28.0;
+/// Elements detected with less confidence than this are refined.
+const LOW_CONFIDENCE: f32 = 0.4;
+/// Context around the target, in screenshot pixels.
+const CROP_PADDING_PX: u32 = 80;
+/// Minimum side of the upscaled crop.
+const CROP_MIN_SIZE: u32 = 512;
+/// Context around a click when checking its effect, in screenshot pixels.
+const CHECK_PADDING_PX: u32 = 160;
+
+/// Normalised box worth a second look for `element_id`, or `None` when the
+/// normal element-centre click is good enough.
+pub fn focus_region(element_id: &str, elements: &[UIElement], meta: &ScreenshotMeta, grid_n: u32) -> Option<[f32; 4]> {
+    if let Some(elem) = elements.iter().find(|e| e.id == element_id) {
+        let w = (elem.bbox[2] - elem.bbox[0]) * meta.physical_width as f32;
+        let h = (elem.bbox[3] - elem.bbox[1]) * meta.physical_height as f32;
+        let ambiguous = w.min(h) < SMALL_TARGET_PX || elem.confidence < LOW_CONFIDENCE || is_overlapped(elem, elements);
+        return ambiguous.then_some(elem.bbox);
+    }
+    target_region(element_id, elements, grid_n)
+}
+
+/// Normalised box of a detected element or SoM grid cell.
+fn target_region(element_id: &str, elements: &[UIElement], grid_n: u32) -> Option<[f32; 4]> {
+    if let Some(elem) = elements.iter().find(|e| e.id == element_id) {
+        return Some(elem.bbox);
+    }
+    let (col, row) = parse_grid_label(element_id)?;
+    let n = grid_n.max(1) as f32;
+    Some([col as f32 / n, row as f32 / n, (col + 1) as f32 / n, (row + 1) as f32 / n])
+}
+
+/// "UI_7 (Save)" — the id with the element's text, when it has any.
+fn label(element_id: &str, elements: &[UIElement]) -> String {
+    elements
+        .iter()
+        .find(|e| e.id == element_id)
+        .and_then(|e| e.content.clone())
+        .map(|c| format!("{element_id} ({c})"))
+        .unwrap_or_else(|| element_id.to_string())
+}
+
+/// Another element — not one enclosing this one — covers its centre.
+fn is_overlapped(elem: &UIElement, elements: &[UIElement]) -> bool {
+    let cx = (elem.bbox[0] + elem.bbox[2]) / 2.0;
+    let cy = (elem.bbox[1] + elem.bbox[3]) / 2.0;
+    elements.iter().any(|other| {
+        let b = other.bbox;
+        let encloses = b[0] <= elem.bbox[0] && b[1] <= elem.bbox[1] && b[2] >= elem.bbox[2] && b[3] >= elem.bbox[3];
+        other.id != elem.id && !encloses && b[0] <= cx && cx <= b[2] && b[1] <= cy && cy <= b[3]
+    })
+}
+
+/// Run the second pass for a click on `element_id`. `None` when no
+/// refinement is needed or the model could not place the target.
+pub async fn refine_click(
+    element_id: &str,
+    goal: &str,
+    shot: &ScreenshotResult,
+    state: &SharedState,
+    ctx: &NodeContext,
+) -> Option<GroundedPoint> {
+    let bbox = focus_region(element_id, &state.detected_elements, &shot.meta, ctx.grid_n)?;
+    let target = label(element_id, &state.detected_elements);
+    let activity = state.lang.pick(format!("正在放大确认 {element_id} 的位置…"), format!("Zooming in on {element_id}…"));
+    let point = point_in_crop(bbox, &target, goal, activity, shot, state, ctx).await?;
+    tracing::info!(element_id, x = point.x, y = point.y, "focus crop refined click");
+    Some(point)
+}
+
+/// Settle an uncertain pick: crop the area around all `candidates`, ask the
+/// vision model where `description` is, and return the candidate the point
+/// falls in (the smallest, when boxes nest) with that point. A point outside
+/// every candidate still goes to the first one — the model found the target
+/// near it. `None` when the model can't place the target.
+pub async fn choose_candidate(
+    candidates: &[ClickCandidate],
+    description: Option<&str>,
+    goal: &str,
+    shot: &ScreenshotResult,
+    state: &SharedState,
+    ctx: &NodeContext,
+) -> Option<(String, GroundedPoint)> {
+    let regions: Vec<(&str, [f32; 4])> = candidates
+        .iter()
+        .filter_map(|c| Some((c.element_id.as_str(), target_region(&c.element_id, &state.detected_elements, ctx.grid_n)?)))
+        .collect();
+    let bbox = regions.iter().map(|(_, b)| *b).reduce(|a, b| {
+        [a[0].min(b[0]), a[1].min(b[1]), a[2].max(b[2]), a[3].max(b[3])]
+    })?;
+
+    let ids: Vec<String> = regions.iter().map(|(id, _)| label(id, &state.detected_elements)).collect();
+    let target = match description {
+        Some(d) => format!("{d} — one of: {}", ids.join(", ")),
+        None => format!("one of: {}", ids.join(", ")),
+    };
+    let activity = state.lang.pick("正在放大比较候选目标…".to_string(), "Zooming in to pick between candidates…".to_string());
+    let point = point_in_crop(bbox, &target, goal, activity, shot, state, ctx).await?;
+
+    let (nx, ny) = shot.meta.desktop_to_normalized(point.x as f64, point.y as f64);
+    let (nx, ny) = (nx as f32, ny as f32);
+    let chosen = regions
+        .iter()
+        .filter(|(_, b)| b[0] <= nx && nx <= b[2] && b[1] <= ny && ny <= b[3])
+        .min_by(|(_, a), (_, b)| area(a).total_cmp(&area(b)))
+        .map(|(id, _)| id.to_string())
+        .unwrap_or_else(|| candidates[0].element_id.clone());
+    tracing::info!(candidates = %target, chosen = %chosen, x = point.x, y = point.y, "focus crop chose between candidates");
+    Some((chosen, point))
+}
+
+/// Ask the vision model whether the click at `point` on `element_id`
+/// activated `description`, judging from `shot` taken after the click.
+/// `None` when the check itself could not run.
+pub async fn verify_click(
+    element_id: &str,
+    point: GroundedPoint,
+    description: Option<&str>,
+    goal: &str,
+    shot: &ScreenshotResult,
+    state: &SharedState,
+    ctx: &NodeContext,
+) -> Option<ClickCheck> {
+    let meta = &shot.meta;
+    let (px, py) = meta.from_desktop(point.x as f64, point.y as f64);
+    let nx = (px / meta.physical_width.max(1) as f64) as f32;
+    let ny = (py / meta.physical_height.max(1) as f64) as f32;
+    if !(0.0..=1.0).contains(&nx) || !(0.0..=1.0).contains(&ny) {
+        return None;
+    }
+    let crop = match crop_bbox(&shot.image_bytes, [nx, ny, nx, ny], CHECK_PADDING_PX, CROP_MIN_SIZE) {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::warn!(error = %e, element_id, "click check crop failed");
+            return None;
+        }
+    };
+    // The click point in crop pixels (the inverse of `crop_to_desktop`).
+    let image_scale = meta.image_scale as f32;
+    let cx = (px as f32 * image_scale - crop.origin_x as f32) * crop.out_w as f32 / crop.crop_w.max(1) as f32;
+    let cy = (py as f32 * image_scale - crop.origin_y as f32) * crop.out_h as f32 / crop.crop_h.max(1) as f32;
+
+    let target = match description {
+        Some(d) => format!("{d} ({})", label(element_id, &state.detected_elements)),
+        None => label(element_id, &state.detected_elements),
+    };
+    let prompt = prompts::get("click_check.md", CLICK_CHECK_PROMPT)
+        .replace("{width}", &crop.out_w.to_string())
+        .replace("{height}", &crop.out_h.to_string())
+        .replace("{x}", &(cx.round() as i32).to_string())
+        .replace("{y}", &(cy.round() as i32).to_string())
+        .replace("{goal}", goal)
+        .replace("{target}", &target);
+    let messages = vec![ChatMessage {
+        role: "user".into(),
+        content: MessageContent::Parts(vec![
+            ContentPart::ImageUrl { image_url: ImageUrl { url: data_url(&crop.image_base64) } },
+            ContentPart::Text { text: prompt },
+        ]),
+        tool_call_id: None,
+        tool_calls: None,
+    }];
+
+    let _ = ctx.app.emit("agent_activity", serde_json::json!({
+        "text": state.lang.pick(format!("正在确认点击 {element_id} 是否生效…"), format!("Checking the click on {element_id}…"))
+    }));
+    let (provider, mut cfg) = {
+        let reg = ctx.registry.lock().await;
+        reg.call_config_for_role("vision").ok()?
+    };
+    cfg.silent = true;
+    cfg.response_schema = Some(click_check_schema());
+    cfg.cancel = state.cancel.clone();
+    let response = match provider.chat(messages, vec![], &cfg, &ctx.app).await {
+        Ok(r) => r,
+        Err(e) => {
+            tracing::warn!(error = %e, element_id, "click check VLM call failed");
+            return None;
+        }
+    };
+    let check = parse_reply::<ClickCheck>(&response.content)?;
+    tracing::info!(element_id, activated = check.activated, reason = %check.reason, "click check");
+    Some(check)
+}
+
+fn area(b: &[f32; 4]) -> f32 {
+    (b[2] - b[0]) * (b[3] - b[1])
+}
+
+/// Crop `bbox`, ask the vision model for `target` inside it and map its
+/// answer back to desktop pixels.
+async fn point_in_crop(
+    bbox: [f32; 4],
+    target: &str,
+    goal: &str,
+    activity: String,
+    shot: &ScreenshotResult,
+    state: &SharedState,
+    ctx: &NodeContext,
+) -> Option<GroundedPoint> {
+    let crop = match crop_bbox(&shot.image_bytes, bbox, CROP_PADDING_PX, CROP_MIN_SIZE) {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::warn!(error = %e, target, "focus crop failed");
+            return None;
+        }
+    };
+
+    let prompt = prompts::get("focus_crop.md", FOCUS_PROMPT)
+        .replace("{width}", &crop.out_w.to_string())
+        .replace("{height}", &crop.out_h.to_string())
+        .replace("{goal}", goal)
+        .replace("{target}", target);
+    let messages = vec![ChatMessage {
+        role: "user".into(),
+        content: MessageContent::Parts(vec![
+            ContentPart::ImageUrl { image_url: ImageUrl { url: data_url(&crop.image_base64) } },
+            ContentPart::Text { text: prompt },
+        ]),
+        tool_call_id: None,
+        tool_calls: None,
+    }];
+
+    let _ = ctx.app.emit("agent_activity", serde_json::json!({ "text": activity }));
+    let (provider, mut cfg) = {
+        let reg = ctx.registry.lock().await;
+        reg.call_config_for_role("vision").ok()?
+    };
+    cfg.silent = true;
+    cfg.response_schema = Some(crop_point_schema());
+    cfg.cancel = state.cancel.clone();
+    let response = match provider.chat(messages, vec![], &cfg, &ctx.app).await {
+        Ok(r) => r,
+        Err(e) => {
+            tracing::warn!(error = %e, target, "focus crop VLM call failed");
+            return None;
+        }
+    };
+
+    let point = parse_reply::<CropPoint>(&response.content).filter(|p| p.found)?;
+    let (x, y) = crop_to_desktop(point.x, point.y, &crop, &shot.meta);
+    tracing::debug!(target, crop_x = point.x, crop_y = point.y, x, y, "focus crop point");
+    Some(GroundedPoint { x, y })
+}
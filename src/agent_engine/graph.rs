@@ -6,18 +6,24 @@
 //! 3. Resolves the next node via the edge definition (static or conditional).
 //! 4. Repeats until `NodeOutput::End` or stop_flag.
 //!
+//! After each node the task is checkpointed (see `checkpoint`) so it can be
+//! resumed after a crash.
+//!
 //! **Design**: Graph only manages topology and the run loop.
 //! All business logic lives in individual `Node` implementations.
 
 use std::collections::HashMap;
-use std::sync::atomic::Ordering;
 use std::time::Instant;
 
-use tauri::Emitter;
+use tracing::Instrument;
 
+use crate::agent_engine::checkpoint;
 use crate::agent_engine::context::NodeContext;
-use crate::agent_engine::node::{Node, NodeOutput};
-use crate::agent_engine::state::{GraphResult, SharedState};
+use crate::agent_engine::event_bus::AgentMessage;
+use crate::agent_engine::node::{wait_stop, Node, NodeOutput};
+use crate::agent_engine::state::{AgentAction, GraphResult, SharedState, StepStatus};
+use crate::errors::{ErrorCategory, SeeClawError, SeeClawResult};
+use crate::executor::watchdog;
 
 // ── Edge types ─────────────────────────────────────────────────────────────
 
@@ -93,29 +99,99 @@ impl Graph {
         &self,
         state: &mut SharedState,
         ctx: &NodeContext,
-    ) -> Result<(), String> {
-        let mut current = self.entry_point.clone();
+    ) -> SeeClawResult<()> {
+        self.run_from(&self.entry_point, state, ctx).await
+    }
+
+    /// Run the graph starting at node `start` instead of the entry point
+    /// (a task restored from a checkpoint).
+    pub async fn run_from(
+        &self,
+        start: &str,
+        state: &mut SharedState,
+        ctx: &NodeContext,
+    ) -> SeeClawResult<()> {
+        let mut current = start.to_string();
 
         loop {
             // ── Stop check ──────────────────────────────────────────────
-            if state.stop_flag.load(Ordering::Relaxed) {
+            if state.is_stopped() {
                 tracing::info!("graph: stop flag detected, terminating");
+                ctx.bus.publish(AgentMessage::StopRequested);
+                let message = state.lang.pick("任务已被用户终止", "Task stopped by the user");
                 state.result = Some(GraphResult::Error {
-                    message: "任务已被用户终止".to_string(),
+                    message: message.to_string(),
+                    category: ErrorCategory::UserStop,
                 });
                 // Notify frontend
                 let _ = ctx.app.emit("agent_state_changed", serde_json::json!({
                     "state": "done",
-                    "summary": "任务已被用户终止",
+                    "summary": message,
+                    "category": ErrorCategory::UserStop,
                 }));
                 break;
             }
 
+            // ── Pause ───────────────────────────────────────────────────
+            // The user took over the mouse and keyboard. Everything in
+            // `state` (conversation, plan, current step) is kept as is.
+            if state.pause.is_paused() {
+                tracing::info!(node = %current, "graph: paused");
+                let _ = ctx.app.emit("agent_state_changed", serde_json::json!({
+                    "state": "paused",
+                    "node": current,
+                }));
+                let pause = state.pause.clone();
+                tokio::select! {
+                    _ = pause.wait_resumed() => {}
+                    _ = wait_stop(state.stop_flag.clone()) => continue,
+                }
+                tracing::info!(node = %current, "graph: resumed");
+                // The screen may have changed while the user was in control.
+                state.pre_action_frame = None;
+                state.last_frame_hash = None;
+                let _ = ctx.app.emit("agent_activity", serde_json::json!({
+                    "text": state.lang.pick("已恢复，继续当前步骤…", "Resumed, continuing the current step…"),
+                }));
+            }
+
+            // ── Budget ──────────────────────────────────────────────────
+            // Out of steps / VLM calls / time, or too many failures: skip
+            // whatever comes next and let the summarizer report what was
+            // done so far.
+            if state.budget_exhausted.is_none() && current != "summarizer" {
+                let exhausted = {
+                    let ctrl = ctx.loop_ctrl.lock().await;
+                    ctrl.stop_reason().map(|reason| (reason, ctrl.usage()))
+                };
+                if let Some((reason, usage)) = exhausted {
+                    tracing::warn!(node = %current, reason = %reason, "graph: task budget exhausted → summarizer");
+                    let completed = state.todo_steps.iter().filter(|s| s.status == StepStatus::Completed).count();
+                    state.steps_log.push(format!("[Budget] {reason}; the task was stopped before it finished."));
+                    let _ = ctx.app.emit("budget_exhausted", serde_json::json!({
+                        "reason": reason,
+                        "node": current,
+                        "usage": usage,
+                        "completed_steps": completed,
+                        "total_steps": state.todo_steps.len(),
+                    }));
+                    let _ = ctx.app.emit("agent_activity", serde_json::json!({
+                        "text": state.lang.pick(
+                            format!("已达到任务预算上限（{reason}），正在总结已完成的部分…"),
+                            format!("Task budget reached ({reason}) — summarising what was done…"),
+                        )
+                    }));
+                    state.budget_exhausted = Some(reason);
+                    current = "summarizer".to_string();
+                    checkpoint::save(state, &current);
+                }
+            }
+
             // ── Find the node ───────────────────────────────────────────
             let node = self
                 .nodes
                 .get(&current)
-                .ok_or_else(|| format!("graph: unknown node '{current}'"))?;
+                .ok_or_else(|| SeeClawError::Agent(format!("graph: unknown node '{current}'")))?;
 
             tracing::debug!(node = %current, "graph: executing node");
 
@@ -130,7 +206,9 @@ impl Graph {
                 "step_evaluate" => "evaluating",
                 "summarizer"    => "evaluating",
                 "verifier"      => "evaluating",
+                "reflect"       => "planning",
                 "user_confirm"  => "waiting_for_user",
+                "stability"     => "waiting_for_stability",
                 _               => "executing",
             };
             let _ = ctx.app.emit("agent_state_changed", serde_json::json!({
@@ -140,7 +218,16 @@ impl Graph {
 
             // ── Execute ─────────────────────────────────────────────────
             let t_start = Instant::now();
-            let output = node.execute(state, ctx).await;
+            // Actions drive the real mouse and keyboard; pause if the user
+            // grabs them meanwhile.
+            // A delegated subtask runs its own nodes (and watchdog) inside.
+            let delegating = matches!(state.current_action, Some(AgentAction::DelegateSubtask { .. }));
+            let watch = (current == "action_exec" && !delegating && ctx.executor.takeover_detection).then(|| {
+                watchdog::watch(state.pause.clone(), ctx.app.clone(), state.lang, ctx.executor.takeover_move_px)
+            });
+            let span = tracing::info_span!("node", name = %current, step = state.current_step_idx);
+            let output = node.execute(state, ctx).instrument(span).await;
+            drop(watch);
             let elapsed_ms = t_start.elapsed().as_millis();
 
             tracing::info!(
@@ -158,6 +245,7 @@ impl Graph {
                 Ok(NodeOutput::GoTo(target)) => {
                     tracing::info!(from = %current, to = %target, elapsed_ms, "[Graph] {} → {} ({}ms)", current, target, elapsed_ms);
                     current = target;
+                    checkpoint::save(state, &current);
                 }
                 Ok(NodeOutput::Continue) => {
                     // Resolve next node via edge
@@ -165,11 +253,13 @@ impl Graph {
                         Some(Edge::Static { to }) => {
                             tracing::info!(from = %current, to = %to, elapsed_ms, "[Graph] {} → {} (static, {}ms)", current, to, elapsed_ms);
                             current = to.clone();
+                            checkpoint::save(state, &current);
                         }
                         Some(Edge::Conditional { router }) => {
                             let next = router(state);
                             tracing::info!(from = %current, to = %next, elapsed_ms, "[Graph] {} → {} (conditional, {}ms)", current, next, elapsed_ms);
                             current = next;
+                            checkpoint::save(state, &current);
                         }
                         None => {
                             tracing::warn!(node = %current, "graph: no outgoing edge, terminating");
@@ -179,17 +269,18 @@ impl Graph {
                 }
                 Err(e) => {
                     tracing::error!(node = %current, error = %e, "graph: node execution failed");
-                    state.result = Some(GraphResult::Error { message: e.clone() });
+                    state.result = Some(GraphResult::Error {
+                        message: e.to_string(),
+                        category: e.category(),
+                    });
                     let _ = ctx.app.emit("agent_state_changed", serde_json::json!({
                         "state": "error",
-                        "message": e,
+                        "message": e.to_string(),
+                        "category": e.category(),
                     }));
                     break;
                 }
             }
-
-            // Yield to allow other async tasks to progress
-            tokio::task::yield_now().await;
         }
 
         Ok(())
@@ -0,0 +1,104 @@
+//! Grounding — resolves an element reference from the LLM/VLM into desktop
+//! coordinates ([`ScreenshotMeta::to_desktop`]: the captured monitor's origin
+//! and pixel density are applied, so targets on secondary and mixed-DPI
+//! monitors land where they were seen).
+//!
+//! Planning (planner / step_router nodes) and execution (action_exec) only
+//! talk to grounding through the `GroundingStrategy` trait, so a fork can
+//! swap the strategy by replacing `NodeContext::grounding` without touching
+//! the nodes.
+
+use crate::perception::som_grid::{grid_cell_to_physical, parse_grid_label};
+use crate::perception::types::{ScreenshotMeta, UIElement};
+
+/// Everything a strategy may need to resolve one reference.
+pub struct GroundingRequest<'a> {
+    /// Element ID ("btn_3", "12") or SoM grid label ("C4") chosen by the model.
+    pub element_id: &'a str,
+    /// Elements detected in the last perception pass.
+    pub elements: &'a [UIElement],
+    /// Metadata of the screenshot the reference was made against.
+    pub meta: &'a ScreenshotMeta,
+    /// Grid resolution used for the SoM overlay.
+    pub grid_n: u32,
+}
+
+/// A resolved click target in desktop coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GroundedPoint {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// Strategy for turning a model's element reference into a click point.
+pub trait GroundingStrategy: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Returns `None` when the reference cannot be resolved.
+    fn resolve(&self, req: &GroundingRequest<'_>) -> Option<GroundedPoint>;
+}
+
+/// Default strategy: detected element centre first, SoM grid cell second.
+pub struct ElementThenGridGrounding;
+
+impl GroundingStrategy for ElementThenGridGrounding {
+    fn name(&self) -> &str {
+        "element_then_grid"
+    }
+
+    fn resolve(&self, req: &GroundingRequest<'_>) -> Option<GroundedPoint> {
+        let from_element = req
+            .elements
+            .iter()
+            .find(|e| e.id == req.element_id)
+            .map(|elem| elem.center_desktop(req.meta));
+
+        from_element
+            .or_else(|| {
+                parse_grid_label(req.element_id).map(|(col, row)| {
+                    let (x, y) = grid_cell_to_physical(
+                        col,
+                        row,
+                        req.meta.physical_width,
+                        req.meta.physical_height,
+                        req.grid_n,
+                    );
+                    req.meta.to_desktop(x as f64, y as f64)
+                })
+            })
+            .map(|(x, y)| GroundedPoint { x, y })
+    }
+}
+
+/// Desktop point for a position in percent (0–100) of the screenshot, or
+/// `None` when it lies outside it.
+pub fn percent_to_desktop(x_pct: f32, y_pct: f32, meta: &ScreenshotMeta) -> Option<GroundedPoint> {
+    let range = 0.0..=100.0;
+    if !range.contains(&x_pct) || !range.contains(&y_pct) {
+        return None;
+    }
+    // Keep 100% on the last pixel rather than just past it.
+    let px = (x_pct as f64 / 100.0 * meta.physical_width as f64).min(meta.physical_width.saturating_sub(1) as f64);
+    let py = (y_pct as f64 / 100.0 * meta.physical_height as f64).min(meta.physical_height.saturating_sub(1) as f64);
+    let (x, y) = meta.to_desktop(px, py);
+    Some(GroundedPoint { x, y })
+}
+
+// ── Confidence ─────────────────────────────────────────────────────────────
+
+/// Assumed detector confidence for a bare SoM grid cell (no detected element).
+const GRID_CELL_CONFIDENCE: f32 = 0.5;
+/// Assumed VLM certainty when the model did not report one.
+const DEFAULT_MODEL_CERTAINTY: f32 = 0.8;
+
+/// How sure we are that clicking `element_id` hits the intended target:
+/// detector confidence of the element × the model's self-reported certainty.
+/// Unknown references score 0.
+pub fn grounding_confidence(element_id: &str, elements: &[UIElement], model_certainty: Option<f32>) -> f32 {
+    let element_conf = match elements.iter().find(|e| e.id == element_id) {
+        Some(el) => el.confidence.clamp(0.0, 1.0),
+        None if parse_grid_label(element_id).is_some() => GRID_CELL_CONFIDENCE,
+        None => 0.0,
+    };
+    element_conf * model_certainty.unwrap_or(DEFAULT_MODEL_CERTAINTY).clamp(0.0, 1.0)
+}
@@ -1,7 +1,21 @@
+//! Session history — one JSONL file of goals and executed actions per app
+//! run (`sessions/session_<id>.jsonl`), with optional screenshot thumbnails
+//! under `sessions/<id>/thumbs/`. The browsing functions below back the
+//! task history view; [`prune`] applies the `[history]` retention policy.
+
 use serde::{Deserialize, Serialize};
 use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use base64::Engine as _;
 
-use crate::errors::SeeClawResult;
+use crate::agent_engine::checkpoint;
+use crate::config::HistoryConfig;
+use crate::errors::{SeeClawError, SeeClawResult};
+
+/// Longest side of a saved thumbnail, in pixels.
+const THUMBNAIL_SIZE: u32 = 320;
+const THUMBNAIL_QUALITY: u8 = 70;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HistoryEntry {
@@ -9,30 +23,80 @@ pub struct HistoryEntry {
     pub role: String,
     pub content: Option<String>,
     pub action: Option<serde_json::Value>,
+    /// Whether the action succeeded; its result text is in `content`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ok: Option<bool>,
+    /// Thumbnail file under the session folder, for UI actions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub screenshot: Option<String>,
+    /// The model's reasoning behind this entry, for reasoning models.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<String>,
 }
 
 pub struct SessionHistory {
     pub session_id: String,
     entries: Vec<HistoryEntry>,
     file_path: std::path::PathBuf,
+    thumbnails: bool,
 }
 
 impl SessionHistory {
     pub fn new() -> Self {
-        let session_id = uuid::Uuid::new_v4().to_string();
-        let dir = data_dir_or_cwd();
-        let file_path = dir.join(format!("session_{session_id}.jsonl"));
+        let session_id = current_session_id().to_string();
+        let file_path = history_path(&session_id);
         Self {
             session_id,
             entries: Vec::new(),
             file_path,
+            thumbnails: true,
         }
     }
 
+    /// Whether [`save_thumbnail`](Self::save_thumbnail) writes anything.
+    pub fn with_thumbnails(mut self, enabled: bool) -> Self {
+        self.thumbnails = enabled;
+        self
+    }
+
     pub fn push(&mut self, entry: HistoryEntry) {
         self.entries.push(entry);
     }
 
+    /// Store a downscaled copy of the encoded screenshot `frame` for the
+    /// entry at `ts`, with a ring at `marker` (fractions of width and height)
+    /// for clicks; returns its file name. `None` when thumbnails are off or
+    /// the image cannot be written.
+    pub fn save_thumbnail(&self, ts: i64, frame: &[u8], marker: Option<(f64, f64)>) -> Option<String> {
+        if !self.thumbnails {
+            return None;
+        }
+        let dir = session_dir(&self.session_id).join("thumbs");
+        let name = format!("{ts}.jpg");
+        let result = (|| -> SeeClawResult<()> {
+            let mut img = image::load_from_memory(frame)
+                .map_err(|e| SeeClawError::Perception(format!("decode screenshot: {e}")))?
+                .thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+                .to_rgb8();
+            if let Some((fx, fy)) = marker.filter(|(x, y)| (0.0..=1.0).contains(x) && (0.0..=1.0).contains(y)) {
+                draw_ring(&mut img, fx, fy);
+            }
+            std::fs::create_dir_all(&dir)?;
+            let mut file = std::io::BufWriter::new(std::fs::File::create(dir.join(&name))?);
+            image::codecs::jpeg::JpegEncoder::new_with_quality(&mut file, THUMBNAIL_QUALITY)
+                .encode_image(&img)
+                .map_err(|e| SeeClawError::Perception(format!("encode thumbnail: {e}")))?;
+            Ok(())
+        })();
+        match result {
+            Ok(()) => Some(format!("thumbs/{name}")),
+            Err(e) => {
+                tracing::debug!(error = %e, "history thumbnail not saved");
+                None
+            }
+        }
+    }
+
     /// Append the latest entry to the JSONL file.
     pub fn flush(&self) -> SeeClawResult<()> {
         if let Some(last) = self.entries.last() {
@@ -57,10 +121,229 @@ impl Default for SessionHistory {
     }
 }
 
-/// Returns `%LOCALAPPDATA%\SeeClaw\sessions` on Windows,
-/// `~/.local/share/seeclaw/sessions` on Linux/macOS,
+/// One saved session, as listed by the `list_sessions` command.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSummary {
+    pub session_id: String,
+    /// Goals started in this session, oldest first.
+    pub goals: Vec<String>,
+    /// Recorded actions.
+    pub actions: usize,
+    /// RFC 3339 time of the first and last history entry (or file times).
+    pub started: String,
+    pub modified: String,
+    pub size_bytes: u64,
+    /// The session left an unfinished task that `resume_session` can restart.
+    pub recoverable: bool,
+    pub has_trace: bool,
+    /// The session of this app run.
+    pub current: bool,
+}
+
+/// A history entry with its thumbnail inlined as a data URL.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionEntry {
+    #[serde(flatten)]
+    pub entry: HistoryEntry,
+    pub thumbnail: Option<String>,
+}
+
+/// Every saved session, newest first.
+pub fn list_sessions() -> SeeClawResult<Vec<SessionSummary>> {
+    let mut out: Vec<SessionSummary> = session_ids()?.iter().map(|id| summarize(id)).collect();
+    out.sort_by(|a, b| b.modified.cmp(&a.modified));
+    Ok(out)
+}
+
+/// All entries of one session, thumbnails included.
+pub fn read_session(session_id: &str) -> SeeClawResult<Vec<SessionEntry>> {
+    validate_session_id(session_id)?;
+    let dir = session_dir(session_id);
+    let entries = read_entries(&history_path(session_id));
+    if entries.is_empty() && !dir.is_dir() {
+        return Err(SeeClawError::Agent(format!("no saved session '{session_id}'")));
+    }
+    Ok(entries
+        .into_iter()
+        .map(|entry| {
+            let thumbnail = entry
+                .screenshot
+                .as_deref()
+                .and_then(|name| std::fs::read(dir.join(name)).ok())
+                .map(|bytes| {
+                    format!("data:image/jpeg;base64,{}", base64::engine::general_purpose::STANDARD.encode(bytes))
+                });
+            SessionEntry { entry, thumbnail }
+        })
+        .collect())
+}
+
+/// Remove a session's history, thumbnails, trace and checkpoint. The
+/// running session cannot be deleted.
+pub fn delete_session(session_id: &str) -> SeeClawResult<()> {
+    validate_session_id(session_id)?;
+    if session_id == current_session_id() {
+        return Err(SeeClawError::Agent("the current session cannot be deleted".into()));
+    }
+    let file = history_path(session_id);
+    if file.is_file() {
+        std::fs::remove_file(&file)?;
+    }
+    let dir = session_dir(session_id);
+    if dir.is_dir() {
+        std::fs::remove_dir_all(&dir)?;
+    }
+    Ok(())
+}
+
+/// Apply the retention policy; returns how many sessions were deleted.
+pub fn prune(cfg: &HistoryConfig) -> usize {
+    let Ok(mut sessions) = list_sessions() else {
+        return 0;
+    };
+    sessions.retain(|s| !s.current);
+    let cutoff = (cfg.retention_days > 0)
+        .then(|| (chrono::Local::now() - chrono::Duration::days(cfg.retention_days as i64)).to_rfc3339());
+    let mut deleted = 0;
+    for (rank, s) in sessions.iter().enumerate() {
+        let expired = cutoff.as_deref().is_some_and(|c| s.modified.as_str() < c);
+        let surplus = cfg.max_sessions > 0 && rank + 1 >= cfg.max_sessions;
+        if !(expired || surplus) {
+            continue;
+        }
+        match delete_session(&s.session_id) {
+            Ok(()) => deleted += 1,
+            Err(e) => tracing::warn!(session = %s.session_id, error = %e, "history: prune failed"),
+        }
+    }
+    if deleted > 0 {
+        tracing::info!(deleted, "history: pruned old sessions");
+    }
+    deleted
+}
+
+/// Red ring around the point at fractions (`fx`, `fy`) of the image.
+fn draw_ring(img: &mut image::RgbImage, fx: f64, fy: f64) {
+    const RADIUS: f64 = 9.0;
+    const WIDTH: f64 = 2.0;
+    let (w, h) = img.dimensions();
+    let (cx, cy) = (fx * w as f64, fy * h as f64);
+    let x0 = (cx - RADIUS - WIDTH).floor().max(0.0) as u32;
+    let y0 = (cy - RADIUS - WIDTH).floor().max(0.0) as u32;
+    let x1 = ((cx + RADIUS + WIDTH).ceil() as u32).min(w.saturating_sub(1));
+    let y1 = ((cy + RADIUS + WIDTH).ceil() as u32).min(h.saturating_sub(1));
+    for y in y0..=y1 {
+        for x in x0..=x1 {
+            let d = ((x as f64 + 0.5 - cx).powi(2) + (y as f64 + 0.5 - cy).powi(2)).sqrt();
+            if (d - RADIUS).abs() <= WIDTH / 2.0 {
+                img.put_pixel(x, y, image::Rgb([230, 40, 40]));
+            }
+        }
+    }
+}
+
+pub(crate) fn validate_session_id(session_id: &str) -> SeeClawResult<()> {
+    if session_id.is_empty() || !session_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(SeeClawError::Config(format!("invalid session id '{session_id}'")));
+    }
+    Ok(())
+}
+
+fn summarize(session_id: &str) -> SessionSummary {
+    let file = history_path(session_id);
+    let dir = session_dir(session_id);
+    let entries = read_entries(&file);
+    let goals = entries
+        .iter()
+        .filter(|e| e.role == "user")
+        .filter_map(|e| e.content.clone())
+        .collect();
+    let actions = entries.iter().filter(|e| e.action.is_some()).count();
+    let size_bytes = std::fs::metadata(&file).map(|m| m.len()).unwrap_or(0) + dir_size(&dir);
+    let modified_fallback = [&file, &dir]
+        .iter()
+        .filter_map(|p| std::fs::metadata(p).and_then(|m| m.modified()).ok())
+        .max()
+        .map(|t| chrono::DateTime::<chrono::Local>::from(t).to_rfc3339())
+        .unwrap_or_default();
+    let time = |ts: i64| {
+        chrono::DateTime::from_timestamp_millis(ts)
+            .map(|t| t.with_timezone(&chrono::Local).to_rfc3339())
+    };
+    SessionSummary {
+        session_id: session_id.to_string(),
+        goals,
+        actions,
+        started: entries.first().and_then(|e| time(e.ts)).unwrap_or_else(|| modified_fallback.clone()),
+        modified: entries.last().and_then(|e| time(e.ts)).unwrap_or(modified_fallback),
+        size_bytes,
+        recoverable: checkpoint::exists(session_id),
+        has_trace: dir.join("llm_trace.jsonl").is_file(),
+        current: session_id == current_session_id(),
+    }
+}
+
+/// Ids of every session with a history file or a session folder.
+fn session_ids() -> SeeClawResult<Vec<String>> {
+    let mut ids = Vec::new();
+    for entry in std::fs::read_dir(data_dir_or_cwd("sessions"))?.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let id = match name.strip_prefix("session_").and_then(|n| n.strip_suffix(".jsonl")) {
+            Some(id) => id.to_string(),
+            None if entry.path().is_dir() => name,
+            None => continue,
+        };
+        if validate_session_id(&id).is_ok() && !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    Ok(ids)
+}
+
+fn read_entries(path: &Path) -> Vec<HistoryEntry> {
+    std::fs::read_to_string(path)
+        .map(|text| {
+            text.lines()
+                .filter(|l| !l.trim().is_empty())
+                .filter_map(|l| serde_json::from_str(l).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|e| match e.metadata() {
+            Ok(m) if m.is_dir() => dir_size(&e.path()),
+            Ok(m) => m.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+fn history_path(session_id: &str) -> PathBuf {
+    data_dir_or_cwd("sessions").join(format!("session_{session_id}.jsonl"))
+}
+
+fn session_dir(session_id: &str) -> PathBuf {
+    data_dir_or_cwd("sessions").join(session_id)
+}
+
+/// Id of this app run; names the session history file and the
+/// `sessions/<id>/` folder used by other per-session logs.
+pub fn current_session_id() -> &'static str {
+    static ID: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+    ID.get_or_init(|| uuid::Uuid::new_v4().to_string())
+}
+
+/// Returns `%LOCALAPPDATA%\SeeClaw\<sub>` on Windows,
+/// `~/.local/share/seeclaw/<sub>` on Linux/macOS,
 /// falling back to the current working directory.
-fn data_dir_or_cwd() -> std::path::PathBuf {
+pub(crate) fn data_dir_or_cwd(sub: &str) -> std::path::PathBuf {
     #[cfg(target_os = "windows")]
     let base = std::env::var("LOCALAPPDATA").ok().map(std::path::PathBuf::from);
 
@@ -70,7 +353,7 @@ fn data_dir_or_cwd() -> std::path::PathBuf {
         .map(|h| std::path::PathBuf::from(h).join(".local").join("share"));
 
     if let Some(data_dir) = base {
-        let d = data_dir.join("SeeClaw").join("sessions");
+        let d = data_dir.join("SeeClaw").join(sub);
         let _ = std::fs::create_dir_all(&d);
         return d;
     }
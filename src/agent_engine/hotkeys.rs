@@ -0,0 +1,78 @@
+//! Hotkey knowledge base — well-known shortcuts for common applications.
+//!
+//! Bundled from `prompts/knowledge/hotkeys.json`. When the foreground app (or an
+//! app named in the goal) matches an entry, its shortcuts are given to the
+//! planner so plans prefer reliable hotkeys over fragile clicking.
+
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use crate::perception::foreground::ForegroundApp;
+
+const HOTKEYS_JSON: &str = include_str!("../../prompts/knowledge/hotkeys.json");
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AppHotkeys {
+    /// Display name used in the prompt.
+    pub app: String,
+    /// Executable names (lower-case) that identify the app as foreground.
+    #[serde(default)]
+    pub processes: Vec<String>,
+    /// Names users write in goals ("vs code", "excel").
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    pub hotkeys: Vec<Hotkey>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Hotkey {
+    pub keys: String,
+    pub action: String,
+}
+
+fn knowledge_base() -> &'static [AppHotkeys] {
+    static KB: OnceLock<Vec<AppHotkeys>> = OnceLock::new();
+    KB.get_or_init(|| {
+        serde_json::from_str(HOTKEYS_JSON).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "failed to parse bundled hotkeys.json");
+            Vec::new()
+        })
+    })
+}
+
+/// Knowledge-base entry for the foreground app.
+pub fn for_foreground(app: &ForegroundApp) -> Option<&'static AppHotkeys> {
+    knowledge_base()
+        .iter()
+        .find(|e| e.processes.iter().any(|p| *p == app.process))
+}
+
+/// Entries for apps named in the goal text.
+pub fn mentioned_in(goal: &str) -> Vec<&'static AppHotkeys> {
+    let goal = goal.to_lowercase();
+    knowledge_base()
+        .iter()
+        .filter(|e| e.aliases.iter().any(|a| goal.contains(a.as_str())))
+        .collect()
+}
+
+/// Prompt section listing `entries`' shortcuts, or `None` when empty.
+/// `foreground` names the app currently in front, if it is among them.
+pub fn prompt_section(entries: &[&AppHotkeys], foreground: Option<&str>) -> Option<String> {
+    if entries.is_empty() {
+        return None;
+    }
+    let mut out = String::from(
+        "## Known keyboard shortcuts\n\
+         Prefer these over clicking when they achieve the step (use the `hotkey` tool):\n",
+    );
+    for entry in entries {
+        let marker = if foreground == Some(entry.app.as_str()) { " (foreground)" } else { "" };
+        out.push_str(&format!("\n### {}{marker}\n", entry.app));
+        for hk in &entry.hotkeys {
+            out.push_str(&format!("- `{}` — {}\n", hk.keys, hk.action));
+        }
+    }
+    Some(out)
+}
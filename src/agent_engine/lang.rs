@@ -0,0 +1,124 @@
+//! Goal language detection — decides whether progress messages, planner
+//! reasoning and the final summary are produced in Chinese or English.
+//!
+//! The UI ships in those two languages only, so detection is a cheap script
+//! heuristic rather than a full language identifier: any goal dominated by
+//! CJK characters is Chinese, everything else falls back to English.
+//! `[preferences] language` pins one language instead.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+/// `[preferences] language`: `auto` follows each goal's language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LanguagePreference {
+    #[default]
+    Auto,
+    En,
+    Zh,
+}
+
+const AUTO: u8 = 0;
+const ZH: u8 = 1;
+const EN: u8 = 2;
+
+static PREFERENCE: AtomicU8 = AtomicU8::new(AUTO);
+/// Language of the running task, for messages emitted outside the graph.
+static CURRENT: AtomicU8 = AtomicU8::new(ZH);
+
+/// Apply `[preferences] language` (on startup and whenever config changes).
+pub fn set_preference(pref: LanguagePreference) {
+    let v = match pref {
+        LanguagePreference::Auto => AUTO,
+        LanguagePreference::Zh => ZH,
+        LanguagePreference::En => EN,
+    };
+    PREFERENCE.store(v, Ordering::Relaxed);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GoalLanguage {
+    #[default]
+    Chinese,
+    English,
+}
+
+impl GoalLanguage {
+    /// Classify `text` by script. One CJK character carries roughly as much
+    /// meaning as a short Latin word, so CJK counts triple against letters —
+    /// "用 Chrome 打开 github.com" is still a Chinese goal.
+    pub fn detect(text: &str) -> Self {
+        let mut cjk = 0usize;
+        let mut latin = 0usize;
+        for c in text.chars() {
+            if is_cjk(c) {
+                cjk += 1;
+            } else if c.is_ascii_alphabetic() {
+                latin += 1;
+            }
+        }
+        if cjk == 0 && latin == 0 {
+            return Self::default();
+        }
+        if cjk * 3 >= latin {
+            Self::Chinese
+        } else {
+            Self::English
+        }
+    }
+
+    /// The language to use for a task on `goal`: the configured preference,
+    /// or the goal's own language under `auto`.
+    pub fn for_goal(goal: &str) -> Self {
+        match PREFERENCE.load(Ordering::Relaxed) {
+            ZH => Self::Chinese,
+            EN => Self::English,
+            _ => Self::detect(goal),
+        }
+    }
+
+    /// Language of the running task — for provider retries and other
+    /// messages emitted where no `SharedState` is at hand.
+    pub fn current() -> Self {
+        if CURRENT.load(Ordering::Relaxed) == EN { Self::English } else { Self::Chinese }
+    }
+
+    /// Make this the language [`current`](Self::current) reports.
+    pub fn make_current(self) {
+        CURRENT.store(self.pick(ZH, EN), Ordering::Relaxed);
+    }
+
+    /// Choose between a Chinese and an English variant.
+    pub fn pick<T>(self, zh: T, en: T) -> T {
+        match self {
+            Self::Chinese => zh,
+            Self::English => en,
+        }
+    }
+
+    /// Short code sent to the frontend ("zh" / "en").
+    pub fn code(self) -> &'static str {
+        self.pick("zh", "en")
+    }
+
+    /// Directive appended to system prompts so the model answers in kind.
+    pub fn prompt_directive(self) -> &'static str {
+        self.pick(
+            "## Output Language\nThe user wrote in Chinese. Write all user-facing text (reasoning, step descriptions, summaries) in Simplified Chinese.",
+            "## Output Language\nThe user wrote in English. Write all user-facing text (reasoning, step descriptions, summaries) in English.",
+        )
+    }
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{4E00}'..='\u{9FFF}'   // CJK Unified Ideographs
+        | '\u{3400}'..='\u{4DBF}' // Extension A
+        | '\u{F900}'..='\u{FAFF}' // Compatibility Ideographs
+        | '\u{3000}'..='\u{303F}' // CJK punctuation
+        | '\u{FF00}'..='\u{FFEF}' // Full-width forms
+    )
+}
@@ -1,18 +1,26 @@
-// Loop control engine — placeholder until Phase 3 full implementation.
-use crate::agent_engine::state::LoopConfig;
+// Loop control engine — per-task failure, time and budget limits.
+use crate::agent_engine::state::{LoopConfig, LoopMode, LoopOverrides};
 
 pub struct LoopController {
+    /// Defaults from `[safety]`.
     config: LoopConfig,
+    /// Limits for the running task (`config` plus its overrides).
+    active: LoopConfig,
     start_time: std::time::Instant,
     failure_count: u32,
+    steps: u32,
+    vlm_calls: u32,
 }
 
 impl LoopController {
     pub fn new(config: LoopConfig) -> Self {
         Self {
+            active: config.clone(),
             config,
             start_time: std::time::Instant::now(),
             failure_count: 0,
+            steps: 0,
+            vlm_calls: 0,
         }
     }
 
@@ -20,30 +28,108 @@ impl LoopController {
         self.failure_count += 1;
     }
 
-    /// Reset counters for a new task cycle.
-    pub fn reset(&mut self) {
+    /// A successful action ends a run of consecutive failures.
+    pub fn record_success(&mut self) {
+        self.failure_count = 0;
+    }
+
+    /// Count executed actions against `max_total_steps`.
+    pub fn record_steps(&mut self, n: u32) {
+        self.steps += n;
+    }
+
+    /// Count one vision-model call against `max_vlm_calls`.
+    pub fn record_vlm_call(&mut self) {
+        self.vlm_calls += 1;
+    }
+
+    /// Replace the `[safety]` defaults (config reloaded); applies from the
+    /// next `begin_task`.
+    pub fn set_defaults(&mut self, config: LoopConfig) {
+        self.config = config;
+    }
+
+    /// Start counting for a new goal, with its own overrides if any.
+    pub fn begin_task(&mut self, overrides: Option<&LoopOverrides>) {
+        self.active = match overrides {
+            Some(o) => self.config.with_overrides(o),
+            None => self.config.clone(),
+        };
         self.start_time = std::time::Instant::now();
         self.failure_count = 0;
+        self.steps = 0;
+        self.vlm_calls = 0;
     }
 
-    pub fn should_stop(&self) -> bool {
-        use crate::agent_engine::state::LoopMode;
-        match &self.config.mode {
-            LoopMode::UntilDone => false,
-            LoopMode::Timed => {
-                if let Some(max_min) = self.config.max_duration_minutes {
-                    self.start_time.elapsed().as_secs() / 60 >= max_min as u64
-                } else {
-                    false
-                }
-            }
-            LoopMode::FailureLimit => {
-                if let Some(max_fail) = self.config.max_failures {
-                    self.failure_count >= max_fail
-                } else {
-                    false
-                }
-            }
+    /// Controller for a delegated subtask: the same mode and limits, at most
+    /// `max_steps` actions, and never more steps or VLM calls than this task
+    /// has left.
+    pub fn child(&self, max_steps: u32) -> LoopController {
+        let mut config = self.active.clone();
+        let b = &mut config.budget;
+        b.max_total_steps = cap(max_steps, b.max_total_steps, self.steps);
+        b.max_vlm_calls = cap(0, b.max_vlm_calls, self.vlm_calls);
+        LoopController::new(config)
+    }
+
+    /// Count a finished subtask's steps and VLM calls against this task.
+    pub fn absorb(&mut self, child: &LoopController) {
+        self.steps += child.steps;
+        self.vlm_calls += child.vlm_calls;
+    }
+
+    /// Plan → execute → verify cycles allowed before the verifier gives up.
+    pub fn max_cycles(&self) -> u32 {
+        self.active.budget.max_cycles
+    }
+
+    /// Why the task has to stop now, or `None` while it may go on.
+    pub fn stop_reason(&self) -> Option<String> {
+        let b = &self.active.budget;
+        if b.max_total_steps > 0 && self.steps >= b.max_total_steps {
+            return Some(format!("step budget used up ({} of {} actions)", self.steps, b.max_total_steps));
+        }
+        if b.max_vlm_calls > 0 && self.vlm_calls >= b.max_vlm_calls {
+            return Some(format!("vision budget used up ({} of {} calls)", self.vlm_calls, b.max_vlm_calls));
         }
+        match self.active.mode {
+            LoopMode::UntilDone => None,
+            LoopMode::Timed => self.active.max_duration_minutes.filter(|&max_min| {
+                self.start_time.elapsed().as_secs() / 60 >= max_min as u64
+            }).map(|max_min| format!("time limit reached ({max_min} min)")),
+            LoopMode::FailureLimit => self.active.max_failures.filter(|&max_fail| {
+                self.failure_count >= max_fail
+            }).map(|max_fail| format!("{max_fail} failed actions in a row")),
+        }
+    }
+
+    /// Whether the task has hit one of its limits.
+    pub fn should_stop(&self) -> bool {
+        self.stop_reason().is_some()
+    }
+
+    /// Counters so far, for status events.
+    pub fn usage(&self) -> serde_json::Value {
+        serde_json::json!({
+            "mode": self.active.mode,
+            "steps": self.steps,
+            "max_total_steps": self.active.budget.max_total_steps,
+            "vlm_calls": self.vlm_calls,
+            "max_vlm_calls": self.active.budget.max_vlm_calls,
+            "failures": self.failure_count,
+            "max_failures": self.active.max_failures,
+            "elapsed_secs": self.start_time.elapsed().as_secs(),
+            "max_duration_minutes": self.active.max_duration_minutes,
+        })
+    }
+}
+
+/// `limit` (0 = none) further capped by what is left of `parent_max` (0 = none)
+/// after `used`.
+fn cap(limit: u32, parent_max: u32, used: u32) -> u32 {
+    if parent_max == 0 {
+        return limit;
     }
+    let left = parent_max.saturating_sub(used).max(1);
+    if limit == 0 { left } else { limit.min(left) }
 }
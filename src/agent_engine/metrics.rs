@@ -0,0 +1,134 @@
+//! Step timing telemetry — where a task's time goes.
+//!
+//! Each [`Metric`] is emitted as an `agent_metrics` event (separate from the
+//! UI state events, so a dashboard can subscribe to just this) and appended to
+//! `SeeClaw/sessions/<session id>/metrics.jsonl`. Perception records carry a
+//! per-parser breakdown, so YOLO, UI Automation, model and action latency can
+//! be told apart.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::agent_engine::history::{current_session_id, data_dir_or_cwd, validate_session_id};
+use crate::errors::SeeClawResult;
+use crate::events::EventSink;
+
+const METRICS_FILE: &str = "metrics.jsonl";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Metric {
+    StepStarted {
+        index: usize,
+        description: String,
+    },
+    PerceptionDone {
+        ms: u64,
+        elements: usize,
+        /// `(stage, ms)`: every parser that ran, then `annotate` / `encode`.
+        parsers: Vec<(String, u64)>,
+    },
+    LlmCallDone {
+        ms: u64,
+        provider: String,
+        model: String,
+        ok: bool,
+        prompt_tokens: u64,
+        completion_tokens: u64,
+    },
+    ActionDone {
+        ms: u64,
+        action: String,
+        ok: bool,
+    },
+}
+
+/// Emit `metric` on the `agent_metrics` channel and persist it.
+pub fn record(app: &dyn EventSink, metric: Metric) {
+    let mut value = serde_json::to_value(&metric).unwrap_or_default();
+    value["ts"] = chrono::Utc::now().timestamp_millis().into();
+    append(&value);
+    app.emit_json("agent_metrics", value);
+}
+
+fn append(value: &Value) {
+    static FILE: OnceLock<Mutex<Option<std::fs::File>>> = OnceLock::new();
+    let file = FILE.get_or_init(|| {
+        let dir = session_dir(current_session_id());
+        let opened = std::fs::create_dir_all(&dir).and_then(|_| {
+            std::fs::OpenOptions::new().create(true).append(true).open(dir.join(METRICS_FILE))
+        });
+        Mutex::new(opened.map_err(|e| tracing::warn!(error = %e, "metrics: cannot open session file")).ok())
+    });
+    if let Some(file) = file.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+        if let Err(e) = writeln!(file, "{value}") {
+            tracing::warn!(error = %e, "metrics: write failed");
+        }
+    }
+}
+
+/// Time spent per kind over one session, for the `get_session_metrics` command.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MetricsTotals {
+    pub steps: u64,
+    pub perception_ms: u64,
+    pub perception_runs: u64,
+    pub llm_ms: u64,
+    pub llm_calls: u64,
+    pub action_ms: u64,
+    pub actions: u64,
+    /// Total ms per perception stage (parser, `annotate`, `encode`).
+    pub parser_ms: std::collections::BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionMetrics {
+    pub totals: MetricsTotals,
+    pub records: Vec<Value>,
+}
+
+/// Every metric recorded in one session, with totals.
+pub fn read(session_id: &str) -> SeeClawResult<SessionMetrics> {
+    validate_session_id(session_id)?;
+    let text = std::fs::read_to_string(session_dir(session_id).join(METRICS_FILE))?;
+    let records: Vec<Value> = text
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|l| serde_json::from_str(l).ok())
+        .collect();
+
+    let mut totals = MetricsTotals::default();
+    for r in &records {
+        let ms = r["ms"].as_u64().unwrap_or(0);
+        match r["kind"].as_str() {
+            Some("step_started") => totals.steps += 1,
+            Some("perception_done") => {
+                totals.perception_runs += 1;
+                totals.perception_ms += ms;
+                for p in r["parsers"].as_array().into_iter().flatten() {
+                    if let (Some(name), Some(ms)) = (p[0].as_str(), p[1].as_u64()) {
+                        *totals.parser_ms.entry(name.to_string()).or_default() += ms;
+                    }
+                }
+            }
+            Some("llm_call_done") => {
+                totals.llm_calls += 1;
+                totals.llm_ms += ms;
+            }
+            Some("action_done") => {
+                totals.actions += 1;
+                totals.action_ms += ms;
+            }
+            _ => {}
+        }
+    }
+    Ok(SessionMetrics { totals, records })
+}
+
+fn session_dir(session_id: &str) -> PathBuf {
+    data_dir_or_cwd("sessions").join(session_id)
+}
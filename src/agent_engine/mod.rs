@@ -1,10 +1,30 @@
+pub mod app_map;
+pub mod approvals;
+pub mod candidates;
+pub mod checkpoint;
+pub mod compaction;
+pub mod constraints;
 pub mod context;
+pub mod context_window;
+pub mod continuity;
+pub mod cooldown;
+pub mod event_bus;
 pub mod flow;
+pub mod focus;
 pub mod graph;
+pub mod grounding;
 pub mod history;
+pub mod hotkeys;
+pub mod lang;
 pub mod loop_control;
+pub mod metrics;
 pub mod node;
 pub mod nodes;
+pub mod replay;
+pub mod report;
 pub mod router;
 pub mod state;
+pub mod subtask;
+pub mod task_queue;
 pub mod tool_parser;
+pub mod undo;
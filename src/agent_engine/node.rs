@@ -8,29 +8,114 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use tokio::sync::Notify;
 
 use crate::agent_engine::context::NodeContext;
 use crate::agent_engine::state::SharedState;
+use crate::errors::SeeClawResult;
 
 // ── Shared cancellation utility ────────────────────────────────────────────
 
-/// Yields until the stop flag is set. Use inside `tokio::select!` in any node
-/// that needs cooperative cancellation.
+/// Stop request shared by the UI, the agent loop and every running node.
+///
+/// A flag for cheap synchronous checks plus a `Notify`, so waiters sleep until
+/// a stop actually happens instead of waking on a timer.
+#[derive(Debug, Default)]
+pub struct StopSignal {
+    flag: AtomicBool,
+    notify: Notify,
+}
+
+impl StopSignal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request a stop and wake everything waiting in [`wait_stop`].
+    pub fn set(&self) {
+        self.flag.store(true, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+
+    /// Clear the flag before the next task starts.
+    pub fn reset(&self) {
+        self.flag.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_set(&self) -> bool {
+        self.flag.load(Ordering::Relaxed)
+    }
+
+    /// Resolves once a stop has been requested.
+    pub async fn wait(&self) {
+        loop {
+            // Register interest before checking the flag, so a `set()` between
+            // the check and the await is not missed.
+            let notified = self.notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if self.is_set() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// Pause request from the UI. While set, the graph waits before starting its
+/// next node; the node in progress finishes first.
+#[derive(Debug, Default)]
+pub struct PauseSignal {
+    paused: AtomicBool,
+    notify: Notify,
+}
+
+impl PauseSignal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::SeqCst);
+    }
+
+    /// Clear the pause and wake the graph waiting in [`wait_resumed`](Self::wait_resumed).
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// Resolves once the pause is lifted (immediately when not paused).
+    pub async fn wait_resumed(&self) {
+        loop {
+            let notified = self.notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if !self.is_paused() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// Yields until a stop is requested. Use inside `tokio::select!` in any node
+/// that needs cooperative cancellation. LLM calls do not need it: set
+/// `cfg.cancel = state.cancel.clone()` and the provider aborts the request.
 ///
 /// ```rust
 /// use tokio::select;
 /// select! {
 ///     result = some_async_call() => { ... }
-///     _ = poll_stop(state.stop_flag.clone()) => return Ok(NodeOutput::End),
+///     _ = wait_stop(state.stop_flag.clone()) => return Ok(NodeOutput::End),
 /// }
 /// ```
-pub async fn poll_stop(flag: Arc<AtomicBool>) {
-    loop {
-        if flag.load(Ordering::Relaxed) {
-            return;
-        }
-        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
-    }
+pub async fn wait_stop(flag: Arc<StopSignal>) {
+    flag.wait().await;
 }
 
 // ── NodeOutput ─────────────────────────────────────────────────────────────
@@ -64,9 +149,11 @@ pub trait Node: Send + Sync {
     /// - Read / mutate `state` (shared mutable data).
     /// - Use `ctx` for immutable resources (registry, app handle, etc.).
     /// - Return `NodeOutput` to guide graph traversal.
+    /// - Return `Err` only for failures that abort the task; the error's
+    ///   category is forwarded to the frontend.
     async fn execute(
         &self,
         state: &mut SharedState,
         ctx: &NodeContext,
-    ) -> Result<NodeOutput, String>;
+    ) -> SeeClawResult<NodeOutput>;
 }
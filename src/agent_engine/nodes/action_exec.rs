@@ -2,21 +2,40 @@
 //!
 //! This is the central executor node. It delegates to `executor::input` for
 //! physical I/O and handles FinishTask / ReportFailure as terminal states.
+//!
+//! Keyboard actions followed by more queued keyboard calls from the same
+//! planner message run as one input batch (`[executor] batch_keyboard_actions`);
+//! each call still gets its own tool result.
 
 use async_trait::async_trait;
 use base64::Engine as _;
-use tauri::Emitter;
-use tokio::process::Command;
 
+use crate::agent_engine::constraints;
 use crate::agent_engine::context::NodeContext;
+use crate::agent_engine::event_bus::AgentMessage;
+use crate::agent_engine::metrics::{self, Metric};
+use crate::agent_engine::grounding::{grounding_confidence, percent_to_desktop, GroundedPoint, GroundingRequest};
 use crate::agent_engine::history::HistoryEntry;
-use crate::agent_engine::node::{poll_stop, Node, NodeOutput};
-use crate::agent_engine::state::{AgentAction, GraphResult, SharedState};
-use crate::agent_engine::tool_parser::{is_auto_approved, needs_stability_wait, parse_action_by_name};
-use crate::executor::input;
+use crate::agent_engine::lang::GoalLanguage;
+use crate::agent_engine::node::{wait_stop, Node, NodeOutput};
+use crate::agent_engine::state::{AgentAction, GraphResult, SharedState, StepMode};
+use crate::agent_engine::tool_parser::{needs_stability_wait, parse_tool_call_to_action};
+use crate::agent_engine::subtask;
+use crate::agent_engine::undo;
+use crate::config::ApprovalMode;
+use crate::errors::{ErrorCategory, SeeClawError, SeeClawResult};
+use crate::executor::input::KeyStroke;
+use crate::executor::{calibration, clipboard, fs, input, keyboard_nav, launcher, safety, shell};
 use crate::llm::types::{ChatMessage, MessageContent, StreamChunk, StreamChunkKind};
-use crate::perception::screenshot::capture_primary;
-use crate::perception::som_grid::{col_label, draw_som_grid, grid_cell_to_physical, parse_grid_label};
+use crate::mcp::manager::tool_result_text;
+use crate::perception::exclusion;
+use crate::perception::screenshot::{capture, data_url, list_monitors, MonitorTarget};
+use crate::perception::som_grid::col_label;
+use crate::perception::stability::frame_hash;
+use crate::skills::SkillExecutor;
+
+/// Clipboard text returned by `clipboard_read` is cut to this many chars.
+const CLIPBOARD_RESULT_CHARS: usize = 4000;
 
 pub struct ActionExecNode;
 
@@ -36,7 +55,7 @@ impl Node for ActionExecNode {
         &self,
         state: &mut SharedState,
         ctx: &NodeContext,
-    ) -> Result<NodeOutput, String> {
+    ) -> SeeClawResult<NodeOutput> {
         if state.is_stopped() {
             return Ok(NodeOutput::End);
         }
@@ -51,25 +70,82 @@ impl Node for ActionExecNode {
             }
         };
 
-        // Safety check: route to user_confirm only if the action is not
-        // auto-approved AND the user hasn't already approved it this round.
+        // Safety check: route to user_confirm only if the safety config
+        // requires approval AND the user hasn't already approved it this round.
         // `action_user_approved` is set by UserConfirmNode after approval and
         // cleared here, preventing an infinite user_confirm ↔ action_exec loop.
-        if !is_auto_approved(&action) && !state.action_user_approved {
-            state.needs_approval = true;
-            state.current_action = Some(action);
-            return Ok(NodeOutput::GoTo("user_confirm".to_string()));
+        if !state.action_user_approved {
+            let low_confidence = low_confidence_click(&action, state, ctx);
+            if safety::requires_approval(&action, &ctx.safety) || low_confidence.is_some() {
+                state.approval_confidence = low_confidence;
+                state.needs_approval = true;
+                state.current_action = Some(action);
+                return Ok(NodeOutput::GoTo("user_confirm".to_string()));
+            }
         }
         // Consume the approval flag so the next action goes through approval again.
         state.action_user_approved = false;
+        state.action_certainty = None;
+        state.approval_confidence = None;
+
+        capture_pre_action_frame(&action, state, ctx).await;
+        if let Some(rest) = take_keyboard_batch(&action, state, ctx) {
+            return self.execute_keyboard_batch(action, rest, state, ctx).await;
+        }
 
         // Emit activity
-        let activity_label = action_activity_label(&action);
+        let activity_label = action_activity_label(&action, state.lang);
         let _ = ctx.app.emit("agent_activity", serde_json::json!({ "text": activity_label }));
 
         tracing::info!(?action, step = state.current_step_idx, "ActionExecNode: executing");
 
-        let (ok, msg) = execute_action_impl(&action, state, ctx).await;
+        let started = std::time::Instant::now();
+        let outcome = match &action {
+            AgentAction::InvokeSkill { skill_name, inputs } => {
+                match SkillExecutor::new(&ctx.skill_registry).run(skill_name, inputs, state, ctx).await {
+                    Some(run) => {
+                        let lines = run.log_lines(state.current_step_idx + 1);
+                        state.steps_log.extend(lines);
+                        run.into_result()
+                    }
+                    None => {
+                        tracing::warn!(skill = %skill_name, "invoke_skill: no combo found in registry");
+                        Err(SeeClawError::Skills(format!("skill '{skill_name}' not found in registry")))
+                    }
+                }
+            }
+            AgentAction::SelectMonitor { target } => select_monitor(*target, state).await,
+            AgentAction::DelegateSubtask { goal, max_steps } => subtask::run(goal, *max_steps, state, ctx).await,
+            _ => execute_action_impl(&action, state, ctx).await,
+        };
+        state.refined_click = None;
+        let category = outcome.as_ref().err().map(|e| e.category());
+        let (ok, msg) = match outcome {
+            Ok(msg) => (true, msg),
+            Err(e) => (false, e.to_string()),
+        };
+        metrics::record(&*ctx.app, Metric::ActionDone {
+            ms: started.elapsed().as_millis() as u64,
+            action: action_kind_tag(&action).to_string(),
+            ok,
+        });
+
+        // Element cooldown: failed clicks count now; successful ones are checked
+        // for a visible screen change in StabilityNode.
+        if let Some(element_id) = click_target(&action) {
+            match category {
+                None => {
+                    state.pending_click_target = Some(element_id.to_string());
+                    state.tracked_click = Some(element_id.to_string());
+                }
+                Some(ErrorCategory::GroundingFailed | ErrorCategory::ExecutorFailed) => {
+                    if state.element_cooldown.record_failure(element_id, &state.detected_elements) {
+                        state.steps_log.push(format!("[cooldown] {element_id} unreachable after repeated failed clicks"));
+                    }
+                }
+                Some(_) => {}
+            }
+        }
 
         // Handle terminal actions
         match &action {
@@ -88,13 +164,16 @@ impl Node for ActionExecNode {
             AgentAction::ReportFailure { reason, .. } => {
                 let _ = ctx.app.emit("llm_stream_chunk", &StreamChunk {
                     kind: StreamChunkKind::Content,
-                    content: format!("Task failed: {reason}"),
+                    content: state.lang.pick(format!("任务失败：{reason}"), format!("Task failed: {reason}")),
                 });
                 let _ = ctx.app.emit("llm_stream_chunk", &StreamChunk {
                     kind: StreamChunkKind::Done,
                     content: String::new(),
                 });
-                state.result = Some(GraphResult::Error { message: reason.clone() });
+                state.result = Some(GraphResult::Error {
+                    message: reason.clone(),
+                    category: ErrorCategory::TaskFailed,
+                });
                 return Ok(NodeOutput::End);
             }
             AgentAction::GetViewport { .. } => {
@@ -104,6 +183,18 @@ impl Node for ActionExecNode {
             _ => {}
         }
 
+        let _ = ctx.app.emit("action_result", serde_json::json!({
+            "action": action_kind_tag(&action),
+            "ok": ok,
+            "message": truncate_str(&msg, 300),
+            "category": category,
+        }));
+        ctx.bus.publish(AgentMessage::ActionCompleted {
+            action: action_kind_tag(&action).to_string(),
+            ok,
+            error: (!ok).then(|| msg.clone()),
+        });
+
         // Push tool result to conversation
         state.conv_messages.push(ChatMessage {
             role: "tool".into(),
@@ -130,22 +221,43 @@ impl Node for ActionExecNode {
             state.step_action_history.push(history_entry);
         }
 
-        // Record in history
+        // Record in history, with the screen the action was taken on
         {
             let mut history = ctx.history.lock().await;
+            let ts = chrono::Utc::now().timestamp_millis();
+            let marker = click_point(&msg)
+                .zip(state.last_meta.as_ref())
+                .map(|((x, y), meta)| meta.desktop_to_normalized(x as f64, y as f64));
+            let screenshot = state
+                .pre_action_frame
+                .as_deref()
+                .and_then(|f| history.save_thumbnail(ts, f, marker));
             history.push(HistoryEntry {
-                ts: chrono::Utc::now().timestamp_millis(),
+                ts,
                 role: "tool".into(),
-                content: None,
+                content: Some(msg.clone()),
                 action: Some(serde_json::to_value(&action).unwrap_or_default()),
+                ok: Some(ok),
+                screenshot,
+                reasoning: Some(std::mem::take(&mut state.last_reasoning)).filter(|r| !r.is_empty()),
             });
             let _ = history.flush();
         }
 
-        if !ok {
+        {
             let mut ctrl = ctx.loop_ctrl.lock().await;
-            ctrl.record_failure();
+            ctrl.record_steps(1);
+            if ok {
+                ctrl.record_success();
+            } else {
+                ctrl.record_failure();
+            }
         }
+        if !ok {
+            state.skip_queued_tool_calls("an earlier call in this turn failed.");
+        }
+
+        let clicked_at = click_point(&msg);
 
         // Log step result
         let step_desc = state
@@ -162,6 +274,12 @@ impl Node for ActionExecNode {
 
         // Determine if stability wait is needed
         state.needs_stability = needs_stability_wait(&action) && ok;
+        state.pending_click_check = None;
+        if state.needs_stability && ctx.perception_cfg.verify_clicks && state.current_loop_mode == StepMode::Vlm {
+            if let (AgentAction::MouseClick { element_id }, Some((x, y))) = (&action, clicked_at) {
+                state.pending_click_check = Some((element_id.clone(), GroundedPoint { x, y }));
+            }
+        }
 
         // Route to step_evaluate for loop control (replaces direct step_advance routing)
         Ok(NodeOutput::Continue)
@@ -169,45 +287,157 @@ impl Node for ActionExecNode {
 }
 
 impl ActionExecNode {
+    /// Run `first` and the queued keyboard calls in `rest` as one input batch.
+    /// Results, history and the step log are recorded per call, as if each
+    /// had gone through the graph on its own.
+    async fn execute_keyboard_batch(
+        &self,
+        first: AgentAction,
+        rest: Vec<(String, AgentAction)>,
+        state: &mut SharedState,
+        ctx: &NodeContext,
+    ) -> SeeClawResult<NodeOutput> {
+        let mut calls = vec![(state.pending_tool_id.clone(), first)];
+        calls.extend(rest);
+        let actions: Vec<AgentAction> = calls.iter().map(|(_, a)| a.clone()).collect();
+
+        let n = calls.len();
+        let _ = ctx.app.emit("agent_activity", serde_json::json!({
+            "text": state.lang.pick(format!("正在连续执行 {n} 个键盘操作…"), format!("Running {n} keyboard steps…"))
+        }));
+        tracing::info!(steps = n, step = state.current_step_idx, "ActionExecNode: executing keyboard batch");
+
+        let started = std::time::Instant::now();
+        let results = run_keyboard_batch(&actions, state, ctx).await;
+        let batch_ms = started.elapsed().as_millis() as u64;
+        let ok = results.len() == n && results.iter().all(|r| r.is_ok());
+        let category = results.iter().find_map(|r| r.as_ref().err()).map(|e| e.category());
+
+        let mut lines = Vec::with_capacity(n);
+        {
+            let mut history = ctx.history.lock().await;
+            for (i, (tool_id, action)) in calls.iter().enumerate() {
+                let (step_ok, msg) = match results.get(i) {
+                    Some(Ok(msg)) => (true, msg.clone()),
+                    Some(Err(e)) => (false, e.to_string()),
+                    None => (false, "Skipped: an earlier call in this turn failed.".to_string()),
+                };
+                state.conv_messages.push(ChatMessage {
+                    role: "tool".into(),
+                    content: MessageContent::Text(msg.clone()),
+                    tool_call_id: Some(tool_id.clone()),
+                    tool_calls: None,
+                });
+                let label = compact_action_label(action);
+                state.step_action_history.push(if step_ok {
+                    format!("iter {}: {} → {}", state.step_iterations, label, truncate_str(&msg, 60))
+                } else {
+                    format!("iter {}: {} → FAILED: {}", state.step_iterations, label, truncate_str(&msg, 60))
+                });
+                if i < results.len() {
+                    history.push(HistoryEntry {
+                        ts: chrono::Utc::now().timestamp_millis(),
+                        role: "tool".into(),
+                        content: Some(msg.clone()),
+                        action: Some(serde_json::to_value(action).unwrap_or_default()),
+                        ok: Some(step_ok),
+                        screenshot: None,
+                        reasoning: Some(std::mem::take(&mut state.last_reasoning)).filter(|r| !r.is_empty()),
+                    });
+                }
+                lines.push(if step_ok { msg } else { format!("FAILED: {msg}") });
+            }
+            let _ = history.flush();
+        }
+
+        let summary = lines.join("; ");
+        let _ = ctx.app.emit("action_result", serde_json::json!({
+            "action": "keyboard_batch",
+            "steps": n,
+            "ok": ok,
+            "message": truncate_str(&summary, 300),
+            "category": category,
+        }));
+        metrics::record(&*ctx.app, Metric::ActionDone { ms: batch_ms, action: "keyboard_batch".to_string(), ok });
+        ctx.bus.publish(AgentMessage::ActionCompleted {
+            action: "keyboard_batch".to_string(),
+            ok,
+            error: (!ok).then(|| summary.clone()),
+        });
+
+        state.last_exec_result = summary.clone();
+        state.last_action_succeeded = ok;
+        let last_attempted = results.len().clamp(1, n) - 1;
+        state.last_action_kind = action_kind_tag(&calls[last_attempted].1).to_string();
+
+        {
+            let mut ctrl = ctx.loop_ctrl.lock().await;
+            ctrl.record_steps(results.len() as u32);
+            if ok {
+                ctrl.record_success();
+            } else {
+                ctrl.record_failure();
+            }
+        }
+        if !ok {
+            state.skip_queued_tool_calls("an earlier call in this turn failed.");
+        }
+
+        let step_desc = state
+            .todo_steps
+            .get(state.current_step_idx)
+            .map(|s| s.description.clone())
+            .unwrap_or_else(|| format!("step {}", state.current_step_idx));
+        state.steps_log.push(format!("Step {}: {} - {}", state.current_step_idx + 1, step_desc, summary));
+
+        state.needs_stability = ok;
+        Ok(NodeOutput::Continue)
+    }
+
     /// Handle GetViewport: capture screenshot, inject into conversation, go to planner.
     async fn handle_get_viewport(
         &self,
         state: &mut SharedState,
         ctx: &NodeContext,
-    ) -> Result<NodeOutput, String> {
+    ) -> SeeClawResult<NodeOutput> {
         tracing::warn!("get_viewport called directly — capturing and injecting into conversation");
-        let shot = capture_primary().await.map_err(|e| e.to_string())?;
+        let shot = capture(state.monitor).await?;
         state.last_meta = Some(shot.meta.clone());
+        state.last_frame_hash = Some(frame_hash(&shot.image_bytes));
 
         let (b64, source_desc) = {
-            let mut detector = ctx.yolo_detector.lock().await;
-            let mut elements = if let Some(ref mut det) = *detector {
-                det.detect(&shot.image_bytes).unwrap_or_default()
-            } else {
-                Vec::new()
-            };
-
-            if ctx.perception_cfg.enable_ui_automation {
-                if let Ok(uia) = crate::perception::ui_automation::collect_ui_elements(&shot.meta).await {
-                    crate::perception::ui_automation::merge_detections(&mut elements, uia, 0.3);
+            let started = std::time::Instant::now();
+            let perceived = ctx
+                .perception
+                .run_tracked(&shot.image_bytes, &shot.meta, &mut state.element_tracker)
+                .await;
+            // Perception is best-effort here: without it the raw screenshot still helps.
+            let (image_base64, elements) = match perceived {
+                Ok(perceived) => {
+                    metrics::record(&*ctx.app, Metric::PerceptionDone {
+                        ms: started.elapsed().as_millis() as u64,
+                        elements: perceived.elements.len(),
+                        parsers: perceived.timings.clone(),
+                    });
+                    (perceived.image_base64, perceived.elements)
                 }
-            }
+                Err(e) => {
+                    tracing::warn!(error = %e, "perception failed — sending the raw screenshot");
+                    (None, Vec::new())
+                }
+            };
+            let b64 = image_base64
+                .unwrap_or_else(|| base64::engine::general_purpose::STANDARD.encode(&shot.image_bytes));
 
             if !elements.is_empty() {
-                state.detected_elements = elements.clone();
-                let annotated = crate::perception::annotator::annotate_image(&shot.image_bytes, &elements)
-                    .unwrap_or(shot.image_bytes.clone());
-                let b64 = base64::engine::general_purpose::STANDARD.encode(&annotated);
                 let desc = format!(
                     "Screenshot captured with {} annotated UI elements.",
                     elements.len()
                 );
+                state.detected_elements = elements;
                 (b64, desc)
             } else {
                 state.detected_elements.clear();
-                let grid = draw_som_grid(&shot.image_bytes, ctx.grid_n)
-                    .unwrap_or(shot.image_bytes.clone());
-                let b64 = base64::engine::general_purpose::STANDARD.encode(&grid);
                 let last_col = col_label(ctx.grid_n - 1);
                 let desc = format!(
                     "Screenshot captured. Grid: {n}x{n}, columns A-{last}.",
@@ -217,13 +447,15 @@ impl ActionExecNode {
             }
         };
 
-        let data_url = format!("data:image/png;base64,{b64}");
+        let data_url = data_url(&b64);
         state.conv_messages.push(ChatMessage {
             role: "tool".into(),
             content: MessageContent::Text(source_desc),
             tool_call_id: Some(state.pending_tool_id.clone()),
             tool_calls: None,
         });
+        // Tool results must directly follow their call, before the screenshot.
+        state.skip_queued_tool_calls("the screen was captured; choose the next actions from the new screenshot.");
         state.conv_messages.push(ChatMessage {
             role: "user".into(),
             content: MessageContent::Parts(vec![
@@ -245,37 +477,187 @@ impl ActionExecNode {
     }
 }
 
+/// The keystroke an action performs, with its result message, or `None` for
+/// anything other than type_text / hotkey / key_press.
+fn keystroke_of(action: &AgentAction, ctx: &NodeContext) -> Option<(KeyStroke, String)> {
+    match action {
+        AgentAction::TypeText { text, clear_first } => Some((
+            KeyStroke::Text(text.clone(), clear_first.then_some(ctx.executor.clear_strategy)),
+            format!("Typed: {text}"),
+        )),
+        AgentAction::Hotkey { keys } => Some((KeyStroke::Keys(keys.clone()), format!("Hotkey: {keys}"))),
+        AgentAction::KeyPress { key } => Some((KeyStroke::Keys(key.clone()), format!("KeyPress: {key}"))),
+        _ => None,
+    }
+}
+
+/// Whether `action` may join a keyboard batch.
+pub(crate) fn is_batchable(action: &AgentAction, ctx: &NodeContext) -> bool {
+    ctx.executor.batch_keyboard_actions
+        && !safety::requires_approval(action, &ctx.safety)
+        && keystroke_of(action, ctx).is_some()
+        && constraints::check_action(action, &ctx.preferences).is_ok()
+}
+
+/// Pop the queued tool calls that can run in one batch with `action`.
+/// `None` when batching is off or nothing queued qualifies.
+fn take_keyboard_batch(
+    action: &AgentAction,
+    state: &mut SharedState,
+    ctx: &NodeContext,
+) -> Option<Vec<(String, AgentAction)>> {
+    if !is_batchable(action, ctx) {
+        return None;
+    }
+    let mut rest = Vec::new();
+    while let Some(tc) = state.queued_tool_calls.front() {
+        match parse_tool_call_to_action(tc) {
+            Ok(next) if is_batchable(&next, ctx) => {
+                let tc = state.queued_tool_calls.pop_front().expect("front exists");
+                rest.push((tc.id, next));
+            }
+            _ => break,
+        }
+    }
+    (!rest.is_empty()).then_some(rest)
+}
+
+/// Run keyboard actions back to back on one input connection. Returns one
+/// result per action attempted; stops after the first failure.
+pub(crate) async fn run_keyboard_batch(
+    actions: &[AgentAction],
+    state: &SharedState,
+    ctx: &NodeContext,
+) -> Vec<SeeClawResult<String>> {
+    let mut strokes = Vec::with_capacity(actions.len());
+    let mut messages = Vec::with_capacity(actions.len());
+    let mut undo_ops = Vec::with_capacity(actions.len());
+    let mut denied = None;
+    for action in actions {
+        if let Err(reason) = constraints::check_action(action, &ctx.preferences) {
+            tracing::warn!(?action, reason = %reason, "action blocked by user constraint");
+            denied = Some(SeeClawError::ExecutorDenied(reason));
+            break;
+        }
+        let Some((stroke, msg)) = keystroke_of(action, ctx) else {
+            denied = Some(SeeClawError::Executor("not a keyboard action".into()));
+            break;
+        };
+        strokes.push(stroke);
+        messages.push(msg);
+        undo_ops.push(undo::prepare(action).await);
+    }
+
+    let mut results: Vec<SeeClawResult<String>> = Vec::with_capacity(actions.len());
+    if !strokes.is_empty() {
+        match input::run_keystrokes(strokes, ctx.executor.keystroke_gap_ms, state.stop_flag.clone()).await {
+            Ok(outcomes) => {
+                for (op, outcome) in undo_ops.into_iter().zip(&outcomes) {
+                    if let (Some(op), Ok(())) = (op, outcome) {
+                        undo::record(op);
+                    }
+                }
+                results.extend(outcomes.into_iter().zip(messages).map(|(r, msg)| r.map(|()| msg)))
+            }
+            Err(e) => return vec![Err(e)],
+        }
+    }
+    if let Some(e) = denied {
+        if results.iter().all(|r| r.is_ok()) {
+            results.push(Err(e));
+        }
+    }
+    results
+}
+
 /// Execute the actual I/O for an action.
-async fn execute_action_impl(
+/// Successful reversible actions are added to the undo journal.
+pub(crate) async fn execute_action_impl(
     action: &AgentAction,
     state: &SharedState,
     ctx: &NodeContext,
-) -> (bool, String) {
+) -> SeeClawResult<String> {
+    if let Err(reason) = constraints::check_action(action, &ctx.preferences) {
+        tracing::warn!(?action, reason = %reason, "action blocked by user constraint");
+        return Err(SeeClawError::ExecutorDenied(reason));
+    }
+    let undo_op = undo::prepare(action).await;
+    let result = execute_action_io(action, state, ctx).await;
+    if let (Ok(_), Some(op)) = (&result, undo_op) {
+        undo::record(op);
+    }
+    result
+}
+
+async fn execute_action_io(
+    action: &AgentAction,
+    state: &SharedState,
+    ctx: &NodeContext,
+) -> SeeClawResult<String> {
     match action {
         AgentAction::MouseClick { element_id }
         | AgentAction::MouseDoubleClick { element_id }
         | AgentAction::MouseRightClick { element_id } => {
             let is_double = matches!(action, AgentAction::MouseDoubleClick { .. });
             let is_right = matches!(action, AgentAction::MouseRightClick { .. });
+            if state.element_cooldown.is_cooling(element_id, &state.detected_elements) {
+                // Grounding keeps missing this element — if we know its label,
+                // try focusing it with the keyboard instead of clicking again.
+                // Activation is left to the model once it sees what got focus.
+                let label = (!is_right)
+                    .then(|| state.detected_elements.iter().find(|e| e.id.eq_ignore_ascii_case(element_id)))
+                    .flatten()
+                    .and_then(|e| e.content.as_deref())
+                    .filter(|c| !c.trim().is_empty());
+                let fallback = match label {
+                    Some(label) => {
+                        match keyboard_nav::navigate_to(label, "tab", 30, false, state.stop_flag.clone()).await {
+                            Ok(nav) => {
+                                return Ok(format!(
+                                    "{element_id} was not clicked; keyboard focus moved to '{}' after {} Tab presses. \
+                                     Press Enter with key_press if that is the intended control.",
+                                    nav.focused, nav.presses
+                                ))
+                            }
+                            Err(SeeClawError::Cancelled) => return Err(SeeClawError::Cancelled),
+                            Err(e) => format!(" Keyboard fallback failed: {e}."),
+                        }
+                    }
+                    None => String::new(),
+                };
+                return Err(SeeClawError::ExecutorDenied(format!(
+                    "{element_id} is unreachable (repeated clicks failed).{fallback} Use keyboard_navigate or a menu route instead."
+                )));
+            }
             if let Some(meta) = &state.last_meta {
-                let coords = state
-                    .detected_elements
-                    .iter()
-                    .find(|e| e.id == *element_id)
-                    .map(|elem| elem.center_physical(meta));
-                let coords = coords.or_else(|| {
-                    parse_grid_label(element_id).map(|(col, row)| {
-                        grid_cell_to_physical(
-                            col,
-                            row,
-                            meta.physical_width,
-                            meta.physical_height,
-                            ctx.grid_n,
-                        )
+                let refined = state
+                    .refined_click
+                    .as_ref()
+                    .filter(|(id, _)| id == element_id)
+                    .map(|(_, point)| *point);
+                let coords = refined.or_else(|| {
+                    ctx.grounding.resolve(&GroundingRequest {
+                        element_id,
+                        elements: &state.detected_elements,
+                        meta,
+                        grid_n: ctx.grid_n,
                     })
                 });
+                if let Some(rec) = ctx.registry.lock().await.recorder() {
+                    rec.record_choice("grounding", serde_json::json!({
+                        "strategy": if refined.is_some() { "focus_crop" } else { ctx.grounding.name() },
+                        "element_id": element_id,
+                        "point": coords.as_ref().map(|p| [p.x, p.y]),
+                    }));
+                }
 
-                if let Some((px, py)) = coords {
+                if let Some(GroundedPoint { x: px, y: py }) = coords.filter(|p| exclusion::contains_point(p.x, p.y)) {
+                    tracing::warn!(element_id = %element_id, x = px, y = py, "click target inside exclusion zone — refused");
+                    Err(SeeClawError::ExecutorDenied(format!(
+                        "{element_id} is inside a user-defined exclusion zone. Choose another target."
+                    )))
+                } else if let Some(GroundedPoint { x: px, y: py }) = coords {
+                    calibration::ensure(meta).await;
                     let result = if is_right {
                         input::mouse_right_click(px, py).await
                     } else if is_double {
@@ -283,199 +665,320 @@ async fn execute_action_impl(
                     } else {
                         input::mouse_click(px, py).await
                     };
-                    match result {
-                        Ok(()) => (true, format!("Clicked {element_id} at ({px},{py})")),
-                        Err(e) => (false, format!("Click failed: {e}")),
-                    }
+                    result?;
+                    Ok(format!("Clicked {element_id} at ({px},{py})"))
                 } else {
-                    (false, format!("Cannot resolve element: {element_id}"))
+                    Err(SeeClawError::GroundingFailed(format!("cannot resolve element: {element_id}")))
                 }
             } else {
-                (false, "No viewport — call get_viewport first".into())
+                Err(SeeClawError::GroundingFailed("no viewport — call get_viewport first".into()))
             }
         }
-        AgentAction::TypeText { text, clear_first } => {
-            match input::type_text(text.clone(), *clear_first).await {
-                Ok(()) => (true, format!("Typed: {text}")),
-                Err(e) => (false, format!("TypeText failed: {e}")),
+        AgentAction::MouseClickAt { x_pct, y_pct } => {
+            let Some(meta) = &state.last_meta else {
+                return Err(SeeClawError::GroundingFailed("no viewport — call get_viewport first".into()));
+            };
+            let GroundedPoint { x: px, y: py } = percent_to_desktop(*x_pct, *y_pct, meta).ok_or_else(|| {
+                SeeClawError::GroundingFailed(format!("({x_pct}%, {y_pct}%) is outside the screenshot"))
+            })?;
+            if exclusion::contains_point(px, py) {
+                tracing::warn!(x = px, y = py, "click point inside exclusion zone — refused");
+                return Err(SeeClawError::ExecutorDenied(
+                    "that point is inside a user-defined exclusion zone. Choose another target.".into(),
+                ));
             }
+            calibration::ensure(meta).await;
+            input::mouse_click(px, py).await?;
+            Ok(format!("Clicked ({x_pct:.1}%,{y_pct:.1}%) at ({px},{py})"))
+        }
+        AgentAction::TypeText { text, clear_first } => {
+            input::type_text(text.clone(), clear_first.then_some(ctx.executor.clear_strategy)).await?;
+            Ok(format!("Typed: {text}"))
+        }
+        AgentAction::Hotkey { keys } => {
+            input::press_hotkey(keys.clone()).await?;
+            Ok(format!("Hotkey: {keys}"))
+        }
+        AgentAction::KeyPress { key } => {
+            input::press_hotkey(key.clone()).await?;
+            Ok(format!("KeyPress: {key}"))
+        }
+        AgentAction::KeyboardNavigate { target, key, max_presses } => {
+            let nav = keyboard_nav::navigate_to(target, key, *max_presses, true, state.stop_flag.clone()).await?;
+            Ok(format!(
+                "Focused '{}' after {} {key} presses and pressed Enter",
+                nav.focused, nav.presses
+            ))
         }
-        AgentAction::Hotkey { keys } => match input::press_hotkey(keys.clone()).await {
-            Ok(()) => (true, format!("Hotkey: {keys}")),
-            Err(e) => (false, format!("Hotkey failed: {e}")),
-        },
-        AgentAction::KeyPress { key } => match input::press_hotkey(key.clone()).await {
-            Ok(()) => (true, format!("KeyPress: {key}")),
-            Err(e) => (false, format!("KeyPress failed: {e}")),
-        },
         AgentAction::Wait { milliseconds } => {
             let flag = state.stop_flag.clone();
             tokio::select! {
                 _ = tokio::time::sleep(std::time::Duration::from_millis(*milliseconds as u64)) => {}
-                _ = poll_stop(flag) => {
-                    return (false, "Stopped by user".into());
-                }
+                _ = wait_stop(flag) => return Err(SeeClawError::Cancelled),
             }
-            (true, format!("Waited {milliseconds}ms"))
-        }
-        AgentAction::ExecuteTerminal { command, reason } => {
-            tracing::info!(%command, %reason, "executing terminal command");
-            match Command::new("powershell")
-                .arg("-NoProfile")
-                .arg("-Command")
-                .arg(command)
-                .kill_on_drop(true)
-                .stdout(std::process::Stdio::piped())
-                .stderr(std::process::Stdio::piped())
-                .spawn()
-            {
-                Ok(child) => {
-                    let flag = state.stop_flag.clone();
-                    let output = tokio::select! {
-                        result = child.wait_with_output() => result,
-                        _ = poll_stop(flag) => {
-                            return (false, "Stopped by user".into());
-                        }
-                    };
-                    match output {
-                        Ok(out) => {
-                            let mut buf = String::new();
-                            if !out.stdout.is_empty() {
-                                buf.push_str(&String::from_utf8_lossy(&out.stdout));
-                            }
-                            if !out.stderr.is_empty() {
-                                if !buf.is_empty() {
-                                    buf.push_str("\n--- STDERR ---\n");
-                                }
-                                buf.push_str(&String::from_utf8_lossy(&out.stderr));
-                            }
-                            let truncated = if buf.len() > 4000 {
-                                format!("{}\n[truncated]", &buf[..4000])
-                            } else {
-                                buf
-                            };
-                            let ok = out.status.success();
-                            (ok, format!("command: {command}\noutput:\n{truncated}"))
-                        }
-                        Err(e) => (false, format!("wait failed: {e}")),
-                    }
+            Ok(format!("Waited {milliseconds}ms"))
+        }
+        AgentAction::ExecuteTerminal { command, reason, cwd, env, timeout_secs } => {
+            let verdict = safety::classify_command(command, cwd.as_deref(), env, &ctx.safety);
+            if verdict.risk == safety::CommandRisk::Blocked {
+                tracing::warn!(%command, reason = %verdict.reason, "terminal command blocked");
+                return Err(SeeClawError::ExecutorDenied(format!("command refused: {}", verdict.reason)));
+            }
+            tracing::info!(%command, %reason, ?cwd, risk = ?verdict.risk, "executing terminal command");
+            let timeout = timeout_secs.unwrap_or(ctx.executor.terminal_timeout_secs).max(1);
+            let app = ctx.app.clone();
+            let out = shell::run(
+                ctx.executor.shell,
+                command,
+                shell::RunOptions {
+                    cwd: cwd.as_deref(),
+                    env: Some(env),
+                    timeout: std::time::Duration::from_secs(timeout),
+                },
+                state.stop_flag.clone(),
+                |stream, line| {
+                    let _ = app.emit("terminal_output", serde_json::json!({ "stream": stream, "line": line }));
+                },
+            )
+            .await?;
+            let mut buf = out.stdout.clone();
+            if !out.stderr.is_empty() {
+                if !buf.is_empty() {
+                    buf.push_str("\n--- STDERR ---\n");
                 }
-                Err(e) => (false, format!("spawn failed: {e}")),
+                buf.push_str(&out.stderr);
+            }
+            let truncated = if buf.chars().count() > 4000 {
+                format!("{}\n[truncated]", buf.chars().take(4000).collect::<String>())
+            } else {
+                buf
+            };
+            let status = match out.exit_code {
+                _ if out.timed_out => format!("timed out after {timeout}s (process killed)"),
+                Some(code) => format!("exit code {code}"),
+                None => "terminated by signal".to_string(),
+            };
+            let report = format!("shell: {}\ncommand: {command}\n{status}\noutput:\n{truncated}", out.shell.name());
+            if out.success() {
+                Ok(report)
+            } else {
+                Err(SeeClawError::Executor(report))
             }
         }
-        AgentAction::Scroll { direction, distance, element_id: _ } => {
-            // Scroll is auto-approved; here we just handle the basic case
-            (true, format!("Scrolled {direction} ({distance})"))
-        }
-        AgentAction::InvokeSkill { skill_name, inputs } => {
-            // Fallback: if invoke_skill reaches action_exec (LLM used invoke_skill
-            // instead of combo mode), expand the combo here and execute inline.
-            tracing::info!(
-                skill = %skill_name,
-                "ActionExecNode: expanding invoke_skill as inline combo"
-            );
-            match ctx.skill_registry.expand_combo(skill_name, inputs) {
-                Some(combo_steps) => {
-                    let total = combo_steps.len();
-                    for (i, combo_step) in combo_steps.iter().enumerate() {
-                        if state.is_stopped() {
-                            return (false, "Stopped by user".into());
-                        }
-                        let sub_action = match parse_action_by_name(&combo_step.action, &combo_step.args) {
-                            Ok(a) => a,
-                            Err(e) => {
-                                tracing::warn!(combo_step = i, error = %e, "invoke_skill: failed to parse combo step — skipping");
-                                continue;
-                            }
-                        };
-                        match &sub_action {
-                            AgentAction::Wait { milliseconds } => {
-                                let flag = state.stop_flag.clone();
-                                let ms = *milliseconds;
-                                tokio::select! {
-                                    _ = tokio::time::sleep(std::time::Duration::from_millis(ms as u64)) => {}
-                                    _ = poll_stop(flag) => return (false, "Stopped by user".into()),
-                                }
-                            }
-                            AgentAction::Hotkey { keys } => {
-                                if let Err(e) = input::press_hotkey(keys.clone()).await {
-                                    tracing::warn!(error = %e, "invoke_skill: hotkey failed");
-                                }
-                            }
-                            AgentAction::KeyPress { key } => {
-                                if let Err(e) = input::press_hotkey(key.clone()).await {
-                                    tracing::warn!(error = %e, "invoke_skill: key_press failed");
-                                }
-                            }
-                            AgentAction::TypeText { text, clear_first } => {
-                                if *clear_first {
-                                    let _ = input::press_hotkey("ctrl+a".to_string()).await;
-                                    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
-                                }
-                                if let Err(e) = input::type_text(text.clone(), *clear_first).await {
-                                    tracing::warn!(error = %e, "invoke_skill: type_text failed");
-                                }
-                            }
-                            other => {
-                                tracing::warn!(action = ?other, "invoke_skill: unsupported action in combo — skipping");
-                            }
-                        }
+        AgentAction::OpenApp { name_or_path, args } => launcher::open_app(name_or_path.clone(), args.clone()).await,
+        AgentAction::ReadFile { path } => fs::read_file(path, &ctx.safety).await,
+        AgentAction::WriteFile { path, content, append } => fs::write_file(path, content, *append, &ctx.safety).await,
+        AgentAction::ListDir { path } => fs::list_dir(path, &ctx.safety).await,
+        AgentAction::MoveFile { from, to } => fs::move_file(from, to, &ctx.safety).await,
+        AgentAction::ClipboardRead => {
+            let text = clipboard::read_text().await?;
+            if text.is_empty() {
+                return Ok("Clipboard is empty (or holds no text).".into());
+            }
+            let total = text.chars().count();
+            let shown: String = text.chars().take(CLIPBOARD_RESULT_CHARS).collect();
+            Ok(if total > CLIPBOARD_RESULT_CHARS {
+                format!("Clipboard text ({total} chars, first {CLIPBOARD_RESULT_CHARS} shown):\n{shown}")
+            } else {
+                format!("Clipboard text ({total} chars):\n{shown}")
+            })
+        }
+        AgentAction::ClipboardWrite { text } => {
+            clipboard::write_text(text.clone()).await?;
+            Ok(format!("Copied {} chars to the clipboard", text.chars().count()))
+        }
+        AgentAction::Scroll { direction, distance, element_id } => {
+            let target = match element_id {
+                Some(id) => {
+                    let meta = state
+                        .last_meta
+                        .as_ref()
+                        .ok_or_else(|| SeeClawError::GroundingFailed("no viewport — call get_viewport first".into()))?;
+                    let point = ctx
+                        .grounding
+                        .resolve(&GroundingRequest {
+                            element_id: id,
+                            elements: &state.detected_elements,
+                            meta,
+                            grid_n: ctx.grid_n,
+                        })
+                        .ok_or_else(|| SeeClawError::GroundingFailed(format!("cannot resolve element: {id}")))?;
+                    if exclusion::contains_point(point.x, point.y) {
+                        return Err(SeeClawError::ExecutorDenied(format!(
+                            "{id} is inside a user-defined exclusion zone. Choose another target."
+                        )));
                     }
-                    (true, format!("Skill '{}' executed ({} combo steps)", skill_name, total))
-                }
-                None => {
-                    tracing::warn!(skill = %skill_name, "invoke_skill: no combo found in registry");
-                    (false, format!("Skill '{}' not found in registry", skill_name))
+                    calibration::ensure(meta).await;
+                    Some((point.x, point.y))
                 }
+                None => None,
+            };
+            input::scroll(direction.clone(), distance.clone(), target).await?;
+            Ok(match element_id {
+                Some(id) => format!("Scrolled {direction} ({distance}) over {id}"),
+                None => format!("Scrolled {direction} ({distance})"),
+            })
+        }
+        AgentAction::InvokeSkill { skill_name, .. } => {
+            // Skills are run by SkillExecutor from ActionExecNode::execute; reaching
+            // here means a skill step tried to invoke another skill.
+            Err(SeeClawError::ExecutorDenied(format!("nested skill invocation is not supported ({skill_name})")))
+        }
+        AgentAction::DelegateSubtask { .. } => {
+            // Run by ActionExecNode::execute, which owns the state.
+            Err(SeeClawError::ExecutorDenied("delegate_subtask is not available here".into()))
+        }
+        AgentAction::McpCall { server_name, tool_name, arguments } => {
+            tracing::info!(server = %server_name, tool = %tool_name, "calling MCP tool");
+            let flag = state.stop_flag.clone();
+            let result = tokio::select! {
+                r = ctx.mcp.call_tool(server_name, tool_name, arguments.clone()) => r,
+                _ = wait_stop(flag) => return Err(SeeClawError::Cancelled),
+            };
+            let (is_error, text) = tool_result_text(&result?);
+            let report = format!("mcp {server_name}/{tool_name}:\n{}", truncate_str(&text, 4000));
+            if is_error {
+                Err(SeeClawError::Mcp(report))
+            } else {
+                Ok(report)
             }
         }
         AgentAction::FinishTask { .. } | AgentAction::ReportFailure { .. } => {
             // Handled above in the node logic
-            (true, String::new())
+            Ok(String::new())
         }
-        AgentAction::GetViewport { .. } => {
+        AgentAction::GetViewport { .. } | AgentAction::SelectMonitor { .. } => {
             // Handled above
-            (true, String::new())
+            Ok(String::new())
         }
         other => {
             tracing::warn!(?other, "action not yet implemented");
-            (false, "Not implemented".into())
+            Err(SeeClawError::Executor("not implemented".into()))
+        }
+    }
+}
+
+/// Screen point from a click result ("Clicked UI_3 at (412,230)").
+pub(crate) fn click_point(result: &str) -> Option<(i32, i32)> {
+    static RE: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+    let re = RE.get_or_init(|| regex::Regex::new(r"^Clicked \S+ at \((-?\d+),\s*(-?\d+)\)").expect("valid regex"));
+    let caps = re.captures(result)?;
+    Some((caps[1].parse().ok()?, caps[2].parse().ok()?))
+}
+
+/// Keep a screenshot of the screen before a UI action so `StabilityNode` can
+/// report what the action changed.
+async fn capture_pre_action_frame(action: &AgentAction, state: &mut SharedState, ctx: &NodeContext) {
+    state.pre_action_frame = None;
+    if !ctx.executor.verify_screen_change || !needs_stability_wait(action) {
+        return;
+    }
+    match capture(state.monitor).await {
+        Ok(shot) => state.pre_action_frame = Some(shot.image_bytes),
+        Err(e) => tracing::debug!(error = %e, "pre-action capture failed — no screen-change check"),
+    }
+}
+
+/// Switch the task's capture target after checking the monitor exists.
+async fn select_monitor(target: MonitorTarget, state: &mut SharedState) -> SeeClawResult<String> {
+    let monitors = list_monitors().await?;
+    if let MonitorTarget::Index(i) = target {
+        if i as usize >= monitors.len() {
+            return Err(SeeClawError::Executor(format!(
+                "no monitor {i}; {} connected (0-{})",
+                monitors.len(),
+                monitors.len().saturating_sub(1)
+            )));
         }
     }
+    state.monitor = target;
+    // Elements and grid cells from the old screen no longer apply.
+    state.detected_elements.clear();
+    state.element_tracker.reset();
+    state.last_meta = None;
+    let listing = monitors
+        .iter()
+        .map(|m| format!(
+            "{}: {} {}x{} at ({}, {}){}",
+            m.index, m.name, m.width, m.height, m.x, m.y,
+            if m.primary { " [primary]" } else { "" }
+        ))
+        .collect::<Vec<_>>()
+        .join("\n");
+    Ok(format!("Now viewing {target:?}. Monitors:\n{listing}"))
 }
 
-fn action_activity_label(action: &AgentAction) -> String {
+fn click_target(action: &AgentAction) -> Option<&str> {
     match action {
-        AgentAction::MouseClick { element_id } => format!("正在点击 {element_id}…"),
-        AgentAction::MouseDoubleClick { element_id } => format!("正在双击 {element_id}…"),
-        AgentAction::MouseRightClick { element_id } => format!("正在右键点击 {element_id}…"),
+        AgentAction::MouseClick { element_id }
+        | AgentAction::MouseDoubleClick { element_id }
+        | AgentAction::MouseRightClick { element_id } => Some(element_id),
+        _ => None,
+    }
+}
+
+/// In `confidence` approval mode, returns the grounding confidence of a click
+/// that falls below the auto-approve threshold.
+fn low_confidence_click(action: &AgentAction, state: &SharedState, ctx: &NodeContext) -> Option<f32> {
+    if ctx.safety.approval_mode != ApprovalMode::Confidence {
+        return None;
+    }
+    let element_id = click_target(action)?;
+    let score = grounding_confidence(element_id, &state.detected_elements, state.action_certainty);
+    tracing::debug!(element_id = %element_id, score, "grounding confidence");
+    (score < ctx.safety.auto_approve_confidence).then_some(score)
+}
+
+pub(crate) fn action_activity_label(action: &AgentAction, lang: GoalLanguage) -> String {
+    match action {
+        AgentAction::MouseClick { element_id } => lang.pick(format!("正在点击 {element_id}…"), format!("Clicking {element_id}…")),
+        AgentAction::MouseDoubleClick { element_id } => lang.pick(format!("正在双击 {element_id}…"), format!("Double-clicking {element_id}…")),
+        AgentAction::MouseRightClick { element_id } => lang.pick(format!("正在右键点击 {element_id}…"), format!("Right-clicking {element_id}…")),
+        AgentAction::MouseClickAt { x_pct, y_pct } => {
+            lang.pick(format!("正在点击 ({x_pct:.0}%, {y_pct:.0}%)…"), format!("Clicking at ({x_pct:.0}%, {y_pct:.0}%)…"))
+        }
         AgentAction::TypeText { text, .. } => {
             let preview: String = text.chars().take(20).collect();
-            format!("正在输入: {preview}…")
+            lang.pick(format!("正在输入: {preview}…"), format!("Typing: {preview}…"))
         }
-        AgentAction::Hotkey { keys } => format!("正在按下快捷键: {keys}"),
-        AgentAction::KeyPress { key } => format!("正在按键: {key}"),
-        AgentAction::Wait { milliseconds } => format!("等待 {milliseconds}ms…"),
+        AgentAction::Hotkey { keys } => lang.pick(format!("正在按下快捷键: {keys}"), format!("Pressing hotkey: {keys}")),
+        AgentAction::KeyPress { key } => lang.pick(format!("正在按键: {key}"), format!("Pressing key: {key}")),
+        AgentAction::KeyboardNavigate { target, .. } => lang.pick(format!("正在用键盘定位: {target}…"), format!("Navigating by keyboard to: {target}…")),
+        AgentAction::Wait { milliseconds } => lang.pick(format!("等待 {milliseconds}ms…"), format!("Waiting {milliseconds}ms…")),
         AgentAction::ExecuteTerminal { command, .. } => {
             let preview: String = command.chars().take(30).collect();
-            format!("正在执行命令: {preview}…")
+            lang.pick(format!("正在执行命令: {preview}…"), format!("Running command: {preview}…"))
         }
-        AgentAction::Scroll { direction, .. } => format!("正在滚动({direction})…"),
-        AgentAction::InvokeSkill { skill_name, .. } => format!("正在执行技能: {skill_name}…"),
-        AgentAction::FinishTask { .. } => "正在完成任务…".to_string(),
-        AgentAction::ReportFailure { .. } => "正在报告结果…".to_string(),
-        _ => "正在执行操作…".to_string(),
+        AgentAction::Scroll { direction, .. } => lang.pick(format!("正在滚动({direction})…"), format!("Scrolling ({direction})…")),
+        AgentAction::OpenApp { name_or_path, .. } => lang.pick(format!("正在启动: {name_or_path}…"), format!("Launching: {name_or_path}…")),
+        AgentAction::ReadFile { path } => lang.pick(format!("正在读取文件: {path}"), format!("Reading file: {path}")),
+        AgentAction::WriteFile { path, .. } => lang.pick(format!("正在写入文件: {path}"), format!("Writing file: {path}")),
+        AgentAction::ListDir { path } => lang.pick(format!("正在列出目录: {path}"), format!("Listing folder: {path}")),
+        AgentAction::MoveFile { from, to } => lang.pick(format!("正在移动: {from} → {to}"), format!("Moving: {from} → {to}")),
+        AgentAction::ClipboardRead => lang.pick("正在读取剪贴板…", "Reading the clipboard…").to_string(),
+        AgentAction::ClipboardWrite { .. } => lang.pick("正在写入剪贴板…", "Copying to the clipboard…").to_string(),
+        AgentAction::InvokeSkill { skill_name, .. } => lang.pick(format!("正在执行技能: {skill_name}…"), format!("Running skill: {skill_name}…")),
+        AgentAction::DelegateSubtask { goal, .. } => lang.pick(format!("正在委派子任务: {goal}…"), format!("Delegating subtask: {goal}…")),
+        AgentAction::McpCall { server_name, tool_name, .. } => lang.pick(
+            format!("正在调用 MCP 工具: {server_name}/{tool_name}…"),
+            format!("Calling MCP tool: {server_name}/{tool_name}…"),
+        ),
+        AgentAction::FinishTask { .. } => lang.pick("正在完成任务…", "Finishing the task…").to_string(),
+        AgentAction::ReportFailure { .. } => lang.pick("正在报告结果…", "Reporting the result…").to_string(),
+        _ => lang.pick("正在执行操作…", "Performing action…").to_string(),
     }
 }
 
 /// Compact human-readable action label for step_action_history.
-fn compact_action_label(action: &AgentAction) -> String {
+pub(crate) fn compact_action_label(action: &AgentAction) -> String {
     match action {
         AgentAction::MouseClick { element_id } => format!("click({})", element_id),
         AgentAction::MouseDoubleClick { element_id } => format!("dblclick({})", element_id),
         AgentAction::MouseRightClick { element_id } => format!("rclick({})", element_id),
+        AgentAction::MouseClickAt { x_pct, y_pct } => format!("click_at({x_pct:.1}%, {y_pct:.1}%)"),
         AgentAction::Hotkey { keys } => format!("hotkey({})", keys),
         AgentAction::KeyPress { key } => format!("key({})", key),
+        AgentAction::KeyboardNavigate { target, key, .. } => format!("kbnav({}, {})", target, key),
         AgentAction::TypeText { text, .. } => {
             let preview: String = text.chars().take(20).collect();
             format!("type(\"{}\")", preview)
@@ -486,7 +989,20 @@ fn compact_action_label(action: &AgentAction) -> String {
         }
         AgentAction::Scroll { direction, .. } => format!("scroll({})", direction),
         AgentAction::Wait { milliseconds } => format!("wait({}ms)", milliseconds),
+        AgentAction::OpenApp { name_or_path, .. } => format!("open_app({})", name_or_path),
+        AgentAction::ReadFile { path } => format!("read_file({})", path),
+        AgentAction::WriteFile { path, .. } => format!("write_file({})", path),
+        AgentAction::ListDir { path } => format!("list_dir({})", path),
+        AgentAction::MoveFile { from, to } => format!("move_file({} → {})", from, to),
+        AgentAction::ClipboardRead => "clipboard_read".to_string(),
+        AgentAction::ClipboardWrite { text } => format!("clipboard_write({} chars)", text.chars().count()),
+        AgentAction::SelectMonitor { target } => format!("monitor({target:?})"),
         AgentAction::InvokeSkill { skill_name, .. } => format!("skill({})", skill_name),
+        AgentAction::DelegateSubtask { goal, .. } => {
+            let preview: String = goal.chars().take(30).collect();
+            format!("subtask(\"{}\")", preview)
+        }
+        AgentAction::McpCall { server_name, tool_name, .. } => format!("mcp({}/{})", server_name, tool_name),
         _ => "other".to_string(),
     }
 }
@@ -502,18 +1018,30 @@ fn truncate_str(s: &str, max: usize) -> String {
 }
 
 /// Return a short tag identifying the action kind (for auto-completion heuristics).
-fn action_kind_tag(action: &AgentAction) -> &'static str {
+pub(crate) fn action_kind_tag(action: &AgentAction) -> &'static str {
     match action {
         AgentAction::MouseClick { .. } => "mouse_click",
         AgentAction::MouseDoubleClick { .. } => "mouse_double_click",
         AgentAction::MouseRightClick { .. } => "mouse_right_click",
+        AgentAction::MouseClickAt { .. } => "mouse_click_at",
         AgentAction::Hotkey { .. } => "hotkey",
         AgentAction::KeyPress { .. } => "key_press",
+        AgentAction::KeyboardNavigate { .. } => "keyboard_navigate",
         AgentAction::TypeText { .. } => "type_text",
         AgentAction::ExecuteTerminal { .. } => "execute_terminal",
         AgentAction::Scroll { .. } => "scroll",
         AgentAction::Wait { .. } => "wait",
+        AgentAction::OpenApp { .. } => "open_app",
+        AgentAction::ReadFile { .. } => "read_file",
+        AgentAction::WriteFile { .. } => "write_file",
+        AgentAction::ListDir { .. } => "list_dir",
+        AgentAction::MoveFile { .. } => "move_file",
+        AgentAction::ClipboardRead => "clipboard_read",
+        AgentAction::ClipboardWrite { .. } => "clipboard_write",
+        AgentAction::SelectMonitor { .. } => "select_monitor",
         AgentAction::InvokeSkill { .. } => "invoke_skill",
+        AgentAction::DelegateSubtask { .. } => "delegate_subtask",
+        AgentAction::McpCall { .. } => "mcp_call",
         AgentAction::FinishTask { .. } => "finish_task",
         AgentAction::ReportFailure { .. } => "report_failure",
         _ => "other",
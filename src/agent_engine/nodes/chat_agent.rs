@@ -7,14 +7,16 @@
 //! The agent can signal a mode switch to VLM via `switch_to_vlm` tool call.
 
 use async_trait::async_trait;
-use tauri::Emitter;
 
+use crate::agent_engine::app_map;
 use crate::agent_engine::context::NodeContext;
-use crate::agent_engine::node::{poll_stop, Node, NodeOutput};
+use crate::agent_engine::node::{Node, NodeOutput};
 use crate::agent_engine::state::{SharedState, StepMode, StepStatus};
 use crate::agent_engine::tool_parser::parse_action_by_name;
+use crate::errors::{SeeClawError, SeeClawResult};
 use crate::llm::tools::load_builtin_tools;
 use crate::llm::types::{ChatMessage, MessageContent};
+use crate::prompts;
 
 const CHAT_AGENT_SYSTEM: &str = include_str!("../../../prompts/system/chat_agent.md");
 
@@ -36,7 +38,7 @@ impl Node for ChatAgentNode {
         &self,
         state: &mut SharedState,
         ctx: &NodeContext,
-    ) -> Result<NodeOutput, String> {
+    ) -> SeeClawResult<NodeOutput> {
         if state.is_stopped() {
             return Ok(NodeOutput::End);
         }
@@ -82,10 +84,14 @@ impl Node for ChatAgentNode {
                 context_parts.push(format!("**Guidance**: {}", guidance));
             }
 
+            if let Some(section) = app_map::prompt_section(&state.app_maps) {
+                context_parts.push(section);
+            }
+
             state.step_messages = vec![
                 ChatMessage {
                     role: "system".into(),
-                    content: MessageContent::Text(CHAT_AGENT_SYSTEM.to_string()),
+                    content: MessageContent::Text(prompts::get("chat_agent.md", CHAT_AGENT_SYSTEM).into_owned()),
                     tool_call_id: None,
                     tool_calls: None,
                 },
@@ -106,25 +112,23 @@ impl Node for ChatAgentNode {
             });
         }
 
-        // Load tools and call LLM
-        let tools = load_builtin_tools().map_err(|e| e.to_string())?;
+        // Load tools (builtins plus connected MCP servers) and call LLM
+        let mut tools = load_builtin_tools()?;
+        tools.extend(ctx.mcp.tool_defs().await);
         let messages = state.step_messages.clone();
 
         let (provider, mut cfg) = {
             let reg = ctx.registry.lock().await;
-            reg.call_config_for_role("tools").map_err(|e| e.to_string())?
+            reg.call_config_for_role("tools")?
         };
         cfg.silent = true;
 
-        let flag = state.stop_flag.clone();
-        let response = tokio::select! {
-            result = provider.chat(messages, tools, &cfg, &ctx.app) => {
-                result.map_err(|e| e.to_string())?
-            }
-            _ = poll_stop(flag) => {
-                return Ok(NodeOutput::End);
-            }
+        cfg.cancel = state.cancel.clone();
+        let response = match provider.chat(messages, tools, &cfg, &ctx.app).await {
+            Err(SeeClawError::Cancelled) => return Ok(NodeOutput::End),
+            result => result?,
         };
+        state.last_reasoning = response.reasoning.clone();
 
         if state.is_stopped() {
             return Ok(NodeOutput::End);
@@ -236,7 +240,7 @@ fn truncate(s: &str, max: usize) -> String {
 }
 
 /// Detect if a finish_step summary indicates failure rather than success.
-fn summary_indicates_failure(summary: &str) -> bool {
+pub(crate) fn summary_indicates_failure(summary: &str) -> bool {
     let lower = summary.to_lowercase();
     let failure_keywords = [
         "fail", "unable", "cannot", "could not", "couldn't",
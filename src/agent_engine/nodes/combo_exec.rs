@@ -3,20 +3,19 @@
 //! When the Planner assigns `mode: "combo"` to a step, this node:
 //! 1. Looks up the combo definition from the SkillRegistry.
 //! 2. Expands parameter placeholders with actual values.
-//! 3. Converts each combo step into an `AgentAction`.
-//! 4. Executes ALL actions sequentially in a single node invocation.
+//! 3. Runs every step through `SkillExecutor` in a single node invocation,
+//!    logging per-step results to `steps_log`.
 //!
 //! **Zero LLM calls** — this is the fastest execution path.
 //! If the combo is not found, the node falls back to `vlm_act`.
 
 use async_trait::async_trait;
-use tauri::Emitter;
 
 use crate::agent_engine::context::NodeContext;
-use crate::agent_engine::node::{poll_stop, Node, NodeOutput};
-use crate::agent_engine::state::{AgentAction, SharedState};
-use crate::agent_engine::tool_parser::parse_action_by_name;
-use crate::executor::input;
+use crate::agent_engine::node::{Node, NodeOutput};
+use crate::agent_engine::state::SharedState;
+use crate::errors::SeeClawResult;
+use crate::skills::SkillExecutor;
 
 pub struct ComboExecNode;
 
@@ -36,7 +35,7 @@ impl Node for ComboExecNode {
         &self,
         state: &mut SharedState,
         ctx: &NodeContext,
-    ) -> Result<NodeOutput, String> {
+    ) -> SeeClawResult<NodeOutput> {
         if state.is_stopped() {
             return Ok(NodeOutput::End);
         }
@@ -64,104 +63,57 @@ impl Node for ComboExecNode {
             "ComboExecNode: expanding combo"
         );
 
-        // Look up and expand the combo
-        let combo_steps = match ctx.skill_registry.expand_combo(&skill_name, &params) {
-            Some(steps) => steps,
-            None => {
-                tracing::warn!(
-                    skill = %skill_name,
-                    "ComboExecNode: no combo found — fallback to chat_agent"
-                );
-                return Ok(NodeOutput::GoTo("chat_agent".to_string()));
-            }
-        };
+        let skill_steps = ctx
+            .skill_registry
+            .get_skill(&skill_name)
+            .map(|s| s.steps.len())
+            .unwrap_or(0);
+        if skill_steps == 0 {
+            tracing::warn!(
+                skill = %skill_name,
+                "ComboExecNode: no combo found — fallback to chat_agent"
+            );
+            return Ok(NodeOutput::GoTo("chat_agent".to_string()));
+        }
 
         let _ = ctx.app.emit(
             "agent_activity",
             serde_json::json!({
-                "text": format!("执行技能组合: {} ({} 步)", skill_name, combo_steps.len())
+                "text": state.lang.pick(
+                    format!("执行技能组合: {} ({} 步)", skill_name, skill_steps),
+                    format!("Running skill combo: {} ({} steps)", skill_name, skill_steps),
+                )
             }),
         );
 
-        // Execute each action in the combo sequence
-        for (i, combo_step) in combo_steps.iter().enumerate() {
-            if state.is_stopped() {
-                return Ok(NodeOutput::End);
-            }
-
-            let action = match parse_action_by_name(&combo_step.action, &combo_step.args) {
-                Ok(a) => a,
-                Err(e) => {
-                    tracing::warn!(
-                        step = idx,
-                        combo_step = i,
-                        error = %e,
-                        "ComboExecNode: failed to parse combo action — skipping"
-                    );
-                    continue;
-                }
-            };
-
-            tracing::debug!(
-                step = idx,
-                combo_step = i,
-                action = ?action,
-                "ComboExecNode: executing combo action"
-            );
-
-            // Execute the action
-            match &action {
-                AgentAction::Wait { milliseconds } => {
-                    let flag = state.stop_flag.clone();
-                    let ms = *milliseconds;
-                    tokio::select! {
-                        _ = tokio::time::sleep(std::time::Duration::from_millis(ms as u64)) => {}
-                        _ = poll_stop(flag) => return Ok(NodeOutput::End),
-                    }
-                }
-                AgentAction::Hotkey { keys } => {
-                    if let Err(e) = input::press_hotkey(keys.clone()).await {
-                        tracing::warn!(error = %e, "ComboExecNode: hotkey failed");
-                    }
-                }
-                AgentAction::KeyPress { key } => {
-                    if let Err(e) = input::press_hotkey(key.clone()).await {
-                        tracing::warn!(error = %e, "ComboExecNode: key_press failed");
-                    }
-                }
-                AgentAction::TypeText { text, clear_first } => {
-                    if *clear_first {
-                        let _ = input::press_hotkey("ctrl+a".to_string()).await;
-                        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
-                    }
-                    if let Err(e) = input::type_text(text.clone(), *clear_first).await {
-                        tracing::warn!(error = %e, "ComboExecNode: type_text failed");
-                    }
-                }
-                AgentAction::MouseClick { element_id } => {
-                    // For combo, element_id would need to be resolved — but combos
-                    // shouldn't normally contain mouse clicks with element_ids.
-                    tracing::debug!(element_id = %element_id, "ComboExecNode: mouse_click in combo");
-                }
-                other => {
-                    tracing::warn!(action = ?other, "ComboExecNode: unsupported action in combo — skipping");
-                }
-            }
+        let run = match SkillExecutor::new(&ctx.skill_registry)
+            .run(&skill_name, &params, state, ctx)
+            .await
+        {
+            Some(run) => run,
+            None => return Ok(NodeOutput::GoTo("chat_agent".to_string())),
+        };
+        if run.stopped {
+            return Ok(NodeOutput::End);
         }
 
         tracing::info!(
             step = idx,
             skill = %skill_name,
+            ok = run.ok(),
             "ComboExecNode: combo completed"
         );
 
-        // Mark step log
-        state.steps_log.push(format!(
-            "Step {}: combo '{}' executed ({} actions)",
-            idx,
-            skill_name,
-            combo_steps.len()
-        ));
+        state.steps_log.extend(run.log_lines(idx + 1));
+        state.steps_log.push(format!("Step {}: {}", idx + 1, run.summary()));
+        state.last_action_succeeded = run.ok();
+        state.last_exec_result = run.summary();
+
+        if !run.ok() {
+            // A failed combo step leaves the UI in an unknown state — let the
+            // VLM observe and recover instead of blindly advancing.
+            return Ok(NodeOutput::GoTo("vlm_act".to_string()));
+        }
 
         // Move to step_advance (combo replaces the action_exec path)
         Ok(NodeOutput::GoTo("step_advance".to_string()))
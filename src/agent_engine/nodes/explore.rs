@@ -0,0 +1,218 @@
+//! ExploreNode — time-boxed survey of an unfamiliar application's menus.
+//!
+//! When the Planner assigns `mode: "explore"` to a step, this node:
+//! 1. Reads the accessibility tree and finds the top-level menu bar items.
+//! 2. Opens each menu in turn, records the commands that appear, then closes it
+//!    with Escape.
+//! 3. Stores the result as an [`AppMap`] in `SharedState::app_maps`, which later
+//!    steps see as prompt context, and advances to the next step.
+//!
+//! **Zero LLM calls.** The survey stops at `EXPLORE_BUDGET` or `MAX_MENUS`,
+//! whichever comes first; what was gathered so far is kept.
+
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use crate::agent_engine::app_map::{self, AppMap};
+use crate::agent_engine::context::NodeContext;
+use crate::agent_engine::node::{wait_stop, Node, NodeOutput};
+use crate::agent_engine::state::SharedState;
+use crate::errors::SeeClawResult;
+use crate::executor::input;
+use crate::perception::exclusion;
+use crate::perception::foreground::foreground_app;
+use crate::perception::screenshot::capture;
+use crate::perception::types::{ElementType, ScreenshotMeta, UIElement};
+use crate::perception::ui_automation::collect_ui_elements;
+
+/// Wall-clock budget for one explore step.
+const EXPLORE_BUDGET: Duration = Duration::from_secs(60);
+/// Top-level menus opened at most.
+const MAX_MENUS: usize = 10;
+/// Commands kept per menu.
+const MAX_COMMANDS_PER_MENU: usize = 25;
+/// Time for a menu to render after the click.
+const MENU_OPEN_WAIT_MS: u64 = 500;
+
+pub struct ExploreNode;
+
+impl ExploreNode {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl Node for ExploreNode {
+    fn name(&self) -> &str {
+        "explore"
+    }
+
+    async fn execute(
+        &self,
+        state: &mut SharedState,
+        ctx: &NodeContext,
+    ) -> SeeClawResult<NodeOutput> {
+        if state.is_stopped() {
+            return Ok(NodeOutput::End);
+        }
+
+        let idx = state.current_step_idx;
+        let app = foreground_app()
+            .map(|a| if a.title.is_empty() { a.process } else { a.title })
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| "current application".to_string());
+
+        tracing::info!(step = idx, app = %app, "ExploreNode: surveying menus");
+        let _ = ctx.app.emit("agent_activity", serde_json::json!({
+            "text": state.lang.pick(
+                format!("正在探索应用菜单: {app}…"),
+                format!("Exploring the menus of {app}…"),
+            )
+        }));
+
+        let deadline = Instant::now() + EXPLORE_BUDGET;
+        let mut map = AppMap { app: app.clone(), menus: Vec::new() };
+
+        let monitor = state.monitor;
+        let survey = async {
+            let shot = capture(monitor).await?;
+            let baseline = collect_ui_elements(&shot.meta).await?;
+            SeeClawResult::Ok((shot.meta, baseline))
+        };
+        let (meta, baseline) = match survey.await {
+            Ok(v) => v,
+            Err(e) => {
+                tracing::warn!(error = %e, "ExploreNode: cannot read the accessibility tree");
+                return Ok(finish(state, idx, map, Some(format!("exploration unavailable: {e}"))));
+            }
+        };
+
+        let menus = top_level_menus(&baseline);
+        let known: HashSet<String> = baseline
+            .iter()
+            .filter(|e| matches!(e.node_type, ElementType::MenuItem))
+            .filter_map(|e| e.content.clone())
+            .collect();
+        tracing::info!(menus = menus.len(), "ExploreNode: menu bar items found");
+
+        for menu in menus.iter().take(MAX_MENUS) {
+            if state.is_stopped() {
+                return Ok(NodeOutput::End);
+            }
+            if Instant::now() >= deadline {
+                tracing::info!("ExploreNode: time budget exhausted");
+                break;
+            }
+            let name = menu.content.clone().unwrap_or_default();
+            let (x, y) = menu.center_desktop(&meta);
+            if exclusion::contains_point(x, y) {
+                tracing::debug!(menu = %name, "ExploreNode: menu inside exclusion zone, skipped");
+                continue;
+            }
+
+            let commands = match open_and_read(&name, x, y, &meta, &known, state).await {
+                Ok(Some(commands)) => commands,
+                Ok(None) => return Ok(NodeOutput::End),
+                Err(e) => {
+                    tracing::warn!(menu = %name, error = %e, "ExploreNode: failed to read menu");
+                    Vec::new()
+                }
+            };
+            tracing::debug!(menu = %name, commands = commands.len(), "ExploreNode: menu read");
+            map.menus.push((name, commands));
+        }
+
+        Ok(finish(state, idx, map, None))
+    }
+}
+
+/// Click the menu, collect the menu items that appeared, close it again.
+/// `Ok(None)` means the user stopped the task.
+async fn open_and_read(
+    menu: &str,
+    x: i32,
+    y: i32,
+    meta: &ScreenshotMeta,
+    known: &HashSet<String>,
+    state: &SharedState,
+) -> SeeClawResult<Option<Vec<String>>> {
+    input::mouse_click(x, y).await?;
+    let opened = tokio::select! {
+        _ = tokio::time::sleep(Duration::from_millis(MENU_OPEN_WAIT_MS)) => true,
+        _ = wait_stop(state.stop_flag.clone()) => false,
+    };
+    if !opened {
+        let _ = input::press_hotkey("escape".to_string()).await;
+        return Ok(None);
+    }
+
+    let read = collect_ui_elements(meta).await;
+    // Close the menu even if reading failed: once for a submenu, once for the menu.
+    input::press_hotkey("escape".to_string()).await?;
+    input::press_hotkey("escape".to_string()).await?;
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let mut seen = HashSet::new();
+    let commands = read?
+        .into_iter()
+        .filter(|e| matches!(e.node_type, ElementType::MenuItem))
+        .filter_map(|e| e.content)
+        .map(|c| c.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|c| !c.is_empty() && c != menu && !known.contains(c))
+        .filter(|c| seen.insert(c.clone()))
+        .take(MAX_COMMANDS_PER_MENU)
+        .collect();
+    Ok(Some(commands))
+}
+
+/// Named menu items that sit directly in a menu bar, in reading order.
+fn top_level_menus(elements: &[UIElement]) -> Vec<UIElement> {
+    let menu_bars: HashSet<&str> = elements
+        .iter()
+        .filter(|e| matches!(e.node_type, ElementType::Menu))
+        .map(|e| e.id.as_str())
+        .collect();
+    let mut names = HashSet::new();
+    let mut menus: Vec<UIElement> = elements
+        .iter()
+        .filter(|e| matches!(e.node_type, ElementType::MenuItem) && e.content.is_some())
+        .filter(|e| e.parent_id.as_deref().is_some_and(|p| menu_bars.contains(p)))
+        .filter(|e| names.insert(e.content.clone()))
+        .cloned()
+        .collect();
+    menus.sort_by(|a, b| {
+        a.bbox[1]
+            .partial_cmp(&b.bbox[1])
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.bbox[0].partial_cmp(&b.bbox[0]).unwrap_or(std::cmp::Ordering::Equal))
+    });
+    menus
+}
+
+/// Record the map, log the step and move on to the next step.
+fn finish(state: &mut SharedState, idx: usize, map: AppMap, problem: Option<String>) -> NodeOutput {
+    let summary = match (&problem, map.is_empty()) {
+        (Some(p), _) => format!("Explored {}: {p}", map.app),
+        (None, true) => format!("Explored {}: no menu bar found", map.app),
+        (None, false) => format!(
+            "Explored {}: {} menus, {} commands",
+            map.app,
+            map.menus.len(),
+            map.menus.iter().map(|(_, c)| c.len()).sum::<usize>()
+        ),
+    };
+    tracing::info!(step = idx, summary = %summary, "ExploreNode: done");
+
+    if !map.is_empty() {
+        app_map::upsert(&mut state.app_maps, map);
+    }
+    state.steps_log.push(format!("Step {}: {summary}", idx + 1));
+    state.last_exec_result = summary;
+    // Exploring is best-effort — a missing menu bar must not fail the plan.
+    state.last_action_succeeded = true;
+    state.step_complete = true;
+    NodeOutput::GoTo("step_advance".to_string())
+}
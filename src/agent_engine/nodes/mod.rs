@@ -3,7 +3,10 @@
 pub mod action_exec;
 pub mod chat_agent;
 pub mod combo_exec;
+pub mod explore;
+pub mod parallel_exec;
 pub mod planner;
+pub mod reflect;
 pub mod router;
 pub mod simple_chat;
 pub mod simple_exec;
@@ -32,6 +35,8 @@ pub fn register_all_nodes(graph: &mut Graph) {
     graph.add_node(Box::new(planner::PlannerNode::new()));
     graph.add_node(Box::new(step_router::StepRouterNode::new()));
     graph.add_node(Box::new(combo_exec::ComboExecNode::new()));
+    graph.add_node(Box::new(parallel_exec::ParallelExecNode::new()));
+    graph.add_node(Box::new(explore::ExploreNode::new()));
     graph.add_node(Box::new(chat_agent::ChatAgentNode::new()));
     graph.add_node(Box::new(vlm_act::VlmActNode::new()));
     graph.add_node(Box::new(action_exec::ActionExecNode::new()));
@@ -39,6 +44,7 @@ pub fn register_all_nodes(graph: &mut Graph) {
     graph.add_node(Box::new(stability::StabilityNode::new()));
     graph.add_node(Box::new(step_evaluate::StepEvaluateNode::new()));
     graph.add_node(Box::new(step_advance::StepAdvanceNode::new()));
+    graph.add_node(Box::new(reflect::ReflectNode::new()));
     graph.add_node(Box::new(summarizer::SummarizerNode::new()));
     graph.add_node(Box::new(verifier::VerifierNode::new()));
 }
@@ -0,0 +1,358 @@
+//! ParallelExecNode — runs a group of independent non-GUI steps at once.
+//!
+//! The planner may give consecutive steps the same `group` when none of them
+//! needs another's result. When StepRouter reaches two or more such `chat`
+//! steps, it sends them here: each step gets its own short tool loop limited
+//! to terminal, file and MCP tools, and the loops run concurrently
+//! (`[executor] max_parallel_steps` at a time). Nothing here touches the
+//! mouse, keyboard or screen, so GUI steps stay strictly serialized.
+//!
+//! A step whose next action needs user approval, or a tool outside that set,
+//! is left `Pending` and picked up by the normal serial path afterwards.
+
+use std::ops::Range;
+
+use async_trait::async_trait;
+use futures_util::future::{BoxFuture, FutureExt};
+use futures_util::StreamExt;
+
+use crate::agent_engine::context::NodeContext;
+use crate::agent_engine::event_bus::AgentMessage;
+use crate::agent_engine::metrics::{self, Metric};
+use crate::agent_engine::node::{Node, NodeOutput};
+use crate::agent_engine::nodes::action_exec::{action_activity_label, action_kind_tag, execute_action_impl};
+use crate::agent_engine::nodes::chat_agent::summary_indicates_failure;
+use crate::agent_engine::state::{SharedState, StepMode, StepStatus, TodoStep};
+use crate::agent_engine::tool_parser::parse_action_by_name;
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::executor::safety;
+use crate::llm::tools::load_builtin_tools;
+use crate::llm::types::{ChatMessage, MessageContent};
+use crate::prompts;
+
+const CHAT_AGENT_SYSTEM: &str = include_str!("../../../prompts/system/chat_agent.md");
+
+/// Tools a parallel step may use — none of them drives the GUI.
+const BRANCH_TOOLS: &[&str] = &[
+    "execute_terminal",
+    "read_file",
+    "list_dir",
+    "write_file",
+    "mcp_call",
+    "finish_step",
+];
+/// Tool calls per parallel step before it is given up as failed.
+const MAX_BRANCH_ITERATIONS: u32 = 8;
+
+const BRANCH_NOTE: &str = "\n\n## Parallel step\n\
+This step runs at the same time as other steps of the plan. Only the tools \
+listed are available — no mouse, keyboard or screen. Do not depend on, or \
+interfere with, files or processes other steps may be working on. Call \
+`finish_step` with the result (the data you found, not just \"done\") as soon \
+as the step is complete.";
+
+pub struct ParallelExecNode;
+
+impl ParallelExecNode {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// The run of steps starting at the current one that can execute together,
+/// or `None` when fewer than two qualify.
+pub fn branch_at(state: &SharedState, ctx: &NodeContext) -> Option<Range<usize>> {
+    if ctx.executor.max_parallel_steps <= 1 {
+        return None;
+    }
+    let start = state.current_step_idx;
+    let group = state.todo_steps.get(start)?.group.clone()?;
+    let len = state.todo_steps[start..]
+        .iter()
+        .take_while(|s| s.group.as_deref() == Some(group.as_str()) && is_branch_step(s))
+        .count();
+    (len >= 2).then_some(start..start + len)
+}
+
+fn is_branch_step(step: &TodoStep) -> bool {
+    step.recommended_mode == StepMode::Chat
+        && step.skill.is_none()
+        && matches!(step.status, StepStatus::Pending | StepStatus::InProgress)
+}
+
+enum BranchOutcome {
+    Done { summary: String, failed: bool },
+    /// Needs the serial path (approval, GUI tool).
+    Deferred(String),
+    Stopped,
+}
+
+struct BranchRun {
+    outcome: BranchOutcome,
+    actions: u32,
+    failures: u32,
+    log: Vec<String>,
+}
+
+#[async_trait]
+impl Node for ParallelExecNode {
+    fn name(&self) -> &str {
+        "parallel_exec"
+    }
+
+    async fn execute(
+        &self,
+        state: &mut SharedState,
+        ctx: &NodeContext,
+    ) -> SeeClawResult<NodeOutput> {
+        if state.is_stopped() {
+            return Ok(NodeOutput::End);
+        }
+        let Some(range) = branch_at(state, ctx) else {
+            return Ok(NodeOutput::GoTo("chat_agent".to_string()));
+        };
+
+        tracing::info!(steps = ?range, limit = ctx.executor.max_parallel_steps, "ParallelExecNode: running steps concurrently");
+        // StepRouter already announced the first one.
+        for idx in range.start + 1..range.end {
+            let step = &mut state.todo_steps[idx];
+            step.status = StepStatus::InProgress;
+            step.mode = StepMode::Chat;
+            let _ = ctx.app.emit("step_started", serde_json::json!({
+                "index": idx,
+                "description": &step.description,
+                "mode": &step.mode,
+                "recommended_mode": &step.recommended_mode,
+                "parallel": true,
+            }));
+            metrics::record(&*ctx.app, Metric::StepStarted { index: idx, description: step.description.clone() });
+        }
+        let n = range.len();
+        let _ = ctx.app.emit("agent_activity", serde_json::json!({
+            "text": state.lang.pick(
+                format!("同时执行 {n} 个独立步骤…"),
+                format!("Running {n} independent steps at once…"),
+            )
+        }));
+
+        let steps = state.todo_steps[range.clone()].to_vec();
+        let runs: Vec<BranchRun> = {
+            let shared: &SharedState = state;
+            // Box each branch up front: a `map` closure here makes the node's
+            // future fail the `Send` check over a higher-ranked lifetime.
+            let mut branches: Vec<BoxFuture<'_, BranchRun>> = Vec::with_capacity(steps.len());
+            for step in &steps {
+                branches.push(run_branch(step, shared, ctx).boxed());
+            }
+            futures_util::stream::iter(branches)
+                .buffered(ctx.executor.max_parallel_steps)
+                .collect()
+                .await
+        };
+        if state.is_stopped() || runs.iter().any(|r| matches!(r.outcome, BranchOutcome::Stopped)) {
+            return Ok(NodeOutput::End);
+        }
+
+        let mut next = range.end;
+        {
+            let mut ctrl = ctx.loop_ctrl.lock().await;
+            for run in &runs {
+                ctrl.record_steps(run.actions);
+                for _ in 0..run.failures {
+                    ctrl.record_failure();
+                }
+            }
+        }
+        for (idx, run) in range.clone().zip(runs) {
+            state.steps_log.extend(run.log);
+            let description = state.todo_steps[idx].description.clone();
+            let status = match run.outcome {
+                BranchOutcome::Done { summary, failed } => {
+                    state.steps_log.push(format!("Step {}: {} - {}", idx + 1, description, summary));
+                    if failed { StepStatus::Failed } else { StepStatus::Completed }
+                }
+                BranchOutcome::Stopped => StepStatus::Pending,
+                BranchOutcome::Deferred(reason) => {
+                    tracing::info!(step = idx, reason = %reason, "ParallelExecNode: step handed back to the serial path");
+                    state.steps_log.push(format!("[Parallel] step {} continues on its own: {reason}", idx + 1));
+                    next = next.min(idx);
+                    // Run it through chat_agent next time, not another group.
+                    state.todo_steps[idx].group = None;
+                    StepStatus::Pending
+                }
+            };
+            state.todo_steps[idx].status = status.clone();
+            if status != StepStatus::Pending {
+                let _ = ctx.app.emit("step_completed", serde_json::json!({
+                    "index": idx,
+                    "status": status,
+                }));
+            }
+        }
+        let _ = ctx.app.emit("todolist_updated", serde_json::json!({
+            "steps": &state.todo_steps,
+            "total": state.todo_steps.len(),
+            "completed": state.todo_steps.iter().filter(|s| s.status == StepStatus::Completed).count(),
+        }));
+
+        // Continue after the group, or at the first step it handed back.
+        state.current_step_idx = next;
+        state.current_action = None;
+        state.step_complete = false;
+        state.last_exec_result.clear();
+        state.step_messages.clear();
+        state.step_iterations = 0;
+        state.step_action_history.clear();
+
+        Ok(NodeOutput::Continue)
+    }
+}
+
+/// One step's tool loop. Only reads `state`, so several run side by side.
+async fn run_branch(step: &TodoStep, state: &SharedState, ctx: &NodeContext) -> BranchRun {
+    let mut run = BranchRun {
+        outcome: BranchOutcome::Deferred("no result".into()),
+        actions: 0,
+        failures: 0,
+        log: Vec::new(),
+    };
+    let tools = match load_builtin_tools() {
+        Ok(tools) => tools
+            .into_iter()
+            .filter(|t| BRANCH_TOOLS.contains(&t.function.name.as_str()))
+            .collect::<Vec<_>>(),
+        Err(e) => {
+            run.outcome = BranchOutcome::Deferred(e.to_string());
+            return run;
+        }
+    };
+
+    let mut context_parts = vec![
+        format!("**Current step goal**: {}", step.description),
+        format!("**Final goal**: {}", state.final_goal),
+        format!("**Plan summary**: {}", state.plan_summary),
+    ];
+    if let Some(guidance) = &step.guidance {
+        context_parts.push(format!("**Guidance**: {guidance}"));
+    }
+    let mut messages = vec![
+        ChatMessage {
+            role: "system".into(),
+            content: MessageContent::Text(format!("{}{BRANCH_NOTE}", prompts::get("chat_agent.md", CHAT_AGENT_SYSTEM))),
+            tool_call_id: None,
+            tool_calls: None,
+        },
+        ChatMessage {
+            role: "user".into(),
+            content: MessageContent::Text(context_parts.join("\n")),
+            tool_call_id: None,
+            tool_calls: None,
+        },
+    ];
+
+    for iter in 1..=MAX_BRANCH_ITERATIONS {
+        if state.is_stopped() {
+            run.outcome = BranchOutcome::Stopped;
+            return run;
+        }
+        let (provider, mut cfg) = {
+            let reg = ctx.registry.lock().await;
+            match reg.call_config_for_role("tools") {
+                Ok(pair) => pair,
+                Err(e) => {
+                    run.outcome = BranchOutcome::Deferred(e.to_string());
+                    return run;
+                }
+            }
+        };
+        cfg.silent = true;
+        cfg.cancel = state.cancel.clone();
+        let response = match provider.chat(messages.clone(), tools.clone(), &cfg, &ctx.app).await {
+            Ok(r) => r,
+            Err(SeeClawError::Cancelled) => {
+                run.outcome = BranchOutcome::Stopped;
+                return run;
+            }
+            Err(e) => {
+                run.outcome = BranchOutcome::Deferred(format!("model call failed: {e}"));
+                return run;
+            }
+        };
+
+        let Some(tc) = response.tool_calls.into_iter().next() else {
+            // Content-only reply — take it as the step's result.
+            run.outcome = BranchOutcome::Done { failed: summary_indicates_failure(&response.content), summary: response.content };
+            return run;
+        };
+        messages.push(ChatMessage {
+            role: "assistant".into(),
+            content: MessageContent::Text(response.content.clone()),
+            tool_call_id: None,
+            tool_calls: Some(vec![tc.clone()]),
+        });
+        let args: serde_json::Value =
+            serde_json::from_str(&tc.function.arguments).unwrap_or(serde_json::json!({}));
+
+        if tc.function.name == "finish_step" {
+            let summary = args["summary"].as_str().unwrap_or("Step completed").to_string();
+            tracing::info!(step = step.index, iter, summary = %summary, "[Parallel] finish_step");
+            run.outcome = BranchOutcome::Done { failed: summary_indicates_failure(&summary), summary };
+            return run;
+        }
+        let action = match parse_action_by_name(&tc.function.name, &args) {
+            Ok(action) if BRANCH_TOOLS.contains(&action_kind_tag(&action)) => action,
+            Ok(_) | Err(_) => {
+                run.outcome = BranchOutcome::Deferred(format!("needs tool '{}'", tc.function.name));
+                return run;
+            }
+        };
+        if safety::requires_approval(&action, &ctx.safety) {
+            run.outcome = BranchOutcome::Deferred(format!("{} needs approval", action_kind_tag(&action)));
+            return run;
+        }
+
+        let _ = ctx.app.emit("agent_activity", serde_json::json!({
+            "text": format!("[{}] {}", step.index + 1, action_activity_label(&action, state.lang)),
+        }));
+        let started = std::time::Instant::now();
+        let result = execute_action_impl(&action, state, ctx).await;
+        let ms = started.elapsed().as_millis() as u64;
+        run.actions += 1;
+        let (ok, msg) = match result {
+            Ok(msg) => (true, msg),
+            Err(SeeClawError::Cancelled) => {
+                run.outcome = BranchOutcome::Stopped;
+                return run;
+            }
+            Err(e) => (false, e.to_string()),
+        };
+        if !ok {
+            run.failures += 1;
+        }
+        metrics::record(&*ctx.app, Metric::ActionDone { ms, action: action_kind_tag(&action).to_string(), ok });
+        let _ = ctx.app.emit("action_result", serde_json::json!({
+            "action": action_kind_tag(&action),
+            "ok": ok,
+            "message": msg.chars().take(300).collect::<String>(),
+            "step": step.index,
+        }));
+        ctx.bus.publish(AgentMessage::ActionCompleted {
+            action: action_kind_tag(&action).to_string(),
+            ok,
+            error: (!ok).then(|| msg.clone()),
+        });
+        run.log.push(format!("[Parallel] step {}: {} → {}", step.index + 1, action_kind_tag(&action), if ok { "ok" } else { "failed" }));
+        messages.push(ChatMessage {
+            role: "tool".into(),
+            content: MessageContent::Text(msg),
+            tool_call_id: Some(tc.id.clone()),
+            tool_calls: None,
+        });
+    }
+
+    run.outcome = BranchOutcome::Done {
+        summary: format!("gave up after {MAX_BRANCH_ITERATIONS} tool calls"),
+        failed: true,
+    };
+    run
+}
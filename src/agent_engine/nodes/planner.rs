@@ -1,23 +1,43 @@
 //! PlannerNode — calls the planner LLM to generate a TodoList for complex tasks.
 //!
 //! This node:
-//! 1. Loads builtin tools + relevant skills.
+//! 1. Loads builtin tools, discovered MCP tools + relevant skills, and known
+//!    hotkeys for the foreground app.
 //! 2. Sends the conversation to the LLM (tools role).
 //! 3. Parses the `plan_task` tool call response.
 //! 4. Writes the resulting TodoStep list into SharedState.
+//!
+//! When one reply carries several tool calls they run in order: the first is
+//! handled now, the rest wait in `SharedState::queued_tool_calls` and the
+//! graph returns here after each action to take the next one — without
+//! another LLM call. A failed or rejected call skips the rest of the batch.
+
+use std::collections::VecDeque;
 
 use async_trait::async_trait;
-use tauri::Emitter;
 
+use crate::agent_engine::compaction::compact_if_needed;
+use crate::agent_engine::constraints;
 use crate::agent_engine::context::NodeContext;
-use crate::agent_engine::node::{poll_stop, Node, NodeOutput};
+use crate::agent_engine::context_window::trim_to_budget;
+use crate::agent_engine::continuity::with_carryover;
+use crate::agent_engine::event_bus::AgentMessage;
+use crate::agent_engine::history::HistoryEntry;
+use crate::agent_engine::hotkeys;
+use crate::agent_engine::node::{Node, NodeOutput};
 use crate::agent_engine::state::{AgentAction, GraphResult, RouteType, SharedState};
 use crate::agent_engine::tool_parser::parse_tool_call_to_action;
+use crate::errors::{ErrorCategory, SeeClawError, SeeClawResult};
+use crate::llm::tokens::estimate_tools;
 use crate::llm::tools::load_builtin_tools;
-use crate::llm::types::{ChatMessage, ContentPart, ImageUrl, MessageContent, StreamChunk, StreamChunkKind};
-use crate::perception::screenshot::capture_primary;
+use crate::llm::types::{ChatMessage, ContentPart, ImageUrl, MessageContent, StreamChunk, StreamChunkKind, ToolCall};
+use crate::perception::foreground::foreground_app;
+use crate::perception::screenshot::{capture, data_url};
+use crate::prompts;
 
 const PLANNER_SYSTEM: &str = include_str!("../../../prompts/system/planner.md");
+/// Tokens left free for the planner's reply when trimming the conversation.
+const RESPONSE_RESERVE_TOKENS: usize = 4_096;
 
 pub struct PlannerNode;
 
@@ -37,23 +57,43 @@ impl Node for PlannerNode {
         &self,
         state: &mut SharedState,
         ctx: &NodeContext,
-    ) -> Result<NodeOutput, String> {
+    ) -> SeeClawResult<NodeOutput> {
         if state.is_stopped() {
             return Ok(NodeOutput::End);
         }
 
+        if let Some(tc) = state.queued_tool_calls.pop_front() {
+            tracing::info!(tool = %tc.function.name, remaining = state.queued_tool_calls.len(), "PlannerNode: next queued tool call");
+            return self.handle_tool_call(tc, state, ctx).await;
+        }
+
         tracing::info!(goal = %state.goal, cycle = state.cycle_count, "PlannerNode: calling planner LLM");
-        let _ = ctx.app.emit("agent_activity", serde_json::json!({ "text": "正在规划任务步骤…" }));
+        let _ = ctx.app.emit("agent_activity", serde_json::json!({ "text": state.lang.pick("正在规划任务步骤…", "Planning task steps…") }));
         state.cycle_count += 1;
 
         // Initialise conversation if empty (first call)
         if state.conv_messages.is_empty() {
             // Build system prompt: base prompt + skills context (if any)
-            let system_prompt = if ctx.skills_context.is_empty() {
-                PLANNER_SYSTEM.to_string()
+            let system_prompt = if state.skills_context.is_empty() {
+                prompts::get("planner.md", PLANNER_SYSTEM).into_owned()
             } else {
-                format!("{}\n\n{}", PLANNER_SYSTEM, ctx.skills_context)
+                format!("{}\n\n{}", prompts::get("planner.md", PLANNER_SYSTEM), state.skills_context)
             };
+            let system_prompt = if state.experience_context.is_empty() {
+                system_prompt
+            } else {
+                format!("{system_prompt}\n\n{}", state.experience_context)
+            };
+            let system_prompt = match hotkey_section(state, true) {
+                Some(section) => format!("{system_prompt}\n\n{section}"),
+                None => system_prompt,
+            };
+            let system_prompt = match constraints::prompt_section(&ctx.preferences) {
+                Some(section) => format!("{system_prompt}\n\n{section}"),
+                None => system_prompt,
+            };
+            let system_prompt = with_carryover(&system_prompt, &state.carryover_context);
+            let system_prompt = format!("{system_prompt}\n\n{}", state.lang.prompt_directive());
 
             // Only capture an initial screenshot when the route is ComplexVisual.
             // For plain Complex tasks (e.g. terminal commands, file operations)
@@ -62,7 +102,7 @@ impl Node for PlannerNode {
             let needs_visual = state.route_type == RouteType::ComplexVisual;
 
             let user_content = if needs_visual {
-                match capture_primary().await {
+                match capture(state.monitor).await {
                     Ok(shot) => {
                         tracing::info!("PlannerNode: initial screenshot captured for planning context (ComplexVisual)");
                         let _ = ctx.app.emit("viewport_captured", serde_json::json!({
@@ -70,9 +110,12 @@ impl Node for PlannerNode {
                             "source": "planner_initial",
                         }));
                         let _ = ctx.app.emit("agent_activity", serde_json::json!({
-                            "text": "已截取当前屏幕，正在结合画面制定计划…"
+                            "text": state.lang.pick(
+                                "已截取当前屏幕，正在结合画面制定计划…",
+                                "Screen captured, planning with it in view…",
+                            )
                         }));
-                        let data_url = format!("data:image/jpeg;base64,{}", shot.image_base64);
+                        let data_url = data_url(&shot.image_base64);
                         MessageContent::Parts(vec![
                             ContentPart::ImageUrl {
                                 image_url: ImageUrl { url: data_url },
@@ -90,7 +133,7 @@ impl Node for PlannerNode {
             } else {
                 tracing::info!("PlannerNode: Complex route — skipping initial screenshot");
                 let _ = ctx.app.emit("agent_activity", serde_json::json!({
-                    "text": "正在制定任务计划…"
+                    "text": state.lang.pick("正在制定任务计划…", "Drafting the task plan…")
                 }));
                 MessageContent::Text(state.goal.clone())
             };
@@ -109,29 +152,66 @@ impl Node for PlannerNode {
                     tool_calls: None,
                 },
             ];
+        } else if let Some(section) = hotkey_section(state, false) {
+            // Replanning after the foreground app changed — hand over its shortcuts.
+            state.conv_messages.push(ChatMessage {
+                role: "user".into(),
+                content: MessageContent::Text(section),
+                tool_call_id: None,
+                tool_calls: None,
+            });
         }
 
-        // Load tools
-        let tools = load_builtin_tools().map_err(|e| e.to_string())?;
-        let messages = state.conv_messages.clone();
+        // Load tools — builtins plus whatever the connected MCP servers expose
+        let mut tools = load_builtin_tools()?;
+        tools.extend(ctx.mcp.tool_defs().await);
 
         // Get provider — planner reasoning is internal, don't stream to frontend
         let (provider, mut cfg) = {
             let reg = ctx.registry.lock().await;
-            reg.call_config_for_role("tools").map_err(|e| e.to_string())?
+            reg.call_config_for_role("tools")?
         };
         cfg.silent = true;
 
-        // Race LLM call against stop flag
-        let flag = state.stop_flag.clone();
-        let response = tokio::select! {
-            result = provider.chat(messages, tools, &cfg, &ctx.app) => {
-                result.map_err(|e| e.to_string())?
-            }
-            _ = poll_stop(flag) => {
-                return Ok(NodeOutput::End);
-            }
+        // Fold old turns into a summary note once the conversation gets large.
+        let threshold = ctx.registry.lock().await.compact_after_tokens();
+        match compact_if_needed(state, ctx, threshold).await {
+            Ok(Some(report)) => tracing::info!(
+                before = report.before,
+                after = report.after,
+                folded = report.messages_folded,
+                "PlannerNode: conversation compacted"
+            ),
+            Ok(None) => {}
+            Err(SeeClawError::Cancelled) => return Ok(NodeOutput::End),
+            Err(e) => tracing::warn!(error = %e, "PlannerNode: compaction failed, trimming instead"),
+        }
+
+        // Keep the conversation inside the model's context window.
+        let budget = cfg
+            .context_tokens
+            .saturating_sub(estimate_tools(&tools) + RESPONSE_RESERVE_TOKENS);
+        let trim = trim_to_budget(&mut state.conv_messages, budget);
+        if trim.changed() {
+            tracing::info!(
+                before = trim.before,
+                after = trim.after,
+                budget,
+                images_stripped = trim.images_stripped,
+                results_shortened = trim.results_shortened,
+                messages_dropped = trim.messages_dropped,
+                "PlannerNode: conversation trimmed"
+            );
+        }
+        let messages = state.conv_messages.clone();
+
+        // A stop aborts the request itself, not just the wait for it.
+        cfg.cancel = state.cancel.clone();
+        let response = match provider.chat(messages, tools, &cfg, &ctx.app).await {
+            Err(SeeClawError::Cancelled) => return Ok(NodeOutput::End),
+            result => result?,
         };
+        state.last_reasoning = response.reasoning.clone();
 
         if state.is_stopped() {
             return Ok(NodeOutput::End);
@@ -149,115 +229,179 @@ impl Node for PlannerNode {
             );
         }
 
-        // Process tool call
-        if let Some(tc) = response.tool_calls.into_iter().next() {
-            // Append assistant message
-            state.conv_messages.push(ChatMessage {
-                role: "assistant".into(),
-                content: MessageContent::Text(response.content.clone()),
-                tool_call_id: None,
-                tool_calls: Some(vec![tc.clone()]),
+        if response.tool_calls.is_empty() {
+            // Content-only response — treat as done
+            tracing::info!("[Planner] content-only response → done");
+            state.result = Some(GraphResult::Done {
+                summary: response.content,
             });
-            state.pending_tool_id = tc.id.clone();
-
-            match parse_tool_call_to_action(&tc) {
-                Ok(AgentAction::PlanTask {
-                    ref final_goal,
-                    ref plan_summary,
-                    ref steps,
-                }) => {
-                    state.final_goal = final_goal.clone();
-                    state.plan_summary = plan_summary.clone();
-                    state.todo_steps = steps.clone();
-                    state.current_step_idx = 0;
-                    state.steps_log.clear();
-                    tracing::info!(
-                        steps = steps.len(),
-                        final_goal = %final_goal,
-                        "PlannerNode: plan created"
-                    );
+            return Ok(NodeOutput::End);
+        }
 
-                    // Ack the plan_task tool call
-                    state.conv_messages.push(ChatMessage {
-                        role: "tool".into(),
-                        content: MessageContent::Text(format!(
-                            "Plan accepted: {} steps.",
-                            steps.len()
-                        )),
-                        tool_call_id: Some(state.pending_tool_id.clone()),
-                        tool_calls: None,
-                    });
+        // Append the assistant message with every call, then run them in order.
+        state.conv_messages.push(ChatMessage {
+            role: "assistant".into(),
+            content: MessageContent::Text(response.content.clone()),
+            tool_call_id: None,
+            tool_calls: Some(response.tool_calls.clone()),
+        });
+        let mut calls: VecDeque<ToolCall> = response.tool_calls.into();
+        let Some(first) = calls.pop_front() else {
+            return Ok(NodeOutput::End);
+        };
+        if !calls.is_empty() {
+            tracing::info!(count = calls.len() + 1, "PlannerNode: multiple tool calls in one turn, running in order");
+        }
+        state.queued_tool_calls = calls;
+        self.handle_tool_call(first, state, ctx).await
+    }
+}
 
-                    // Emit todolist to frontend
-                    let _ = ctx.app.emit("todolist_updated", serde_json::json!({
-                        "steps": &state.todo_steps,
-                        "total": state.todo_steps.len(),
-                    }));
+impl PlannerNode {
+    /// Handle one tool call from a planner message.
+    async fn handle_tool_call(
+        &self,
+        tc: ToolCall,
+        state: &mut SharedState,
+        ctx: &NodeContext,
+    ) -> SeeClawResult<NodeOutput> {
+        state.pending_tool_id = tc.id.clone();
 
-                    Ok(NodeOutput::Continue)
-                }
-                Ok(AgentAction::FinishTask { ref summary }) => {
-                    tracing::info!(summary = %summary, "PlannerNode: task finished");
-                    let _ = ctx.app.emit("llm_stream_chunk", &StreamChunk {
-                        kind: StreamChunkKind::Content,
-                        content: summary.clone(),
-                    });
-                    let _ = ctx.app.emit("llm_stream_chunk", &StreamChunk {
-                        kind: StreamChunkKind::Done,
-                        content: String::new(),
-                    });
-                    state.result = Some(GraphResult::Done {
-                        summary: summary.clone(),
-                    });
-                    Ok(NodeOutput::End)
-                }
-                Ok(AgentAction::ReportFailure { ref reason, .. }) => {
-                    tracing::warn!(reason = %reason, "PlannerNode: task failure reported");
-                    let _ = ctx.app.emit("llm_stream_chunk", &StreamChunk {
-                        kind: StreamChunkKind::Content,
-                        content: format!("Task failed: {reason}"),
-                    });
-                    let _ = ctx.app.emit("llm_stream_chunk", &StreamChunk {
-                        kind: StreamChunkKind::Done,
-                        content: String::new(),
-                    });
-                    state.result = Some(GraphResult::Error {
-                        message: reason.clone(),
-                    });
-                    Ok(NodeOutput::End)
-                }
-                Ok(action) => {
-                    // Direct action from planner (rare but possible)
-                    state.current_action = Some(action);
-                    Ok(NodeOutput::GoTo("action_exec".to_string()))
-                }
-                Err(e) => {
-                    // Unknown tool — inject error feedback for self-correction
-                    tracing::warn!(error = %e, tool = %tc.function.name, "[Planner] unrecognised tool");
-                    state.conv_messages.push(ChatMessage {
-                        role: "tool".into(),
-                        content: MessageContent::Text(format!(
-                            "Error: unknown tool '{}'. Please call plan_task or one of the registered tools.",
-                            tc.function.name
-                        )),
-                        tool_call_id: Some(tc.id.clone()),
-                        tool_calls: None,
+        match parse_tool_call_to_action(&tc) {
+            Ok(AgentAction::PlanTask {
+                ref final_goal,
+                ref plan_summary,
+                ref steps,
+            }) => {
+                state.final_goal = final_goal.clone();
+                state.plan_summary = plan_summary.clone();
+                state.todo_steps = steps.clone();
+                state.current_step_idx = 0;
+                state.steps_log.clear();
+                tracing::info!(
+                    steps = steps.len(),
+                    final_goal = %final_goal,
+                    "PlannerNode: plan created"
+                );
+                {
+                    let mut plan = plan_summary.clone();
+                    for (i, step) in steps.iter().enumerate() {
+                        plan.push_str(&format!("\n{}. {}", i + 1, step.description));
+                    }
+                    let mut history = ctx.history.lock().await;
+                    history.push(HistoryEntry {
+                        ts: chrono::Utc::now().timestamp_millis(),
+                        role: "plan".into(),
+                        content: Some(plan),
+                        action: None,
+                        ok: None,
+                        screenshot: None,
+                        reasoning: Some(std::mem::take(&mut state.last_reasoning)).filter(|r| !r.is_empty()),
                     });
-                    // Re-enter planner for self-correction
-                    Ok(NodeOutput::GoTo("planner".to_string()))
+                    let _ = history.flush();
                 }
+
+                // Ack the plan_task tool call
+                state.conv_messages.push(ChatMessage {
+                    role: "tool".into(),
+                    content: MessageContent::Text(format!(
+                        "Plan accepted: {} steps.",
+                        steps.len()
+                    )),
+                    tool_call_id: Some(state.pending_tool_id.clone()),
+                    tool_calls: None,
+                });
+                // Actions sent alongside the plan belong to its steps.
+                state.skip_queued_tool_calls("a plan was accepted in this turn; its steps will carry out this action.");
+
+                // Emit todolist to frontend
+                let _ = ctx.app.emit("todolist_updated", serde_json::json!({
+                    "steps": &state.todo_steps,
+                    "total": state.todo_steps.len(),
+                }));
+                ctx.bus.publish(AgentMessage::PlanGenerated {
+                    final_goal: final_goal.clone(),
+                    steps: steps.iter().map(|s| s.description.clone()).collect(),
+                });
+
+                Ok(NodeOutput::Continue)
+            }
+            Ok(AgentAction::FinishTask { ref summary }) => {
+                tracing::info!(summary = %summary, "PlannerNode: task finished");
+                let _ = ctx.app.emit("llm_stream_chunk", &StreamChunk {
+                    kind: StreamChunkKind::Content,
+                    content: summary.clone(),
+                });
+                let _ = ctx.app.emit("llm_stream_chunk", &StreamChunk {
+                    kind: StreamChunkKind::Done,
+                    content: String::new(),
+                });
+                state.result = Some(GraphResult::Done {
+                    summary: summary.clone(),
+                });
+                Ok(NodeOutput::End)
+            }
+            Ok(AgentAction::ReportFailure { ref reason, .. }) => {
+                tracing::warn!(reason = %reason, "PlannerNode: task failure reported");
+                let _ = ctx.app.emit("llm_stream_chunk", &StreamChunk {
+                    kind: StreamChunkKind::Content,
+                    content: format!("Task failed: {reason}"),
+                });
+                let _ = ctx.app.emit("llm_stream_chunk", &StreamChunk {
+                    kind: StreamChunkKind::Done,
+                    content: String::new(),
+                });
+                state.result = Some(GraphResult::Error {
+                    message: reason.clone(),
+                    category: ErrorCategory::TaskFailed,
+                });
+                Ok(NodeOutput::End)
+            }
+            Ok(action) => {
+                // Direct action from planner (rare but possible)
+                state.current_action = Some(action);
+                Ok(NodeOutput::GoTo("action_exec".to_string()))
+            }
+            Err(e) => {
+                // Unknown tool — inject error feedback for self-correction
+                tracing::warn!(error = %e, tool = %tc.function.name, "[Planner] unrecognised tool");
+                state.conv_messages.push(ChatMessage {
+                    role: "tool".into(),
+                    content: MessageContent::Text(format!(
+                        "Error: unknown tool '{}'. Please call plan_task or one of the registered tools.",
+                        tc.function.name
+                    )),
+                    tool_call_id: Some(tc.id.clone()),
+                    tool_calls: None,
+                });
+                state.skip_queued_tool_calls("an earlier call in this turn was invalid.");
+                // Re-enter planner for self-correction
+                Ok(NodeOutput::GoTo("planner".to_string()))
             }
-        } else {
-            // Content-only response — treat as done
-            tracing::info!("[Planner] content-only response → done");
-            state.result = Some(GraphResult::Done {
-                summary: response.content,
-            });
-            Ok(NodeOutput::End)
         }
     }
 }
 
+/// Hotkey knowledge for apps not yet shown to the planner: the foreground app,
+/// plus (on the first call) apps named in the goal.
+fn hotkey_section(state: &mut SharedState, include_goal: bool) -> Option<String> {
+    let foreground = foreground_app().and_then(|app| hotkeys::for_foreground(&app));
+    let mut entries: Vec<&hotkeys::AppHotkeys> = foreground.into_iter().collect();
+    if include_goal {
+        for entry in hotkeys::mentioned_in(&state.goal) {
+            if !entries.iter().any(|e| e.app == entry.app) {
+                entries.push(entry);
+            }
+        }
+    }
+    entries.retain(|e| !state.hotkey_apps.contains(&e.app));
+    state.hotkey_apps.extend(entries.iter().map(|e| e.app.clone()));
+    if !entries.is_empty() {
+        tracing::info!(apps = ?entries.iter().map(|e| e.app.as_str()).collect::<Vec<_>>(), "PlannerNode: adding known hotkeys");
+    }
+    hotkeys::prompt_section(&entries, foreground.map(|e| e.app.as_str()))
+}
+
 /// Truncate to `max` chars with "…" if longer (for log display).
 fn truncate(s: &str, max: usize) -> String {
     let chars: Vec<char> = s.chars().collect();
@@ -0,0 +1,175 @@
+//! ReflectNode — patches the plan right after a step fails.
+//!
+//! Instead of carrying on with a plan that may no longer fit (and waiting
+//! for the verifier at the end), the vision model gets the failure, the
+//! current screen and the remaining steps, and either keeps the plan or
+//! replaces the remaining steps. Runs at most `MAX_REFLECTIONS` times per
+//! task; after that failures just move on as before.
+
+use async_trait::async_trait;
+
+use crate::agent_engine::context::NodeContext;
+use crate::agent_engine::node::{Node, NodeOutput};
+use crate::agent_engine::state::{AgentAction, SharedState, StepStatus};
+use crate::agent_engine::tool_parser::parse_plan_task;
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::llm::structured::{parse_json_reply, reflection_schema};
+use crate::llm::types::{ChatMessage, ContentPart, ImageUrl, MessageContent};
+use crate::perception::screenshot::{capture, data_url};
+use crate::prompts;
+
+const REFLECT_PROMPT: &str = include_str!("../../../prompts/system/reflect.md");
+
+/// Reflection turns allowed per task.
+pub const MAX_REFLECTIONS: u32 = 3;
+/// Log lines about the failure passed to the model.
+const FAILURE_LOG_LINES: usize = 4;
+
+pub struct ReflectNode;
+
+impl ReflectNode {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Whether the step just advanced past failed and a reflection is left.
+pub fn should_reflect(state: &SharedState) -> bool {
+    state.reflections < MAX_REFLECTIONS
+        && state
+            .current_step_idx
+            .checked_sub(1)
+            .and_then(|i| state.todo_steps.get(i))
+            .is_some_and(|s| s.status == StepStatus::Failed)
+}
+
+#[async_trait]
+impl Node for ReflectNode {
+    fn name(&self) -> &str {
+        "reflect"
+    }
+
+    async fn execute(
+        &self,
+        state: &mut SharedState,
+        ctx: &NodeContext,
+    ) -> SeeClawResult<NodeOutput> {
+        if state.is_stopped() {
+            return Ok(NodeOutput::End);
+        }
+        state.reflections += 1;
+
+        let idx = state.current_step_idx;
+        let failed = state.todo_steps.get(idx.saturating_sub(1)).map(|s| s.description.clone()).unwrap_or_default();
+        tracing::info!(step = idx, failed = %failed, reflection = state.reflections, "ReflectNode: step failed, reflecting");
+        let _ = ctx.app.emit("agent_activity", serde_json::json!({
+            "text": state.lang.pick(
+                format!("步骤 {} 失败，正在调整计划…", idx),
+                format!("Step {} failed — adjusting the plan…", idx),
+            )
+        }));
+
+        let shot = match capture(state.monitor).await {
+            Ok(shot) => shot,
+            Err(e) => {
+                tracing::warn!(error = %e, "ReflectNode: screenshot failed, keeping the plan");
+                return Ok(NodeOutput::Continue);
+            }
+        };
+        let _ = ctx.app.emit("viewport_captured", serde_json::json!({
+            "image_base64": &shot.image_base64,
+            "source": "reflect",
+        }));
+
+        let failure = state
+            .steps_log
+            .iter()
+            .rev()
+            .take(FAILURE_LOG_LINES)
+            .rev()
+            .cloned()
+            .collect::<Vec<_>>()
+            .join("\n");
+        let remaining = if idx < state.todo_steps.len() {
+            state.todo_steps[idx..]
+                .iter()
+                .enumerate()
+                .map(|(i, s)| format!("{}. {}", idx + i + 1, s.description))
+                .collect::<Vec<_>>()
+                .join("\n")
+        } else {
+            "(none — the failed step was the last one)".to_string()
+        };
+        let prompt = prompts::get("reflect.md", REFLECT_PROMPT)
+            .replace("{goal}", &state.goal)
+            .replace("{failed_step}", &failed)
+            .replace("{failure}", &failure)
+            .replace("{remaining}", &remaining);
+        let messages = vec![ChatMessage {
+            role: "user".into(),
+            content: MessageContent::Parts(vec![
+                ContentPart::ImageUrl {
+                    image_url: ImageUrl { url: data_url(&shot.image_base64) },
+                },
+                ContentPart::Text {
+                    text: format!("{prompt}\n\n{}", state.lang.prompt_directive()),
+                },
+            ]),
+            tool_call_id: None,
+            tool_calls: None,
+        }];
+
+        let (provider, mut cfg) = {
+            let reg = ctx.registry.lock().await;
+            reg.call_config_for_role("vision")?
+        };
+        ctx.loop_ctrl.lock().await.record_vlm_call();
+        cfg.silent = true;
+        cfg.response_schema = Some(reflection_schema());
+        cfg.cancel = state.cancel.clone();
+        let response = match provider.chat(messages, vec![], &cfg, &ctx.app).await {
+            Err(SeeClawError::Cancelled) => return Ok(NodeOutput::End),
+            Err(e) => {
+                tracing::warn!(error = %e, "ReflectNode: reflection call failed, keeping the plan");
+                return Ok(NodeOutput::Continue);
+            }
+            Ok(r) => r,
+        };
+
+        let Some(reply) = parse_json_reply(response.content.trim()) else {
+            tracing::warn!(raw = %response.content, "ReflectNode: unparseable reply, keeping the plan");
+            return Ok(NodeOutput::Continue);
+        };
+        let reason = reply["reason"].as_str().unwrap_or_default().to_string();
+        if reply["decision"].as_str() != Some("patch") {
+            tracing::info!(reason = %reason, "ReflectNode: keeping the remaining plan");
+            state.steps_log.push(format!("[Reflect] kept the plan: {reason}"));
+            return Ok(NodeOutput::Continue);
+        }
+
+        let Ok(AgentAction::PlanTask { steps, .. }) = parse_plan_task(&serde_json::json!({ "steps": reply["steps"] })) else {
+            return Ok(NodeOutput::Continue);
+        };
+        let patched = steps.len();
+        state.todo_steps.truncate(idx);
+        state.todo_steps.extend(steps.into_iter().enumerate().map(|(i, mut s)| {
+            s.index = idx + i;
+            s
+        }));
+        tracing::info!(reason = %reason, steps = patched, "ReflectNode: remaining plan replaced");
+        state.steps_log.push(format!("[Reflect] replaced the remaining plan with {patched} step(s): {reason}"));
+        let _ = ctx.app.emit("agent_activity", serde_json::json!({
+            "text": state.lang.pick(
+                format!("已调整计划：{reason}"),
+                format!("Plan adjusted: {reason}"),
+            )
+        }));
+        let _ = ctx.app.emit("todolist_updated", serde_json::json!({
+            "steps": &state.todo_steps,
+            "total": state.todo_steps.len(),
+            "completed": state.todo_steps.iter().filter(|s| s.status == StepStatus::Completed).count(),
+        }));
+
+        Ok(NodeOutput::Continue)
+    }
+}
@@ -6,7 +6,8 @@ use async_trait::async_trait;
 use crate::agent_engine::context::NodeContext;
 use crate::agent_engine::node::{Node, NodeOutput};
 use crate::agent_engine::router::RouterPipeline;
-use crate::agent_engine::state::SharedState;
+use crate::agent_engine::state::{RouteType, SharedState};
+use crate::errors::SeeClawResult;
 
 pub struct RouterNode {
     pipeline: RouterPipeline,
@@ -30,7 +31,7 @@ impl Node for RouterNode {
         &self,
         state: &mut SharedState,
         ctx: &NodeContext,
-    ) -> Result<NodeOutput, String> {
+    ) -> SeeClawResult<NodeOutput> {
         if state.is_stopped() {
             return Ok(NodeOutput::End);
         }
@@ -40,6 +41,12 @@ impl Node for RouterNode {
         let result = self.pipeline.classify(&state.goal, ctx).await;
 
         state.route_type = result.route_type.clone();
+        // Without terminal permission a shell-only route would be denied at
+        // execution; plan it as a normal task instead.
+        if state.route_type == RouteType::Terminal && !ctx.safety.allow_terminal_commands {
+            tracing::info!("RouterNode: terminal commands disabled — routing Terminal as Complex");
+            state.route_type = RouteType::Complex;
+        }
 
         tracing::info!(
             route = ?state.route_type,
@@ -8,12 +8,14 @@
 //! Flow: `router` → (Chat) → `simple_chat` → (end)
 
 use async_trait::async_trait;
-use tauri::Emitter;
 
 use crate::agent_engine::context::NodeContext;
-use crate::agent_engine::node::{poll_stop, Node, NodeOutput};
+use crate::agent_engine::continuity::with_carryover;
+use crate::agent_engine::node::{Node, NodeOutput};
 use crate::agent_engine::state::{GraphResult, SharedState};
+use crate::errors::{SeeClawError, SeeClawResult};
 use crate::llm::types::{ChatMessage, MessageContent, StreamChunk, StreamChunkKind};
+use crate::prompts;
 
 const SIMPLE_CHAT_SYSTEM: &str = include_str!("../../../prompts/system/simple_chat.md");
 
@@ -35,7 +37,7 @@ impl Node for SimpleChatNode {
         &self,
         state: &mut SharedState,
         ctx: &NodeContext,
-    ) -> Result<NodeOutput, String> {
+    ) -> SeeClawResult<NodeOutput> {
         if state.is_stopped() {
             return Ok(NodeOutput::End);
         }
@@ -43,13 +45,13 @@ impl Node for SimpleChatNode {
         tracing::info!(goal = %state.goal, "SimpleChatNode: answering conversational query");
         let _ = ctx.app.emit(
             "agent_activity",
-            serde_json::json!({ "text": "正在回复…" }),
+            serde_json::json!({ "text": state.lang.pick("正在回复…", "Replying…") }),
         );
 
         let messages = vec![
             ChatMessage {
                 role: "system".into(),
-                content: MessageContent::Text(SIMPLE_CHAT_SYSTEM.to_string()),
+                content: MessageContent::Text(with_carryover(&prompts::get("simple_chat.md", SIMPLE_CHAT_SYSTEM), &state.carryover_context)),
                 tool_call_id: None,
                 tool_calls: None,
             },
@@ -64,20 +66,16 @@ impl Node for SimpleChatNode {
         // Use the lightweight "chat" role — no tools needed
         let (provider, mut cfg) = {
             let reg = ctx.registry.lock().await;
-            reg.call_config_for_role("chat").map_err(|e| e.to_string())?
+            reg.call_config_for_role("chat")?
         };
         // Stream to frontend so the user sees the response in real-time
         cfg.silent = false;
         cfg.stream = true;
 
-        let flag = state.stop_flag.clone();
-        let response = tokio::select! {
-            result = provider.chat(messages, vec![], &cfg, &ctx.app) => {
-                result.map_err(|e| e.to_string())?
-            }
-            _ = poll_stop(flag) => {
-                return Ok(NodeOutput::End);
-            }
+        cfg.cancel = state.cancel.clone();
+        let response = match provider.chat(messages, vec![], &cfg, &ctx.app).await {
+            Err(SeeClawError::Cancelled) => return Ok(NodeOutput::End),
+            result => result?,
         };
 
         if state.is_stopped() {
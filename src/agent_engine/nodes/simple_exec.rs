@@ -6,21 +6,30 @@
 //! off to this dedicated node, which uses a minimal, tool-focused prompt to
 //! produce the single action needed.
 //!
-//! Flow: `router` → (Simple) → `simple_exec` → `action_exec` → `summarizer`
+//! The Terminal route uses the same node with `execute_terminal` as the only
+//! tool.
+//!
+//! Flow: `router` → (Simple / Terminal) → `simple_exec` → `action_exec` → `summarizer`
 
 use async_trait::async_trait;
-use tauri::Emitter;
 
 use crate::agent_engine::context::NodeContext;
-use crate::agent_engine::node::{poll_stop, Node, NodeOutput};
+use crate::agent_engine::continuity::with_carryover;
+use crate::agent_engine::node::{Node, NodeOutput};
 use crate::agent_engine::state::{RouteType, SharedState};
 use crate::agent_engine::tool_parser::parse_tool_call_to_action;
+use crate::errors::{SeeClawError, SeeClawResult};
 use crate::llm::tools::load_builtin_tools;
 use crate::llm::types::{ChatMessage, MessageContent};
+use crate::prompts;
 
 const SIMPLE_EXECUTOR_SYSTEM: &str =
     include_str!("../../../prompts/system/simple_exec.md");
 
+/// Appended to the system prompt on the Terminal route.
+const TERMINAL_ONLY_NOTE: &str = "This task is done in the terminal: answer with exactly one \
+execute_terminal call (chain commands in one line if needed). Do not touch the GUI.";
+
 pub struct SimpleExecNode;
 
 impl SimpleExecNode {
@@ -39,7 +48,7 @@ impl Node for SimpleExecNode {
         &self,
         state: &mut SharedState,
         ctx: &NodeContext,
-    ) -> Result<NodeOutput, String> {
+    ) -> SeeClawResult<NodeOutput> {
         if state.is_stopped() {
             return Ok(NodeOutput::End);
         }
@@ -51,14 +60,15 @@ impl Node for SimpleExecNode {
         // vision. Escalate immediately to ComplexVisual → Planner → VLM
         // instead of wasting 10-30s on an LLM call that will inevitably fail
         // or produce a terminal-command workaround.
-        if needs_vision(&state.goal) {
+        let terminal = state.route_type == RouteType::Terminal;
+        if !terminal && needs_vision(&state.goal) {
             tracing::info!(
                 goal = %state.goal,
                 "SimpleExecNode: task requires vision (click/GUI element) — escalating to ComplexVisual"
             );
             let _ = ctx.app.emit(
                 "agent_activity",
-                serde_json::json!({ "text": "该任务需要视觉，切换到视觉模式…" }),
+                serde_json::json!({ "text": state.lang.pick("该任务需要视觉，切换到视觉模式…", "This task needs vision — switching to visual mode…") }),
             );
             state.route_type = RouteType::ComplexVisual;
             return Ok(NodeOutput::GoTo("planner".to_string()));
@@ -66,12 +76,17 @@ impl Node for SimpleExecNode {
 
         let _ = ctx
             .app
-            .emit("agent_activity", serde_json::json!({ "text": "正在执行简单任务…" }));
+            .emit("agent_activity", serde_json::json!({ "text": state.lang.pick("正在执行简单任务…", "Running a simple task…") }));
 
+        let system = if terminal {
+            format!("{}\n\n{TERMINAL_ONLY_NOTE}", prompts::get("simple_exec.md", SIMPLE_EXECUTOR_SYSTEM))
+        } else {
+            prompts::get("simple_exec.md", SIMPLE_EXECUTOR_SYSTEM).into_owned()
+        };
         let messages = vec![
             ChatMessage {
                 role: "system".into(),
-                content: MessageContent::Text(SIMPLE_EXECUTOR_SYSTEM.to_string()),
+                content: MessageContent::Text(with_carryover(&system, &state.carryover_context)),
                 tool_call_id: None,
                 tool_calls: None,
             },
@@ -87,11 +102,13 @@ impl Node for SimpleExecNode {
         // only make sense inside the step loop (chat_agent / vlm_act). If they
         // leak here, the LLM will try to call switch_to_vlm instead of doing the
         // actual single-step action.
-        let tools = load_builtin_tools()
-            .map_err(|e| e.to_string())?
+        let tools = load_builtin_tools()?
             .into_iter()
             .filter(|t| {
                 let name = &t.function.name;
+                if terminal {
+                    return name == "execute_terminal";
+                }
                 !matches!(
                     name.as_str(),
                     "plan_task"
@@ -105,19 +122,16 @@ impl Node for SimpleExecNode {
 
         let (provider, mut cfg) = {
             let reg = ctx.registry.lock().await;
-            reg.call_config_for_role("tools").map_err(|e| e.to_string())?
+            reg.call_config_for_role("tools")?
         };
         cfg.silent = true;
 
-        let flag = state.stop_flag.clone();
-        let response = tokio::select! {
-            result = provider.chat(messages, tools, &cfg, &ctx.app) => {
-                result.map_err(|e| e.to_string())?
-            }
-            _ = poll_stop(flag) => {
-                return Ok(NodeOutput::End);
-            }
+        cfg.cancel = state.cancel.clone();
+        let response = match provider.chat(messages, tools, &cfg, &ctx.app).await {
+            Err(SeeClawError::Cancelled) => return Ok(NodeOutput::End),
+            result => result?,
         };
+        state.last_reasoning = response.reasoning.clone();
 
         if state.is_stopped() {
             return Ok(NodeOutput::End);
@@ -156,8 +170,9 @@ impl Node for SimpleExecNode {
         // Fallback: promote to the full planning path.
         // Use ComplexVisual (not Complex) because a Simple-route that failed
         // typically needs vision context (e.g. "click desktop icon" requires
-        // a screenshot to know WHERE to click).
-        state.route_type = RouteType::ComplexVisual;
+        // a screenshot to know WHERE to click). A terminal task that needs
+        // more than one command is planned without a screenshot.
+        state.route_type = if terminal { RouteType::Complex } else { RouteType::ComplexVisual };
         Ok(NodeOutput::GoTo("planner".to_string()))
     }
 }
@@ -1,13 +1,17 @@
 //! StabilityNode — waits for UI visual stability after an action.
 
 use async_trait::async_trait;
-use tauri::Emitter;
 
 use crate::agent_engine::context::NodeContext;
+use crate::agent_engine::event_bus::AgentMessage;
+use crate::agent_engine::focus;
+use crate::agent_engine::grounding::GroundedPoint;
 use crate::agent_engine::node::{Node, NodeOutput};
-use crate::agent_engine::state::SharedState;
-use crate::perception::screenshot::capture_primary;
-use crate::perception::stability::{wait_for_visual_stability, StabilityConfig};
+use crate::agent_engine::state::{AgentAction, SharedState};
+use crate::errors::SeeClawResult;
+use crate::perception::screenshot::{capture, ScreenshotResult};
+use crate::llm::types::MessageContent;
+use crate::perception::stability::{diff_frames, frame_hash, wait_for_visual_stability, StabilityConfig};
 
 pub struct StabilityNode;
 
@@ -27,44 +31,167 @@ impl Node for StabilityNode {
         &self,
         state: &mut SharedState,
         ctx: &NodeContext,
-    ) -> Result<NodeOutput, String> {
+    ) -> SeeClawResult<NodeOutput> {
         if state.is_stopped() {
             return Ok(NodeOutput::End);
         }
 
-        tracing::info!("StabilityNode: waiting for visual stability");
-        let _ = ctx.app.emit("agent_activity", serde_json::json!({ "text": "等待页面稳定…" }));
+        let max_wait_ms = ctx.stability.max_wait_for(&state.last_action_kind);
+        tracing::info!(action = %state.last_action_kind, max_wait_ms, "StabilityNode: waiting for visual stability");
+        let _ = ctx.app.emit("agent_activity", serde_json::json!({ "text": state.lang.pick("等待页面稳定…", "Waiting for the screen to settle…") }));
 
-        let config = StabilityConfig {
-            max_wait_ms: 3000,
-            check_interval_ms: 200,
-            stability_threshold: 0.02,
-            min_stable_frames: 2,
-        };
-
-        let stop_flag = state.stop_flag.clone();
-        let capture_fn = || async {
-            let result = capture_primary().await?;
-            Ok(result.image_bytes)
-        };
+        let started = std::time::Instant::now();
+        let stable = settle(state, ctx, max_wait_ms).await;
+        ctx.bus.publish(AgentMessage::VisualStable {
+            stable,
+            waited_ms: started.elapsed().as_millis() as u64,
+        });
+        if state.is_stopped() {
+            return Ok(NodeOutput::End);
+        }
 
-        match wait_for_visual_stability(capture_fn, config, stop_flag).await {
-            Ok(true) => {
-                tracing::info!("StabilityNode: visual stability achieved");
-            }
-            Ok(false) => {
-                tracing::warn!("StabilityNode: stability timeout or stopped");
-                if state.is_stopped() {
-                    return Ok(NodeOutput::End);
+        let pre_action = state.pre_action_frame.take();
+        let click_target = state.pending_click_target.take();
+        let click_check = state.pending_click_check.take();
+        let mut retry = false;
+        if pre_action.is_some() || click_target.is_some() || click_check.is_some() {
+            match capture(state.monitor).await {
+                Ok(shot) => {
+                    let mut screen_changed = None;
+                    if let Some(before) = pre_action {
+                        match diff_frames(&before, &shot.image_bytes) {
+                            Ok(diff) => {
+                                tracing::info!(changed = diff.changed(), regions = diff.regions.len(), "StabilityNode: post-action diff");
+                                attach_screen_note(state, &diff.note());
+                                screen_changed = Some(diff.changed());
+                            }
+                            Err(e) => tracing::debug!(error = %e, "StabilityNode: post-action diff failed"),
+                        }
+                    }
+                    // Without a pre-action frame, fall back to the hash of the
+                    // screenshot the click was chosen on.
+                    let screen_changed = screen_changed
+                        .or_else(|| state.last_frame_hash.map(|before| frame_hash(&shot.image_bytes) != before));
+                    // A click that left the screen untouched counts as a failed click.
+                    if let (Some(target), Some(changed)) = (click_target, screen_changed) {
+                        if !changed {
+                            tracing::warn!(element_id = %target, "StabilityNode: click had no visible effect");
+                            if state.element_cooldown.record_failure(&target, &state.detected_elements) {
+                                state.steps_log.push(format!("[cooldown] {target} unreachable: clicks had no visible effect"));
+                            }
+                        } else {
+                            state.element_cooldown.record_success(&target, &state.detected_elements);
+                        }
+                    }
+                    if let Some((element_id, point)) = click_check {
+                        retry = check_click(&element_id, point, &shot, state, ctx).await;
+                    }
                 }
-                // Timeout — proceed anyway
-            }
-            Err(e) => {
-                tracing::error!(error = %e, "StabilityNode: check failed, proceeding anyway");
+                Err(e) => tracing::debug!(error = %e, "StabilityNode: post-action capture failed"),
             }
         }
 
         state.needs_stability = false;
+        if retry {
+            return Ok(NodeOutput::GoTo("action_exec".to_string()));
+        }
         Ok(NodeOutput::Continue)
     }
 }
+
+/// Check a VLM click against the settled screen. When the target did not
+/// respond, queue a click on the next candidate the model offered and return
+/// true; without one, the click is marked failed.
+async fn check_click(
+    element_id: &str,
+    point: GroundedPoint,
+    shot: &ScreenshotResult,
+    state: &mut SharedState,
+    ctx: &NodeContext,
+) -> bool {
+    let goal = state
+        .todo_steps
+        .get(state.current_step_idx)
+        .map(|s| s.description.clone())
+        .unwrap_or_else(|| state.goal.clone());
+    let target = state.click_target.clone();
+    let Some(check) = focus::verify_click(element_id, point, target.as_deref(), &goal, shot, state, ctx).await else {
+        return false;
+    };
+    if check.activated {
+        return false;
+    }
+
+    let note = format!("Click check: {element_id} did not respond as intended ({}).", check.reason);
+    state.last_action_succeeded = false;
+    if let Some(entry) = state.step_action_history.last_mut() {
+        entry.push_str(" → FAILED: click check");
+    }
+    state.click_candidates.retain(|c| c.element_id != element_id);
+    match state.click_candidates.first().cloned() {
+        Some(next) => {
+            tracing::info!(element_id, next = %next.element_id, "StabilityNode: click check failed, trying the next candidate");
+            attach_screen_note(state, &format!("{note} Trying {} instead.", next.element_id));
+            state.action_certainty = next.confidence;
+            state.current_action = Some(AgentAction::MouseClick { element_id: next.element_id });
+            true
+        }
+        None => {
+            tracing::info!(element_id, "StabilityNode: click check failed");
+            attach_screen_note(state, &note);
+            false
+        }
+    }
+}
+
+/// Wait up to `max_wait_ms` for consecutive frames to stop changing. A
+/// timeout or failed capture is logged and otherwise ignored; returns whether
+/// the screen settled.
+pub(crate) async fn settle(state: &SharedState, ctx: &NodeContext, max_wait_ms: u64) -> bool {
+    let config = StabilityConfig {
+        max_wait_ms,
+        check_interval_ms: ctx.stability.check_interval_ms,
+        min_stable_frames: ctx.stability.min_stable_frames,
+        ..StabilityConfig::default()
+    };
+    let monitor = state.monitor;
+    let capture_fn = move || async move {
+        let result = capture(monitor).await?;
+        Ok(result.image_bytes)
+    };
+
+    match wait_for_visual_stability(capture_fn, config, state.stop_flag.clone()).await {
+        Ok(true) => {
+            tracing::info!("visual stability achieved");
+            true
+        }
+        Ok(false) => {
+            // Timeout (or stop) — proceed anyway
+            tracing::warn!(max_wait_ms, "stability timeout or stopped");
+            false
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "stability check failed, proceeding anyway");
+            false
+        }
+    }
+}
+
+/// Append the screen-change note to the action's tool result, as seen by the
+/// planner (`conv_messages`), the loop agents (`last_exec_result`) and the
+/// verifier (`steps_log`).
+fn attach_screen_note(state: &mut SharedState, note: &str) {
+    if let Some(MessageContent::Text(text)) = state
+        .conv_messages
+        .iter_mut()
+        .rev()
+        .find(|m| m.role == "tool")
+        .map(|m| &mut m.content)
+    {
+        text.push_str(&format!("\n{note}"));
+    }
+    state.last_exec_result.push_str(&format!("\n{note}"));
+    if let Some(line) = state.steps_log.last_mut() {
+        line.push_str(&format!(" [{note}]"));
+    }
+}
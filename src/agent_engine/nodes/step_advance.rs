@@ -1,11 +1,11 @@
 //! StepAdvanceNode — marks the current step complete and advances the index.
 
 use async_trait::async_trait;
-use tauri::Emitter;
 
 use crate::agent_engine::context::NodeContext;
 use crate::agent_engine::node::{Node, NodeOutput};
 use crate::agent_engine::state::{SharedState, StepStatus};
+use crate::errors::SeeClawResult;
 
 pub struct StepAdvanceNode;
 
@@ -25,7 +25,7 @@ impl Node for StepAdvanceNode {
         &self,
         state: &mut SharedState,
         ctx: &NodeContext,
-    ) -> Result<NodeOutput, String> {
+    ) -> SeeClawResult<NodeOutput> {
         if state.is_stopped() {
             return Ok(NodeOutput::End);
         }
@@ -71,6 +71,7 @@ impl Node for StepAdvanceNode {
         state.step_messages.clear();
         state.step_iterations = 0;
         state.step_action_history.clear();
+        state.click_refine_asked = false;
 
         Ok(NodeOutput::Continue)
     }
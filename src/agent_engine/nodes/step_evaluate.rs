@@ -8,11 +8,11 @@
 //! 4. **Continue** → loop back to the current agent for another iteration.
 
 use async_trait::async_trait;
-use tauri::Emitter;
 
 use crate::agent_engine::context::NodeContext;
 use crate::agent_engine::node::{Node, NodeOutput};
 use crate::agent_engine::state::{SharedState, StepMode, StepStatus};
+use crate::errors::SeeClawResult;
 
 /// Maximum iterations per step before forced advancement.
 /// VLM is expensive (screenshot + LLM), so it gets a lower cap.
@@ -37,7 +37,7 @@ impl Node for StepEvaluateNode {
         &self,
         state: &mut SharedState,
         ctx: &NodeContext,
-    ) -> Result<NodeOutput, String> {
+    ) -> SeeClawResult<NodeOutput> {
         if state.is_stopped() {
             return Ok(NodeOutput::End);
         }
@@ -66,7 +66,7 @@ impl Node for StepEvaluateNode {
         if state.step_complete {
             tracing::info!(step = idx, iterations = step_iterations, "[StepEvaluate] ✅ step complete after {} iters → step_advance", step_iterations);
             let _ = ctx.app.emit("agent_activity", serde_json::json!({
-                "text": format!("步骤 {} 完成", idx + 1)
+                "text": state.lang.pick(format!("步骤 {} 完成", idx + 1), format!("Step {} done", idx + 1))
             }));
             return Ok(NodeOutput::GoTo("step_advance".to_string()));
         }
@@ -124,7 +124,10 @@ impl Node for StepEvaluateNode {
                     idx + 1, successful_action_count, tier_label
                 ));
                 let _ = ctx.app.emit("agent_activity", serde_json::json!({
-                    "text": format!("步骤 {} 完成（自动确认）", idx + 1)
+                    "text": state.lang.pick(
+                        format!("步骤 {} 完成（自动确认）", idx + 1),
+                        format!("Step {} done (auto-confirmed)", idx + 1),
+                    )
                 }));
                 return Ok(NodeOutput::GoTo("step_advance".to_string()));
             }
@@ -162,9 +165,9 @@ impl Node for StepEvaluateNode {
 
         // Case 4: Continue the loop — route back to current agent
         let target = match state.current_loop_mode {
-            StepMode::Combo => {
-                // Combo should never loop — it executes atomically.
-                // If we get here, the combo finished; advance.
+            StepMode::Combo | StepMode::Explore => {
+                // Combo / explore never loop — they execute atomically.
+                // If we get here, the step finished; advance.
                 return Ok(NodeOutput::GoTo("step_advance".to_string()));
             }
             StepMode::Chat => "chat_agent",
@@ -188,7 +191,7 @@ impl Node for StepEvaluateNode {
 fn is_definitive_gui_action(kind: &str) -> bool {
     matches!(
         kind,
-        "mouse_click" | "mouse_double_click" | "mouse_right_click"
+        "mouse_click" | "mouse_double_click" | "mouse_right_click" | "mouse_click_at"
     )
 }
 
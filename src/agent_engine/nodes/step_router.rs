@@ -3,6 +3,9 @@
 //! The Planner only provides a `recommended_mode` hint. StepRouter makes the
 //! final decision using a lightweight multi-signal approach:
 //!
+//! 0. **Parallel group / explore request** — two or more consecutive non-GUI
+//!    steps sharing a `group` run together in `parallel_exec`; the planner's
+//!    `explore` hint is always honoured.
 //! 1. **Skill trigger matching** — if description matches a skill's triggers,
 //!    force Combo mode (zero LLM, fastest path).
 //! 2. **Keyword heuristics** — regex patterns for chat-like vs vlm-like tasks.
@@ -11,11 +14,14 @@
 //! This node also handles mode_switch_requested from loop agents.
 
 use async_trait::async_trait;
-use tauri::Emitter;
 
 use crate::agent_engine::context::NodeContext;
-use crate::agent_engine::node::{poll_stop, Node, NodeOutput};
+use crate::agent_engine::metrics::{self, Metric};
+use crate::agent_engine::node::{Node, NodeOutput};
+use crate::agent_engine::nodes::parallel_exec;
+use crate::agent_engine::nodes::stability::settle;
 use crate::agent_engine::state::{SharedState, StepMode, StepStatus};
+use crate::errors::SeeClawResult;
 
 pub struct StepRouterNode;
 
@@ -35,11 +41,18 @@ impl Node for StepRouterNode {
         &self,
         state: &mut SharedState,
         ctx: &NodeContext,
-    ) -> Result<NodeOutput, String> {
+    ) -> SeeClawResult<NodeOutput> {
         if state.is_stopped() {
             return Ok(NodeOutput::End);
         }
 
+        // Steps already finished inside a parallel group are skipped.
+        while state.todo_steps.get(state.current_step_idx).is_some_and(|s| {
+            matches!(s.status, StepStatus::Completed | StepStatus::Failed | StepStatus::Skipped)
+        }) {
+            state.current_step_idx += 1;
+        }
+
         let idx = state.current_step_idx;
         if idx >= state.todo_steps.len() {
             // All steps done → go to verifier
@@ -86,17 +99,37 @@ impl Node for StepRouterNode {
             "mode": &step.recommended_mode,
             "recommended_mode": &step.recommended_mode,
         }));
+        metrics::record(&*ctx.app, Metric::StepStarted { index: idx, description: step.description.clone() });
 
-        // Inter-step delay (give OS time to process previous UI action)
+        // Let the screen settle after the previous step before routing this one
         if idx > 0 {
-            tokio::select! {
-                _ = tokio::time::sleep(std::time::Duration::from_millis(500)) => {}
-                _ = poll_stop(state.stop_flag.clone()) => return Ok(NodeOutput::End),
+            let _ = ctx.app.emit("agent_state_changed", serde_json::json!({
+                "state": "waiting_for_stability",
+                "node": "step_router",
+            }));
+            settle(state, ctx, ctx.stability.between_steps_ms).await;
+            if state.is_stopped() {
+                return Ok(NodeOutput::End);
             }
         }
 
         // ── Decision logic ─────────────────────────────────────────────
 
+        // Signal 0: Independent non-GUI steps of one group run together
+        if let Some(range) = parallel_exec::branch_at(state, ctx) {
+            tracing::info!(steps = ?range, "[StepRouter] ✅ parallel group → parallel_exec");
+            return Ok(NodeOutput::GoTo("parallel_exec".to_string()));
+        }
+        let step = &mut state.todo_steps[idx];
+
+        // Signal 0: The planner asked to survey an unfamiliar app first
+        if step.recommended_mode == StepMode::Explore {
+            step.mode = StepMode::Explore;
+            state.current_loop_mode = StepMode::Explore;
+            tracing::info!(step = idx, "[StepRouter] ✅ Explore requested → explore");
+            return Ok(NodeOutput::GoTo("explore".to_string()));
+        }
+
         // Signal 1: If step has a combo skill, check if it exists in registry
         if step.recommended_mode == StepMode::Combo {
             if let Some(skill_name) = &step.skill {
@@ -185,6 +218,7 @@ fn mode_to_node(mode: &StepMode) -> String {
         StepMode::Combo => "combo_exec".to_string(),
         StepMode::Chat => "chat_agent".to_string(),
         StepMode::Vlm => "vlm_act".to_string(),
+        StepMode::Explore => "explore".to_string(),
     }
 }
 
@@ -8,14 +8,15 @@
 
 use async_trait::async_trait;
 use base64::Engine as _;
-use tauri::Emitter;
 
 use crate::agent_engine::context::NodeContext;
-use crate::agent_engine::node::{poll_stop, Node, NodeOutput};
+use crate::agent_engine::node::{Node, NodeOutput};
 use crate::agent_engine::nodes::visual_router::VisualDecisionPipeline;
 use crate::agent_engine::state::{GraphResult, SharedState};
+use crate::errors::{SeeClawError, SeeClawResult};
 use crate::llm::types::{ChatMessage, ContentPart, ImageUrl, MessageContent, StreamChunk, StreamChunkKind};
-use crate::perception::screenshot::capture_primary;
+use crate::perception::screenshot::{capture, data_url};
+use crate::prompts;
 
 const SUMMARIZER_PROMPT: &str = include_str!("../../../prompts/system/summarizer.md");
 
@@ -42,7 +43,7 @@ impl Node for SummarizerNode {
         &self,
         state: &mut SharedState,
         ctx: &NodeContext,
-    ) -> Result<NodeOutput, String> {
+    ) -> SeeClawResult<NodeOutput> {
         if state.is_stopped() {
             return Ok(NodeOutput::End);
         }
@@ -50,7 +51,7 @@ impl Node for SummarizerNode {
         tracing::info!(goal = %state.goal, "SummarizerNode: generating final response");
         let _ = ctx.app.emit(
             "agent_activity",
-            serde_json::json!({ "text": "正在总结回复…" }),
+            serde_json::json!({ "text": state.lang.pick("正在总结回复…", "Summarizing…") }),
         );
 
         // Build execution log context
@@ -60,9 +61,10 @@ impl Node for SummarizerNode {
             state.steps_log.join("\n")
         };
 
-        let system_prompt = SUMMARIZER_PROMPT
+        let system_prompt = prompts::get("summarizer.md", SUMMARIZER_PROMPT)
             .replace("{goal}", &state.goal)
             .replace("{steps_summary}", &steps_summary);
+        let system_prompt = format!("{system_prompt}\n\n{}", state.lang.prompt_directive());
 
         // Ask the 3-layer visual decision pipeline: regex → Bayesian → LLM.
         // Only acquires a screenshot when genuinely needed for the answer.
@@ -77,8 +79,8 @@ impl Node for SummarizerNode {
         );
 
         let (messages, role) = if needs_visual {
-            let _ = ctx.app.emit("agent_activity", serde_json::json!({ "text": "正在截取屏幕用于总结…" }));
-            match capture_primary().await {
+            let _ = ctx.app.emit("agent_activity", serde_json::json!({ "text": state.lang.pick("正在截取屏幕用于总结…", "Capturing the screen for the summary…") }));
+            match capture(state.monitor).await {
                 Ok(shot) => {
                     let screenshot_b64 =
                         base64::engine::general_purpose::STANDARD.encode(&shot.image_bytes);
@@ -89,7 +91,7 @@ impl Node for SummarizerNode {
                         "source": "summarizer",
                     }));
 
-                    let data_url = format!("data:image/png;base64,{screenshot_b64}");
+                    let data_url = data_url(&screenshot_b64);
                     let msgs = vec![
                         ChatMessage {
                             role: "system".into(),
@@ -137,20 +139,16 @@ impl Node for SummarizerNode {
 
         let (provider, mut cfg) = {
             let reg = ctx.registry.lock().await;
-            reg.call_config_for_role(role).map_err(|e| e.to_string())?
+            reg.call_config_for_role(role)?
         };
         // Stream to the user (silent = false means provider emits llm_stream_chunk)
         cfg.silent = false;
         cfg.stream = true;
 
-        let flag = state.stop_flag.clone();
-        let response = tokio::select! {
-            result = provider.chat(messages, vec![], &cfg, &ctx.app) => {
-                result.map_err(|e| e.to_string())?
-            }
-            _ = poll_stop(flag) => {
-                return Ok(NodeOutput::End);
-            }
+        cfg.cancel = state.cancel.clone();
+        let response = match provider.chat(messages, vec![], &cfg, &ctx.app).await {
+            Err(SeeClawError::Cancelled) => return Ok(NodeOutput::End),
+            result => result?,
         };
 
         if state.is_stopped() {
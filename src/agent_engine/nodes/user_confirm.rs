@@ -1,11 +1,14 @@
 //! UserConfirmNode — waits for human approval on high-risk actions.
 
 use async_trait::async_trait;
-use tauri::Emitter;
 
+use crate::agent_engine::approvals;
 use crate::agent_engine::context::NodeContext;
 use crate::agent_engine::node::{Node, NodeOutput};
-use crate::agent_engine::state::{AgentEvent, SharedState};
+use crate::agent_engine::nodes::action_exec::action_kind_tag;
+use crate::agent_engine::state::{AgentAction, AgentEvent, SharedState};
+use crate::errors::{ErrorCategory, SeeClawResult};
+use crate::executor::safety::{self, CommandRisk};
 
 pub struct UserConfirmNode;
 
@@ -25,7 +28,7 @@ impl Node for UserConfirmNode {
         &self,
         state: &mut SharedState,
         ctx: &NodeContext,
-    ) -> Result<NodeOutput, String> {
+    ) -> SeeClawResult<NodeOutput> {
         if state.is_stopped() {
             return Ok(NodeOutput::End);
         }
@@ -33,21 +36,46 @@ impl Node for UserConfirmNode {
         let action = state
             .current_action
             .as_ref()
-            .ok_or_else(|| "UserConfirmNode: no pending action")?;
+            .ok_or_else(|| "UserConfirmNode: no pending action".to_string())?;
 
         tracing::info!(?action, "UserConfirmNode: waiting for user approval");
 
+        let terminal = match action {
+            AgentAction::ExecuteTerminal { command, cwd, env, .. } => {
+                Some(safety::classify_command(command, cwd.as_deref(), env, &ctx.safety))
+            }
+            _ => None,
+        };
+
         // Emit approval request to frontend
+        let mut reason = match state.approval_confidence {
+            Some(c) => state.lang.pick(
+                format!("步骤 {}：目标置信度较低（{:.0}%）", state.current_step_idx + 1, c * 100.0),
+                format!("Step {}: low confidence in the target ({:.0}%)", state.current_step_idx + 1, c * 100.0),
+            ),
+            None => state.lang.pick(
+                format!("步骤 {}", state.current_step_idx + 1),
+                format!("Step {}", state.current_step_idx + 1),
+            ),
+        };
+        if let Some(v) = terminal.as_ref().filter(|v| v.risk == CommandRisk::High) {
+            reason.push_str(&state.lang.pick(format!("：高风险命令，{}", v.reason), format!(": high-risk command, {}", v.reason)));
+        }
         let req = serde_json::json!({
             "id": format!("step-{}", state.current_step_idx),
             "action": serde_json::to_value(action).unwrap_or_default(),
-            "reason": format!("步骤 {}", state.current_step_idx + 1),
+            "reason": reason,
+            "confidence": state.approval_confidence,
+            "risk": terminal.map(|v| v.risk),
             "timestamp": chrono::Utc::now().to_rfc3339(),
         });
+        approvals::set(req.clone());
         let _ = ctx.app.emit("action_required", &req);
 
         // Wait for user response via event channel
-        match state.event_rx.recv().await {
+        let response = state.event_rx.recv().await;
+        approvals::clear();
+        match response {
             Some(AgentEvent::UserApproved) => {
                 tracing::info!("UserConfirmNode: approved");
                 state.needs_approval = false;
@@ -57,10 +85,25 @@ impl Node for UserConfirmNode {
                 // Action is still in current_action — go to action_exec
                 Ok(NodeOutput::GoTo("action_exec".to_string()))
             }
-            Some(AgentEvent::UserRejected) | Some(AgentEvent::Stop) | None => {
+            evt @ (Some(AgentEvent::UserRejected) | Some(AgentEvent::Stop) | None) => {
                 tracing::info!("UserConfirmNode: rejected/stop");
-                state.current_action = None;
+                let (message, category) = match evt {
+                    Some(AgentEvent::UserRejected) => ("Rejected by user", ErrorCategory::ExecutorDenied),
+                    _ => ("Stopped by user", ErrorCategory::UserStop),
+                };
+                if let Some(action) = state.current_action.take() {
+                    let _ = ctx.app.emit("action_result", serde_json::json!({
+                        "action": action_kind_tag(&action),
+                        "ok": false,
+                        "message": message,
+                        "category": category,
+                    }));
+                }
                 state.needs_approval = false;
+                state.action_certainty = None;
+                state.approval_confidence = None;
+                state.skip_queued_tool_calls("an earlier call in this turn was not approved.");
+                state.refined_click = None;
                 // Skip this step
                 Ok(NodeOutput::GoTo("step_evaluate".to_string()))
             }
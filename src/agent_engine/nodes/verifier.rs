@@ -5,19 +5,19 @@
 //! - Fail → GoTo("planner") with failure context injected
 
 use async_trait::async_trait;
-use tauri::Emitter;
 
+use crate::agent_engine::app_map;
 use crate::agent_engine::context::NodeContext;
-use crate::agent_engine::node::{poll_stop, Node, NodeOutput};
+use crate::agent_engine::node::{Node, NodeOutput};
 use crate::agent_engine::state::SharedState;
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::llm::structured::{parse_json_reply, verification_schema};
 use crate::llm::types::{ChatMessage, ContentPart, ImageUrl, MessageContent};
-use crate::perception::screenshot::capture_primary;
+use crate::perception::screenshot::{capture, data_url};
+use crate::prompts;
 
 const VERIFIER_PROMPT: &str = include_str!("../../../prompts/system/verifier.md");
 
-/// Maximum number of replan cycles before giving up.
-const MAX_REPLAN_CYCLES: u32 = 2;
-
 pub struct VerifierNode;
 
 impl VerifierNode {
@@ -36,7 +36,7 @@ impl Node for VerifierNode {
         &self,
         state: &mut SharedState,
         ctx: &NodeContext,
-    ) -> Result<NodeOutput, String> {
+    ) -> SeeClawResult<NodeOutput> {
         if state.is_stopped() {
             return Ok(NodeOutput::End);
         }
@@ -47,22 +47,23 @@ impl Node for VerifierNode {
             "VerifierNode: verifying task completion"
         );
 
-        let _ = ctx.app.emit("agent_activity", serde_json::json!({ "text": "正在验证任务完成情况…" }));
+        let _ = ctx.app.emit("agent_activity", serde_json::json!({ "text": state.lang.pick("正在验证任务完成情况…", "Verifying the result…") }));
 
         // Check cycle limit — delegate to summarizer even on exhaustion
-        if state.cycle_count >= MAX_REPLAN_CYCLES {
+        let max_cycles = ctx.loop_ctrl.lock().await.max_cycles();
+        if state.cycle_count >= max_cycles {
             tracing::warn!("VerifierNode: max replan cycles reached → summarizer");
-            state.steps_log.push(format!(
-                "[验证] 已达到最大重试次数 ({})，任务可能未完全完成。",
-                state.cycle_count
+            state.steps_log.push(state.lang.pick(
+                format!("[验证] 已达到最大重试次数 ({})，任务可能未完全完成。", state.cycle_count),
+                format!("[Verify] Reached the retry limit ({}); the task may not be fully complete.", state.cycle_count),
             ));
             return Ok(NodeOutput::GoTo("summarizer".to_string()));
         }
 
         // Capture final screenshot
-        let shot = capture_primary().await.map_err(|e| e.to_string())?;
+        let shot = capture(state.monitor).await?;
         let b64 = &shot.image_base64;
-        let data_url = format!("data:image/jpeg;base64,{b64}");
+        let data_url = data_url(b64);
 
         // Show the verification screenshot to the user
         let _ = ctx.app.emit("viewport_captured", serde_json::json!({
@@ -72,7 +73,7 @@ impl Node for VerifierNode {
 
         // Build verification prompt
         let steps_summary = state.steps_log.join("\n");
-        let verify_prompt = VERIFIER_PROMPT
+        let verify_prompt = prompts::get("verifier.md", VERIFIER_PROMPT)
             .replace("{goal}", &state.goal)
             .replace("{steps_summary}", &steps_summary);
 
@@ -92,18 +93,16 @@ impl Node for VerifierNode {
 
         let (provider, mut cfg) = {
             let reg = ctx.registry.lock().await;
-            reg.call_config_for_role("vision").map_err(|e| e.to_string())?
+            reg.call_config_for_role("vision")?
         };
+        ctx.loop_ctrl.lock().await.record_vlm_call();
         cfg.silent = true;
+        cfg.response_schema = Some(verification_schema());
 
-        let flag = state.stop_flag.clone();
-        let response = tokio::select! {
-            result = provider.chat(messages, vec![], &cfg, &ctx.app) => {
-                result.map_err(|e| e.to_string())?
-            }
-            _ = poll_stop(flag) => {
-                return Ok(NodeOutput::End);
-            }
+        cfg.cancel = state.cancel.clone();
+        let response = match provider.chat(messages, vec![], &cfg, &ctx.app).await {
+            Err(SeeClawError::Cancelled) => return Ok(NodeOutput::End),
+            result => result?,
         };
 
         if state.is_stopped() {
@@ -122,14 +121,8 @@ impl Node for VerifierNode {
 
         // Parse verification result
         let raw = response.content.trim();
-        let json_str = raw
-            .trim_start_matches("```json")
-            .trim_start_matches("```")
-            .trim_end_matches("```")
-            .trim();
-
-        let (pass, reason) = match serde_json::from_str::<serde_json::Value>(json_str) {
-            Ok(v) => {
+        let (pass, reason) = match parse_json_reply(raw) {
+            Some(v) => {
                 let pass = v["pass"].as_bool().unwrap_or(false)
                     || v["result"].as_str() == Some("pass")
                     || v["verified"].as_bool().unwrap_or(false);
@@ -140,7 +133,7 @@ impl Node for VerifierNode {
                     .to_string();
                 (pass, reason)
             }
-            Err(_) => {
+            None => {
                 // If we can't parse JSON, check for keywords
                 let lower = raw.to_lowercase();
                 let pass = lower.contains("pass") || lower.contains("success") || lower.contains("completed");
@@ -151,7 +144,7 @@ impl Node for VerifierNode {
         if pass {
             tracing::info!(reason = %reason, "VerifierNode: PASS → summarizer");
             if !reason.is_empty() {
-                state.steps_log.push(format!("[验证通过] {reason}"));
+                state.steps_log.push(state.lang.pick(format!("[验证通过] {reason}"), format!("[Verified] {reason}")));
             }
             // Delegate human-readable response generation to SummarizerNode
             Ok(NodeOutput::GoTo("summarizer".to_string()))
@@ -159,14 +152,21 @@ impl Node for VerifierNode {
             tracing::warn!(reason = %reason, cycle = state.cycle_count, "VerifierNode: FAIL → replan");
 
             // Inject failure context into conversation
+            let mut text = format!(
+                "Verification failed. Reason: {reason}\n\
+                 Please re-plan to complete the goal: {}\n\
+                 This is retry cycle {}.",
+                state.goal, state.cycle_count
+            );
+            if let Some(note) = state.element_cooldown.prompt_note(&state.detected_elements) {
+                text.push_str(&format!("\n{note}"));
+            }
+            if let Some(section) = app_map::prompt_section(&state.app_maps) {
+                text.push_str(&format!("\n{section}"));
+            }
             state.conv_messages.push(ChatMessage {
                 role: "user".into(),
-                content: MessageContent::Text(format!(
-                    "Verification failed. Reason: {reason}\n\
-                     Please re-plan to complete the goal: {}\n\
-                     This is retry cycle {}.",
-                    state.goal, state.cycle_count
-                )),
+                content: MessageContent::Text(text),
                 tool_call_id: None,
                 tool_calls: None,
             });
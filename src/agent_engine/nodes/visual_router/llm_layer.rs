@@ -8,7 +8,10 @@ use async_trait::async_trait;
 use crate::agent_engine::context::NodeContext;
 use crate::agent_engine::nodes::visual_router::layer::{VisualDecisionLayer, VisualDecisionResult};
 use crate::agent_engine::state::TodoStep;
+use crate::errors::SeeClawError;
+use crate::llm::structured::{parse_json_reply, visual_decision_schema};
 use crate::llm::types::{ChatMessage, MessageContent};
+use crate::prompts;
 
 const VISUAL_ROUTER_PROMPT: &str = include_str!("../../../../prompts/system/visual_router.md");
 
@@ -46,6 +49,8 @@ impl VisualDecisionLayer for VisualLlmLayer {
         cfg.stream = false;
         cfg.silent = true;
         cfg.json_mode = true;
+        cfg.response_schema = Some(visual_decision_schema());
+        cfg.timeout = Some(std::time::Duration::from_secs(15));
 
         let log_summary = if steps_log.is_empty() {
             "(no steps executed)".to_string()
@@ -56,7 +61,7 @@ impl VisualDecisionLayer for VisualLlmLayer {
         let messages = vec![
             ChatMessage {
                 role: "system".into(),
-                content: MessageContent::Text(VISUAL_ROUTER_PROMPT.to_string()),
+                content: MessageContent::Text(prompts::get("visual_router.md", VISUAL_ROUTER_PROMPT).into_owned()),
                 tool_call_id: None,
                 tool_calls: None,
             },
@@ -70,25 +75,13 @@ impl VisualDecisionLayer for VisualLlmLayer {
             },
         ];
 
-        let result = tokio::time::timeout(
-            std::time::Duration::from_secs(15),
-            provider.chat(messages, vec![], &cfg, &ctx.app),
-        )
-        .await;
-
-        match result {
-            Ok(Ok(response)) => {
+        match provider.chat(messages, vec![], &cfg, &ctx.app).await {
+            Ok(response) => {
                 let raw = response.content.trim();
                 tracing::debug!(layer = "visual_llm", raw = %raw, "LLM response");
 
-                let json_str = raw
-                    .trim_start_matches("```json")
-                    .trim_start_matches("```")
-                    .trim_end_matches("```")
-                    .trim();
-
-                match serde_json::from_str::<serde_json::Value>(json_str) {
-                    Ok(v) => {
+                match parse_json_reply(raw) {
+                    Some(v) => {
                         let needs_visual = v["needs_visual"].as_bool().unwrap_or(false);
                         let confidence = v["confidence"].as_f64().unwrap_or(0.7) as f32;
                         tracing::info!(
@@ -100,18 +93,18 @@ impl VisualDecisionLayer for VisualLlmLayer {
                         );
                         Some(VisualDecisionResult { needs_visual, confidence })
                     }
-                    Err(e) => {
-                        tracing::warn!(error = %e, raw = %json_str, "visual_router LLM JSON parse failed — defaulting to false");
+                    None => {
+                        tracing::warn!(raw = %raw, "visual_router LLM JSON parse failed — defaulting to false");
                         Some(VisualDecisionResult { needs_visual: false, confidence: 0.5 })
                     }
                 }
             }
-            Ok(Err(e)) => {
-                tracing::warn!(error = %e, "visual_router LLM call failed — defaulting to false");
+            Err(SeeClawError::ProviderTimeout(_)) => {
+                tracing::warn!("visual_router LLM call timed out — defaulting to false");
                 Some(VisualDecisionResult { needs_visual: false, confidence: 0.5 })
             }
-            Err(_) => {
-                tracing::warn!("visual_router LLM call timed out — defaulting to false");
+            Err(e) => {
+                tracing::warn!(error = %e, "visual_router LLM call failed — defaulting to false");
                 Some(VisualDecisionResult { needs_visual: false, confidence: 0.5 })
             }
         }
@@ -13,17 +13,23 @@
 
 use async_trait::async_trait;
 use base64::Engine as _;
-use tauri::Emitter;
 
+use crate::agent_engine::app_map;
+use crate::agent_engine::candidates;
 use crate::agent_engine::context::NodeContext;
-use crate::agent_engine::node::{poll_stop, Node, NodeOutput};
-use crate::agent_engine::state::{SharedState, StepMode, StepStatus};
+use crate::agent_engine::event_bus::AgentMessage;
+use crate::agent_engine::metrics::{self, Metric};
+use crate::agent_engine::focus;
+use crate::agent_engine::node::{Node, NodeOutput};
+use crate::agent_engine::state::{AgentAction, SharedState, StepMode, StepStatus};
 use crate::agent_engine::tool_parser::parse_action_by_name;
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::llm::structured::parse_json_reply;
 use crate::llm::tools::load_builtin_tools;
 use crate::llm::types::{ChatMessage, ContentPart, ImageUrl, MessageContent};
 use crate::perception::annotator;
-use crate::perception::screenshot::capture_primary;
-use crate::perception::som_grid::draw_som_grid;
+use crate::perception::screenshot::{capture, data_url};
+use crate::perception::stability::frame_hash;
 
 /// Maximum VLM iterations per step (must match step_evaluate::MAX_VLM_ITERATIONS).
 const MAX_VLM_ITERATIONS: u32 = 4;
@@ -39,7 +45,8 @@ You are a GUI automation agent that interacts with a computer screen.
 You observe screenshots, reason about what you see, and execute ONE action per turn.
 
 ## Available tools
-mouse_click, mouse_double_click, mouse_right_click, scroll, type_text, hotkey, key_press, wait, finish_step, switch_to_chat.
+mouse_click, mouse_double_click, mouse_right_click, mouse_click_at, scroll, type_text, hotkey, key_press, keyboard_navigate, wait, finish_step, switch_to_chat.
+Tools named `mcp__<server>__<tool>` come from connected MCP servers; use one when it does the step more directly than the GUI.
 
 ## Core rules
 1. ONE action per turn. Observe the screenshot, decide, act. You will see the result in the next turn.
@@ -51,12 +58,17 @@ mouse_click, mouse_double_click, mouse_right_click, scroll, type_text, hotkey, k
 For mouse_click, use the `element_id` parameter:
 - PREFERRED: Use element IDs from the detected elements list (e.g. \"UI_7\"). Match the element by its content/label text, NOT just by visual position.
 - FALLBACK: If the target is NOT in the detected elements list, use grid coordinates (e.g. \"C4\", \"E7\") based on the grid overlay on the screenshot.
+- LAST RESORT: If neither an element nor a grid cell fits (e.g. a tiny control between cells), use `mouse_click_at` with the position in percent of the screenshot's width and height.
 - Read the element list carefully. Match by content text (e.g. if looking for '英雄联盟', find the element whose content contains that text).
+- Element IDs are stable across screenshots: the same control keeps its ID from one turn to the next.
+- Each element shows where it came from: `uia` (accessibility tree, exact name and bounds), `yolo` (visual detector, may be a phantom), `merged` (confirmed by both). Prefer `merged`/`uia` elements; check a low-confidence `yolo` element against the screenshot before clicking it.
+- Say what you mean to click in `target` (e.g. \"Save button in the toolbar\") and rate your pick with `confidence`.
+- If two or three elements could be the target, put the others in `alternatives` with their own `confidence`. An uncertain pick is checked before it is clicked; don't list alternatives when you are sure.
 
 ## Anti-loop rules (CRITICAL)
 5. If your previous action succeeded (screen changed as expected), call `finish_step` with a summary. Do NOT repeat the action.
 6. If you already performed a click/type and the screen shows the expected result, call `finish_step` immediately.
-7. If the same action failed twice, try a different approach (different coordinates, different element, scroll first). Do NOT retry the exact same action.
+7. If the same action failed twice, try a different approach (different coordinates, different element, scroll first, or `keyboard_navigate` to the control by name). Do NOT retry the exact same action.
 8. If you cannot find the target element after scrolling, call `finish_step` with a failure message rather than looping.
 9. Before acting, verify the previous action's effect by comparing the current screenshot with your memory of what you did.
 10. Never click the same coordinates more than once if the first click succeeded.
@@ -79,7 +91,7 @@ impl Node for VlmActNode {
         &self,
         state: &mut SharedState,
         ctx: &NodeContext,
-    ) -> Result<NodeOutput, String> {
+    ) -> SeeClawResult<NodeOutput> {
         if state.is_stopped() {
             return Ok(NodeOutput::End);
         }
@@ -103,15 +115,24 @@ impl Node for VlmActNode {
             "[VlmAct] iter={} goal='{}'", iter, truncate(vlm_goal, 80)
         );
         let _ = ctx.app.emit("agent_activity", serde_json::json!({
-            "text": format!("VLM 观察屏幕 (第{}次)…", iter)
+            "text": state.lang.pick(
+                format!("VLM 观察屏幕 (第{}次)…", iter),
+                format!("VLM looking at the screen (pass {})…", iter),
+            )
         }));
 
         // ── Capture screenshot & run perception pipeline ─────────────────
-        let shot = capture_primary().await.map_err(|e| e.to_string())?;
+        let shot = capture(state.monitor).await?;
         state.last_meta = Some(shot.meta.clone());
+        state.last_frame_hash = Some(frame_hash(&shot.image_bytes));
 
-        let (image_b64, elements) = run_perception(ctx, &shot).await?;
+        let (image_b64, elements) = run_perception(ctx, &shot, state).await?;
         state.detected_elements = elements.clone();
+        let click_effect = state.tracked_click.take().and_then(|id| {
+            let change = state.element_tracker.change(&id)?;
+            tracing::debug!(element_id = %id, ?change, "[VlmAct] clicked element after action");
+            Some(format!("The element you clicked ({id}) is {}.\n", change.describe()))
+        });
 
         // Build text listing of detected elements so VLM has both visual AND textual info
         let element_list_text = annotator::build_element_list(&elements);
@@ -125,7 +146,7 @@ impl Node for VlmActNode {
 
         // ── Build / extend conversation in step_messages ─────────────────
         let max_iters = MAX_VLM_ITERATIONS;
-        let data_url = format!("data:image/png;base64,{image_b64}");
+        let data_url = data_url(&image_b64);
 
         if state.step_messages.is_empty() {
             // First iteration: system prompt + initial user message with screenshot
@@ -147,6 +168,12 @@ impl Node for VlmActNode {
                 "\nUse element IDs (e.g. UI_7) from the list above for mouse_click. \
                  If the target element is NOT in the list, you can use grid coordinates (e.g. \"C4\") instead.\n"
             );
+            if let Some(note) = state.element_cooldown.prompt_note(&state.detected_elements) {
+                user_text.push_str(&format!("\n{note}\n"));
+            }
+            if let Some(section) = app_map::prompt_section(&state.app_maps) {
+                user_text.push_str(&format!("\n{section}"));
+            }
 
             state.step_messages = vec![
                 ChatMessage {
@@ -197,6 +224,9 @@ impl Node for VlmActNode {
                      Compare this screenshot with the previous state. If the sub-goal is achieved, call `finish_step`.\n",
                     state.last_action_kind, state.last_exec_result
                 ));
+                if let Some(effect) = &click_effect {
+                    feedback_text.push_str(effect);
+                }
             } else if !state.last_action_kind.is_empty() {
                 feedback_text.push_str(&format!(
                     "Previous action `{}` FAILED. Result: {}\n\
@@ -209,6 +239,9 @@ impl Node for VlmActNode {
                     "WARNING: This is your last iteration. You MUST call `finish_step` now.\n"
                 );
             }
+            if let Some(note) = state.element_cooldown.prompt_note(&state.detected_elements) {
+                feedback_text.push_str(&format!("{note}\n"));
+            }
             // Inject updated element list for this new screenshot
             feedback_text.push_str(&format!("\n{element_list_text}\n"));
 
@@ -228,37 +261,35 @@ impl Node for VlmActNode {
         // ── Strip old images (sliding window) ────────────────────────────
         strip_old_images(&mut state.step_messages, MAX_RECENT_IMAGES);
 
-        // ── Filter tools to VLM-relevant set ─────────────────────────────
-        let tools = load_builtin_tools()
-            .map_err(|e| e.to_string())?
+        // ── Filter tools to VLM-relevant set, plus MCP tools ─────────────
+        let mut tools = load_builtin_tools()?
             .into_iter()
             .filter(|t| {
                 matches!(
                     t.function.name.as_str(),
-                    "mouse_click" | "mouse_double_click" | "mouse_right_click"
-                        | "scroll" | "type_text" | "hotkey" | "key_press"
+                    "mouse_click" | "mouse_double_click" | "mouse_right_click" | "mouse_click_at"
+                        | "scroll" | "type_text" | "hotkey" | "key_press" | "keyboard_navigate"
                         | "wait" | "finish_step" | "switch_to_chat"
                 )
             })
             .collect::<Vec<_>>();
+        tools.extend(ctx.mcp.tool_defs().await);
 
         let (provider, mut cfg) = {
             let reg = ctx.registry.lock().await;
-            reg.call_config_for_role("vision").map_err(|e| e.to_string())?
+            reg.call_config_for_role("vision")?
         };
+        ctx.loop_ctrl.lock().await.record_vlm_call();
         cfg.silent = true;
 
         // ── Call VLM with full conversation ──────────────────────────────
         let messages = state.step_messages.clone();
-        let flag = state.stop_flag.clone();
-        let response = tokio::select! {
-            result = provider.chat(messages, tools, &cfg, &ctx.app) => {
-                result.map_err(|e| e.to_string())?
-            }
-            _ = poll_stop(flag) => {
-                return Ok(NodeOutput::End);
-            }
+        cfg.cancel = state.cancel.clone();
+        let response = match provider.chat(messages, tools, &cfg, &ctx.app).await {
+            Err(SeeClawError::Cancelled) => return Ok(NodeOutput::End),
+            result => result?,
         };
+        state.last_reasoning = response.reasoning.clone();
 
         if state.is_stopped() {
             return Ok(NodeOutput::End);
@@ -321,6 +352,7 @@ impl Node for VlmActNode {
                     state.pending_tool_id = tc.id.clone();
                     match parse_action_by_name(name, &args) {
                         Ok(action) => {
+                            record_click_args(state, &args);
                             state.current_action = Some(action);
                         }
                         Err(e) => {
@@ -344,15 +376,8 @@ impl Node for VlmActNode {
             }
         } else {
             // No tool call — try parsing JSON from content (fallback)
-            let raw = response.content.trim();
-            let json_str = raw
-                .trim_start_matches("```json")
-                .trim_start_matches("```")
-                .trim_end_matches("```")
-                .trim();
-
-            match serde_json::from_str::<serde_json::Value>(json_str) {
-                Ok(v) => {
+            match parse_json_reply(&response.content) {
+                Some(v) => {
                     let name = v["name"].as_str()
                         .or_else(|| v["tool_call"]["name"].as_str());
                     let args = v.get("arguments")
@@ -372,6 +397,7 @@ impl Node for VlmActNode {
                             }
                             _ => match parse_action_by_name(name, args) {
                                 Ok(action) => {
+                                    record_click_args(state, args);
                                     state.current_action = Some(action);
                                 }
                                 Err(e) => {
@@ -393,7 +419,7 @@ impl Node for VlmActNode {
                         return Ok(NodeOutput::GoTo("step_evaluate".to_string()));
                     }
                 }
-                Err(_) => {
+                None => {
                     tracing::warn!("VlmActNode: couldn't parse VLM response");
                     state.steps_log.push("FAIL: VLM act response unparseable".to_string());
                     if let Some(step) = state.todo_steps.get_mut(idx) {
@@ -404,10 +430,81 @@ impl Node for VlmActNode {
             }
         }
 
+        // ── Uncertain picks and small / ambiguous click targets ──────────
+        state.refined_click = None;
+        let may_ask = !std::mem::take(&mut state.click_refine_asked);
+        let target = match &state.current_action {
+            Some(
+                AgentAction::MouseClick { element_id }
+                | AgentAction::MouseDoubleClick { element_id }
+                | AgentAction::MouseRightClick { element_id },
+            ) => Some(element_id.clone()),
+            _ => None,
+        };
+        let Some(element_id) = target else {
+            return Ok(NodeOutput::Continue);
+        };
+        if candidates::is_ambiguous(&state.click_candidates) {
+            let options = candidates::describe(&state.click_candidates);
+            let chosen = if ctx.perception_cfg.enable_focus_crop {
+                focus::choose_candidate(&state.click_candidates, state.click_target.as_deref(), vlm_goal, &shot, state, ctx)
+                    .await
+            } else {
+                None
+            };
+            match chosen {
+                Some((chosen_id, point)) => {
+                    if let Some(
+                        AgentAction::MouseClick { element_id }
+                        | AgentAction::MouseDoubleClick { element_id }
+                        | AgentAction::MouseRightClick { element_id },
+                    ) = state.current_action.as_mut()
+                    {
+                        *element_id = chosen_id.clone();
+                    }
+                    state.action_certainty =
+                        state.click_candidates.iter().find(|c| c.element_id == chosen_id).and_then(|c| c.confidence);
+                    state.refined_click = Some((chosen_id, point));
+                }
+                None if may_ask => {
+                    tracing::info!(step = idx, iter, candidates = %options, "[VlmAct] uncertain click target, asking the model to refine it");
+                    state.current_action = None;
+                    state.click_refine_asked = true;
+                    // A text-only reply has no tool call to answer.
+                    let answered_call = state.step_messages.last().is_some_and(|m| m.tool_calls.is_some());
+                    state.step_messages.push(ChatMessage {
+                        role: if answered_call { "tool" } else { "user" }.into(),
+                        content: MessageContent::Text(format!(
+                            "Not clicked: the target is uncertain ({options}). Look again and click the one element \
+                             that matches the sub-goal, describing it in `target`; if none fits, scroll or use \
+                             keyboard_navigate."
+                        )),
+                        tool_call_id: answered_call.then(|| state.pending_tool_id.clone()),
+                        tool_calls: None,
+                    });
+                    return Ok(NodeOutput::GoTo("step_evaluate".to_string()));
+                }
+                None => {
+                    tracing::info!(step = idx, iter, candidates = %options, "[VlmAct] still uncertain after refining, clicking the pick");
+                }
+            }
+        } else if ctx.perception_cfg.enable_focus_crop {
+            if let Some(point) = focus::refine_click(&element_id, vlm_goal, &shot, state, ctx).await {
+                state.refined_click = Some((element_id, point));
+            }
+        }
+
         Ok(NodeOutput::Continue)
     }
 }
 
+/// Keep the click-target details from a tool call's arguments.
+fn record_click_args(state: &mut SharedState, args: &serde_json::Value) {
+    state.action_certainty = args["confidence"].as_f64().map(|c| c as f32);
+    state.click_candidates = candidates::parse(args);
+    state.click_target = args["target"].as_str().map(str::trim).filter(|t| !t.is_empty()).map(str::to_string);
+}
+
 /// Strip images from older messages, keeping only the most recent `keep` images.
 /// Older images are replaced with a text placeholder: "[Previous screenshot]".
 /// This is the CUA-style `only_n_most_recent_images` strategy.
@@ -454,31 +551,25 @@ fn strip_old_images(messages: &mut [ChatMessage], keep: usize) {
 async fn run_perception(
     ctx: &NodeContext,
     shot: &crate::perception::screenshot::ScreenshotResult,
-) -> Result<(String, Vec<crate::perception::types::UIElement>), String> {
-    let mut detector = ctx.yolo_detector.lock().await;
-    let mut elements = if let Some(ref mut det) = *detector {
-        det.detect(&shot.image_bytes).unwrap_or_default()
-    } else {
-        Vec::new()
+    state: &mut SharedState,
+) -> SeeClawResult<(String, Vec<crate::perception::types::UIElement>)> {
+    let started = std::time::Instant::now();
+    let perceived = ctx
+        .perception
+        .run_tracked(&shot.image_bytes, &shot.meta, &mut state.element_tracker)
+        .await?;
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    ctx.bus.publish(AgentMessage::PerceptionReady { elements: perceived.elements.len(), elapsed_ms });
+    metrics::record(&*ctx.app, Metric::PerceptionDone {
+        ms: elapsed_ms,
+        elements: perceived.elements.len(),
+        parsers: perceived.timings.clone(),
+    });
+    let b64 = match perceived.image_base64 {
+        Some(b64) => b64,
+        None => base64::engine::general_purpose::STANDARD.encode(&shot.image_bytes),
     };
-
-    if ctx.perception_cfg.enable_ui_automation {
-        if let Ok(uia) = crate::perception::ui_automation::collect_ui_elements(&shot.meta).await {
-            crate::perception::ui_automation::merge_detections(&mut elements, uia, 0.3);
-        }
-    }
-
-    if !elements.is_empty() {
-        let annotated = annotator::annotate_image(&shot.image_bytes, &elements)
-            .map_err(|e| e.to_string())?;
-        let b64 = base64::engine::general_purpose::STANDARD.encode(&annotated);
-        Ok((b64, elements))
-    } else {
-        let grid = draw_som_grid(&shot.image_bytes, ctx.grid_n)
-            .unwrap_or_else(|_| shot.image_bytes.clone());
-        let b64 = base64::engine::general_purpose::STANDARD.encode(&grid);
-        Ok((b64, Vec::new()))
-    }
+    Ok((b64, perceived.elements))
 }
 
 /// Truncate to `max` chars with "…" if longer (for log display).
@@ -0,0 +1,251 @@
+//! Session replay — step through a saved session, or run its actions again.
+//!
+//! `visual` mode emits each goal and action of the session as a
+//! `replay_step` event, with its thumbnail, at the pace it originally ran.
+//! `execute` mode also performs the GUI actions: clicks go to the screen
+//! point recorded in the action's result (not re-grounded), typing, keys,
+//! waits, scrolls, app launches and clipboard writes run as recorded.
+//! Actions that failed originally, read-only tools, and anything that would
+//! need approval under the current `[safety]` settings (terminal commands,
+//! file changes, MCP and skill calls by default) are skipped. A
+//! `replay_finished` event closes either mode.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::agent_engine::constraints;
+use crate::agent_engine::history::{self, SessionEntry};
+use crate::agent_engine::node::{wait_stop, StopSignal};
+use crate::agent_engine::nodes::action_exec::{action_kind_tag, click_point, compact_action_label};
+use crate::agent_engine::state::AgentAction;
+use crate::config::AppConfig;
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::events::EventSink;
+use crate::executor::{clipboard, input, keyboard_nav, launcher, safety};
+use crate::perception::exclusion;
+
+/// Gaps between recorded steps are clamped to this range.
+const MIN_GAP_MS: u64 = 300;
+const MAX_GAP_MS: u64 = 5000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayMode {
+    /// Show the recorded steps one by one; nothing is executed.
+    Visual,
+    /// Perform the recorded GUI actions again.
+    Execute,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    Shown,
+    Executed,
+    Skipped,
+    Failed,
+}
+
+/// Totals of one replay, also sent as `replay_finished`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReplayReport {
+    pub session_id: String,
+    pub steps: usize,
+    pub executed: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub stopped: bool,
+}
+
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+fn stop_signal() -> Arc<StopSignal> {
+    static STOP: OnceLock<Arc<StopSignal>> = OnceLock::new();
+    STOP.get_or_init(|| Arc::new(StopSignal::new())).clone()
+}
+
+/// Stop the replay in progress after its current step.
+pub fn stop() {
+    stop_signal().set();
+}
+
+/// Replay `session_id` in `mode`. Only one replay runs at a time.
+pub async fn replay_session(
+    session_id: &str,
+    mode: ReplayMode,
+    config: &AppConfig,
+    sink: &dyn EventSink,
+) -> SeeClawResult<ReplayReport> {
+    if RUNNING.swap(true, Ordering::SeqCst) {
+        return Err(SeeClawError::Agent("a replay is already running".into()));
+    }
+    let stop = stop_signal();
+    stop.reset();
+    let result = run(session_id, mode, config, sink, stop).await;
+    RUNNING.store(false, Ordering::SeqCst);
+    result
+}
+
+async fn run(
+    session_id: &str,
+    mode: ReplayMode,
+    config: &AppConfig,
+    sink: &dyn EventSink,
+    stop: Arc<StopSignal>,
+) -> SeeClawResult<ReplayReport> {
+    let id = session_id.to_string();
+    let entries: Vec<SessionEntry> = tokio::task::spawn_blocking(move || history::read_session(&id))
+        .await
+        .map_err(|e| SeeClawError::Agent(format!("replay: {e}")))??
+        .into_iter()
+        .filter(|e| e.entry.role == "user" || e.entry.action.is_some())
+        .collect();
+    tracing::info!(%session_id, ?mode, steps = entries.len(), "replay: starting");
+
+    let mut report = ReplayReport { session_id: session_id.to_string(), steps: entries.len(), ..Default::default() };
+    let mut prev_ts: Option<i64> = None;
+    for (index, item) in entries.iter().enumerate() {
+        let gap = prev_ts.map_or(0, |p| (item.entry.ts - p).max(0) as u64).clamp(MIN_GAP_MS, MAX_GAP_MS);
+        prev_ts = Some(item.entry.ts);
+        if index > 0 {
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_millis(gap)) => {}
+                _ = wait_stop(stop.clone()) => {}
+            }
+        }
+        if stop.is_set() {
+            report.stopped = true;
+            break;
+        }
+
+        let action = item.entry.action.clone().and_then(|v| serde_json::from_value::<AgentAction>(v).ok());
+        let label = match &action {
+            Some(a) => compact_action_label(a),
+            None => item.entry.content.clone().unwrap_or_default(),
+        };
+        let (status, detail) = match (&action, mode) {
+            (Some(action), ReplayMode::Execute) => {
+                match execute_step(action, item.entry.ok, item.entry.content.as_deref(), config, stop.clone()).await {
+                    Outcome::Executed(msg) => {
+                        report.executed += 1;
+                        (StepStatus::Executed, msg)
+                    }
+                    Outcome::Skipped(reason) => {
+                        report.skipped += 1;
+                        (StepStatus::Skipped, reason)
+                    }
+                    Outcome::Failed(error) => {
+                        report.failed += 1;
+                        (StepStatus::Failed, error)
+                    }
+                    Outcome::Stopped => {
+                        report.stopped = true;
+                        break;
+                    }
+                }
+            }
+            _ => (StepStatus::Shown, item.entry.content.clone().unwrap_or_default()),
+        };
+        sink.emit_json("replay_step", serde_json::json!({
+            "session_id": session_id,
+            "index": index,
+            "total": entries.len(),
+            "mode": mode,
+            "role": item.entry.role,
+            "kind": action.as_ref().map(action_kind_tag),
+            "label": label,
+            "status": status,
+            "detail": detail,
+            "original_ok": item.entry.ok,
+            "thumbnail": item.thumbnail,
+        }));
+    }
+    tracing::info!(?report, "replay: finished");
+    sink.emit_json("replay_finished", serde_json::to_value(&report).unwrap_or_default());
+    Ok(report)
+}
+
+enum Outcome {
+    Executed(String),
+    Skipped(String),
+    Failed(String),
+    Stopped,
+}
+
+async fn execute_step(
+    action: &AgentAction,
+    original_ok: Option<bool>,
+    original_result: Option<&str>,
+    config: &AppConfig,
+    stop: Arc<StopSignal>,
+) -> Outcome {
+    let skip = |reason: &str| Outcome::Skipped(reason.to_string());
+    if original_ok == Some(false) {
+        return skip("failed in the original run");
+    }
+    if safety::requires_approval(action, &config.safety) {
+        return skip("needs approval under the current safety settings");
+    }
+    if let Err(reason) = constraints::check_action(action, &config.preferences) {
+        return Outcome::Skipped(format!("blocked by a user constraint: {reason}"));
+    }
+    let result: SeeClawResult<String> = match action {
+        AgentAction::MouseClick { .. }
+        | AgentAction::MouseDoubleClick { .. }
+        | AgentAction::MouseRightClick { .. }
+        | AgentAction::MouseClickAt { .. } => {
+            let Some((x, y)) = original_result.and_then(click_point) else {
+                return skip("no recorded click point");
+            };
+            if exclusion::contains_point(x, y) {
+                return skip("click point is inside an exclusion zone");
+            }
+            match action {
+                AgentAction::MouseDoubleClick { .. } => input::mouse_double_click(x, y).await,
+                AgentAction::MouseRightClick { .. } => input::mouse_right_click(x, y).await,
+                _ => input::mouse_click(x, y).await,
+            }
+            .map(|_| format!("clicked ({x},{y})"))
+        }
+        AgentAction::TypeText { text, clear_first } => input::type_text(
+            text.clone(),
+            clear_first.then_some(config.executor.clear_strategy),
+        )
+        .await
+        .map(|_| format!("typed {} chars", text.chars().count())),
+        AgentAction::Hotkey { keys } => input::press_hotkey(keys.clone()).await.map(|_| format!("pressed {keys}")),
+        AgentAction::KeyPress { key } => input::press_hotkey(key.clone()).await.map(|_| format!("pressed {key}")),
+        AgentAction::KeyboardNavigate { target, key, max_presses } => {
+            keyboard_nav::navigate_to(target, key, *max_presses, true, stop)
+                .await
+                .map(|nav| format!("focused '{}' after {} presses", nav.focused, nav.presses))
+        }
+        AgentAction::Scroll { direction, distance, element_id } => {
+            // Element targets are not re-grounded; scroll where the cursor is.
+            input::scroll(direction.clone(), distance.clone(), None).await.map(|_| match element_id {
+                Some(id) => format!("scrolled {direction} at the cursor (recorded over {id})"),
+                None => format!("scrolled {direction}"),
+            })
+        }
+        AgentAction::Wait { milliseconds } => {
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_millis(*milliseconds as u64)) => {}
+                _ = wait_stop(stop) => return Outcome::Stopped,
+            }
+            Ok(format!("waited {milliseconds}ms"))
+        }
+        AgentAction::OpenApp { name_or_path, args } => launcher::open_app(name_or_path.clone(), args.clone()).await,
+        AgentAction::ClipboardWrite { text } => clipboard::write_text(text.clone())
+            .await
+            .map(|_| format!("copied {} chars", text.chars().count())),
+        _ => return skip("not replayed (no GUI effect or not repeatable)"),
+    };
+    match result {
+        Ok(msg) => Outcome::Executed(msg),
+        Err(SeeClawError::Cancelled) => Outcome::Stopped,
+        Err(e) => Outcome::Failed(e.to_string()),
+    }
+}
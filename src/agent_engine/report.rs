@@ -0,0 +1,220 @@
+//! Session reports — a readable write-up of a saved session to share.
+//!
+//! Each task of the session becomes a section: goal, plan, every action with
+//! its time offset, result and screenshot (click targets are ringed), and
+//! the final summary. Markdown reports link the thumbnails in the session
+//! folder; HTML reports embed them and are self-contained.
+
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::agent_engine::history::{self, data_dir_or_cwd, SessionEntry};
+use crate::agent_engine::nodes::action_exec::compact_action_label;
+use crate::agent_engine::state::AgentAction;
+use crate::errors::SeeClawResult;
+
+/// Action results longer than this are cut in the report.
+const MAX_RESULT_CHARS: usize = 600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+struct Step {
+    label: String,
+    offset_ms: i64,
+    ok: Option<bool>,
+    result: String,
+    /// Thumbnail path relative to the session folder.
+    screenshot: Option<String>,
+    thumbnail: Option<String>,
+}
+
+#[derive(Default)]
+struct Task {
+    goal: String,
+    started: i64,
+    ended: i64,
+    plan: Option<String>,
+    steps: Vec<Step>,
+    outcome: Option<(String, bool)>,
+}
+
+/// Write the report for `session_id` into its session folder; returns the
+/// file's path.
+pub fn export_session(session_id: &str, format: ReportFormat) -> SeeClawResult<PathBuf> {
+    let tasks = group(history::read_session(session_id)?);
+    let (text, file) = match format {
+        ReportFormat::Markdown => (markdown(session_id, &tasks), "report.md"),
+        ReportFormat::Html => (html(session_id, &tasks), "report.html"),
+    };
+    let dir = data_dir_or_cwd("sessions").join(session_id);
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(file);
+    std::fs::write(&path, text)?;
+    tracing::info!(path = %path.display(), tasks = tasks.len(), "session report written");
+    Ok(path)
+}
+
+/// Split the session's entries into tasks, one per goal.
+fn group(entries: Vec<SessionEntry>) -> Vec<Task> {
+    let mut tasks: Vec<Task> = Vec::new();
+    for SessionEntry { entry, thumbnail } in entries {
+        if entry.role == "user" || tasks.is_empty() {
+            tasks.push(Task {
+                goal: if entry.role == "user" { entry.content.clone().unwrap_or_default() } else { String::new() },
+                started: entry.ts,
+                ..Default::default()
+            });
+        }
+        let task = tasks.last_mut().expect("a task was pushed");
+        task.ended = entry.ts;
+        match entry.role.as_str() {
+            "user" => {}
+            "plan" => task.plan = entry.content,
+            "result" => task.outcome = Some((entry.content.unwrap_or_default(), entry.ok.unwrap_or(true))),
+            _ => {
+                let Some(action) = entry.action else { continue };
+                let label = serde_json::from_value::<AgentAction>(action.clone())
+                    .map(|a| compact_action_label(&a))
+                    .unwrap_or_else(|_| action.get("type").and_then(|t| t.as_str()).unwrap_or("action").to_string());
+                task.steps.push(Step {
+                    label,
+                    offset_ms: entry.ts - task.started,
+                    ok: entry.ok,
+                    result: truncate(entry.content.as_deref().unwrap_or_default()),
+                    screenshot: entry.screenshot,
+                    thumbnail,
+                });
+            }
+        }
+    }
+    tasks
+}
+
+fn markdown(session_id: &str, tasks: &[Task]) -> String {
+    let mut out = format!(
+        "# SeeClaw session report\n\nSession `{session_id}` · exported {}\n",
+        chrono::Local::now().format("%Y-%m-%d %H:%M")
+    );
+    for (i, task) in tasks.iter().enumerate() {
+        out.push_str(&format!("\n## Task {}: {}\n\n", i + 1, one_line(&task.goal)));
+        out.push_str(&format!("Started {} · took {} · {}\n", local_time(task.started), seconds(task.ended - task.started), status(task)));
+        if let Some(plan) = &task.plan {
+            out.push_str(&format!("\n### Plan\n\n{plan}\n"));
+        }
+        if !task.steps.is_empty() {
+            out.push_str("\n### Steps\n");
+        }
+        for (n, step) in task.steps.iter().enumerate() {
+            let mark = match step.ok {
+                Some(false) => " · **failed**",
+                _ => "",
+            };
+            out.push_str(&format!("\n**{}. `{}`** · +{}{mark}\n", n + 1, step.label, seconds(step.offset_ms)));
+            if !step.result.is_empty() {
+                for line in step.result.lines() {
+                    out.push_str(&format!("> {line}\n"));
+                }
+            }
+            if let Some(shot) = &step.screenshot {
+                out.push_str(&format!("\n![step {}]({shot})\n", n + 1));
+            }
+        }
+        if let Some((summary, _)) = &task.outcome {
+            out.push_str(&format!("\n### Outcome\n\n{summary}\n"));
+        }
+    }
+    out
+}
+
+fn html(session_id: &str, tasks: &[Task]) -> String {
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>SeeClaw session {id}</title>\n<style>\
+body{{font-family:system-ui,sans-serif;max-width:860px;margin:2em auto;padding:0 1em;color:#222}}\
+h2{{border-bottom:1px solid #ddd;padding-bottom:.3em}}.meta{{color:#666;font-size:.9em}}\
+.step{{margin:1em 0;padding:.6em .8em;border-left:3px solid #4a8;background:#f7f9f8}}\
+.step.failed{{border-color:#d44;background:#fdf5f5}}pre{{white-space:pre-wrap;margin:.4em 0;font-size:.85em}}\
+img{{max-width:100%;border:1px solid #ccc;margin-top:.4em}}</style></head><body>\n\
+<h1>SeeClaw session report</h1>\n<p class=\"meta\">Session <code>{id}</code> · exported {}</p>\n",
+        chrono::Local::now().format("%Y-%m-%d %H:%M"),
+        id = escape(session_id),
+    );
+    for (i, task) in tasks.iter().enumerate() {
+        out.push_str(&format!("<h2>Task {}: {}</h2>\n", i + 1, escape(&one_line(&task.goal))));
+        out.push_str(&format!(
+            "<p class=\"meta\">Started {} · took {} · {}</p>\n",
+            local_time(task.started),
+            seconds(task.ended - task.started),
+            status(task)
+        ));
+        if let Some(plan) = &task.plan {
+            out.push_str(&format!("<h3>Plan</h3>\n<pre>{}</pre>\n", escape(plan)));
+        }
+        if !task.steps.is_empty() {
+            out.push_str("<h3>Steps</h3>\n");
+        }
+        for (n, step) in task.steps.iter().enumerate() {
+            let failed = step.ok == Some(false);
+            out.push_str(&format!(
+                "<div class=\"step{}\"><b>{}. <code>{}</code></b> <span class=\"meta\">+{}{}</span>\n",
+                if failed { " failed" } else { "" },
+                n + 1,
+                escape(&step.label),
+                seconds(step.offset_ms),
+                if failed { " · failed" } else { "" },
+            ));
+            if !step.result.is_empty() {
+                out.push_str(&format!("<pre>{}</pre>\n", escape(&step.result)));
+            }
+            if let Some(src) = &step.thumbnail {
+                out.push_str(&format!("<img src=\"{src}\" alt=\"step {}\">\n", n + 1));
+            }
+            out.push_str("</div>\n");
+        }
+        if let Some((summary, _)) = &task.outcome {
+            out.push_str(&format!("<h3>Outcome</h3>\n<pre>{}</pre>\n", escape(summary)));
+        }
+    }
+    out.push_str("</body></html>\n");
+    out
+}
+
+fn status(task: &Task) -> &'static str {
+    match &task.outcome {
+        Some((_, true)) => "succeeded",
+        Some((_, false)) => "failed",
+        None => "unfinished",
+    }
+}
+
+fn local_time(ts: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(ts)
+        .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default()
+}
+
+fn seconds(ms: i64) -> String {
+    format!("{:.1} s", ms.max(0) as f64 / 1000.0)
+}
+
+fn one_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn truncate(text: &str) -> String {
+    let text = text.trim();
+    if text.chars().count() > MAX_RESULT_CHARS {
+        format!("{}…", text.chars().take(MAX_RESULT_CHARS).collect::<String>())
+    } else {
+        text.to_string()
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
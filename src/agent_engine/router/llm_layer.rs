@@ -1,7 +1,7 @@
 //! L3: LLM fallback classification layer.
 //!
 //! Uses a lightweight model (the `routing` role in the provider registry)
-//! to classify the query as Simple or Complex.
+//! to classify the query as Chat, Simple, Terminal, Complex or ComplexVisual.
 //!
 //! **Single responsibility**: This layer ONLY classifies. Tool-call generation
 //! for Simple tasks is delegated to `SimpleExecNode`, keeping this prompt lean.
@@ -11,7 +11,9 @@ use async_trait::async_trait;
 use crate::agent_engine::context::NodeContext;
 use crate::agent_engine::router::layer::{RouteResult, RouterLayer};
 use crate::agent_engine::state::RouteType;
+use crate::llm::structured::parse_json_reply;
 use crate::llm::types::{ChatMessage, MessageContent};
+use crate::prompts;
 
 const ROUTER_SYSTEM_PROMPT: &str = include_str!("../../../prompts/system/router.md");
 
@@ -57,7 +59,7 @@ impl RouterLayer for LlmLayer {
         let messages = vec![
             ChatMessage {
                 role: "system".into(),
-                content: MessageContent::Text(ROUTER_SYSTEM_PROMPT.to_string()),
+                content: MessageContent::Text(prompts::get("router.md", ROUTER_SYSTEM_PROMPT).into_owned()),
                 tool_call_id: None,
                 tool_calls: None,
             },
@@ -74,18 +76,13 @@ impl RouterLayer for LlmLayer {
                 let raw = response.content.trim();
                 tracing::info!(layer = "llm", raw = %raw, "[Router] LLM response");
 
-                // Parse the response JSON: { "route_type": "simple"|"complex", "tool_calls": [...] }
-                let json_str = raw
-                    .trim_start_matches("```json")
-                    .trim_start_matches("```")
-                    .trim_end_matches("```")
-                    .trim();
-
-                match serde_json::from_str::<serde_json::Value>(json_str) {
-                    Ok(v) => {
+                // Parse the response JSON: { "route_type": "chat"|"simple"|"terminal"|..., "confidence": … }
+                match parse_json_reply(raw) {
+                    Some(v) => {
                         let route_type = match v["route_type"].as_str() {
                             Some("chat") => RouteType::Chat,
                             Some("simple") => RouteType::Simple,
+                            Some("terminal") => RouteType::Terminal,
                             Some("complex_visual") => RouteType::ComplexVisual,
                             Some("complex") => RouteType::Complex,
                             _ => RouteType::Chat, // safe default
@@ -96,8 +93,8 @@ impl RouterLayer for LlmLayer {
                             confidence: v["confidence"].as_f64().unwrap_or(0.8) as f32,
                         })
                     }
-                    Err(e) => {
-                        tracing::warn!(error = %e, raw = %json_str, "router LLM JSON parse failed — defaulting to Chat");
+                    None => {
+                        tracing::warn!(raw = %raw, "router LLM JSON parse failed — defaulting to Chat");
                         Some(RouteResult {
                             route_type: RouteType::Chat,
                             confidence: 0.3,
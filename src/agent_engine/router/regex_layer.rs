@@ -39,6 +39,10 @@ impl RegexLayer {
             (Regex::new(r"(?i)^(open|launch|start|close|minimize|maximize)\s+[\w.-]{1,30}\s*$").unwrap(), RouteType::Simple),
             (Regex::new(r"(?i)^(按|press)\s+(ctrl|alt|shift|win|enter|tab|esc)").unwrap(), RouteType::Simple),
 
+            // ── Terminal patterns: explicit shell commands ──
+            (Regex::new(r"(?i)^(在终端|用终端|在命令行)?\s*(运行|执行)\s*(命令|指令)").unwrap(), RouteType::Terminal),
+            (Regex::new(r"(?i)^(run|execute)\s+(the\s+)?(shell\s+|terminal\s+)?command\b").unwrap(), RouteType::Terminal),
+
             // ── ComplexVisual patterns: tasks that explicitly reference screen content ──
             // These need the planner to see the current screen before generating a plan.
             // "点击" / "click" tasks always require vision to know WHERE to click.
@@ -4,13 +4,24 @@
 //! — the graph's conditional edges read fields from `SharedState` to decide
 //! which node runs next.
 
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::{BTreeMap, VecDeque};
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
-
-use crate::llm::types::{ChatMessage, ContentPart, MessageContent};
+use tokio_util::sync::{CancellationToken, DropGuard};
+
+use crate::agent_engine::app_map::AppMap;
+use crate::agent_engine::candidates::ClickCandidate;
+use crate::agent_engine::checkpoint::Checkpoint;
+use crate::agent_engine::cooldown::ElementCooldown;
+use crate::agent_engine::grounding::GroundedPoint;
+use crate::agent_engine::lang::GoalLanguage;
+use crate::agent_engine::node::{PauseSignal, StopSignal};
+use crate::errors::ErrorCategory;
+use crate::llm::types::{ChatMessage, ContentPart, MessageContent, ToolCall};
+use crate::perception::screenshot::MonitorTarget;
+use crate::perception::tracking::ElementTracker;
 use crate::perception::types::{ScreenshotMeta, UIElement};
 
 // ── Route type ─────────────────────────────────────────────────────────────
@@ -23,6 +34,8 @@ pub enum RouteType {
     Chat,
     /// Single GUI action (open app, click button, etc.).
     Simple,
+    /// Done entirely with a shell command — no screen, no GUI input.
+    Terminal,
     /// Multi-step workflow requiring planning (no initial screenshot).
     Complex,
     /// Multi-step workflow that *needs* the current screen to plan.
@@ -49,6 +62,8 @@ pub enum StepMode {
     Chat,
     /// VLM-driven loop: screenshot → VLM → action → screenshot verify.
     Vlm,
+    /// Time-boxed menu survey of an unfamiliar app — zero LLM, builds an app map.
+    Explore,
 }
 
 impl Default for StepMode {
@@ -105,6 +120,10 @@ pub struct TodoStep {
     /// Parameters for the skill combo (e.g. {"software_name": "Edge"}).
     #[serde(default)]
     pub params: Option<serde_json::Value>,
+    /// Consecutive steps sharing a group don't depend on each other; non-GUI
+    /// ones may run concurrently (see `ParallelExecNode`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
     /// Current lifecycle status.
     #[serde(default)]
     pub status: StepStatus,
@@ -126,14 +145,41 @@ pub enum AgentAction {
     MouseClick { element_id: String },
     MouseDoubleClick { element_id: String },
     MouseRightClick { element_id: String },
+    /// Left-click a point given in percent (0–100) of the screenshot, for
+    /// targets no element or grid cell covers.
+    MouseClickAt { x_pct: f32, y_pct: f32 },
     Scroll { direction: String, distance: String, element_id: Option<String> },
     TypeText { text: String, clear_first: bool },
     Hotkey { keys: String },
     KeyPress { key: String },
+    /// Press `key` until the UIA-focused element's name matches `target`, then Enter.
+    KeyboardNavigate { target: String, key: String, max_presses: u32 },
     GetViewport { annotate: bool },
-    ExecuteTerminal { command: String, reason: String },
+    /// Point perception (and therefore clicks) at another monitor.
+    SelectMonitor { target: MonitorTarget },
+    /// Run `command` in the configured shell. `cwd`, `env` and `timeout_secs`
+    /// override the working directory, add environment variables and replace
+    /// `[executor] terminal_timeout_secs` for this command.
+    ExecuteTerminal {
+        command: String,
+        reason: String,
+        cwd: Option<String>,
+        env: BTreeMap<String, String>,
+        timeout_secs: Option<u64>,
+    },
+    /// Resolve an installed application (or executable path) and start it.
+    OpenApp { name_or_path: String, args: Vec<String> },
+    ReadFile { path: String },
+    WriteFile { path: String, content: String, append: bool },
+    ListDir { path: String },
+    MoveFile { from: String, to: String },
+    /// Return the clipboard's text as the tool result.
+    ClipboardRead,
+    ClipboardWrite { text: String },
     McpCall { server_name: String, tool_name: String, arguments: serde_json::Value },
     InvokeSkill { skill_name: String, inputs: serde_json::Value },
+    /// Run `goal` as a scoped child agent; only its summary comes back.
+    DelegateSubtask { goal: String, max_steps: Option<u32> },
     Wait { milliseconds: u32 },
     FinishTask { summary: String },
     ReportFailure { reason: String, last_attempted_action: Option<String> },
@@ -162,7 +208,11 @@ pub struct ActionResult {
 #[serde(tag = "outcome", rename_all = "snake_case")]
 pub enum GraphResult {
     Done { summary: String },
-    Error { message: String },
+    Error {
+        message: String,
+        #[serde(default)]
+        category: ErrorCategory,
+    },
 }
 
 // ── Loop config ────────────────────────────────────────────────────────────
@@ -172,11 +222,94 @@ pub struct LoopConfig {
     pub mode: LoopMode,
     pub max_duration_minutes: Option<u32>,
     pub max_failures: Option<u32>,
+    #[serde(default)]
+    pub budget: LoopBudget,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Per-task limits from `[safety]`, enforced by the `LoopController`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct LoopBudget {
+    /// Plan → execute → verify cycles before the verifier stops replanning.
+    pub max_cycles: u32,
+    /// Executed actions per task (0 = unlimited).
+    pub max_total_steps: u32,
+    /// Vision-model calls per task (0 = unlimited).
+    pub max_vlm_calls: u32,
+}
+
+impl Default for LoopBudget {
+    fn default() -> Self {
+        Self { max_cycles: 2, max_total_steps: 0, max_vlm_calls: 0 }
+    }
+}
+
+impl From<&crate::config::SafetyConfig> for LoopConfig {
+    fn from(s: &crate::config::SafetyConfig) -> Self {
+        Self {
+            mode: s.loop_mode,
+            max_duration_minutes: (s.max_loop_duration_minutes > 0).then_some(s.max_loop_duration_minutes),
+            max_failures: (s.max_consecutive_failures > 0).then_some(s.max_consecutive_failures),
+            budget: LoopBudget::from(s),
+        }
+    }
+}
+
+impl LoopConfig {
+    /// This config with the fields set in `o` replaced.
+    pub fn with_overrides(&self, o: &LoopOverrides) -> Self {
+        let mut cfg = self.clone();
+        if let Some(mode) = o.mode {
+            cfg.mode = mode;
+        }
+        if o.max_duration_minutes.is_some() {
+            cfg.max_duration_minutes = o.max_duration_minutes;
+        }
+        if o.max_failures.is_some() {
+            cfg.max_failures = o.max_failures;
+        }
+        if let Some(n) = o.max_total_steps {
+            cfg.budget.max_total_steps = n;
+        }
+        if let Some(n) = o.max_vlm_calls {
+            cfg.budget.max_vlm_calls = n;
+        }
+        cfg
+    }
+}
+
+/// Loop settings for a single goal (`start_task`'s `loop_config`); unset
+/// fields keep the `[safety]` values.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LoopOverrides {
+    #[serde(default)]
+    pub mode: Option<LoopMode>,
+    #[serde(default)]
+    pub max_duration_minutes: Option<u32>,
+    #[serde(default)]
+    pub max_failures: Option<u32>,
+    #[serde(default)]
+    pub max_total_steps: Option<u32>,
+    #[serde(default)]
+    pub max_vlm_calls: Option<u32>,
+    /// Config profile (`[profiles.<name>]`) for this goal only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+}
+
+impl From<&crate::config::SafetyConfig> for LoopBudget {
+    fn from(s: &crate::config::SafetyConfig) -> Self {
+        Self {
+            max_cycles: s.max_cycles,
+            max_total_steps: s.max_total_steps,
+            max_vlm_calls: s.max_vlm_calls,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum LoopMode {
+    #[default]
     UntilDone,
     Timed,
     FailureLimit,
@@ -188,7 +321,15 @@ pub enum LoopMode {
 #[derive(Debug)]
 pub enum AgentEvent {
     GoalReceived(String),
+    /// A goal with its own loop mode / limits.
+    GoalWithLoop(String, LoopOverrides),
     Stop,
+    /// Hold the task before its next node so the user can use the mouse and
+    /// keyboard; `Resume` continues from the same step.
+    Pause,
+    Resume,
+    /// Start the unfinished task saved in a checkpoint (`resume_session`).
+    RestoreSession(Box<Checkpoint>),
     UserApproved,
     UserRejected,
 }
@@ -203,6 +344,8 @@ pub struct SharedState {
     // ── Task ────────────────────────────────────────────────────────────
     /// The user's original goal / query.
     pub goal: String,
+    /// Language of the goal; drives activity messages and output language.
+    pub lang: GoalLanguage,
 
     // ── Routing ─────────────────────────────────────────────────────────
     /// Classification result from the Router pipeline.
@@ -213,6 +356,12 @@ pub struct SharedState {
     pub conv_messages: Vec<ChatMessage>,
     /// Tool-call ID of the most recent pending tool call (for tool-result ack).
     pub pending_tool_id: String,
+    /// Later tool calls from the same planner message, executed in order
+    /// after the current one.
+    pub queued_tool_calls: VecDeque<ToolCall>,
+    /// Reasoning text of the last model reply that produced a tool call;
+    /// stored with the action's history entry.
+    pub last_reasoning: String,
 
     // ── Plan context (from Planner) ─────────────────────────────────────
     /// Planner's summary of the overall plan (injected into loop agent context).
@@ -237,6 +386,24 @@ pub struct SharedState {
     /// Cleared by `ActionExecNode` once it consumes the approval and proceeds.
     /// This prevents `action_exec` from re-routing to `user_confirm` in a loop.
     pub action_user_approved: bool,
+    /// Certainty the model reported for `current_action` (`confidence` tool arg).
+    pub action_certainty: Option<f32>,
+    /// Click point for `current_action`'s element refined by the focus-crop
+    /// pass; used instead of the element centre when the id matches.
+    pub refined_click: Option<(String, GroundedPoint)>,
+    /// Elements the model considered for the current click, its pick first.
+    pub click_candidates: Vec<ClickCandidate>,
+    /// What the model says the current click is meant to hit (`target` arg).
+    pub click_target: Option<String>,
+    /// The model was just asked to name an uncertain click target more
+    /// precisely; its next pick is clicked without asking again.
+    pub click_refine_asked: bool,
+    /// A VLM click for `StabilityNode` to check once the screen settles:
+    /// element id and the point clicked (`[perception] verify_clicks`).
+    pub pending_click_check: Option<(String, GroundedPoint)>,
+    /// Grounding confidence that sent the current action to approval, shown
+    /// to the user. `None` when approval was required by rule.
+    pub approval_confidence: Option<f32>,
 
     // ── Dynamic loop control ────────────────────────────────────────────
     /// Current loop mode for the active step (set by StepRouter).
@@ -265,16 +432,60 @@ pub struct SharedState {
     pub detected_elements: Vec<UIElement>,
     /// Metadata from the last screenshot capture.
     pub last_meta: Option<ScreenshotMeta>,
+    /// Screen every capture in this task looks at (`select_monitor`).
+    pub monitor: MonitorTarget,
+    /// Sampled hash of the screenshot the current action was chosen from.
+    pub last_frame_hash: Option<u64>,
+    /// Screenshot taken just before the last UI action, diffed by
+    /// `StabilityNode` once the screen settles.
+    pub pre_action_frame: Option<Vec<u8>>,
+    /// Element clicked by the last successful click, awaiting the
+    /// screen-change check in `StabilityNode`.
+    pub pending_click_target: Option<String>,
+    /// Click targets that keep failing; lasts for the whole task.
+    pub element_cooldown: ElementCooldown,
+    /// Keeps element IDs stable across this task's screenshots.
+    pub element_tracker: ElementTracker,
+    /// Element clicked by the last successful click, looked up in the
+    /// tracker after the next perception pass (did it vanish or change?).
+    pub tracked_click: Option<String>,
 
     // ── Execution log ───────────────────────────────────────────────────
     /// Accumulated step results for the evaluator / verifier.
     pub steps_log: Vec<String>,
     /// How many plan → execute → verify cycles have run (anti-loop guard).
     pub cycle_count: u32,
+    /// Reflection turns run after failed steps in this task.
+    pub reflections: u32,
+    /// 0 for a task, 1 for a subtask started by `delegate_subtask`.
+    pub subtask_depth: u32,
+    /// Set when the task hit a loop limit (step / VLM budget, time limit,
+    /// failures in a row); the graph then goes straight to the summarizer.
+    pub budget_exhausted: Option<String>,
+    /// Skills section of the planner prompt, filtered by relevance to the goal.
+    pub skills_context: String,
+    /// Apps whose shortcuts from the hotkey knowledge base are already in the
+    /// planner conversation.
+    pub hotkey_apps: Vec<String>,
+    /// Menu commands discovered by `explore` steps in this task.
+    pub app_maps: Vec<AppMap>,
+    /// Recalled experiences from similar past tasks, injected into the
+    /// planner prompt. Empty when none were found.
+    pub experience_context: String,
+    /// Summary of the task that finished just before this one, for
+    /// follow-up goals. Empty when there is none or it has expired.
+    pub carryover_context: String,
 
     // ── Control ─────────────────────────────────────────────────────────
-    /// Shared atomic flag for immediate cancellation from the UI.
-    pub stop_flag: Arc<AtomicBool>,
+    /// Stop request from the UI; checked synchronously or awaited with `wait_stop`.
+    pub stop_flag: Arc<StopSignal>,
+    /// Pause request from the UI; the graph waits on it between nodes.
+    pub pause: Arc<PauseSignal>,
+    /// Cancelled as soon as `stop_flag` is set; passed to LLM calls through
+    /// `CallConfig::cancel` so a stop aborts the request in flight.
+    pub cancel: CancellationToken,
+    /// Cancels `cancel` when the state is dropped, ending the flag watcher.
+    _cancel_guard: DropGuard,
     /// Channel to receive user events (approval, rejection, etc.).
     pub event_rx: mpsc::Receiver<AgentEvent>,
     /// Final result of the graph execution.
@@ -285,14 +496,18 @@ impl SharedState {
     /// Create a new SharedState for a given goal.
     pub fn new(
         goal: String,
-        stop_flag: Arc<AtomicBool>,
+        stop_flag: Arc<StopSignal>,
         event_rx: mpsc::Receiver<AgentEvent>,
     ) -> Self {
+        let cancel = cancel_on_stop(stop_flag.clone());
         Self {
+            lang: GoalLanguage::for_goal(&goal),
             goal,
             route_type: RouteType::default(),
             conv_messages: Vec::new(),
             pending_tool_id: String::new(),
+            queued_tool_calls: VecDeque::new(),
+            last_reasoning: String::new(),
             plan_summary: String::new(),
             final_goal: String::new(),
             todo_steps: Vec::new(),
@@ -301,6 +516,13 @@ impl SharedState {
             needs_stability: false,
             needs_approval: false,
             action_user_approved: false,
+            action_certainty: None,
+            refined_click: None,
+            click_candidates: Vec::new(),
+            click_target: None,
+            click_refine_asked: false,
+            pending_click_check: None,
+            approval_confidence: None,
             current_loop_mode: StepMode::Chat,
             mode_switch_requested: None,
             step_complete: false,
@@ -312,9 +534,27 @@ impl SharedState {
             last_action_kind: String::new(),
             detected_elements: Vec::new(),
             last_meta: None,
+            monitor: MonitorTarget::default(),
+            last_frame_hash: None,
+            pre_action_frame: None,
+            pending_click_target: None,
+            element_cooldown: ElementCooldown::default(),
+            element_tracker: ElementTracker::default(),
+            tracked_click: None,
             steps_log: Vec::new(),
             cycle_count: 0,
+            reflections: 0,
+            subtask_depth: 0,
+            budget_exhausted: None,
+            skills_context: String::new(),
+            hotkey_apps: Vec::new(),
+            app_maps: Vec::new(),
+            experience_context: String::new(),
+            carryover_context: String::new(),
             stop_flag,
+            pause: Arc::new(PauseSignal::new()),
+            _cancel_guard: cancel.clone().drop_guard(),
+            cancel,
             event_rx,
             result: None,
         }
@@ -322,7 +562,20 @@ impl SharedState {
 
     /// Check whether the stop flag has been set by the UI.
     pub fn is_stopped(&self) -> bool {
-        self.stop_flag.load(Ordering::Relaxed)
+        self.stop_flag.is_set()
+    }
+
+    /// Answer every queued tool call with `reason` instead of running it, so
+    /// the conversation keeps one tool result per call id.
+    pub fn skip_queued_tool_calls(&mut self, reason: &str) {
+        for tc in self.queued_tool_calls.drain(..) {
+            self.conv_messages.push(ChatMessage {
+                role: "tool".into(),
+                content: MessageContent::Text(format!("Skipped: {reason}")),
+                tool_call_id: Some(tc.id),
+                tool_calls: None,
+            });
+        }
     }
 
     /// Reset state for a new planning cycle (keeps goal and conv_messages).
@@ -354,7 +607,14 @@ impl SharedState {
         self.todo_steps.clear();
         self.current_step_idx = 0;
         self.current_action = None;
+        self.refined_click = None;
+        self.click_candidates.clear();
+        self.click_target = None;
+        self.click_refine_asked = false;
+        self.pending_click_check = None;
+        self.queued_tool_calls.clear();
         self.needs_stability = false;
+        self.pre_action_frame = None;
         self.needs_approval = false;
         self.action_user_approved = false;
         self.mode_switch_requested = None;
@@ -369,3 +629,17 @@ impl SharedState {
         self.final_goal.clear();
     }
 }
+
+/// A token that is cancelled once `flag` is set. The watcher task exits when
+/// the token is cancelled either way.
+fn cancel_on_stop(flag: Arc<StopSignal>) -> CancellationToken {
+    let token = CancellationToken::new();
+    let watched = token.clone();
+    tokio::spawn(async move {
+        tokio::select! {
+            _ = flag.wait() => watched.cancel(),
+            _ = watched.cancelled() => {}
+        }
+    });
+    token
+}
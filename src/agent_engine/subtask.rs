@@ -0,0 +1,117 @@
+//! Delegated subtasks — `delegate_subtask` runs a scoped child agent.
+//!
+//! The child gets a fresh `SharedState` (own conversation, plan and step
+//! log) and its own step budget, but shares the parent's providers,
+//! perception, executor, stop / pause signals and approval channel. It
+//! starts at the planner and only its final summary comes back to the
+//! parent as the tool result, so long multi-application tasks don't carry
+//! every sub-step in the parent's context.
+
+use std::sync::Arc;
+
+use tokio::sync::{mpsc, Mutex};
+
+use crate::agent_engine::context::NodeContext;
+use crate::agent_engine::flow::build_default_flow;
+use crate::agent_engine::state::{GraphResult, RouteType, SharedState};
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::events::{EventSink, SharedEventSink};
+
+/// Actions a subtask may take when the caller gives no `max_steps`.
+pub const DEFAULT_SUBTASK_STEPS: u32 = 30;
+/// Subtasks may not delegate further.
+const MAX_DEPTH: u32 = 1;
+
+/// Events the user still needs to see from a subtask; everything else
+/// (state changes, todo list, streamed summary) is wrapped in a
+/// `subtask_event` so it doesn't replace the parent task in the UI.
+const PASS_THROUGH: &[&str] = &[
+    "agent_activity",
+    "action_required",
+    "action_result",
+    "viewport_captured",
+    "takeover_detected",
+];
+
+struct SubtaskSink {
+    inner: SharedEventSink,
+    goal: String,
+}
+
+impl EventSink for SubtaskSink {
+    fn emit_json(&self, event: &str, payload: serde_json::Value) {
+        if PASS_THROUGH.contains(&event) {
+            self.inner.emit_json(event, payload);
+        } else {
+            self.inner.emit_json("subtask_event", serde_json::json!({
+                "goal": &self.goal,
+                "event": event,
+                "payload": payload,
+            }));
+        }
+    }
+}
+
+/// Run `goal` as a child agent and return its summary.
+pub async fn run(
+    goal: &str,
+    max_steps: Option<u32>,
+    state: &mut SharedState,
+    ctx: &NodeContext,
+) -> SeeClawResult<String> {
+    if state.subtask_depth >= MAX_DEPTH {
+        return Err(SeeClawError::ExecutorDenied(
+            "a subtask cannot delegate further; do the work directly".into(),
+        ));
+    }
+    tracing::info!(goal = %goal, parent = %state.goal, max_steps, "subtask: starting");
+    let _ = ctx.app.emit("subtask_started", serde_json::json!({ "goal": goal, "parent": &state.goal }));
+
+    let child_ctrl = ctx.loop_ctrl.lock().await.child(max_steps.unwrap_or(DEFAULT_SUBTASK_STEPS));
+    let sink: SharedEventSink = Arc::new(SubtaskSink { inner: ctx.app.clone(), goal: goal.to_string() });
+    let child_ctx = ctx.scoped(sink, Arc::new(Mutex::new(child_ctrl)));
+
+    // The child answers approvals on the parent's channel while it runs.
+    let event_rx = std::mem::replace(&mut state.event_rx, mpsc::channel(1).1);
+    let mut child = SharedState::new(goal.to_string(), state.stop_flag.clone(), event_rx);
+    child.lang = state.lang;
+    child.pause = state.pause.clone();
+    child.monitor = state.monitor;
+    child.subtask_depth = state.subtask_depth + 1;
+    child.route_type = RouteType::Complex;
+    child.skills_context = ctx.skill_registry.skills_context_for_goal(goal);
+    child.app_maps = state.app_maps.clone();
+    child.carryover_context = format!(
+        "## Parent Task\nThis request is one delegated part of a larger task: \"{}\". Do only this part, then finish; the larger task continues afterwards.\n",
+        state.goal
+    );
+
+    let result = build_default_flow().run_from("planner", &mut child, &child_ctx).await;
+
+    ctx.loop_ctrl.lock().await.absorb(&*child_ctx.loop_ctrl.lock().await);
+    state.event_rx = child.event_rx;
+    state.app_maps = child.app_maps;
+    result?;
+    if state.is_stopped() {
+        return Err(SeeClawError::Cancelled);
+    }
+
+    let outcome = match (child.result, child.budget_exhausted) {
+        (Some(GraphResult::Done { summary }), None) => Ok(format!("Subtask done: {summary}")),
+        (Some(GraphResult::Done { summary }), Some(reason)) => {
+            Err(SeeClawError::Agent(format!("subtask stopped early ({reason}): {summary}")))
+        }
+        (Some(GraphResult::Error { message, .. }), _) => Err(SeeClawError::Agent(format!("subtask failed: {message}"))),
+        (None, _) => Ok("Subtask finished without a summary.".to_string()),
+    };
+    tracing::info!(goal = %goal, ok = outcome.is_ok(), "subtask: finished");
+    let _ = ctx.app.emit("subtask_finished", serde_json::json!({
+        "goal": goal,
+        "ok": outcome.is_ok(),
+        "summary": match &outcome {
+            Ok(s) => s.clone(),
+            Err(e) => e.to_string(),
+        },
+    }));
+    outcome
+}
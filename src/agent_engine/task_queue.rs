@@ -0,0 +1,103 @@
+//! Task queue — goals submitted while another task is running.
+//!
+//! `start_task` during a task no longer interrupts it: the goal is appended
+//! here and `agent_loop` starts it once the current task ends. The queue can
+//! be listed, trimmed and reordered from the UI; every change is broadcast
+//! as a `queue_updated` event carrying the full list. Goals accepted while
+//! every LLM provider is unreachable wait here as well.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use serde::Serialize;
+
+use crate::agent_engine::state::LoopOverrides;
+use crate::events::EventSink;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct QueuedTask {
+    pub id: u64,
+    pub goal: String,
+    /// RFC 3339 time the goal was queued.
+    pub queued_at: String,
+    /// Loop settings given with the goal, applied when it starts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loop_overrides: Option<LoopOverrides>,
+}
+
+fn queue() -> &'static Mutex<VecDeque<QueuedTask>> {
+    static QUEUE: OnceLock<Mutex<VecDeque<QueuedTask>>> = OnceLock::new();
+    QUEUE.get_or_init(|| Mutex::new(VecDeque::new()))
+}
+
+fn lock() -> std::sync::MutexGuard<'static, VecDeque<QueuedTask>> {
+    queue().lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Append `goal`; returns its queue entry.
+pub fn push(goal: String, loop_overrides: Option<LoopOverrides>) -> QueuedTask {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+    let task = QueuedTask {
+        id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+        goal,
+        queued_at: chrono::Utc::now().to_rfc3339(),
+        loop_overrides,
+    };
+    lock().push_back(task.clone());
+    task
+}
+
+/// Take the next goal to run.
+pub fn pop() -> Option<QueuedTask> {
+    lock().pop_front()
+}
+
+/// Put a popped goal back at the front, keeping its ID.
+pub fn requeue(task: QueuedTask) {
+    lock().push_front(task);
+}
+
+pub fn len() -> usize {
+    lock().len()
+}
+
+/// Drop every queued goal; returns how many there were.
+pub fn clear() -> usize {
+    let mut q = lock();
+    let n = q.len();
+    q.clear();
+    n
+}
+
+pub fn list() -> Vec<QueuedTask> {
+    lock().iter().cloned().collect()
+}
+
+/// Remove the queued goal `id`. `false` when it is not (or no longer) queued.
+pub fn cancel(id: u64) -> bool {
+    let mut q = lock();
+    let before = q.len();
+    q.retain(|t| t.id != id);
+    q.len() != before
+}
+
+/// Move the goals listed in `ids` to the front, in that order. Unknown IDs
+/// are ignored; unlisted goals keep their relative order behind them.
+pub fn reorder(ids: &[u64]) {
+    let mut q = lock();
+    let mut front: Vec<QueuedTask> = Vec::with_capacity(ids.len());
+    for id in ids {
+        if let Some(pos) = q.iter().position(|t| t.id == *id) {
+            front.extend(q.remove(pos));
+        }
+    }
+    for task in front.into_iter().rev() {
+        q.push_front(task);
+    }
+}
+
+/// Broadcast the current queue.
+pub fn emit(sink: &dyn EventSink) {
+    sink.emit_json("queue_updated", serde_json::json!({ "items": list() }));
+}
@@ -7,6 +7,8 @@ use crate::agent_engine::state::{
     AgentAction, StepMode, StepStatus, TodoStep,
 };
 use crate::llm::types::ToolCall;
+use crate::mcp::manager::split_namespaced_tool_name;
+use crate::perception::screenshot::MonitorTarget;
 
 // ── Public API ─────────────────────────────────────────────────────────────
 
@@ -39,6 +41,16 @@ pub fn parse_action_by_name(name: &str, args: &serde_json::Value) -> Result<Agen
         "mouse_right_click" => Ok(AgentAction::MouseRightClick {
             element_id: str_field(args, "element_id"),
         }),
+        "mouse_click_at" => {
+            let pct = |key: &str| {
+                args[key]
+                    .as_f64()
+                    .map(|v| v as f32)
+                    .filter(|v| (0.0..=100.0).contains(v))
+                    .ok_or_else(|| format!("mouse_click_at: '{key}' must be a number from 0 to 100"))
+            };
+            Ok(AgentAction::MouseClickAt { x_pct: pct("x_pct")?, y_pct: pct("y_pct")? })
+        }
         "scroll" => Ok(AgentAction::Scroll {
             direction: args["direction"].as_str().unwrap_or("down").to_string(),
             distance: args["distance"].as_str().unwrap_or("short").to_string(),
@@ -54,12 +66,47 @@ pub fn parse_action_by_name(name: &str, args: &serde_json::Value) -> Result<Agen
         "key_press" => Ok(AgentAction::KeyPress {
             key: str_field(args, "key"),
         }),
+        "keyboard_navigate" => Ok(AgentAction::KeyboardNavigate {
+            target: str_field(args, "target"),
+            key: args["key"].as_str().filter(|k| !k.trim().is_empty()).unwrap_or("tab").to_string(),
+            max_presses: args["max_presses"]
+                .as_u64()
+                .unwrap_or(30)
+                .clamp(1, crate::executor::keyboard_nav::MAX_PRESSES_LIMIT as u64) as u32,
+        }),
         "get_viewport" => Ok(AgentAction::GetViewport {
             annotate: args["annotate"].as_bool().unwrap_or(true),
         }),
+        "select_monitor" => Ok(AgentAction::SelectMonitor {
+            target: match &args["monitor"] {
+                serde_json::Value::Number(n) => MonitorTarget::Index(n.as_u64().unwrap_or(0) as u32),
+                v => match v.as_str().map(str::trim) {
+                    Some("all") => MonitorTarget::All,
+                    Some(s) => s.parse().map(MonitorTarget::Index).unwrap_or_default(),
+                    None => MonitorTarget::Primary,
+                },
+            },
+        }),
         "execute_terminal" => Ok(AgentAction::ExecuteTerminal {
             command: str_field(args, "command"),
             reason: str_field(args, "reason"),
+            cwd: args["cwd"].as_str().filter(|d| !d.trim().is_empty()).map(str::to_string),
+            env: args["env"]
+                .as_object()
+                .map(|m| {
+                    m.iter()
+                        .filter_map(|(k, v)| Some((k.clone(), v.as_str()?.to_string())))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            timeout_secs: args["timeout_secs"].as_u64(),
+        }),
+        "open_app" => Ok(AgentAction::OpenApp {
+            name_or_path: str_field(args, "name_or_path"),
+            args: args["args"]
+                .as_array()
+                .map(|a| a.iter().filter_map(|v| v.as_str().map(str::to_string)).collect())
+                .unwrap_or_default(),
         }),
         "mcp_call" => Ok(AgentAction::McpCall {
             server_name: str_field(args, "server_name"),
@@ -70,6 +117,35 @@ pub fn parse_action_by_name(name: &str, args: &serde_json::Value) -> Result<Agen
             skill_name: str_field(args, "skill_name"),
             inputs: args["inputs"].clone(),
         }),
+        "delegate_subtask" => {
+            let goal = str_field(args, "goal");
+            if goal.trim().is_empty() {
+                return Err("delegate_subtask: goal is empty".into());
+            }
+            Ok(AgentAction::DelegateSubtask {
+                goal,
+                max_steps: args["max_steps"].as_u64().map(|n| n.clamp(1, 200) as u32),
+            })
+        }
+        "read_file" => Ok(AgentAction::ReadFile {
+            path: str_field(args, "path"),
+        }),
+        "write_file" => Ok(AgentAction::WriteFile {
+            path: str_field(args, "path"),
+            content: str_field(args, "content"),
+            append: args["append"].as_bool().unwrap_or(false),
+        }),
+        "list_dir" => Ok(AgentAction::ListDir {
+            path: str_field(args, "path"),
+        }),
+        "move_file" => Ok(AgentAction::MoveFile {
+            from: str_field(args, "from"),
+            to: str_field(args, "to"),
+        }),
+        "clipboard_read" => Ok(AgentAction::ClipboardRead),
+        "clipboard_write" => Ok(AgentAction::ClipboardWrite {
+            text: str_field(args, "text"),
+        }),
         "wait" => Ok(AgentAction::Wait {
             milliseconds: args["milliseconds"].as_u64().unwrap_or(1000) as u32,
         }),
@@ -82,7 +158,15 @@ pub fn parse_action_by_name(name: &str, args: &serde_json::Value) -> Result<Agen
                 .as_str()
                 .map(|s| s.to_string()),
         }),
-        other => Err(format!("unknown tool: {other}")),
+        other => match split_namespaced_tool_name(other) {
+            // Discovered MCP tool: the whole argument object goes to the server.
+            Some((server, tool)) => Ok(AgentAction::McpCall {
+                server_name: server.to_string(),
+                tool_name: tool.to_string(),
+                arguments: args.clone(),
+            }),
+            None => Err(format!("unknown tool: {other}")),
+        },
     }
 }
 
@@ -122,20 +206,31 @@ pub fn patch_element_id(action: AgentAction, cell: &str) -> AgentAction {
     }
 }
 
-/// Safety check: actions that don't need user approval.
+/// Actions that run without approval unless the user lists them in
+/// `safety.require_approval_for` (see `executor::safety::requires_approval`).
+/// `open_app` only qualifies for a bare application name without arguments;
+/// explicit paths and command-line arguments still go through approval.
 pub fn is_auto_approved(action: &AgentAction) -> bool {
+    if let AgentAction::OpenApp { name_or_path, args } = action {
+        return args.is_empty() && !name_or_path.contains(['/', '\\']);
+    }
     matches!(
         action,
         AgentAction::GetViewport { .. }
+            | AgentAction::SelectMonitor { .. }
+            | AgentAction::ClipboardWrite { .. }
             | AgentAction::Wait { .. }
+            | AgentAction::DelegateSubtask { .. }
             | AgentAction::FinishTask { .. }
             | AgentAction::ReportFailure { .. }
             | AgentAction::MouseClick { .. }
             | AgentAction::MouseDoubleClick { .. }
             | AgentAction::MouseRightClick { .. }
+            | AgentAction::MouseClickAt { .. }
             | AgentAction::TypeText { .. }
             | AgentAction::Hotkey { .. }
             | AgentAction::KeyPress { .. }
+            | AgentAction::KeyboardNavigate { .. }
             | AgentAction::Scroll { .. }
             | AgentAction::InvokeSkill { .. }
     )
@@ -148,10 +243,13 @@ pub fn needs_stability_wait(action: &AgentAction) -> bool {
         AgentAction::MouseClick { .. }
             | AgentAction::MouseDoubleClick { .. }
             | AgentAction::MouseRightClick { .. }
+            | AgentAction::MouseClickAt { .. }
             | AgentAction::TypeText { .. }
             | AgentAction::Hotkey { .. }
             | AgentAction::KeyPress { .. }
+            | AgentAction::KeyboardNavigate { .. }
             | AgentAction::Scroll { .. }
+            | AgentAction::OpenApp { .. }
     )
 }
 
@@ -173,7 +271,7 @@ pub fn extract_cell_label_from_text(text: &str) -> Option<String> {
 ///   "steps": [
 ///     {
 ///       "description": "...",
-///       "recommended_mode": "combo|chat|vlm",
+///       "recommended_mode": "combo|chat|vlm|explore",
 ///       "required_skills": ["skill_name"],
 ///       "guidance": "optional hint for the loop agent",
 ///       "skill": "skill_name (for combo mode)",
@@ -182,7 +280,7 @@ pub fn extract_cell_label_from_text(text: &str) -> Option<String> {
 ///   ]
 /// }
 /// ```
-fn parse_plan_task(args: &serde_json::Value) -> Result<AgentAction, String> {
+pub(crate) fn parse_plan_task(args: &serde_json::Value) -> Result<AgentAction, String> {
     let final_goal = args["final_goal"]
         .as_str()
         .unwrap_or("")
@@ -209,6 +307,7 @@ fn parse_plan_task(args: &serde_json::Value) -> Result<AgentAction, String> {
         let recommended_mode = match s["recommended_mode"].as_str() {
             Some("combo") => StepMode::Combo,
             Some("vlm") => StepMode::Vlm,
+            Some("explore") => StepMode::Explore,
             _ => StepMode::Chat, // default to Chat
         };
 
@@ -229,6 +328,13 @@ fn parse_plan_task(args: &serde_json::Value) -> Result<AgentAction, String> {
         // Parse guidance
         let guidance = s["guidance"].as_str().map(|g| g.to_string());
 
+        // Parallel group (models sometimes send a number)
+        let group = match &s["group"] {
+            serde_json::Value::String(g) if !g.trim().is_empty() => Some(g.trim().to_string()),
+            serde_json::Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        };
+
         steps.push(TodoStep {
             index: i,
             description: s["description"].as_str().unwrap_or("").to_string(),
@@ -238,6 +344,7 @@ fn parse_plan_task(args: &serde_json::Value) -> Result<AgentAction, String> {
             guidance,
             skill,
             params,
+            group,
             status: StepStatus::Pending,
         });
     }
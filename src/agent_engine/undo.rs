@@ -0,0 +1,190 @@
+//! Undo journal — inverse operations for the reversible actions of the last task.
+//!
+//! Before a reversible action runs, [`prepare`] captures what is needed to
+//! reverse it (a file's previous contents, the clipboard text, the app whose
+//! window is about to close); once the action succeeds, [`record`] adds the
+//! inverse to the journal. `undo_last_task` replays the journal newest-first.
+//!
+//! Reversible: typed text (erased with Backspace — text removed by
+//! `clear_first` is not restored), `write_file`, `move_file`,
+//! `clipboard_write`, Alt+F4 (the app is relaunched) and Ctrl+W in a browser
+//! (the tab is reopened). Keyboard undo goes to whatever window has focus,
+//! so the user should bring the original window forward first.
+
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+use serde::Serialize;
+
+use crate::agent_engine::state::AgentAction;
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::executor::{clipboard, fs, input, launcher};
+use crate::perception::foreground::foreground_app;
+
+/// Files larger than this are not snapshotted before `write_file`.
+const MAX_SNAPSHOT_BYTES: u64 = 4 * 1024 * 1024;
+const BROWSERS: &[&str] = &["chrome.exe", "msedge.exe", "firefox.exe", "brave.exe", "opera.exe"];
+
+/// One inverse operation.
+#[derive(Debug, Clone)]
+pub enum UndoOp {
+    /// Delete this many characters typed into the focused field.
+    EraseTypedText { chars: usize },
+    /// Put back a file's previous bytes, or delete it when it did not exist.
+    RestoreFile { path: String, previous: Option<Vec<u8>> },
+    /// Move `from` back to `to`.
+    MoveBack { from: String, to: String },
+    RestoreClipboard { previous: String },
+    /// Reopen the browser tab closed with Ctrl+W.
+    ReopenTab,
+    /// Start an application whose window was closed with Alt+F4.
+    Relaunch { app: String },
+}
+
+impl UndoOp {
+    pub fn describe(&self) -> String {
+        match self {
+            Self::EraseTypedText { chars } => format!("erase {chars} typed characters"),
+            Self::RestoreFile { path, previous: Some(_) } => format!("restore previous contents of {path}"),
+            Self::RestoreFile { path, previous: None } => format!("delete created file {path}"),
+            Self::MoveBack { from, to } => format!("move {from} back to {to}"),
+            Self::RestoreClipboard { .. } => "restore previous clipboard text".into(),
+            Self::ReopenTab => "reopen closed browser tab".into(),
+            Self::Relaunch { app } => format!("relaunch {app}"),
+        }
+    }
+
+    async fn apply(&self) -> SeeClawResult<()> {
+        match self {
+            Self::EraseTypedText { chars } => {
+                let strokes = vec![input::KeyStroke::Keys("backspace".into()); *chars];
+                let stop = std::sync::Arc::new(crate::agent_engine::node::StopSignal::new());
+                input::run_keystrokes(strokes, 0, stop).await?.into_iter().collect()
+            }
+            Self::RestoreFile { path, previous: Some(bytes) } => tokio::fs::write(path, bytes)
+                .await
+                .map_err(|e| SeeClawError::Executor(format!("restore {path}: {e}"))),
+            Self::RestoreFile { path, previous: None } => tokio::fs::remove_file(path)
+                .await
+                .map_err(|e| SeeClawError::Executor(format!("delete {path}: {e}"))),
+            Self::MoveBack { from, to } => fs::rename_or_copy(Path::new(from), Path::new(to)).await,
+            Self::RestoreClipboard { previous } => clipboard::write_text(previous.clone()).await,
+            Self::ReopenTab => input::press_hotkey("ctrl+shift+t".into()).await,
+            Self::Relaunch { app } => launcher::open_app(app.clone(), Vec::new()).await.map(|_| ()),
+        }
+    }
+}
+
+/// Result of undoing one journal entry, for the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct UndoOutcome {
+    pub description: String,
+    pub ok: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Default)]
+struct Journal {
+    goal: String,
+    running: bool,
+    ops: Vec<UndoOp>,
+}
+
+fn journal() -> &'static Mutex<Journal> {
+    static JOURNAL: OnceLock<Mutex<Journal>> = OnceLock::new();
+    JOURNAL.get_or_init(|| Mutex::new(Journal::default()))
+}
+
+fn lock() -> std::sync::MutexGuard<'static, Journal> {
+    journal().lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Start a fresh journal for `goal`; the previous task's entries are dropped.
+pub fn begin_task(goal: &str) {
+    *lock() = Journal { goal: goal.to_string(), running: true, ops: Vec::new() };
+}
+
+pub fn end_task() {
+    lock().running = false;
+}
+
+/// Capture what is needed to reverse `action`, before it runs. `None` for
+/// actions that cannot (or need not) be undone.
+pub async fn prepare(action: &AgentAction) -> Option<UndoOp> {
+    match action {
+        AgentAction::TypeText { text, .. } => Some(UndoOp::EraseTypedText { chars: text.chars().count() }),
+        AgentAction::WriteFile { path, .. } => {
+            // Same resolution as `fs::write_file`, so the snapshot is of the file it writes.
+            let path = fs::absolute(path).ok()?;
+            let previous = match tokio::fs::metadata(&path).await {
+                Ok(meta) if meta.len() > MAX_SNAPSHOT_BYTES => {
+                    tracing::info!(path = %path.display(), size = meta.len(), "undo: file too large to snapshot");
+                    return None;
+                }
+                Ok(_) => Some(tokio::fs::read(&path).await.ok()?),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                Err(e) => {
+                    tracing::info!(path = %path.display(), error = %e, "undo: cannot snapshot file");
+                    return None;
+                }
+            };
+            Some(UndoOp::RestoreFile { path: path.display().to_string(), previous })
+        }
+        AgentAction::MoveFile { from, to } => {
+            let (from, to) = (fs::absolute(from).ok()?, fs::absolute(to).ok()?);
+            Some(UndoOp::MoveBack { from: to.display().to_string(), to: from.display().to_string() })
+        }
+        AgentAction::ClipboardWrite { .. } => {
+            clipboard::read_text().await.ok().map(|previous| UndoOp::RestoreClipboard { previous })
+        }
+        AgentAction::Hotkey { keys } => {
+            let keys = keys.to_lowercase().replace(' ', "");
+            let app = foreground_app()?.process;
+            match keys.as_str() {
+                "alt+f4" if !app.is_empty() => {
+                    Some(UndoOp::Relaunch { app: app.trim_end_matches(".exe").to_string() })
+                }
+                "ctrl+w" if BROWSERS.contains(&app.as_str()) => Some(UndoOp::ReopenTab),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Add the inverse of an action that just succeeded.
+pub fn record(op: UndoOp) {
+    let mut j = lock();
+    if j.running {
+        tracing::debug!(op = %op.describe(), "undo: recorded");
+        j.ops.push(op);
+    }
+}
+
+/// Goal and entry descriptions of the journal, newest first.
+pub fn summary() -> (String, Vec<String>) {
+    let j = lock();
+    (j.goal.clone(), j.ops.iter().rev().map(UndoOp::describe).collect())
+}
+
+/// Reverse the last task's recorded actions, newest first. The journal is
+/// emptied either way; a failed step does not stop the rest.
+pub async fn undo_last_task() -> SeeClawResult<Vec<UndoOutcome>> {
+    let ops = {
+        let mut j = lock();
+        if j.running {
+            return Err(SeeClawError::Agent("a task is still running; stop it before undoing".into()));
+        }
+        std::mem::take(&mut j.ops)
+    };
+    let mut outcomes = Vec::with_capacity(ops.len());
+    for op in ops.iter().rev() {
+        let result = op.apply().await;
+        if let Err(e) = &result {
+            tracing::warn!(op = %op.describe(), error = %e, "undo: step failed");
+        }
+        outcomes.push(UndoOutcome { description: op.describe(), ok: result.is_ok(), error: result.err().map(|e| e.to_string()) });
+        tokio::time::sleep(std::time::Duration::from_millis(150)).await;
+    }
+    Ok(outcomes)
+}
@@ -0,0 +1,86 @@
+//! Background mode — the agent outlives its window.
+//!
+//! The agent loop is spawned in `setup` and only ends with the process, so
+//! keeping it alive is a matter of not exiting when the last window closes.
+//! With `[background] keep_running` the main window can be closed (its
+//! webview is destroyed, not hidden) while the tray, scheduled and remote
+//! goals keep working; the tray recreates the window on demand and the new
+//! UI picks up any pending approval. `autostart` registers a login item that
+//! launches with [`BACKGROUND_ARG`], so no window is created at all.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tauri::{AppHandle, Manager, RunEvent, Runtime, WebviewWindowBuilder, Window, WindowEvent};
+use tauri_plugin_autostart::ManagerExt;
+
+use crate::config::BackgroundConfig;
+
+/// Command-line flag: start in the tray without creating the main window.
+pub const BACKGROUND_ARG: &str = "--background";
+const MAIN_WINDOW: &str = "main";
+
+static KEEP_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Whether this process was launched with [`BACKGROUND_ARG`].
+pub fn launched_hidden() -> bool {
+    std::env::args().skip(1).any(|a| a == BACKGROUND_ARG)
+}
+
+/// Apply `[background]` settings: exit policy and the OS login item.
+/// Called at startup and after the config is saved.
+pub fn apply<R: Runtime>(app: &AppHandle<R>, cfg: &BackgroundConfig) {
+    KEEP_RUNNING.store(cfg.keeps_running() || launched_hidden(), Ordering::Relaxed);
+    let autolaunch = app.autolaunch();
+    let registered = autolaunch.is_enabled().unwrap_or(false);
+    let result = match (cfg.autostart, registered) {
+        (true, false) => autolaunch.enable(),
+        (false, true) => autolaunch.disable(),
+        _ => Ok(()),
+    };
+    if let Err(e) = result {
+        tracing::warn!(error = %e, autostart = cfg.autostart, "background: failed to update login item");
+    }
+}
+
+/// Show the main window, creating it from `tauri.conf.json` if it was never
+/// opened or has been closed.
+pub fn show_main_window<R: Runtime>(app: &AppHandle<R>) {
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW) {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+        return;
+    }
+    let Some(conf) = app.config().app.windows.iter().find(|w| w.label == MAIN_WINDOW) else {
+        tracing::warn!("background: no main window in tauri.conf.json");
+        return;
+    };
+    if let Err(e) = WebviewWindowBuilder::from_config(app, conf).and_then(|b| b.build()) {
+        tracing::warn!(error = %e, "background: failed to create main window");
+    }
+}
+
+/// Closing the main window quits the app unless background mode is on.
+/// Checked on `Destroyed` rather than by Tauri's "last window" rule, since
+/// a hidden quick-goal window would otherwise keep the process alive.
+pub fn on_window_event<R: Runtime>(window: &Window<R>, event: &WindowEvent) {
+    if window.label() == MAIN_WINDOW
+        && matches!(event, WindowEvent::Destroyed)
+        && !KEEP_RUNNING.load(Ordering::Relaxed)
+    {
+        window.app_handle().exit(0);
+    }
+}
+
+/// Keep the process alive when the last window closes in background mode.
+/// Explicit exits (tray "Quit") carry an exit code and are let through.
+pub fn on_run_event<R: Runtime>(_app: &AppHandle<R>, event: RunEvent) {
+    match event {
+        RunEvent::ExitRequested { code: None, api, .. } if KEEP_RUNNING.load(Ordering::Relaxed) => {
+            tracing::info!("background: last window closed, agent keeps running in the tray");
+            api.prevent_exit();
+        }
+        RunEvent::Exit => crate::telemetry::shutdown(),
+        _ => {}
+    }
+}
@@ -0,0 +1,366 @@
+//! Benchmark task suite — compare model configurations on scripted goals.
+//!
+//! A suite is a folder with a `suite.toml` listing the model variants to
+//! compare, plus one `*.toml` file per task:
+//!
+//! ```toml
+//! # suite.toml
+//! [[variants]]
+//! name = "gpt-4o everywhere"
+//! [variants.roles.tools]
+//! provider = "openai"
+//! model = "gpt-4o"
+//!
+//! # open_notepad.toml
+//! goal = "open notepad and type hello"
+//! expect = ["hello"]      # substrings the final summary must contain
+//! timeout_secs = 180
+//! approve = true          # answer approval prompts with "yes"
+//! ```
+//!
+//! Every task runs once per variant through the embeddable [`Agent`] on the
+//! current display — run it on a dedicated test VM, not your own desktop.
+//! Success, steps, latency and cost come from the agent's event stream; the
+//! report is saved as JSON and a Markdown comparison table under
+//! `SeeClaw/bench/`.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+use crate::agent::Agent;
+use crate::agent_engine::history::data_dir_or_cwd;
+use crate::config::{AppConfig, RolesConfig};
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::events::{AgentEventMessage, ChannelSink};
+
+const SUITE_FILE: &str = "suite.toml";
+const DEFAULT_TIMEOUT_SECS: u64 = 300;
+/// How long a stopped task gets to wind down before the next one starts.
+const STOP_GRACE: Duration = Duration::from_secs(15);
+
+/// One scripted goal.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BenchTask {
+    /// File stem; not read from the file.
+    #[serde(skip)]
+    pub name: String,
+    pub goal: String,
+    /// Substrings (case-insensitive) the final summary must contain.
+    #[serde(default)]
+    pub expect: Vec<String>,
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    /// Approve every `action_required` prompt instead of rejecting it.
+    #[serde(default)]
+    pub approve: bool,
+}
+
+/// A model configuration to compare. Roles set here replace the base
+/// config's; unset roles keep it.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BenchVariant {
+    pub name: String,
+    #[serde(default)]
+    pub roles: RolesConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct SuiteFile {
+    #[serde(default)]
+    variants: Vec<BenchVariant>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BenchSuite {
+    pub variants: Vec<BenchVariant>,
+    pub tasks: Vec<BenchTask>,
+}
+
+impl BenchSuite {
+    /// Load `suite.toml` and every other `*.toml` in `dir` as a task, sorted
+    /// by name. Without variants the base config runs as the only one.
+    pub fn load(dir: &Path) -> SeeClawResult<Self> {
+        let suite_path = dir.join(SUITE_FILE);
+        let variants = if suite_path.exists() {
+            let text = std::fs::read_to_string(&suite_path)?;
+            toml::from_str::<SuiteFile>(&text)
+                .map_err(|e| SeeClawError::Config(format!("{}: {e}", suite_path.display())))?
+                .variants
+        } else {
+            Vec::new()
+        };
+
+        let mut tasks = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("toml")
+                || path.file_name().and_then(|n| n.to_str()) == Some(SUITE_FILE)
+            {
+                continue;
+            }
+            let text = std::fs::read_to_string(&path)?;
+            let mut task: BenchTask = toml::from_str(&text)
+                .map_err(|e| SeeClawError::Config(format!("{}: {e}", path.display())))?;
+            task.name = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default().to_string();
+            tasks.push(task);
+        }
+        if tasks.is_empty() {
+            return Err(SeeClawError::Config(format!("no task files in {}", dir.display())));
+        }
+        tasks.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let variants = if variants.is_empty() {
+            vec![BenchVariant { name: "base".into(), roles: RolesConfig::default() }]
+        } else {
+            variants
+        };
+        Ok(Self { variants, tasks })
+    }
+}
+
+/// Outcome of one task under one variant.
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskResult {
+    pub task: String,
+    pub success: bool,
+    pub timed_out: bool,
+    /// Actions executed (each call of a keyboard batch counts).
+    pub steps: u32,
+    pub latency_ms: u64,
+    pub llm_calls: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub cost_usd: f64,
+    /// Final summary or error message.
+    pub summary: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VariantReport {
+    pub variant: String,
+    pub results: Vec<TaskResult>,
+}
+
+impl VariantReport {
+    pub fn success_rate(&self) -> f64 {
+        if self.results.is_empty() {
+            return 0.0;
+        }
+        self.results.iter().filter(|r| r.success).count() as f64 / self.results.len() as f64
+    }
+
+    fn mean(&self, f: impl Fn(&TaskResult) -> f64) -> f64 {
+        if self.results.is_empty() {
+            return 0.0;
+        }
+        self.results.iter().map(f).sum::<f64>() / self.results.len() as f64
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchReport {
+    pub started_at: String,
+    pub suite: String,
+    pub variants: Vec<VariantReport>,
+}
+
+impl BenchReport {
+    /// Comparison table (one row per variant) followed by per-task results.
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# Benchmark: {}\n\nStarted {}\n\n", self.suite, self.started_at);
+        out.push_str("| Variant | Success | Mean steps | Mean latency (s) | Tokens | Cost (USD) |\n");
+        out.push_str("|---|---|---|---|---|---|\n");
+        for v in &self.variants {
+            let tokens: u64 = v.results.iter().map(|r| r.prompt_tokens + r.completion_tokens).sum();
+            let cost: f64 = v.results.iter().map(|r| r.cost_usd).sum();
+            out.push_str(&format!(
+                "| {} | {:.0}% | {:.1} | {:.1} | {} | {:.4} |\n",
+                v.variant,
+                v.success_rate() * 100.0,
+                v.mean(|r| r.steps as f64),
+                v.mean(|r| r.latency_ms as f64) / 1000.0,
+                tokens,
+                cost,
+            ));
+        }
+        for v in &self.variants {
+            out.push_str(&format!("\n## {}\n\n| Task | Result | Steps | Latency (s) | Cost (USD) |\n|---|---|---|---|---|\n", v.variant));
+            for r in &v.results {
+                let outcome = if r.success { "pass" } else if r.timed_out { "timeout" } else { "fail" };
+                out.push_str(&format!(
+                    "| {} | {} | {} | {:.1} | {:.4} |\n",
+                    r.task,
+                    outcome,
+                    r.steps,
+                    r.latency_ms as f64 / 1000.0,
+                    r.cost_usd
+                ));
+            }
+        }
+        out
+    }
+
+    /// Write `<timestamp>.json` and `<timestamp>.md` under `SeeClaw/bench/`.
+    /// Returns the Markdown path.
+    pub fn save(&self) -> SeeClawResult<PathBuf> {
+        let dir = data_dir_or_cwd("bench");
+        let stem = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+        std::fs::write(dir.join(format!("{stem}.json")), serde_json::to_string_pretty(self)?)?;
+        let md_path = dir.join(format!("{stem}.md"));
+        std::fs::write(&md_path, self.to_markdown())?;
+        Ok(md_path)
+    }
+}
+
+/// Run every task of the suite in `dir` under every variant, one agent per
+/// variant. Must be called inside a Tokio runtime.
+pub async fn run_suite(dir: &Path, base: &AppConfig) -> SeeClawResult<BenchReport> {
+    let suite = BenchSuite::load(dir)?;
+    let mut report = BenchReport {
+        started_at: chrono::Local::now().to_rfc3339(),
+        suite: dir.display().to_string(),
+        variants: Vec::with_capacity(suite.variants.len()),
+    };
+
+    for variant in &suite.variants {
+        tracing::info!(variant = %variant.name, tasks = suite.tasks.len(), "bench: running variant");
+        let config = variant_config(base, variant);
+        let (sink, mut events) = ChannelSink::new(1024);
+        let agent = Agent::builder().config(config).event_sink(Arc::new(sink)).build();
+
+        let mut results = Vec::with_capacity(suite.tasks.len());
+        for task in &suite.tasks {
+            let result = run_task(&agent, &mut events, task).await?;
+            tracing::info!(variant = %variant.name, task = %task.name, success = result.success, "bench: task finished");
+            results.push(result);
+        }
+        report.variants.push(VariantReport { variant: variant.name.clone(), results });
+    }
+    Ok(report)
+}
+
+fn variant_config(base: &AppConfig, variant: &BenchVariant) -> AppConfig {
+    let mut config = base.clone();
+    let roles = &mut config.llm.roles;
+    let overrides = variant.roles.clone();
+    if overrides.routing.is_some() {
+        roles.routing = overrides.routing;
+    }
+    if overrides.chat.is_some() {
+        roles.chat = overrides.chat;
+    }
+    if overrides.tools.is_some() {
+        roles.tools = overrides.tools;
+    }
+    if overrides.vision.is_some() {
+        roles.vision = overrides.vision;
+    }
+    config
+}
+
+async fn run_task(
+    agent: &Agent,
+    events: &mut broadcast::Receiver<AgentEventMessage>,
+    task: &BenchTask,
+) -> SeeClawResult<TaskResult> {
+    let mut result = TaskResult {
+        task: task.name.clone(),
+        success: false,
+        timed_out: false,
+        steps: 0,
+        latency_ms: 0,
+        llm_calls: 0,
+        prompt_tokens: 0,
+        completion_tokens: 0,
+        cost_usd: 0.0,
+        summary: String::new(),
+    };
+
+    // Drop whatever the previous task left in the channel.
+    while events.try_recv().is_ok() {}
+
+    let started = Instant::now();
+    let deadline = started + Duration::from_secs(task.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS));
+    agent.submit_goal(task.goal.clone()).await?;
+
+    let finished = loop {
+        let msg = match tokio::time::timeout_at(deadline.into(), events.recv()).await {
+            Err(_) => break false,
+            Ok(Err(broadcast::error::RecvError::Lagged(n))) => {
+                tracing::warn!(skipped = n, "bench: event stream lagged");
+                continue;
+            }
+            Ok(Err(broadcast::error::RecvError::Closed)) => {
+                return Err(SeeClawError::Agent("agent loop stopped".into()));
+            }
+            Ok(Ok(msg)) => msg,
+        };
+        match observe(&msg, task, &mut result) {
+            Some(Observed::ApprovalRequested) => agent.respond_to_approval(task.approve).await?,
+            Some(Observed::Finished) => break true,
+            None => {}
+        }
+    };
+    result.latency_ms = started.elapsed().as_millis() as u64;
+
+    if !finished {
+        result.timed_out = true;
+        result.summary = "timed out".into();
+        agent.stop().await?;
+        // Wait for the loop to settle so its events don't leak into the next task.
+        let grace = Instant::now() + STOP_GRACE;
+        while let Ok(Ok(msg)) = tokio::time::timeout_at(grace.into(), events.recv()).await {
+            let state = msg.payload["state"].as_str().unwrap_or_default();
+            if msg.event == "agent_state_changed" && matches!(state, "done" | "error" | "idle") {
+                break;
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Events of a running task that need a reaction from the runner.
+enum Observed {
+    ApprovalRequested,
+    Finished,
+}
+
+/// Fold one event into `result`.
+fn observe(msg: &AgentEventMessage, task: &BenchTask, result: &mut TaskResult) -> Option<Observed> {
+    let p = &msg.payload;
+    match msg.event.as_str() {
+        "action_result" => {
+            result.steps += p["steps"].as_u64().unwrap_or(1) as u32;
+            None
+        }
+        "usage_updated" => {
+            let t = &p["task"];
+            result.llm_calls = t["calls"].as_u64().unwrap_or(result.llm_calls);
+            result.prompt_tokens = t["prompt_tokens"].as_u64().unwrap_or(result.prompt_tokens);
+            result.completion_tokens = t["completion_tokens"].as_u64().unwrap_or(result.completion_tokens);
+            result.cost_usd = t["cost_usd"].as_f64().unwrap_or(result.cost_usd);
+            None
+        }
+        "action_required" => Some(Observed::ApprovalRequested),
+        "agent_state_changed" => match p["state"].as_str().unwrap_or_default() {
+            "done" => {
+                let summary = p["summary"].as_str().unwrap_or_default().to_string();
+                let lower = summary.to_lowercase();
+                result.success = p["category"].is_null()
+                    && task.expect.iter().all(|e| lower.contains(&e.to_lowercase()));
+                result.summary = summary;
+                Some(Observed::Finished)
+            }
+            "error" => {
+                result.summary = p["message"].as_str().unwrap_or_default().to_string();
+                Some(Observed::Finished)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
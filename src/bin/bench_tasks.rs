@@ -0,0 +1,42 @@
+//! Run a benchmark task suite and print the comparison table.
+//!
+//! Usage: `bench_tasks <suite-dir>` — see `seeclaw_lib::bench` for the
+//! folder layout. Uses the regular `config.toml` as the base configuration.
+
+use std::path::PathBuf;
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("seeclaw_lib=info")),
+        )
+        .init();
+
+    let Some(dir) = std::env::args_os().nth(1).map(PathBuf::from) else {
+        eprintln!("usage: bench_tasks <suite-dir>");
+        std::process::exit(2);
+    };
+    let config = match seeclaw_lib::config::load_config() {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("failed to load config: {e}");
+            std::process::exit(1);
+        }
+    };
+
+    match seeclaw_lib::bench::run_suite(&dir, &config).await {
+        Ok(report) => {
+            println!("{}", report.to_markdown());
+            match report.save() {
+                Ok(path) => println!("Report saved to {}", path.display()),
+                Err(e) => eprintln!("failed to save report: {e}"),
+            }
+        }
+        Err(e) => {
+            eprintln!("benchmark failed: {e}");
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! Headless SeeClaw — run one goal without the window, events as JSON lines.
+//!
+//! Usage: `seeclaw-cli run "<goal>" [--config <path>] [--approve all|none]
+//! [--timeout <secs>]`. Every agent event is printed to stdout as one JSON
+//! object (`{"ts", "event", "payload"}`); logs go to stderr. Approval
+//! requests are answered by `--approve` (default `none`: reject). Exits 0
+//! when the task finishes, 1 when it fails or times out, 2 on bad usage.
+//!
+//! `seeclaw-cli replay <repro-log> [--task <n>] [...run options]` re-runs
+//! task `n` (default 1) of a reproducibility log, answering every LLM call
+//! from the log instead of the network; divergences are logged to stderr.
+//!
+//! `seeclaw-cli benchmark-perception [--samples <n>] [--config <path>]`
+//! prints a perception benchmark of the configured parser chain as JSON.
+//!
+//! Built with `--features cli`.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use seeclaw_lib::events::{AgentEventMessage, EventSink};
+use tokio::sync::mpsc;
+
+const USAGE: &str = "usage: seeclaw-cli run \"<goal>\" [--config <path>] [--approve all|none] [--timeout <secs>]\n       seeclaw-cli replay <repro-log> [--task <n>] [--config <path>] [--approve all|none] [--timeout <secs>]\n       seeclaw-cli benchmark-perception [--samples <n>] [--config <path>]";
+
+/// Forwards events to `main` unbounded, so none are dropped while it prints.
+struct ForwardSink(mpsc::UnboundedSender<AgentEventMessage>);
+
+impl EventSink for ForwardSink {
+    fn emit_json(&self, event: &str, payload: serde_json::Value) {
+        let _ = self.0.send(AgentEventMessage { event: event.to_string(), payload });
+    }
+}
+
+struct Args {
+    /// The goal for `run`, the log path for `replay`.
+    goal: String,
+    /// `Some(task)` for `replay`.
+    replay_task: Option<usize>,
+    config: Option<PathBuf>,
+    approve_all: bool,
+    timeout: Option<Duration>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = std::env::args().skip(1);
+    let replay_task = match args.next().as_deref() {
+        Some("run") => None,
+        Some("replay") => Some(1),
+        _ => return Err(USAGE.into()),
+    };
+    let mut parsed = Args { goal: String::new(), replay_task, config: None, approve_all: false, timeout: None };
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{arg} needs a value\n{USAGE}"));
+        match arg.as_str() {
+            "--config" => parsed.config = Some(PathBuf::from(value()?)),
+            "--task" if parsed.replay_task.is_some() => {
+                parsed.replay_task = Some(value()?.parse().map_err(|_| "--task takes a number".to_string())?);
+            }
+            "--approve" => {
+                parsed.approve_all = match value()?.as_str() {
+                    "all" => true,
+                    "none" => false,
+                    other => return Err(format!("--approve takes all or none, not '{other}'")),
+                }
+            }
+            "--timeout" => {
+                let secs = value()?.parse::<u64>().map_err(|_| "--timeout takes seconds".to_string())?;
+                parsed.timeout = Some(Duration::from_secs(secs));
+            }
+            other if other.starts_with("--") => return Err(format!("unknown option {other}\n{USAGE}")),
+            goal if parsed.goal.is_empty() => parsed.goal = goal.to_string(),
+            _ => return Err(USAGE.into()),
+        }
+    }
+    if parsed.goal.trim().is_empty() {
+        return Err(USAGE.into());
+    }
+    Ok(parsed)
+}
+
+struct BenchArgs {
+    samples: usize,
+    config: Option<PathBuf>,
+}
+
+fn parse_bench_args() -> Result<BenchArgs, String> {
+    let mut args = std::env::args().skip(2);
+    let mut parsed = BenchArgs { samples: seeclaw_lib::perception::benchmark::DEFAULT_SAMPLES, config: None };
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{arg} needs a value\n{USAGE}"));
+        match arg.as_str() {
+            "--config" => parsed.config = Some(PathBuf::from(value()?)),
+            "--samples" => parsed.samples = value()?.parse().map_err(|_| "--samples takes a number".to_string())?,
+            _ => return Err(format!("unknown option {arg}\n{USAGE}")),
+        }
+    }
+    Ok(parsed)
+}
+
+fn load_config(path: Option<&PathBuf>) -> seeclaw_lib::config::AppConfig {
+    let config = match path {
+        Some(path) => seeclaw_lib::config::load_config_from(path),
+        None => seeclaw_lib::config::load_config(),
+    };
+    match config {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("failed to load config: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+async fn benchmark_perception() -> i32 {
+    let args = match parse_bench_args() {
+        Ok(a) => a,
+        Err(msg) => {
+            eprintln!("{msg}");
+            return 2;
+        }
+    };
+    let config = load_config(args.config.as_ref());
+    seeclaw_lib::telemetry::init("seeclaw_lib=warn", std::io::stderr, &config.telemetry);
+    match seeclaw_lib::perception::benchmark::run(&config.perception, args.samples).await {
+        Ok(report) => {
+            println!("{}", serde_json::to_string_pretty(&report).unwrap_or_default());
+            0
+        }
+        Err(e) => {
+            eprintln!("benchmark failed: {e}");
+            1
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    if std::env::args().nth(1).as_deref() == Some("benchmark-perception") {
+        std::process::exit(benchmark_perception().await);
+    }
+    let args = match parse_args() {
+        Ok(a) => a,
+        Err(msg) => {
+            eprintln!("{msg}");
+            std::process::exit(2);
+        }
+    };
+    let mut config = load_config(args.config.as_ref());
+    seeclaw_lib::telemetry::init("seeclaw_lib=info", std::io::stderr, &config.telemetry);
+
+    let goal = match args.replay_task {
+        Some(task) => {
+            let log = match seeclaw_lib::llm::repro::ReplayLog::load(std::path::Path::new(&args.goal), task) {
+                Ok(log) => log,
+                Err(e) => {
+                    eprintln!("cannot replay {}: {e}", args.goal);
+                    std::process::exit(1);
+                }
+            };
+            config.reproducibility.replay = Some(args.goal.clone());
+            config.reproducibility.replay_task = task;
+            log.goal
+        }
+        None => args.goal.clone(),
+    };
+
+    let (tx, mut events) = mpsc::unbounded_channel();
+    let agent = seeclaw_lib::Agent::builder()
+        .config(config)
+        .event_sink(Arc::new(ForwardSink(tx)))
+        .build();
+    if let Err(e) = agent.submit_goal(goal).await {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
+
+    let run = async {
+        let mut stdout = std::io::stdout().lock();
+        while let Some(msg) = events.recv().await {
+            let line = serde_json::json!({
+                "ts": chrono::Local::now().to_rfc3339(),
+                "event": msg.event,
+                "payload": msg.payload,
+            });
+            let _ = writeln!(stdout, "{line}");
+            let _ = stdout.flush();
+
+            match msg.event.as_str() {
+                "action_required" => {
+                    let _ = agent.respond_to_approval(args.approve_all).await;
+                }
+                "agent_state_changed" => match msg.payload["state"].as_str() {
+                    Some("done") if msg.payload["category"].is_null() => return 0,
+                    Some("done") | Some("error") => return 1,
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+        1
+    };
+    let code = match args.timeout {
+        Some(limit) => match tokio::time::timeout(limit, run).await {
+            Ok(code) => code,
+            Err(_) => {
+                eprintln!("timed out after {}s, stopping the task", limit.as_secs());
+                let _ = agent.stop().await;
+                1
+            }
+        },
+        None => run.await,
+    };
+    seeclaw_lib::telemetry::shutdown();
+    std::process::exit(code);
+}
@@ -3,11 +3,17 @@ use std::sync::Arc;
 use tauri::{AppHandle, Emitter, State};
 use tokio::sync::Mutex;
 
-use crate::agent_engine::state::AgentEvent;
-use crate::config::{load_config, save_config, get_config_path, AppConfig};
+use crate::agent_engine::{checkpoint, history, replay, report};
+use crate::agent_engine::state::{AgentEvent, LoopOverrides};
+use crate::agent_engine::task_queue;
+use crate::config::{load_config, save_config, get_config_path, AppConfig, ExclusionZone};
+use crate::events::SharedEventSink;
+use crate::llm::probe::{self, ProviderTestReport};
 use crate::llm::registry::ProviderRegistry;
 use crate::llm::tools::load_builtin_tools;
 use crate::llm::types::ChatMessage;
+use crate::profiles::{self, ProfileList};
+use crate::scheduler;
 use crate::AgentHandle;
 
 /// Ping command for IPC verification.
@@ -28,17 +34,30 @@ pub async fn get_config_file_path() -> Result<String, String> {
     get_config_path().map_err(|e| e.to_string())
 }
 
-/// Send a goal to the AgentEngine and start the run loop.
+/// Send a goal to the AgentEngine. While a task is running the goal is
+/// queued behind it (see `list_queue`) instead of interrupting it.
+/// `loop_config` overrides the `[safety]` loop mode and limits for this goal;
+/// `profile` runs it with another config profile (see `list_profiles`).
 #[tauri::command]
 pub async fn start_task(
     _app: AppHandle,
     handle: State<'_, Arc<AgentHandle>>,
     task: String,
+    loop_config: Option<LoopOverrides>,
+    profile: Option<String>,
 ) -> Result<(), String> {
     tracing::info!(task = %task, "start_task: forwarding GoalReceived to AgentEngine");
+    let loop_config = match profile.filter(|p| !p.trim().is_empty()) {
+        Some(p) => Some(LoopOverrides { profile: Some(p), ..loop_config.unwrap_or_default() }),
+        None => loop_config,
+    };
+    let event = match loop_config {
+        Some(l) => AgentEvent::GoalWithLoop(task, l),
+        None => AgentEvent::GoalReceived(task),
+    };
     handle
         .tx
-        .send(AgentEvent::GoalReceived(task))
+        .send(event)
         .await
         .map_err(|e| {
             tracing::error!("start_task: channel send failed: {e}");
@@ -54,16 +73,72 @@ pub async fn stop_task(
     _app: AppHandle,
     handle: State<'_, Arc<AgentHandle>>,
 ) -> Result<(), String> {
-    tracing::info!("stop_task: signalling stop via atomic flag + channel");
+    tracing::info!("stop_task: signalling stop via stop signal + channel");
     // Set the atomic flag FIRST — immediately visible to the engine even mid-operation
-    handle
-        .stop_flag
-        .store(true, std::sync::atomic::Ordering::SeqCst);
+    handle.stop_flag.set();
     // Also send the channel event as backup for when the engine is blocked on recv()
     let _ = handle.tx.send(AgentEvent::Stop).await;
     Ok(())
 }
 
+/// Goals waiting for the running task to finish, in the order they will run.
+#[tauri::command]
+pub async fn list_queue() -> Result<Vec<task_queue::QueuedTask>, String> {
+    Ok(task_queue::list())
+}
+
+/// Drop a queued goal before it starts.
+#[tauri::command]
+pub async fn cancel_queued(app: AppHandle, id: u64) -> Result<bool, String> {
+    let removed = task_queue::cancel(id);
+    if removed {
+        task_queue::emit(&app);
+    }
+    Ok(removed)
+}
+
+/// Move the queued goals `ids` to the front of the queue, in that order.
+#[tauri::command]
+pub async fn reorder_queue(app: AppHandle, ids: Vec<u64>) -> Result<Vec<task_queue::QueuedTask>, String> {
+    task_queue::reorder(&ids);
+    task_queue::emit(&app);
+    Ok(task_queue::list())
+}
+
+/// Registered scheduled and recurring goals.
+#[tauri::command]
+pub async fn list_schedules() -> Result<Vec<scheduler::Schedule>, String> {
+    Ok(scheduler::list())
+}
+
+/// Run `goal` on a cron schedule (`{"kind":"cron","expr":"0 9 * * 1-5"}`)
+/// or once (`{"kind":"at","time":"<RFC 3339>"}`).
+#[tauri::command]
+pub async fn add_schedule(goal: String, when: scheduler::When) -> Result<scheduler::Schedule, String> {
+    scheduler::add(goal, when).map_err(|e| e.to_string())
+}
+
+/// Delete a schedule. Returns `false` when it does not exist.
+#[tauri::command]
+pub async fn remove_schedule(id: String) -> Result<bool, String> {
+    Ok(scheduler::remove(&id))
+}
+
+/// Pause the running task before its next node, so the user can take over
+/// the mouse and keyboard. Ignored when no task is running.
+#[tauri::command]
+pub async fn pause_task(handle: State<'_, Arc<AgentHandle>>) -> Result<(), String> {
+    tracing::info!("pause_task");
+    handle.tx.send(AgentEvent::Pause).await.map_err(|e| format!("agent channel closed: {e}"))
+}
+
+/// Continue a paused task from the step it was on.
+#[tauri::command]
+pub async fn resume_task(handle: State<'_, Arc<AgentHandle>>) -> Result<(), String> {
+    tracing::info!("resume_task");
+    handle.tx.send(AgentEvent::Resume).await.map_err(|e| format!("agent channel closed: {e}"))
+}
+
 /// Confirm or deny a pending high-risk action.
 #[tauri::command]
 pub async fn confirm_action(
@@ -85,6 +160,107 @@ pub async fn confirm_action(
     Ok(())
 }
 
+/// The approval request the agent is waiting on, for a window that opened
+/// after `action_required` was emitted.
+#[tauri::command]
+pub async fn get_pending_approval() -> Result<Option<serde_json::Value>, String> {
+    Ok(crate::agent_engine::approvals::pending())
+}
+
+/// Reverse the reversible actions of the last finished task (typed text,
+/// file writes and moves, clipboard changes, closed windows), newest first.
+/// Emits `undo_completed` with the per-step outcomes.
+#[tauri::command]
+pub async fn undo_last_task(app: AppHandle) -> Result<Vec<crate::agent_engine::undo::UndoOutcome>, String> {
+    let (goal, steps) = crate::agent_engine::undo::summary();
+    tracing::info!(%goal, steps = steps.len(), "undo_last_task");
+    let outcomes = crate::agent_engine::undo::undo_last_task().await.map_err(|e| e.to_string())?;
+    let _ = app.emit("undo_completed", serde_json::json!({ "goal": goal, "outcomes": &outcomes }));
+    Ok(outcomes)
+}
+
+/// Saved task sessions, newest first, for the history view.
+#[tauri::command]
+pub async fn list_sessions() -> Result<Vec<history::SessionSummary>, String> {
+    tokio::task::spawn_blocking(history::list_sessions)
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// One session's goals and actions, with screenshot thumbnails as data URLs.
+#[tauri::command]
+pub async fn get_session(session_id: String) -> Result<Vec<history::SessionEntry>, String> {
+    tokio::task::spawn_blocking(move || history::read_session(&session_id))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a saved session and everything stored with it.
+#[tauri::command]
+pub async fn delete_session(session_id: String) -> Result<(), String> {
+    tracing::info!(%session_id, "delete_session");
+    tokio::task::spawn_blocking(move || history::delete_session(&session_id))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Write a Markdown or HTML report of a saved session (goal, plan, steps
+/// with screenshots, outcome) into its session folder; returns the path.
+#[tauri::command]
+pub async fn export_session(session_id: String, format: report::ReportFormat) -> Result<String, String> {
+    tokio::task::spawn_blocking(move || report::export_session(&session_id, format))
+        .await
+        .map_err(|e| e.to_string())?
+        .map(|path| path.display().to_string())
+        .map_err(|e| e.to_string())
+}
+
+/// Play a saved session back step by step (`visual`), or perform its GUI
+/// actions again (`execute`). Progress arrives as `replay_step` events.
+#[tauri::command]
+pub async fn replay_session(
+    app: AppHandle,
+    session_id: String,
+    mode: replay::ReplayMode,
+) -> Result<replay::ReplayReport, String> {
+    let config = load_config().map_err(|e| e.to_string())?;
+    replay::replay_session(&session_id, mode, &config, &app)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Stop the running replay after its current step.
+#[tauri::command]
+pub async fn stop_replay() -> Result<(), String> {
+    replay::stop();
+    Ok(())
+}
+
+/// Restart the unfinished task that session `session_id` left behind (the
+/// app was closed or crashed mid-task) from the step it was on. Returns the
+/// task's goal.
+#[tauri::command]
+pub async fn resume_session(
+    handle: State<'_, Arc<AgentHandle>>,
+    session_id: String,
+) -> Result<String, String> {
+    let checkpoint = tokio::task::spawn_blocking(move || checkpoint::load(&session_id))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+    let goal = checkpoint.goal.clone();
+    tracing::info!(session = %checkpoint.session_id, %goal, step = checkpoint.current_step_idx, "resume_session");
+    handle
+        .tx
+        .send(AgentEvent::RestoreSession(Box::new(checkpoint)))
+        .await
+        .map_err(|e| format!("agent channel closed: {e}"))?;
+    Ok(goal)
+}
+
 /// Direct chat command — bypasses the agent engine, uses the "chat" role config.
 /// Emits "llm_stream_chunk" events to the frontend as chunks arrive.
 #[tauri::command]
@@ -98,36 +274,38 @@ pub async fn start_chat(
         let registry = state.lock().await;
         registry.call_config_for_role("chat").map_err(|e| e.to_string())?
     };
+    let sink: SharedEventSink = Arc::new(app);
     provider
-        .chat(messages, tools, &cfg, &app)
+        .chat(messages, tools, &cfg, &sink)
         .await
         .map(|_| ())
         .map_err(|e| e.to_string())
 }
 
 /// Return the current AppConfig as JSON for the settings UI.
-/// If api_key is empty in config.toml, populate from environment variable.
+/// If api_key is empty in config.toml, populate from the OS credential store
+/// (`[llm] keyring`) or the environment variable.
 /// API keys are shown to allow editing (not redacted in settings UI).
 /// Falls back to a default config if config.toml is missing (first-run scenario).
 #[tauri::command]
 pub async fn get_config() -> Result<serde_json::Value, String> {
     let mut cfg = load_config().unwrap_or_default();
     
-    // Populate api_key from environment variables if not set in config
+    // Populate api_key from the keyring / environment if not set in config
     for (id, entry) in cfg.llm.providers.iter_mut() {
         if entry.api_key.as_deref().map(|k| k.is_empty()).unwrap_or(true) {
-            // Try to read from environment variable SEECLAW_{ID}_API_KEY
-            let env_key = format!("SEECLAW_{}_API_KEY", id.to_uppercase());
-            if let Ok(key) = std::env::var(&env_key) {
-                if !key.is_empty() {
-                    tracing::debug!(provider = id, "populated api_key from environment variable");
-                    entry.api_key = Some(key);
-                }
+            let key = entry.resolve_api_key(id);
+            if !key.is_empty() {
+                tracing::debug!(provider = id, "populated api_key from keyring / environment variable");
+                entry.api_key = Some(key);
             }
         }
     }
     
-    serde_json::to_value(&cfg).map_err(|e| e.to_string())
+    let mut value = serde_json::to_value(&cfg).map_err(|e| e.to_string())?;
+    // Action types the approval toggles are offered for (not a config field).
+    value["approval_actions"] = serde_json::json!(crate::executor::safety::APPROVAL_ACTIONS);
+    Ok(value)
 }
 
 /// Save settings from the UI back to config.toml.
@@ -139,8 +317,34 @@ pub async fn save_config_ui(
     registry_state: State<'_, Arc<Mutex<ProviderRegistry>>>,
     payload: serde_json::Value,
 ) -> Result<(), String> {
-    let new_cfg: AppConfig = serde_json::from_value(payload).map_err(|e| e.to_string())?;
-    
+    // The settings UI only sends the sections and fields it edits; keep the
+    // rest (perception, exclusion zones, approval mode, …) from the current
+    // file. Merging stops at the field level so maps such as
+    // `llm.providers` are still replaced wholesale (deletions stick).
+    let mut merged = load_config()
+        .ok()
+        .and_then(|c| serde_json::to_value(c).ok())
+        .unwrap_or_else(|| serde_json::json!({}));
+    if let (Some(base), Some(update)) = (merged.as_object_mut(), payload.as_object()) {
+        for (section, fields) in update {
+            match (base.get_mut(section).and_then(|b| b.as_object_mut()), fields.as_object()) {
+                (Some(base_section), Some(fields)) => {
+                    for (k, v) in fields {
+                        base_section.insert(k.clone(), v.clone());
+                    }
+                }
+                _ => {
+                    base.insert(section.clone(), fields.clone());
+                }
+            }
+        }
+    }
+    let new_cfg: AppConfig = serde_json::from_value(merged).map_err(|e| format!("Invalid settings: {e}"))?;
+    // Refuse to write a config the agent can't run with; the message lists
+    // every key to fix.
+    new_cfg.validate().map_err(|e| e.to_string())?;
+    crate::secrets::configure(new_cfg.llm.keyring);
+
     // Save the new config directly
     save_config(&new_cfg).map_err(|e| {
         tracing::error!(error = %e, "Failed to save config");
@@ -149,17 +353,277 @@ pub async fn save_config_ui(
     
     tracing::info!("Configuration saved successfully");
 
-    // Rebuild in-memory registry so changes take effect immediately
-    let new_registry = ProviderRegistry::from_config(&new_cfg);
-    *registry_state.lock().await = new_registry;
+    apply_config(&app, &registry_state, &new_cfg).await;
+    Ok(())
+}
 
-    // Notify the frontend so MobX store can sync
-    if let Err(e) = app.emit(
-        "config_updated",
-        serde_json::to_value(&new_cfg).unwrap_or_default(),
-    ) {
+/// Make a freshly saved config live: rebuild the in-memory registry and
+/// runtime settings from it (with its active profile applied) and emit
+/// `config_updated` with the file's contents so the MobX store can sync.
+pub(crate) async fn apply_config(app: &AppHandle, registry_state: &Arc<Mutex<ProviderRegistry>>, cfg: &AppConfig) {
+    let effective = profiles::effective(cfg).unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "active_profile not applied; using the base config");
+        cfg.clone()
+    });
+    crate::perception::exclusion::set_zones(effective.perception.exclusion_zones.clone());
+    crate::perception::screenshot::set_encoding(&effective.perception);
+    crate::executor::pacing::set_pacing(&effective.executor);
+    crate::prompts::configure(&effective.prompts);
+    crate::agent_engine::lang::set_preference(effective.preferences.language);
+    crate::background::apply(app, &effective.background);
+    *registry_state.lock().await = ProviderRegistry::from_config(&effective);
+    profiles::set_active(&cfg.active_profile);
+    crate::config_watch::mark_applied();
+
+    if let Err(e) = app.emit("config_updated", serde_json::to_value(cfg).unwrap_or_default()) {
         tracing::warn!("Failed to emit config_updated event: {e}");
     }
+}
+
+/// Config profiles defined under `[profiles]` and the active one.
+#[tauri::command]
+pub async fn list_profiles() -> Result<ProfileList, String> {
+    let cfg = load_config().map_err(|e| e.to_string())?;
+    Ok(profiles::list(&cfg))
+}
+
+/// Switch every following task to profile `name` (`""` = no profile). The
+/// choice is saved as `active_profile`; the running task keeps its settings.
+#[tauri::command]
+pub async fn set_active_profile(
+    app: AppHandle,
+    registry_state: State<'_, Arc<Mutex<ProviderRegistry>>>,
+    name: String,
+) -> Result<(), String> {
+    let mut cfg = load_config().map_err(|e| e.to_string())?;
+    cfg.active_profile = name.trim().to_string();
+    profiles::effective(&cfg).map_err(|e| e.to_string())?;
+    save_config(&cfg).map_err(|e| e.to_string())?;
+    tracing::info!(profile = %cfg.active_profile, "active profile changed");
+    apply_config(&app, &registry_state, &cfg).await;
+    Ok(())
+}
+
+/// Send a tiny probe request to one provider and list its models, so the
+/// settings page can check a key / model / endpoint before a task uses it.
+/// `role` picks that role's model; `"vision"` also sends a test image.
+#[tauri::command]
+pub async fn test_provider(
+    registry_state: State<'_, Arc<Mutex<ProviderRegistry>>>,
+    provider_id: String,
+    role: Option<String>,
+) -> Result<ProviderTestReport, String> {
+    let cfg = load_config().and_then(|c| profiles::effective(&c)).map_err(|e| e.to_string())?;
+    let entry = cfg
+        .llm
+        .providers
+        .get(&provider_id)
+        .cloned()
+        .ok_or_else(|| format!("unknown provider '{provider_id}' — save it in settings first"))?;
+    // Release the registry before the (possibly slow) request.
+    let (provider, call_cfg) = registry_state
+        .lock()
+        .await
+        .call_config_for_provider(&provider_id, role.as_deref())
+        .map_err(|e| e.to_string())?;
+    Ok(probe::test_provider(provider, call_cfg, &provider_id, &entry, role.as_deref()).await)
+}
 
+/// Current screen exclusion zones (desktop coordinates).
+#[tauri::command]
+pub async fn get_exclusion_zones() -> Result<Vec<ExclusionZone>, String> {
+    Ok(crate::perception::exclusion::zones())
+}
+
+/// Replace the exclusion zones (e.g. drawn in the frontend) and persist them
+/// to config.toml. Takes effect on the next capture.
+#[tauri::command]
+pub async fn set_exclusion_zones(app: AppHandle, zones: Vec<ExclusionZone>) -> Result<(), String> {
+    let mut cfg = load_config().map_err(|e| e.to_string())?;
+    cfg.perception.exclusion_zones = zones.clone();
+    save_config(&cfg).map_err(|e| e.to_string())?;
+    crate::perception::exclusion::set_zones(zones);
+
+    if let Err(e) = app.emit("config_updated", serde_json::to_value(&cfg).unwrap_or_default()) {
+        tracing::warn!("Failed to emit config_updated event: {e}");
+    }
     Ok(())
 }
+
+/// Models installed on a local Ollama server, for the settings model picker.
+/// `api_base` defaults to `http://localhost:11434`.
+#[tauri::command]
+pub async fn list_local_models(
+    api_base: Option<String>,
+) -> Result<Vec<crate::llm::providers::ollama::LocalModel>, String> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(5))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let base = api_base.unwrap_or_default();
+    crate::llm::providers::ollama::list_local_models(&client, &base)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Every distinct host contacted in this session (LLM endpoints, embeddings,
+/// MCP servers, reachability probes), so users can verify where data went.
+#[tauri::command]
+pub async fn get_network_activity() -> Result<Vec<crate::net_audit::HostActivity>, String> {
+    Ok(crate::net_audit::snapshot())
+}
+
+/// Token usage and estimated cost for the current task, this session and
+/// all time, broken down by provider/model.
+#[tauri::command]
+pub async fn get_usage_stats() -> Result<crate::llm::usage::UsageStats, String> {
+    Ok(crate::llm::usage::snapshot())
+}
+
+/// Re-run input calibration for the current display (moves the cursor) and
+/// return the stored mapping.
+#[tauri::command]
+pub async fn calibrate_input() -> Result<crate::executor::calibration::InputCalibration, String> {
+    let shot = crate::perception::screenshot::capture_primary().await.map_err(|e| e.to_string())?;
+    crate::executor::calibration::calibrate(&shot.meta).await.map_err(|e| e.to_string())
+}
+
+/// Check screen capture, detection, input, window creation and provider
+/// reachability in one go.
+#[tauri::command]
+pub async fn run_self_test(
+    app: AppHandle,
+    registry: State<'_, Arc<Mutex<ProviderRegistry>>>,
+) -> Result<crate::self_test::SelfTestReport, String> {
+    let cfg = load_config().unwrap_or_default();
+    Ok(crate::self_test::run(&app, &cfg, registry.inner()).await)
+}
+
+/// Benchmark the perception chain with the saved `[perception]` settings over
+/// `samples` screenshots (default 10).
+#[tauri::command]
+pub async fn benchmark_perception(
+    samples: Option<usize>,
+) -> Result<crate::perception::benchmark::PerceptionBenchmark, String> {
+    let cfg = load_config().unwrap_or_default();
+    crate::perception::benchmark::run(&cfg.perception, samples.unwrap_or(crate::perception::benchmark::DEFAULT_SAMPLES))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Sessions that have an LLM trace (`[llm] trace = true`), newest first.
+#[tauri::command]
+pub async fn list_llm_traces() -> Result<Vec<crate::llm::trace::TraceSummary>, String> {
+    tokio::task::spawn_blocking(crate::llm::trace::list_traces)
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Every recorded call of one session's LLM trace.
+#[tauri::command]
+pub async fn get_llm_trace(session_id: String) -> Result<Vec<serde_json::Value>, String> {
+    tokio::task::spawn_blocking(move || crate::llm::trace::read_trace(&session_id))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Step timings recorded in one session (`agent_metrics`), with totals per kind.
+#[tauri::command]
+pub async fn get_session_metrics(session_id: String) -> Result<crate::agent_engine::metrics::SessionMetrics, String> {
+    tokio::task::spawn_blocking(move || crate::agent_engine::metrics::read(&session_id))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Start recording the user's mouse/keyboard input as a new skill.
+#[tauri::command]
+pub async fn start_skill_recording() -> Result<(), String> {
+    crate::skills::recorder::start().await.map_err(|e| e.to_string())
+}
+
+/// Stop recording and save the captured steps as a `.skill.json` file under
+/// `prompts/skills/recorded/`. The skill is loaded on the next app start.
+#[tauri::command]
+pub async fn stop_skill_recording(
+    name: String,
+    description: String,
+    triggers: Option<String>,
+) -> Result<crate::skills::recorder::RecordedSkill, String> {
+    let grid_n = load_config().unwrap_or_default().perception.grid_n.clamp(4, 26);
+    crate::skills::recorder::stop(
+        std::path::Path::new(crate::skills::SKILLS_DIR),
+        &name,
+        &description,
+        triggers.as_deref().unwrap_or(""),
+        grid_n,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Export the named skills as a zip skill pack at `dest`. The pack is signed
+/// when `[skills] signing_key` or `SEECLAW_SKILL_SIGNING_KEY` is set.
+#[tauri::command]
+pub async fn export_skills(
+    names: Vec<String>,
+    dest: String,
+    pack_name: String,
+    description: Option<String>,
+) -> Result<crate::skills::pack::ExportReport, String> {
+    let cfg = load_config().unwrap_or_default().skills;
+    tokio::task::spawn_blocking(move || {
+        crate::skills::pack::export_skills(
+            std::path::Path::new(crate::skills::SKILLS_DIR),
+            &names,
+            &pack_name,
+            description.as_deref().unwrap_or(""),
+            std::path::Path::new(&dest),
+            &cfg,
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
+}
+
+/// Verify and unpack a skill pack into `prompts/skills/imported/<pack>/`.
+/// The skills are loaded on the next app start.
+#[tauri::command]
+pub async fn import_skill_pack(path: String) -> Result<crate::skills::pack::ImportReport, String> {
+    let cfg = load_config().map_err(|e| e.to_string())?;
+    tokio::task::spawn_blocking(move || {
+        crate::skills::pack::import_skill_pack(
+            std::path::Path::new(crate::skills::SKILLS_DIR),
+            std::path::Path::new(&path),
+            &cfg.skills,
+            &cfg.safety,
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
+}
+
+/// Skills that would be enabled under the current config, and the ones
+/// refused because their permissions exceed `[safety]` (with the reason).
+#[tauri::command]
+pub async fn list_skills() -> Result<serde_json::Value, String> {
+    let safety = load_config().map_err(|e| e.to_string())?.safety;
+    let registry = crate::skills::load_skill_registry(crate::skills::SKILLS_DIR, &safety).await;
+    let mut enabled: Vec<serde_json::Value> = registry
+        .all_skills()
+        .map(|s| serde_json::json!({
+            "name": s.name,
+            "description": s.description,
+            "permissions": s.permissions,
+        }))
+        .collect();
+    enabled.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+    Ok(serde_json::json!({
+        "enabled": enabled,
+        "rejected": registry.rejected_skills(),
+    }))
+}
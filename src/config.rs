@@ -1,12 +1,20 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
+use crate::agent_engine::lang::LanguagePreference;
+use crate::agent_engine::state::LoopMode;
 use crate::errors::{SeeClawError, SeeClawResult};
+use crate::executor::input::ClearStrategy;
+use crate::executor::shell::Shell;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AppConfig {
+    /// Profile from `[profiles]` merged over this file (see `profiles`).
+    /// Empty: none.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub active_profile: String,
     pub llm: LlmConfig,
     #[serde(default)]
     pub safety: SafetyConfig,
@@ -16,8 +24,435 @@ pub struct AppConfig {
     pub mcp: McpConfig,
     #[serde(default)]
     pub perception: PerceptionConfig,
+    #[serde(default)]
+    pub reproducibility: ReproducibilityConfig,
+    #[serde(default)]
+    pub rag: RagConfig,
+    #[serde(default)]
+    pub skills: SkillsConfig,
+    #[serde(default)]
+    pub preferences: PreferencesConfig,
+    #[serde(default)]
+    pub background: BackgroundConfig,
+    #[serde(default)]
+    pub executor: ExecutorConfig,
+    #[serde(default)]
+    pub stability: StabilityWaitConfig,
+    #[serde(default)]
+    pub history: HistoryConfig,
+    #[serde(default)]
+    pub remote: RemoteConfig,
+    #[serde(default)]
+    pub hotkeys: HotkeysConfig,
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
+    /// Named partial configs, e.g. `[profiles.offline.llm]`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, toml::Table>,
+}
+
+/// Span export for long unattended runs. Only used in builds with the `otel`
+/// feature.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetryConfig {
+    /// OTLP/gRPC collector, e.g. `http://localhost:4317` for a local Jaeger
+    /// or Tempo. Empty falls back to `OTEL_EXPORTER_OTLP_ENDPOINT`; when
+    /// both are unset nothing is exported.
+    #[serde(default)]
+    pub otlp_endpoint: String,
+    /// `service.name` the spans are reported under.
+    #[serde(default = "default_service_name")]
+    pub service_name: String,
+}
+
+fn default_service_name() -> String {
+    "seeclaw".into()
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self { otlp_endpoint: String::new(), service_name: default_service_name() }
+    }
+}
+
+impl TelemetryConfig {
+    /// Endpoint from config.toml, falling back to `OTEL_EXPORTER_OTLP_ENDPOINT`.
+    pub fn resolve_endpoint(&self) -> Option<String> {
+        Some(self.otlp_endpoint.trim().to_string())
+            .filter(|e| !e.is_empty())
+            .or_else(|| std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT").ok().filter(|e| !e.trim().is_empty()))
+    }
+}
+
+/// System-wide shortcuts, in the form `"CmdOrCtrl+Alt+Space"`; an empty
+/// string disables one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotkeysConfig {
+    /// Abort the current task at once, even while the agent holds the mouse.
+    #[serde(default = "default_stop_hotkey")]
+    pub stop: String,
+    /// Open the quick-goal prompt.
+    #[serde(default = "default_quick_goal_hotkey")]
+    pub quick_goal: String,
+}
+
+fn default_stop_hotkey() -> String {
+    "CmdOrCtrl+Alt+Escape".into()
+}
+
+fn default_quick_goal_hotkey() -> String {
+    "CmdOrCtrl+Alt+Space".into()
+}
+
+impl Default for HotkeysConfig {
+    fn default() -> Self {
+        Self { stop: default_stop_hotkey(), quick_goal: default_quick_goal_hotkey() }
+    }
+}
+
+/// Local HTTP/WebSocket API for driving the agent from other programs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Listen address. Keep it on 127.0.0.1 unless other machines must connect.
+    #[serde(default = "default_remote_bind")]
+    pub bind: String,
+    /// Bearer token every request must carry; falls back to
+    /// `SEECLAW_REMOTE_TOKEN`. The server does not start without one.
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+fn default_remote_bind() -> String {
+    "127.0.0.1:7788".into()
+}
+
+impl Default for RemoteConfig {
+    fn default() -> Self {
+        Self { enabled: false, bind: default_remote_bind(), token: None }
+    }
+}
+
+impl RemoteConfig {
+    /// Token from config.toml, falling back to `SEECLAW_REMOTE_TOKEN`.
+    pub fn resolve_token(&self) -> String {
+        self.token
+            .as_deref()
+            .filter(|t| !t.is_empty())
+            .map(|t| t.to_string())
+            .unwrap_or_else(|| std::env::var("SEECLAW_REMOTE_TOKEN").unwrap_or_default())
+    }
+}
+
+/// Saved task sessions (history, thumbnails, traces, checkpoints).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryConfig {
+    /// Delete sessions older than this many days on startup. 0 keeps them.
+    #[serde(default = "default_retention_days")]
+    pub retention_days: u32,
+    /// Keep at most this many sessions, newest first. 0 means no limit.
+    #[serde(default = "default_max_sessions")]
+    pub max_sessions: usize,
+    /// Save a small screenshot of the screen before each UI action.
+    #[serde(default = "default_true")]
+    pub thumbnails: bool,
+}
+
+fn default_retention_days() -> u32 {
+    30
+}
+
+fn default_max_sessions() -> usize {
+    200
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            retention_days: default_retention_days(),
+            max_sessions: default_max_sessions(),
+            thumbnails: true,
+        }
+    }
+}
+
+/// Waiting for the screen to settle after UI actions and between plan steps.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StabilityWaitConfig {
+    /// Longest wait after an action whose kind has no entry in `max_wait_by_action`.
+    #[serde(default = "default_stability_max_wait_ms")]
+    pub max_wait_ms: u64,
+    /// Per-action-kind limits ("mouse_click", "type_text", "scroll", ...).
+    #[serde(default = "default_stability_max_wait_by_action")]
+    pub max_wait_by_action: HashMap<String, u64>,
+    /// Longest wait before starting the next plan step.
+    #[serde(default = "default_stability_between_steps_ms")]
+    pub between_steps_ms: u64,
+    #[serde(default = "default_stability_check_interval_ms")]
+    pub check_interval_ms: u64,
+    /// Identical consecutive frames needed to call the screen settled.
+    #[serde(default = "default_stability_min_stable_frames")]
+    pub min_stable_frames: usize,
+}
+
+fn default_stability_max_wait_ms() -> u64 {
+    3000
+}
+
+fn default_stability_max_wait_by_action() -> HashMap<String, u64> {
+    HashMap::from([
+        ("type_text".to_string(), 1500),
+        ("key_press".to_string(), 2000),
+        ("scroll".to_string(), 1500),
+    ])
+}
+
+fn default_stability_between_steps_ms() -> u64 {
+    1000
+}
+
+fn default_stability_check_interval_ms() -> u64 {
+    200
+}
+
+fn default_stability_min_stable_frames() -> usize {
+    2
+}
+
+impl StabilityWaitConfig {
+    /// Wait limit after an action of `kind` (see `action_kind_tag`).
+    pub fn max_wait_for(&self, kind: &str) -> u64 {
+        self.max_wait_by_action.get(kind).copied().unwrap_or(self.max_wait_ms)
+    }
+}
+
+impl Default for StabilityWaitConfig {
+    fn default() -> Self {
+        Self {
+            max_wait_ms: default_stability_max_wait_ms(),
+            max_wait_by_action: default_stability_max_wait_by_action(),
+            between_steps_ms: default_stability_between_steps_ms(),
+            check_interval_ms: default_stability_check_interval_ms(),
+            min_stable_frames: default_stability_min_stable_frames(),
+        }
+    }
+}
+
+/// Physical input execution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutorConfig {
+    /// Run consecutive type_text / hotkey / key_press steps (queued tool
+    /// calls, skill combo steps) as one batch on a single input connection.
+    #[serde(default = "default_true")]
+    pub batch_keyboard_actions: bool,
+    /// Pause between steps of a keyboard batch, so the target app keeps up.
+    #[serde(default = "default_keystroke_gap_ms")]
+    pub keystroke_gap_ms: u64,
+    /// After clicks, typing and scrolling, diff a screenshot taken once the
+    /// screen settles against one taken just before the action, and add a
+    /// "screen changed" note to the tool result.
+    #[serde(default = "default_true")]
+    pub verify_screen_change: bool,
+    /// How type_text with `clear_first` empties the field: "select_all"
+    /// (Ctrl+A, Delete) or "line" (End, Shift+Home, Delete).
+    #[serde(default)]
+    pub clear_strategy: ClearStrategy,
+    /// Shell for execute_terminal: "auto" (PowerShell on Windows, the login
+    /// shell elsewhere), "powershell", "cmd", "bash", "zsh" or "sh".
+    #[serde(default)]
+    pub shell: Shell,
+    /// Default limit for one terminal command; the process is killed after it.
+    #[serde(default = "default_terminal_timeout_secs")]
+    pub terminal_timeout_secs: u64,
+    /// Pause the task when the user moves the mouse or presses Escape while
+    /// an action runs (needs the `recorder` feature).
+    #[serde(default = "default_true")]
+    pub takeover_detection: bool,
+    /// Cursor travel, in pixels, that counts as the user taking over.
+    #[serde(default = "default_takeover_move_px")]
+    pub takeover_move_px: u32,
+    /// Least time between the starts of two mouse / keyboard actions, in ms.
+    #[serde(default)]
+    pub min_action_gap_ms: u64,
+    /// Random extra delay of up to this many ms before each action.
+    #[serde(default)]
+    pub action_jitter_ms: u64,
+    /// Type text at about this many characters per second; 0 types it at
+    /// once. Text pasted through the clipboard (CJK) is not slowed down.
+    #[serde(default)]
+    pub typing_chars_per_sec: u32,
+    /// Plan steps of one parallel group (terminal, file and MCP work only)
+    /// run at the same time, at most this many at once; 1 runs them in order.
+    #[serde(default = "default_max_parallel_steps")]
+    pub max_parallel_steps: usize,
+}
+
+fn default_keystroke_gap_ms() -> u64 {
+    40
+}
+
+fn default_terminal_timeout_secs() -> u64 {
+    120
+}
+
+fn default_takeover_move_px() -> u32 {
+    40
+}
+
+fn default_max_parallel_steps() -> usize {
+    4
+}
+
+impl Default for ExecutorConfig {
+    fn default() -> Self {
+        Self {
+            batch_keyboard_actions: true,
+            keystroke_gap_ms: default_keystroke_gap_ms(),
+            verify_screen_change: true,
+            clear_strategy: ClearStrategy::default(),
+            shell: Shell::default(),
+            terminal_timeout_secs: default_terminal_timeout_secs(),
+            takeover_detection: true,
+            takeover_move_px: default_takeover_move_px(),
+            min_action_gap_ms: 0,
+            action_jitter_ms: 0,
+            typing_chars_per_sec: 0,
+            max_parallel_steps: default_max_parallel_steps(),
+        }
+    }
+}
+
+/// Running the agent without its window.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct BackgroundConfig {
+    /// Keep the agent running in the tray after the main window is closed.
+    #[serde(default)]
+    pub keep_running: bool,
+    /// Start with the OS login session, in the tray with no window.
+    /// Implies `keep_running`.
+    #[serde(default)]
+    pub autostart: bool,
+}
+
+impl BackgroundConfig {
+    pub fn keeps_running(&self) -> bool {
+        self.keep_running || self.autostart
+    }
+}
+
+/// Standing user rules for every task.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PreferencesConfig {
+    /// Free-text rules given to the planner as hard constraints,
+    /// e.g. "never send emails without showing me first".
+    #[serde(default)]
+    pub constraints: Vec<String>,
+    /// Folders files may be saved to. When non-empty, typed paths and
+    /// terminal output redirects outside them are blocked.
+    #[serde(default)]
+    pub save_paths: Vec<String>,
+    /// Language of progress messages, approvals and summaries: `auto`
+    /// (follow each goal), `en` or `zh`.
+    #[serde(default)]
+    pub language: LanguagePreference,
+}
+
+/// Skill pack sharing (`export_skills` / `import_skill_pack`).
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SkillsConfig {
+    /// Base64 ed25519 public keys whose signed packs are trusted.
+    #[serde(default)]
+    pub trusted_keys: Vec<String>,
+    /// Refuse packs that are unsigned or signed by a key not in `trusted_keys`.
+    #[serde(default)]
+    pub require_signed_packs: bool,
+    /// Base64 ed25519 secret key (32-byte seed) used to sign exported packs.
+    /// Falls back to `SEECLAW_SKILL_SIGNING_KEY`; unsigned when neither is set.
+    #[serde(default)]
+    pub signing_key: Option<String>,
+}
+
+impl SkillsConfig {
+    /// Configured signing key, or the `SEECLAW_SKILL_SIGNING_KEY` env var.
+    pub fn resolved_signing_key(&self) -> Option<String> {
+        self.signing_key
+            .clone()
+            .filter(|k| !k.is_empty())
+            .or_else(|| std::env::var("SEECLAW_SKILL_SIGNING_KEY").ok())
+            .filter(|k| !k.trim().is_empty())
+    }
+}
+
+/// Retrieval / experience memory settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RagConfig {
+    /// Local ONNX sentence-embedding model. When missing, the API is used.
+    #[serde(default = "default_embedding_model_path")]
+    pub embedding_model_path: String,
+    /// HuggingFace `tokenizer.json` matching the local model.
+    #[serde(default = "default_tokenizer_path")]
+    pub tokenizer_path: String,
+    #[serde(default = "default_max_seq_len")]
+    pub max_seq_len: usize,
+    #[serde(default = "default_embed_batch_size")]
+    pub batch_size: usize,
+    /// Provider id for API embeddings. Empty = active provider.
+    #[serde(default)]
+    pub embedding_provider: String,
+    /// Model name sent to `/embeddings`.
+    #[serde(default = "default_embedding_model")]
+    pub embedding_model: String,
+}
+
+impl Default for RagConfig {
+    fn default() -> Self {
+        Self {
+            embedding_model_path: default_embedding_model_path(),
+            tokenizer_path: default_tokenizer_path(),
+            max_seq_len: default_max_seq_len(),
+            batch_size: default_embed_batch_size(),
+            embedding_provider: String::new(),
+            embedding_model: default_embedding_model(),
+        }
+    }
 }
 
+fn default_embedding_model_path() -> String { "models/embedding.onnx".to_string() }
+fn default_tokenizer_path() -> String { "models/tokenizer.json".to_string() }
+fn default_max_seq_len() -> usize { 256 }
+fn default_embed_batch_size() -> usize { 16 }
+fn default_embedding_model() -> String { "text-embedding-3-small".to_string() }
+
+/// Reproducibility mode — deterministic LLM sampling plus a decision log
+/// that is sufficient to replay a task step by step when debugging model
+/// regressions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReproducibilityConfig {
+    /// Pin every role's temperature to 0 and record all decisions.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Seed sent to providers that support it (OpenAI-compatible `seed`).
+    #[serde(default = "default_repro_seed")]
+    pub seed: u64,
+    /// Repro log to replay: LLM calls are answered from the log, in order,
+    /// instead of going to the network. Set by `seeclaw-cli replay`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replay: Option<String>,
+    /// 1-based task within the replayed log.
+    #[serde(default = "default_replay_task")]
+    pub replay_task: usize,
+}
+
+impl Default for ReproducibilityConfig {
+    fn default() -> Self {
+        Self { enabled: false, seed: default_repro_seed(), replay: None, replay_task: default_replay_task() }
+    }
+}
+
+fn default_repro_seed() -> u64 { 42 }
+fn default_replay_task() -> usize { 1 }
+
 /// Visual perception / screenshot settings.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PerceptionConfig {
@@ -43,7 +478,12 @@ pub struct PerceptionConfig {
     #[serde(default = "default_true")]
     pub use_yolo: bool,
 
-    /// Enable Windows UI Automation accessibility tree collection.
+    /// ONNX execution providers to try for YOLO, in order ("cuda",
+    /// "directml", "coreml"); CPU is used when none loads. Empty = CPU.
+    #[serde(default)]
+    pub execution_providers: Vec<String>,
+
+    /// Enable accessibility tree collection (Windows UI Automation, macOS AX).
     #[serde(default = "default_true")]
     pub enable_ui_automation: bool,
 
@@ -51,9 +491,55 @@ pub struct PerceptionConfig {
     #[serde(default)]
     pub enable_focus_crop: bool,
 
+    /// After each VLM click, ask the vision model whether the target
+    /// responded and fall back to the next candidate when it did not.
+    #[serde(default)]
+    pub verify_clicks: bool,
+
     /// Custom YOLO class names. If empty, uses default UI class list.
     #[serde(default)]
     pub class_names: Vec<String>,
+
+    /// Ordered vision parser chain ("yolo", "uia", "som_grid").
+    /// Detectors are merged in order; "som_grid" is used only when nothing
+    /// was detected. Empty means the default order.
+    #[serde(default = "crate::perception::pipeline::default_parser_order")]
+    pub parsers: Vec<String>,
+
+    /// Private screen regions: blacked out in captures, excluded from
+    /// detection and never clicked.
+    #[serde(default)]
+    pub exclusion_zones: Vec<ExclusionZone>,
+
+    /// Longest side, in pixels, of screenshots sent to models; larger
+    /// captures are downscaled before detection and annotation. 0 = full size.
+    #[serde(default = "default_max_image_dimension")]
+    pub max_image_dimension: u32,
+
+    /// JPEG quality (1–100) of screenshots sent to models; 0 = lossless PNG.
+    #[serde(default = "default_jpeg_quality")]
+    pub jpeg_quality: u8,
+}
+
+/// A screen rectangle in desktop coordinates — physical pixels on Windows and
+/// Linux, points on macOS — with the primary monitor's top-left at 0,0.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExclusionZone {
+    #[serde(default)]
+    pub label: String,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ExclusionZone {
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x
+            && y >= self.y
+            && (x as i64) < self.x as i64 + self.width as i64
+            && (y as i64) < self.y as i64 + self.height as i64
+    }
 }
 
 impl Default for PerceptionConfig {
@@ -64,9 +550,15 @@ impl Default for PerceptionConfig {
             confidence_threshold: default_conf_threshold(),
             iou_threshold: default_iou_threshold(),
             use_yolo: true,
+            execution_providers: Vec::new(),
             enable_ui_automation: true,
             enable_focus_crop: false,
+            verify_clicks: false,
             class_names: Vec::new(),
+            parsers: crate::perception::pipeline::default_parser_order(),
+            exclusion_zones: Vec::new(),
+            max_image_dimension: default_max_image_dimension(),
+            jpeg_quality: default_jpeg_quality(),
         }
     }
 }
@@ -75,6 +567,8 @@ fn default_grid_n() -> u32 { 12 }
 fn default_yolo_model_path() -> String { "models/gpa_gui_detector.onnx".to_string() }
 fn default_conf_threshold() -> f32 { 0.05 }
 fn default_iou_threshold() -> f32 { 0.5 }
+fn default_max_image_dimension() -> u32 { 1920 }
+fn default_jpeg_quality() -> u8 { 80 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct LlmConfig {
@@ -83,6 +577,42 @@ pub struct LlmConfig {
     /// Role-to-model mapping. If a role is absent, falls back to active_provider defaults.
     #[serde(default)]
     pub roles: RolesConfig,
+    /// Seconds a provider that failed (429 / 5xx / timeout) is skipped by
+    /// failover chains.
+    #[serde(default = "default_circuit_cooldown_secs")]
+    pub circuit_cooldown_secs: u64,
+    /// Planner conversations larger than this (estimated tokens) have their
+    /// older turns summarised by the `routing` model. 0 disables compaction.
+    #[serde(default = "default_compact_after_tokens")]
+    pub compact_after_tokens: usize,
+    /// Record every request body, raw response and parsed result to
+    /// `sessions/<id>/llm_trace.jsonl`.
+    #[serde(default)]
+    pub trace: bool,
+    /// Keep provider API keys in the OS credential store instead of this
+    /// file (see `secrets`). Existing keys are moved on the next start.
+    #[serde(default)]
+    pub keyring: bool,
+    /// Prices by model id, for the cost estimates in usage stats.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub pricing: HashMap<String, ModelPrice>,
+}
+
+/// USD per million tokens.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ModelPrice {
+    #[serde(default)]
+    pub input: f64,
+    #[serde(default)]
+    pub output: f64,
+}
+
+fn default_circuit_cooldown_secs() -> u64 {
+    crate::llm::failover::DEFAULT_CIRCUIT_COOLDOWN_SECS
+}
+
+fn default_compact_after_tokens() -> usize {
+    16_000
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -93,11 +623,82 @@ pub struct ProviderEntry {
     pub model: String,
     #[serde(default = "default_temperature")]
     pub temperature: f64,
-    /// "anthropic" for Claude, None for OpenAI-compatible
+    /// "gemini" for the native Google Gemini API, "ollama" for a local Ollama
+    /// server, None for OpenAI-compatible
     pub adapter: Option<String>,
     /// Optional API key stored in config.toml (falls back to env var SEECLAW_<ID>_API_KEY).
     #[serde(default)]
     pub api_key: Option<String>,
+    /// How many times an SSE stream that drops mid-response is resumed before
+    /// the call fails. 0 disables resumption.
+    #[serde(default = "default_max_stream_resumes")]
+    pub max_stream_resumes: u32,
+    /// Provider accepts a trailing assistant message with `"prefix": true`
+    /// and continues it (e.g. DeepSeek). When false, interrupted streams are
+    /// retried from scratch instead.
+    #[serde(default)]
+    pub prefix_continuation: bool,
+    /// The API accepts `response_format: {"type": "json_schema"}` (OpenAI,
+    /// DeepSeek, vLLM, …). When false, schema-constrained calls fall back to
+    /// JSON mode.
+    #[serde(default)]
+    pub structured_output: bool,
+    /// Retry policy for rate limits and transient failures (OpenAI-compatible
+    /// providers).
+    #[serde(default)]
+    pub retry: RetryConfig,
+}
+
+/// Exponential backoff with jitter for failed LLM requests.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryConfig {
+    /// Total attempts including the first request. 1 disables retries.
+    #[serde(default = "default_retry_max_attempts")]
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles on every further attempt.
+    #[serde(default = "default_retry_base_delay_ms")]
+    pub base_delay_ms: u64,
+    /// Upper bound for a single delay, including a server's `Retry-After`.
+    #[serde(default = "default_retry_max_delay_ms")]
+    pub max_delay_ms: u64,
+    /// HTTP statuses that are retried. Connection errors are always retried.
+    #[serde(default = "default_retry_on")]
+    pub retry_on: Vec<u16>,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_retry_max_attempts(),
+            base_delay_ms: default_retry_base_delay_ms(),
+            max_delay_ms: default_retry_max_delay_ms(),
+            retry_on: default_retry_on(),
+        }
+    }
+}
+
+fn default_retry_max_attempts() -> u32 { 3 }
+fn default_retry_base_delay_ms() -> u64 { 1000 }
+fn default_retry_max_delay_ms() -> u64 { 30_000 }
+fn default_retry_on() -> Vec<u16> { vec![408, 429, 500, 502, 503, 504] }
+
+impl ProviderEntry {
+    /// API key from config.toml, then the OS credential store (with
+    /// `[llm] keyring`), then `SEECLAW_<ID>_API_KEY`.
+    pub fn resolve_api_key(&self, id: &str) -> String {
+        self.api_key
+            .as_deref()
+            .filter(|k| !k.is_empty())
+            .map(|k| k.to_string())
+            .or_else(|| crate::secrets::api_key(id))
+            .unwrap_or_else(|| {
+                std::env::var(format!("SEECLAW_{}_API_KEY", id.to_uppercase())).unwrap_or_default()
+            })
+    }
+}
+
+fn default_max_stream_resumes() -> u32 {
+    2
 }
 
 /// Maps agent roles to specific provider+model combinations.
@@ -124,6 +725,56 @@ pub struct RoleEntry {
     pub stream: bool,
     /// Overrides the provider-level temperature for this role.
     pub temperature: Option<f64>,
+    /// Providers tried in order when `provider` is rate-limited, failing or
+    /// unreachable. Each item is a provider id (uses its default model) or
+    /// `"<provider>:<model>"`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback: Vec<String>,
+    /// Context window of `model` in tokens. Older conversation turns are
+    /// trimmed to fit. Default: 32000.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_tokens: Option<usize>,
+    /// Give up on a call after this many seconds (retries and streaming
+    /// included). Unset: no limit beyond the HTTP client's own.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Extra sampling / request parameters for this role.
+    #[serde(flatten)]
+    pub params: RequestParams,
+}
+
+/// Optional request parameters of a role. Unset fields are not sent, so the
+/// provider's defaults apply.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RequestParams {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f64>,
+    /// OpenAI-style `reasoning_effort` (`"low"`, `"medium"`, `"high"`) for
+    /// reasoning models. Only sent to OpenAI-compatible providers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<String>,
+    /// Merged into the top level of the request body last, for
+    /// provider-specific switches, e.g. GLM's
+    /// `extra_body = { thinking = { type = "disabled" } }`.
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub extra_body: serde_json::Map<String, serde_json::Value>,
+}
+
+impl RequestParams {
+    /// Copy `extra_body` into `body`, replacing keys it already has.
+    pub fn apply_extra_body(&self, body: &mut serde_json::Value) {
+        if let Some(obj) = body.as_object_mut() {
+            for (k, v) in &self.extra_body {
+                obj.insert(k.clone(), v.clone());
+            }
+        }
+    }
 }
 
 fn default_temperature() -> f64 {
@@ -136,12 +787,81 @@ pub struct SafetyConfig {
     pub allow_terminal_commands: bool,
     #[serde(default)]
     pub allow_file_operations: bool,
+    /// Allow skills that declare the `network` permission (web requests,
+    /// remote MCP tools).
+    #[serde(default)]
+    pub allow_network: bool,
     #[serde(default)]
     pub require_approval_for: Vec<String>,
     #[serde(default = "default_max_failures")]
     pub max_consecutive_failures: u32,
     #[serde(default)]
     pub max_loop_duration_minutes: u32,
+    /// Which of the two limits above ends a task: `until_done` (neither),
+    /// `timed` (`max_loop_duration_minutes`) or `failure_limit`
+    /// (`max_consecutive_failures`).
+    #[serde(default)]
+    pub loop_mode: LoopMode,
+    /// Plan → execute → verify cycles before the verifier gives up and
+    /// summarises.
+    #[serde(default = "default_max_cycles")]
+    pub max_cycles: u32,
+    /// Actions executed per task before it is cut short (0 = unlimited).
+    #[serde(default)]
+    pub max_total_steps: u32,
+    /// Vision-model calls per task before it is cut short (0 = unlimited).
+    #[serde(default)]
+    pub max_vlm_calls: u32,
+    /// How GUI actions are gated on user approval.
+    #[serde(default)]
+    pub approval_mode: ApprovalMode,
+    /// In `confidence` mode, clicks whose grounding confidence
+    /// (element confidence × VLM certainty) is below this ask for approval.
+    #[serde(default = "default_auto_approve_confidence")]
+    pub auto_approve_confidence: f32,
+    /// Regex patterns (case-insensitive) for terminal commands that run
+    /// without approval. Every part of a pipeline must match.
+    #[serde(default = "default_terminal_allow")]
+    pub terminal_allow: Vec<String>,
+    /// Regex patterns for terminal commands that are always refused.
+    #[serde(default = "default_terminal_deny")]
+    pub terminal_deny: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalMode {
+    /// Fixed rules: GUI actions run, `require_approval_for` actions ask.
+    #[default]
+    Rules,
+    /// Like `rules`, but low-confidence clicks also ask ("ask me only when unsure").
+    Confidence,
+}
+
+fn default_auto_approve_confidence() -> f32 {
+    0.5
+}
+
+fn default_terminal_allow() -> Vec<String> {
+    vec![
+        r"^(ls|dir|pwd|cd|echo|cat|type|head|tail|wc|whoami|hostname|date|where|which|findstr|grep)\b".into(),
+        r"^(Get-ChildItem|Get-Content|Get-Location|Get-Date|Get-Process|Get-Service|Get-Item|Test-Path|Select-String|Write-Output)\b".into(),
+        r"^git\s+(status|log|diff|show)\b".into(),
+        // Listing only: `git branch <name>`, `-d/-D`, `-m/-M` change refs.
+        r"^git\s+branch(\s+(-a|-r|-v|-vv|--all|--remotes|--list|--verbose|--show-current))*\s*$".into(),
+    ]
+}
+
+fn default_terminal_deny() -> Vec<String> {
+    vec![
+        r"\brm\s+-[a-z]*(rf|fr)[a-z]*\s+(/|~|\*)(\s|$)".into(),
+        r"\b(format|Format-Volume)\s+[a-z]:".into(),
+        r"\b(mkfs|diskpart|fdisk|bcdedit|vssadmin)\b".into(),
+        r"\breg(\.exe)?\s+(add|delete|import)\b".into(),
+        r"\b(New|Set|Remove)-ItemProperty\b.*\bHK(LM|CU|EY_)".into(),
+        r"\b(shutdown|Restart-Computer|Stop-Computer)\b".into(),
+        r"\bRemove-Item\b.*-Recurse\b.*\s[a-z]:\\?\s*$".into(),
+    ]
 }
 
 impl Default for SafetyConfig {
@@ -149,9 +869,18 @@ impl Default for SafetyConfig {
         Self {
             allow_terminal_commands: false,
             allow_file_operations: false,
+            allow_network: false,
             require_approval_for: vec!["execute_terminal".into(), "mcp_call".into()],
             max_consecutive_failures: default_max_failures(),
             max_loop_duration_minutes: 0,
+            loop_mode: LoopMode::default(),
+            max_cycles: default_max_cycles(),
+            max_total_steps: 0,
+            max_vlm_calls: 0,
+            approval_mode: ApprovalMode::default(),
+            auto_approve_confidence: default_auto_approve_confidence(),
+            terminal_allow: default_terminal_allow(),
+            terminal_deny: default_terminal_deny(),
         }
     }
 }
@@ -160,14 +889,26 @@ fn default_max_failures() -> u32 {
     5
 }
 
+fn default_max_cycles() -> u32 {
+    2
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct PromptsConfig {
+    /// Replaces the built-in tool definitions (`builtin.json`).
     #[serde(default)]
     pub tools_file: String,
+    /// Template of the original single-prompt agent; the graph engine uses
+    /// the per-node prompts under `dir` instead.
     #[serde(default)]
     pub system_template: String,
+    /// Replaces the experience memory summary prompt.
     #[serde(default)]
     pub experience_summary_template: String,
+    /// Folder of prompt overrides named like the built-ins (`planner.md`,
+    /// `chat_agent.md`, …). Files are re-read when they change.
+    #[serde(default)]
+    pub dir: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -179,13 +920,31 @@ pub struct McpConfig {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct McpServerEntry {
     pub name: String,
+    /// Executable for stdio servers. Ignored when `url` is set.
+    #[serde(default)]
     pub command: String,
     #[serde(default)]
     pub args: Vec<String>,
+    /// SSE endpoint of a remote server (e.g. `https://host/sse`).
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Bearer token for `url`. Falls back to `SEECLAW_MCP_<NAME>_TOKEN`.
+    #[serde(default)]
+    pub bearer_token: Option<String>,
     #[serde(default = "default_true")]
     pub enabled: bool,
 }
 
+impl McpServerEntry {
+    /// Configured bearer token, or the `SEECLAW_MCP_<NAME>_TOKEN` env var.
+    pub fn resolved_bearer_token(&self) -> Option<String> {
+        self.bearer_token
+            .clone()
+            .filter(|t| !t.is_empty())
+            .or_else(|| std::env::var(format!("SEECLAW_MCP_{}_TOKEN", self.name.to_uppercase())).ok())
+    }
+}
+
 fn default_true() -> bool {
     true
 }
@@ -212,6 +971,11 @@ fn find_config_path() -> SeeClawResult<PathBuf> {
     ))
 }
 
+/// The config.toml in use, or where it will be created.
+pub(crate) fn config_file_path() -> SeeClawResult<PathBuf> {
+    find_config_path().or_else(|_| write_config_path())
+}
+
 /// Returns the canonical path where config should be **written**.
 /// Prefers the exe-adjacent path (works for production bundles).
 /// Falls back to cwd (works for `cargo tauri dev`).
@@ -229,9 +993,113 @@ fn write_config_path() -> SeeClawResult<PathBuf> {
     Ok(path)
 }
 
+impl AppConfig {
+    /// Check cross-field consistency the TOML schema can't express. Every
+    /// problem is listed, each naming the key to fix.
+    pub fn validate(&self) -> SeeClawResult<()> {
+        let mut problems = Vec::new();
+        let providers = &self.llm.providers;
+        let known = || {
+            let mut ids: Vec<&str> = providers.keys().map(String::as_str).collect();
+            ids.sort();
+            ids.join(", ")
+        };
+
+        if !providers.contains_key(&self.llm.active_provider) {
+            problems.push(format!(
+                "llm.active_provider = \"{}\" is not defined under [llm.providers] (known: {})",
+                self.llm.active_provider,
+                known()
+            ));
+        }
+        for (id, entry) in providers {
+            let base = entry.api_base.trim();
+            if base.is_empty() {
+                problems.push(format!("llm.providers.{id}.api_base is empty"));
+            } else if !base.starts_with("http://") && !base.starts_with("https://") {
+                problems.push(format!("llm.providers.{id}.api_base must start with http:// or https:// (got \"{base}\")"));
+            }
+            if let Some(adapter) = entry.adapter.as_deref().filter(|a| !a.is_empty()) {
+                if !matches!(adapter, "openai" | "openai_compatible" | "gemini" | "ollama") {
+                    problems.push(format!(
+                        "llm.providers.{id}.adapter = \"{adapter}\" is unknown (use gemini, ollama, or leave it unset for OpenAI-compatible)"
+                    ));
+                }
+            }
+        }
+
+        let roles = &self.llm.roles;
+        for (name, role) in [
+            ("routing", &roles.routing),
+            ("chat", &roles.chat),
+            ("tools", &roles.tools),
+            ("vision", &roles.vision),
+        ] {
+            let Some(role) = role else { continue };
+            if !providers.contains_key(&role.provider) {
+                problems.push(format!(
+                    "llm.roles.{name}.provider = \"{}\" is not defined under [llm.providers] (known: {})",
+                    role.provider,
+                    known()
+                ));
+            }
+            if role.model.trim().is_empty() {
+                problems.push(format!("llm.roles.{name}.model is empty"));
+            }
+            for fallback in &role.fallback {
+                let id = fallback.split_once(':').map_or(fallback.as_str(), |(id, _)| id);
+                if !providers.contains_key(id) {
+                    problems.push(format!("llm.roles.{name}.fallback: \"{fallback}\" names unknown provider \"{id}\""));
+                }
+            }
+        }
+
+        let safety = &self.safety;
+        match safety.loop_mode {
+            LoopMode::Timed if safety.max_loop_duration_minutes == 0 => problems
+                .push("safety.loop_mode = \"timed\" needs safety.max_loop_duration_minutes > 0".to_string()),
+            LoopMode::FailureLimit if safety.max_consecutive_failures == 0 => problems.push(
+                "safety.loop_mode = \"failure_limit\" needs safety.max_consecutive_failures > 0".to_string(),
+            ),
+            _ => {}
+        }
+        if !(0.0..=1.0).contains(&safety.auto_approve_confidence) {
+            problems.push(format!(
+                "safety.auto_approve_confidence must be between 0 and 1 (got {})",
+                safety.auto_approve_confidence
+            ));
+        }
+
+        if self.remote.enabled {
+            if self.remote.resolve_token().is_empty() {
+                problems.push("remote.enabled is true but remote.token (or SEECLAW_REMOTE_TOKEN) is not set".to_string());
+            }
+            if self.remote.bind.parse::<std::net::SocketAddr>().is_err() {
+                problems.push(format!("remote.bind = \"{}\" is not an address like 127.0.0.1:7788", self.remote.bind));
+            }
+        }
+
+        if !self.active_profile.is_empty() {
+            if let Err(e) = crate::profiles::effective(self) {
+                problems.push(format!("active_profile = \"{}\": {e}", self.active_profile));
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(SeeClawError::Config(problems.join("\n")))
+        }
+    }
+}
+
 pub fn load_config() -> SeeClawResult<AppConfig> {
-    let path = find_config_path()?;
-    let content = std::fs::read_to_string(&path)?;
+    load_config_from(&find_config_path()?)
+}
+
+/// Load the config file at `path` instead of the usual locations.
+pub fn load_config_from(path: &std::path::Path) -> SeeClawResult<AppConfig> {
+    let content = std::fs::read_to_string(path)?;
     let config: AppConfig = toml::from_str(&content)?;
     tracing::info!(path = %path.display(), provider = %config.llm.active_provider, "config loaded");
     Ok(config)
@@ -240,7 +1108,12 @@ pub fn load_config() -> SeeClawResult<AppConfig> {
 pub fn save_config(config: &AppConfig) -> SeeClawResult<()> {
     // Use write_config_path so saving works even on first run (no existing file required).
     let path = write_config_path()?;
-    let content = toml::to_string_pretty(config)?;
+    let content = if config.llm.keyring {
+        toml::to_string_pretty(&crate::secrets::strip_api_keys(config)?)?
+    } else {
+        toml::to_string_pretty(config)?
+    };
+    crate::config_watch::note_saved(&content);
     std::fs::write(&path, content)?;
     tracing::info!(path = %path.display(), "config saved");
     Ok(())
@@ -0,0 +1,130 @@
+//! Live reload of config.toml.
+//!
+//! Watches the folder holding config.toml and, when the file is edited by
+//! hand, re-reads it and applies it the way saving from the settings panel
+//! does: the provider registry and runtime settings are rebuilt and
+//! `config_updated` is emitted. The running task keeps its settings; the agent
+//! loop picks up `[perception]` and the rest before the next one (see
+//! [`revision`]).
+//!
+//! The app's own saves are recognised by content and not applied twice. A
+//! file that no longer parses or validates is reported and ignored, so a
+//! half-finished edit never replaces a working config.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use notify::{RecursiveMode, Watcher};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::Mutex;
+
+use crate::config::{config_file_path, load_config};
+use crate::llm::registry::ProviderRegistry;
+
+/// Quiet period after the last change before reloading; editors often write
+/// a file in several steps.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Bumped whenever a config is applied at runtime.
+static REVISION: AtomicU64 = AtomicU64::new(0);
+/// Hash of the contents the app itself last wrote or applied.
+static LAST_SEEN: AtomicU64 = AtomicU64::new(0);
+
+/// Changes every time a new config is applied; the agent loop compares it
+/// between tasks to rebuild its context.
+pub fn revision() -> u64 {
+    REVISION.load(Ordering::Relaxed)
+}
+
+pub(crate) fn mark_applied() {
+    REVISION.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Remember contents written by `save_config` so the watcher skips them.
+pub(crate) fn note_saved(content: &str) {
+    LAST_SEEN.store(hash(content), Ordering::Relaxed);
+}
+
+fn hash(content: &str) -> u64 {
+    let mut h = DefaultHasher::new();
+    content.hash(&mut h);
+    h.finish()
+}
+
+/// Start watching config.toml. The watcher lives as long as the app.
+pub fn spawn(app: AppHandle, registry: Arc<Mutex<ProviderRegistry>>) {
+    let path = match config_file_path() {
+        Ok(p) => p,
+        Err(e) => {
+            tracing::warn!(error = %e, "config watch: no config path, live reload disabled");
+            return;
+        }
+    };
+    let Some(dir) = path.parent().map(PathBuf::from) else { return };
+    if let Ok(content) = std::fs::read_to_string(&path) {
+        note_saved(&content);
+    }
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<()>();
+    let file_name = path.file_name().map(|n| n.to_os_string());
+    let watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        let Ok(event) = res else { return };
+        if event.kind.is_access() {
+            return;
+        }
+        if event.paths.iter().any(|p| p.file_name().map(|n| n.to_os_string()) == file_name) {
+            let _ = tx.send(());
+        }
+    });
+    let mut watcher = match watcher {
+        Ok(w) => w,
+        Err(e) => {
+            tracing::warn!(error = %e, "config watch: cannot create watcher, live reload disabled");
+            return;
+        }
+    };
+    if let Err(e) = watcher.watch(&dir, RecursiveMode::NonRecursive) {
+        tracing::warn!(dir = %dir.display(), error = %e, "config watch: cannot watch folder, live reload disabled");
+        return;
+    }
+    tracing::info!(path = %path.display(), "config watch: watching for edits");
+
+    tauri::async_runtime::spawn(async move {
+        // Dropping the watcher stops it; keep it with the task.
+        let _watcher = watcher;
+        while rx.recv().await.is_some() {
+            // Debounce: wait until the file has been quiet for a moment.
+            loop {
+                match tokio::time::timeout(DEBOUNCE, rx.recv()).await {
+                    Ok(Some(())) => continue,
+                    Ok(None) => return,
+                    Err(_) => break,
+                }
+            }
+            reload(&app, &registry, &path).await;
+        }
+    });
+}
+
+async fn reload(app: &AppHandle, registry: &Arc<Mutex<ProviderRegistry>>, path: &PathBuf) {
+    let Ok(content) = std::fs::read_to_string(path) else { return };
+    let h = hash(&content);
+    if LAST_SEEN.swap(h, Ordering::Relaxed) == h {
+        return;
+    }
+    let cfg = match load_config().and_then(|c| c.validate().map(|_| c)) {
+        Ok(cfg) => cfg,
+        Err(e) => {
+            tracing::warn!(error = %e, "config watch: edited config.toml not applied");
+            let _ = app.emit("config_reload_failed", serde_json::json!({ "message": e.to_string() }));
+            return;
+        }
+    };
+    tracing::info!(path = %path.display(), "config watch: config.toml changed on disk, reloading");
+    crate::secrets::configure(cfg.llm.keyring);
+    crate::commands::apply_config(app, registry, &cfg).await;
+}
@@ -17,6 +17,15 @@ pub enum SeeClawError {
     #[error("Executor error: {0}")]
     Executor(String),
 
+    #[error("Action denied: {0}")]
+    ExecutorDenied(String),
+
+    #[error("Grounding failed: {0}")]
+    GroundingFailed(String),
+
+    #[error("LLM provider timed out: {0}")]
+    ProviderTimeout(String),
+
     #[error("Safety violation: {0}")]
     SafetyViolation(String),
 
@@ -51,13 +60,79 @@ pub enum SeeClawError {
     Cancelled,
 }
 
+impl SeeClawError {
+    /// Coarse category the frontend uses to pick a recovery action.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Self::GroundingFailed(_) => ErrorCategory::GroundingFailed,
+            Self::ProviderTimeout(_) => ErrorCategory::ProviderTimeout,
+            Self::Http(e) if e.is_timeout() => ErrorCategory::ProviderTimeout,
+            Self::LlmProvider(_) | Self::SseParsing(_) | Self::Http(_) => ErrorCategory::ProviderError,
+            Self::Perception(_) => ErrorCategory::PerceptionFailed,
+            Self::Executor(_) => ErrorCategory::ExecutorFailed,
+            Self::ExecutorDenied(_) | Self::SafetyViolation(_) => ErrorCategory::ExecutorDenied,
+            Self::Cancelled => ErrorCategory::UserStop,
+            Self::Config(_) | Self::TomlDe(_) | Self::TomlSer(_) => ErrorCategory::Config,
+            Self::Mcp(_) => ErrorCategory::ToolFailed,
+            Self::Rag(_) | Self::Skills(_) | Self::Io(_) | Self::Json(_) | Self::Agent(_) => {
+                ErrorCategory::Internal
+            }
+        }
+    }
+}
+
+/// Untyped engine errors (e.g. `ok_or_else(|| format!(..))?` in nodes).
+impl From<String> for SeeClawError {
+    fn from(message: String) -> Self {
+        Self::Agent(message)
+    }
+}
+
+/// Serialized as `{ "category": "...", "message": "..." }` so events and
+/// command results carry a machine-readable category next to the text.
 impl serde::Serialize for SeeClawError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::ser::Serializer,
     {
-        serializer.serialize_str(self.to_string().as_str())
+        use serde::ser::SerializeStruct;
+        let mut s = serializer.serialize_struct("SeeClawError", 2)?;
+        s.serialize_field("category", &self.category())?;
+        s.serialize_field("message", &self.to_string())?;
+        s.end()
     }
 }
 
+// ── ErrorCategory ──────────────────────────────────────────────────────────
+
+/// Error taxonomy shared with the frontend (`agent_state_changed`, `action_result`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    /// The target element / grid cell could not be resolved to a screen point.
+    GroundingFailed,
+    /// An LLM request timed out.
+    ProviderTimeout,
+    /// An LLM request failed for another reason (HTTP status, bad stream).
+    ProviderError,
+    /// Screenshot capture or element detection failed.
+    PerceptionFailed,
+    /// Physical input or a terminal command failed.
+    ExecutorFailed,
+    /// The action was refused (user rejection, exclusion zone, safety rule).
+    ExecutorDenied,
+    /// An MCP tool call failed.
+    ToolFailed,
+    /// The agent itself gave up (`report_failure`).
+    TaskFailed,
+    /// The user stopped the task.
+    UserStop,
+    /// The task hit a loop limit (budget, time, failures) and was summarised early.
+    BudgetExhausted,
+    /// Invalid or unreadable configuration.
+    Config,
+    #[default]
+    Internal,
+}
+
 pub type SeeClawResult<T> = Result<T, SeeClawError>;
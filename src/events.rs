@@ -0,0 +1,89 @@
+//! Event sink — the only way the agent talks to its host.
+//!
+//! Nodes and providers emit named JSON events ("agent_state_changed",
+//! "llm_stream_chunk", …) through an `EventSink`. Inside the desktop app the
+//! sink is the Tauri `AppHandle`; embedders can use `ChannelSink` to receive
+//! the same events on a channel, or implement the trait themselves.
+
+use std::sync::Arc;
+
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+/// Receiver of agent events.
+pub trait EventSink: Send + Sync {
+    fn emit_json(&self, event: &str, payload: serde_json::Value);
+}
+
+/// Shared handle stored in `NodeContext` and passed to providers.
+pub type SharedEventSink = Arc<dyn EventSink>;
+
+impl dyn EventSink {
+    /// Serialize `payload` and forward it to the sink.
+    pub fn emit<S: Serialize>(&self, event: &str, payload: S) -> Result<(), String> {
+        let value = serde_json::to_value(payload).map_err(|e| e.to_string())?;
+        self.emit_json(event, value);
+        Ok(())
+    }
+}
+
+impl<R: tauri::Runtime> EventSink for tauri::AppHandle<R> {
+    fn emit_json(&self, event: &str, payload: serde_json::Value) {
+        if let Err(e) = tauri::Emitter::emit(self, event, payload) {
+            tracing::warn!(event = event, error = %e, "failed to emit event to frontend");
+        }
+    }
+}
+
+/// A single event as delivered by `ChannelSink`.
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentEventMessage {
+    pub event: String,
+    pub payload: serde_json::Value,
+}
+
+/// Broadcasts every event to any number of subscribers.
+pub struct ChannelSink {
+    tx: broadcast::Sender<AgentEventMessage>,
+}
+
+impl ChannelSink {
+    /// Create a sink and its first subscriber. Slow subscribers lose the
+    /// oldest events once `capacity` is exceeded.
+    pub fn new(capacity: usize) -> (Self, broadcast::Receiver<AgentEventMessage>) {
+        let (tx, rx) = broadcast::channel(capacity);
+        (Self { tx }, rx)
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<AgentEventMessage> {
+        self.tx.subscribe()
+    }
+}
+
+impl EventSink for ChannelSink {
+    fn emit_json(&self, event: &str, payload: serde_json::Value) {
+        // No subscribers is not an error — events are simply dropped.
+        let _ = self.tx.send(AgentEventMessage {
+            event: event.to_string(),
+            payload,
+        });
+    }
+}
+
+/// Delivers every event to each of several sinks, in order.
+pub struct TeeSink(pub Vec<SharedEventSink>);
+
+impl EventSink for TeeSink {
+    fn emit_json(&self, event: &str, payload: serde_json::Value) {
+        for sink in &self.0 {
+            sink.emit_json(event, payload.clone());
+        }
+    }
+}
+
+/// Discards every event.
+pub struct NullSink;
+
+impl EventSink for NullSink {
+    fn emit_json(&self, _event: &str, _payload: serde_json::Value) {}
+}
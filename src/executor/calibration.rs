@@ -0,0 +1,208 @@
+//! Input calibration — maps desktop coordinates to the input space `enigo`
+//! uses.
+//!
+//! Grounding already produces desktop coordinates (monitor origin and pixel
+//! density applied, see `ScreenshotMeta::to_desktop`), the space `enigo`
+//! normally works in. Input can still land elsewhere, e.g. when Windows
+//! virtualizes coordinates for a process that did not get per-monitor DPI
+//! awareness. The routine moves the cursor to known normalized positions,
+//! reads back where the OS really put it, and fits
+//! `desktop = scale × input + offset` per axis. The fit is stored in
+//! `calibration/input.json` together with the display it was measured on, so
+//! it runs once per display setup; clicks go through [`to_input`].
+
+use std::path::PathBuf;
+use std::sync::{OnceLock, RwLock};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::executor::input;
+use crate::perception::types::ScreenshotMeta;
+
+/// Normalized cursor positions probed during calibration.
+const PROBES: [(f64, f64); 5] = [(0.25, 0.25), (0.75, 0.25), (0.5, 0.5), (0.25, 0.75), (0.75, 0.75)];
+/// Time for the OS to apply a cursor move before reading it back.
+const SETTLE: Duration = Duration::from_millis(60);
+/// Worst per-probe error (desktop units) accepted for a linear fit.
+const MAX_RESIDUAL_PX: f64 = 3.0;
+/// Fits this close to identity are stored as identity.
+const IDENTITY_TOLERANCE: f64 = 0.01;
+
+/// Per-axis linear map from input space to desktop coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AxisFit {
+    pub scale: f64,
+    pub offset: f64,
+}
+
+impl AxisFit {
+    const IDENTITY: Self = Self { scale: 1.0, offset: 0.0 };
+
+    fn to_input(self, desktop: i32) -> i32 {
+        ((desktop as f64 - self.offset) / self.scale).round() as i32
+    }
+
+    fn is_identity(self) -> bool {
+        (self.scale - 1.0).abs() < IDENTITY_TOLERANCE && self.offset.abs() < 1.0
+    }
+}
+
+/// Stored calibration for one display setup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InputCalibration {
+    pub x: AxisFit,
+    pub y: AxisFit,
+    pub physical_width: u32,
+    pub physical_height: u32,
+    pub scale_factor: f64,
+    /// Largest probe error of the fit, in desktop units.
+    pub residual_px: f64,
+    pub calibrated_at: String,
+}
+
+impl InputCalibration {
+    fn matches(&self, meta: &ScreenshotMeta) -> bool {
+        self.physical_width == meta.physical_width
+            && self.physical_height == meta.physical_height
+            && (self.scale_factor - meta.scale_factor).abs() < 0.01
+    }
+}
+
+fn current() -> &'static RwLock<Option<InputCalibration>> {
+    static CURRENT: OnceLock<RwLock<Option<InputCalibration>>> = OnceLock::new();
+    CURRENT.get_or_init(|| RwLock::new(load()))
+}
+
+fn calibration_path() -> PathBuf {
+    crate::agent_engine::history::data_dir_or_cwd("calibration").join("input.json")
+}
+
+fn load() -> Option<InputCalibration> {
+    let text = std::fs::read_to_string(calibration_path()).ok()?;
+    serde_json::from_str(&text)
+        .map_err(|e| tracing::warn!(error = %e, "calibration: ignoring unreadable input.json"))
+        .ok()
+}
+
+/// Convert a desktop point to the coordinates to send to `enigo`.
+pub fn to_input(x: i32, y: i32) -> (i32, i32) {
+    match current().read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        Some(cal) => (cal.x.to_input(x), cal.y.to_input(y)),
+        None => (x, y),
+    }
+}
+
+/// Calibrate unless a calibration for this display is already stored.
+/// Failures are logged and leave the identity mapping in place.
+pub async fn ensure(meta: &ScreenshotMeta) {
+    let calibrated = current()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .is_some_and(|cal| cal.matches(meta));
+    if calibrated {
+        return;
+    }
+    if let Err(e) = calibrate(meta).await {
+        tracing::warn!(error = %e, "calibration: failed, clicking without correction");
+    }
+}
+
+/// Run the calibration routine now and store the result. The cursor is put
+/// back where it was.
+pub async fn calibrate(meta: &ScreenshotMeta) -> SeeClawResult<InputCalibration> {
+    let (input_w, input_h) = input::input_display_size().await?;
+    if input_w <= 0 || input_h <= 0 {
+        return Err(SeeClawError::Executor(format!("input display size is {input_w}x{input_h}")));
+    }
+    tracing::info!(
+        input_w, input_h,
+        physical_w = meta.physical_width,
+        physical_h = meta.physical_height,
+        scale = meta.scale_factor,
+        "calibration: probing cursor mapping"
+    );
+
+    let home = input::mouse_position().await?;
+    let mut samples = Vec::with_capacity(PROBES.len());
+    let mut probe_error = None;
+    for (nx, ny) in PROBES {
+        let sent = ((nx * input_w as f64).round() as i32, (ny * input_h as f64).round() as i32);
+        if let Err(e) = input::mouse_move(sent.0, sent.1).await {
+            probe_error = Some(e);
+            break;
+        }
+        tokio::time::sleep(SETTLE).await;
+        match input::desktop_cursor_position().await {
+            Ok(seen) => samples.push((sent, seen)),
+            Err(e) => {
+                probe_error = Some(e);
+                break;
+            }
+        }
+    }
+    let _ = input::mouse_move(home.0, home.1).await;
+    if let Some(e) = probe_error {
+        return Err(e);
+    }
+
+    let xs: Vec<(f64, f64)> = samples.iter().map(|(s, p)| (s.0 as f64, p.0 as f64)).collect();
+    let ys: Vec<(f64, f64)> = samples.iter().map(|(s, p)| (s.1 as f64, p.1 as f64)).collect();
+    let (Some(x), Some(y)) = (fit(&xs), fit(&ys)) else {
+        return Err(SeeClawError::Executor("calibration: cursor did not move between probes".into()));
+    };
+    let residual_px = residual(&xs, x).max(residual(&ys, y));
+    if residual_px > MAX_RESIDUAL_PX {
+        return Err(SeeClawError::Executor(format!(
+            "calibration: cursor mapping is not linear (error {residual_px:.1}px); is another program moving the mouse?"
+        )));
+    }
+
+    let cal = InputCalibration {
+        x: if x.is_identity() { AxisFit::IDENTITY } else { x },
+        y: if y.is_identity() { AxisFit::IDENTITY } else { y },
+        physical_width: meta.physical_width,
+        physical_height: meta.physical_height,
+        scale_factor: meta.scale_factor,
+        residual_px,
+        calibrated_at: chrono::Utc::now().to_rfc3339(),
+    };
+    if cal.x.is_identity() && cal.y.is_identity() {
+        tracing::info!("calibration: input and capture spaces agree");
+    } else {
+        tracing::warn!(x = ?cal.x, y = ?cal.y, "calibration: input space differs from capture space, correcting clicks");
+    }
+    match serde_json::to_string_pretty(&cal) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(calibration_path(), json) {
+                tracing::warn!(error = %e, "calibration: failed to save");
+            }
+        }
+        Err(e) => tracing::warn!(error = %e, "calibration: failed to serialize"),
+    }
+    *current().write().unwrap_or_else(|e| e.into_inner()) = Some(cal.clone());
+    Ok(cal)
+}
+
+/// Least-squares line through `(input, desktop)` pairs.
+fn fit(points: &[(f64, f64)]) -> Option<AxisFit> {
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let var: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+    if var == 0.0 {
+        return None;
+    }
+    let cov: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+    let scale = cov / var;
+    if scale <= 0.0 {
+        return None;
+    }
+    Some(AxisFit { scale, offset: mean_y - scale * mean_x })
+}
+
+fn residual(points: &[(f64, f64)], f: AxisFit) -> f64 {
+    points.iter().map(|p| (f.scale * p.0 + f.offset - p.1).abs()).fold(0.0, f64::max)
+}
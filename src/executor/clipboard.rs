@@ -0,0 +1,30 @@
+//! System clipboard access for the `clipboard_read` / `clipboard_write` tools.
+
+use crate::errors::{SeeClawError, SeeClawResult};
+
+/// Text currently on the clipboard. Empty when it holds no text (nothing
+/// copied yet, or an image / file list).
+pub async fn read_text() -> SeeClawResult<String> {
+    tokio::task::spawn_blocking(|| match new_clipboard()?.get_text() {
+        Ok(text) => Ok(text),
+        Err(arboard::Error::ContentNotAvailable) => Ok(String::new()),
+        Err(e) => Err(SeeClawError::Executor(format!("clipboard read: {e}"))),
+    })
+    .await
+    .map_err(|e| SeeClawError::Executor(e.to_string()))?
+}
+
+/// Replace the clipboard contents with `text`.
+pub async fn write_text(text: String) -> SeeClawResult<()> {
+    tokio::task::spawn_blocking(move || {
+        new_clipboard()?
+            .set_text(text)
+            .map_err(|e| SeeClawError::Executor(format!("clipboard write: {e}")))
+    })
+    .await
+    .map_err(|e| SeeClawError::Executor(e.to_string()))?
+}
+
+pub(crate) fn new_clipboard() -> SeeClawResult<arboard::Clipboard> {
+    arboard::Clipboard::new().map_err(|e| SeeClawError::Executor(format!("clipboard: {e}")))
+}
@@ -0,0 +1,157 @@
+//! File tools: `read_file`, `write_file`, `list_dir`, `move_file`.
+//!
+//! Gives the planner direct file access instead of PowerShell one-liners.
+//! Paths must be absolute. Every tool is refused unless
+//! `safety.allow_file_operations` is on: reading and listing can hand any
+//! file (keys, exported secrets) to the remote model just as writing can
+//! damage one.
+
+use std::path::{Path, PathBuf};
+
+use crate::config::SafetyConfig;
+use crate::errors::{SeeClawError, SeeClawResult};
+
+/// `read_file` returns at most this many characters.
+pub const READ_RESULT_CHARS: usize = 8000;
+/// `list_dir` lists at most this many entries.
+const LIST_MAX_ENTRIES: usize = 200;
+
+/// Text of the file at `path`, truncated to [`READ_RESULT_CHARS`].
+pub async fn read_file(path: &str, safety: &SafetyConfig) -> SeeClawResult<String> {
+    ensure_allowed(safety, "read_file")?;
+    let path = absolute(path)?;
+    let bytes = tokio::fs::read(&path)
+        .await
+        .map_err(|e| SeeClawError::Executor(format!("read {}: {e}", path.display())))?;
+    if bytes.contains(&0) {
+        return Err(SeeClawError::Executor(format!(
+            "{} looks like a binary file ({} bytes); only text files can be read",
+            path.display(),
+            bytes.len()
+        )));
+    }
+    let text = String::from_utf8_lossy(&bytes);
+    let total = text.chars().count();
+    let shown: String = text.chars().take(READ_RESULT_CHARS).collect();
+    Ok(if total > READ_RESULT_CHARS {
+        format!("{} ({total} chars, first {READ_RESULT_CHARS} shown):\n{shown}", path.display())
+    } else {
+        format!("{} ({total} chars):\n{shown}", path.display())
+    })
+}
+
+/// Write (or append) `content` to `path`, creating missing parent folders.
+pub async fn write_file(path: &str, content: &str, append: bool, safety: &SafetyConfig) -> SeeClawResult<String> {
+    ensure_allowed(safety, "write_file")?;
+    let path = absolute(path)?;
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| SeeClawError::Executor(format!("create {}: {e}", parent.display())))?;
+    }
+    let result = if append {
+        use tokio::io::AsyncWriteExt;
+        match tokio::fs::OpenOptions::new().create(true).append(true).open(&path).await {
+            Ok(mut file) => file.write_all(content.as_bytes()).await,
+            Err(e) => Err(e),
+        }
+    } else {
+        tokio::fs::write(&path, content).await
+    };
+    result.map_err(|e| SeeClawError::Executor(format!("write {}: {e}", path.display())))?;
+    let verb = if append { "Appended" } else { "Wrote" };
+    Ok(format!("{verb} {} bytes to {}", content.len(), path.display()))
+}
+
+/// One line per entry: folders end with `/`, files show their size.
+pub async fn list_dir(path: &str, safety: &SafetyConfig) -> SeeClawResult<String> {
+    ensure_allowed(safety, "list_dir")?;
+    let path = absolute(path)?;
+    let mut reader = tokio::fs::read_dir(&path)
+        .await
+        .map_err(|e| SeeClawError::Executor(format!("list {}: {e}", path.display())))?;
+    let mut entries = Vec::new();
+    while let Some(entry) = reader
+        .next_entry()
+        .await
+        .map_err(|e| SeeClawError::Executor(format!("list {}: {e}", path.display())))?
+    {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let line = match entry.metadata().await {
+            Ok(meta) if meta.is_dir() => format!("{name}/"),
+            Ok(meta) => format!("{name}  ({} bytes)", meta.len()),
+            Err(_) => name,
+        };
+        entries.push(line);
+    }
+    entries.sort_by_key(|l| (!l.ends_with('/'), l.to_lowercase()));
+    let total = entries.len();
+    if total == 0 {
+        return Ok(format!("{} is empty", path.display()));
+    }
+    let mut out = format!("{} ({total} entries):\n", path.display());
+    out.push_str(&entries[..total.min(LIST_MAX_ENTRIES)].join("\n"));
+    if total > LIST_MAX_ENTRIES {
+        out.push_str(&format!("\n[{} more not shown]", total - LIST_MAX_ENTRIES));
+    }
+    Ok(out)
+}
+
+/// Move or rename `from` to `to`. Never overwrites an existing target.
+pub async fn move_file(from: &str, to: &str, safety: &SafetyConfig) -> SeeClawResult<String> {
+    ensure_allowed(safety, "move_file")?;
+    let (from, to) = (absolute(from)?, absolute(to)?);
+    if tokio::fs::try_exists(&to).await.unwrap_or(false) {
+        return Err(SeeClawError::Executor(format!("{} already exists; choose another name", to.display())));
+    }
+    if let Some(parent) = to.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| SeeClawError::Executor(format!("create {}: {e}", parent.display())))?;
+    }
+    rename_or_copy(&from, &to).await?;
+    Ok(format!("Moved {} to {}", from.display(), to.display()))
+}
+
+/// Rename `from` to `to`. Rename fails across volumes; fall back to
+/// copy + delete for files. Shared with the undo journal.
+pub(crate) async fn rename_or_copy(from: &Path, to: &Path) -> SeeClawResult<()> {
+    if tokio::fs::rename(from, to).await.is_ok() {
+        return Ok(());
+    }
+    tokio::fs::copy(from, to)
+        .await
+        .map_err(|e| SeeClawError::Executor(format!("move {} → {}: {e}", from.display(), to.display())))?;
+    tokio::fs::remove_file(from)
+        .await
+        .map_err(|e| SeeClawError::Executor(format!("remove {}: {e}", from.display())))
+}
+
+fn ensure_allowed(safety: &SafetyConfig, tool: &str) -> SeeClawResult<()> {
+    if safety.allow_file_operations {
+        Ok(())
+    } else {
+        Err(SeeClawError::ExecutorDenied(format!(
+            "{tool} is disabled: file operations are turned off in settings (safety.allow_file_operations)"
+        )))
+    }
+}
+
+/// `path` as an absolute path, expanding a leading `~`. The undo journal
+/// resolves paths with this too, so it snapshots the file that gets written.
+pub(crate) fn absolute(path: &str) -> SeeClawResult<PathBuf> {
+    let path = path.trim().trim_matches('"');
+    let expanded = match path.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with(['/', '\\']) => {
+            let home = std::env::var_os("USERPROFILE")
+                .or_else(|| std::env::var_os("HOME"))
+                .ok_or_else(|| SeeClawError::Executor("cannot expand ~: home folder unknown".into()))?;
+            PathBuf::from(home).join(rest.trim_start_matches(['/', '\\']))
+        }
+        _ => PathBuf::from(path),
+    };
+    if !Path::new(&expanded).is_absolute() {
+        return Err(SeeClawError::Executor(format!("'{path}' is not an absolute path")));
+    }
+    Ok(expanded)
+}
@@ -1,8 +1,15 @@
-use enigo::{Button, Coordinate, Direction, Enigo, Keyboard, Mouse, Settings};
+use std::sync::Arc;
 
+use enigo::{Axis, Button, Coordinate, Direction, Enigo, Keyboard, Mouse, Settings};
+use serde::{Deserialize, Serialize};
+
+use crate::agent_engine::node::StopSignal;
 use crate::errors::{SeeClawError, SeeClawResult};
+use crate::executor::{calibration, clipboard, pacing, watchdog};
 
-/// Single left-click at absolute physical pixel coordinates.
+/// Single left-click at desktop coordinates, as grounding produces them
+/// (`ScreenshotMeta::to_desktop`): the same space on every monitor, whatever
+/// its scale factor.
 pub async fn mouse_click(x: i32, y: i32) -> SeeClawResult<()> {
     tokio::task::spawn_blocking(move || click_sync(x, y, Button::Left, false))
         .await
@@ -23,15 +30,112 @@ pub async fn mouse_right_click(x: i32, y: i32) -> SeeClawResult<()> {
         .map_err(|e| SeeClawError::Executor(e.to_string()))?
 }
 
-/// Type text into the focused control (via clipboard paste to handle CJK).
-pub async fn type_text(text: String, _clear_first: bool) -> SeeClawResult<()> {
+/// Current cursor position in input (`enigo`) coordinates.
+pub async fn mouse_position() -> SeeClawResult<(i32, i32)> {
+    tokio::task::spawn_blocking(|| {
+        new_enigo()?
+            .location()
+            .map_err(|e| SeeClawError::Executor(format!("mouse location: {e}")))
+    })
+    .await
+    .map_err(|e| SeeClawError::Executor(e.to_string()))?
+}
+
+/// Move the cursor without clicking, in input (`enigo`) coordinates.
+pub async fn mouse_move(x: i32, y: i32) -> SeeClawResult<()> {
+    tokio::task::spawn_blocking(move || {
+        pacing::wait_turn();
+        let _injecting = watchdog::injecting();
+        new_enigo()?
+            .move_mouse(x, y, Coordinate::Abs)
+            .map_err(|e| SeeClawError::Executor(format!("move_mouse: {e}")))
+    })
+    .await
+    .map_err(|e| SeeClawError::Executor(e.to_string()))?
+}
+
+/// Size of the main display as `enigo` sees it.
+pub async fn input_display_size() -> SeeClawResult<(i32, i32)> {
+    tokio::task::spawn_blocking(|| {
+        new_enigo()?
+            .main_display()
+            .map_err(|e| SeeClawError::Executor(format!("main_display: {e}")))
+    })
+    .await
+    .map_err(|e| SeeClawError::Executor(e.to_string()))?
+}
+
+/// Where the cursor really is, in desktop coordinates.
+///
+/// Windows reports this directly (`GetPhysicalCursorPos`, immune to DPI
+/// virtualization). On macOS and X11 the OS position is already in desktop
+/// units (points / pixels).
+pub async fn desktop_cursor_position() -> SeeClawResult<(i32, i32)> {
+    #[cfg(all(target_os = "windows", feature = "uia"))]
+    {
+        let mut pt = windows::Win32::Foundation::POINT::default();
+        unsafe { windows::Win32::UI::WindowsAndMessaging::GetPhysicalCursorPos(&mut pt) }
+            .map_err(|e| SeeClawError::Executor(format!("GetPhysicalCursorPos: {e}")))?;
+        Ok((pt.x, pt.y))
+    }
+    #[cfg(not(all(target_os = "windows", feature = "uia")))]
+    {
+        mouse_position().await
+    }
+}
+
+/// Make the process per-monitor DPI aware (Windows), so screen capture, UI
+/// Automation rectangles, the cursor and synthetic input all use physical
+/// pixels on every monitor instead of coordinates virtualized per display
+/// scale. Call once at startup, before any window exists; a no-op elsewhere.
+pub fn init_dpi_awareness() {
+    #[cfg(all(target_os = "windows", feature = "uia"))]
+    {
+        use windows::Win32::UI::HiDpi::{SetProcessDpiAwarenessContext, DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2};
+        // Fails harmlessly when the manifest already set an awareness mode.
+        if let Err(e) = unsafe { SetProcessDpiAwarenessContext(DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2) } {
+            tracing::debug!(error = %e, "SetProcessDpiAwarenessContext: awareness already set");
+        }
+    }
+}
+
+/// Scroll the wheel `direction` ("up" / "down" / "left" / "right") by
+/// `distance` ("short" = one notch, "long" = about a page). With `target`
+/// (desktop coordinates) the cursor moves there first, so the element under it
+/// scrolls instead of whatever had the pointer.
+pub async fn scroll(direction: String, distance: String, target: Option<(i32, i32)>) -> SeeClawResult<()> {
+    tokio::task::spawn_blocking(move || scroll_sync(&direction, &distance, target))
+        .await
+        .map_err(|e| SeeClawError::Executor(e.to_string()))?
+}
+
+/// Paste shortcut used for clipboard-based typing.
+const PASTE_HOTKEY: &str = if cfg!(target_os = "macos") { "meta+v" } else { "ctrl+v" };
+const SELECT_ALL_HOTKEY: &str = if cfg!(target_os = "macos") { "meta+a" } else { "ctrl+a" };
+
+/// How `type_text` with `clear_first` empties the focused field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClearStrategy {
+    /// Select all (Ctrl+A / Cmd+A), then Delete. Clears multi-line fields too.
+    #[default]
+    SelectAll,
+    /// End, Shift+Home, Delete — only the current line, for apps where
+    /// select-all grabs the whole page instead of the field.
+    Line,
+}
+
+/// Type text into the focused control (CJK text goes through a clipboard
+/// paste), after emptying it with `clear` when given.
+pub async fn type_text(text: String, clear: Option<ClearStrategy>) -> SeeClawResult<()> {
     tokio::task::spawn_blocking(move || {
+        pacing::wait_turn();
+        let _injecting = watchdog::injecting();
         let mut enigo = new_enigo()?;
-        // Use key sequence for ASCII, clipboard paste for non-ASCII
-        enigo
-            .text(&text)
-            .map_err(|e| SeeClawError::Executor(format!("type_text: {e}")))?;
-        Ok(())
+        if let Some(strategy) = clear {
+            clear_sync(&mut enigo, strategy)?;
+        }
+        type_sync(&mut enigo, &text)
     })
     .await
     .map_err(|e| SeeClawError::Executor(e.to_string()))?
@@ -40,35 +144,59 @@ pub async fn type_text(text: String, _clear_first: bool) -> SeeClawResult<()> {
 /// Press a key combination like "ctrl+c", "win+d", "alt+f4".
 pub async fn press_hotkey(keys: String) -> SeeClawResult<()> {
     tokio::task::spawn_blocking(move || {
-        let mut enigo = new_enigo()?;
-        let parts: Vec<&str> = keys.split('+').map(|s| s.trim()).collect();
-
-        let modifier_keys: Vec<enigo::Key> = parts[..parts.len().saturating_sub(1)]
-            .iter()
-            .filter_map(|k| parse_modifier_key(k))
-            .collect();
+        pacing::wait_turn();
+        let _injecting = watchdog::injecting();
+        hotkey_sync(&mut new_enigo()?, &keys)
+    })
+        .await
+        .map_err(|e| SeeClawError::Executor(e.to_string()))?
+}
 
-        let main_key = parts.last().and_then(|k| parse_key(k));
+/// One step of a keyboard batch run by [`run_keystrokes`].
+#[derive(Debug, Clone)]
+pub enum KeyStroke {
+    /// Text typed into the focused control, cleared first when a strategy is given.
+    Text(String, Option<ClearStrategy>),
+    /// A key or combination in [`press_hotkey`] syntax.
+    Keys(String),
+}
 
-        // Press modifiers
-        for mk in &modifier_keys {
-            enigo
-                .key(*mk, Direction::Press)
-                .map_err(|e| SeeClawError::Executor(format!("modifier press: {e}")))?;
-        }
-        // Tap main key
-        if let Some(k) = main_key {
-            enigo
-                .key(k, Direction::Click)
-                .map_err(|e| SeeClawError::Executor(format!("key click: {e}")))?;
-        }
-        // Release modifiers in reverse
-        for mk in modifier_keys.iter().rev() {
-            enigo
-                .key(*mk, Direction::Release)
-                .map_err(|e| SeeClawError::Executor(format!("modifier release: {e}")))?;
+/// Run keyboard steps back to back on one `Enigo` connection, `gap_ms` apart,
+/// instead of opening a connection and a blocking task per step.
+///
+/// Stops at the first failure or when `stop_flag` is set (that step reports
+/// `Cancelled`); returns the outcome of every step attempted, in order.
+pub async fn run_keystrokes(
+    strokes: Vec<KeyStroke>,
+    gap_ms: u64,
+    stop_flag: Arc<StopSignal>,
+) -> SeeClawResult<Vec<SeeClawResult<()>>> {
+    tokio::task::spawn_blocking(move || {
+        let _injecting = watchdog::injecting();
+        let mut enigo = new_enigo()?;
+        let mut outcomes = Vec::with_capacity(strokes.len());
+        for (i, stroke) in strokes.iter().enumerate() {
+            if stop_flag.is_set() {
+                outcomes.push(Err(SeeClawError::Cancelled));
+                break;
+            }
+            if i > 0 && gap_ms > 0 {
+                std::thread::sleep(std::time::Duration::from_millis(gap_ms));
+            }
+            pacing::wait_turn();
+            let result = match stroke {
+                KeyStroke::Text(text, clear) => clear
+                    .map_or(Ok(()), |strategy| clear_sync(&mut enigo, strategy))
+                    .and_then(|_| type_sync(&mut enigo, text)),
+                KeyStroke::Keys(keys) => hotkey_sync(&mut enigo, keys),
+            };
+            let failed = result.is_err();
+            outcomes.push(result);
+            if failed {
+                break;
+            }
         }
-        Ok(())
+        Ok(outcomes)
     })
     .await
     .map_err(|e| SeeClawError::Executor(e.to_string()))?
@@ -81,7 +209,115 @@ fn new_enigo() -> SeeClawResult<Enigo> {
         .map_err(|e| SeeClawError::Executor(format!("Enigo::new: {e}")))
 }
 
+fn clear_sync(enigo: &mut Enigo, strategy: ClearStrategy) -> SeeClawResult<()> {
+    let keys: &[&str] = match strategy {
+        ClearStrategy::SelectAll => &[SELECT_ALL_HOTKEY, "delete"],
+        ClearStrategy::Line => &["end", "shift+home", "delete"],
+    };
+    for keys in keys {
+        hotkey_sync(enigo, keys)?;
+        std::thread::sleep(std::time::Duration::from_millis(30));
+    }
+    Ok(())
+}
+
+/// True when `text` contains CJK ideographs, kana, hangul or full-width forms.
+pub fn contains_cjk(text: &str) -> bool {
+    text.chars().any(|c| {
+        matches!(
+            c as u32,
+            0x3000..=0x30FF       // CJK punctuation, hiragana, katakana
+                | 0x3400..=0x4DBF // CJK extension A
+                | 0x4E00..=0x9FFF // CJK unified ideographs
+                | 0xAC00..=0xD7AF // hangul syllables
+                | 0xF900..=0xFAFF // CJK compatibility ideographs
+                | 0xFF00..=0xFFEF // half/full-width forms
+                | 0x20000..=0x2FA1F
+        )
+    })
+}
+
+fn type_sync(enigo: &mut Enigo, text: &str) -> SeeClawResult<()> {
+    if contains_cjk(text) {
+        return paste_sync(enigo, text);
+    }
+    if pacing::char_delay().is_none() {
+        return enigo
+            .text(text)
+            .map_err(|e| SeeClawError::Executor(format!("type_text: {e}")));
+    }
+    let mut buf = [0u8; 4];
+    for (i, c) in text.chars().enumerate() {
+        if let Some(delay) = pacing::char_delay().filter(|_| i > 0) {
+            std::thread::sleep(delay);
+        }
+        enigo
+            .text(c.encode_utf8(&mut buf))
+            .map_err(|e| SeeClawError::Executor(format!("type_text: {e}")))?;
+    }
+    Ok(())
+}
+
+/// Type `text` by pasting it: synthetic key events into an active IME
+/// (Chinese / Japanese / Korean input methods) get composed or dropped.
+/// The user's clipboard text is put back afterwards.
+fn paste_sync(enigo: &mut Enigo, text: &str) -> SeeClawResult<()> {
+    let mut board = clipboard::new_clipboard()?;
+    let saved = board.get_text().ok();
+    board
+        .set_text(text.to_string())
+        .map_err(|e| SeeClawError::Executor(format!("clipboard write: {e}")))?;
+    std::thread::sleep(std::time::Duration::from_millis(50));
+    let pasted = hotkey_sync(enigo, PASTE_HOTKEY);
+    // The target app reads the clipboard asynchronously; restoring too early
+    // would paste the old contents.
+    std::thread::sleep(std::time::Duration::from_millis(150));
+    let restored = match saved {
+        Some(prev) => board.set_text(prev),
+        None => board.clear(),
+    };
+    if let Err(e) = restored {
+        tracing::warn!(error = %e, "could not restore the clipboard after pasting");
+    }
+    pasted
+}
+
+fn hotkey_sync(enigo: &mut Enigo, keys: &str) -> SeeClawResult<()> {
+    let parts: Vec<&str> = keys.split('+').map(|s| s.trim()).collect();
+
+    let modifier_keys: Vec<enigo::Key> = parts[..parts.len().saturating_sub(1)]
+        .iter()
+        .filter_map(|k| parse_modifier_key(k))
+        .collect();
+
+    let main_key = parts.last().and_then(|k| parse_key(k));
+
+    // Press modifiers
+    for mk in &modifier_keys {
+        enigo
+            .key(*mk, Direction::Press)
+            .map_err(|e| SeeClawError::Executor(format!("modifier press: {e}")))?;
+    }
+    // Tap main key
+    if let Some(k) = main_key {
+        enigo
+            .key(k, Direction::Click)
+            .map_err(|e| SeeClawError::Executor(format!("key click: {e}")))?;
+    }
+    // Release modifiers in reverse
+    for mk in modifier_keys.iter().rev() {
+        enigo
+            .key(*mk, Direction::Release)
+            .map_err(|e| SeeClawError::Executor(format!("modifier release: {e}")))?;
+    }
+    Ok(())
+}
+
+/// Click at desktop coordinates, corrected by the stored calibration.
 fn click_sync(x: i32, y: i32, button: Button, double: bool) -> SeeClawResult<()> {
+    let (x, y) = calibration::to_input(x, y);
+    pacing::wait_turn();
+    let _injecting = watchdog::injecting();
     let mut enigo = new_enigo()?;
     enigo
         .move_mouse(x, y, Coordinate::Abs)
@@ -99,6 +335,33 @@ fn click_sync(x: i32, y: i32, button: Button, double: bool) -> SeeClawResult<()>
     Ok(())
 }
 
+fn scroll_sync(direction: &str, distance: &str, target: Option<(i32, i32)>) -> SeeClawResult<()> {
+    let notches = match distance.to_lowercase().as_str() {
+        "long" | "page" => 5,
+        _ => 1,
+    };
+    let (length, axis) = match direction.to_lowercase().as_str() {
+        "up" => (-notches, Axis::Vertical),
+        "down" => (notches, Axis::Vertical),
+        "left" => (-notches, Axis::Horizontal),
+        "right" => (notches, Axis::Horizontal),
+        other => return Err(SeeClawError::Executor(format!("unknown scroll direction: {other}"))),
+    };
+    pacing::wait_turn();
+    let _injecting = watchdog::injecting();
+    let mut enigo = new_enigo()?;
+    if let Some((x, y)) = target {
+        let (x, y) = calibration::to_input(x, y);
+        enigo
+            .move_mouse(x, y, Coordinate::Abs)
+            .map_err(|e| SeeClawError::Executor(format!("move_mouse: {e}")))?;
+        std::thread::sleep(std::time::Duration::from_millis(80));
+    }
+    enigo
+        .scroll(length, axis)
+        .map_err(|e| SeeClawError::Executor(format!("scroll: {e}")))
+}
+
 fn parse_modifier_key(s: &str) -> Option<enigo::Key> {
     match s.to_lowercase().as_str() {
         "ctrl" | "control" => Some(enigo::Key::Control),
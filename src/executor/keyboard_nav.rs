@@ -0,0 +1,109 @@
+//! Keyboard navigation — grounding-free way to reach a control.
+//!
+//! Presses Tab (or an arrow key) and reads the focused element's name from UI
+//! Automation after every press, until the focus lands on the target; then
+//! activates it with Enter. Works in keyboard-friendly apps where visual
+//! grounding keeps missing (tiny targets, overlapping elements, odd DPI).
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::agent_engine::node::StopSignal;
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::executor::input;
+use crate::perception::ui_automation::focused_element;
+
+/// Upper bound for `max_presses` regardless of what the model asks for.
+pub const MAX_PRESSES_LIMIT: u32 = 60;
+/// Settle time after each key press before reading focus.
+const FOCUS_SETTLE_MS: u64 = 80;
+
+/// Where keyboard navigation ended up.
+#[derive(Debug, Clone)]
+pub struct NavOutcome {
+    /// Accessible name of the element that matched.
+    pub focused: String,
+    /// Number of navigation key presses it took.
+    pub presses: u32,
+}
+
+/// Press `key` until the focused element's name matches `target`, then press
+/// Enter when `activate` is set.
+///
+/// Fails with `GroundingFailed` when the target is not reached within
+/// `max_presses` or the focus cycles back to where it started.
+pub async fn navigate_to(
+    target: &str,
+    key: &str,
+    max_presses: u32,
+    activate: bool,
+    stop_flag: Arc<StopSignal>,
+) -> SeeClawResult<NavOutcome> {
+    let target = normalize(target);
+    if target.is_empty() {
+        return Err(SeeClawError::Executor("keyboard navigation needs a target name".into()));
+    }
+    let max_presses = max_presses.clamp(1, MAX_PRESSES_LIMIT);
+
+    let start = focused_element().await?.map(|f| f.name);
+    let mut seen_start = false;
+
+    for presses in 0..=max_presses {
+        if stop_flag.is_set() {
+            return Err(SeeClawError::Cancelled);
+        }
+
+        let current = if presses == 0 {
+            start.clone()
+        } else {
+            input::press_hotkey(key.to_string()).await?;
+            tokio::time::sleep(Duration::from_millis(FOCUS_SETTLE_MS)).await;
+            focused_element().await?.map(|f| f.name)
+        };
+
+        if let Some(name) = &current {
+            if matches_target(&normalize(name), &target) {
+                tracing::info!(target = %target, focused = %name, presses, "keyboard nav: target focused");
+                if activate {
+                    input::press_hotkey("enter".to_string()).await?;
+                }
+                return Ok(NavOutcome { focused: name.clone(), presses });
+            }
+            tracing::debug!(focused = %name, presses, "keyboard nav: not the target");
+        }
+
+        // Back at the starting element: the focus ring has wrapped around.
+        if presses > 1 && current.is_some() && current == start {
+            if seen_start {
+                break;
+            }
+            seen_start = true;
+        }
+    }
+
+    Err(SeeClawError::GroundingFailed(format!(
+        "'{target}' was not reached with {key} navigation"
+    )))
+}
+
+fn normalize(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// Exact match, or the target followed only by decoration (UIA names often
+/// carry an accelerator or ellipsis, e.g. "Save (Ctrl+S)", "Save…").
+/// Containment is not enough: "save" must not hit "Don't Save" or "Save As",
+/// nor "ok" hit "Bookmarks".
+fn matches_target(name: &str, target: &str) -> bool {
+    if name.is_empty() {
+        return false;
+    }
+    match name.strip_prefix(target) {
+        Some("") => true,
+        Some(rest) => {
+            let rest = rest.trim_start();
+            rest.starts_with(['(', '[', ':', '…']) || rest.starts_with("...")
+        }
+        None => false,
+    }
+}
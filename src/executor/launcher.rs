@@ -0,0 +1,228 @@
+//! Application launcher for the `open_app` tool.
+//!
+//! Resolves a program name to an executable, shortcut or app bundle and starts
+//! it directly, instead of driving the OS search box with keystrokes.
+//! Lookup order: an existing path, then `PATH`, then the platform's install
+//! locations (App Paths registry keys and Start Menu shortcuts on Windows,
+//! `/Applications` on macOS, `.desktop` entries on Linux).
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::errors::{SeeClawError, SeeClawResult};
+
+/// A launch target and where it was found.
+#[derive(Debug, Clone)]
+pub struct ResolvedApp {
+    pub path: PathBuf,
+    pub source: &'static str,
+}
+
+/// Resolve `name_or_path` and start it with `args`, without waiting for it.
+pub async fn open_app(name_or_path: String, args: Vec<String>) -> SeeClawResult<String> {
+    tokio::task::spawn_blocking(move || {
+        let app = resolve(&name_or_path).ok_or_else(|| {
+            SeeClawError::Executor(format!("no installed application or executable found for '{name_or_path}'"))
+        })?;
+        launch(&app.path, &args)?;
+        tracing::info!(app = %app.path.display(), source = app.source, "open_app: launched");
+        Ok(format!("Launched {} (found via {})", app.path.display(), app.source))
+    })
+    .await
+    .map_err(|e| SeeClawError::Executor(e.to_string()))?
+}
+
+/// Find what `name_or_path` refers to.
+pub fn resolve(name_or_path: &str) -> Option<ResolvedApp> {
+    let name = name_or_path.trim().trim_matches('"');
+    if name.is_empty() {
+        return None;
+    }
+    let direct = Path::new(name);
+    if direct.is_absolute() && direct.exists() {
+        return Some(ResolvedApp { path: direct.to_path_buf(), source: "path" });
+    }
+    if let Some(path) = find_on_path(name) {
+        return Some(ResolvedApp { path, source: "PATH" });
+    }
+    platform::resolve(name)
+}
+
+fn find_on_path(name: &str) -> Option<PathBuf> {
+    let exts: Vec<String> = if cfg!(windows) {
+        std::env::var("PATHEXT")
+            .unwrap_or_else(|_| ".EXE;.CMD;.BAT;.COM".into())
+            .split(';')
+            .filter(|e| !e.is_empty())
+            .map(|e| e.to_lowercase())
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let has_ext = Path::new(name).extension().is_some();
+    let path_var = std::env::var_os("PATH")?;
+    std::env::split_paths(&path_var).find_map(|dir| {
+        let mut candidates = vec![dir.join(name)];
+        if !has_ext {
+            candidates.extend(exts.iter().map(|ext| dir.join(format!("{name}{ext}"))));
+        }
+        candidates.into_iter().find(|p| p.is_file() && (has_ext || !cfg!(windows) || p.extension().is_some()))
+    })
+}
+
+/// Best shortcut/bundle name match in `entries`: exact stem first, then the
+/// shortest stem containing `name`.
+fn best_match(name: &str, entries: impl Iterator<Item = PathBuf>) -> Option<PathBuf> {
+    let wanted = name.to_lowercase();
+    let mut contains: Option<(usize, PathBuf)> = None;
+    for path in entries {
+        let Some(stem) = path.file_stem().map(|s| s.to_string_lossy().to_lowercase()) else {
+            continue;
+        };
+        if stem == wanted {
+            return Some(path);
+        }
+        if stem.contains(&wanted) && contains.as_ref().is_none_or(|(len, _)| stem.len() < *len) {
+            contains = Some((stem.len(), path));
+        }
+    }
+    contains.map(|(_, p)| p)
+}
+
+/// Files under `dir` (up to `depth` levels) with extension `ext`.
+fn files_with_ext(dir: &Path, ext: &str, depth: u32, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_some_and(|e| e.eq_ignore_ascii_case(ext)) {
+            out.push(path);
+        } else if depth > 0 && path.is_dir() {
+            files_with_ext(&path, ext, depth - 1, out);
+        }
+    }
+}
+
+fn spawn_detached(cmd: &mut Command) -> SeeClawResult<()> {
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| SeeClawError::Executor(format!("launch failed: {e}")))
+}
+
+#[cfg(windows)]
+mod platform {
+    use super::*;
+    use std::os::windows::process::CommandExt;
+
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+    const APP_PATHS: &str = r"SOFTWARE\Microsoft\Windows\CurrentVersion\App Paths";
+
+    pub fn resolve(name: &str) -> Option<ResolvedApp> {
+        if let Some(path) = app_path(name) {
+            return Some(ResolvedApp { path, source: "App Paths registry" });
+        }
+        let mut shortcuts = Vec::new();
+        for var in ["ProgramData", "APPDATA"] {
+            if let Some(base) = std::env::var_os(var) {
+                let root = PathBuf::from(base).join(r"Microsoft\Windows\Start Menu\Programs");
+                files_with_ext(&root, "lnk", 4, &mut shortcuts);
+            }
+        }
+        best_match(name, shortcuts.into_iter()).map(|path| ResolvedApp { path, source: "Start Menu" })
+    }
+
+    /// `App Paths\<name>.exe` default value from HKCU, then HKLM.
+    fn app_path(name: &str) -> Option<PathBuf> {
+        let exe = if name.to_lowercase().ends_with(".exe") { name.to_string() } else { format!("{name}.exe") };
+        ["HKCU", "HKLM"].into_iter().find_map(|hive| {
+            let output = Command::new("reg")
+                .args(["query", &format!(r"{hive}\{APP_PATHS}\{exe}"), "/ve"])
+                .creation_flags(CREATE_NO_WINDOW)
+                .output()
+                .ok()?;
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let value = stdout
+                .lines()
+                .find_map(|l| l.split_once("REG_SZ").or_else(|| l.split_once("REG_EXPAND_SZ")))?
+                .1
+                .trim()
+                .trim_matches('"');
+            let path = PathBuf::from(value);
+            path.exists().then_some(path)
+        })
+    }
+
+    pub fn launch(path: &Path, args: &[String]) -> SeeClawResult<()> {
+        let is_exe = path.extension().is_some_and(|e| e.eq_ignore_ascii_case("exe"));
+        if is_exe {
+            let mut cmd = Command::new(path);
+            cmd.args(args);
+            if let Some(dir) = path.parent() {
+                cmd.current_dir(dir);
+            }
+            spawn_detached(&mut cmd)
+        } else {
+            // Shortcuts, scripts and documents go through the shell's file associations.
+            let mut cmd = Command::new("cmd");
+            cmd.args(["/C", "start", ""]).arg(path).args(args).creation_flags(CREATE_NO_WINDOW);
+            spawn_detached(&mut cmd)
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::*;
+
+    pub fn resolve(name: &str) -> Option<ResolvedApp> {
+        let mut bundles = Vec::new();
+        let home = std::env::var_os("HOME").map(|h| PathBuf::from(h).join("Applications"));
+        for dir in [Some(PathBuf::from("/Applications")), Some(PathBuf::from("/System/Applications")), home]
+            .into_iter()
+            .flatten()
+        {
+            files_with_ext(&dir, "app", 1, &mut bundles);
+        }
+        best_match(name, bundles.into_iter()).map(|path| ResolvedApp { path, source: "Applications" })
+    }
+
+    pub fn launch(path: &Path, args: &[String]) -> SeeClawResult<()> {
+        if path.extension().is_some_and(|e| e == "app") {
+            let mut cmd = Command::new("open");
+            cmd.arg("-a").arg(path);
+            if !args.is_empty() {
+                cmd.arg("--args").args(args);
+            }
+            spawn_detached(&mut cmd)
+        } else {
+            spawn_detached(Command::new(path).args(args))
+        }
+    }
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+mod platform {
+    use super::*;
+
+    pub fn resolve(name: &str) -> Option<ResolvedApp> {
+        let mut entries = Vec::new();
+        let home = std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local/share/applications"));
+        for dir in [Some(PathBuf::from("/usr/share/applications")), home].into_iter().flatten() {
+            files_with_ext(&dir, "desktop", 1, &mut entries);
+        }
+        best_match(name, entries.into_iter()).map(|path| ResolvedApp { path, source: "desktop entries" })
+    }
+
+    pub fn launch(path: &Path, args: &[String]) -> SeeClawResult<()> {
+        if path.extension().is_some_and(|e| e == "desktop") {
+            let id = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+            spawn_detached(Command::new("gtk-launch").arg(id).args(args))
+        } else {
+            spawn_detached(Command::new(path).args(args))
+        }
+    }
+}
+
+use platform::launch;
@@ -1,2 +1,11 @@
-// coordinator, dispatcher, safety, text_input removed — logic now lives in agent_engine nodes
+// coordinator, dispatcher, text_input removed — logic now lives in agent_engine nodes
+pub mod calibration;
+pub mod clipboard;
+pub mod fs;
 pub mod input;
+pub mod keyboard_nav;
+pub mod launcher;
+pub mod pacing;
+pub mod safety;
+pub mod shell;
+pub mod watchdog;
@@ -0,0 +1,75 @@
+//! Human-like input pacing.
+//!
+//! `[executor] min_action_gap_ms` spaces out mouse and keyboard actions,
+//! `action_jitter_ms` adds a random extra delay to each, and
+//! `typing_chars_per_sec` types text one character at a time instead of in
+//! a single burst. All default to 0 (as fast as possible). Input functions
+//! call [`wait_turn`] on their blocking thread before acting.
+
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+
+use crate::config::ExecutorConfig;
+
+#[derive(Debug, Clone, Copy)]
+struct Pacing {
+    min_gap_ms: u64,
+    jitter_ms: u64,
+    typing_cps: u32,
+}
+
+static PACING: RwLock<Pacing> = RwLock::new(Pacing { min_gap_ms: 0, jitter_ms: 0, typing_cps: 0 });
+/// When the last action started.
+static LAST_ACTION: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Apply the `[executor]` pacing settings (on startup and whenever config
+/// changes).
+pub fn set_pacing(cfg: &ExecutorConfig) {
+    if let Ok(mut p) = PACING.write() {
+        *p = Pacing {
+            min_gap_ms: cfg.min_action_gap_ms,
+            jitter_ms: cfg.action_jitter_ms,
+            typing_cps: cfg.typing_chars_per_sec,
+        };
+    }
+}
+
+fn pacing() -> Pacing {
+    PACING.read().map(|p| *p).unwrap_or(Pacing { min_gap_ms: 0, jitter_ms: 0, typing_cps: 0 })
+}
+
+/// Block until the next action may start: at least `min_action_gap_ms`
+/// after the previous one, plus up to `action_jitter_ms` at random.
+pub fn wait_turn() {
+    let p = pacing();
+    let mut last = LAST_ACTION.lock().unwrap_or_else(|e| e.into_inner());
+    let gap = Duration::from_millis(p.min_gap_ms + jitter(p.jitter_ms));
+    if let Some(since) = last.map(|t| t.elapsed()) {
+        if since < gap {
+            std::thread::sleep(gap - since);
+        }
+    }
+    *last = Some(Instant::now());
+}
+
+/// Delay between typed characters, or `None` to type text in one go.
+pub fn char_delay() -> Option<Duration> {
+    let p = pacing();
+    if p.typing_cps == 0 {
+        return None;
+    }
+    let base = 1000.0 / p.typing_cps as f64;
+    // ±30 % per keystroke, so the rhythm is not perfectly regular.
+    let factor = rand::thread_rng().gen_range(0.7..1.3);
+    Some(Duration::from_secs_f64(base * factor / 1000.0))
+}
+
+fn jitter(max_ms: u64) -> u64 {
+    if max_ms == 0 {
+        0
+    } else {
+        rand::thread_rng().gen_range(0..=max_ms)
+    }
+}
@@ -0,0 +1,179 @@
+//! Shell selection and command execution for `execute_terminal`.
+//!
+//! The shell comes from `[executor] shell`; `auto` picks PowerShell on
+//! Windows and the user's login shell (zsh/bash, falling back to sh)
+//! elsewhere. Output lines are handed to a callback as they arrive so the
+//! UI can show progress on long-running commands.
+
+use std::collections::BTreeMap;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+
+use crate::agent_engine::node::{wait_stop, StopSignal};
+use crate::errors::{SeeClawError, SeeClawResult};
+
+/// Output kept per stream; anything beyond is dropped (the tool result is
+/// truncated much further anyway).
+const MAX_CAPTURE_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Shell {
+    #[default]
+    Auto,
+    Powershell,
+    Cmd,
+    Bash,
+    Zsh,
+    Sh,
+}
+
+impl Shell {
+    /// The concrete shell `Auto` stands for on this machine.
+    pub fn resolve(self) -> Self {
+        if self != Self::Auto {
+            return self;
+        }
+        if cfg!(windows) {
+            return Self::Powershell;
+        }
+        let login = std::env::var("SHELL").unwrap_or_default();
+        if login.ends_with("/zsh") {
+            Self::Zsh
+        } else if login.ends_with("/bash") || std::path::Path::new("/bin/bash").exists() {
+            Self::Bash
+        } else {
+            Self::Sh
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Powershell => "powershell",
+            Self::Cmd => "cmd",
+            Self::Bash => "bash",
+            Self::Zsh => "zsh",
+            Self::Sh => "sh",
+        }
+    }
+
+    fn command(self, script: &str) -> Command {
+        let mut cmd = match self.resolve() {
+            Self::Powershell => {
+                let mut c = Command::new("powershell");
+                c.args(["-NoProfile", "-NonInteractive", "-Command", script]);
+                c
+            }
+            Self::Cmd => {
+                let mut c = Command::new("cmd");
+                c.args(["/C", script]);
+                c
+            }
+            other => {
+                let mut c = Command::new(other.name());
+                c.args(["-c", script]);
+                c
+            }
+        };
+        cmd.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped()).kill_on_drop(true);
+        cmd
+    }
+}
+
+/// Where and how to run one command.
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions<'a> {
+    pub cwd: Option<&'a str>,
+    pub env: Option<&'a BTreeMap<String, String>>,
+    pub timeout: Duration,
+}
+
+/// Result of a finished (or timed-out) command.
+#[derive(Debug, Clone)]
+pub struct ShellOutput {
+    pub shell: Shell,
+    /// `None` when the process was killed (timeout) or ended by a signal.
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+    pub timed_out: bool,
+}
+
+impl ShellOutput {
+    pub fn success(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+}
+
+/// Run `script` in `shell`, calling `on_line(stream, line)` for every output
+/// line (`stream` is "stdout" or "stderr"). Returns `Cancelled` when `stop`
+/// is set; a timeout kills the process and returns what it printed so far.
+pub async fn run(
+    shell: Shell,
+    script: &str,
+    opts: RunOptions<'_>,
+    stop: Arc<StopSignal>,
+    on_line: impl Fn(&'static str, &str),
+) -> SeeClawResult<ShellOutput> {
+    let shell = shell.resolve();
+    let mut cmd = shell.command(script);
+    if let Some(dir) = opts.cwd.map(str::trim).filter(|d| !d.is_empty()) {
+        if !std::path::Path::new(dir).is_dir() {
+            return Err(SeeClawError::Executor(format!("working directory does not exist: {dir}")));
+        }
+        cmd.current_dir(dir);
+    }
+    if let Some(env) = opts.env {
+        cmd.envs(env);
+    }
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| SeeClawError::Executor(format!("spawn {} failed: {e}", shell.name())))?;
+    let mut out_lines = BufReader::new(child.stdout.take().expect("stdout piped")).lines();
+    let mut err_lines = BufReader::new(child.stderr.take().expect("stderr piped")).lines();
+
+    let (mut stdout, mut stderr) = (String::new(), String::new());
+    let collect = async {
+        let (mut out_done, mut err_done) = (false, false);
+        while !(out_done && err_done) {
+            tokio::select! {
+                line = out_lines.next_line(), if !out_done => match line {
+                    Ok(Some(l)) => { on_line("stdout", &l); append(&mut stdout, &l); }
+                    _ => out_done = true,
+                },
+                line = err_lines.next_line(), if !err_done => match line {
+                    Ok(Some(l)) => { on_line("stderr", &l); append(&mut stderr, &l); }
+                    _ => err_done = true,
+                },
+            }
+        }
+        child.wait().await
+    };
+    let finished = tokio::select! {
+        r = tokio::time::timeout(opts.timeout, collect) => r,
+        _ = wait_stop(stop) => return Err(SeeClawError::Cancelled),
+    };
+    match finished {
+        Ok(status) => {
+            let status = status.map_err(|e| SeeClawError::Executor(format!("wait failed: {e}")))?;
+            Ok(ShellOutput { shell, exit_code: status.code(), stdout, stderr, timed_out: false })
+        }
+        Err(_) => {
+            let _ = child.start_kill();
+            Ok(ShellOutput { shell, exit_code: None, stdout, stderr, timed_out: true })
+        }
+    }
+}
+
+fn append(buf: &mut String, line: &str) {
+    if buf.len() < MAX_CAPTURE_BYTES {
+        buf.push_str(line);
+        buf.push('\n');
+    }
+}
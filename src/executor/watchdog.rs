@@ -0,0 +1,167 @@
+//! Takeover watchdog — notice the user grabbing the mouse mid-action.
+//!
+//! While an action node runs, [`watch`] listens to the global input hook.
+//! Input the executor injects itself is ignored: every injecting call holds an
+//! [`injecting`] guard, and events up to `INJECT_GRACE_MS` after the last one
+//! still count as the agent's. Anything else — the cursor travelling more
+//! than `takeover_move_px` from where it was, or Escape — pauses the task.
+//! The action in flight finishes and the graph waits before the next node
+//! until the user resumes.
+//!
+//! Needs the `recorder` feature (the global hook); without it [`watch`] is
+//! a no-op.
+
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::agent_engine::lang::GoalLanguage;
+use crate::agent_engine::node::PauseSignal;
+use crate::events::SharedEventSink;
+
+/// Input seen this long after an injection ends is still attributed to it
+/// (the OS delivers synthetic events with some delay).
+const INJECT_GRACE_MS: u64 = 250;
+
+/// Number of injecting calls in progress.
+static IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+/// When the last injecting call ended, in ms since the Unix epoch.
+static LAST_INJECTED_MS: AtomicU64 = AtomicU64::new(0);
+
+struct Watch {
+    pause: Arc<PauseSignal>,
+    sink: SharedEventSink,
+    lang: GoalLanguage,
+    threshold_px: f64,
+    /// Cursor position the user's movement is measured from.
+    anchor: Option<(f64, f64)>,
+}
+
+static ACTIVE: Mutex<Option<Watch>> = Mutex::new(None);
+
+/// Held while the executor sends synthetic input.
+pub struct Injecting(());
+
+impl Drop for Injecting {
+    fn drop(&mut self) {
+        LAST_INJECTED_MS.store(now_ms(), Ordering::SeqCst);
+        IN_FLIGHT.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Mark synthetic input in progress until the guard drops.
+pub fn injecting() -> Injecting {
+    IN_FLIGHT.fetch_add(1, Ordering::SeqCst);
+    Injecting(())
+}
+
+/// Watching stops when this is dropped.
+pub struct WatchGuard(());
+
+impl Drop for WatchGuard {
+    fn drop(&mut self) {
+        *ACTIVE.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
+/// Pause via `pause` when the user takes over, until the guard drops.
+/// `threshold_px` is the cursor travel (in input coordinates) that counts.
+pub fn watch(pause: Arc<PauseSignal>, sink: SharedEventSink, lang: GoalLanguage, threshold_px: u32) -> WatchGuard {
+    if let Err(e) = hook::ensure_listening() {
+        tracing::debug!(error = %e, "takeover watchdog unavailable");
+    }
+    *ACTIVE.lock().unwrap_or_else(|e| e.into_inner()) = Some(Watch {
+        pause,
+        sink,
+        lang,
+        threshold_px: threshold_px.max(1) as f64,
+        anchor: None,
+    });
+    WatchGuard(())
+}
+
+/// What the user did.
+#[derive(Debug, Clone, Copy)]
+enum Takeover {
+    MouseMoved,
+    Escape,
+}
+
+fn agent_input() -> bool {
+    IN_FLIGHT.load(Ordering::SeqCst) > 0
+        || now_ms().saturating_sub(LAST_INJECTED_MS.load(Ordering::SeqCst)) < INJECT_GRACE_MS
+}
+
+/// Feed one cursor position or Escape press from the hook.
+#[cfg_attr(not(feature = "recorder"), allow(dead_code))]
+fn observe(moved_to: Option<(f64, f64)>, escape: bool) {
+    let mut active = ACTIVE.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(watch) = active.as_mut() else { return };
+    if agent_input() {
+        // The agent moved the cursor; measure the user from its new spot.
+        watch.anchor = None;
+        return;
+    }
+    let takeover = match moved_to {
+        Some(pos) => {
+            let anchor = *watch.anchor.get_or_insert(pos);
+            let dist = ((pos.0 - anchor.0).powi(2) + (pos.1 - anchor.1).powi(2)).sqrt();
+            (dist > watch.threshold_px).then_some(Takeover::MouseMoved)
+        }
+        None => escape.then_some(Takeover::Escape),
+    };
+    let Some(takeover) = takeover else { return };
+    if watch.pause.is_paused() {
+        return;
+    }
+    tracing::info!(?takeover, "takeover watchdog: user input during an action, pausing");
+    watch.pause.pause();
+    watch.anchor = None;
+    let reason = match takeover {
+        Takeover::MouseMoved => "mouse_moved",
+        Takeover::Escape => "escape",
+    };
+    let _ = watch.sink.emit("takeover_detected", serde_json::json!({ "reason": reason }));
+    let _ = watch.sink.emit("agent_activity", serde_json::json!({
+        "text": watch.lang.pick(
+            "检测到你在操作鼠标或键盘，任务已暂停；需要继续时点击恢复",
+            "You took over the mouse or keyboard, so the task is paused — resume when the agent should continue",
+        ),
+    }));
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(feature = "recorder")]
+mod hook {
+    use std::sync::OnceLock;
+
+    use crate::errors::SeeClawResult;
+    use crate::input_hook::{self, EventType, Key};
+
+    static STARTED: OnceLock<()> = OnceLock::new();
+
+    pub fn ensure_listening() -> SeeClawResult<()> {
+        if STARTED.set(()).is_err() {
+            return Ok(());
+        }
+        input_hook::subscribe(|event| match event.event_type {
+            EventType::MouseMove { x, y } => super::observe(Some((x, y)), false),
+            EventType::KeyPress(Key::Escape) => super::observe(None, true),
+            _ => {}
+        })
+    }
+}
+
+#[cfg(not(feature = "recorder"))]
+mod hook {
+    use crate::errors::{SeeClawError, SeeClawResult};
+
+    pub fn ensure_listening() -> SeeClawResult<()> {
+        Err(SeeClawError::Executor("takeover detection requires the `recorder` feature".into()))
+    }
+}
@@ -0,0 +1,64 @@
+//! Global hotkeys — reach the agent while it has the mouse and keyboard.
+//!
+//! `[hotkeys] stop` aborts the current task the same way the Stop button
+//! does (the stop flag is set right away, so an action in flight is cut
+//! short); `[hotkeys] quick_goal` opens the tray's quick-goal prompt.
+//! A shortcut that fails to parse or is taken by another program is logged
+//! and skipped.
+
+use std::str::FromStr;
+
+use tauri::AppHandle;
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+use crate::config::HotkeysConfig;
+use crate::tray;
+
+/// Register the configured shortcuts.
+pub fn init(app: &AppHandle, cfg: &HotkeysConfig) {
+    let stop = parse("stop", &cfg.stop);
+    let quick_goal = parse("quick_goal", &cfg.quick_goal);
+    if stop.is_none() && quick_goal.is_none() {
+        return;
+    }
+
+    let plugin = tauri_plugin_global_shortcut::Builder::new()
+        .with_handler(move |app, shortcut, event| {
+            if event.state() != ShortcutState::Pressed {
+                return;
+            }
+            if Some(shortcut) == stop.as_ref() {
+                tracing::info!("hotkeys: stop pressed");
+                tray::stop_task(app);
+            } else if Some(shortcut) == quick_goal.as_ref() {
+                tray::show_quick_goal(app);
+            }
+        })
+        .build();
+    if let Err(e) = app.plugin(plugin) {
+        tracing::warn!(error = %e, "hotkeys: failed to load the global-shortcut plugin");
+        return;
+    }
+
+    for (name, shortcut) in [("stop", stop), ("quick_goal", quick_goal)] {
+        let Some(shortcut) = shortcut else { continue };
+        match app.global_shortcut().register(shortcut) {
+            Ok(()) => tracing::info!(hotkey = name, ?shortcut, "hotkeys: registered"),
+            Err(e) => tracing::warn!(hotkey = name, ?shortcut, error = %e, "hotkeys: registration failed"),
+        }
+    }
+}
+
+fn parse(name: &str, spec: &str) -> Option<Shortcut> {
+    let spec = spec.trim();
+    if spec.is_empty() {
+        return None;
+    }
+    match Shortcut::from_str(spec) {
+        Ok(s) => Some(s),
+        Err(e) => {
+            tracing::warn!(hotkey = name, spec, error = %e, "hotkeys: invalid shortcut");
+            None
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! Process-wide global input hook.
+//!
+//! `rdev::listen` installs one OS hook and never returns, so it can only run
+//! once per process. The skill recorder and the takeover watchdog both
+//! subscribe here and share a single listener thread.
+
+use std::sync::{Mutex, OnceLock};
+
+pub use rdev::{Button, Event, EventType, Key};
+
+use crate::errors::{SeeClawError, SeeClawResult};
+
+type Handler = Box<dyn FnMut(&Event) + Send>;
+
+static HANDLERS: Mutex<Vec<Handler>> = Mutex::new(Vec::new());
+static STARTED: OnceLock<()> = OnceLock::new();
+
+/// Call `handler` with every global mouse and keyboard event from now on,
+/// starting the listener thread on first use. Handlers run on that thread
+/// and must return quickly.
+pub fn subscribe(handler: impl FnMut(&Event) + Send + 'static) -> SeeClawResult<()> {
+    HANDLERS.lock().unwrap_or_else(|e| e.into_inner()).push(Box::new(handler));
+    if STARTED.set(()).is_err() {
+        return Ok(());
+    }
+    std::thread::Builder::new()
+        .name("input-hook".into())
+        .spawn(|| {
+            let result = rdev::listen(|event| {
+                for handler in HANDLERS.lock().unwrap_or_else(|e| e.into_inner()).iter_mut() {
+                    handler(&event);
+                }
+            });
+            if let Err(e) = result {
+                tracing::error!(error = ?e, "input hook failed");
+            }
+        })
+        .map_err(|e| SeeClawError::Executor(format!("failed to start input hook: {e}")))?;
+    Ok(())
+}
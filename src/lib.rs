@@ -1,91 +1,132 @@
+pub mod agent;
 pub mod agent_engine;
+pub mod background;
+pub mod bench;
 pub mod commands;
 pub mod config;
+pub mod config_watch;
 pub mod errors;
+pub mod events;
 pub mod executor;
+pub mod hotkeys;
+#[cfg(feature = "recorder")]
+pub mod input_hook;
 pub mod llm;
 pub mod mcp;
+pub mod net_audit;
 pub mod perception;
+pub mod profiles;
+pub mod prompts;
+#[cfg(feature = "rag")]
 pub mod rag;
+#[cfg(feature = "remote")]
+pub mod remote;
+pub mod scheduler;
+pub mod secrets;
+pub mod self_test;
 pub mod skills;
+pub mod telemetry;
+pub mod tray;
 
-use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio::sync::Mutex;
+use tracing::Instrument;
 
+use crate::agent_engine::checkpoint::{self, Checkpoint};
 use crate::agent_engine::context::NodeContext;
+use crate::agent_engine::continuity::TaskCarryover;
+use crate::agent_engine::event_bus::EventBus;
 use crate::agent_engine::flow::build_default_flow;
+use crate::agent_engine::history::{self, HistoryEntry};
+use crate::agent_engine::lang::GoalLanguage;
 use crate::agent_engine::loop_control::LoopController;
-use crate::agent_engine::state::{AgentEvent, GraphResult, LoopConfig, LoopMode, SharedState};
+use crate::agent_engine::node::{PauseSignal, StopSignal};
+use crate::agent_engine::state::{AgentEvent, GraphResult, LoopConfig, LoopOverrides, SharedState};
+use crate::agent_engine::task_queue;
+use crate::errors::{ErrorCategory, SeeClawResult};
+use crate::events::SharedEventSink;
+use crate::llm::health::any_provider_reachable;
 use crate::llm::registry::ProviderRegistry;
+use crate::mcp::manager::McpManager;
+use crate::perception::pipeline::PerceptionChain;
 use crate::perception::yolo_detector::YoloDetector;
 
+pub use crate::agent::{Agent, AgentBuilder};
+
+/// How often deferred goals re-probe provider reachability.
+const DEFERRED_RETRY_INTERVAL: Duration = Duration::from_secs(15);
+
 /// Handle passed to Tauri commands so they can send events into the agent loop.
 pub struct AgentHandle {
     pub tx: mpsc::Sender<AgentEvent>,
-    pub stop_flag: Arc<AtomicBool>,
+    pub stop_flag: Arc<StopSignal>,
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| {
-                // Default dev filter: 只对 seeclaw_lib 开 debug，其它库降噪
-                tracing_subscriber::EnvFilter::new(
-                    "seeclaw_lib=debug,tauri=info,reqwest=warn,hyper=warn",
-                )
-            }),
-        )
-        .init();
-
     // Load .env file if present (ignore error if not found)
     let _ = dotenvy::dotenv();
 
+    // Before any window or capture, so every coordinate source agrees.
+    executor::input::init_dpi_awareness();
+
+    // Load config once; subsystems take what they need from it.
+    let loaded = config::load_config();
+    let telemetry_cfg = loaded.as_ref().map(|c| c.telemetry.clone()).unwrap_or_default();
+    // Inside the runtime so the OTLP exporter (if any) can spawn its worker.
+    tauri::async_runtime::block_on(async {
+        // Default dev filter: 只对 seeclaw_lib 开 debug，其它库降噪
+        telemetry::init(
+            "seeclaw_lib=debug,tauri=info,reqwest=warn,hyper=warn",
+            std::io::stdout,
+            &telemetry_cfg,
+        );
+    });
+
     // Build the provider registry from config; fall back to an empty registry on error.
-    // Load config once; extract values needed by different subsystems.
-    let (registry, perception_cfg) = match config::load_config() {
+    let (registry, app_config) = match loaded {
         Ok(cfg) => {
-            let pcfg = cfg.perception.clone();
-            (ProviderRegistry::from_config(&cfg), pcfg)
+            secrets::configure(cfg.llm.keyring);
+            secrets::migrate(&cfg);
+            let cfg = profiles::effective(&cfg).unwrap_or_else(|e| {
+                tracing::warn!(error = %e, "active_profile not applied; using the base config");
+                cfg
+            });
+            (ProviderRegistry::from_config(&cfg), cfg)
         }
         Err(e) => {
             tracing::error!(error = %e, "Failed to load config; starting with empty LLM registry");
-            (ProviderRegistry::new(String::new()), config::PerceptionConfig::default())
+            (ProviderRegistry::new(String::new()), config::AppConfig::default())
         }
     };
     let registry_state: Arc<Mutex<ProviderRegistry>> = Arc::new(Mutex::new(registry));
 
     // Create the agent event channel (buffer=32).
     let (agent_tx, agent_rx) = mpsc::channel::<AgentEvent>(32);
-    let stop_flag = Arc::new(AtomicBool::new(false));
+    let stop_flag = Arc::new(StopSignal::new());
+    let scheduler_tx = agent_tx.clone();
     let agent_handle = Arc::new(AgentHandle { tx: agent_tx, stop_flag: stop_flag.clone() });
+    #[cfg(feature = "remote")]
+    let (remote_handle, remote_cfg) = (agent_handle.clone(), app_config.remote.clone());
 
-    let loop_config = LoopConfig {
-        mode: LoopMode::UntilDone,
-        max_duration_minutes: None,
-        max_failures: Some(5),
-    };
+    let loop_config = LoopConfig::from(&app_config.safety);
 
     // Try loading the YOLO detector model (non-critical: falls back to SoM grid)
-    let yolo_detector = if perception_cfg.use_yolo {
-        let class_names = if perception_cfg.class_names.is_empty() {
-            crate::perception::yolo_detector::default_ui_class_names()
-        } else {
-            perception_cfg.class_names.clone()
-        };
-        YoloDetector::try_new(
-            &perception_cfg.yolo_model_path,
-            perception_cfg.confidence_threshold,
-            perception_cfg.iou_threshold,
-            class_names,
-        )
-    } else {
-        None
-    };
+    perception::exclusion::set_zones(app_config.perception.exclusion_zones.clone());
+    perception::screenshot::set_encoding(&app_config.perception);
+    executor::pacing::set_pacing(&app_config.executor);
+    prompts::configure(&app_config.prompts);
+    agent_engine::lang::set_preference(app_config.preferences.language);
+    let yolo_detector = YoloDetector::from_config(&app_config.perception);
+    let perception_chain = PerceptionChain::from_config(&app_config.perception, yolo_detector);
 
     tauri::Builder::default()
+        .plugin(tauri_plugin_autostart::init(
+            tauri_plugin_autostart::MacosLauncher::LaunchAgent,
+            Some(vec![background::BACKGROUND_ARG]),
+        ))
         .manage(registry_state.clone())
         .manage(agent_handle)
         .invoke_handler(tauri::generate_handler![
@@ -94,79 +135,248 @@ pub fn run() {
             commands::get_config_file_path,
             commands::start_task,
             commands::stop_task,
+            commands::pause_task,
+            commands::resume_task,
             commands::confirm_action,
             commands::start_chat,
             commands::get_config,
             commands::save_config_ui,
+            commands::test_provider,
+            commands::list_profiles,
+            commands::set_active_profile,
+            commands::get_exclusion_zones,
+            commands::set_exclusion_zones,
+            commands::list_local_models,
+            commands::get_network_activity,
+            commands::get_pending_approval,
+            commands::list_queue,
+            commands::cancel_queued,
+            commands::reorder_queue,
+            commands::list_schedules,
+            commands::add_schedule,
+            commands::remove_schedule,
+            commands::undo_last_task,
+            commands::resume_session,
+            commands::list_sessions,
+            commands::get_session,
+            commands::delete_session,
+            commands::export_session,
+            commands::replay_session,
+            commands::stop_replay,
+            commands::get_usage_stats,
+            commands::list_llm_traces,
+            commands::get_llm_trace,
+            commands::get_session_metrics,
+            commands::benchmark_perception,
+            commands::run_self_test,
+            commands::calibrate_input,
+            commands::start_skill_recording,
+            commands::stop_skill_recording,
+            commands::export_skills,
+            commands::import_skill_pack,
+            commands::list_skills,
         ])
+        .on_window_event(background::on_window_event)
         .setup(move |app| {
-            let app_handle = app.handle().clone();
+            if let Err(e) = tray::init(app.handle()) {
+                tracing::warn!(error = %e, "failed to create tray icon");
+            }
+            background::apply(app.handle(), &app_config.background);
+            hotkeys::init(app.handle(), &app_config.hotkeys);
+            if background::launched_hidden() {
+                tracing::info!("started in background mode; main window deferred");
+            } else {
+                background::show_main_window(app.handle());
+            }
+            let sink: SharedEventSink = Arc::new(app.handle().clone());
+            #[cfg(feature = "remote")]
+            let sink = remote::start(&remote_cfg, remote_handle, sink);
+            scheduler::spawn(scheduler_tx, sink.clone());
+            config_watch::spawn(app.handle().clone(), registry_state.clone());
             let registry_for_ctx = registry_state.clone();
             let stop_flag_for_ctx = stop_flag.clone();
-            let perception_cfg_clone = perception_cfg.clone();
 
             tracing::info!("spawning Graph-based agent loop");
             tauri::async_runtime::spawn(async move {
                 agent_loop(
-                    app_handle,
+                    sink,
                     agent_rx,
                     registry_for_ctx,
-                    perception_cfg_clone,
-                    yolo_detector,
+                    app_config,
+                    perception_chain,
                     loop_config,
                     stop_flag_for_ctx,
+                    EventBus::new(),
                 )
                 .await;
                 tracing::info!("Agent loop task exited");
             });
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running SeeClaw application");
+        .build(tauri::generate_context!())
+        .expect("error while building SeeClaw application")
+        .run(background::on_run_event);
 }
 
 /// Main agent loop: waits for GoalReceived events, then executes the graph.
-async fn agent_loop(
-    app: tauri::AppHandle,
+/// Shared by the Tauri app and the embeddable [`Agent`] facade.
+pub(crate) async fn agent_loop(
+    app: SharedEventSink,
     mut event_rx: mpsc::Receiver<AgentEvent>,
     registry: Arc<Mutex<ProviderRegistry>>,
-    perception_cfg: config::PerceptionConfig,
-    yolo_detector: Option<YoloDetector>,
+    config: config::AppConfig,
+    perception: PerceptionChain,
     loop_config: LoopConfig,
-    stop_flag: Arc<AtomicBool>,
+    stop_flag: Arc<StopSignal>,
+    bus: EventBus,
 ) {
-    use tauri::Emitter;
-
     // Build the graph once (topology is static)
     let graph = build_default_flow();
 
     // Load skill registry (manifests + combos)
     let skill_registry = {
-        crate::skills::manager::load_skill_registry("prompts/skills").await
+        crate::skills::manager::load_skill_registry(crate::skills::SKILLS_DIR, &config.safety).await
     };
     tracing::info!(skills = skill_registry.skill_names().len(), "Skill registry loaded");
 
+    // Connect enabled MCP servers in the background (failures are logged and skipped)
+    let mcp = McpManager::connect_all(&config.mcp);
+
+    // Experience memory (local index + embedder); disabled when unavailable
+    #[cfg(feature = "rag")]
+    let experience = rag::experience::ExperienceStore::open(&config).map(Arc::new);
+
+    // Drop sessions past the `[history]` retention limits.
+    let history_cfg = config.history.clone();
+    tokio::task::spawn_blocking(move || history::prune(&history_cfg));
+
     // Build the node context (immutable resources)
-    let ctx = NodeContext::new(
+    let mut ctx = NodeContext::new(
         app.clone(),
         registry,
-        perception_cfg,
-        yolo_detector,
+        &config,
+        perception,
         LoopController::new(loop_config),
         skill_registry,
+        mcp,
+        bus,
     );
-
-    // Goal buffered from a mid-task interruption (see forwarder logic below).
-    let mut buffered_goal: Option<String> = None;
+    ctx.bus.spawn_logger();
+
+    // Profile and config revision `ctx` was built with; saving settings,
+    // `set_active_profile` and edits to config.toml move the global ones.
+    let mut ctx_profile = config.active_profile.clone();
+    let mut ctx_revision = config_watch::revision();
+    profiles::set_active(&ctx_profile);
+
+    // Pause signal shared with each task's SharedState; set by the forwarder.
+    let pause = Arc::new(PauseSignal::new());
+
+    // Previous task, kept briefly so follow-up goals can refer back to it.
+    let mut last_task: Option<TaskCarryover> = None;
+
+    // Set when a goal was deferred because every LLM provider was
+    // unreachable; deferred goals wait in the task queue (restored sessions
+    // in `pending_restore`) until a health probe succeeds again.
+    let mut offline = false;
+    // Set by a successful probe so the next goal doesn't probe twice.
+    let mut just_probed = false;
+    // Restored session that was deferred; it starts before the queue.
+    let mut pending_restore: Option<Box<Checkpoint>> = None;
+    // Pause received while goals were waiting: hold them until Resume.
+    let mut held = false;
 
     loop {
-        // Wait for a GoalReceived event, or consume one buffered from a
-        // mid-task interruption (Bug 3 fix: new goals must not be lost).
-        let goal = if let Some(g) = buffered_goal.take() {
+        // Waiting on the network or on Resume: keep handling events and
+        // retry the probe, without starting anything.
+        let waiting = pending_restore.is_some() || task_queue::len() > 0;
+        held &= waiting;
+        if (offline || held) && waiting {
+            tokio::select! {
+                evt = event_rx.recv() => match evt {
+                    Some(AgentEvent::GoalReceived(g)) => {
+                        task_queue::push(g, None);
+                        task_queue::emit(&*app);
+                    }
+                    Some(AgentEvent::GoalWithLoop(g, l)) => {
+                        task_queue::push(g, Some(l));
+                        task_queue::emit(&*app);
+                    }
+                    Some(AgentEvent::RestoreSession(cp)) => {
+                        if let Some(old) = pending_restore.replace(cp) {
+                            tracing::warn!(goal = %old.goal, "agent_loop: deferred restore replaced by a newer one");
+                        }
+                    }
+                    Some(AgentEvent::Pause) => {
+                        tracing::info!("agent_loop: pause received, holding queued goals");
+                        held = true;
+                        let _ = app.emit("agent_state_changed", serde_json::json!({ "state": "paused" }));
+                    }
+                    Some(AgentEvent::Resume) => {
+                        tracing::info!("agent_loop: resume received, releasing queued goals");
+                        held = false;
+                    }
+                    Some(AgentEvent::Stop) => {
+                        let discarded = task_queue::clear() + usize::from(pending_restore.take().is_some());
+                        tracing::info!(discarded, "agent_loop: stop received, discarding waiting goals");
+                        held = false;
+                        task_queue::emit(&*app);
+                        let _ = app.emit("agent_state_changed", serde_json::json!({ "state": "idle" }));
+                    }
+                    Some(AgentEvent::UserApproved | AgentEvent::UserRejected) => {
+                        tracing::debug!("agent_loop: approval answer with no action awaiting it, ignored");
+                    }
+                    None => {
+                        tracing::info!("agent_loop: channel closed, exiting");
+                        break;
+                    }
+                },
+                _ = tokio::time::sleep(DEFERRED_RETRY_INTERVAL), if offline => {
+                    if any_provider_reachable(&ctx.registry).await {
+                        tracing::info!(
+                            queued = task_queue::len(),
+                            "agent_loop: providers reachable again, starting deferred goals"
+                        );
+                        offline = false;
+                        just_probed = true;
+                    }
+                }
+            }
+            continue;
+        }
+
+        // Take the next queued goal (submitted while the previous task ran,
+        // or deferred while offline), or wait for a GoalReceived event.
+        // `probed` is true right after a successful health check, so we
+        // don't probe twice.
+        let mut queued: Option<task_queue::QueuedTask> = None;
+        // Set when the goal is an unfinished task restored by `resume_session`.
+        let mut restore: Option<Box<Checkpoint>> = None;
+        // Per-goal loop settings from `start_task`.
+        let mut overrides: Option<LoopOverrides> = None;
+        let probed = std::mem::take(&mut just_probed);
+        let goal = if let Some(cp) = pending_restore.take() {
+            let g = cp.goal.clone();
+            restore = Some(cp);
+            g
+        } else if let Some(t) = task_queue::pop() {
+            task_queue::emit(&*app);
+            overrides = t.loop_overrides.clone();
+            let g = t.goal.clone();
+            queued = Some(t);
             g
         } else {
             match event_rx.recv().await {
                 Some(AgentEvent::GoalReceived(g)) => g,
+                Some(AgentEvent::GoalWithLoop(g, l)) => {
+                    overrides = Some(l);
+                    g
+                }
+                Some(AgentEvent::RestoreSession(cp)) => {
+                    let g = cp.goal.clone();
+                    restore = Some(cp);
+                    g
+                }
                 Some(AgentEvent::Stop) => {
                     tracing::info!("agent_loop: stop received while idle");
                     continue;
@@ -179,30 +389,85 @@ async fn agent_loop(
             }
         };
 
+        // Follow config changes made since the last task, then give a goal
+        // that names its own profile a context (and provider registry) of its own.
+        let revision = config_watch::revision();
+        if revision != ctx_revision {
+            let active = profiles::active();
+            match load_profile(&active, &config) {
+                Ok(cfg) => {
+                    ctx = ctx.with_config(&cfg, ctx.registry.clone()).await;
+                    ctx.loop_ctrl.lock().await.set_defaults(LoopConfig::from(&cfg.safety));
+                    tracing::info!(profile = %active, "agent_loop: applied updated config");
+                }
+                Err(e) => tracing::warn!(profile = %active, error = %e, "agent_loop: cannot apply updated config"),
+            }
+            ctx_profile = active;
+            ctx_revision = revision;
+        }
+        let goal_profile = overrides.as_ref().and_then(|o| o.profile.as_deref()).filter(|p| *p != ctx_profile);
+        let scoped = match goal_profile.map(|name| (name, load_profile(name, &config))) {
+            Some((name, Ok(cfg))) => {
+                tracing::info!(profile = %name, "agent_loop: using config profile for this goal");
+                let registry = Arc::new(Mutex::new(ProviderRegistry::from_config(&cfg)));
+                Some(ctx.with_config(&cfg, registry).await)
+            }
+            Some((name, Err(e))) => {
+                tracing::warn!(profile = %name, error = %e, "agent_loop: profile not applied");
+                None
+            }
+            None => None,
+        };
+        let task_ctx = scoped.as_ref().unwrap_or(&ctx);
+
+        // Offline: accept the goal but hold it until a provider answers,
+        // instead of failing the task on the first LLM call.
+        if !probed && !any_provider_reachable(&task_ctx.registry).await {
+            offline = true;
+            match (restore.take(), queued) {
+                (Some(cp), _) => pending_restore = Some(cp),
+                (None, Some(t)) => task_queue::requeue(t),
+                (None, None) => {
+                    task_queue::push(goal.clone(), overrides);
+                }
+            }
+            defer_goal(&app, &goal);
+            continue;
+        }
+
         tracing::info!(goal = %goal, "agent_loop: starting task");
 
         // Reset stop flag for new task
-        stop_flag.store(false, std::sync::atomic::Ordering::SeqCst);
+        stop_flag.reset();
 
-        // Reset loop controller
+        // Reset loop controller (limits and counters are per goal)
         {
-            let mut ctrl = ctx.loop_ctrl.lock().await;
-            ctrl.reset();
+            let mut ctrl = task_ctx.loop_ctrl.lock().await;
+            ctrl.begin_task(overrides.as_ref());
         }
 
+        if let Some(rec) = task_ctx.registry.lock().await.recorder() {
+            rec.begin_task(&goal);
+        }
+        crate::llm::usage::begin_task();
+        crate::agent_engine::undo::begin_task(&goal);
+        record_history(task_ctx, "user", goal.clone(), None).await;
+
         // Notify frontend — "routing" because the router node runs first
         let _ = app.emit("agent_state_changed", serde_json::json!({
             "state": "routing",
             "goal": &goal,
+            "lang": GoalLanguage::for_goal(&goal).code(),
+            "from_queue": queued.is_some(),
+            "restored": restore.is_some(),
         }));
 
         // Create a new per-task channel for mid-task events (approve/reject/stop)
         let (task_tx, task_rx) = mpsc::channel::<AgentEvent>(32);
 
-        // Shared slot for a goal that arrives while this task is still running.
-        let pending_goal: Arc<Mutex<Option<String>>> = Arc::new(Mutex::new(None));
-        let pg = pending_goal.clone();
-        let sf = stop_flag.clone();
+        let sink = app.clone();
+        pause.resume();
+        let ps = pause.clone();
 
         // Oneshot used to tell the forwarder "graph is done, stop waiting".
         // Without this the forwarder blocks forever on event_rx.recv() after a
@@ -219,12 +484,29 @@ async fn agent_loop(
                     evt = event_rx.recv() => {
                         let Some(evt) = evt else { break };
                         match evt {
-                            // New goal mid-execution: store it, interrupt current task.
+                            // New goal mid-execution: queue it behind the current task.
                             AgentEvent::GoalReceived(new_goal) => {
-                                *pg.lock().await = Some(new_goal);
-                                sf.store(true, std::sync::atomic::Ordering::SeqCst);
-                                let _ = task_tx.send(AgentEvent::Stop).await;
-                                break;
+                                let queued = task_queue::push(new_goal, None);
+                                tracing::info!(id = queued.id, goal = %queued.goal, "agent_loop: task running, goal queued");
+                                task_queue::emit(&*sink);
+                            }
+                            AgentEvent::GoalWithLoop(new_goal, l) => {
+                                let queued = task_queue::push(new_goal, Some(l));
+                                tracing::info!(id = queued.id, goal = %queued.goal, "agent_loop: task running, goal queued");
+                                task_queue::emit(&*sink);
+                            }
+                            // Pause / resume act through the shared signal
+                            // the graph checks between nodes.
+                            AgentEvent::Pause => ps.pause(),
+                            AgentEvent::Resume => ps.resume(),
+                            AgentEvent::RestoreSession(cp) => {
+                                tracing::warn!(session = %cp.session_id, "agent_loop: task running, resume_session ignored");
+                                let _ = sink.emit("agent_activity", serde_json::json!({
+                                    "text": GoalLanguage::current().pick(
+                                        "当前任务仍在运行，结束后再恢复会话",
+                                        "A task is still running — resume the session after it ends",
+                                    ),
+                                }));
                             }
                             other => {
                                 let should_break = matches!(other, AgentEvent::Stop);
@@ -242,9 +524,50 @@ async fn agent_loop(
 
         // Build per-task SharedState
         let mut state = SharedState::new(goal.clone(), stop_flag.clone(), task_rx);
+        state.lang.make_current();
+        state.skills_context = task_ctx.skill_registry.skills_context_for_goal(&goal);
+        state.pause = pause.clone();
+
+        if let Some(prev) = last_task.take().filter(|t| t.is_fresh()) {
+            tracing::info!(previous_goal = %prev.goal, "agent_loop: carrying over previous task context");
+            state.carryover_context = prev.to_prompt();
+        }
+
+        #[cfg(feature = "rag")]
+        if let Some(store) = &experience {
+            match store.recall(&goal, rag::experience::RECALL_TOP_K).await {
+                Ok(hits) => {
+                    tracing::info!(recalled = hits.len(), "agent_loop: experience recall");
+                    state.experience_context = rag::experience::format_for_planner(&hits);
+                }
+                Err(e) => tracing::warn!(error = %e, "agent_loop: experience recall failed"),
+            }
+        }
+
+        // A restored task continues at its saved step; its old checkpoint is
+        // dropped now that this session checkpoints it.
+        let start = restore.take().map(|cp| {
+            let session_id = cp.session_id.clone();
+            let start = cp.restore(&mut state);
+            checkpoint::remove(&session_id);
+            tracing::info!(session = %session_id, step = state.current_step_idx, start, "agent_loop: resuming saved task");
+            if !state.todo_steps.is_empty() {
+                let _ = app.emit("todolist_updated", serde_json::json!({
+                    "steps": &state.todo_steps,
+                    "total": state.todo_steps.len(),
+                }));
+            }
+            start
+        });
 
         // Run the graph
-        let result = graph.run(&mut state, &ctx).await;
+        let task_span = tracing::info_span!("task", goal = %state.goal);
+        let result = match start {
+            Some(node) => graph.run_from(node, &mut state, task_ctx).instrument(task_span).await,
+            None => graph.run(&mut state, task_ctx).instrument(task_span).await,
+        };
+        crate::agent_engine::undo::end_task();
+        checkpoint::clear();
 
         // Signal the forwarder to exit (it may be blocked on recv()).
         // Any events already in event_rx are untouched and will be read next iteration.
@@ -259,35 +582,91 @@ async fn agent_loop(
             }
         };
 
-        // Recover goal that arrived mid-task (if any), to process on next iteration.
-        buffered_goal = pending_goal.lock().await.take();
-
-        // Report result (skip if we were interrupted by a new goal)
-        if buffered_goal.is_none() {
-            match result {
-                Ok(()) => {
-                    let summary = match &state.result {
-                        Some(GraphResult::Done { summary }) => summary.clone(),
-                        Some(GraphResult::Error { message }) => format!("Error: {message}"),
-                        None => "Task completed.".to_string(),
-                    };
-                    tracing::info!(summary = %summary, "agent_loop: task finished");
-                    let _ = app.emit("agent_state_changed", serde_json::json!({
-                        "state": "done",
-                        "summary": summary,
-                    }));
-                }
-                Err(e) => {
-                    tracing::error!(error = %e, "agent_loop: graph execution failed");
-                    let _ = app.emit("agent_state_changed", serde_json::json!({
-                        "state": "error",
-                        "message": e,
-                    }));
+        // Report result
+        match result {
+            Ok(()) => {
+                let (summary, category) = match &state.result {
+                    Some(GraphResult::Done { summary }) if state.budget_exhausted.is_some() => {
+                        (summary.clone(), Some(ErrorCategory::BudgetExhausted))
+                    }
+                    Some(GraphResult::Done { summary }) => (summary.clone(), None),
+                    Some(GraphResult::Error { message, category }) => {
+                        (format!("Error: {message}"), Some(*category))
+                    }
+                    None => ("Task completed.".to_string(), None),
+                };
+                tracing::info!(summary = %summary, "agent_loop: task finished");
+                record_history(task_ctx, "result", summary.clone(), Some(category.is_none())).await;
+                last_task = Some(TaskCarryover::from_state(&state, &summary));
+
+                // Post-mortem in the background so the next goal isn't delayed.
+                #[cfg(feature = "rag")]
+                if let Some(store) = experience.clone() {
+                    let registry = task_ctx.registry.clone();
+                    let (goal, steps_log, outcome) = (goal.clone(), state.steps_log.clone(), summary.clone());
+                    tokio::spawn(async move {
+                        if let Err(e) = store.append_experience(&registry, &goal, &steps_log, &outcome).await {
+                            tracing::warn!(error = %e, "agent_loop: failed to store experience");
+                        }
+                    });
                 }
+                let _ = app.emit("agent_state_changed", serde_json::json!({
+                    "state": "done",
+                    "summary": summary,
+                    "category": category,
+                }));
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "agent_loop: graph execution failed");
+                record_history(task_ctx, "result", format!("Error: {e}"), Some(false)).await;
+                let _ = app.emit("agent_state_changed", serde_json::json!({
+                    "state": "error",
+                    "message": e.to_string(),
+                    "category": e.category(),
+                }));
             }
-        } else {
-            tracing::info!("agent_loop: task interrupted by new goal, picking up immediately");
         }
     }
 }
 
+/// Profile `name` applied to config.toml as it is now (so profiles added
+/// after startup are found), or to the startup config when there is no file.
+fn load_profile(name: &str, startup: &config::AppConfig) -> SeeClawResult<config::AppConfig> {
+    let base = config::load_config().unwrap_or_else(|_| startup.clone());
+    profiles::apply(&base, name)
+}
+
+/// Append a goal or task outcome to the session history.
+async fn record_history(ctx: &NodeContext, role: &str, content: String, ok: Option<bool>) {
+    let mut history = ctx.history.lock().await;
+    history.push(HistoryEntry {
+        ts: chrono::Utc::now().timestamp_millis(),
+        role: role.into(),
+        content: Some(content),
+        action: None,
+        ok,
+        screenshot: None,
+        reasoning: None,
+    });
+    let _ = history.flush();
+}
+
+/// Tell the frontend that `goal`, which cannot run because no provider is
+/// reachable, has been accepted in the `deferred` state.
+fn defer_goal(app: &SharedEventSink, goal: &str) {
+    let queued = task_queue::len();
+    tracing::warn!(goal = %goal, queued, "agent_loop: providers unreachable, deferring goal");
+    task_queue::emit(&**app);
+    let _ = app.emit("agent_state_changed", serde_json::json!({
+        "state": "deferred",
+        "goal": goal,
+        "queued": queued,
+    }));
+    let _ = app.emit(
+        "agent_activity",
+        serde_json::json!({ "text": GoalLanguage::for_goal(&goal).pick(
+            "网络不可用，任务已排队，恢复连接后自动开始…",
+            "Network unavailable — task queued and will start when the connection is back…",
+        ) }),
+    );
+}
@@ -0,0 +1,165 @@
+//! Provider failover — retries a role's call on the next provider in its chain.
+//!
+//! A role with `fallback = [...]` in config.toml gets a [`FailoverProvider`]
+//! from `ProviderRegistry::call_config_for_role`. When a provider answers with
+//! 429 / 5xx, times out, or cannot be reached, the call moves on to the next
+//! entry, and the failed provider's circuit opens: it is skipped by every role
+//! until the cooldown expires.
+//!
+//! A provider may fail after part of its answer was already streamed. Before
+//! the next provider starts, a `restart` chunk tells the UI to drop that
+//! partial text so the answer is not shown twice.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use crate::agent_engine::lang::GoalLanguage;
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::events::SharedEventSink;
+use crate::llm::provider::LlmProvider;
+use crate::llm::types::{CallConfig, ChatMessage, LlmResponse, StreamChunk, StreamChunkKind, ToolDef};
+
+/// Default time a failed provider is skipped.
+pub const DEFAULT_CIRCUIT_COOLDOWN_SECS: u64 = 60;
+
+/// Per-provider open/closed state, shared by all roles of a registry.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    cooldown: Duration,
+    open_until: Mutex<HashMap<String, Instant>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(cooldown: Duration) -> Self {
+        Self {
+            cooldown,
+            open_until: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// `true` while the provider is cooling down after a failure.
+    pub fn is_open(&self, provider: &str) -> bool {
+        let mut map = self.open_until.lock().unwrap_or_else(|e| e.into_inner());
+        match map.get(provider) {
+            Some(until) if Instant::now() < *until => true,
+            Some(_) => {
+                map.remove(provider);
+                false
+            }
+            None => false,
+        }
+    }
+
+    pub fn trip(&self, provider: &str) {
+        tracing::warn!(provider, cooldown_secs = self.cooldown.as_secs(), "circuit opened for provider");
+        self.open_until
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(provider.to_string(), Instant::now() + self.cooldown);
+    }
+
+    pub fn reset(&self, provider: &str) {
+        self.open_until.lock().unwrap_or_else(|e| e.into_inner()).remove(provider);
+    }
+}
+
+/// One link of a failover chain.
+#[derive(Clone)]
+pub struct ChainEntry {
+    pub provider: Arc<dyn LlmProvider>,
+    /// Model sent to this provider (overrides `CallConfig::model`).
+    pub model: String,
+}
+
+/// Tries each entry in order, skipping providers whose circuit is open.
+pub struct FailoverProvider {
+    role: String,
+    chain: Vec<ChainEntry>,
+    breaker: Arc<CircuitBreaker>,
+}
+
+impl FailoverProvider {
+    pub fn new(role: String, chain: Vec<ChainEntry>, breaker: Arc<CircuitBreaker>) -> Self {
+        Self { role, chain, breaker }
+    }
+}
+
+/// Errors worth retrying on another provider: rate limits, server errors,
+/// timeouts and transport failures. Auth / bad-request errors are not — the
+/// next provider would not see a different request.
+pub fn is_failover_error(err: &SeeClawError) -> bool {
+    match err {
+        SeeClawError::ProviderTimeout(_) | SeeClawError::Http(_) => true,
+        SeeClawError::LlmProvider(msg) => {
+            let status: String = msg.chars().take_while(|c| c.is_ascii_digit()).collect();
+            match status.parse::<u16>() {
+                Ok(code) => code == 429 || (500..600).contains(&code),
+                Err(_) => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+#[async_trait]
+impl LlmProvider for FailoverProvider {
+    fn name(&self) -> &str {
+        self.chain.first().map(|e| e.provider.name()).unwrap_or("failover")
+    }
+
+    async fn chat(
+        &self,
+        messages: Vec<ChatMessage>,
+        tools: Vec<ToolDef>,
+        cfg: &CallConfig,
+        app: &SharedEventSink,
+    ) -> SeeClawResult<LlmResponse> {
+        // Providers with an open circuit go last rather than being dropped, so
+        // a role never ends up with nothing to try.
+        let (closed, open): (Vec<&ChainEntry>, Vec<&ChainEntry>) =
+            self.chain.iter().partition(|e| !self.breaker.is_open(e.provider.name()));
+        let ordered: Vec<&ChainEntry> = closed.into_iter().chain(open).collect();
+
+        let mut last_err = None;
+        for (attempt, entry) in ordered.iter().enumerate() {
+            let name = entry.provider.name();
+            if attempt > 0 {
+                tracing::warn!(role = %self.role, provider = name, model = %entry.model, attempt, "failing over to next provider");
+                // Reasoning streams even for silent calls, so always reset.
+                let _ = app.emit(
+                    "llm_stream_chunk",
+                    &StreamChunk { kind: StreamChunkKind::Restart, content: String::new() },
+                );
+                if !cfg.silent {
+                    let _ = app.emit(
+                        "agent_activity",
+                        serde_json::json!({ "text": GoalLanguage::current().pick(
+                            format!("模型服务不可用，切换到 {name}…"),
+                            format!("Model service unavailable, switching to {name}…"),
+                        ) }),
+                    );
+                }
+            }
+            let mut call_cfg = cfg.clone();
+            call_cfg.model = entry.model.clone();
+            match entry.provider.chat(messages.clone(), tools.clone(), &call_cfg, app).await {
+                Ok(resp) => {
+                    self.breaker.reset(name);
+                    return Ok(resp);
+                }
+                Err(e) if is_failover_error(&e) => {
+                    tracing::warn!(role = %self.role, provider = name, error = %e, "provider failed");
+                    self.breaker.trip(name);
+                    last_err = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last_err.unwrap_or_else(|| {
+            SeeClawError::Config(format!("Role '{}' has no providers to call", self.role))
+        }))
+    }
+}
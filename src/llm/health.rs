@@ -0,0 +1,77 @@
+//! Provider health checks — lightweight reachability probes for LLM endpoints.
+//!
+//! Used by the agent loop to decide whether a goal can run now or must be
+//! deferred until connectivity comes back (laptops on flaky Wi-Fi, VPN drops).
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::stream::{FuturesUnordered, StreamExt};
+use tokio::sync::Mutex;
+
+use crate::llm::registry::ProviderRegistry;
+
+/// Roles whose providers a task may need. A goal is only deferred when the
+/// providers behind ALL of these are unreachable.
+pub const REQUIRED_ROLES: &[&str] = &["routing", "chat", "tools", "vision"];
+
+/// Timeout for a single reachability probe.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Probe a single endpoint. Any HTTP response (including 4xx/405) counts as
+/// reachable — we only care whether the host answers, not whether the probe
+/// request itself is valid for the API.
+pub async fn probe_endpoint(client: &reqwest::Client, url: &str) -> bool {
+    crate::net_audit::record(url, crate::net_audit::NetPurpose::HealthProbe);
+    match client.head(url).send().await {
+        Ok(_) => true,
+        Err(e) => {
+            tracing::debug!(url = %url, error = %e, "provider probe failed");
+            false
+        }
+    }
+}
+
+/// Returns `true` if at least one provider used by [`REQUIRED_ROLES`] answers.
+///
+/// An empty registry (no endpoints configured) is reported as reachable so the
+/// task fails fast with the usual config error instead of being deferred forever.
+/// So is a registry replaying a repro log, which never touches the network.
+pub async fn any_provider_reachable(registry: &Arc<Mutex<ProviderRegistry>>) -> bool {
+    let endpoints: Vec<String> = {
+        let reg = registry.lock().await;
+        if reg.replaying() {
+            return true;
+        }
+        let mut urls: Vec<String> = REQUIRED_ROLES
+            .iter()
+            .filter_map(|role| reg.api_base_for_role(role))
+            .collect();
+        urls.sort();
+        urls.dedup();
+        urls
+    };
+
+    if endpoints.is_empty() {
+        return true;
+    }
+
+    let client = match reqwest::Client::builder().timeout(PROBE_TIMEOUT).build() {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::warn!(error = %e, "failed to build probe client; assuming reachable");
+            return true;
+        }
+    };
+
+    // Probe every endpoint at once and stop at the first that answers, so a
+    // dead provider costs one timeout rather than one per endpoint.
+    let mut probes: FuturesUnordered<_> = endpoints.iter().map(|url| probe_endpoint(&client, url)).collect();
+    while let Some(reachable) = probes.next().await {
+        if reachable {
+            return true;
+        }
+    }
+    tracing::warn!(endpoints = ?endpoints, "no LLM provider reachable");
+    false
+}
@@ -1,6 +1,14 @@
+pub mod failover;
+pub mod health;
+pub mod probe;
 pub mod provider;
 pub mod providers;
 pub mod registry;
+pub mod repro;
 pub mod sse_parser;
+pub mod structured;
+pub mod tokens;
 pub mod tools;
+pub mod trace;
 pub mod types;
+pub mod usage;
@@ -0,0 +1,144 @@
+//! Provider connection test — the `test_provider` command.
+//!
+//! Sends one tiny request through the configured provider (with a 1×1 image
+//! for the `vision` role, so a text-only model is caught before a task needs
+//! it) and lists the models the endpoint offers, reporting latency and the
+//! error category instead of letting a bad key or model name surface
+//! mid-task.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::config::ProviderEntry;
+use crate::errors::ErrorCategory;
+use crate::events::{NullSink, SharedEventSink};
+use crate::llm::provider::LlmProvider;
+use crate::llm::providers::ollama;
+use crate::llm::types::{CallConfig, ChatMessage, ContentPart, ImageUrl, MessageContent};
+use crate::net_audit::{self, NetPurpose};
+
+/// Upper bound for the probe call and for the model listing.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(30);
+/// 1×1 white PNG.
+const TEST_IMAGE: &str = "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mP8/5+hHgAHggJ/PchI7wAAAABJRU5ErkJggg==";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderTestReport {
+    pub provider: String,
+    pub role: Option<String>,
+    pub model: String,
+    pub ok: bool,
+    pub latency_ms: u64,
+    /// Start of the model's reply.
+    pub reply: Option<String>,
+    pub error: Option<String>,
+    pub category: Option<ErrorCategory>,
+    /// Models the endpoint lists; empty when it has no listing API.
+    pub models: Vec<String>,
+    pub models_error: Option<String>,
+    /// The configured model is missing from a non-empty `models` list.
+    pub model_not_listed: bool,
+}
+
+/// Probe provider `id` as `role` would use it (`vision` sends an image).
+/// `provider` and `cfg` come from [`ProviderRegistry::call_config_for_provider`](crate::llm::registry::ProviderRegistry::call_config_for_provider).
+pub async fn test_provider(
+    provider: Arc<dyn LlmProvider>,
+    mut cfg: CallConfig,
+    id: &str,
+    entry: &ProviderEntry,
+    role: Option<&str>,
+) -> ProviderTestReport {
+    cfg.timeout = Some(PROBE_TIMEOUT);
+
+    let prompt = "Reply with the single word OK.";
+    let content = if role == Some("vision") {
+        MessageContent::Parts(vec![
+            ContentPart::Text { text: format!("{prompt} (The attached image is a blank test pixel.)") },
+            ContentPart::ImageUrl { image_url: ImageUrl { url: TEST_IMAGE.to_string() } },
+        ])
+    } else {
+        MessageContent::Text(prompt.to_string())
+    };
+    let messages = vec![ChatMessage { role: "user".into(), content, tool_call_id: None, tool_calls: None }];
+
+    let sink: SharedEventSink = Arc::new(NullSink);
+    let started = Instant::now();
+    let result = provider.chat(messages, Vec::new(), &cfg, &sink).await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+    let (models, models_error) = match list_models(id, entry).await {
+        Ok(models) => (models, None),
+        Err(e) => (Vec::new(), Some(e)),
+    };
+
+    let model_not_listed = !models.is_empty() && !models.iter().any(|m| model_matches(m, &cfg.model));
+    let mut report = ProviderTestReport {
+        provider: id.to_string(),
+        role: role.map(str::to_string),
+        model: cfg.model,
+        ok: false,
+        latency_ms,
+        reply: None,
+        error: None,
+        category: None,
+        models,
+        models_error,
+        model_not_listed,
+    };
+    match result {
+        Ok(resp) => {
+            report.ok = true;
+            report.reply = Some(resp.content.chars().take(80).collect());
+        }
+        Err(e) => {
+            report.category = Some(e.category());
+            report.error = Some(e.to_string());
+        }
+    }
+    tracing::info!(provider = id, role = ?role, ok = report.ok, latency_ms, "provider test");
+    report
+}
+
+/// `gemini-1.5-flash` matches `models/gemini-1.5-flash`; Ollama's `llama3`
+/// matches `llama3:latest`.
+fn model_matches(listed: &str, model: &str) -> bool {
+    listed == model
+        || listed.strip_prefix("models/") == Some(model)
+        || listed.strip_suffix(":latest") == Some(model)
+}
+
+/// Model ids from the endpoint's listing API.
+async fn list_models(id: &str, entry: &ProviderEntry) -> Result<Vec<String>, String> {
+    let client = reqwest::Client::builder().timeout(PROBE_TIMEOUT).build().map_err(|e| e.to_string())?;
+    if entry.adapter.as_deref() == Some("ollama") {
+        let models = ollama::list_local_models(&client, &entry.api_base).await.map_err(|e| e.to_string())?;
+        return Ok(models.into_iter().map(|m| m.name).collect());
+    }
+
+    let url = format!("{}/models", entry.api_base.trim_end_matches('/'));
+    net_audit::record(&url, NetPurpose::ModelList);
+    let key = entry.resolve_api_key(id);
+    let request = match entry.adapter.as_deref() {
+        Some("gemini") => client.get(&url).header("x-goog-api-key", key),
+        _ => client.get(&url).bearer_auth(key),
+    };
+    let response = request.send().await.map_err(|e| e.to_string())?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("GET {url}: {status}"));
+    }
+    let body: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    // OpenAI: {"data": [{"id"}]}; Gemini: {"models": [{"name"}]}.
+    let mut models: Vec<String> = body["data"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|m| m["id"].as_str())
+        .chain(body["models"].as_array().into_iter().flatten().filter_map(|m| m["name"].as_str()))
+        .map(str::to_string)
+        .collect();
+    models.sort();
+    Ok(models)
+}
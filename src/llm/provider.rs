@@ -1,8 +1,13 @@
+use std::future::Future;
+
 use async_trait::async_trait;
-use tauri::AppHandle;
+use tracing::Instrument;
 
-use crate::errors::SeeClawResult;
-use crate::llm::types::{CallConfig, ChatMessage, LlmResponse, ToolDef};
+use crate::agent_engine::metrics::{self, Metric};
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::events::SharedEventSink;
+use crate::llm::types::{CallConfig, ChatMessage, LlmResponse, ReasoningChunk, ToolDef};
+use crate::llm::usage;
 
 /// Unified LLM provider trait. All providers implement this trait.
 /// New providers only need to implement this trait and register in config.toml.
@@ -13,7 +18,9 @@ pub trait LlmProvider: Send + Sync {
 
     /// Execute a chat call with per-call configuration.
     ///
-    /// Streams "llm_stream_chunk" events to the frontend in real time, and returns
+    /// Streams "llm_stream_chunk" events (answer text, tool calls) and
+    /// "llm_reasoning_chunk" events (see [`emit_reasoning`]) to the event sink
+    /// in real time, and returns
     /// the fully-accumulated `LlmResponse` (content, reasoning, tool_calls) so the
     /// engine can act on any tool calls the model requested.
     async fn chat(
@@ -21,6 +28,64 @@ pub trait LlmProvider: Send + Sync {
         messages: Vec<ChatMessage>,
         tools: Vec<ToolDef>,
         cfg: &CallConfig,
-        app: &AppHandle,
+        app: &SharedEventSink,
     ) -> SeeClawResult<LlmResponse>;
 }
+
+/// Run a provider call under `cfg.cancel` and `cfg.timeout`, then record the
+/// token usage it reported and an `llm_call_done` metric.
+///
+/// Dropping the losing future drops the in-flight `reqwest` request or byte
+/// stream, which closes the connection instead of reading the rest of a
+/// response nobody wants.
+pub async fn guarded(
+    provider: &str,
+    cfg: &CallConfig,
+    app: &SharedEventSink,
+    call: impl Future<Output = SeeClawResult<LlmResponse>>,
+) -> SeeClawResult<LlmResponse> {
+    let deadline = async {
+        match cfg.timeout {
+            Some(limit) => tokio::time::sleep(limit).await,
+            None => std::future::pending().await,
+        }
+    };
+    let started = std::time::Instant::now();
+    let response = tokio::select! {
+        result = call.instrument(tracing::info_span!("llm_call", provider, model = %cfg.model)) => result,
+        _ = cfg.cancel.cancelled() => Err(SeeClawError::Cancelled),
+        _ = deadline => Err(SeeClawError::ProviderTimeout(format!(
+            "no response from {} within {}s",
+            cfg.model,
+            cfg.timeout.map(|t| t.as_secs_f32()).unwrap_or_default()
+        ))),
+    };
+    if !matches!(response, Err(SeeClawError::Cancelled)) {
+        let tokens = response.as_ref().ok().and_then(|r| r.usage).unwrap_or_default();
+        metrics::record(&**app, Metric::LlmCallDone {
+            ms: started.elapsed().as_millis() as u64,
+            provider: provider.to_string(),
+            model: cfg.model.clone(),
+            ok: response.is_ok(),
+            prompt_tokens: tokens.prompt_tokens,
+            completion_tokens: tokens.completion_tokens,
+        });
+    }
+    let response = response?;
+    if let Some(u) = response.usage {
+        usage::record(provider, &cfg.model, u, app);
+    }
+    if !response.reasoning.is_empty() {
+        let _ = app.emit("llm_reasoning_chunk", &ReasoningChunk { content: String::new(), done: true });
+    }
+    Ok(response)
+}
+
+/// Forward reasoning text on `llm_reasoning_chunk`. Unlike answer text this is
+/// sent for silent calls too, so the UI can show (collapsed) why the planner
+/// or the VLM chose an action.
+pub fn emit_reasoning(app: &SharedEventSink, content: &str) {
+    if !content.is_empty() {
+        let _ = app.emit("llm_reasoning_chunk", &ReasoningChunk { content: content.to_string(), done: false });
+    }
+}
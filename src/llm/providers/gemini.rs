@@ -0,0 +1,441 @@
+//! Google Gemini provider — native `generateContent` API.
+//!
+//! Translates the OpenAI-style message list into Gemini `contents`
+//! (system prompt → `systemInstruction`, assistant → `model`, tool results →
+//! `functionResponse`), sends images as `inline_data`, and maps
+//! `functionCall` parts back into `ToolCall`s. Selected with
+//! `adapter = "gemini"`; `api_base` is the API root, e.g.
+//! `https://generativelanguage.googleapis.com/v1beta`.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use serde_json::{json, Value};
+
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::events::SharedEventSink;
+use crate::llm::provider::{emit_reasoning, guarded, LlmProvider};
+use crate::llm::trace::CallCapture;
+use crate::llm::types::{
+    CallConfig, ChatMessage, ContentPart, FunctionCall, LlmResponse, MessageContent, StreamChunk,
+    StreamChunkKind, TokenUsage, ToolCall, ToolDef,
+};
+use crate::net_audit::{self, NetPurpose};
+
+pub struct GeminiProvider {
+    id: String,
+    api_base: String,
+    api_key: String,
+    client: reqwest::Client,
+}
+
+impl GeminiProvider {
+    pub fn new(id: String, api_base: String, api_key: String) -> Self {
+        Self {
+            id,
+            api_base,
+            api_key,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn endpoint(&self, model: &str, stream: bool) -> String {
+        let base = self.api_base.trim_end_matches('/');
+        if stream {
+            format!("{base}/models/{model}:streamGenerateContent?alt=sse")
+        } else {
+            format!("{base}/models/{model}:generateContent")
+        }
+    }
+}
+
+#[async_trait]
+impl LlmProvider for GeminiProvider {
+    fn name(&self) -> &str {
+        &self.id
+    }
+
+    async fn chat(
+        &self,
+        messages: Vec<ChatMessage>,
+        tools: Vec<ToolDef>,
+        cfg: &CallConfig,
+        app: &SharedEventSink,
+    ) -> SeeClawResult<LlmResponse> {
+        guarded(&self.id, cfg, app, self.complete(messages, tools, cfg, app)).await
+    }
+}
+
+impl GeminiProvider {
+    /// The call itself; cancellation and the timeout are applied by `chat`.
+    async fn complete(
+        &self,
+        messages: Vec<ChatMessage>,
+        tools: Vec<ToolDef>,
+        cfg: &CallConfig,
+        app: &SharedEventSink,
+    ) -> SeeClawResult<LlmResponse> {
+        let body = build_request(&messages, &tools, cfg);
+
+        tracing::debug!(
+            provider = %self.id,
+            model = %cfg.model,
+            stream = cfg.stream,
+            contents = body["contents"].as_array().map(|a| a.len()).unwrap_or(0),
+            tools = tools.len(),
+            "sending Gemini request"
+        );
+
+        let capture = cfg.capture.as_deref();
+        if let Some(c) = capture {
+            c.request(&body);
+        }
+
+        let response = self.send(&self.endpoint(&cfg.model, cfg.stream), &body).await?;
+        let resp = if cfg.stream {
+            self.handle_stream(response, app, cfg.silent, capture).await?
+        } else {
+            let text = response.text().await?;
+            if let Some(c) = capture {
+                c.raw(&text);
+            }
+            let json: Value = serde_json::from_str(&text)?;
+            let mut acc = Accumulator::default();
+            acc.push(&json)?;
+            let resp = acc.finish();
+            emit_reasoning(app, &resp.reasoning);
+            if !cfg.silent {
+                emit_response(app, &resp);
+            }
+            resp
+        };
+
+        tracing::info!(
+            content_len = resp.content.len(),
+            reasoning_len = resp.reasoning.len(),
+            tool_calls = resp.tool_calls.len(),
+            tools = ?resp.tool_calls.iter().map(|tc| tc.function.name.as_str()).collect::<Vec<_>>(),
+            "Gemini response complete"
+        );
+        Ok(resp)
+    }
+}
+
+impl GeminiProvider {
+    /// POST the request body and fail on non-2xx status.
+    async fn send(&self, url: &str, body: &Value) -> SeeClawResult<reqwest::Response> {
+        net_audit::record(url, NetPurpose::Llm);
+        let response = self
+            .client
+            .post(url)
+            .header("x-goog-api-key", &self.api_key)
+            .json(body)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let err_body = response.text().await.unwrap_or_default();
+            if matches!(status.as_u16(), 408 | 504) {
+                return Err(SeeClawError::ProviderTimeout(format!("{}: {}", status, err_body)));
+            }
+            return Err(SeeClawError::LlmProvider(format!("{}: {}", status, err_body)));
+        }
+        Ok(response)
+    }
+
+    /// Consume a `alt=sse` stream. Every `data:` line is a complete
+    /// `GenerateContentResponse` carrying the next slice of parts.
+    async fn handle_stream(
+        &self,
+        response: reqwest::Response,
+        app: &SharedEventSink,
+        silent: bool,
+        capture: Option<&CallCapture>,
+    ) -> SeeClawResult<LlmResponse> {
+        let mut byte_stream = response.bytes_stream();
+        let mut buf: Vec<u8> = Vec::new();
+        let mut acc = Accumulator::default();
+
+        while let Some(result) = byte_stream.next().await {
+            buf.extend_from_slice(&result?);
+            while let Some(pos) = buf.iter().position(|b| *b == b'\n') {
+                let line: Vec<u8> = buf.drain(..=pos).collect();
+                let line = String::from_utf8_lossy(&line);
+                if let Some(c) = capture.filter(|_| !line.trim().is_empty()) {
+                    c.raw(line.trim());
+                }
+                let Some(data) = line.trim().strip_prefix("data:") else {
+                    continue;
+                };
+                let json: Value = match serde_json::from_str(data.trim()) {
+                    Ok(v) => v,
+                    Err(e) => {
+                        tracing::debug!("Gemini SSE parse skipped: {e}");
+                        continue;
+                    }
+                };
+                let delta = acc.push(&json)?;
+                emit_reasoning(app, &delta.reasoning);
+                if !silent && !delta.content.is_empty() {
+                    emit_chunk(app, StreamChunkKind::Content, delta.content);
+                }
+            }
+        }
+
+        let resp = acc.finish();
+        if !silent {
+            if !resp.tool_calls.is_empty() {
+                if let Ok(tc_json) = serde_json::to_string(&resp.tool_calls) {
+                    emit_chunk(app, StreamChunkKind::ToolCall, tc_json);
+                }
+            }
+            emit_chunk(app, StreamChunkKind::Done, String::new());
+        }
+        Ok(resp)
+    }
+}
+
+// ── Request translation ─────────────────────────────────────────────────────
+
+fn build_request(messages: &[ChatMessage], tools: &[ToolDef], cfg: &CallConfig) -> Value {
+    let mut system_text: Vec<String> = Vec::new();
+    let mut contents: Vec<Value> = Vec::new();
+    // Gemini matches function responses by name, not by call id.
+    let mut call_names: HashMap<String, String> = HashMap::new();
+
+    for msg in messages {
+        let (role, parts) = match msg.role.as_str() {
+            "system" => {
+                system_text.push(message_text(&msg.content));
+                continue;
+            }
+            "assistant" => {
+                let mut parts = content_parts(&msg.content);
+                for tc in msg.tool_calls.iter().flatten() {
+                    call_names.insert(tc.id.clone(), tc.function.name.clone());
+                    let args: Value = serde_json::from_str(&tc.function.arguments).unwrap_or_else(|_| json!({}));
+                    parts.push(json!({ "functionCall": { "name": tc.function.name, "args": args } }));
+                }
+                ("model", parts)
+            }
+            "tool" => {
+                let name = msg
+                    .tool_call_id
+                    .as_ref()
+                    .and_then(|id| call_names.get(id))
+                    .cloned()
+                    .unwrap_or_else(|| "tool".to_string());
+                let text = message_text(&msg.content);
+                ("user", vec![json!({ "functionResponse": { "name": name, "response": { "content": text } } })])
+            }
+            _ => ("user", content_parts(&msg.content)),
+        };
+        if parts.is_empty() {
+            continue;
+        }
+        // Gemini expects alternating turns — merge consecutive same-role messages.
+        match contents.last_mut() {
+            Some(last) if last["role"] == role => {
+                if let Some(existing) = last["parts"].as_array_mut() {
+                    existing.extend(parts);
+                }
+            }
+            _ => contents.push(json!({ "role": role, "parts": parts })),
+        }
+    }
+
+    let mut generation = json!({ "temperature": cfg.temperature });
+    if let Some(rs) = &cfg.response_schema {
+        generation["responseMimeType"] = json!("application/json");
+        generation["responseJsonSchema"] = rs.schema.clone();
+    } else if cfg.json_mode {
+        generation["responseMimeType"] = json!("application/json");
+    }
+    if let Some(seed) = cfg.seed {
+        generation["seed"] = json!(seed);
+    }
+    if let Some(n) = cfg.params.max_tokens {
+        generation["maxOutputTokens"] = json!(n);
+    }
+    if let Some(p) = cfg.params.top_p {
+        generation["topP"] = json!(p);
+    }
+    if let Some(p) = cfg.params.frequency_penalty {
+        generation["frequencyPenalty"] = json!(p);
+    }
+    if let Some(p) = cfg.params.presence_penalty {
+        generation["presencePenalty"] = json!(p);
+    }
+
+    let mut body = json!({
+        "contents": contents,
+        "generationConfig": generation,
+    });
+    if !system_text.is_empty() {
+        body["systemInstruction"] = json!({ "parts": [{ "text": system_text.join("\n\n") }] });
+    }
+    if !tools.is_empty() {
+        let decls: Vec<Value> = tools
+            .iter()
+            .map(|t| {
+                let mut params = t.function.parameters.clone();
+                strip_unsupported_schema(&mut params);
+                json!({
+                    "name": t.function.name,
+                    "description": t.function.description,
+                    "parameters": params,
+                })
+            })
+            .collect();
+        body["tools"] = json!([{ "functionDeclarations": decls }]);
+        body["toolConfig"] = json!({ "functionCallingConfig": { "mode": "AUTO" } });
+    }
+    cfg.params.apply_extra_body(&mut body);
+    body
+}
+
+fn message_text(content: &MessageContent) -> String {
+    match content {
+        MessageContent::Text(t) => t.clone(),
+        MessageContent::Parts(parts) => parts
+            .iter()
+            .filter_map(|p| match p {
+                ContentPart::Text { text } => Some(text.as_str()),
+                ContentPart::ImageUrl { .. } => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+fn content_parts(content: &MessageContent) -> Vec<Value> {
+    match content {
+        MessageContent::Text(t) if t.is_empty() => Vec::new(),
+        MessageContent::Text(t) => vec![json!({ "text": t })],
+        MessageContent::Parts(parts) => parts
+            .iter()
+            .map(|p| match p {
+                ContentPart::Text { text } => json!({ "text": text }),
+                ContentPart::ImageUrl { image_url } => image_part(&image_url.url),
+            })
+            .collect(),
+    }
+}
+
+/// `data:<mime>;base64,<data>` → `inline_data`; anything else → `file_data`.
+fn image_part(url: &str) -> Value {
+    if let Some((mime, data)) = url
+        .strip_prefix("data:")
+        .and_then(|rest| rest.split_once(";base64,"))
+    {
+        return json!({ "inline_data": { "mime_type": mime, "data": data } });
+    }
+    json!({ "file_data": { "mime_type": "image/png", "file_uri": url } })
+}
+
+/// Gemini accepts an OpenAPI subset — drop JSON-Schema keywords it rejects.
+fn strip_unsupported_schema(schema: &mut Value) {
+    match schema {
+        Value::Object(map) => {
+            map.remove("additionalProperties");
+            map.remove("$schema");
+            for v in map.values_mut() {
+                strip_unsupported_schema(v);
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_unsupported_schema),
+        _ => {}
+    }
+}
+
+// ── Response translation ────────────────────────────────────────────────────
+
+#[derive(Default)]
+struct Accumulator {
+    content: String,
+    reasoning: String,
+    tool_calls: Vec<ToolCall>,
+    usage: Option<TokenUsage>,
+}
+
+/// Text that arrived in one response chunk.
+#[derive(Default)]
+struct Delta {
+    content: String,
+    reasoning: String,
+}
+
+impl Accumulator {
+    fn push(&mut self, json: &Value) -> SeeClawResult<Delta> {
+        if let Some(msg) = json["error"]["message"].as_str() {
+            return Err(SeeClawError::LlmProvider(msg.to_string()));
+        }
+        if let Some(reason) = json["promptFeedback"]["blockReason"].as_str() {
+            return Err(SeeClawError::LlmProvider(format!("prompt blocked: {reason}")));
+        }
+
+        // Streaming chunks carry running totals; the last one wins.
+        if let Some(prompt) = json["usageMetadata"]["promptTokenCount"].as_u64() {
+            self.usage = Some(TokenUsage {
+                prompt_tokens: prompt,
+                completion_tokens: json["usageMetadata"]["candidatesTokenCount"].as_u64().unwrap_or(0),
+            });
+        }
+
+        let mut delta = Delta::default();
+        let parts = json["candidates"][0]["content"]["parts"].as_array().cloned().unwrap_or_default();
+        for part in parts {
+            if let Some(call) = part.get("functionCall") {
+                let id = call["id"]
+                    .as_str()
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| format!("gemini_call_{}", self.tool_calls.len()));
+                self.tool_calls.push(ToolCall {
+                    id,
+                    call_type: "function".to_string(),
+                    function: FunctionCall {
+                        name: call["name"].as_str().unwrap_or("").to_string(),
+                        arguments: call.get("args").map(|a| a.to_string()).unwrap_or_else(|| "{}".into()),
+                    },
+                });
+            } else if let Some(text) = part["text"].as_str() {
+                if part["thought"].as_bool().unwrap_or(false) {
+                    delta.reasoning.push_str(text);
+                } else {
+                    delta.content.push_str(text);
+                }
+            }
+        }
+        self.content.push_str(&delta.content);
+        self.reasoning.push_str(&delta.reasoning);
+        Ok(delta)
+    }
+
+    fn finish(self) -> LlmResponse {
+        LlmResponse {
+            content: self.content,
+            reasoning: self.reasoning,
+            tool_calls: self.tool_calls.into_iter().filter(|tc| !tc.function.name.is_empty()).collect(),
+            usage: self.usage,
+        }
+    }
+}
+
+fn emit_chunk(app: &SharedEventSink, kind: StreamChunkKind, content: String) {
+    let _ = app.emit("llm_stream_chunk", &StreamChunk { kind, content });
+}
+
+/// Forward a complete (non-streamed) response to the frontend.
+fn emit_response(app: &SharedEventSink, resp: &LlmResponse) {
+    if !resp.content.is_empty() {
+        emit_chunk(app, StreamChunkKind::Content, resp.content.clone());
+    }
+    if !resp.tool_calls.is_empty() {
+        if let Ok(tc_json) = serde_json::to_string(&resp.tool_calls) {
+            emit_chunk(app, StreamChunkKind::ToolCall, tc_json);
+        }
+    }
+    emit_chunk(app, StreamChunkKind::Done, String::new());
+}
@@ -1 +1,3 @@
+pub mod gemini;
+pub mod ollama;
 pub mod openai_compatible;
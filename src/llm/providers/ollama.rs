@@ -0,0 +1,324 @@
+//! Ollama provider — native `/api/chat` for locally hosted models.
+//!
+//! Streams newline-delimited JSON, sends images as base64 in `images`, and
+//! passes tools through in OpenAI function format (models without tool support
+//! simply answer in text). Selected with `adapter = "ollama"`; `api_base` is the
+//! server root, e.g. `http://localhost:11434`.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::events::SharedEventSink;
+use crate::llm::provider::{emit_reasoning, guarded, LlmProvider};
+use crate::llm::types::{
+    CallConfig, ChatMessage, ContentPart, FunctionCall, LlmResponse, MessageContent, StreamChunk,
+    StreamChunkKind, TokenUsage, ToolCall, ToolDef,
+};
+use crate::net_audit::{self, NetPurpose};
+
+pub const DEFAULT_OLLAMA_BASE: &str = "http://localhost:11434";
+
+pub struct OllamaProvider {
+    id: String,
+    api_base: String,
+    client: reqwest::Client,
+}
+
+impl OllamaProvider {
+    pub fn new(id: String, api_base: String) -> Self {
+        Self {
+            id,
+            api_base,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+/// A model installed in the local Ollama server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalModel {
+    pub name: String,
+    /// Size on disk in bytes.
+    #[serde(default)]
+    pub size: u64,
+    #[serde(default)]
+    pub modified_at: String,
+}
+
+/// Server root for `api_base`, tolerating values that already end in `/api/...`.
+fn server_root(api_base: &str) -> &str {
+    let base = api_base.trim_end_matches('/');
+    let base = if base.is_empty() { DEFAULT_OLLAMA_BASE } else { base };
+    match base.find("/api/") {
+        Some(idx) => &base[..idx],
+        None => base.strip_suffix("/api").unwrap_or(base),
+    }
+}
+
+/// List models installed on the Ollama server at `api_base` (`GET /api/tags`).
+pub async fn list_local_models(client: &reqwest::Client, api_base: &str) -> SeeClawResult<Vec<LocalModel>> {
+    #[derive(Deserialize)]
+    struct Tags {
+        #[serde(default)]
+        models: Vec<LocalModel>,
+    }
+
+    let url = format!("{}/api/tags", server_root(api_base));
+    net_audit::record(&url, NetPurpose::ModelList);
+    let response = client.get(&url).send().await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(SeeClawError::LlmProvider(format!("{url}: {status}: {body}")));
+    }
+    let mut models = response.json::<Tags>().await?.models;
+    models.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(models)
+}
+
+#[async_trait]
+impl LlmProvider for OllamaProvider {
+    fn name(&self) -> &str {
+        &self.id
+    }
+
+    async fn chat(
+        &self,
+        messages: Vec<ChatMessage>,
+        tools: Vec<ToolDef>,
+        cfg: &CallConfig,
+        app: &SharedEventSink,
+    ) -> SeeClawResult<LlmResponse> {
+        guarded(&self.id, cfg, app, self.complete(messages, tools, cfg, app)).await
+    }
+}
+
+impl OllamaProvider {
+    /// The call itself; cancellation and the timeout are applied by `chat`.
+    async fn complete(
+        &self,
+        messages: Vec<ChatMessage>,
+        tools: Vec<ToolDef>,
+        cfg: &CallConfig,
+        app: &SharedEventSink,
+    ) -> SeeClawResult<LlmResponse> {
+        let mut options = json!({ "temperature": cfg.temperature });
+        if let Some(seed) = cfg.seed {
+            options["seed"] = json!(seed);
+        }
+        if let Some(n) = cfg.params.max_tokens {
+            options["num_predict"] = json!(n);
+        }
+        if let Some(p) = cfg.params.top_p {
+            options["top_p"] = json!(p);
+        }
+        if let Some(p) = cfg.params.frequency_penalty {
+            options["frequency_penalty"] = json!(p);
+        }
+        if let Some(p) = cfg.params.presence_penalty {
+            options["presence_penalty"] = json!(p);
+        }
+        let mut body = json!({
+            "model": cfg.model,
+            "messages": convert_messages(&messages),
+            "stream": cfg.stream,
+            "options": options,
+        });
+        if !tools.is_empty() {
+            body["tools"] = serde_json::to_value(&tools)?;
+        }
+        if let Some(rs) = &cfg.response_schema {
+            body["format"] = rs.schema.clone();
+        } else if cfg.json_mode {
+            body["format"] = json!("json");
+        }
+        cfg.params.apply_extra_body(&mut body);
+
+        tracing::debug!(
+            provider = %self.id,
+            model = %cfg.model,
+            stream = cfg.stream,
+            messages = messages.len(),
+            tools = tools.len(),
+            "sending Ollama request"
+        );
+
+        if let Some(c) = &cfg.capture {
+            c.request(&body);
+        }
+
+        let url = format!("{}/api/chat", server_root(&self.api_base));
+        net_audit::record(&url, NetPurpose::Llm);
+        let response = self.client.post(&url).json(&body).send().await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let err_body = response.text().await.unwrap_or_default();
+            if matches!(status.as_u16(), 408 | 504) {
+                return Err(SeeClawError::ProviderTimeout(format!("{}: {}", status, err_body)));
+            }
+            return Err(SeeClawError::LlmProvider(format!("{}: {}", status, err_body)));
+        }
+
+        let mut acc = LlmResponse::default();
+        if cfg.stream {
+            let mut byte_stream = response.bytes_stream();
+            let mut buf: Vec<u8> = Vec::new();
+            'stream: while let Some(result) = byte_stream.next().await {
+                buf.extend_from_slice(&result?);
+                while let Some(pos) = buf.iter().position(|b| *b == b'\n') {
+                    let line: Vec<u8> = buf.drain(..=pos).collect();
+                    let line = String::from_utf8_lossy(&line);
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    if let Some(c) = &cfg.capture {
+                        c.raw(line.trim());
+                    }
+                    let json: Value = match serde_json::from_str(line.trim()) {
+                        Ok(v) => v,
+                        Err(e) => {
+                            tracing::debug!("Ollama stream line skipped: {e}");
+                            continue;
+                        }
+                    };
+                    if merge_chunk(&json, &mut acc, app, cfg.silent)? {
+                        break 'stream;
+                    }
+                }
+            }
+        } else {
+            let text = response.text().await?;
+            if let Some(c) = &cfg.capture {
+                c.raw(&text);
+            }
+            let json: Value = serde_json::from_str(&text)?;
+            merge_chunk(&json, &mut acc, app, cfg.silent)?;
+        }
+
+        if !cfg.silent {
+            if !acc.tool_calls.is_empty() {
+                if let Ok(tc_json) = serde_json::to_string(&acc.tool_calls) {
+                    emit_chunk(app, StreamChunkKind::ToolCall, tc_json);
+                }
+            }
+            emit_chunk(app, StreamChunkKind::Done, String::new());
+        }
+
+        tracing::info!(
+            content_len = acc.content.len(),
+            reasoning_len = acc.reasoning.len(),
+            tool_calls = acc.tool_calls.len(),
+            tools = ?acc.tool_calls.iter().map(|tc| tc.function.name.as_str()).collect::<Vec<_>>(),
+            "Ollama response complete"
+        );
+        Ok(acc)
+    }
+}
+
+/// Fold one `/api/chat` object into `acc`. Returns `true` on the final (`done`) object.
+fn merge_chunk(json: &Value, acc: &mut LlmResponse, app: &SharedEventSink, silent: bool) -> SeeClawResult<bool> {
+    if let Some(err) = json["error"].as_str() {
+        return Err(SeeClawError::LlmProvider(err.to_string()));
+    }
+    let message = &json["message"];
+    if let Some(thinking) = message["thinking"].as_str().filter(|s| !s.is_empty()) {
+        acc.reasoning.push_str(thinking);
+        emit_reasoning(app, thinking);
+    }
+    if let Some(content) = message["content"].as_str().filter(|s| !s.is_empty()) {
+        acc.content.push_str(content);
+        if !silent {
+            emit_chunk(app, StreamChunkKind::Content, content.to_string());
+        }
+    }
+    for tc in message["tool_calls"].as_array().into_iter().flatten() {
+        let name = tc["function"]["name"].as_str().unwrap_or("");
+        if name.is_empty() {
+            continue;
+        }
+        let arguments = match &tc["function"]["arguments"] {
+            Value::String(s) => s.clone(),
+            Value::Null => "{}".to_string(),
+            other => other.to_string(),
+        };
+        acc.tool_calls.push(ToolCall {
+            id: format!("ollama_call_{}", acc.tool_calls.len()),
+            call_type: "function".to_string(),
+            function: FunctionCall { name: name.to_string(), arguments },
+        });
+    }
+    let done = json["done"].as_bool().unwrap_or(false);
+    if done {
+        if let Some(prompt) = json["prompt_eval_count"].as_u64() {
+            acc.usage = Some(TokenUsage {
+                prompt_tokens: prompt,
+                completion_tokens: json["eval_count"].as_u64().unwrap_or(0),
+            });
+        }
+    }
+    Ok(done)
+}
+
+/// OpenAI-style messages → Ollama messages (`images` array, object tool arguments).
+fn convert_messages(messages: &[ChatMessage]) -> Vec<Value> {
+    let mut call_names: HashMap<&str, &str> = HashMap::new();
+    messages
+        .iter()
+        .map(|msg| {
+            let (text, images) = split_content(&msg.content);
+            let mut out = json!({ "role": msg.role, "content": text });
+            if !images.is_empty() {
+                out["images"] = json!(images);
+            }
+            if let Some(calls) = &msg.tool_calls {
+                let converted: Vec<Value> = calls
+                    .iter()
+                    .map(|tc| {
+                        call_names.insert(tc.id.as_str(), tc.function.name.as_str());
+                        let args: Value = serde_json::from_str(&tc.function.arguments).unwrap_or_else(|_| json!({}));
+                        json!({ "function": { "name": tc.function.name, "arguments": args } })
+                    })
+                    .collect();
+                out["tool_calls"] = json!(converted);
+            }
+            if msg.role == "tool" {
+                if let Some(name) = msg.tool_call_id.as_deref().and_then(|id| call_names.get(id)) {
+                    out["tool_name"] = json!(name);
+                }
+            }
+            out
+        })
+        .collect()
+}
+
+/// Text parts joined, plus base64 payloads of `data:` image URLs.
+fn split_content(content: &MessageContent) -> (String, Vec<String>) {
+    match content {
+        MessageContent::Text(t) => (t.clone(), Vec::new()),
+        MessageContent::Parts(parts) => {
+            let mut texts = Vec::new();
+            let mut images = Vec::new();
+            for part in parts {
+                match part {
+                    ContentPart::Text { text } => texts.push(text.clone()),
+                    ContentPart::ImageUrl { image_url } => {
+                        match image_url.url.split_once(";base64,") {
+                            Some((_, data)) => images.push(data.to_string()),
+                            None => tracing::warn!("Ollama: skipping non-inline image URL"),
+                        }
+                    }
+                }
+            }
+            (texts.join("\n"), images)
+        }
+    }
+}
+
+fn emit_chunk(app: &SharedEventSink, kind: StreamChunkKind, content: String) {
+    let _ = app.emit("llm_stream_chunk", &StreamChunk { kind, content });
+}
@@ -1,12 +1,18 @@
 use std::collections::BTreeMap;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use futures_util::StreamExt;
-use tauri::{AppHandle, Emitter};
+use rand::{Rng, SeedableRng};
 
+use crate::agent_engine::lang::GoalLanguage;
+use crate::config::RetryConfig;
 use crate::errors::{SeeClawError, SeeClawResult};
-use crate::llm::provider::LlmProvider;
+use crate::events::SharedEventSink;
+use crate::llm::provider::{emit_reasoning, guarded, LlmProvider};
 use crate::llm::sse_parser;
+use crate::llm::trace::CallCapture;
+use crate::net_audit::{self, NetPurpose};
 use crate::llm::types::{
     CallConfig, ChatMessage, FunctionCall, LlmResponse, StreamChunk, StreamChunkKind, ToolCall,
     ToolDef,
@@ -17,6 +23,14 @@ pub struct OpenAiCompatibleProvider {
     api_base: String,
     api_key: String,
     client: reqwest::Client,
+    /// Max resume attempts for an SSE stream that drops mid-response.
+    max_stream_resumes: u32,
+    /// Whether the API continues a trailing `"prefix": true` assistant message.
+    prefix_continuation: bool,
+    /// Backoff policy for rate limits and transient failures in `send`.
+    retry: RetryConfig,
+    /// Whether the API supports `response_format: json_schema`.
+    structured_output: bool,
 }
 
 impl OpenAiCompatibleProvider {
@@ -26,8 +40,53 @@ impl OpenAiCompatibleProvider {
             api_base,
             api_key,
             client: reqwest::Client::new(),
+            max_stream_resumes: 2,
+            prefix_continuation: false,
+            retry: RetryConfig::default(),
+            structured_output: false,
         }
     }
+
+    /// Declare support for schema-constrained output.
+    pub fn with_structured_output(mut self, supported: bool) -> Self {
+        self.structured_output = supported;
+        self
+    }
+
+    /// Configure retries for 429 / 5xx responses and connection failures.
+    pub fn with_retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Configure how interrupted SSE streams are resumed.
+    pub fn with_stream_resume(mut self, max_resumes: u32, prefix_continuation: bool) -> Self {
+        self.max_stream_resumes = max_resumes;
+        self.prefix_continuation = prefix_continuation;
+        self
+    }
+}
+
+/// How much of one call's answer has reached the UI, in bytes of the full
+/// response text. A resumed stream repeats what was already shown (from the
+/// start, or from the continuation prefix); only text past these offsets is
+/// forwarded again.
+#[derive(Debug, Default, Clone, Copy)]
+struct Emitted {
+    content: usize,
+    reasoning: usize,
+}
+
+/// Result of consuming an SSE stream.
+enum StreamOutcome {
+    Complete(LlmResponse),
+    /// The connection dropped, or the stream ended without a finish marker,
+    /// before the answer was complete; carries whatever arrived so far so
+    /// the caller can resume from it.
+    Interrupted {
+        partial: LlmResponse,
+        error: SeeClawError,
+    },
 }
 
 #[async_trait]
@@ -41,7 +100,20 @@ impl LlmProvider for OpenAiCompatibleProvider {
         messages: Vec<ChatMessage>,
         tools: Vec<ToolDef>,
         cfg: &CallConfig,
-        app: &AppHandle,
+        app: &SharedEventSink,
+    ) -> SeeClawResult<LlmResponse> {
+        guarded(&self.id, cfg, app, self.complete(messages, tools, cfg, app)).await
+    }
+}
+
+impl OpenAiCompatibleProvider {
+    /// The call itself; cancellation and the timeout are applied by `chat`.
+    async fn complete(
+        &self,
+        messages: Vec<ChatMessage>,
+        tools: Vec<ToolDef>,
+        cfg: &CallConfig,
+        app: &SharedEventSink,
     ) -> SeeClawResult<LlmResponse> {
         let mut body = serde_json::json!({
             "model": cfg.model,
@@ -49,16 +121,49 @@ impl LlmProvider for OpenAiCompatibleProvider {
             "stream": cfg.stream,
             "temperature": cfg.temperature,
         });
+        if cfg.stream {
+            body["stream_options"] = serde_json::json!({ "include_usage": true });
+        }
 
         if !tools.is_empty() {
             body["tools"] = serde_json::to_value(&tools)?;
             body["tool_choice"] = serde_json::json!("auto");
         }
 
-        if cfg.json_mode {
-            body["response_format"] = serde_json::json!({ "type": "json_object" });
+        match &cfg.response_schema {
+            Some(rs) if self.structured_output => {
+                body["response_format"] = serde_json::json!({
+                    "type": "json_schema",
+                    "json_schema": { "name": rs.name, "schema": rs.schema, "strict": true },
+                });
+            }
+            _ if cfg.json_mode => {
+                body["response_format"] = serde_json::json!({ "type": "json_object" });
+            }
+            _ => {}
         }
 
+        if let Some(seed) = cfg.seed {
+            body["seed"] = serde_json::json!(seed);
+        }
+        let params = &cfg.params;
+        if let Some(n) = params.max_tokens {
+            body["max_tokens"] = serde_json::json!(n);
+        }
+        if let Some(p) = params.top_p {
+            body["top_p"] = serde_json::json!(p);
+        }
+        if let Some(p) = params.frequency_penalty {
+            body["frequency_penalty"] = serde_json::json!(p);
+        }
+        if let Some(p) = params.presence_penalty {
+            body["presence_penalty"] = serde_json::json!(p);
+        }
+        if let Some(effort) = &params.reasoning_effort {
+            body["reasoning_effort"] = serde_json::json!(effort);
+        }
+        params.apply_extra_body(&mut body);
+
         tracing::debug!(
             provider = %self.id,
             model = %cfg.model,
@@ -111,37 +216,200 @@ impl LlmProvider for OpenAiCompatibleProvider {
             }
         }
 
-        let response = self
-            .client
-            .post(&self.api_base)
-            .bearer_auth(&self.api_key)
-            .json(&body)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let err_body = response.text().await.unwrap_or_default();
-            return Err(SeeClawError::LlmProvider(format!("{}: {}", status, err_body)));
+        let capture = cfg.capture.as_deref();
+        if let Some(c) = capture {
+            c.request(&body);
         }
 
-        if cfg.stream {
-            self.handle_stream(response, app, cfg.silent).await
-        } else {
-            self.handle_json(response, app, cfg.silent).await
+        if !cfg.stream {
+            let response = self.send(&body, cfg.seed, app).await?;
+            return self.handle_json(response, app, cfg.silent, capture).await;
+        }
+
+        // ── Streaming with resumption ────────────────────────────────────
+        // Content that arrived before one or more interruptions and was
+        // handed back to the API as a continuation prefix.
+        let mut carried = LlmResponse::default();
+        let mut request_body = body.clone();
+        let mut resumes = 0u32;
+        let mut emitted = Emitted::default();
+
+        loop {
+            let response = self.send(&request_body, cfg.seed, app).await?;
+            let base = Emitted { content: carried.content.len(), reasoning: carried.reasoning.len() };
+            match self.handle_stream(response, app, cfg.silent, capture, base, &mut emitted).await? {
+                StreamOutcome::Complete(mut resp) => {
+                    if !carried.content.is_empty() || !carried.reasoning.is_empty() {
+                        carried.content.push_str(&resp.content);
+                        carried.reasoning.push_str(&resp.reasoning);
+                        resp.content = carried.content;
+                        resp.reasoning = carried.reasoning;
+                    }
+                    return Ok(resp);
+                }
+                StreamOutcome::Interrupted { partial, error } => {
+                    if resumes >= self.max_stream_resumes {
+                        tracing::error!(
+                            provider = %self.id,
+                            resumes = resumes,
+                            error = %error,
+                            "SSE stream interrupted, resume attempts exhausted"
+                        );
+                        return Err(error);
+                    }
+                    resumes += 1;
+
+                    // Tool-call deltas cannot be continued reliably — only plain
+                    // content is handed back as a prefix.
+                    let can_continue = self.prefix_continuation
+                        && partial.tool_calls.is_empty()
+                        && !partial.content.is_empty();
+
+                    request_body = body.clone();
+                    if can_continue {
+                        carried.content.push_str(&partial.content);
+                        carried.reasoning.push_str(&partial.reasoning);
+                        if let Some(msgs) = request_body["messages"].as_array_mut() {
+                            msgs.push(serde_json::json!({
+                                "role": "assistant",
+                                "content": carried.content,
+                                "prefix": true,
+                            }));
+                        }
+                    } else {
+                        carried = LlmResponse::default();
+                    }
+
+                    tracing::warn!(
+                        provider = %self.id,
+                        attempt = resumes,
+                        max = self.max_stream_resumes,
+                        continuation = can_continue,
+                        partial_len = partial.content.len(),
+                        error = %error,
+                        "SSE stream interrupted, resuming"
+                    );
+                    if !cfg.silent {
+                        let _ = app.emit(
+                            "agent_activity",
+                            serde_json::json!({ "text": GoalLanguage::current().pick(
+                                "连接中断，正在恢复输出…",
+                                "Connection dropped, resuming the response…",
+                            ) }),
+                        );
+                    }
+                    tokio::time::sleep(std::time::Duration::from_millis(500 * resumes as u64)).await;
+                }
+            }
         }
     }
 }
 
 impl OpenAiCompatibleProvider {
+    /// POST the request body and fail on non-2xx status.
+    ///
+    /// Statuses in `retry.retry_on` and connection failures are retried with
+    /// exponential backoff and jitter, honouring `Retry-After`. Each retry is
+    /// reported as an `agent_activity` event — even for silent calls, since
+    /// the user otherwise sees an unexplained pause. `seed` (reproducibility
+    /// mode) makes the jitter deterministic.
+    async fn send(
+        &self,
+        body: &serde_json::Value,
+        seed: Option<u64>,
+        app: &SharedEventSink,
+    ) -> SeeClawResult<reqwest::Response> {
+        let max_attempts = self.retry.max_attempts.max(1);
+        let mut attempt = 1u32;
+        loop {
+            net_audit::record(&self.api_base, NetPurpose::Llm);
+            let result = self
+                .client
+                .post(&self.api_base)
+                .bearer_auth(&self.api_key)
+                .json(body)
+                .send()
+                .await;
+
+            let (error, retry_after) = match result {
+                Ok(response) if response.status().is_success() => return Ok(response),
+                Ok(response) => {
+                    let status = response.status();
+                    let retry_after =
+                        parse_retry_after(response.headers(), Duration::from_millis(self.retry.max_delay_ms));
+                    let err_body = response.text().await.unwrap_or_default();
+                    let error = if matches!(status.as_u16(), 408 | 504) {
+                        SeeClawError::ProviderTimeout(format!("{}: {}", status, err_body))
+                    } else {
+                        SeeClawError::LlmProvider(format!("{}: {}", status, err_body))
+                    };
+                    if !self.retry.retry_on.contains(&status.as_u16()) {
+                        return Err(error);
+                    }
+                    (error, retry_after)
+                }
+                Err(e) if e.is_connect() || e.is_timeout() => (e.into(), None),
+                Err(e) => return Err(e.into()),
+            };
+
+            if attempt >= max_attempts {
+                if max_attempts > 1 {
+                    tracing::error!(provider = %self.id, attempts = attempt, error = %error, "LLM request failed, retries exhausted");
+                }
+                return Err(error);
+            }
+
+            let delay = backoff_delay(&self.retry, attempt, retry_after, seed);
+            tracing::warn!(
+                provider = %self.id,
+                attempt,
+                max_attempts,
+                delay_ms = delay.as_millis() as u64,
+                error = %error,
+                "LLM request failed, retrying"
+            );
+            let _ = app.emit(
+                "agent_activity",
+                serde_json::json!({
+                    "text": GoalLanguage::current().pick(
+                        format!(
+                            "模型服务暂时不可用，{:.1} 秒后重试（{}/{}）…",
+                            delay.as_secs_f32(),
+                            attempt,
+                            max_attempts - 1
+                        ),
+                        format!(
+                            "Model service temporarily unavailable, retrying in {:.1}s ({}/{})…",
+                            delay.as_secs_f32(),
+                            attempt,
+                            max_attempts - 1
+                        ),
+                    )
+                }),
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
     /// Handle SSE streaming response.
     /// Streams chunks to the frontend (unless `silent`) and accumulates the full response.
+    /// A transport error mid-stream, or an end of stream with neither a
+    /// `finish_reason` nor `[DONE]`, yields `StreamOutcome::Interrupted` with
+    /// the partial response instead of discarding it.
+    ///
+    /// `base` is where this stream's text starts in the full response (the
+    /// continuation prefix); text before `emitted` was already forwarded by an
+    /// earlier attempt and is not sent again.
     async fn handle_stream(
         &self,
         response: reqwest::Response,
-        app: &AppHandle,
+        app: &SharedEventSink,
         silent: bool,
-    ) -> SeeClawResult<LlmResponse> {
+        capture: Option<&CallCapture>,
+        base: Emitted,
+        emitted: &mut Emitted,
+    ) -> SeeClawResult<StreamOutcome> {
         let mut byte_stream = response.bytes_stream();
         let mut line_buf = String::new();
 
@@ -149,10 +417,24 @@ impl OpenAiCompatibleProvider {
         let mut resp_reasoning = String::new();
         // Tool call accumulator: delta index → (id, type, name, accumulated_arguments)
         let mut tc_builders: BTreeMap<usize, (String, String, String, String)> = BTreeMap::new();
+        let mut usage = None;
         let mut done_emitted = false;
 
         'stream: while let Some(result) = byte_stream.next().await {
-            let bytes = result?;
+            let bytes = match result {
+                Ok(b) => b,
+                Err(e) => {
+                    return Ok(StreamOutcome::Interrupted {
+                        partial: LlmResponse {
+                            content: resp_content,
+                            reasoning: resp_reasoning,
+                            tool_calls: build_tool_calls(tc_builders),
+                            usage,
+                        },
+                        error: e.into(),
+                    });
+                }
+            };
             let text = String::from_utf8_lossy(&bytes);
 
             for ch in text.chars() {
@@ -163,6 +445,13 @@ impl OpenAiCompatibleProvider {
                     if line.is_empty() {
                         continue;
                     }
+                    if let Some(c) = capture {
+                        c.raw(&line);
+                    }
+
+                    if let Some(u) = sse_parser::parse_sse_usage(&line) {
+                        usage = Some(u);
+                    }
 
                     match sse_parser::parse_sse_line(&line) {
                         Ok(Some(chunk)) => {
@@ -171,10 +460,22 @@ impl OpenAiCompatibleProvider {
                             // Accumulate before forwarding to frontend
                             match &chunk.kind {
                                 StreamChunkKind::Reasoning => {
+                                    let start = base.reasoning + resp_reasoning.len();
                                     resp_reasoning.push_str(&chunk.content);
+                                    emit_reasoning(app, unsent(&chunk.content, start, &mut emitted.reasoning));
+                                    continue;
                                 }
                                 StreamChunkKind::Content => {
+                                    let start = base.content + resp_content.len();
                                     resp_content.push_str(&chunk.content);
+                                    let rest = unsent(&chunk.content, start, &mut emitted.content);
+                                    if !silent && !rest.is_empty() {
+                                        let _ = app.emit(
+                                            "llm_stream_chunk",
+                                            &StreamChunk { kind: StreamChunkKind::Content, content: rest.to_string() },
+                                        );
+                                    }
+                                    continue;
                                 }
                                 StreamChunkKind::ToolCall => {
                                     merge_tool_call_deltas(&chunk.content, &mut tc_builders);
@@ -182,13 +483,17 @@ impl OpenAiCompatibleProvider {
                                 _ => {}
                             }
 
-                            if !silent {
+                            if !silent && !(is_done && done_emitted) {
                                 let _ = app.emit("llm_stream_chunk", &chunk);
                             }
 
                             if is_done {
                                 done_emitted = true;
-                                break 'stream;
+                                // The usage chunk follows `finish_reason`;
+                                // read on until it (or `[DONE]`) arrives.
+                                if usage.is_some() || line == "data: [DONE]" {
+                                    break 'stream;
+                                }
                             }
                         }
                         Ok(None) => {}
@@ -202,17 +507,19 @@ impl OpenAiCompatibleProvider {
             }
         }
 
-        // Fallback Done in case stream ended without [DONE] marker
-        if !done_emitted && !silent {
-            let _ = app.emit(
-                "llm_stream_chunk",
-                &StreamChunk {
-                    kind: StreamChunkKind::Done,
-                    content: String::new(),
+        // Neither `finish_reason` nor `[DONE]`: the connection was closed
+        // mid-answer without a transport error.
+        if !done_emitted {
+            return Ok(StreamOutcome::Interrupted {
+                partial: LlmResponse {
+                    content: resp_content,
+                    reasoning: resp_reasoning,
+                    tool_calls: build_tool_calls(tc_builders),
+                    usage,
                 },
-            );
+                error: SeeClawError::SseParsing("stream ended before [DONE]; the response was truncated".into()),
+            });
         }
-
         let tool_calls = build_tool_calls(tc_builders);
 
         tracing::info!(
@@ -223,26 +530,40 @@ impl OpenAiCompatibleProvider {
             "LLM stream complete"
         );
 
-        Ok(LlmResponse {
+        Ok(StreamOutcome::Complete(LlmResponse {
             content: resp_content,
             reasoning: resp_reasoning,
             tool_calls,
-        })
+            usage,
+        }))
     }
 
     /// Handle a non-streaming JSON response.
     async fn handle_json(
         &self,
         response: reqwest::Response,
-        app: &AppHandle,
+        app: &SharedEventSink,
         silent: bool,
+        capture: Option<&CallCapture>,
     ) -> SeeClawResult<LlmResponse> {
-        let json: serde_json::Value = response.json().await?;
+        let text = response.text().await?;
+        if let Some(c) = capture {
+            c.raw(&text);
+        }
+        let json: serde_json::Value = serde_json::from_str(&text)?;
 
         let content = json["choices"][0]["message"]["content"]
             .as_str()
             .unwrap_or("")
             .to_string();
+        // DeepSeek / GLM / Qwen: `reasoning_content`; some gateways: `reasoning`.
+        let message = &json["choices"][0]["message"];
+        let reasoning = message["reasoning_content"]
+            .as_str()
+            .or_else(|| message["reasoning"].as_str())
+            .unwrap_or("")
+            .to_string();
+        emit_reasoning(app, &reasoning);
 
         let tool_calls: Vec<ToolCall> = json["choices"][0]["message"]["tool_calls"]
             .as_array()
@@ -265,6 +586,7 @@ impl OpenAiCompatibleProvider {
 
         tracing::info!(
             content_len = content.len(),
+            reasoning_len = reasoning.len(),
             tool_calls = tool_calls.len(),
             "LLM JSON response received"
         );
@@ -301,12 +623,28 @@ impl OpenAiCompatibleProvider {
 
         Ok(LlmResponse {
             content,
-            reasoning: String::new(),
+            reasoning,
             tool_calls,
+            usage: sse_parser::parse_usage(&json["usage"]),
         })
     }
 }
 
+/// The part of `text`, which starts at byte `start` of the full response,
+/// that lies past `sent`; advances `sent` over it.
+fn unsent<'a>(text: &'a str, start: usize, sent: &mut usize) -> &'a str {
+    let end = start + text.len();
+    if end <= *sent {
+        return "";
+    }
+    let mut skip = sent.saturating_sub(start);
+    while !text.is_char_boundary(skip) {
+        skip += 1;
+    }
+    *sent = end;
+    &text[skip..]
+}
+
 /// Merge streaming tool-call delta fragments into the accumulator map (keyed by delta index).
 fn merge_tool_call_deltas(
     chunk_content: &str,
@@ -358,3 +696,112 @@ fn build_tool_calls(
         })
         .collect()
 }
+
+/// Delay before retry number `attempt` (1-based): the server's `Retry-After`
+/// when given, otherwise `base * 2^(attempt-1)` with equal jitter, both capped
+/// at `max_delay_ms`. With a `seed` the jitter depends only on the seed and
+/// the attempt, so a reproducibility run waits the same on every replay.
+fn backoff_delay(retry: &RetryConfig, attempt: u32, retry_after: Option<Duration>, seed: Option<u64>) -> Duration {
+    let cap = Duration::from_millis(retry.max_delay_ms);
+    if let Some(wait) = retry_after {
+        return wait.min(cap);
+    }
+    let exp = retry
+        .base_delay_ms
+        .saturating_mul(1u64 << (attempt - 1).min(20))
+        .min(retry.max_delay_ms);
+    let half = exp / 2;
+    // Jitter spreads out clients that were rate-limited at the same moment.
+    let random = match seed {
+        Some(seed) => rand::rngs::StdRng::seed_from_u64(seed ^ u64::from(attempt)).gen::<u64>(),
+        None => rand::thread_rng().gen::<u64>(),
+    };
+    let jitter = random % (half + 1);
+    Duration::from_millis(half + jitter)
+}
+
+/// `Retry-After` as delta-seconds or an HTTP date, capped at `max`.
+/// Negative, NaN and out-of-range values are ignored.
+fn parse_retry_after(headers: &reqwest::header::HeaderMap, max: Duration) -> Option<Duration> {
+    let value = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?.trim();
+    let wait = match value.parse::<f64>() {
+        Ok(secs) => Duration::try_from_secs_f64(secs).ok()?,
+        Err(_) => {
+            let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+            (at.with_timezone(&chrono::Utc) - chrono::Utc::now()).to_std().ok()?
+        }
+    };
+    Some(wait.min(max))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsent_skips_text_already_streamed() {
+        let mut sent = 0;
+        assert_eq!(unsent("Hello", 0, &mut sent), "Hello");
+        assert_eq!(sent, 5);
+        // A resumed stream repeats the start of the response.
+        assert_eq!(unsent("Hello world", 0, &mut sent), " world");
+        assert_eq!(sent, 11);
+        assert_eq!(unsent("Hello", 0, &mut sent), "");
+        assert_eq!(sent, 11);
+    }
+
+    #[test]
+    fn test_unsent_with_offset_and_multibyte_text() {
+        let mut sent = 8;
+        assert_eq!(unsent("world", 6, &mut sent), "rld");
+        assert_eq!(sent, 11);
+        // `sent` inside a multi-byte character moves on to the next boundary.
+        let mut sent = 2;
+        assert_eq!(unsent("héllo", 0, &mut sent), "llo");
+        assert_eq!(sent, 6);
+    }
+
+    fn retry() -> RetryConfig {
+        RetryConfig { base_delay_ms: 1_000, max_delay_ms: 30_000, ..RetryConfig::default() }
+    }
+
+    #[test]
+    fn test_backoff_grows_with_jitter_and_cap() {
+        let cfg = retry();
+        for attempt in 1..=4 {
+            let exp = 1_000u64 << (attempt - 1);
+            let ms = backoff_delay(&cfg, attempt, None, None).as_millis() as u64;
+            assert!((exp / 2..=exp).contains(&ms), "attempt {attempt}: {ms}ms");
+        }
+        assert!(backoff_delay(&cfg, 40, None, None) <= Duration::from_millis(30_000));
+    }
+
+    #[test]
+    fn test_backoff_honours_retry_after_and_seed() {
+        let cfg = retry();
+        assert_eq!(backoff_delay(&cfg, 1, Some(Duration::from_secs(3)), None), Duration::from_secs(3));
+        assert_eq!(backoff_delay(&cfg, 1, Some(Duration::from_secs(90)), None), Duration::from_secs(30));
+        assert_eq!(backoff_delay(&cfg, 2, None, Some(7)), backoff_delay(&cfg, 2, None, Some(7)));
+    }
+
+    fn retry_after(value: &str) -> Option<Duration> {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(reqwest::header::RETRY_AFTER, value.parse().unwrap());
+        parse_retry_after(&headers, Duration::from_secs(30))
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(retry_after("5"), Some(Duration::from_secs(5)));
+        assert_eq!(retry_after(" 1.5 "), Some(Duration::from_millis(1_500)));
+        assert_eq!(retry_after("3600"), Some(Duration::from_secs(30)));
+        assert_eq!(retry_after("inf"), None);
+        assert_eq!(retry_after("NaN"), None);
+        assert_eq!(retry_after("-1"), None);
+        assert_eq!(retry_after("1e300"), None);
+        assert_eq!(retry_after("soon"), None);
+        // An HTTP date in the past means "now", which is not a wait.
+        assert_eq!(retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+        assert_eq!(parse_retry_after(&reqwest::header::HeaderMap::new(), Duration::from_secs(30)), None);
+    }
+}
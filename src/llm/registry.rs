@@ -1,12 +1,21 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
+
+use tokio_util::sync::CancellationToken;
 
 use crate::config::AppConfig;
 use crate::errors::{SeeClawError, SeeClawResult};
+use crate::llm::failover::{ChainEntry, CircuitBreaker, FailoverProvider, DEFAULT_CIRCUIT_COOLDOWN_SECS};
 use crate::llm::provider::LlmProvider;
+use crate::llm::providers::gemini::GeminiProvider;
+use crate::llm::providers::ollama::OllamaProvider;
 use crate::llm::providers::openai_compatible::OpenAiCompatibleProvider;
+use crate::llm::repro::{RecordingProvider, ReplayLog, ReplayProvider, ReproRecorder};
+use crate::llm::tokens::DEFAULT_CONTEXT_TOKENS;
+use crate::llm::trace::{LlmTrace, TracingProvider};
 use crate::llm::types::CallConfig;
-use crate::config::LlmConfig;
+use crate::config::{LlmConfig, RequestParams};
 
 /// Registry of all available LLM providers, keyed by their config.toml identifier.
 pub struct ProviderRegistry {
@@ -14,6 +23,12 @@ pub struct ProviderRegistry {
     active: String,
     /// Kept for role-to-model lookups (does not need to be mutable after init).
     llm_config: LlmConfig,
+    /// Set in reproducibility mode: pins temperature/seed and logs every call.
+    recorder: Option<Arc<ReproRecorder>>,
+    /// Log being replayed instead of calling providers (`seeclaw-cli replay`).
+    replay: Option<Arc<ReplayLog>>,
+    /// Failed-provider state shared by every role's failover chain.
+    breaker: Arc<CircuitBreaker>,
 }
 
 impl ProviderRegistry {
@@ -22,6 +37,9 @@ impl ProviderRegistry {
             providers: HashMap::new(),
             active,
             llm_config: LlmConfig::default(),
+            recorder: None,
+            replay: None,
+            breaker: Arc::new(CircuitBreaker::new(Duration::from_secs(DEFAULT_CIRCUIT_COOLDOWN_SECS))),
         }
     }
 
@@ -52,7 +70,8 @@ impl ProviderRegistry {
     /// Return the provider and call configuration for a named agent role.
     ///
     /// Role resolution order:
-    /// 1. `[llm.roles.<role>]` in config.toml
+    /// 1. `[llm.roles.<role>]` in config.toml — wrapped in a [`FailoverProvider`]
+    ///    when the role lists a `fallback` chain
     /// 2. Fallback: active provider with its default model / temperature and `stream = true`
     pub fn call_config_for_role(&self, role: &str) -> SeeClawResult<(Arc<dyn LlmProvider>, CallConfig)> {
         let role_entry = match role {
@@ -67,12 +86,17 @@ impl ProviderRegistry {
         };
 
         if let Some(entry) = role_entry {
-            let provider = self.providers.get(&entry.provider).cloned().ok_or_else(|| {
+            let mut provider = self.providers.get(&entry.provider).cloned().ok_or_else(|| {
                 SeeClawError::Config(format!(
                     "Role '{}' references unknown provider '{}'",
                     role, entry.provider
                 ))
             })?;
+            if !entry.fallback.is_empty() {
+                let mut chain = vec![ChainEntry { provider, model: entry.model.clone() }];
+                chain.extend(entry.fallback.iter().filter_map(|f| self.chain_entry(role, f)));
+                provider = Arc::new(FailoverProvider::new(role.to_string(), chain, self.breaker.clone()));
+            }
             let temperature = entry.temperature.unwrap_or_else(|| {
                 self.llm_config
                     .providers
@@ -88,13 +112,20 @@ impl ProviderRegistry {
                 temperature = temperature,
                 "resolved role config"
             );
-            return Ok((provider, CallConfig {
+            return Ok((provider, self.pin(CallConfig {
                 model: entry.model.clone(),
                 stream: entry.stream,
                 temperature,
                 silent: false,
                 json_mode: false,
-            }));
+                response_schema: None,
+                seed: None,
+                context_tokens: entry.context_tokens.unwrap_or(DEFAULT_CONTEXT_TOKENS),
+                cancel: CancellationToken::new(),
+                timeout: entry.timeout_secs.filter(|s| *s > 0).map(Duration::from_secs),
+                capture: None,
+                params: entry.params.clone(),
+            })));
         }
 
         // Fallback: active provider, provider-level defaults
@@ -109,7 +140,137 @@ impl ProviderRegistry {
             model = %model,
             "role not configured, using active provider fallback"
         );
-        Ok((provider, CallConfig { model, stream: true, temperature, silent: false, json_mode: false }))
+        Ok((provider, self.pin(CallConfig {
+            model,
+            stream: true,
+            temperature,
+            silent: false,
+            json_mode: false,
+            response_schema: None,
+            seed: None,
+            context_tokens: DEFAULT_CONTEXT_TOKENS,
+            cancel: CancellationToken::new(),
+            timeout: None,
+            capture: None,
+            params: RequestParams::default(),
+        })))
+    }
+
+    /// Provider `id` on its own (no failover), with the model `role` uses on
+    /// it when that role is mapped to this provider, else the provider's
+    /// default model. Used by connection tests.
+    pub fn call_config_for_provider(&self, id: &str, role: Option<&str>) -> SeeClawResult<(Arc<dyn LlmProvider>, CallConfig)> {
+        let provider = self
+            .providers
+            .get(id)
+            .cloned()
+            .ok_or_else(|| SeeClawError::Config(format!("unknown provider '{id}' — add it under [llm.providers.{id}]")))?;
+        let role_entry = role
+            .and_then(|r| match r {
+                "routing" => self.llm_config.roles.routing.as_ref(),
+                "chat" => self.llm_config.roles.chat.as_ref(),
+                "tools" => self.llm_config.roles.tools.as_ref(),
+                "vision" => self.llm_config.roles.vision.as_ref(),
+                _ => None,
+            })
+            .filter(|e| e.provider == id);
+        let defaults = self.llm_config.providers.get(id);
+        let model = role_entry
+            .map(|e| e.model.clone())
+            .or_else(|| defaults.map(|p| p.model.clone()))
+            .unwrap_or_default();
+        let temperature = role_entry
+            .and_then(|e| e.temperature)
+            .or_else(|| defaults.map(|p| p.temperature))
+            .unwrap_or(0.1);
+        Ok((provider, CallConfig {
+            model,
+            stream: false,
+            temperature,
+            silent: true,
+            json_mode: false,
+            response_schema: None,
+            seed: None,
+            context_tokens: DEFAULT_CONTEXT_TOKENS,
+            cancel: CancellationToken::new(),
+            timeout: None,
+            capture: None,
+            params: role_entry.map(|e| e.params.clone()).unwrap_or_default(),
+        }))
+    }
+
+    /// Resolve one `fallback` item: `"<provider>"` or `"<provider>:<model>"`.
+    fn chain_entry(&self, role: &str, spec: &str) -> Option<ChainEntry> {
+        let (id, model) = match spec.split_once(':') {
+            Some((id, model)) => (id.trim(), Some(model.trim().to_string())),
+            None => (spec.trim(), None),
+        };
+        let Some(provider) = self.providers.get(id).cloned() else {
+            tracing::warn!(role, provider = id, "fallback references unknown provider, skipped");
+            return None;
+        };
+        let model = model
+            .filter(|m| !m.is_empty())
+            .or_else(|| self.llm_config.providers.get(id).map(|p| p.model.clone()))
+            .unwrap_or_default();
+        Some(ChainEntry { provider, model })
+    }
+
+    /// Planner conversation size that triggers compaction (0 = disabled).
+    pub fn compact_after_tokens(&self) -> usize {
+        self.llm_config.compact_after_tokens
+    }
+
+    /// Decision recorder, present only in reproducibility mode.
+    pub fn recorder(&self) -> Option<Arc<ReproRecorder>> {
+        self.recorder.clone()
+    }
+
+    /// Whether LLM calls are answered from a replayed repro log.
+    pub fn replaying(&self) -> bool {
+        self.replay.is_some()
+    }
+
+    /// Apply reproducibility overrides (temperature 0, fixed seed) if enabled.
+    fn pin(&self, mut cfg: CallConfig) -> CallConfig {
+        if let Some(rec) = &self.recorder {
+            cfg.temperature = 0.0;
+            cfg.seed = Some(rec.seed());
+        }
+        cfg
+    }
+
+    /// `(provider id, api_base)` of every configured provider with an endpoint.
+    pub fn endpoints(&self) -> Vec<(String, String)> {
+        let mut out: Vec<(String, String)> = self
+            .llm_config
+            .providers
+            .iter()
+            .filter(|(_, p)| !p.api_base.is_empty())
+            .map(|(id, p)| (id.clone(), p.api_base.clone()))
+            .collect();
+        out.sort();
+        out
+    }
+
+    /// Return the API endpoint that serves `role`, following the same
+    /// resolution order as [`Self::call_config_for_role`].
+    pub fn api_base_for_role(&self, role: &str) -> Option<String> {
+        let provider_id = match role {
+            "routing" => self.llm_config.roles.routing.as_ref(),
+            "chat"    => self.llm_config.roles.chat.as_ref(),
+            "tools"   => self.llm_config.roles.tools.as_ref(),
+            "vision"  => self.llm_config.roles.vision.as_ref(),
+            _ => None,
+        }
+        .map(|entry| entry.provider.as_str())
+        .unwrap_or(self.active.as_str());
+
+        self.llm_config
+            .providers
+            .get(provider_id)
+            .map(|p| p.api_base.clone())
+            .filter(|url| !url.is_empty())
     }
 
     /// Build a registry from the loaded app config.
@@ -119,24 +280,69 @@ impl ProviderRegistry {
             providers: HashMap::new(),
             active: config.llm.active_provider.clone(),
             llm_config: config.llm.clone(),
+            recorder: None,
+            replay: None,
+            breaker: Arc::new(CircuitBreaker::new(Duration::from_secs(config.llm.circuit_cooldown_secs))),
         };
+        if config.reproducibility.enabled {
+            match ReproRecorder::create(config.reproducibility.seed) {
+                Ok(rec) => {
+                    tracing::info!(path = %rec.path().display(), seed = rec.seed(), "reproducibility mode on");
+                    registry.recorder = Some(Arc::new(rec));
+                }
+                Err(e) => tracing::warn!(error = %e, "failed to open reproducibility log; mode disabled"),
+            }
+        }
+        if let Some(path) = config.reproducibility.replay.as_deref() {
+            match ReplayLog::load(std::path::Path::new(path), config.reproducibility.replay_task) {
+                Ok(log) => {
+                    tracing::info!(path, task = config.reproducibility.replay_task, "replaying reproducibility log");
+                    registry.replay = Some(Arc::new(log));
+                }
+                Err(e) => tracing::warn!(error = %e, path, "failed to load replay log; calling providers instead"),
+            }
+        }
+        crate::llm::usage::set_pricing(config.llm.pricing.clone());
+        let trace = if config.llm.trace {
+            match LlmTrace::create() {
+                Ok(trace) => {
+                    tracing::info!(path = %trace.path().display(), "LLM trace mode on");
+                    Some(Arc::new(trace))
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, "failed to open LLM trace file; trace mode disabled");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let recorder = registry.recorder.clone();
         for (id, entry) in &config.llm.providers {
             // UI config key takes highest priority; fall back to env var only when unset
-            let api_key = entry
-                .api_key
-                .as_deref()
-                .filter(|k| !k.is_empty())
-                .map(|k| k.to_string())
-                .unwrap_or_else(|| {
-                    std::env::var(format!("SEECLAW_{}_API_KEY", id.to_uppercase()))
-                        .unwrap_or_default()
-                });
-            let provider = OpenAiCompatibleProvider::new(
-                id.clone(),
-                entry.api_base.clone(),
-                api_key,
-            );
-            registry.register(Arc::new(provider));
+            let api_key = entry.resolve_api_key(id);
+            let provider: Arc<dyn LlmProvider> = match entry.adapter.as_deref() {
+                Some("gemini") => Arc::new(GeminiProvider::new(id.clone(), entry.api_base.clone(), api_key)),
+                Some("ollama") => Arc::new(OllamaProvider::new(id.clone(), entry.api_base.clone())),
+                _ => Arc::new(
+                    OpenAiCompatibleProvider::new(id.clone(), entry.api_base.clone(), api_key)
+                        .with_stream_resume(entry.max_stream_resumes, entry.prefix_continuation)
+                        .with_structured_output(entry.structured_output)
+                        .with_retry(entry.retry.clone()),
+                ),
+            };
+            let provider: Arc<dyn LlmProvider> = match &registry.replay {
+                Some(log) => Arc::new(ReplayProvider::new(id.clone(), log.clone())),
+                None => provider,
+            };
+            let provider: Arc<dyn LlmProvider> = match &trace {
+                Some(trace) => Arc::new(TracingProvider::new(provider, trace.clone())),
+                None => provider,
+            };
+            match &recorder {
+                Some(rec) => registry.register(Arc::new(RecordingProvider::new(provider, rec.clone()))),
+                None => registry.register(provider),
+            }
         }
         registry
     }
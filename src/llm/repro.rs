@@ -0,0 +1,298 @@
+//! Reproducibility mode — decision log for replaying a task.
+//!
+//! When `[reproducibility] enabled = true`, every provider in the registry is
+//! wrapped in a [`RecordingProvider`], temperatures are pinned to 0 and the
+//! configured seed is sent with each request. Every LLM exchange and every
+//! non-LLM choice the agent makes (grounding, retries, …) is appended to a
+//! JSONL file under `SeeClaw/repro/`, in order, so a run can be replayed
+//! decision-for-decision.
+//!
+//! Screenshots are not inlined: each image is written once to a folder next
+//! to the log (`repro_<timestamp>_images/<sha256>.<ext>`) and referenced by
+//! hash. Every LLM call also records a hash of its request, images included.
+//!
+//! `seeclaw-cli replay <log>` sets `[reproducibility] replay`, which swaps
+//! every provider for a [`ReplayProvider`] answering from the log. A request
+//! whose hash differs from the recorded one (the screen looked different) is
+//! reported as a divergence and still answered with the recorded response.
+
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use base64::Engine as _;
+use sha2::{Digest, Sha256};
+
+use crate::agent_engine::history::data_dir_or_cwd;
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::events::SharedEventSink;
+use crate::llm::provider::{emit_reasoning, guarded, LlmProvider};
+use crate::llm::types::{CallConfig, ChatMessage, LlmResponse, StreamChunk, StreamChunkKind, ToolDef};
+
+/// Prefix of an image reference in a logged request.
+const IMAGE_REF_PREFIX: &str = "repro-image:";
+
+pub struct ReproRecorder {
+    seed: u64,
+    path: PathBuf,
+    images_dir: PathBuf,
+    seq: AtomicU64,
+    file: Mutex<std::fs::File>,
+}
+
+impl ReproRecorder {
+    /// Open a new log file `repro_<timestamp>.jsonl` and its image folder.
+    pub fn create(seed: u64) -> SeeClawResult<Self> {
+        let dir = data_dir_or_cwd("repro");
+        let stem = format!("repro_{}", chrono::Utc::now().format("%Y%m%d_%H%M%S"));
+        let path = dir.join(format!("{stem}.jsonl"));
+        let images_dir = dir.join(format!("{stem}_images"));
+        std::fs::create_dir_all(&images_dir)?;
+        let file = std::fs::OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self { seed, path, images_dir, seq: AtomicU64::new(0), file: Mutex::new(file) })
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Mark the start of a new goal.
+    pub fn begin_task(&self, goal: &str) {
+        self.write("task_started", serde_json::json!({ "goal": goal, "seed": self.seed }));
+    }
+
+    /// Record a non-LLM decision (element tie-break, retry delay, …).
+    pub fn record_choice(&self, kind: &str, value: serde_json::Value) {
+        self.write("choice", serde_json::json!({ "kind": kind, "value": value }));
+    }
+
+    fn write(&self, record: &str, mut payload: serde_json::Value) {
+        payload["record"] = serde_json::json!(record);
+        payload["seq"] = serde_json::json!(self.seq.fetch_add(1, Ordering::SeqCst));
+        payload["ts"] = serde_json::json!(chrono::Utc::now().timestamp_millis());
+        let Ok(mut file) = self.file.lock() else { return };
+        if let Err(e) = writeln!(file, "{payload}") {
+            tracing::warn!(error = %e, "failed to write reproducibility record");
+        }
+    }
+}
+
+/// Provider wrapper that logs each request/response pair.
+pub struct RecordingProvider {
+    inner: Arc<dyn LlmProvider>,
+    recorder: Arc<ReproRecorder>,
+}
+
+impl RecordingProvider {
+    pub fn new(inner: Arc<dyn LlmProvider>, recorder: Arc<ReproRecorder>) -> Self {
+        Self { inner, recorder }
+    }
+}
+
+#[async_trait]
+impl LlmProvider for RecordingProvider {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn chat(
+        &self,
+        messages: Vec<ChatMessage>,
+        tools: Vec<ToolDef>,
+        cfg: &CallConfig,
+        app: &SharedEventSink,
+    ) -> SeeClawResult<LlmResponse> {
+        let logged = image_refs(serde_json::to_value(&messages).unwrap_or_default(), Some(&self.recorder.images_dir));
+        let request = serde_json::json!({
+            "provider": self.inner.name(),
+            "model": cfg.model,
+            "temperature": cfg.temperature,
+            "seed": cfg.seed,
+            "json_mode": cfg.json_mode,
+            "response_schema": cfg.response_schema.as_ref().map(|s| s.name),
+            "request_hash": request_hash(&logged),
+            "messages": logged,
+            "tools": tools.iter().map(|t| t.function.name.as_str()).collect::<Vec<_>>(),
+        });
+        let result = self.inner.chat(messages, tools, cfg, app).await;
+        let outcome = match &result {
+            Ok(resp) => serde_json::json!({ "response": resp }),
+            Err(e) => serde_json::json!({ "error": e.to_string() }),
+        };
+        self.recorder.write("llm_call", serde_json::json!({ "request": request, "outcome": outcome }));
+        result
+    }
+}
+
+/// Replace inline base64 images with `repro-image:<sha256>.<ext>`. With
+/// `store`, each image is also written to that folder (once per hash).
+fn image_refs(mut messages: serde_json::Value, store: Option<&Path>) -> serde_json::Value {
+    if let Some(msgs) = messages.as_array_mut() {
+        for part in msgs
+            .iter_mut()
+            .filter_map(|m| m.get_mut("content").and_then(|c| c.as_array_mut()))
+            .flatten()
+        {
+            if let Some(url) = part.pointer_mut("/image_url/url") {
+                if let Some(reference) = url.as_str().and_then(|u| image_ref(u, store)) {
+                    *url = serde_json::json!(reference);
+                }
+            }
+        }
+    }
+    messages
+}
+
+fn image_ref(data_url: &str, store: Option<&Path>) -> Option<String> {
+    let (header, data) = data_url.strip_prefix("data:")?.split_once(";base64,")?;
+    let bytes = base64::engine::general_purpose::STANDARD.decode(data).ok()?;
+    let ext = header.strip_prefix("image/").unwrap_or("bin");
+    let name = format!("{:x}.{ext}", Sha256::digest(&bytes));
+    if let Some(dir) = store {
+        let path = dir.join(&name);
+        if !path.exists() {
+            if let Err(e) = std::fs::write(&path, &bytes) {
+                tracing::warn!(error = %e, path = %path.display(), "failed to store reproducibility image");
+            }
+        }
+    }
+    Some(format!("{IMAGE_REF_PREFIX}{name}"))
+}
+
+/// Hash of a request's messages after `image_refs`.
+fn request_hash(messages: &serde_json::Value) -> String {
+    format!("{:x}", Sha256::digest(messages.to_string().as_bytes()))
+}
+
+// ── Replay ─────────────────────────────────────────────────────────────────
+
+/// One recorded task: its goal and LLM calls in order.
+pub struct ReplayLog {
+    pub goal: String,
+    calls: Mutex<VecDeque<serde_json::Value>>,
+    total: usize,
+}
+
+impl ReplayLog {
+    /// Load task number `task` (1-based) from a repro log.
+    pub fn load(path: &Path, task: usize) -> SeeClawResult<Self> {
+        let text = std::fs::read_to_string(path)?;
+        let mut records: Vec<serde_json::Value> = text
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .filter_map(|l| serde_json::from_str(l).ok())
+            .collect();
+        records.sort_by_key(|r| r["seq"].as_u64().unwrap_or(0));
+
+        let mut tasks: Vec<(String, VecDeque<serde_json::Value>)> = Vec::new();
+        for record in records {
+            match record["record"].as_str() {
+                Some("task_started") => {
+                    tasks.push((record["goal"].as_str().unwrap_or_default().to_string(), VecDeque::new()));
+                }
+                Some("llm_call") => {
+                    if let Some((_, calls)) = tasks.last_mut() {
+                        calls.push_back(record);
+                    }
+                }
+                _ => {}
+            }
+        }
+        let count = tasks.len();
+        let (goal, calls) = task
+            .checked_sub(1)
+            .and_then(|i| tasks.into_iter().nth(i))
+            .ok_or_else(|| SeeClawError::Config(format!("{} has {count} task(s), no task {task}", path.display())))?;
+        Ok(Self { goal, total: calls.len(), calls: Mutex::new(calls) })
+    }
+
+    /// Next recorded call and its 1-based position.
+    fn next(&self) -> Option<(usize, serde_json::Value)> {
+        let mut calls = self.calls.lock().unwrap_or_else(|e| e.into_inner());
+        let call = calls.pop_front()?;
+        Some((self.total - calls.len(), call))
+    }
+}
+
+/// Provider that answers every call with the next response from a
+/// [`ReplayLog`]. All providers of a registry share one log, since the
+/// recorded order spans roles.
+pub struct ReplayProvider {
+    id: String,
+    log: Arc<ReplayLog>,
+}
+
+impl ReplayProvider {
+    pub fn new(id: String, log: Arc<ReplayLog>) -> Self {
+        Self { id, log }
+    }
+
+    async fn answer(
+        &self,
+        messages: Vec<ChatMessage>,
+        cfg: &CallConfig,
+        app: &SharedEventSink,
+    ) -> SeeClawResult<LlmResponse> {
+        let Some((n, call)) = self.log.next() else {
+            return Err(SeeClawError::LlmProvider(format!(
+                "replay log exhausted after {} calls",
+                self.log.total
+            )));
+        };
+        let hash = request_hash(&image_refs(serde_json::to_value(&messages).unwrap_or_default(), None));
+        if call["request"]["request_hash"].as_str() != Some(hash.as_str()) {
+            tracing::warn!(call = n, provider = %self.id, "replay diverged: request differs from the recorded one");
+            let _ = app.emit("agent_activity", serde_json::json!({
+                "text": format!("Replay diverged at LLM call {n}: the request differs from the recording"),
+            }));
+        }
+
+        let outcome = &call["outcome"];
+        if let Some(error) = outcome["error"].as_str() {
+            return Err(SeeClawError::LlmProvider(format!("replayed error: {error}")));
+        }
+        let response: LlmResponse = serde_json::from_value(outcome["response"].clone())
+            .map_err(|e| SeeClawError::LlmProvider(format!("replay call {n}: bad recorded response: {e}")))?;
+
+        emit_reasoning(app, &response.reasoning);
+        if !cfg.silent {
+            if !response.content.is_empty() {
+                let _ = app.emit(
+                    "llm_stream_chunk",
+                    &StreamChunk { kind: StreamChunkKind::Content, content: response.content.clone() },
+                );
+            }
+            if !response.tool_calls.is_empty() {
+                if let Ok(tc_json) = serde_json::to_string(&response.tool_calls) {
+                    let _ = app.emit("llm_stream_chunk", &StreamChunk { kind: StreamChunkKind::ToolCall, content: tc_json });
+                }
+            }
+            let _ = app.emit("llm_stream_chunk", &StreamChunk { kind: StreamChunkKind::Done, content: String::new() });
+        }
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl LlmProvider for ReplayProvider {
+    fn name(&self) -> &str {
+        &self.id
+    }
+
+    async fn chat(
+        &self,
+        messages: Vec<ChatMessage>,
+        _tools: Vec<ToolDef>,
+        cfg: &CallConfig,
+        app: &SharedEventSink,
+    ) -> SeeClawResult<LlmResponse> {
+        guarded(&self.id, cfg, app, self.answer(messages, cfg, app)).await
+    }
+}
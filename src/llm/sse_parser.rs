@@ -1,5 +1,5 @@
 use crate::errors::{SeeClawError, SeeClawResult};
-use crate::llm::types::{StreamChunk, StreamChunkKind};
+use crate::llm::types::{StreamChunk, StreamChunkKind, TokenUsage};
 
 /// Parses a raw SSE line (OpenAI-compatible format) into a StreamChunk.
 /// Returns None if the line is a keep-alive or non-data line.
@@ -72,3 +72,24 @@ pub fn parse_sse_line(line: &str) -> SeeClawResult<Option<StreamChunk>> {
 
     Ok(None)
 }
+
+/// Token usage carried by an SSE line, if any. With
+/// `stream_options.include_usage` it arrives in a final chunk after the one
+/// with `finish_reason`; some providers put it on the `finish_reason` chunk.
+pub fn parse_sse_usage(line: &str) -> Option<TokenUsage> {
+    let data = line.strip_prefix("data: ")?.trim();
+    if !data.contains("\"usage\"") {
+        return None;
+    }
+    let json: serde_json::Value = serde_json::from_str(data).ok()?;
+    parse_usage(&json["usage"])
+}
+
+/// OpenAI-style `usage` object.
+pub fn parse_usage(usage: &serde_json::Value) -> Option<TokenUsage> {
+    let prompt_tokens = usage["prompt_tokens"].as_u64()?;
+    Some(TokenUsage {
+        prompt_tokens,
+        completion_tokens: usage["completion_tokens"].as_u64().unwrap_or(0),
+    })
+}
@@ -0,0 +1,175 @@
+//! Structured output — JSON schemas for replies the engine parses itself.
+//!
+//! Setting `CallConfig::response_schema` asks the provider for constrained
+//! decoding: `response_format: json_schema` on OpenAI-compatible APIs that
+//! declare `structured_output = true`, `responseJsonSchema` on Gemini and
+//! `format` on Ollama. Providers without support fall back to plain JSON mode
+//! (when `json_mode` is set) and the prompt's instructions, so replies are
+//! still read with the tolerant [`parse_json_reply`].
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// A named JSON schema for one kind of reply.
+#[derive(Debug, Clone)]
+pub struct ResponseSchema {
+    pub name: &'static str,
+    pub schema: Value,
+}
+
+/// Reply to the `vlm_grid` prompt: a SoM grid cell such as "C4".
+#[derive(Debug, Clone, Deserialize)]
+pub struct GridLocation {
+    pub cell: Option<String>,
+    pub found: bool,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// Reply to the `vlm_annotated` prompt: a detected element id.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AnnotatedLocation {
+    pub element_id: Option<String>,
+    pub found: bool,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// Reply to the focus-crop prompt: a point in the zoomed crop.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CropPoint {
+    pub found: bool,
+    pub x: f32,
+    pub y: f32,
+}
+
+/// Reply to the click-check prompt.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClickCheck {
+    pub activated: bool,
+    #[serde(default)]
+    pub reason: String,
+}
+
+/// Reply to the `verifier` prompt.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Verification {
+    pub pass: bool,
+    #[serde(default)]
+    pub reason: String,
+    #[serde(default)]
+    pub evidence: String,
+}
+
+/// Reply to the `visual_router` prompt.
+#[derive(Debug, Clone, Deserialize)]
+pub struct VisualDecision {
+    pub needs_visual: bool,
+    pub confidence: f64,
+}
+
+pub fn grid_location_schema() -> ResponseSchema {
+    object_schema("grid_location", json!({
+        "cell": { "type": ["string", "null"], "description": "Grid label such as \"C4\", or null when not found" },
+        "found": { "type": "boolean" },
+        "description": { "type": "string" },
+    }))
+}
+
+pub fn annotated_location_schema() -> ResponseSchema {
+    object_schema("annotated_location", json!({
+        "element_id": { "type": ["string", "null"], "description": "Label of the annotated element, or null when not found" },
+        "found": { "type": "boolean" },
+        "description": { "type": "string" },
+    }))
+}
+
+pub fn crop_point_schema() -> ResponseSchema {
+    object_schema("crop_point", json!({
+        "found": { "type": "boolean" },
+        "x": { "type": "number", "description": "Pixel column in the crop image" },
+        "y": { "type": "number", "description": "Pixel row in the crop image" },
+    }))
+}
+
+pub fn click_check_schema() -> ResponseSchema {
+    object_schema("click_check", json!({
+        "activated": { "type": "boolean" },
+        "reason": { "type": "string" },
+    }))
+}
+
+pub fn verification_schema() -> ResponseSchema {
+    object_schema("verification", json!({
+        "pass": { "type": "boolean" },
+        "reason": { "type": "string" },
+        "evidence": { "type": "string" },
+    }))
+}
+
+pub fn reflection_schema() -> ResponseSchema {
+    object_schema("reflection", json!({
+        "decision": { "type": "string", "enum": ["continue", "patch"] },
+        "reason": { "type": "string" },
+        "steps": {
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "description": { "type": "string" },
+                    "recommended_mode": { "type": "string", "enum": ["chat", "vlm", "combo", "explore"] },
+                    "guidance": { "type": "string" },
+                },
+                "required": ["description", "recommended_mode", "guidance"],
+                "additionalProperties": false,
+            },
+        },
+    }))
+}
+
+pub fn visual_decision_schema() -> ResponseSchema {
+    object_schema("visual_decision", json!({
+        "needs_visual": { "type": "boolean" },
+        "confidence": { "type": "number" },
+    }))
+}
+
+/// Strict-mode object: every property required, nothing else allowed.
+fn object_schema(name: &'static str, properties: Value) -> ResponseSchema {
+    let required: Vec<&String> = properties.as_object().map(|p| p.keys().collect()).unwrap_or_default();
+    ResponseSchema {
+        name,
+        schema: json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false,
+        }),
+    }
+}
+
+/// Pull a JSON object out of a model reply: bare JSON, a fenced ```json
+/// block, or the outermost `{…}` inside surrounding prose.
+pub fn parse_json_reply(raw: &str) -> Option<Value> {
+    let trimmed = raw.trim();
+    let unfenced = trimmed
+        .trim_start_matches("```json")
+        .trim_start_matches("```")
+        .trim_end_matches("```")
+        .trim();
+    if let Ok(v) = serde_json::from_str::<Value>(unfenced) {
+        return Some(v);
+    }
+    let start = trimmed.find('{')?;
+    let end = trimmed.rfind('}')?;
+    if end <= start {
+        return None;
+    }
+    serde_json::from_str(&trimmed[start..=end]).ok()
+}
+
+/// [`parse_json_reply`] into a typed reply.
+pub fn parse_reply<T: DeserializeOwned>(raw: &str) -> Option<T> {
+    parse_json_reply(raw).and_then(|v| serde_json::from_value(v).ok())
+}
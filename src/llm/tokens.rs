@@ -0,0 +1,63 @@
+//! Token estimation — a cheap, tokenizer-free approximation.
+//!
+//! Good enough to keep requests inside a model's context window, not for
+//! billing. English / code averages about 4 characters per token; CJK text is
+//! close to one token per character. Images are charged a flat cost, since
+//! providers resize them server-side and the base64 length says little about
+//! the real token count.
+
+use crate::llm::types::{ChatMessage, ContentPart, MessageContent, ToolDef};
+
+/// Context window assumed when a role does not set `context_tokens`.
+pub const DEFAULT_CONTEXT_TOKENS: usize = 32_000;
+/// Flat cost charged per image part.
+pub const IMAGE_TOKENS: usize = 1_000;
+/// Role / separator overhead per message.
+const MESSAGE_OVERHEAD: usize = 4;
+
+/// Estimated tokens in a piece of text.
+pub fn estimate_text(text: &str) -> usize {
+    let mut ascii = 0usize;
+    let mut other = 0usize;
+    for c in text.chars() {
+        if c.is_ascii() {
+            ascii += 1;
+        } else {
+            other += 1;
+        }
+    }
+    ascii.div_ceil(4) + other
+}
+
+/// Estimated tokens for one message, including tool calls and images.
+pub fn estimate_message(msg: &ChatMessage) -> usize {
+    let content = match &msg.content {
+        MessageContent::Text(t) => estimate_text(t),
+        MessageContent::Parts(parts) => parts
+            .iter()
+            .map(|p| match p {
+                ContentPart::Text { text } => estimate_text(text),
+                ContentPart::ImageUrl { .. } => IMAGE_TOKENS,
+            })
+            .sum(),
+    };
+    let calls: usize = msg
+        .tool_calls
+        .iter()
+        .flatten()
+        .map(|tc| estimate_text(&tc.function.name) + estimate_text(&tc.function.arguments))
+        .sum();
+    MESSAGE_OVERHEAD + content + calls
+}
+
+pub fn estimate_messages(messages: &[ChatMessage]) -> usize {
+    messages.iter().map(estimate_message).sum()
+}
+
+/// Estimated tokens taken by the tool schemas sent with a request.
+pub fn estimate_tools(tools: &[ToolDef]) -> usize {
+    tools
+        .iter()
+        .map(|t| serde_json::to_string(t).map(|s| estimate_text(&s)).unwrap_or(0))
+        .sum()
+}
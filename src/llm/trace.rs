@@ -0,0 +1,202 @@
+//! LLM trace mode — a full capture of every model call for debugging.
+//!
+//! With `[llm] trace = true`, every provider is wrapped in a
+//! [`TracingProvider`]. Each call appends one JSON line to
+//! `SeeClaw/sessions/<session id>/llm_trace.jsonl` holding the exact request
+//! body the provider sent, the raw response (every SSE / NDJSON line, or the
+//! JSON body), and the parsed [`LlmResponse`] or error. Inline images are
+//! replaced by their size. Unlike reproducibility mode nothing about the
+//! calls themselves changes — this is purely a recorder.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::agent_engine::history::{current_session_id, data_dir_or_cwd};
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::events::SharedEventSink;
+use crate::llm::provider::LlmProvider;
+use crate::llm::types::{CallConfig, ChatMessage, LlmResponse, ToolDef};
+
+const TRACE_FILE: &str = "llm_trace.jsonl";
+/// Strings at least this long that look like base64 are treated as images.
+const INLINE_DATA_MIN_LEN: usize = 512;
+
+/// Per-call capture buffer, handed to the provider through `CallConfig::capture`.
+#[derive(Debug, Default)]
+pub struct CallCapture {
+    request: Mutex<Option<Value>>,
+    raw: Mutex<Vec<String>>,
+}
+
+impl CallCapture {
+    /// The request body as sent (first attempt only).
+    pub fn request(&self, body: &Value) {
+        let mut slot = self.request.lock().unwrap_or_else(|e| e.into_inner());
+        if slot.is_none() {
+            *slot = Some(sanitize(body.clone()));
+        }
+    }
+
+    /// One raw line of a stream, or a whole non-streaming response body.
+    pub fn raw(&self, text: &str) {
+        self.raw.lock().unwrap_or_else(|e| e.into_inner()).push(text.to_string());
+    }
+}
+
+/// Appends call records to the session's trace file.
+pub struct LlmTrace {
+    path: PathBuf,
+    seq: AtomicU64,
+    file: Mutex<std::fs::File>,
+}
+
+impl LlmTrace {
+    pub fn create() -> SeeClawResult<Self> {
+        let dir = session_dir(current_session_id());
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join(TRACE_FILE);
+        let file = std::fs::OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self { path, seq: AtomicU64::new(0), file: Mutex::new(file) })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn write(&self, mut record: Value) {
+        record["seq"] = self.seq.fetch_add(1, Ordering::SeqCst).into();
+        record["ts"] = chrono::Local::now().to_rfc3339().into();
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        if let Err(e) = writeln!(file, "{record}") {
+            tracing::warn!(error = %e, "failed to write LLM trace");
+        }
+    }
+}
+
+/// Provider wrapper that records every call made through `inner`.
+pub struct TracingProvider {
+    inner: Arc<dyn LlmProvider>,
+    trace: Arc<LlmTrace>,
+}
+
+impl TracingProvider {
+    pub fn new(inner: Arc<dyn LlmProvider>, trace: Arc<LlmTrace>) -> Self {
+        Self { inner, trace }
+    }
+}
+
+#[async_trait]
+impl LlmProvider for TracingProvider {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn chat(
+        &self,
+        messages: Vec<ChatMessage>,
+        tools: Vec<ToolDef>,
+        cfg: &CallConfig,
+        app: &SharedEventSink,
+    ) -> SeeClawResult<LlmResponse> {
+        let capture = Arc::new(CallCapture::default());
+        let mut traced_cfg = cfg.clone();
+        traced_cfg.capture = Some(capture.clone());
+
+        let started = Instant::now();
+        let result = self.inner.chat(messages, tools, &traced_cfg, app).await;
+
+        let outcome = match &result {
+            Ok(resp) => serde_json::json!({ "response": resp }),
+            Err(e) => serde_json::json!({ "error": e.to_string() }),
+        };
+        let request = capture.request.lock().unwrap_or_else(|e| e.into_inner()).take();
+        let raw = std::mem::take(&mut *capture.raw.lock().unwrap_or_else(|e| e.into_inner()));
+        self.trace.write(serde_json::json!({
+            "provider": self.inner.name(),
+            "model": cfg.model,
+            "stream": cfg.stream,
+            "duration_ms": started.elapsed().as_millis() as u64,
+            "request": request,
+            "raw": raw,
+            "outcome": outcome,
+        }));
+        result
+    }
+}
+
+/// One session's trace, as listed by the `list_llm_traces` command.
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceSummary {
+    pub session_id: String,
+    pub calls: usize,
+    pub size_bytes: u64,
+    /// RFC 3339 time of the last write.
+    pub modified: String,
+}
+
+/// Every session that has a trace, newest first.
+pub fn list_traces() -> SeeClawResult<Vec<TraceSummary>> {
+    let mut out = Vec::new();
+    for entry in std::fs::read_dir(data_dir_or_cwd("sessions"))?.flatten() {
+        let path = entry.path().join(TRACE_FILE);
+        let Ok(meta) = std::fs::metadata(&path) else {
+            continue;
+        };
+        let calls = std::fs::read_to_string(&path)
+            .map(|s| s.lines().filter(|l| !l.trim().is_empty()).count())
+            .unwrap_or(0);
+        let modified = meta
+            .modified()
+            .map(|t| chrono::DateTime::<chrono::Local>::from(t).to_rfc3339())
+            .unwrap_or_default();
+        out.push(TraceSummary {
+            session_id: entry.file_name().to_string_lossy().into_owned(),
+            calls,
+            size_bytes: meta.len(),
+            modified,
+        });
+    }
+    out.sort_by(|a, b| b.modified.cmp(&a.modified));
+    Ok(out)
+}
+
+/// All call records of one session's trace.
+pub fn read_trace(session_id: &str) -> SeeClawResult<Vec<Value>> {
+    if session_id.is_empty() || !session_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(SeeClawError::Config(format!("invalid session id '{session_id}'")));
+    }
+    let text = std::fs::read_to_string(session_dir(session_id).join(TRACE_FILE))?;
+    Ok(text
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|l| serde_json::from_str(l).ok())
+        .collect())
+}
+
+fn session_dir(session_id: &str) -> PathBuf {
+    data_dir_or_cwd("sessions").join(session_id)
+}
+
+/// Replace inline image data anywhere in a request body (OpenAI data URLs,
+/// Gemini `inline_data`, Ollama `images`) with its size.
+fn sanitize(value: Value) -> Value {
+    match value {
+        Value::String(s) if is_inline_data(&s) => Value::String(format!("<image {} bytes base64>", s.len())),
+        Value::Array(items) => Value::Array(items.into_iter().map(sanitize).collect()),
+        Value::Object(map) => Value::Object(map.into_iter().map(|(k, v)| (k, sanitize(v))).collect()),
+        other => other,
+    }
+}
+
+fn is_inline_data(s: &str) -> bool {
+    s.starts_with("data:image/")
+        || (s.len() >= INLINE_DATA_MIN_LEN
+            && s.bytes().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'=')))
+}
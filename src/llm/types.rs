@@ -1,4 +1,12 @@
+use std::sync::Arc;
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+
+use crate::config::RequestParams;
+use crate::llm::structured::ResponseSchema;
+use crate::llm::trace::CallCapture;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatMessage {
@@ -63,12 +71,31 @@ pub struct StreamChunk {
     pub content: String,
 }
 
+/// Payload of `llm_reasoning_chunk`. `done` marks the end of one call's
+/// reasoning (with empty `content`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReasoningChunk {
+    pub content: String,
+    #[serde(default)]
+    pub done: bool,
+}
+
 /// The fully-accumulated response returned by `LlmProvider::chat`.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct LlmResponse {
     pub content: String,
     pub reasoning: String,
     pub tool_calls: Vec<ToolCall>,
+    /// Token counts reported by the API, when it reports them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<TokenUsage>,
+}
+
+/// Tokens billed for one call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -79,6 +106,9 @@ pub enum StreamChunkKind {
     ToolCall,
     Done,
     Error,
+    /// Discard what this call has streamed so far; the answer is sent again
+    /// from the start (another provider took over after a failure).
+    Restart,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -103,4 +133,22 @@ pub struct CallConfig {
     pub silent: bool,
     /// When true, force the LLM to respond with valid JSON (response_format: json_object).
     pub json_mode: bool,
+    /// Constrain the reply to this schema where the provider supports it.
+    pub response_schema: Option<ResponseSchema>,
+    /// Sampling seed; only set in reproducibility mode.
+    pub seed: Option<u64>,
+    /// Context window of the model, from the role's `context_tokens`.
+    pub context_tokens: usize,
+    /// Cancelling this token aborts the request, including a stream in
+    /// progress; the call then fails with `SeeClawError::Cancelled`. Nodes
+    /// set it to `SharedState::cancel`.
+    pub cancel: CancellationToken,
+    /// Upper bound for the whole call (retries and stream included), from the
+    /// role's `timeout_secs`. Exceeding it fails with `ProviderTimeout`.
+    pub timeout: Option<Duration>,
+    /// Set in LLM trace mode; providers record the request body and raw
+    /// response into it.
+    pub capture: Option<Arc<CallCapture>>,
+    /// The role's `max_tokens`, `top_p`, penalties and `extra_body`.
+    pub params: RequestParams,
 }
@@ -0,0 +1,155 @@
+//! Token usage and cost accounting.
+//!
+//! Providers report the `usage` of every response; [`record`] adds it to three
+//! ledgers — the current task, this app session, and a lifetime total kept in
+//! `SeeClaw/usage/usage.json` — and emits `usage_updated` so the UI can show
+//! what a task cost. Cost is estimated from `[llm.pricing]` (USD per million
+//! tokens); models without a price only count tokens.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::agent_engine::history::data_dir_or_cwd;
+use crate::config::ModelPrice;
+use crate::events::SharedEventSink;
+use crate::llm::types::TokenUsage;
+
+/// Accumulated usage for one scope or one model.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsageTotals {
+    pub calls: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    /// Estimated cost in USD; 0 when no price is configured.
+    pub cost_usd: f64,
+}
+
+impl UsageTotals {
+    fn add(&mut self, usage: TokenUsage, cost: f64) {
+        self.calls += 1;
+        self.prompt_tokens += usage.prompt_tokens;
+        self.completion_tokens += usage.completion_tokens;
+        self.cost_usd += cost;
+    }
+}
+
+/// Totals plus a breakdown keyed by `"<provider>/<model>"`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsageLedger {
+    pub total: UsageTotals,
+    pub by_model: BTreeMap<String, UsageTotals>,
+}
+
+impl UsageLedger {
+    fn add(&mut self, key: &str, usage: TokenUsage, cost: f64) {
+        self.total.add(usage, cost);
+        self.by_model.entry(key.to_string()).or_default().add(usage, cost);
+    }
+}
+
+/// Returned by the `get_usage_stats` command.
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageStats {
+    /// The running (or last finished) task.
+    pub task: UsageLedger,
+    /// Since the app started.
+    pub session: UsageLedger,
+    /// Across all sessions.
+    pub lifetime: UsageLedger,
+}
+
+#[derive(Default)]
+struct Tracker {
+    task: UsageLedger,
+    session: UsageLedger,
+    lifetime: UsageLedger,
+    pricing: HashMap<String, ModelPrice>,
+}
+
+fn tracker() -> &'static Mutex<Tracker> {
+    static TRACKER: OnceLock<Mutex<Tracker>> = OnceLock::new();
+    TRACKER.get_or_init(|| {
+        Mutex::new(Tracker {
+            lifetime: load_lifetime(),
+            ..Default::default()
+        })
+    })
+}
+
+fn lifetime_path() -> PathBuf {
+    data_dir_or_cwd("usage").join("usage.json")
+}
+
+fn load_lifetime() -> UsageLedger {
+    std::fs::read_to_string(lifetime_path())
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+/// Replace the price table; called whenever the provider registry is rebuilt.
+pub fn set_pricing(pricing: HashMap<String, ModelPrice>) {
+    tracker().lock().unwrap_or_else(|e| e.into_inner()).pricing = pricing;
+}
+
+/// Start a fresh task ledger.
+pub fn begin_task() {
+    tracker().lock().unwrap_or_else(|e| e.into_inner()).task = UsageLedger::default();
+}
+
+/// Add one response's usage to every ledger and notify the UI.
+pub fn record(provider: &str, model: &str, usage: TokenUsage, app: &SharedEventSink) {
+    let key = format!("{provider}/{model}");
+    let (cost, task, lifetime) = {
+        let mut t = tracker().lock().unwrap_or_else(|e| e.into_inner());
+        let cost = t
+            .pricing
+            .get(model)
+            .map(|p| {
+                (usage.prompt_tokens as f64 * p.input + usage.completion_tokens as f64 * p.output)
+                    / 1_000_000.0
+            })
+            .unwrap_or(0.0);
+        t.task.add(&key, usage, cost);
+        t.session.add(&key, usage, cost);
+        t.lifetime.add(&key, usage, cost);
+        (cost, t.task.total.clone(), t.lifetime.clone())
+    };
+
+    tracing::debug!(
+        provider,
+        model,
+        prompt_tokens = usage.prompt_tokens,
+        completion_tokens = usage.completion_tokens,
+        cost_usd = cost,
+        "LLM usage recorded"
+    );
+    match serde_json::to_string_pretty(&lifetime) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(lifetime_path(), json) {
+                tracing::warn!(error = %e, "failed to save lifetime usage");
+            }
+        }
+        Err(e) => tracing::warn!(error = %e, "failed to serialise lifetime usage"),
+    }
+    let _ = app.emit("usage_updated", serde_json::json!({
+        "provider": provider,
+        "model": model,
+        "prompt_tokens": usage.prompt_tokens,
+        "completion_tokens": usage.completion_tokens,
+        "cost_usd": cost,
+        "task": task,
+    }));
+}
+
+pub fn snapshot() -> UsageStats {
+    let t = tracker().lock().unwrap_or_else(|e| e.into_inner());
+    UsageStats {
+        task: t.task.clone(),
+        session: t.session.clone(),
+        lifetime: t.lifetime.clone(),
+    }
+}
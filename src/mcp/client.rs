@@ -1,5 +1,18 @@
-// MCP client — full implementation in Phase 8.
+//! MCP client — initialize handshake, `tools/list` and `tools/call` over any
+//! `McpTransport`.
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::mpsc;
+
+use crate::config::McpServerEntry;
 use crate::errors::{SeeClawError, SeeClawResult};
+use crate::mcp::http_sse_transport::HttpSseTransport;
+use crate::mcp::stdio_transport::StdioTransport;
+use crate::mcp::transport::McpTransport;
+
+/// MCP protocol revision we speak.
+const PROTOCOL_VERSION: &str = "2024-11-05";
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct McpTool {
@@ -10,22 +23,147 @@ pub struct McpTool {
 
 pub struct McpClient {
     pub server_name: String,
+    transport: Arc<dyn McpTransport>,
+    next_id: AtomicU64,
+    /// Notifications forwarded by the transport, drained by `tools_changed`.
+    notifications: Mutex<mpsc::UnboundedReceiver<serde_json::Value>>,
 }
 
 impl McpClient {
-    pub fn new(server_name: String) -> Self {
-        Self { server_name }
+    pub fn new(
+        server_name: String,
+        transport: Arc<dyn McpTransport>,
+        notifications: mpsc::UnboundedReceiver<serde_json::Value>,
+    ) -> Self {
+        Self {
+            server_name,
+            transport,
+            next_id: AtomicU64::new(1),
+            notifications: Mutex::new(notifications),
+        }
+    }
+
+    /// Connect to the server described by `entry` — a remote URL if `url` is
+    /// set, otherwise a spawned `command` — and perform the initialize handshake.
+    pub async fn connect(entry: &McpServerEntry) -> SeeClawResult<Self> {
+        let (notify_tx, notify_rx) = mpsc::unbounded_channel();
+        let transport: Arc<dyn McpTransport> = match entry.url.as_deref().filter(|u| !u.is_empty()) {
+            Some(url) => Arc::new(HttpSseTransport::connect(url, entry.resolved_bearer_token(), notify_tx).await?),
+            None => Arc::new(StdioTransport::spawn(&entry.command, &entry.args, notify_tx)?),
+        };
+        let client = Self::new(entry.name.clone(), transport, notify_rx);
+        client.initialize().await?;
+        Ok(client)
+    }
+
+    /// `initialize` request followed by the `notifications/initialized` notification.
+    pub async fn initialize(&self) -> SeeClawResult<serde_json::Value> {
+        let result = self
+            .request(
+                "initialize",
+                serde_json::json!({
+                    "protocolVersion": PROTOCOL_VERSION,
+                    "capabilities": {},
+                    "clientInfo": {
+                        "name": "seeclaw",
+                        "version": env!("CARGO_PKG_VERSION"),
+                    },
+                }),
+            )
+            .await?;
+        self.transport
+            .notify(serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "notifications/initialized",
+            }))
+            .await?;
+        tracing::info!(
+            server = %self.server_name,
+            server_info = %result["serverInfo"],
+            "MCP server initialized"
+        );
+        Ok(result)
+    }
+
+    /// Drain pending server notifications; returns whether the server sent
+    /// `notifications/tools/list_changed` since the last call.
+    pub fn tools_changed(&self) -> bool {
+        let mut rx = self.notifications.lock().unwrap_or_else(|e| e.into_inner());
+        let mut changed = false;
+        while let Ok(msg) = rx.try_recv() {
+            match msg["method"].as_str() {
+                Some("notifications/tools/list_changed") => changed = true,
+                method => tracing::debug!(
+                    server = %self.server_name,
+                    method = method.unwrap_or(""),
+                    "MCP: server notification ignored"
+                ),
+            }
+        }
+        changed
     }
 
     pub async fn list_tools(&self) -> SeeClawResult<Vec<McpTool>> {
-        Err(SeeClawError::Mcp("MCP not implemented yet (Phase 8)".to_string()))
+        let mut tools = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let params = match &cursor {
+                Some(c) => serde_json::json!({ "cursor": c }),
+                None => serde_json::json!({}),
+            };
+            let result = self.request("tools/list", params).await?;
+            if let Some(arr) = result["tools"].as_array() {
+                tools.extend(arr.iter().map(|t| McpTool {
+                    name: t["name"].as_str().unwrap_or("").to_string(),
+                    description: t["description"].as_str().unwrap_or("").to_string(),
+                    input_schema: t
+                        .get("inputSchema")
+                        .cloned()
+                        .unwrap_or_else(|| serde_json::json!({ "type": "object" })),
+                }));
+            }
+            cursor = result["nextCursor"].as_str().map(|s| s.to_string());
+            if cursor.is_none() {
+                break;
+            }
+        }
+        Ok(tools)
     }
 
     pub async fn call_tool(
         &self,
-        _name: &str,
-        _args: serde_json::Value,
+        name: &str,
+        args: serde_json::Value,
     ) -> SeeClawResult<serde_json::Value> {
-        Err(SeeClawError::Mcp("MCP not implemented yet (Phase 8)".to_string()))
+        self.request(
+            "tools/call",
+            serde_json::json!({ "name": name, "arguments": args }),
+        )
+        .await
+    }
+
+    /// Send a JSON-RPC request and unwrap its `result` (or map its `error`).
+    async fn request(&self, method: &str, params: serde_json::Value) -> SeeClawResult<serde_json::Value> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let response = self
+            .transport
+            .send(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "method": method,
+                "params": params,
+            }))
+            .await?;
+
+        if let Some(err) = response.get("error") {
+            return Err(SeeClawError::Mcp(format!(
+                "{} {}: {} ({})",
+                self.server_name,
+                method,
+                err["message"].as_str().unwrap_or("unknown error"),
+                err["code"],
+            )));
+        }
+        Ok(response.get("result").cloned().unwrap_or(serde_json::Value::Null))
     }
 }
@@ -0,0 +1,376 @@
+//! HTTP + SSE transport for remote MCP servers (2024-11-05 transport).
+//!
+//! The client keeps a `GET <url>` event stream open. The server's first
+//! `endpoint` event names the URL that requests are POSTed to; responses come
+//! back as `message` events on the stream and are routed to the waiting
+//! request by JSON-RPC `id`, exactly like the stdio transport. Servers that
+//! answer the POST inline with `application/json` are handled too.
+//!
+//! A dropped stream is reopened with capped exponential backoff. Requests that
+//! were in flight at the moment of the drop fail immediately; new requests
+//! wait for the fresh `endpoint` event. Each new stream is a new server
+//! session, so the client's `initialize` handshake is replayed on it before
+//! any other message goes out.
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use tokio::sync::{oneshot, watch, Mutex};
+
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::mcp::transport::{is_notification, McpTransport, NotificationSink};
+
+/// Upper bound for a single request (tool calls may do real work).
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+/// How long to wait for the server's `endpoint` event.
+const ENDPOINT_TIMEOUT: Duration = Duration::from_secs(15);
+/// Reconnect backoff ceiling.
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+type PendingMap = Arc<Mutex<HashMap<String, oneshot::Sender<serde_json::Value>>>>;
+
+/// POST endpoint of one event-stream connection.
+#[derive(Clone)]
+struct Endpoint {
+    url: String,
+    /// Increments on every reconnect; each value is a separate server session.
+    session: u64,
+}
+
+/// The client's `initialize` request and the session it was last sent on.
+#[derive(Default)]
+struct Handshake {
+    request: Option<serde_json::Value>,
+    session: u64,
+}
+
+pub struct HttpSseTransport {
+    pub url: String,
+    client: reqwest::Client,
+    bearer_token: Option<String>,
+    /// POST endpoint announced by the server; `None` while (re)connecting.
+    endpoint: watch::Receiver<Option<Endpoint>>,
+    pending: PendingMap,
+    handshake: Mutex<Handshake>,
+    reader: tokio::task::JoinHandle<()>,
+}
+
+impl HttpSseTransport {
+    /// Open the event stream and wait until the server announces its endpoint.
+    /// Server notifications are forwarded to `notifications`.
+    pub async fn connect(
+        url: &str,
+        bearer_token: Option<String>,
+        notifications: NotificationSink,
+    ) -> SeeClawResult<Self> {
+        let client = reqwest::Client::new();
+        let pending: PendingMap = Arc::new(Mutex::new(HashMap::new()));
+        let (endpoint_tx, mut endpoint) = watch::channel(None);
+
+        let reader = tokio::spawn(run_event_stream(
+            client.clone(),
+            url.to_string(),
+            bearer_token.clone(),
+            endpoint_tx,
+            pending.clone(),
+            notifications,
+        ));
+
+        let announced = matches!(
+            tokio::time::timeout(ENDPOINT_TIMEOUT, endpoint.wait_for(|e| e.is_some())).await,
+            Ok(Ok(_))
+        );
+        if !announced {
+            reader.abort();
+            return Err(SeeClawError::Mcp(format!("'{url}' did not announce an endpoint")));
+        }
+
+        Ok(Self {
+            url: url.to_string(),
+            client,
+            bearer_token,
+            endpoint,
+            pending,
+            handshake: Mutex::new(Handshake::default()),
+            reader,
+        })
+    }
+
+    /// Wait for the current endpoint.
+    async fn current_endpoint(&self) -> SeeClawResult<Endpoint> {
+        let mut endpoint = self.endpoint.clone();
+        let current = match tokio::time::timeout(ENDPOINT_TIMEOUT, endpoint.wait_for(|e| e.is_some())).await {
+            Ok(Ok(e)) => e.clone(),
+            _ => None,
+        };
+        current.ok_or_else(|| SeeClawError::Mcp(format!("'{}' is not connected", self.url)))
+    }
+
+    /// Endpoint of an initialized session: after a reconnect the recorded
+    /// `initialize` handshake is sent again before anything else.
+    async fn session_endpoint(&self) -> SeeClawResult<String> {
+        let endpoint = self.current_endpoint().await?;
+        let mut handshake = self.handshake.lock().await;
+        let Some(init) = handshake.request.clone() else {
+            return Ok(endpoint.url);
+        };
+        if handshake.session == endpoint.session {
+            return Ok(endpoint.url);
+        }
+
+        tracing::info!(url = %self.url, session = endpoint.session, "MCP: re-initializing after reconnect");
+        let response = self.exchange(&endpoint.url, init).await?;
+        if let Some(err) = response.get("error") {
+            return Err(SeeClawError::Mcp(format!(
+                "'{}' rejected re-initialize: {}",
+                self.url,
+                err["message"].as_str().unwrap_or("unknown error"),
+            )));
+        }
+        self.post(
+            &endpoint.url,
+            &serde_json::json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+        )
+        .await?;
+        handshake.session = endpoint.session;
+        Ok(endpoint.url)
+    }
+
+    /// POST a request to `target` and wait for its response, inline or on the stream.
+    async fn exchange(&self, target: &str, request: serde_json::Value) -> SeeClawResult<serde_json::Value> {
+        let id = request
+            .get("id")
+            .map(|v| v.to_string())
+            .ok_or_else(|| SeeClawError::Mcp("request without id".into()))?;
+
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().await.insert(id.clone(), tx);
+
+        match self.post(target, &request).await {
+            Ok(Some(response)) => {
+                self.pending.lock().await.remove(&id);
+                return Ok(response);
+            }
+            Ok(None) => {}
+            Err(e) => {
+                self.pending.lock().await.remove(&id);
+                return Err(e);
+            }
+        }
+
+        match tokio::time::timeout(REQUEST_TIMEOUT, rx).await {
+            Ok(Ok(response)) => Ok(response),
+            Ok(Err(_)) => Err(SeeClawError::Mcp(format!("connection to '{}' dropped", self.url))),
+            Err(_) => {
+                self.pending.lock().await.remove(&id);
+                Err(SeeClawError::Mcp(format!("request {id} timed out")))
+            }
+        }
+    }
+
+    /// POST a message. Returns the response if the server answered inline.
+    async fn post(&self, target: &str, msg: &serde_json::Value) -> SeeClawResult<Option<serde_json::Value>> {
+        crate::net_audit::record(target, crate::net_audit::NetPurpose::Mcp);
+        let mut req = self.client.post(target).json(msg);
+        if let Some(token) = &self.bearer_token {
+            req = req.bearer_auth(token);
+        }
+        let resp = req.send().await?;
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            return Err(SeeClawError::Mcp(format!("'{}' returned {status}: {body}", self.url)));
+        }
+
+        let is_json = resp
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|ct| ct.starts_with("application/json"));
+        if !is_json {
+            return Ok(None);
+        }
+        let body = resp.text().await?;
+        if body.trim().is_empty() {
+            return Ok(None);
+        }
+        let value: serde_json::Value = serde_json::from_str(&body)?;
+        Ok(value.get("id").is_some().then_some(value))
+    }
+}
+
+impl Drop for HttpSseTransport {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+#[async_trait]
+impl McpTransport for HttpSseTransport {
+    async fn send(&self, request: serde_json::Value) -> SeeClawResult<serde_json::Value> {
+        if request["method"] == "initialize" {
+            // Remember the handshake so a reconnected session can be initialized too.
+            let endpoint = self.current_endpoint().await?;
+            let mut handshake = self.handshake.lock().await;
+            let response = self.exchange(&endpoint.url, request.clone()).await?;
+            handshake.request = Some(request);
+            handshake.session = endpoint.session;
+            return Ok(response);
+        }
+        let target = self.session_endpoint().await?;
+        self.exchange(&target, request).await
+    }
+
+    async fn notify(&self, notification: serde_json::Value) -> SeeClawResult<()> {
+        let target = if notification["method"] == "notifications/initialized" {
+            self.current_endpoint().await?.url
+        } else {
+            self.session_endpoint().await?
+        };
+        self.post(&target, &notification).await.map(|_| ())
+    }
+}
+
+// ── Event stream ───────────────────────────────────────────────────────────
+
+/// Keep the SSE stream open for the lifetime of the transport, reconnecting
+/// whenever it drops.
+async fn run_event_stream(
+    client: reqwest::Client,
+    url: String,
+    bearer_token: Option<String>,
+    endpoint_tx: watch::Sender<Option<Endpoint>>,
+    pending: PendingMap,
+    notifications: NotificationSink,
+) {
+    let mut attempt: u32 = 0;
+    let mut session: u64 = 0;
+    loop {
+        session += 1;
+        crate::net_audit::record(&url, crate::net_audit::NetPurpose::Mcp);
+        let mut req = client.get(&url).header(reqwest::header::ACCEPT, "text/event-stream");
+        if let Some(token) = &bearer_token {
+            req = req.bearer_auth(token);
+        }
+        match req.send().await.and_then(|r| r.error_for_status()) {
+            Ok(resp) => {
+                attempt = 0;
+                read_events(resp, &url, session, &endpoint_tx, &pending, &notifications).await;
+                tracing::warn!(url = %url, "MCP: event stream closed, reconnecting");
+            }
+            Err(e) => {
+                tracing::warn!(url = %url, error = %e, "MCP: event stream connect failed");
+            }
+        }
+
+        endpoint_tx.send_replace(None);
+        // Responses for in-flight requests were lost with the old stream;
+        // dropping their senders wakes the waiters with an error.
+        pending.lock().await.clear();
+
+        attempt = attempt.saturating_add(1);
+        let backoff = Duration::from_secs(1u64 << attempt.min(5)).min(MAX_BACKOFF);
+        tokio::time::sleep(backoff).await;
+    }
+}
+
+/// Consume one SSE connection until it ends.
+async fn read_events(
+    resp: reqwest::Response,
+    url: &str,
+    session: u64,
+    endpoint_tx: &watch::Sender<Option<Endpoint>>,
+    pending: &PendingMap,
+    notifications: &NotificationSink,
+) {
+    let mut stream = resp.bytes_stream();
+    let mut buf = String::new();
+    let mut event = String::new();
+    let mut data = String::new();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = match chunk {
+            Ok(c) => c,
+            Err(e) => {
+                tracing::warn!(url = %url, error = %e, "MCP: event stream read failed");
+                return;
+            }
+        };
+        buf.push_str(&String::from_utf8_lossy(&chunk));
+
+        while let Some(pos) = buf.find('\n') {
+            let line = buf[..pos].trim_end_matches('\r').to_string();
+            buf.drain(..=pos);
+
+            if line.is_empty() {
+                // Blank line terminates an event.
+                if !data.is_empty() {
+                    dispatch_event(&event, &data, url, session, endpoint_tx, pending, notifications).await;
+                }
+                event.clear();
+                data.clear();
+            } else if let Some(v) = line.strip_prefix("event:") {
+                event = v.trim().to_string();
+            } else if let Some(v) = line.strip_prefix("data:") {
+                if !data.is_empty() {
+                    data.push('\n');
+                }
+                data.push_str(v.strip_prefix(' ').unwrap_or(v));
+            }
+            // Comments (":") and other fields (id:, retry:) are ignored.
+        }
+    }
+}
+
+async fn dispatch_event(
+    event: &str,
+    data: &str,
+    url: &str,
+    session: u64,
+    endpoint_tx: &watch::Sender<Option<Endpoint>>,
+    pending: &PendingMap,
+    notifications: &NotificationSink,
+) {
+    match event {
+        "endpoint" => {
+            let resolved = reqwest::Url::parse(url)
+                .and_then(|base| base.join(data.trim()))
+                .map(|u| u.to_string())
+                .unwrap_or_else(|_| data.trim().to_string());
+            tracing::info!(url = %url, endpoint = %resolved, "MCP: endpoint announced");
+            endpoint_tx.send_replace(Some(Endpoint { url: resolved, session }));
+        }
+        "" | "message" => {
+            let msg: serde_json::Value = match serde_json::from_str(data) {
+                Ok(v) => v,
+                Err(e) => {
+                    tracing::debug!(url = %url, error = %e, "MCP: non-JSON event skipped");
+                    return;
+                }
+            };
+            let is_response = msg.get("result").is_some() || msg.get("error").is_some();
+            match msg.get("id") {
+                Some(id) if is_response => {
+                    if let Some(tx) = pending.lock().await.remove(&id.to_string()) {
+                        let _ = tx.send(msg);
+                    }
+                }
+                _ if is_notification(&msg) => {
+                    let _ = notifications.send(msg);
+                }
+                _ => {
+                    tracing::debug!(
+                        url = %url,
+                        method = msg["method"].as_str().unwrap_or(""),
+                        "MCP: server request ignored"
+                    );
+                }
+            }
+        }
+        other => {
+            tracing::debug!(url = %url, event = other, "MCP: unknown SSE event ignored");
+        }
+    }
+}
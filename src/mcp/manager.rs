@@ -0,0 +1,250 @@
+//! MCP manager — connects every enabled server from `[mcp]` config and
+//! routes tool calls to the right client.
+//!
+//! Servers are connected in the background so a slow or dead server never
+//! holds up the agent loop; their tools appear as soon as they are online.
+//!
+//! Always compiled so the agent can hold an `McpManager` unconditionally;
+//! without the `mcp` feature it simply has no servers.
+use std::collections::BTreeMap;
+#[cfg(feature = "mcp")]
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+#[cfg(feature = "mcp")]
+use std::time::Duration;
+
+#[cfg(feature = "mcp")]
+use crate::config::McpServerEntry;
+use crate::config::McpConfig;
+use crate::errors::{SeeClawError, SeeClawResult};
+#[cfg(feature = "mcp")]
+use crate::llm::types::FunctionDef;
+use crate::llm::types::ToolDef;
+#[cfg(feature = "mcp")]
+use crate::mcp::client::{McpClient, McpTool};
+
+/// Prefix for MCP tools exposed to the LLM: `mcp__<server>__<tool>`.
+pub const MCP_TOOL_PREFIX: &str = "mcp__";
+
+/// Upper bound for starting one server, its handshake and the first
+/// `tools/list` (and for later `tools/list` refreshes). Much shorter than the
+/// per-request timeout: a server that cannot come up in this time is skipped
+/// for the session.
+#[cfg(feature = "mcp")]
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Default)]
+pub struct McpManager {
+    #[cfg(feature = "mcp")]
+    clients: RwLock<HashMap<String, Arc<McpClient>>>,
+    /// Tools discovered so far per server, already converted to `ToolDef`s.
+    tool_defs: RwLock<BTreeMap<String, Vec<ToolDef>>>,
+}
+
+impl McpManager {
+    /// Start connecting all enabled servers and return immediately. Servers
+    /// connect concurrently in a background task, each bounded by
+    /// `CONNECT_TIMEOUT`; one that fails or times out is logged and skipped —
+    /// MCP is optional and must never block the agent.
+    #[cfg(feature = "mcp")]
+    pub fn connect_all(cfg: &McpConfig) -> Arc<Self> {
+        let manager = Arc::new(Self::default());
+        let entries: Vec<McpServerEntry> = cfg.servers.iter().filter(|s| s.enabled).cloned().collect();
+        if entries.is_empty() {
+            return manager;
+        }
+        let background = manager.clone();
+        tokio::spawn(async move {
+            futures_util::future::join_all(entries.iter().map(|entry| background.connect_one(entry))).await;
+            tracing::info!(servers = background.server_names().len(), "MCP servers connected");
+        });
+        manager
+    }
+
+    #[cfg(not(feature = "mcp"))]
+    pub fn connect_all(cfg: &McpConfig) -> Arc<Self> {
+        if cfg.servers.iter().any(|s| s.enabled) {
+            tracing::warn!("MCP servers configured but this build has no `mcp` feature — ignoring");
+        }
+        Arc::new(Self::default())
+    }
+
+    /// Connect one server and register it with its tools.
+    #[cfg(feature = "mcp")]
+    async fn connect_one(&self, entry: &McpServerEntry) {
+        let connect = async {
+            let client = McpClient::connect(entry).await?;
+            let tools = client.list_tools().await;
+            Ok::<_, SeeClawError>((client, tools))
+        };
+        let (client, tools) = match tokio::time::timeout(CONNECT_TIMEOUT, connect).await {
+            Ok(Ok(connected)) => connected,
+            Ok(Err(e)) => {
+                tracing::warn!(server = %entry.name, error = %e, "MCP server failed to connect");
+                return;
+            }
+            Err(_) => {
+                tracing::warn!(
+                    server = %entry.name,
+                    timeout_s = CONNECT_TIMEOUT.as_secs(),
+                    "MCP server did not come up in time — skipped"
+                );
+                return;
+            }
+        };
+        match tools {
+            Ok(tools) => {
+                tracing::info!(server = %entry.name, tools = tools.len(), "MCP tools discovered");
+                self.set_tools(&entry.name, tools);
+            }
+            Err(e) => {
+                tracing::warn!(server = %entry.name, error = %e, "MCP tools/list failed");
+            }
+        }
+        self.clients
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(entry.name.clone(), Arc::new(client));
+    }
+
+    #[cfg(feature = "mcp")]
+    pub fn get(&self, server_name: &str) -> Option<Arc<McpClient>> {
+        self.clients.read().unwrap_or_else(|e| e.into_inner()).get(server_name).cloned()
+    }
+
+    /// Re-list the tools of every server that sent
+    /// `notifications/tools/list_changed` since the last check. A failed
+    /// refresh keeps the previous list.
+    #[cfg(feature = "mcp")]
+    async fn refresh_changed(&self) {
+        let changed: Vec<Arc<McpClient>> = self
+            .clients
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .filter(|c| c.tools_changed())
+            .cloned()
+            .collect();
+        futures_util::future::join_all(changed.iter().map(|client| async move {
+            match tokio::time::timeout(CONNECT_TIMEOUT, client.list_tools()).await {
+                Ok(Ok(tools)) => {
+                    tracing::info!(server = %client.server_name, tools = tools.len(), "MCP tools refreshed");
+                    self.set_tools(&client.server_name, tools);
+                }
+                Ok(Err(e)) => {
+                    tracing::warn!(server = %client.server_name, error = %e, "MCP tools/list refresh failed");
+                }
+                Err(_) => {
+                    tracing::warn!(server = %client.server_name, "MCP tools/list refresh timed out");
+                }
+            }
+        }))
+        .await;
+    }
+
+    /// Replace the tool list of `server_name`.
+    #[cfg(feature = "mcp")]
+    fn set_tools(&self, server_name: &str, tools: Vec<McpTool>) {
+        let defs = tools
+            .into_iter()
+            .map(|t| ToolDef {
+                def_type: "function".into(),
+                function: FunctionDef {
+                    name: namespaced_tool_name(server_name, &t.name),
+                    description: format!("[MCP: {server_name}] {}", t.description),
+                    parameters: t.input_schema,
+                },
+            })
+            .collect();
+        self.tool_defs
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(server_name.to_string(), defs);
+    }
+
+    /// Tool definitions for every MCP tool discovered so far, to be merged
+    /// with the builtin tools before each LLM call. Servers that announced a
+    /// changed tool list are re-listed first.
+    pub async fn tool_defs(&self) -> Vec<ToolDef> {
+        #[cfg(feature = "mcp")]
+        self.refresh_changed().await;
+        self.tool_defs
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
+    #[cfg(feature = "mcp")]
+    pub fn server_names(&self) -> Vec<String> {
+        self.clients.read().unwrap_or_else(|e| e.into_inner()).keys().cloned().collect()
+    }
+
+    #[cfg(not(feature = "mcp"))]
+    pub fn server_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    #[cfg(feature = "mcp")]
+    pub async fn call_tool(
+        &self,
+        server_name: &str,
+        tool_name: &str,
+        args: serde_json::Value,
+    ) -> SeeClawResult<serde_json::Value> {
+        let client = self
+            .get(server_name)
+            .ok_or_else(|| SeeClawError::Mcp(format!("MCP server '{server_name}' is not connected")))?;
+        client.call_tool(tool_name, args).await
+    }
+
+    #[cfg(not(feature = "mcp"))]
+    pub async fn call_tool(
+        &self,
+        server_name: &str,
+        _tool_name: &str,
+        _args: serde_json::Value,
+    ) -> SeeClawResult<serde_json::Value> {
+        Err(SeeClawError::Mcp(format!(
+            "cannot reach MCP server '{server_name}': built without the `mcp` feature"
+        )))
+    }
+}
+
+/// Build the LLM-facing name of an MCP tool.
+pub fn namespaced_tool_name(server_name: &str, tool_name: &str) -> String {
+    format!("{MCP_TOOL_PREFIX}{server_name}__{tool_name}")
+}
+
+/// Split `mcp__<server>__<tool>` back into `(server, tool)`.
+pub fn split_namespaced_tool_name(name: &str) -> Option<(&str, &str)> {
+    let rest = name.strip_prefix(MCP_TOOL_PREFIX)?;
+    let (server, tool) = rest.split_once("__")?;
+    if server.is_empty() || tool.is_empty() {
+        return None;
+    }
+    Some((server, tool))
+}
+
+/// Flatten a `tools/call` result into text for the conversation.
+/// Returns `(is_error, text)`.
+pub fn tool_result_text(result: &serde_json::Value) -> (bool, String) {
+    let is_error = result["isError"].as_bool().unwrap_or(false);
+    let text = result["content"]
+        .as_array()
+        .map(|parts| {
+            parts
+                .iter()
+                .map(|p| match p["type"].as_str() {
+                    Some("text") => p["text"].as_str().unwrap_or("").to_string(),
+                    Some(other) => format!("[{other} content omitted]"),
+                    None => p.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .unwrap_or_else(|| result.to_string());
+    (is_error, text)
+}
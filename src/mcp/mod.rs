@@ -1,3 +1,9 @@
+#[cfg(feature = "mcp")]
 pub mod client;
+#[cfg(feature = "mcp")]
+pub mod http_sse_transport;
+pub mod manager;
+#[cfg(feature = "mcp")]
 pub mod stdio_transport;
+#[cfg(feature = "mcp")]
 pub mod transport;
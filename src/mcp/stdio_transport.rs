@@ -1,16 +1,169 @@
-// stdio transport for MCP — full implementation in Phase 8.
+//! stdio transport for MCP — newline-delimited JSON-RPC over a child process.
+//!
+//! The server is spawned once; a background reader task routes each response
+//! to the request that is waiting for it (matched by JSON-RPC `id`), so several
+//! requests may be in flight at the same time.
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
 use async_trait::async_trait;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, Command};
+use tokio::sync::{oneshot, Mutex};
+
 use crate::errors::{SeeClawError, SeeClawResult};
-use crate::mcp::transport::McpTransport;
+use crate::mcp::transport::{is_notification, McpTransport, NotificationSink};
+
+/// Upper bound for a single request (tool calls may do real work).
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+type PendingMap = Arc<Mutex<HashMap<String, oneshot::Sender<serde_json::Value>>>>;
 
 pub struct StdioTransport {
     pub command: String,
     pub args: Vec<String>,
+    stdin: Mutex<ChildStdin>,
+    pending: PendingMap,
+    /// Held so the server is killed when the transport is dropped.
+    _child: Child,
+}
+
+impl StdioTransport {
+    /// Spawn the server process and start the response reader. Server
+    /// notifications are forwarded to `notifications`.
+    pub fn spawn(command: &str, args: &[String], notifications: NotificationSink) -> SeeClawResult<Self> {
+        let mut cmd = Command::new(command);
+        cmd.args(args)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::null())
+            .kill_on_drop(true);
+
+        // On Windows, `npx` and friends are .cmd shims that need a shell.
+        #[cfg(target_os = "windows")]
+        {
+            if !command.to_lowercase().ends_with(".exe") {
+                cmd = Command::new("cmd");
+                cmd.arg("/C")
+                    .arg(command)
+                    .args(args)
+                    .stdin(std::process::Stdio::piped())
+                    .stdout(std::process::Stdio::piped())
+                    .stderr(std::process::Stdio::null())
+                    .kill_on_drop(true);
+            }
+        }
+
+        let mut child = cmd
+            .spawn()
+            .map_err(|e| SeeClawError::Mcp(format!("spawn '{command}': {e}")))?;
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| SeeClawError::Mcp("server stdin unavailable".into()))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| SeeClawError::Mcp("server stdout unavailable".into()))?;
+
+        let pending: PendingMap = Arc::new(Mutex::new(HashMap::new()));
+        let pending_reader = pending.clone();
+        let server = command.to_string();
+
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stdout).lines();
+            loop {
+                match lines.next_line().await {
+                    Ok(Some(line)) => {
+                        let line = line.trim();
+                        if line.is_empty() {
+                            continue;
+                        }
+                        let msg: serde_json::Value = match serde_json::from_str(line) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                tracing::debug!(server = %server, error = %e, "MCP: non-JSON line skipped");
+                                continue;
+                            }
+                        };
+                        let is_response = msg.get("result").is_some() || msg.get("error").is_some();
+                        match msg.get("id") {
+                            Some(id) if is_response => {
+                                if let Some(tx) = pending_reader.lock().await.remove(&id.to_string()) {
+                                    let _ = tx.send(msg);
+                                }
+                            }
+                            _ if is_notification(&msg) => {
+                                let _ = notifications.send(msg);
+                            }
+                            _ => {
+                                tracing::debug!(
+                                    server = %server,
+                                    method = msg["method"].as_str().unwrap_or(""),
+                                    "MCP: server request ignored"
+                                );
+                            }
+                        }
+                    }
+                    Ok(None) => break,
+                    Err(e) => {
+                        tracing::warn!(server = %server, error = %e, "MCP: stdout read failed");
+                        break;
+                    }
+                }
+            }
+            // Dropping the senders wakes every waiter with a "closed" error.
+            pending_reader.lock().await.clear();
+            tracing::info!(server = %server, "MCP: server stdout closed");
+        });
+
+        Ok(Self {
+            command: command.to_string(),
+            args: args.to_vec(),
+            stdin: Mutex::new(stdin),
+            pending,
+            _child: child,
+        })
+    }
+
+    async fn write_message(&self, msg: &serde_json::Value) -> SeeClawResult<()> {
+        let mut line = serde_json::to_string(msg)?;
+        line.push('\n');
+        let mut stdin = self.stdin.lock().await;
+        stdin.write_all(line.as_bytes()).await?;
+        stdin.flush().await?;
+        Ok(())
+    }
 }
 
 #[async_trait]
 impl McpTransport for StdioTransport {
-    async fn send(&self, _request: serde_json::Value) -> SeeClawResult<serde_json::Value> {
-        Err(SeeClawError::Mcp("stdio transport not implemented yet (Phase 8)".to_string()))
+    async fn send(&self, request: serde_json::Value) -> SeeClawResult<serde_json::Value> {
+        let id = request
+            .get("id")
+            .map(|v| v.to_string())
+            .ok_or_else(|| SeeClawError::Mcp("request without id".into()))?;
+
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().await.insert(id.clone(), tx);
+
+        if let Err(e) = self.write_message(&request).await {
+            self.pending.lock().await.remove(&id);
+            return Err(e);
+        }
+
+        match tokio::time::timeout(REQUEST_TIMEOUT, rx).await {
+            Ok(Ok(response)) => Ok(response),
+            Ok(Err(_)) => Err(SeeClawError::Mcp(format!("server '{}' closed the connection", self.command))),
+            Err(_) => {
+                self.pending.lock().await.remove(&id);
+                Err(SeeClawError::Mcp(format!("request {id} timed out")))
+            }
+        }
+    }
+
+    async fn notify(&self, notification: serde_json::Value) -> SeeClawResult<()> {
+        self.write_message(&notification).await
     }
 }
@@ -1,8 +1,23 @@
-// MCP transport trait — full implementation in Phase 8.
+//! MCP transport trait — moves JSON-RPC 2.0 messages to and from a server.
 use async_trait::async_trait;
+use tokio::sync::mpsc;
+
 use crate::errors::SeeClawResult;
 
+/// Where a transport forwards server-initiated notifications (messages with
+/// a `method` and no `id`), e.g. `notifications/tools/list_changed`.
+pub type NotificationSink = mpsc::UnboundedSender<serde_json::Value>;
+
+/// Whether `msg` is a server notification rather than a response or request.
+pub fn is_notification(msg: &serde_json::Value) -> bool {
+    msg.get("method").is_some() && msg.get("id").is_none()
+}
+
 #[async_trait]
 pub trait McpTransport: Send + Sync {
+    /// Send a JSON-RPC request (must carry an `id`) and wait for the matching response.
     async fn send(&self, request: serde_json::Value) -> SeeClawResult<serde_json::Value>;
+
+    /// Send a JSON-RPC notification (no `id`, no response expected).
+    async fn notify(&self, notification: serde_json::Value) -> SeeClawResult<()>;
 }
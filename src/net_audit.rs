@@ -0,0 +1,86 @@
+//! Network access log — every distinct host SeeClaw contacted this session.
+//!
+//! SeeClaw sends no telemetry of its own; the only outbound traffic is what
+//! the user configured (LLM / embedding endpoints, MCP servers, an opt-in
+//! OTLP collector) plus reachability probes. Every HTTP call site records its URL here so the user can verify
+//! that via the `get_network_activity` command. In-memory only, reset on restart.
+
+use std::collections::BTreeMap;
+use std::sync::{Mutex, OnceLock};
+
+use serde::Serialize;
+
+/// Why a host was contacted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NetPurpose {
+    /// Chat / vision model calls.
+    Llm,
+    /// Remote embeddings for the RAG index.
+    Embeddings,
+    /// Reachability probe before starting a task.
+    HealthProbe,
+    /// Listing locally installed models (Ollama).
+    ModelList,
+    /// Remote MCP server.
+    Mcp,
+    /// OTLP span export (`[telemetry] otlp_endpoint`).
+    Telemetry,
+}
+
+/// Activity summary for one host.
+#[derive(Debug, Clone, Serialize)]
+pub struct HostActivity {
+    /// `host[:port]`.
+    pub host: String,
+    pub scheme: String,
+    pub purposes: Vec<NetPurpose>,
+    pub requests: u64,
+    pub first_seen: String,
+    pub last_seen: String,
+}
+
+fn log() -> &'static Mutex<BTreeMap<String, HostActivity>> {
+    static LOG: OnceLock<Mutex<BTreeMap<String, HostActivity>>> = OnceLock::new();
+    LOG.get_or_init(|| Mutex::new(BTreeMap::new()))
+}
+
+/// Record a request to `url`. Unparseable URLs are recorded verbatim.
+pub fn record(url: &str, purpose: NetPurpose) {
+    let (host, scheme) = match reqwest::Url::parse(url) {
+        Ok(u) => {
+            let host = u.host_str().unwrap_or("").to_string();
+            let host = match u.port() {
+                Some(port) => format!("{host}:{port}"),
+                None => host,
+            };
+            (host, u.scheme().to_string())
+        }
+        Err(_) => (url.to_string(), String::new()),
+    };
+    let now = chrono::Local::now().to_rfc3339();
+
+    let mut log = log().lock().unwrap_or_else(|e| e.into_inner());
+    let entry = log.entry(host.clone()).or_insert_with(|| {
+        tracing::info!(host = %host, purpose = ?purpose, "first request to host this session");
+        HostActivity {
+            host,
+            scheme,
+            purposes: Vec::new(),
+            requests: 0,
+            first_seen: now.clone(),
+            last_seen: now.clone(),
+        }
+    });
+    entry.requests += 1;
+    entry.last_seen = now;
+    if !entry.purposes.contains(&purpose) {
+        entry.purposes.push(purpose);
+        entry.purposes.sort();
+    }
+}
+
+/// All hosts contacted so far, sorted by host name.
+pub fn snapshot() -> Vec<HostActivity> {
+    log().lock().unwrap_or_else(|e| e.into_inner()).values().cloned().collect()
+}
@@ -3,7 +3,8 @@
 /// Each detected element gets a colour-coded rectangle and a text label
 /// (e.g. "btn_1: OK") drawn directly onto the image.
 use crate::errors::{SeeClawError, SeeClawResult};
-use crate::perception::types::{ElementType, UIElement};
+use crate::perception::screenshot::encode_payload;
+use crate::perception::types::{ElementSource, ElementType, UIElement};
 
 /// RGBA colour palette indexed by element type.
 fn element_colour(et: &ElementType) -> [u8; 4] {
@@ -25,7 +26,7 @@ fn element_colour(et: &ElementType) -> [u8; 4] {
 }
 
 /// Annotate `src_bytes` (JPEG/PNG) with bounding boxes for each element.
-/// Returns PNG-encoded bytes of the annotated image.
+/// Returns the annotated image encoded like captures (`screenshot::encode_payload`).
 ///
 /// On high-resolution images (width > 1600) the label font is drawn at 2×
 /// scale so it remains readable when the image is shown to a VLM.
@@ -68,16 +69,7 @@ pub fn annotate_image(
         );
     }
 
-    // Encode as PNG
-    let mut out = Vec::new();
-    image::DynamicImage::ImageRgba8(canvas)
-        .write_to(
-            &mut std::io::Cursor::new(&mut out),
-            image::ImageFormat::Png,
-        )
-        .map_err(|e| SeeClawError::Perception(format!("PNG encode: {e}")))?;
-
-    Ok(out)
+    encode_payload(&image::DynamicImage::ImageRgba8(canvas))
 }
 
 /// Build a text listing of detected elements for the VLM prompt.
@@ -106,16 +98,32 @@ pub fn build_element_list(elements: &[UIElement]) -> String {
             _ => String::new(),
         };
         lines.push(format!(
-            "  - [{}] {:?} ({:.0}%){}",
+            "  - [{}] {:?} ({}){}",
             chain,
             e.node_type,
-            e.confidence * 100.0,
+            source_part(e),
             name_part,
         ));
     }
     lines.join("\n")
 }
 
+/// Attribution shown after the element type, e.g. "uia 90%" or
+/// "merged: yolo 71%, uia 90%".
+fn source_part(e: &UIElement) -> String {
+    let scores = e
+        .source_confidence
+        .iter()
+        .map(|(source, conf)| format!("{} {:.0}%", source.as_str(), conf * 100.0))
+        .collect::<Vec<_>>()
+        .join(", ");
+    match e.source {
+        ElementSource::Merged => format!("merged: {scores}"),
+        _ if scores.is_empty() => format!("{} {:.0}%", e.source.as_str(), e.confidence * 100.0),
+        _ => scores,
+    }
+}
+
 /// Build a containment chain string like "3>7>12" by walking parent_id links.
 fn build_chain<'a>(
     id: &'a str,
@@ -0,0 +1,139 @@
+//! Perception benchmark — how fast and how well the parser chain works on
+//! this machine with the current `[perception]` settings.
+//!
+//! Captures `samples` screenshots of the primary monitor, runs the chain on
+//! each, and reports element counts, per-stage latency (capture, every
+//! parser, annotate, encode) and process memory. Run it before and after
+//! changing `grid_n`, the YOLO thresholds or `execution_providers` to compare.
+
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use serde::Serialize;
+
+use crate::config::PerceptionConfig;
+use crate::errors::SeeClawResult;
+use crate::perception::pipeline::PerceptionChain;
+use crate::perception::screenshot::capture_primary;
+use crate::perception::yolo_detector::YoloDetector;
+
+pub const DEFAULT_SAMPLES: usize = 10;
+const MAX_SAMPLES: usize = 200;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PerceptionBenchmark {
+    pub parsers: Vec<String>,
+    pub grid_n: u32,
+    pub resolution: (u32, u32),
+    pub samples: Vec<BenchmarkSample>,
+    /// Per stage over all samples, in capture → parsers → annotate → encode order.
+    pub stages: Vec<StageStats>,
+    pub elements: StageStats,
+    /// Resident memory in MiB before the first and after the last sample;
+    /// 0 where the platform doesn't report it.
+    pub memory_before_mb: f64,
+    pub memory_after_mb: f64,
+    pub memory_peak_mb: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchmarkSample {
+    pub elements: usize,
+    pub source: String,
+    pub total_ms: u64,
+    pub stages: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StageStats {
+    pub name: String,
+    pub mean: f64,
+    pub min: u64,
+    pub max: u64,
+}
+
+impl StageStats {
+    fn of(name: &str, values: &[u64]) -> Self {
+        if values.is_empty() {
+            return Self { name: name.to_string(), ..Self::default() };
+        }
+        Self {
+            name: name.to_string(),
+            mean: values.iter().sum::<u64>() as f64 / values.len() as f64,
+            min: values.iter().copied().min().unwrap_or(0),
+            max: values.iter().copied().max().unwrap_or(0),
+        }
+    }
+}
+
+/// Build a chain from `cfg` (loading the YOLO model if configured) and
+/// benchmark it over `samples` screenshots.
+pub async fn run(cfg: &PerceptionConfig, samples: usize) -> SeeClawResult<PerceptionBenchmark> {
+    let samples = samples.clamp(1, MAX_SAMPLES);
+    let memory_before_mb = resident_mb();
+    let chain = PerceptionChain::from_config(cfg, YoloDetector::from_config(cfg));
+
+    let mut memory_peak_mb = memory_before_mb;
+    let mut resolution = (0, 0);
+    let mut results = Vec::with_capacity(samples);
+    for i in 0..samples {
+        let started = Instant::now();
+        let shot = capture_primary().await?;
+        let capture_ms = started.elapsed().as_millis() as u64;
+        resolution = (shot.meta.physical_width, shot.meta.physical_height);
+
+        let ctx = chain.run(&shot.image_bytes, &shot.meta).await?;
+        let mut stages = BTreeMap::from([("capture".to_string(), capture_ms)]);
+        stages.extend(ctx.timings.iter().cloned());
+        let total_ms = started.elapsed().as_millis() as u64;
+        tracing::debug!(sample = i, elements = ctx.elements.len(), total_ms, "perception benchmark sample");
+        results.push((ctx.timings, BenchmarkSample {
+            elements: ctx.elements.len(),
+            source: format!("{:?}", ctx.source),
+            total_ms,
+            stages,
+        }));
+        memory_peak_mb = memory_peak_mb.max(resident_mb());
+    }
+
+    // Stage order as first seen, so parsers stay in chain order.
+    let mut order = vec!["capture".to_string()];
+    for (timings, _) in &results {
+        for (name, _) in timings {
+            if !order.contains(name) {
+                order.push(name.clone());
+            }
+        }
+    }
+    order.push("total".to_string());
+    let samples: Vec<BenchmarkSample> = results.into_iter().map(|(_, s)| s).collect();
+    let stages = order
+        .iter()
+        .map(|name| {
+            let values: Vec<u64> = samples
+                .iter()
+                .filter_map(|s| if name == "total" { Some(s.total_ms) } else { s.stages.get(name).copied() })
+                .collect();
+            StageStats::of(name, &values)
+        })
+        .collect();
+    let counts: Vec<u64> = samples.iter().map(|s| s.elements as u64).collect();
+
+    Ok(PerceptionBenchmark {
+        parsers: chain.names(),
+        grid_n: cfg.grid_n,
+        resolution,
+        elements: StageStats::of("elements", &counts),
+        samples,
+        stages,
+        memory_before_mb,
+        memory_after_mb: resident_mb(),
+        memory_peak_mb,
+    })
+}
+
+fn resident_mb() -> f64 {
+    memory_stats::memory_stats()
+        .map(|m| m.physical_mem as f64 / (1024.0 * 1024.0))
+        .unwrap_or(0.0)
+}
@@ -0,0 +1,64 @@
+/// Screen exclusion zones — private regions that are blacked out in every
+/// capture, dropped from element detection and never clicked.
+///
+/// Zones are process-wide: every capture path (`screenshot::capture`) masks
+/// them, so no caller can accidentally send a private region to a model.
+/// Coordinates are desktop coordinates (see `ScreenshotMeta::to_desktop`),
+/// with the primary monitor at 0,0.
+use std::sync::RwLock;
+
+use crate::config::ExclusionZone;
+use crate::perception::types::{ScreenshotMeta, UIElement};
+
+static ZONES: RwLock<Vec<ExclusionZone>> = RwLock::new(Vec::new());
+
+/// Replace the active zones (on startup and whenever config changes).
+pub fn set_zones(zones: Vec<ExclusionZone>) {
+    tracing::info!(count = zones.len(), "exclusion zones updated");
+    if let Ok(mut z) = ZONES.write() {
+        *z = zones;
+    }
+}
+
+pub fn zones() -> Vec<ExclusionZone> {
+    ZONES.read().map(|z| z.clone()).unwrap_or_default()
+}
+
+/// Whether a desktop point lies inside any zone.
+pub fn contains_point(x: i32, y: i32) -> bool {
+    ZONES
+        .read()
+        .map(|z| z.iter().any(|zone| zone.contains(x, y)))
+        .unwrap_or(false)
+}
+
+/// Paint every zone black on a full-resolution capture described by `meta`.
+pub fn mask_image(img: &mut image::RgbaImage, meta: &ScreenshotMeta) {
+    let Ok(zones) = ZONES.read() else { return };
+    let (w, h) = img.dimensions();
+    for zone in zones.iter() {
+        let (zx0, zy0) = meta.from_desktop(zone.x as f64, zone.y as f64);
+        let (zx1, zy1) = meta.from_desktop(zone.x as f64 + zone.width as f64, zone.y as f64 + zone.height as f64);
+        let x0 = (zx0.floor() as i64).clamp(0, w as i64) as u32;
+        let y0 = (zy0.floor() as i64).clamp(0, h as i64) as u32;
+        let x1 = (zx1.ceil() as i64).clamp(0, w as i64) as u32;
+        let y1 = (zy1.ceil() as i64).clamp(0, h as i64) as u32;
+        for y in y0..y1 {
+            for x in x0..x1 {
+                img.put_pixel(x, y, image::Rgba([0, 0, 0, 255]));
+            }
+        }
+    }
+}
+
+/// Drop detected elements whose centre falls inside a zone.
+pub fn retain_outside(elements: &mut Vec<UIElement>, meta: &ScreenshotMeta) {
+    let before = elements.len();
+    elements.retain(|el| {
+        let (cx, cy) = el.center_desktop(meta);
+        !contains_point(cx, cy)
+    });
+    if elements.len() != before {
+        tracing::debug!(removed = before - elements.len(), "elements inside exclusion zones dropped");
+    }
+}
@@ -4,7 +4,7 @@
 /// This is an **optional** second-pass — adds one extra VLM call per step
 /// but significantly improves click accuracy for small or dense UI elements.
 use crate::errors::{SeeClawError, SeeClawResult};
-use crate::perception::types::UIElement;
+use crate::perception::types::{ScreenshotMeta, UIElement};
 
 /// Result of a focus crop operation.
 pub struct FocusCrop {
@@ -18,6 +18,9 @@ pub struct FocusCrop {
     /// Size of the crop in the original image (before upscaling).
     pub crop_w: u32,
     pub crop_h: u32,
+    /// Size of the returned image (after upscaling).
+    pub out_w: u32,
+    pub out_h: u32,
 }
 
 /// Crop the area around `element` from the source image, with `padding_px`
@@ -32,13 +35,24 @@ pub fn crop_element(
     element: &UIElement,
     padding_px: u32,
     min_size: u32,
+) -> SeeClawResult<FocusCrop> {
+    crop_bbox(src_bytes, element.bbox, padding_px, min_size)
+}
+
+/// [`crop_element`] for any normalised `[xmin, ymin, xmax, ymax]` box, such
+/// as a SoM grid cell.
+pub fn crop_bbox(
+    src_bytes: &[u8],
+    bbox: [f32; 4],
+    padding_px: u32,
+    min_size: u32,
 ) -> SeeClawResult<FocusCrop> {
     let img = image::load_from_memory(src_bytes)
         .map_err(|e| SeeClawError::Perception(format!("crop load: {e}")))?;
     let (w, h) = (img.width(), img.height());
 
     // Convert normalised bbox to pixel coordinates
-    let [x1n, y1n, x2n, y2n] = element.bbox;
+    let [x1n, y1n, x2n, y2n] = bbox;
     let ex1 = (x1n * w as f32).round() as i32;
     let ey1 = (y1n * h as f32).round() as i32;
     let ex2 = (x2n * w as f32).round() as i32;
@@ -97,21 +111,24 @@ pub fn crop_element(
         origin_y: cy1,
         crop_w: cw,
         crop_h: ch,
+        out_w,
+        out_h,
     })
 }
 
 /// Given pixel coordinates *within the cropped image*, convert back to
-/// physical coordinates in the full screenshot.
-pub fn crop_to_physical(
+/// desktop coordinates, undoing the upscale and any capture downscale
+/// (`meta.image_scale`), then applying the monitor origin and density.
+pub fn crop_to_desktop(
     crop_x: f32,
     crop_y: f32,
     focus: &FocusCrop,
-    upscaled_w: u32,
-    upscaled_h: u32,
+    meta: &ScreenshotMeta,
 ) -> (i32, i32) {
-    let sx = focus.crop_w as f32 / upscaled_w as f32;
-    let sy = focus.crop_h as f32 / upscaled_h as f32;
-    let orig_x = (crop_x * sx + focus.origin_x as f32).round() as i32;
-    let orig_y = (crop_y * sy + focus.origin_y as f32).round() as i32;
-    (orig_x, orig_y)
+    let sx = focus.crop_w as f32 / focus.out_w.max(1) as f32;
+    let sy = focus.crop_h as f32 / focus.out_h.max(1) as f32;
+    let image_scale = meta.image_scale.max(f64::EPSILON) as f32;
+    let img_x = crop_x.clamp(0.0, focus.out_w as f32) * sx + focus.origin_x as f32;
+    let img_y = crop_y.clamp(0.0, focus.out_h as f32) * sy + focus.origin_y as f32;
+    meta.to_desktop((img_x / image_scale) as f64, (img_y / image_scale) as f64)
 }
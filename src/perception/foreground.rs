@@ -0,0 +1,81 @@
+/// Foreground window detection — which application the user is looking at.
+///
+/// Used to pick app-specific context (e.g. the hotkey knowledge base) for the
+/// planner. On non-Windows platforms (or without the `uia` feature) this
+/// always returns `None`.
+
+/// Process and title of the foreground window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForegroundApp {
+    /// Executable file name, lower-cased (e.g. `code.exe`).
+    pub process: String,
+    pub title: String,
+}
+
+#[cfg(all(target_os = "windows", feature = "uia"))]
+mod win {
+    use super::ForegroundApp;
+    use windows::core::PWSTR;
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetForegroundWindow, GetWindowTextW, GetWindowThreadProcessId,
+    };
+
+    pub fn foreground_app() -> Option<ForegroundApp> {
+        unsafe {
+            let hwnd = GetForegroundWindow();
+            if hwnd.0.is_null() {
+                return None;
+            }
+
+            let mut title_buf = [0u16; 512];
+            let len = GetWindowTextW(hwnd, &mut title_buf).max(0) as usize;
+            let title = String::from_utf16_lossy(&title_buf[..len]);
+
+            let mut pid = 0u32;
+            GetWindowThreadProcessId(hwnd, Some(&mut pid));
+            let process = if pid == 0 {
+                String::new()
+            } else {
+                match OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) {
+                    Ok(handle) => {
+                        let mut buf = [0u16; 1024];
+                        let mut size = buf.len() as u32;
+                        let path = QueryFullProcessImageNameW(
+                            handle,
+                            PROCESS_NAME_WIN32,
+                            PWSTR(buf.as_mut_ptr()),
+                            &mut size,
+                        )
+                        .ok()
+                        .map(|_| String::from_utf16_lossy(&buf[..size as usize]));
+                        let _ = CloseHandle(handle);
+                        path.and_then(|p| p.rsplit(['\\', '/']).next().map(|s| s.to_lowercase()))
+                            .unwrap_or_default()
+                    }
+                    Err(e) => {
+                        tracing::debug!(pid, error = %e, "OpenProcess failed for foreground window");
+                        String::new()
+                    }
+                }
+            };
+
+            Some(ForegroundApp { process, title })
+        }
+    }
+}
+
+/// The application owning the foreground window, if it can be determined.
+#[cfg(all(target_os = "windows", feature = "uia"))]
+pub fn foreground_app() -> Option<ForegroundApp> {
+    win::foreground_app()
+}
+
+#[cfg(not(all(target_os = "windows", feature = "uia")))]
+pub fn foreground_app() -> Option<ForegroundApp> {
+    None
+}
@@ -1,10 +1,15 @@
 pub mod annotator;
+pub mod benchmark;
+pub mod exclusion;
 pub mod focus_crop;
+pub mod foreground;
 pub mod pipeline;
 pub mod screenshot;
 pub mod som_grid;
 pub mod stability;
+pub mod tracking;
 pub mod traits;
 pub mod types;
 pub mod ui_automation;
+#[cfg_attr(not(feature = "onnx"), path = "yolo_detector_stub.rs")]
 pub mod yolo_detector;
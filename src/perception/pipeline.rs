@@ -1,100 +1,210 @@
-/// Perception pipeline — integrates YOLO detection, UI Automation, annotation,
-/// and SoM grid fallback into a single flow.
+/// Perception pipeline — runs an ordered chain of `VisionParser`s (YOLO, UI
+/// Automation, SoM grid fallback, or custom parsers) and produces a single
+/// annotated `PerceptionContext`.
+use std::sync::Arc;
+
 use base64::Engine as _;
+use tracing::Instrument;
+use crate::config::PerceptionConfig;
 use crate::errors::SeeClawResult;
 use crate::perception::annotator;
+use crate::perception::exclusion;
 use crate::perception::screenshot::{capture_primary, ScreenshotResult};
-use crate::perception::types::{PerceptionContext, PerceptionSource};
-use crate::perception::ui_automation;
-use crate::perception::yolo_detector::YoloDetector;
-use crate::perception::som_grid::draw_som_grid;
+use crate::perception::som_grid::SomGridParser;
+use crate::perception::tracking::ElementTracker;
+use crate::perception::traits::{ParserKind, VisionParser};
+use crate::perception::types::{PerceptionContext, PerceptionSource, ScreenshotMeta};
+use crate::perception::ui_automation::{self, UiaParser};
+use crate::perception::yolo_detector::{YoloDetector, YoloParser};
 
-/// Run the full perception pipeline:
-///
-/// 1. Capture screenshot.
-/// 2. If a YOLO detector is available, run inference → element detections.
-/// 3. If `enable_uia` is true, collect Windows Accessibility elements and merge.
-/// 4. Annotate the screenshot with bounding boxes and labels.
-/// 5. If YOLO produced zero detections, fall back to SoM Grid overlay.
+/// IoU above which a later detector's element is treated as a duplicate of an
+/// earlier one (and only enriches its label).
+const MERGE_IOU_THRESHOLD: f32 = 0.3;
+
+/// Default parser order when `PerceptionConfig::parsers` is empty.
+pub fn default_parser_order() -> Vec<String> {
+    vec!["yolo".into(), "uia".into(), "som_grid".into()]
+}
+
+/// Ordered list of vision parsers.
 ///
-/// Returns a `PerceptionContext` containing the annotated image (base64),
-/// the list of detected elements, and metadata.
-pub async fn run(
-    yolo: Option<&mut YoloDetector>,
-    enable_uia: bool,
-    grid_n: u32,
-) -> SeeClawResult<(PerceptionContext, ScreenshotResult)> {
-    // Step 1: capture
-    let shot = capture_primary().await?;
+/// - `Detector` parsers all run; the first one's elements form the base list
+///   and later ones are merged into it.
+/// - `Fallback` parsers only run when no detector produced any element; the
+///   first successful fallback's image is returned as-is.
+pub struct PerceptionChain {
+    parsers: Vec<Arc<dyn VisionParser>>,
+}
 
-    // Step 2: YOLO detection (on a blocking thread — inference is CPU-intensive)
-    let mut elements = if let Some(detector) = yolo {
-        let bytes = shot.image_bytes.clone();
-        let det = detector as *mut YoloDetector;
-        // SAFETY: detector lives at least as long as `run` and YoloDetector is Send+Sync.
-        let det_ref = unsafe { &mut *det };
-        tokio::task::spawn_blocking(move || det_ref.detect(&bytes))
-            .await
-            .map_err(|e| crate::errors::SeeClawError::Perception(format!("join: {e}")))??
-    } else {
-        Vec::new()
-    };
-
-    tracing::debug!(yolo_count = elements.len(), "YOLO detections");
-
-    // Step 3: UIA merge
-    if enable_uia {
-        match ui_automation::collect_ui_elements(&shot.meta).await {
-            Ok(uia_elements) => {
-                tracing::debug!(
-                    uia_count = uia_elements.len(),
-                    "UIA elements (after smart filter + NMS)"
-                );
-                ui_automation::merge_detections(&mut elements, uia_elements, 0.3);
-            }
-            Err(e) => {
-                tracing::warn!(error = %e, "UIA collection failed — continuing without");
+impl PerceptionChain {
+    pub fn new(parsers: Vec<Arc<dyn VisionParser>>) -> Self {
+        Self { parsers }
+    }
+
+    /// Build the chain from config, in the order given by `cfg.parsers`.
+    /// Built-ins: "yolo" (skipped when no detector is loaded), "uia" (skipped
+    /// when `enable_ui_automation` is off) and "som_grid".
+    pub fn from_config(cfg: &PerceptionConfig, yolo: Option<YoloDetector>) -> Self {
+        let mut yolo = yolo;
+        let order = if cfg.parsers.is_empty() { default_parser_order() } else { cfg.parsers.clone() };
+        let mut parsers: Vec<Arc<dyn VisionParser>> = Vec::new();
+        for name in &order {
+            match name.as_str() {
+                "yolo" => {
+                    if let Some(det) = yolo.take() {
+                        parsers.push(Arc::new(YoloParser::new(det)));
+                    }
+                }
+                "uia" => {
+                    if cfg.enable_ui_automation {
+                        parsers.push(Arc::new(UiaParser));
+                    }
+                }
+                "som_grid" => parsers.push(Arc::new(SomGridParser::new(cfg.grid_n.clamp(4, 26)))),
+                other => tracing::warn!(parser = other, "unknown vision parser in config — ignored"),
             }
         }
+        let chain = Self { parsers };
+        tracing::info!(parsers = ?chain.names(), "perception chain built");
+        chain
+    }
+
+    /// Append a custom parser at the end of the chain.
+    pub fn push(&mut self, parser: Arc<dyn VisionParser>) {
+        self.parsers.push(parser);
     }
 
-    tracing::debug!(total = elements.len(), "Total elements after merge");
+    pub fn names(&self) -> Vec<String> {
+        self.parsers.iter().map(|p| p.name().to_string()).collect()
+    }
+
+    /// Run all parsers over one screenshot.
+    pub async fn run(&self, image_bytes: &[u8], meta: &ScreenshotMeta) -> SeeClawResult<PerceptionContext> {
+        self.run_inner(image_bytes, meta, None, false).await
+    }
 
-    // Step 3.5: Compute containment hierarchy and assign short numeric IDs
-    compute_hierarchy(&mut elements);
+    /// Like [`run`](Self::run), but element IDs come from `tracker`, so the
+    /// same control keeps its ID (and annotation label) across screenshots.
+    pub async fn run_tracked(
+        &self,
+        image_bytes: &[u8],
+        meta: &ScreenshotMeta,
+        tracker: &mut ElementTracker,
+    ) -> SeeClawResult<PerceptionContext> {
+        self.run_inner(image_bytes, meta, Some(tracker), false).await
+    }
 
-    // Step 4: Choose annotation strategy
-    if !elements.is_empty() {
-        // Annotate with bounding boxes
-        let annotated_bytes = annotator::annotate_image(&shot.image_bytes, &elements)?;
-        let annotated_b64 = base64::engine::general_purpose::STANDARD.encode(&annotated_bytes);
+    /// With `hierarchy`, parent links and short IDs are assigned before the
+    /// image is annotated, so the labels drawn match the element list.
+    async fn run_inner(
+        &self,
+        image_bytes: &[u8],
+        meta: &ScreenshotMeta,
+        tracker: Option<&mut ElementTracker>,
+        hierarchy: bool,
+    ) -> SeeClawResult<PerceptionContext> {
+        let mut elements = Vec::new();
+        let mut timings = Vec::new();
 
-        let ctx = PerceptionContext {
-            image_base64: Some(annotated_b64),
+        for parser in self.parsers.iter().filter(|p| p.kind() == ParserKind::Detector) {
+            let started = std::time::Instant::now();
+            let parsed = parser
+                .parse(image_bytes, meta)
+                .instrument(tracing::info_span!("perception_parser", parser = parser.name()))
+                .await;
+            timings.push((parser.name().to_string(), started.elapsed().as_millis() as u64));
+            match parsed {
+                Ok(ctx) => {
+                    tracing::debug!(parser = parser.name(), count = ctx.elements.len(), "parser detections");
+                    if elements.is_empty() {
+                        elements = ctx.elements;
+                    } else {
+                        ui_automation::merge_detections(&mut elements, ctx.elements, MERGE_IOU_THRESHOLD);
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(parser = parser.name(), error = %e, "parser failed — continuing without");
+                }
+            }
+        }
+
+        exclusion::retain_outside(&mut elements, meta);
+        tracing::debug!(total = elements.len(), "Total elements after merge");
+        if let Some(tracker) = tracker {
+            tracker.update(&mut elements);
+        }
+        if hierarchy {
+            compute_hierarchy(&mut elements);
+        }
+
+        if !elements.is_empty() {
+            let started = std::time::Instant::now();
+            let annotated = annotator::annotate_image(image_bytes, &elements);
+            timings.push(("annotate".to_string(), started.elapsed().as_millis() as u64));
+            // An annotation failure costs the labels, not the screenshot.
+            let (image, source) = match &annotated {
+                Ok(bytes) => (bytes.as_slice(), PerceptionSource::YoloAnnotated),
+                Err(e) => {
+                    tracing::warn!(error = %e, "annotation failed — sending the raw screenshot");
+                    (image_bytes, PerceptionSource::Raw)
+                }
+            };
+            let started = std::time::Instant::now();
+            let image_base64 = base64::engine::general_purpose::STANDARD.encode(image);
+            timings.push(("encode".to_string(), started.elapsed().as_millis() as u64));
+            return Ok(PerceptionContext {
+                image_base64: Some(image_base64),
+                elements,
+                resolution: (meta.physical_width, meta.physical_height),
+                meta: meta.clone(),
+                source,
+                timings,
+            });
+        }
+
+        for parser in self.parsers.iter().filter(|p| p.kind() == ParserKind::Fallback) {
+            let started = std::time::Instant::now();
+            let parsed = parser
+                .parse(image_bytes, meta)
+                .instrument(tracing::info_span!("perception_parser", parser = parser.name()))
+                .await;
+            timings.push((parser.name().to_string(), started.elapsed().as_millis() as u64));
+            match parsed {
+                Ok(mut ctx) => {
+                    tracing::info!(parser = parser.name(), "No detections — using fallback parser");
+                    ctx.timings = timings;
+                    return Ok(ctx);
+                }
+                Err(e) => tracing::warn!(parser = parser.name(), error = %e, "fallback parser failed"),
+            }
+        }
+
+        Ok(PerceptionContext {
+            image_base64: Some(base64::engine::general_purpose::STANDARD.encode(image_bytes)),
             elements,
-            resolution: (shot.meta.physical_width, shot.meta.physical_height),
-            meta: shot.meta.clone(),
-            source: PerceptionSource::YoloAnnotated,
-        };
-        Ok((ctx, shot))
-    } else {
-        // Fallback: SoM grid
-        tracing::info!("No YOLO/UIA detections — falling back to SoM grid");
-        let grid_bytes = draw_som_grid(&shot.image_bytes, grid_n)
-            .unwrap_or_else(|_| shot.image_bytes.clone());
-        let grid_b64 = base64::engine::general_purpose::STANDARD.encode(&grid_bytes);
-
-        let ctx = PerceptionContext {
-            image_base64: Some(grid_b64),
-            elements: Vec::new(),
-            resolution: (shot.meta.physical_width, shot.meta.physical_height),
-            meta: shot.meta.clone(),
-            source: PerceptionSource::SomGrid,
-        };
-        Ok((ctx, shot))
+            resolution: (meta.physical_width, meta.physical_height),
+            meta: meta.clone(),
+            source: PerceptionSource::Raw,
+            timings,
+        })
     }
 }
 
+/// Run the full perception pipeline:
+///
+/// 1. Capture screenshot.
+/// 2. Run the parser chain (detectors merged, fallback if nothing found).
+/// 3. Compute containment hierarchy and assign short numeric IDs.
+/// 4. Annotate the screenshot once with the final IDs.
+///
+/// Returns a `PerceptionContext` containing the annotated image (base64),
+/// the list of detected elements, and metadata.
+pub async fn run(chain: &PerceptionChain) -> SeeClawResult<(PerceptionContext, ScreenshotResult)> {
+    let shot = capture_primary().await?;
+    let ctx = chain.run_inner(&shot.image_bytes, &shot.meta, None, true).await?;
+    Ok((ctx, shot))
+}
+
 /// Compute containment hierarchy among detected elements.
 ///
 /// For each element, find its *smallest* enclosing parent box.
@@ -1,8 +1,18 @@
+use std::sync::RwLock;
+
 use base64::Engine as _;
+use image::imageops::FilterType;
+use image::DynamicImage;
+use serde::{Deserialize, Serialize};
 use xcap::Monitor;
 
+use crate::config::PerceptionConfig;
 use crate::errors::{SeeClawError, SeeClawResult};
-use crate::perception::types::ScreenshotMeta;
+use crate::perception::exclusion;
+use crate::perception::types::{MonitorRegion, ScreenshotMeta};
+
+/// `ScreenshotMeta::monitor_index` of a stitched all-monitor overview.
+pub const ALL_MONITORS: u32 = u32::MAX;
 
 pub struct ScreenshotResult {
     pub image_bytes: Vec<u8>,
@@ -10,24 +20,161 @@ pub struct ScreenshotResult {
     pub meta: ScreenshotMeta,
 }
 
-/// Captures the primary monitor and returns PNG bytes + metadata.
+/// Which screen a task looks at, chosen with the `select_monitor` tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MonitorTarget {
+    #[default]
+    Primary,
+    /// Index into the OS monitor list (see [`list_monitors`]).
+    Index(u32),
+    /// Every monitor stitched into one image at their desktop positions.
+    All,
+}
+
+/// How captures are sized and encoded before they reach a model.
+#[derive(Debug, Clone, Copy)]
+pub struct ImageEncoding {
+    /// Longest side in pixels; 0 = no limit.
+    pub max_dimension: u32,
+    /// JPEG quality; 0 = PNG.
+    pub jpeg_quality: u8,
+}
+
+static ENCODING: RwLock<ImageEncoding> = RwLock::new(ImageEncoding { max_dimension: 0, jpeg_quality: 75 });
+
+/// Apply `[perception] max_image_dimension / jpeg_quality` (on startup and
+/// whenever config changes).
+pub fn set_encoding(cfg: &PerceptionConfig) {
+    if let Ok(mut e) = ENCODING.write() {
+        *e = ImageEncoding {
+            max_dimension: cfg.max_image_dimension,
+            jpeg_quality: cfg.jpeg_quality.min(100),
+        };
+    }
+}
+
+pub fn encoding() -> ImageEncoding {
+    ENCODING.read().map(|e| *e).unwrap_or(ImageEncoding { max_dimension: 0, jpeg_quality: 75 })
+}
+
+/// Encode an image for a model payload in the configured format. Used for
+/// captures and for the annotated / gridded images derived from them.
+pub fn encode_payload(img: &DynamicImage) -> SeeClawResult<Vec<u8>> {
+    let mut out = Vec::new();
+    match encoding().jpeg_quality {
+        0 => img
+            .write_to(&mut std::io::Cursor::new(&mut out), image::ImageFormat::Png)
+            .map_err(|e| SeeClawError::Perception(format!("PNG encode: {e}")))?,
+        quality => image::codecs::jpeg::JpegEncoder::new_with_quality(&mut out, quality)
+            .encode_image(&DynamicImage::ImageRgb8(img.to_rgb8()))
+            .map_err(|e| SeeClawError::Perception(format!("JPEG encode: {e}")))?,
+    }
+    Ok(out)
+}
+
+/// `data:` URL for base64 image bytes, with the MIME type read from the data.
+pub fn data_url(b64: &str) -> String {
+    let mime = if b64.starts_with("/9j/") { "image/jpeg" } else { "image/png" };
+    format!("data:{mime};base64,{b64}")
+}
+
+/// One connected monitor, as offered to the planner.
+#[derive(Debug, Clone, Serialize)]
+pub struct MonitorInfo {
+    pub index: u32,
+    pub name: String,
+    pub primary: bool,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub scale_factor: f64,
+}
+
+/// Captures the primary monitor and returns encoded bytes + metadata.
 /// Runs the sync xcap call on a blocking thread pool so as not to block the async runtime.
 pub async fn capture_primary() -> SeeClawResult<ScreenshotResult> {
-    tokio::task::spawn_blocking(capture_sync)
+    capture(MonitorTarget::Primary).await
+}
+
+/// Captures the monitor at `index` in the OS monitor list.
+pub async fn capture_monitor(index: u32) -> SeeClawResult<ScreenshotResult> {
+    capture(MonitorTarget::Index(index)).await
+}
+
+/// Captures every monitor separately.
+pub async fn capture_all() -> SeeClawResult<Vec<ScreenshotResult>> {
+    tokio::task::spawn_blocking(|| {
+        let monitors = monitors()?;
+        monitors
+            .iter()
+            .enumerate()
+            .map(|(i, m)| grab(m, i as u32).and_then(|(img, meta)| encode(img, meta)))
+            .collect()
+    })
+    .await
+    .map_err(|e| SeeClawError::Perception(e.to_string()))?
+}
+
+/// Captures `target`.
+pub async fn capture(target: MonitorTarget) -> SeeClawResult<ScreenshotResult> {
+    tokio::task::spawn_blocking(move || capture_sync(target))
         .await
         .map_err(|e| SeeClawError::Perception(e.to_string()))?
 }
 
-fn capture_sync() -> SeeClawResult<ScreenshotResult> {
-    let monitors =
-        Monitor::all().map_err(|e| SeeClawError::Perception(format!("Monitor::all: {e}")))?;
+/// Connected monitors in OS order.
+pub async fn list_monitors() -> SeeClawResult<Vec<MonitorInfo>> {
+    tokio::task::spawn_blocking(|| {
+        Ok(monitors()?
+            .iter()
+            .enumerate()
+            .map(|(i, m)| MonitorInfo {
+                index: i as u32,
+                name: m.name().to_string(),
+                primary: m.is_primary(),
+                x: m.x(),
+                y: m.y(),
+                width: m.width(),
+                height: m.height(),
+                scale_factor: m.scale_factor() as f64,
+            })
+            .collect())
+    })
+    .await
+    .map_err(|e| SeeClawError::Perception(e.to_string()))?
+}
+
+fn monitors() -> SeeClawResult<Vec<Monitor>> {
+    Monitor::all().map_err(|e| SeeClawError::Perception(format!("Monitor::all: {e}")))
+}
 
-    let primary = monitors
-        .into_iter()
-        .find(|m| m.is_primary())
-        .ok_or_else(|| SeeClawError::Perception("no primary monitor found".into()))?;
+fn capture_sync(target: MonitorTarget) -> SeeClawResult<ScreenshotResult> {
+    let monitors = monitors()?;
+    let (img, meta) = match target {
+        MonitorTarget::Primary => {
+            let (i, primary) = monitors
+                .iter()
+                .enumerate()
+                .find(|(_, m)| m.is_primary())
+                .ok_or_else(|| SeeClawError::Perception("no primary monitor found".into()))?;
+            grab(primary, i as u32)?
+        }
+        MonitorTarget::Index(i) => {
+            let monitor = monitors.get(i as usize).ok_or_else(|| {
+                SeeClawError::Perception(format!("no monitor {i} ({} connected)", monitors.len()))
+            })?;
+            grab(monitor, i)?
+        }
+        MonitorTarget::All => stitch(&monitors)?,
+    };
+    encode(img, meta)
+}
 
-    let img = primary
+/// Capture one monitor. Its desktop position becomes the meta origin.
+fn grab(monitor: &Monitor, index: u32) -> SeeClawResult<(image::RgbaImage, ScreenshotMeta)> {
+    let img = monitor
         .capture_image()
         .map_err(|e| SeeClawError::Perception(format!("capture_image: {e}")))?;
 
@@ -35,32 +182,99 @@ fn capture_sync() -> SeeClawResult<ScreenshotResult> {
     let phys_h = img.height();
 
     let meta = ScreenshotMeta {
-        monitor_index: 0,
-        scale_factor: primary.scale_factor() as f64,
+        monitor_index: index,
+        scale_factor: monitor.scale_factor() as f64,
         physical_width: phys_w,
         physical_height: phys_h,
-        logical_width: primary.width(),
-        logical_height: primary.height(),
+        logical_width: monitor.width(),
+        logical_height: monitor.height(),
+        origin_x: monitor.x(),
+        origin_y: monitor.y(),
+        regions: Vec::new(),
+        image_scale: 1.0,
     };
 
-    // Convert xcap RgbaImage to image::DynamicImage and encode as PNG
+    // Convert xcap RgbaImage to image::RgbaImage
     let raw: Vec<u8> = img.into_raw();
     let rgba_img = image::RgbaImage::from_raw(phys_w, phys_h, raw)
         .ok_or_else(|| SeeClawError::Perception("image::from_raw failed".into()))?;
+    Ok((rgba_img, meta))
+}
+
+/// Every monitor pasted at its desktop position on one canvas, which is laid
+/// out at the densest monitor's pixels per desktop unit. Monitors are not
+/// resampled, so a lower-density one leaves a margin on mixed-DPI setups;
+/// `regions` records where each landed so clicks map back exactly.
+fn stitch(monitors: &[Monitor]) -> SeeClawResult<(image::RgbaImage, ScreenshotMeta)> {
+    let shots = monitors
+        .iter()
+        .enumerate()
+        .map(|(i, m)| grab(m, i as u32))
+        .collect::<SeeClawResult<Vec<_>>>()?;
+    let left = shots.iter().map(|(_, m)| m.origin_x).min().unwrap_or(0);
+    let top = shots.iter().map(|(_, m)| m.origin_y).min().unwrap_or(0);
+    let density = shots.iter().map(|(_, m)| m.desktop_scale()).fold(1.0, f64::max);
+    let regions: Vec<MonitorRegion> = shots
+        .iter()
+        .map(|(_, m)| MonitorRegion {
+            x: ((m.origin_x - left) as f64 * density).round() as u32,
+            y: ((m.origin_y - top) as f64 * density).round() as u32,
+            width: m.physical_width,
+            height: m.physical_height,
+            desktop_x: m.origin_x,
+            desktop_y: m.origin_y,
+            desktop_scale: m.desktop_scale(),
+        })
+        .collect();
+    let w = regions.iter().map(|r| r.x + r.width).max().unwrap_or(1).max(1);
+    let h = regions.iter().map(|r| r.y + r.height).max().unwrap_or(1).max(1);
+
+    let mut canvas = image::RgbaImage::new(w, h);
+    for ((img, _), r) in shots.iter().zip(&regions) {
+        image::imageops::overlay(&mut canvas, img, r.x as i64, r.y as i64);
+    }
+    let scale = shots
+        .iter()
+        .find(|(_, m)| m.origin_x == 0 && m.origin_y == 0)
+        .or(shots.first())
+        .map(|(_, m)| m.scale_factor)
+        .unwrap_or(1.0);
+    let meta = ScreenshotMeta {
+        monitor_index: ALL_MONITORS,
+        scale_factor: scale,
+        physical_width: w,
+        physical_height: h,
+        logical_width: (w as f64 / scale).round() as u32,
+        logical_height: (h as f64 / scale).round() as u32,
+        origin_x: left,
+        origin_y: top,
+        regions,
+        image_scale: 1.0,
+    };
+    Ok((canvas, meta))
+}
+
+/// Mask, downscale to `max_dimension` and encode. The meta keeps the
+/// physical size and records the scale applied.
+fn encode(mut rgba_img: image::RgbaImage, mut meta: ScreenshotMeta) -> SeeClawResult<ScreenshotResult> {
+    // Private regions never leave this function.
+    exclusion::mask_image(&mut rgba_img, &meta);
 
-    // Encode as moderately compressed JPEG to keep size within LLM limits.
-    let mut jpeg_bytes = Vec::new();
-    image::DynamicImage::ImageRgba8(rgba_img)
-        .write_to(
-            &mut std::io::Cursor::new(&mut jpeg_bytes),
-            image::ImageFormat::Jpeg,
-        )
-        .map_err(|e| SeeClawError::Perception(format!("JPEG encode: {e}")))?;
+    let max_dimension = encoding().max_dimension;
+    let longest = rgba_img.width().max(rgba_img.height());
+    if max_dimension > 0 && longest > max_dimension {
+        let scale = max_dimension as f64 / longest as f64;
+        let w = ((rgba_img.width() as f64 * scale).round() as u32).max(1);
+        let h = ((rgba_img.height() as f64 * scale).round() as u32).max(1);
+        rgba_img = image::imageops::resize(&rgba_img, w, h, FilterType::Triangle);
+        meta.image_scale = w as f64 / meta.physical_width.max(1) as f64;
+    }
 
-    let image_base64 = base64::engine::general_purpose::STANDARD.encode(&jpeg_bytes);
+    let image_bytes = encode_payload(&DynamicImage::ImageRgba8(rgba_img))?;
+    let image_base64 = base64::engine::general_purpose::STANDARD.encode(&image_bytes);
 
     Ok(ScreenshotResult {
-        image_bytes: jpeg_bytes,
+        image_bytes,
         image_base64,
         meta,
     })
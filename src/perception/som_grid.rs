@@ -6,7 +6,12 @@
 /// Grid labeling convention:
 ///   - Columns: A, B, C … Z, AA, AB … (left → right)
 ///   - Rows:    1, 2, 3 … N           (top  → bottom)
+use base64::Engine as _;
+
 use crate::errors::{SeeClawError, SeeClawResult};
+use crate::perception::screenshot::encode_payload;
+use crate::perception::traits::{ParserKind, VisionParser};
+use crate::perception::types::{PerceptionContext, PerceptionSource, ScreenshotMeta};
 
 // ── Minimal 5×5 bitmap font ───────────────────────────────────────────────────
 // Each glyph: 5 rows, each row is a u8 where bit4=leftmost pixel, bit0=rightmost.
@@ -142,7 +147,7 @@ pub fn cell_label(col: u32, row: u32) -> String {
 ///
 /// **Every cell gets its unique label drawn inside the cell** at the top-left
 /// corner (e.g. "A1", "C4", "L12").  The VLM simply reads the visible text —
-/// no counting, no mental arithmetic.  Returns PNG-encoded bytes.
+/// no counting, no mental arithmetic.  Returns bytes encoded like captures.
 pub fn draw_som_grid(src_bytes: &[u8], grid_n: u32) -> SeeClawResult<Vec<u8>> {
     let img = image::load_from_memory(src_bytes)
         .map_err(|e| SeeClawError::Perception(format!("load image: {e}")))?;
@@ -188,13 +193,7 @@ pub fn draw_som_grid(src_bytes: &[u8], grid_n: u32) -> SeeClawResult<Vec<u8>> {
         }
     }
 
-    // ── Encode result as PNG ──────────────────────────────────────────────
-    let mut out = Vec::new();
-    image::DynamicImage::ImageRgba8(canvas)
-        .write_to(&mut std::io::Cursor::new(&mut out), image::ImageFormat::Png)
-        .map_err(|e| SeeClawError::Perception(format!("PNG encode: {e}")))?;
-
-    Ok(out)
+    encode_payload(&image::DynamicImage::ImageRgba8(canvas))
 }
 
 fn blend_pixel(pixel: &mut image::Rgba<u8>, r: u8, g: u8, b: u8, a: u8) {
@@ -257,3 +256,44 @@ pub fn build_grid_prompt(goal: &str, grid_n: u32) -> String {
         goal = goal,
     )
 }
+
+// ── VisionParser adapter ──────────────────────────────────────────────────────
+
+/// Fallback [`VisionParser`]: overlays the SoM grid when no detector found elements.
+pub struct SomGridParser {
+    grid_n: u32,
+}
+
+impl SomGridParser {
+    pub fn new(grid_n: u32) -> Self {
+        Self { grid_n }
+    }
+}
+
+#[async_trait::async_trait]
+impl VisionParser for SomGridParser {
+    fn name(&self) -> &str {
+        "som_grid"
+    }
+
+    fn kind(&self) -> ParserKind {
+        ParserKind::Fallback
+    }
+
+    async fn parse(
+        &self,
+        image_bytes: &[u8],
+        meta: &ScreenshotMeta,
+    ) -> SeeClawResult<PerceptionContext> {
+        let grid_bytes = draw_som_grid(image_bytes, self.grid_n)
+            .unwrap_or_else(|_| image_bytes.to_vec());
+        Ok(PerceptionContext {
+            image_base64: Some(base64::engine::general_purpose::STANDARD.encode(&grid_bytes)),
+            elements: Vec::new(),
+            resolution: (meta.physical_width, meta.physical_height),
+            meta: meta.clone(),
+            source: PerceptionSource::SomGrid,
+            timings: Vec::new(),
+        })
+    }
+}
@@ -1,6 +1,22 @@
-use crate::errors::SeeClawResult;
+use crate::agent_engine::node::StopSignal;
+use crate::errors::{SeeClawError, SeeClawResult};
+use image::imageops::FilterType;
 use std::time::Duration;
 
+/// Grid the frames are compared on (cells across / down).
+const DIFF_COLS: u32 = 32;
+const DIFF_ROWS: u32 = 18;
+/// Pixels per cell side after downscaling.
+const DIFF_CELL: u32 = 8;
+/// Per-pixel luma difference that counts as changed.
+const DIFF_PIXEL_THRESHOLD: u8 = 24;
+/// Share of a cell's pixels that must change for the cell to count.
+const DIFF_CELL_FRACTION: f32 = 0.1;
+/// Changed regions listed in a `FrameDiff` note.
+const MAX_NOTE_REGIONS: usize = 3;
+/// Luma quantisation step for `frame_hash`.
+const HASH_LUMA_STEP: u8 = 32;
+
 #[derive(Debug, Clone)]
 pub struct StabilityConfig {
     pub max_wait_ms: u64,
@@ -20,6 +36,144 @@ impl Default for StabilityConfig {
     }
 }
 
+/// Hash of an encoded frame's content; equal hashes mean "no visible change".
+///
+/// The frame is decoded and shrunk to the diff grid with coarse luma levels,
+/// so a moved cursor, a ticking clock or re-encoding noise does not change
+/// the hash the way hashing the compressed bytes would. Frames that fail to
+/// decode hash their bytes.
+pub fn frame_hash(frame: &[u8]) -> u64 {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    match image::load_from_memory(frame) {
+        Ok(img) => {
+            let small = img.resize_exact(DIFF_COLS * 2, DIFF_ROWS * 2, FilterType::Triangle).to_luma8();
+            for px in small.pixels() {
+                (px[0] / HASH_LUMA_STEP).hash(&mut hasher);
+            }
+        }
+        Err(_) => frame.hash(&mut hasher),
+    }
+    hasher.finish()
+}
+
+/// Where two frames differ, on a coarse grid.
+#[derive(Debug, Clone)]
+pub struct FrameDiff {
+    /// Share of grid cells that changed (0–1).
+    pub changed_fraction: f64,
+    /// Normalised `[x1, y1, x2, y2]` boxes around connected changed cells,
+    /// largest first.
+    pub regions: Vec<[f32; 4]>,
+}
+
+impl FrameDiff {
+    pub fn changed(&self) -> bool {
+        !self.regions.is_empty()
+    }
+
+    /// One-line summary for tool results, e.g.
+    /// "Screen changed: yes (12% of the screen; regions: x 10–40% y 5–20%)".
+    pub fn note(&self) -> String {
+        if !self.changed() {
+            return "Screen changed: no — the action had no visible effect.".into();
+        }
+        let pct = |v: f32| (v * 100.0).round() as u32;
+        let mut regions = self
+            .regions
+            .iter()
+            .take(MAX_NOTE_REGIONS)
+            .map(|r| format!("x {}–{}% y {}–{}%", pct(r[0]), pct(r[2]), pct(r[1]), pct(r[3])))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if self.regions.len() > MAX_NOTE_REGIONS {
+            regions.push_str(&format!(", +{} more", self.regions.len() - MAX_NOTE_REGIONS));
+        }
+        format!(
+            "Screen changed: yes ({:.0}% of the screen; regions: {regions})",
+            self.changed_fraction * 100.0
+        )
+    }
+}
+
+/// Compare two encoded frames (PNG/JPEG) and report the changed regions.
+/// Frames of different sizes count as entirely changed.
+pub fn diff_frames(before: &[u8], after: &[u8]) -> SeeClawResult<FrameDiff> {
+    let load = |bytes: &[u8]| {
+        image::load_from_memory(bytes).map_err(|e| SeeClawError::Perception(format!("diff decode: {e}")))
+    };
+    let (a, b) = (load(before)?, load(after)?);
+    if (a.width(), a.height()) != (b.width(), b.height()) {
+        return Ok(FrameDiff { changed_fraction: 1.0, regions: vec![[0.0, 0.0, 1.0, 1.0]] });
+    }
+    let (w, h) = (DIFF_COLS * DIFF_CELL, DIFF_ROWS * DIFF_CELL);
+    let a = a.resize_exact(w, h, FilterType::Triangle).to_luma8();
+    let b = b.resize_exact(w, h, FilterType::Triangle).to_luma8();
+
+    let mut changed = vec![false; (DIFF_COLS * DIFF_ROWS) as usize];
+    for row in 0..DIFF_ROWS {
+        for col in 0..DIFF_COLS {
+            let mut hits = 0;
+            for y in row * DIFF_CELL..(row + 1) * DIFF_CELL {
+                for x in col * DIFF_CELL..(col + 1) * DIFF_CELL {
+                    if a.get_pixel(x, y)[0].abs_diff(b.get_pixel(x, y)[0]) > DIFF_PIXEL_THRESHOLD {
+                        hits += 1;
+                    }
+                }
+            }
+            changed[(row * DIFF_COLS + col) as usize] = hits as f32 >= DIFF_CELL_FRACTION * (DIFF_CELL * DIFF_CELL) as f32;
+        }
+    }
+
+    // Group changed cells into 4-connected regions.
+    let mut seen = vec![false; changed.len()];
+    let mut regions: Vec<([u32; 4], usize)> = Vec::new();
+    for start in 0..changed.len() {
+        if !changed[start] || seen[start] {
+            continue;
+        }
+        seen[start] = true;
+        let (mut bounds, mut cells, mut stack) = ([u32::MAX, u32::MAX, 0, 0], 0, vec![start]);
+        while let Some(i) = stack.pop() {
+            let (col, row) = (i as u32 % DIFF_COLS, i as u32 / DIFF_COLS);
+            bounds = [bounds[0].min(col), bounds[1].min(row), bounds[2].max(col), bounds[3].max(row)];
+            cells += 1;
+            let neighbours = [
+                (col > 0).then(|| i - 1),
+                (col + 1 < DIFF_COLS).then(|| i + 1),
+                (row > 0).then(|| i - DIFF_COLS as usize),
+                (row + 1 < DIFF_ROWS).then(|| i + DIFF_COLS as usize),
+            ];
+            for n in neighbours.into_iter().flatten() {
+                if changed[n] && !seen[n] {
+                    seen[n] = true;
+                    stack.push(n);
+                }
+            }
+        }
+        regions.push((bounds, cells));
+    }
+    regions.sort_by(|a, b| b.1.cmp(&a.1));
+
+    let total: usize = regions.iter().map(|(_, c)| c).sum();
+    Ok(FrameDiff {
+        changed_fraction: total as f64 / changed.len() as f64,
+        regions: regions
+            .into_iter()
+            .map(|([c1, r1, c2, r2], _)| {
+                [
+                    c1 as f32 / DIFF_COLS as f32,
+                    r1 as f32 / DIFF_ROWS as f32,
+                    (c2 + 1) as f32 / DIFF_COLS as f32,
+                    (r2 + 1) as f32 / DIFF_ROWS as f32,
+                ]
+            })
+            .collect(),
+    })
+}
+
 pub struct VisualStabilityDetector {
     config: StabilityConfig,
     last_frame_hash: Option<u64>,
@@ -45,17 +199,7 @@ impl VisualStabilityDetector {
     }
 
     pub fn compute_frame_hash(&self, frame: &[u8]) -> u64 {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-
-        let mut hasher = DefaultHasher::new();
-        
-        let sample_step = (frame.len() / 1000).max(1);
-        for i in (0..frame.len()).step_by(sample_step) {
-            frame[i].hash(&mut hasher);
-        }
-        
-        hasher.finish()
+        frame_hash(frame)
     }
 
     pub fn compute_frame_difference(&self, frame1: &[u8], frame2: &[u8]) -> f64 {
@@ -103,7 +247,7 @@ impl VisualStabilityDetector {
 pub async fn wait_for_visual_stability<F, Fut>(
     capture_frame: F,
     config: StabilityConfig,
-    stop_flag: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    stop_flag: std::sync::Arc<StopSignal>,
 ) -> SeeClawResult<bool>
 where
     F: Fn() -> Fut + Clone + Send + 'static,
@@ -113,7 +257,7 @@ where
     let start_time = std::time::Instant::now();
 
     while start_time.elapsed() < Duration::from_millis(config.max_wait_ms) {
-        if stop_flag.load(std::sync::atomic::Ordering::Relaxed) {
+        if stop_flag.is_set() {
             return Ok(false);
         }
 
@@ -134,7 +278,7 @@ where
 pub async fn wait_for_animation_completion<F, Fut>(
     capture_frame: F,
     config: StabilityConfig,
-    stop_flag: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    stop_flag: std::sync::Arc<StopSignal>,
 ) -> SeeClawResult<bool>
 where
     F: Fn() -> Fut + Clone + Send + 'static,
@@ -147,7 +291,7 @@ where
     tokio::time::sleep(Duration::from_millis(300)).await;
 
     while start_time.elapsed() < Duration::from_millis(config.max_wait_ms) {
-        if stop_flag.load(std::sync::atomic::Ordering::Relaxed) {
+        if stop_flag.is_set() {
             return Ok(false);
         }
 
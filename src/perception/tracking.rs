@@ -0,0 +1,195 @@
+//! Element tracking across consecutive screenshots.
+//!
+//! Detectors number their elements afresh on every frame, so `btn_3` on one
+//! screenshot says nothing about `btn_3` on the next. `ElementTracker` matches
+//! each frame's detections against the previous ones (box overlap plus label
+//! similarity) and rewrites their IDs so the same control keeps the same ID
+//! for the whole task. It also records what happened to every element seen
+//! on the previous frame, which lets the engine check whether a clicked
+//! element actually disappeared or changed.
+use std::collections::HashMap;
+
+use crate::perception::types::UIElement;
+use crate::perception::ui_automation::bbox_iou;
+
+/// Overlap above which two boxes can be the same element.
+const MATCH_IOU: f32 = 0.3;
+/// Label similarity that lets a moved element (little or no overlap) match.
+const MOVED_SIMILARITY: f32 = 0.9;
+/// Largest centre shift (normalised) accepted for a moved element.
+const MAX_MOVE: f32 = 0.15;
+/// Overlap at or above which a matched element counts as not having moved.
+const STILL_IOU: f32 = 0.85;
+/// Frames a track survives without a match before its ID is retired, so an
+/// element briefly covered by a popup gets its old ID back.
+const MAX_MISSES: u32 = 3;
+
+/// What happened to an element from the previous frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElementChange {
+    Unchanged,
+    /// Same label, different position or size.
+    Moved,
+    /// Still there, but its label changed.
+    Changed,
+    /// Not found on the new frame.
+    Gone,
+}
+
+impl ElementChange {
+    pub fn describe(self) -> &'static str {
+        match self {
+            Self::Unchanged => "still present and unchanged",
+            Self::Moved => "still present but moved",
+            Self::Changed => "still present but its label changed",
+            Self::Gone => "no longer on screen",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Track {
+    id: String,
+    bbox: [f32; 4],
+    content: Option<String>,
+    misses: u32,
+}
+
+/// Keeps element IDs stable across the screenshots of one task.
+#[derive(Debug, Default)]
+pub struct ElementTracker {
+    tracks: Vec<Track>,
+    next_id: u32,
+    changes: HashMap<String, ElementChange>,
+}
+
+impl ElementTracker {
+    /// Match `elements` against the known tracks and replace their IDs (and
+    /// `parent_id` links) with stable ones. Unmatched elements get new IDs
+    /// that keep the detector's prefix, e.g. `btn_14`.
+    pub fn update(&mut self, elements: &mut [UIElement]) {
+        let mut pairs: Vec<(f32, usize, usize)> = Vec::new();
+        for (ei, e) in elements.iter().enumerate() {
+            for (ti, t) in self.tracks.iter().enumerate() {
+                let iou = bbox_iou(&e.bbox, &t.bbox);
+                let sim = similarity(e.content.as_deref(), t.content.as_deref());
+                let moved = e.content.is_some() && sim >= MOVED_SIMILARITY && centre_shift(&e.bbox, &t.bbox) <= MAX_MOVE;
+                if iou >= MATCH_IOU || moved {
+                    pairs.push((0.6 * iou + 0.4 * sim, ei, ti));
+                }
+            }
+        }
+        pairs.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        let mut element_track: Vec<Option<usize>> = vec![None; elements.len()];
+        let mut track_taken = vec![false; self.tracks.len()];
+        for (_, ei, ti) in pairs {
+            if element_track[ei].is_none() && !track_taken[ti] {
+                element_track[ei] = Some(ti);
+                track_taken[ti] = true;
+            }
+        }
+
+        self.changes.clear();
+        let mut renamed: HashMap<String, String> = HashMap::new();
+        for (e, matched) in elements.iter_mut().zip(&element_track) {
+            let id = match *matched {
+                Some(ti) => {
+                    let track = &mut self.tracks[ti];
+                    if track.misses == 0 {
+                        let change = if normalise(e.content.as_deref()) != normalise(track.content.as_deref()) {
+                            ElementChange::Changed
+                        } else if bbox_iou(&e.bbox, &track.bbox) < STILL_IOU {
+                            ElementChange::Moved
+                        } else {
+                            ElementChange::Unchanged
+                        };
+                        self.changes.insert(track.id.clone(), change);
+                    }
+                    track.bbox = e.bbox;
+                    track.content = e.content.clone();
+                    track.misses = 0;
+                    track.id.clone()
+                }
+                None => {
+                    self.next_id += 1;
+                    let prefix = e.id.rsplit_once('_').map(|(p, _)| p).unwrap_or("el");
+                    let id = format!("{prefix}_{}", self.next_id);
+                    self.tracks.push(Track { id: id.clone(), bbox: e.bbox, content: e.content.clone(), misses: 0 });
+                    id
+                }
+            };
+            renamed.insert(std::mem::replace(&mut e.id, id.clone()), id);
+        }
+        for e in elements.iter_mut() {
+            e.parent_id = e.parent_id.take().and_then(|p| renamed.get(&p).cloned());
+        }
+
+        // Tracks that existed before this frame and found no element.
+        for (ti, taken) in track_taken.into_iter().enumerate() {
+            if taken {
+                continue;
+            }
+            let track = &mut self.tracks[ti];
+            if track.misses == 0 {
+                self.changes.insert(track.id.clone(), ElementChange::Gone);
+            }
+            track.misses += 1;
+        }
+        self.tracks.retain(|t| t.misses <= MAX_MISSES);
+    }
+
+    /// What happened to `id` between the previous frame and the latest one;
+    /// `None` when it was not on the previous frame.
+    pub fn change(&self, id: &str) -> Option<ElementChange> {
+        self.changes
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(id))
+            .map(|(_, c)| *c)
+    }
+
+    /// Forget every track (e.g. after switching to another monitor).
+    pub fn reset(&mut self) {
+        self.tracks.clear();
+        self.changes.clear();
+    }
+}
+
+fn normalise(s: Option<&str>) -> String {
+    s.map(|s| s.trim().to_lowercase()).unwrap_or_default()
+}
+
+/// Label similarity in 0–1 (Dice coefficient over character bigrams).
+/// Two unlabelled elements score a neutral 0.5.
+fn similarity(a: Option<&str>, b: Option<&str>) -> f32 {
+    let (a, b) = (normalise(a), normalise(b));
+    if a.is_empty() && b.is_empty() {
+        return 0.5;
+    }
+    if a == b {
+        return 1.0;
+    }
+    let bigrams = |s: &str| {
+        let chars: Vec<char> = s.chars().collect();
+        chars.windows(2).map(|w| (w[0], w[1])).collect::<Vec<_>>()
+    };
+    let (ba, mut bb) = (bigrams(&a), bigrams(&b));
+    let total = ba.len() + bb.len();
+    if total == 0 {
+        return 0.0;
+    }
+    let mut shared = 0;
+    for g in &ba {
+        if let Some(pos) = bb.iter().position(|x| x == g) {
+            bb.swap_remove(pos);
+            shared += 1;
+        }
+    }
+    2.0 * shared as f32 / total as f32
+}
+
+fn centre_shift(a: &[f32; 4], b: &[f32; 4]) -> f32 {
+    let dx = (a[0] + a[2] - b[0] - b[2]) / 2.0;
+    let dy = (a[1] + a[3] - b[1] - b[3]) / 2.0;
+    (dx * dx + dy * dy).sqrt()
+}
@@ -3,10 +3,27 @@ use async_trait::async_trait;
 use crate::errors::SeeClawResult;
 use crate::perception::types::{PerceptionContext, ScreenshotMeta};
 
+/// How a parser participates in a [`PerceptionChain`](crate::perception::pipeline::PerceptionChain).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParserKind {
+    /// Produces element detections; results of all detectors are merged.
+    Detector,
+    /// Only runs when no detector found anything; its overlay image is used as-is.
+    Fallback,
+}
+
 /// Strategy trait for UI element detection.
-/// Three implementations: ONNX/YOLO, OS Accessibility tree, SoM Grid fallback.
+/// Built-in implementations: ONNX/YOLO, OS Accessibility tree, SoM Grid fallback.
+/// Custom parsers can be pushed onto a `PerceptionChain` without touching the nodes.
 #[async_trait]
 pub trait VisionParser: Send + Sync {
+    /// Identifier used in `PerceptionConfig::parsers` (e.g. "yolo", "uia", "som_grid").
+    fn name(&self) -> &str;
+
+    fn kind(&self) -> ParserKind {
+        ParserKind::Detector
+    }
+
     async fn parse(
         &self,
         image_bytes: &[u8],
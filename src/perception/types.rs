@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +20,28 @@ pub enum ElementType {
     Unknown,
 }
 
+/// Which perception backend reported an element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ElementSource {
+    Yolo,
+    Uia,
+    Ocr,
+    /// Reported by more than one backend; see `UIElement::source_confidence`.
+    Merged,
+}
+
+impl ElementSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Yolo => "yolo",
+            Self::Uia => "uia",
+            Self::Ocr => "ocr",
+            Self::Merged => "merged",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UIElement {
     pub id: String,
@@ -25,18 +49,29 @@ pub struct UIElement {
     /// Normalized bounding box [xmin, ymin, xmax, ymax] in range 0.0–1.0
     pub bbox: [f32; 4],
     pub content: Option<String>,
+    /// Best confidence across the sources that reported this element.
     pub confidence: f32,
+    pub source: ElementSource,
+    /// Confidence reported by each contributing source.
+    pub source_confidence: BTreeMap<ElementSource, f32>,
     /// Optional parent element ID for hierarchy context.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parent_id: Option<String>,
 }
 
 impl UIElement {
-    /// Centre of the bounding box in physical pixel coordinates.
-    pub fn center_physical(&self, meta: &ScreenshotMeta) -> (i32, i32) {
-        let cx = ((self.bbox[0] + self.bbox[2]) / 2.0 * meta.physical_width as f32).round() as i32;
-        let cy = ((self.bbox[1] + self.bbox[3]) / 2.0 * meta.physical_height as f32).round() as i32;
-        (cx, cy)
+    /// Attribution map for an element reported by a single source.
+    pub fn single_source(source: ElementSource, confidence: f32) -> BTreeMap<ElementSource, f32> {
+        BTreeMap::from([(source, confidence)])
+    }
+
+    /// Centre of the bounding box in desktop coordinates (see
+    /// [`ScreenshotMeta::to_desktop`]).
+    pub fn center_desktop(&self, meta: &ScreenshotMeta) -> (i32, i32) {
+        meta.normalized_to_desktop(
+            ((self.bbox[0] + self.bbox[2]) / 2.0) as f64,
+            ((self.bbox[1] + self.bbox[3]) / 2.0) as f64,
+        )
     }
 }
 
@@ -48,6 +83,125 @@ pub struct ScreenshotMeta {
     pub physical_height: u32,
     pub logical_width: u32,
     pub logical_height: u32,
+    /// Desktop position of the captured area's top-left corner; non-zero for
+    /// secondary monitors and stitched overviews.
+    #[serde(default)]
+    pub origin_x: i32,
+    #[serde(default)]
+    pub origin_y: i32,
+    /// Where each monitor sits in a stitched capture; empty for a single
+    /// monitor.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub regions: Vec<MonitorRegion>,
+    /// Encoded image size ÷ physical size (< 1 when the capture was
+    /// downscaled). Normalised coordinates are unaffected; pixel positions
+    /// read off the image divide by this to get physical pixels.
+    #[serde(default = "default_image_scale")]
+    pub image_scale: f64,
+}
+
+fn default_image_scale() -> f64 {
+    1.0
+}
+
+/// One monitor's part of a stitched capture.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MonitorRegion {
+    /// Top-left corner and size in the capture, in physical pixels.
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Desktop position of the monitor's top-left corner.
+    pub desktop_x: i32,
+    pub desktop_y: i32,
+    /// Physical pixels per desktop unit on this monitor.
+    pub desktop_scale: f64,
+}
+
+impl MonitorRegion {
+    fn contains_pixel(&self, px: f64, py: f64) -> bool {
+        px >= self.x as f64
+            && py >= self.y as f64
+            && px < (self.x + self.width) as f64
+            && py < (self.y + self.height) as f64
+    }
+
+    fn contains_desktop(&self, x: f64, y: f64) -> bool {
+        let (w, h) = (self.width as f64 / self.desktop_scale, self.height as f64 / self.desktop_scale);
+        x >= self.desktop_x as f64
+            && y >= self.desktop_y as f64
+            && x < self.desktop_x as f64 + w
+            && y < self.desktop_y as f64 + h
+    }
+}
+
+/// Physical pixels per desktop unit on a monitor `physical_width` pixels
+/// wide that the OS reports as `logical_width` wide.
+///
+/// The desktop — monitor origins, accessibility frames, the cursor and
+/// synthetic input — is laid out in points on macOS, so a Retina monitor has
+/// two pixels per unit. Windows (per-monitor DPI aware, see
+/// `executor::input::init_dpi_awareness`) and X11 use physical pixels.
+pub fn desktop_scale(physical_width: u32, logical_width: u32) -> f64 {
+    if cfg!(target_os = "macos") && logical_width > 0 {
+        physical_width as f64 / logical_width as f64
+    } else {
+        1.0
+    }
+}
+
+impl ScreenshotMeta {
+    /// Desktop coordinates of a pixel offset (physical pixels from the
+    /// capture's top-left corner). The result is what `executor::input`
+    /// takes and what exclusion zones and accessibility frames are given in.
+    pub fn to_desktop(&self, px: f64, py: f64) -> (i32, i32) {
+        let (x0, y0, dx, dy, scale) = match self.regions.iter().find(|r| r.contains_pixel(px, py)) {
+            Some(r) => (r.x as f64, r.y as f64, r.desktop_x, r.desktop_y, r.desktop_scale),
+            None => (0.0, 0.0, self.origin_x, self.origin_y, self.desktop_scale()),
+        };
+        (dx + ((px - x0) / scale).round() as i32, dy + ((py - y0) / scale).round() as i32)
+    }
+
+    /// The pixel offset in the capture of a desktop point; inverse of
+    /// [`to_desktop`](Self::to_desktop).
+    pub fn from_desktop(&self, x: f64, y: f64) -> (f64, f64) {
+        match self.regions.iter().find(|r| r.contains_desktop(x, y)) {
+            Some(r) => (
+                r.x as f64 + (x - r.desktop_x as f64) * r.desktop_scale,
+                r.y as f64 + (y - r.desktop_y as f64) * r.desktop_scale,
+            ),
+            None => {
+                let scale = self.desktop_scale();
+                ((x - self.origin_x as f64) * scale, (y - self.origin_y as f64) * scale)
+            }
+        }
+    }
+
+    /// Desktop coordinates of a normalised `[0, 1]` position in the capture.
+    pub fn normalized_to_desktop(&self, nx: f64, ny: f64) -> (i32, i32) {
+        self.to_desktop(nx * self.physical_width as f64, ny * self.physical_height as f64)
+    }
+
+    /// Normalised position in the capture of a desktop point (outside
+    /// `[0, 1]` when the point is off the captured area).
+    pub fn desktop_to_normalized(&self, x: f64, y: f64) -> (f64, f64) {
+        let (px, py) = self.from_desktop(x, y);
+        (px / self.physical_width.max(1) as f64, py / self.physical_height.max(1) as f64)
+    }
+
+    /// Physical pixels per desktop unit for a single-monitor capture.
+    pub fn desktop_scale(&self) -> f64 {
+        desktop_scale(self.physical_width, self.logical_width)
+    }
+
+    /// Size of the encoded image sent to models.
+    pub fn image_size(&self) -> (u32, u32) {
+        (
+            (self.physical_width as f64 * self.image_scale).round() as u32,
+            (self.physical_height as f64 * self.image_scale).round() as u32,
+        )
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,6 +211,10 @@ pub struct PerceptionContext {
     pub resolution: (u32, u32),
     pub meta: ScreenshotMeta,
     pub source: PerceptionSource,
+    /// `(stage, ms)` filled by `PerceptionChain`: each parser it ran, then
+    /// `annotate` and `encode` when it drew the element boxes.
+    #[serde(default)]
+    pub timings: Vec<(String, u64)>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -67,4 +225,6 @@ pub enum PerceptionSource {
     Accessibility,
     /// YOLO detection + optional UIA merge + annotation
     YoloAnnotated,
+    /// Unannotated screenshot (no parser produced anything).
+    Raw,
 }
@@ -1,15 +1,174 @@
-/// Windows UI Automation (UIA) element collection.
+/// Accessibility tree element collection: Windows UI Automation (UIA) and the
+/// macOS Accessibility API (AXUIElement).
 ///
-/// Walks the accessibility tree of the desktop and returns visible interactive
-/// elements with their bounding rectangles, control types, and names.
-/// On non-Windows platforms this module is a no-op stub.
+/// Walks the accessibility tree of the desktop (Windows) or the frontmost
+/// application (macOS) and returns visible interactive elements with their
+/// bounding rectangles, control types, and names.
+/// On other platforms (or without the `uia` feature) this module is a no-op stub.
 use crate::errors::SeeClawResult;
-use crate::perception::types::{ElementType, ScreenshotMeta, UIElement};
+use crate::perception::traits::VisionParser;
+use crate::perception::types::{
+    ElementSource, ElementType, PerceptionContext, PerceptionSource, ScreenshotMeta, UIElement,
+};
+
+// ── Shared tree filtering ───────────────────────────────────────────────────
+
+#[cfg(all(any(target_os = "windows", target_os = "macos"), feature = "uia"))]
+mod tree_filter {
+    use super::*;
+
+    /// Maximum normalised area — elements larger than this fraction of the screen
+    /// are treated as background containers and dropped (unless they are
+    /// explicitly interactive with a name, e.g. a named full-screen button).
+    const MAX_AREA_RATIO: f32 = 0.25;
+
+    /// Minimum normalised edge length — elements smaller than this are noise.
+    const MIN_EDGE: f32 = 0.008;
+
+    /// Returns `true` for element types that are *primary* interactive controls.
+    /// Menu/MenuItem are excluded because taskbar & system tray flood the view
+    /// with unnamed MenuItem elements.
+    fn is_interactive(et: &ElementType) -> bool {
+        matches!(
+            et,
+            ElementType::Button
+                | ElementType::Input
+                | ElementType::Link
+                | ElementType::Checkbox
+                | ElementType::Radio
+                | ElementType::Select
+                | ElementType::Icon
+        )
+    }
+
+    /// Smart filtering shared by the platform walkers: drops noise-sized
+    /// boxes, background containers and unnamed low-signal elements.
+    pub(super) fn keep_element(ui_elem: &UIElement) -> bool {
+        let bw = ui_elem.bbox[2] - ui_elem.bbox[0];
+        let bh = ui_elem.bbox[3] - ui_elem.bbox[1];
+        let area = bw * bh;
+
+        let too_small = bw < MIN_EDGE || bh < MIN_EDGE;
+        let too_large = area > MAX_AREA_RATIO
+            && !(is_interactive(&ui_elem.node_type) && ui_elem.content.is_some());
+
+        // Drop unnamed elements of low-signal types (containers,
+        // text labels, menu items, images without a name, etc.)
+        let unnamed_low_signal = ui_elem.content.is_none()
+            && matches!(
+                ui_elem.node_type,
+                ElementType::Container
+                    | ElementType::Unknown
+                    | ElementType::Text
+                    | ElementType::MenuItem
+                    | ElementType::Menu
+                    | ElementType::Image
+            );
+
+        !too_small && !too_large && !unnamed_low_signal && bw < 1.0 && bh < 1.0
+    }
+
+    /// NMS for UIA elements: among highly overlapping boxes, keep the *more
+    /// specific* one (smaller area, or interactive type).
+    /// Also performs **containment suppression**: if a larger box fully contains
+    /// a smaller one and the larger box is not a primary interactive control,
+    /// the larger box is suppressed.
+    pub(super) fn nms_elements(elems: Vec<UIElement>, iou_threshold: f32) -> Vec<UIElement> {
+        if elems.is_empty() {
+            return elems;
+        }
+        // Score: smaller area + interactive bonus → higher priority
+        let scores: Vec<f32> = elems
+            .iter()
+            .map(|e| {
+                let area = (e.bbox[2] - e.bbox[0]).max(0.0) * (e.bbox[3] - e.bbox[1]).max(0.0);
+                let interactive_bonus = if is_interactive(&e.node_type) { 0.5 } else { 0.0 };
+                let named_bonus = if e.content.is_some() { 0.3 } else { 0.0 };
+                // Lower area is better → invert; add bonuses
+                (1.0 - area) + interactive_bonus + named_bonus
+            })
+            .collect();
+
+        let mut indices: Vec<usize> = (0..elems.len()).collect();
+        indices.sort_by(|&a, &b| {
+            scores[b]
+                .partial_cmp(&scores[a])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let mut suppressed = vec![false; elems.len()];
+
+        // ── Pass 1: Containment suppression ─────────────────────────────
+        // If box A fully contains box B, suppress the LARGER one (A) unless
+        // A is an interactive control (button, input, etc.).
+        for i in 0..elems.len() {
+            if suppressed[i] { continue; }
+            for j in 0..elems.len() {
+                if i == j || suppressed[j] { continue; }
+                let (a, b) = (&elems[i].bbox, &elems[j].bbox);
+                // Check if i fully contains j
+                if a[0] <= b[0] && a[1] <= b[1] && a[2] >= b[2] && a[3] >= b[3] {
+                    // i contains j → suppress i (the bigger one) if it's not interactive
+                    if !is_interactive(&elems[i].node_type) {
+                        suppressed[i] = true;
+                        break;
+                    }
+                }
+            }
+        }
+
+        // ── Pass 2: IoU-based NMS ───────────────────────────────────────
+        let mut keep = Vec::new();
+        for &i in &indices {
+            if suppressed[i] {
+                continue;
+            }
+            keep.push(i);
+            for &j in &indices {
+                if suppressed[j] || j == i {
+                    continue;
+                }
+                if super::bbox_iou(&elems[i].bbox, &elems[j].bbox) > iou_threshold {
+                    suppressed[j] = true;
+                }
+            }
+        }
+
+        // Preserve original order for determinism
+        keep.sort();
+        let keep_set: std::collections::HashSet<usize> = keep.into_iter().collect();
+        elems
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| keep_set.contains(i))
+            .map(|(_, e)| e)
+            .collect()
+    }
+
+    pub(super) fn element_type_prefix(et: &ElementType) -> &'static str {
+        match et {
+            ElementType::Button => "btn",
+            ElementType::Input => "input",
+            ElementType::Link => "link",
+            ElementType::Icon => "icon",
+            ElementType::Checkbox => "chk",
+            ElementType::Radio => "radio",
+            ElementType::Select => "sel",
+            ElementType::Menu => "menu",
+            ElementType::MenuItem => "mi",
+            ElementType::Text => "txt",
+            ElementType::Image => "img",
+            ElementType::Container => "cont",
+            ElementType::Unknown => "unk",
+        }
+    }
+}
 
 // ── Windows implementation ──────────────────────────────────────────────────
 
-#[cfg(target_os = "windows")]
+#[cfg(all(target_os = "windows", feature = "uia"))]
 mod win {
+    use super::tree_filter::{element_type_prefix, keep_element, nms_elements};
     use super::*;
     use crate::errors::SeeClawError;
     use windows::Win32::Foundation::RECT;
@@ -40,34 +199,10 @@ mod win {
         }
     }
 
-    /// Maximum normalised area — elements larger than this fraction of the screen
-    /// are treated as background containers and dropped (unless they are
-    /// explicitly interactive with a name, e.g. a named full-screen button).
-    const MAX_AREA_RATIO: f32 = 0.25;
-
-    /// Minimum normalised edge length — elements smaller than this are noise.
-    const MIN_EDGE: f32 = 0.008;
-
     /// Bottom region of the screen considered as taskbar (normalised Y).
     /// Elements entirely within this strip are likely taskbar/tray items.
     const TASKBAR_Y_THRESHOLD: f32 = 0.96;
 
-    /// Returns `true` for element types that are *primary* interactive controls.
-    /// Menu/MenuItem are excluded because taskbar & system tray flood the view
-    /// with unnamed MenuItem elements.
-    fn is_interactive(et: &ElementType) -> bool {
-        matches!(
-            et,
-            ElementType::Button
-                | ElementType::Input
-                | ElementType::Link
-                | ElementType::Checkbox
-                | ElementType::Radio
-                | ElementType::Select
-                | ElementType::Icon
-        )
-    }
-
     /// Collects visible UI elements from the accessibility tree.
     /// Must be called from a blocking thread (COM is not async-safe).
     ///
@@ -138,34 +273,10 @@ mod win {
         // Extract element properties (ignore errors — some elements are inaccessible)
         let current_id: Option<String> =
             if let Ok(mut ui_elem) = extract_element(element, meta, counters) {
-                let bw = ui_elem.bbox[2] - ui_elem.bbox[0];
-                let bh = ui_elem.bbox[3] - ui_elem.bbox[1];
-                let area = bw * bh;
-
-                // ── Smart filtering ────────────────────────────────────────
-                let too_small = bw < MIN_EDGE || bh < MIN_EDGE;
-                let too_large = area > MAX_AREA_RATIO
-                    && !(is_interactive(&ui_elem.node_type) && ui_elem.content.is_some());
-
-                // Drop unnamed elements of low-signal types (containers,
-                // text labels, menu items, images without a name, etc.)
-                let unnamed_low_signal = ui_elem.content.is_none()
-                    && matches!(
-                        ui_elem.node_type,
-                        ElementType::Container
-                            | ElementType::Unknown
-                            | ElementType::Text
-                            | ElementType::MenuItem
-                            | ElementType::Menu
-                            | ElementType::Image
-                    );
-
                 // Elements sitting entirely in the bottom taskbar strip
                 let in_taskbar = ui_elem.bbox[1] >= TASKBAR_Y_THRESHOLD;
 
-                if !too_small && !too_large && !unnamed_low_signal && !in_taskbar
-                    && bw < 1.0 && bh < 1.0
-                {
+                if keep_element(&ui_elem) && !in_taskbar {
                     // Record parent_id for hierarchy context
                     ui_elem.parent_id = parent_id.map(|s| s.to_string());
                     let id_clone = ui_elem.id.clone();
@@ -237,17 +348,13 @@ mod win {
         *count += 1;
         let id = format!("uia_{}_{}", prefix, count);
 
-        // Convert screen rect to normalised [0, 1] using physical dimensions
-        let pw = meta.physical_width as f32;
-        let ph = meta.physical_height as f32;
-
-        // UIA BoundingRectangle is in screen coordinates.
-        // On DPI-aware processes these are physical pixels; on unaware they're logical.
-        // We treat them as physical and clamp.
-        let x1 = (rect.left as f32 / pw).clamp(0.0, 1.0);
-        let y1 = (rect.top as f32 / ph).clamp(0.0, 1.0);
-        let x2 = (rect.right as f32 / pw).clamp(0.0, 1.0);
-        let y2 = (rect.bottom as f32 / ph).clamp(0.0, 1.0);
+        // Convert screen rect to normalised [0, 1]
+        // UIA BoundingRectangle is in desktop coordinates: physical pixels,
+        // since the process is per-monitor DPI aware.
+        let (x1, y1) = meta.desktop_to_normalized(rect.left as f64, rect.top as f64);
+        let (x2, y2) = meta.desktop_to_normalized(rect.right as f64, rect.bottom as f64);
+        let (x1, y1) = ((x1 as f32).clamp(0.0, 1.0), (y1 as f32).clamp(0.0, 1.0));
+        let (x2, y2) = ((x2 as f32).clamp(0.0, 1.0), (y2 as f32).clamp(0.0, 1.0));
 
         Ok(UIElement {
             id,
@@ -255,85 +362,31 @@ mod win {
             bbox: [x1, y1, x2, y2],
             content: if name.is_empty() { None } else { Some(name) },
             confidence: 0.9,
+            source: ElementSource::Uia,
+            source_confidence: UIElement::single_source(ElementSource::Uia, 0.9),
             parent_id: None, // set later in walk_tree
         })
     }
 
-    /// NMS for UIA elements: among highly overlapping boxes, keep the *more
-    /// specific* one (smaller area, or interactive type).
-    /// Also performs **containment suppression**: if a larger box fully contains
-    /// a smaller one and the larger box is not a primary interactive control,
-    /// the larger box is suppressed.
-    fn nms_elements(elems: Vec<UIElement>, iou_threshold: f32) -> Vec<UIElement> {
-        if elems.is_empty() {
-            return elems;
-        }
-        // Score: smaller area + interactive bonus → higher priority
-        let scores: Vec<f32> = elems
-            .iter()
-            .map(|e| {
-                let area = (e.bbox[2] - e.bbox[0]).max(0.0) * (e.bbox[3] - e.bbox[1]).max(0.0);
-                let interactive_bonus = if is_interactive(&e.node_type) { 0.5 } else { 0.0 };
-                let named_bonus = if e.content.is_some() { 0.3 } else { 0.0 };
-                // Lower area is better → invert; add bonuses
-                (1.0 - area) + interactive_bonus + named_bonus
-            })
-            .collect();
-
-        let mut indices: Vec<usize> = (0..elems.len()).collect();
-        indices.sort_by(|&a, &b| {
-            scores[b]
-                .partial_cmp(&scores[a])
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
-
-        let mut suppressed = vec![false; elems.len()];
-
-        // ── Pass 1: Containment suppression ─────────────────────────────
-        // If box A fully contains box B, suppress the LARGER one (A) unless
-        // A is an interactive control (button, input, etc.).
-        for i in 0..elems.len() {
-            if suppressed[i] { continue; }
-            for j in 0..elems.len() {
-                if i == j || suppressed[j] { continue; }
-                let (a, b) = (&elems[i].bbox, &elems[j].bbox);
-                // Check if i fully contains j
-                if a[0] <= b[0] && a[1] <= b[1] && a[2] >= b[2] && a[3] >= b[3] {
-                    // i contains j → suppress i (the bigger one) if it's not interactive
-                    if !is_interactive(&elems[i].node_type) {
-                        suppressed[i] = true;
-                        break;
-                    }
-                }
-            }
-        }
-
-        // ── Pass 2: IoU-based NMS ───────────────────────────────────────
-        let mut keep = Vec::new();
-        for &i in &indices {
-            if suppressed[i] {
-                continue;
-            }
-            keep.push(i);
-            for &j in &indices {
-                if suppressed[j] || j == i {
-                    continue;
-                }
-                if super::bbox_iou(&elems[i].bbox, &elems[j].bbox) > iou_threshold {
-                    suppressed[j] = true;
-                }
-            }
-        }
+    /// Name and control type of the element that currently has keyboard focus.
+    pub fn focused_element_sync() -> SeeClawResult<Option<FocusedElement>> {
+        let _com = ComGuard::new()?;
 
-        // Preserve original order for determinism
-        keep.sort();
-        let keep_set: std::collections::HashSet<usize> = keep.into_iter().collect();
-        elems
-            .into_iter()
-            .enumerate()
-            .filter(|(i, _)| keep_set.contains(i))
-            .map(|(_, e)| e)
-            .collect()
+        let automation: IUIAutomation = unsafe {
+            CoCreateInstance(&CUIAutomation, None, CLSCTX_ALL)
+                .map_err(|e| SeeClawError::Perception(format!("CoCreateInstance UIA: {e}")))?
+        };
+        let Ok(element) = (unsafe { automation.GetFocusedElement() }) else {
+            return Ok(None);
+        };
+        let name = unsafe { element.CurrentName().unwrap_or_default().to_string() };
+        let control_type = unsafe { element.CurrentControlType().unwrap_or(UIA_CONTROLTYPE_ID(0)) };
+        let rect: Option<RECT> = unsafe { element.CurrentBoundingRectangle().ok() };
+        Ok(Some(FocusedElement {
+            name,
+            node_type: control_type_to_element(control_type.0),
+            rect: rect.map(|r| [r.left, r.top, r.right, r.bottom]),
+        }))
     }
 
     fn control_type_to_element(ct: i32) -> ElementType {
@@ -364,21 +417,183 @@ mod win {
         }
     }
 
-    fn element_type_prefix(et: &ElementType) -> &'static str {
-        match et {
-            ElementType::Button => "btn",
-            ElementType::Input => "input",
-            ElementType::Link => "link",
-            ElementType::Icon => "icon",
-            ElementType::Checkbox => "chk",
-            ElementType::Radio => "radio",
-            ElementType::Select => "sel",
-            ElementType::Menu => "menu",
-            ElementType::MenuItem => "mi",
-            ElementType::Text => "txt",
-            ElementType::Image => "img",
-            ElementType::Container => "cont",
-            ElementType::Unknown => "unk",
+}
+
+// ── macOS implementation ────────────────────────────────────────────────────
+
+#[cfg(all(target_os = "macos", feature = "uia"))]
+mod mac {
+    use super::tree_filter::{element_type_prefix, keep_element, nms_elements};
+    use super::*;
+    use crate::errors::SeeClawError;
+    use accessibility_sys::{
+        kAXErrorSuccess, kAXValueTypeCGPoint, kAXValueTypeCGSize, AXIsProcessTrusted,
+        AXUIElementCopyAttributeValue, AXUIElementCreateSystemWide, AXUIElementRef,
+        AXUIElementSetMessagingTimeout, AXValueGetValue, AXValueRef, AXValueType,
+    };
+    use core_foundation::array::CFArray;
+    use core_foundation::base::{CFType, CFTypeRef, TCFType};
+    use core_foundation::string::CFString;
+
+    /// AX trees nest deeper than UIA's control view (groups, split views,
+    /// scroll areas), so the walk goes further down.
+    const MAX_DEPTH: u32 = 12;
+    const MAX_ELEMENTS: usize = 500;
+    /// Seconds a hung application may block a single attribute read.
+    const MESSAGING_TIMEOUT: f32 = 0.5;
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct Point {
+        x: f64,
+        y: f64,
+    }
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct Size {
+        width: f64,
+        height: f64,
+    }
+
+    /// Collects visible UI elements of the frontmost application.
+    /// Needs the Accessibility permission (System Settings › Privacy &
+    /// Security › Accessibility); runs on a blocking thread.
+    pub fn collect_elements_sync(meta: &ScreenshotMeta) -> SeeClawResult<Vec<UIElement>> {
+        if unsafe { AXIsProcessTrusted() } == 0 {
+            return Err(SeeClawError::Perception(
+                "Accessibility permission not granted (System Settings › Privacy & Security › Accessibility)".into(),
+            ));
+        }
+        let system = unsafe { CFType::wrap_under_create_rule(AXUIElementCreateSystemWide() as CFTypeRef) };
+        let Some(app) = attr(&system, "AXFocusedApplication") else {
+            return Ok(Vec::new());
+        };
+        unsafe { AXUIElementSetMessagingTimeout(app.as_CFTypeRef() as AXUIElementRef, MESSAGING_TIMEOUT) };
+
+        let mut elements = Vec::new();
+        let mut counters = std::collections::HashMap::<String, u32>::new();
+        walk_tree(&app, meta, None, 0, &mut elements, &mut counters);
+
+        let elements = nms_elements(elements, 0.50);
+        tracing::debug!(count = elements.len(), "AX elements collected (after filter+NMS)");
+        Ok(elements)
+    }
+
+    fn walk_tree(
+        element: &CFType,
+        meta: &ScreenshotMeta,
+        parent_id: Option<&str>,
+        depth: u32,
+        out: &mut Vec<UIElement>,
+        counters: &mut std::collections::HashMap<String, u32>,
+    ) {
+        if depth > MAX_DEPTH || out.len() >= MAX_ELEMENTS {
+            return;
+        }
+
+        let current_id = extract_element(element, meta, counters)
+            .filter(keep_element)
+            .map(|mut ui_elem| {
+                ui_elem.parent_id = parent_id.map(|s| s.to_string());
+                let id = ui_elem.id.clone();
+                out.push(ui_elem);
+                id
+            });
+        let child_parent = current_id.as_deref().or(parent_id);
+
+        let Some(children) = attr(element, "AXChildren").and_then(|c| c.downcast::<CFArray>()) else {
+            return;
+        };
+        for child in children.iter() {
+            let child = unsafe { CFType::wrap_under_get_rule(*child as CFTypeRef) };
+            walk_tree(&child, meta, child_parent, depth + 1, out, counters);
+        }
+    }
+
+    fn extract_element(
+        element: &CFType,
+        meta: &ScreenshotMeta,
+        counters: &mut std::collections::HashMap<String, u32>,
+    ) -> Option<UIElement> {
+        let role = attr_string(element, "AXRole")?;
+        let pos: Point = attr_value(element, "AXPosition", kAXValueTypeCGPoint)?;
+        let size: Size = attr_value(element, "AXSize", kAXValueTypeCGSize)?;
+        let name = ["AXTitle", "AXDescription"]
+            .into_iter()
+            .find_map(|a| attr_string(element, a).filter(|s| !s.trim().is_empty()))
+            .or_else(|| {
+                (role == "AXStaticText")
+                    .then(|| attr_string(element, "AXValue"))
+                    .flatten()
+                    .filter(|s| !s.trim().is_empty())
+            });
+
+        let node_type = role_to_element(&role);
+        let prefix = element_type_prefix(&node_type);
+        let count = counters.entry(prefix.to_string()).or_insert(0);
+        *count += 1;
+        let id = format!("ax_{}_{}", prefix, count);
+
+        // AX frames are global display points — desktop coordinates on macOS.
+        let (x1, y1) = meta.desktop_to_normalized(pos.x, pos.y);
+        let (x2, y2) = meta.desktop_to_normalized(pos.x + size.width, pos.y + size.height);
+        let (x1, y1) = (x1.clamp(0.0, 1.0) as f32, y1.clamp(0.0, 1.0) as f32);
+        let (x2, y2) = (x2.clamp(0.0, 1.0) as f32, y2.clamp(0.0, 1.0) as f32);
+
+        Some(UIElement {
+            id,
+            node_type,
+            bbox: [x1, y1, x2, y2],
+            content: name,
+            confidence: 0.9,
+            source: ElementSource::Uia,
+            source_confidence: UIElement::single_source(ElementSource::Uia, 0.9),
+            parent_id: None, // set later in walk_tree
+        })
+    }
+
+    fn attr(element: &CFType, name: &'static str) -> Option<CFType> {
+        let name = CFString::from_static_string(name);
+        let mut value: CFTypeRef = std::ptr::null();
+        let err = unsafe {
+            AXUIElementCopyAttributeValue(
+                element.as_CFTypeRef() as AXUIElementRef,
+                name.as_concrete_TypeRef(),
+                &mut value,
+            )
+        };
+        (err == kAXErrorSuccess && !value.is_null()).then(|| unsafe { CFType::wrap_under_create_rule(value) })
+    }
+
+    fn attr_string(element: &CFType, name: &'static str) -> Option<String> {
+        attr(element, name)?.downcast::<CFString>().map(|s| s.to_string())
+    }
+
+    fn attr_value<T: Default>(element: &CFType, name: &'static str, kind: AXValueType) -> Option<T> {
+        let value = attr(element, name)?;
+        let mut out = T::default();
+        let ok = unsafe {
+            AXValueGetValue(value.as_CFTypeRef() as AXValueRef, kind, &mut out as *mut T as *mut std::ffi::c_void)
+        };
+        (ok != 0).then_some(out)
+    }
+
+    fn role_to_element(role: &str) -> ElementType {
+        match role {
+            "AXButton" | "AXMenuButton" | "AXDisclosureTriangle" => ElementType::Button,
+            "AXTextField" | "AXTextArea" | "AXSearchField" | "AXSecureTextField" => ElementType::Input,
+            "AXLink" => ElementType::Link,
+            "AXCheckBox" | "AXSwitch" => ElementType::Checkbox,
+            "AXRadioButton" => ElementType::Radio,
+            "AXPopUpButton" | "AXComboBox" => ElementType::Select,
+            "AXMenu" | "AXMenuBar" => ElementType::Menu,
+            "AXMenuItem" | "AXMenuBarItem" | "AXRow" | "AXCell" => ElementType::MenuItem,
+            "AXStaticText" | "AXHeading" => ElementType::Text,
+            "AXImage" => ElementType::Image,
+            "AXGroup" | "AXWindow" | "AXScrollArea" | "AXSplitGroup" | "AXToolbar" | "AXTabGroup"
+            | "AXList" | "AXTable" | "AXOutline" | "AXSlider" | "AXProgressIndicator" => ElementType::Container,
+            _ => ElementType::Unknown,
         }
     }
 }
@@ -386,7 +601,7 @@ mod win {
 // ── Async wrapper ───────────────────────────────────────────────────────────
 
 /// Async entry point: spawns collection on a blocking thread.
-#[cfg(target_os = "windows")]
+#[cfg(all(target_os = "windows", feature = "uia"))]
 pub async fn collect_ui_elements(meta: &ScreenshotMeta) -> SeeClawResult<Vec<UIElement>> {
     let meta = meta.clone();
     tokio::task::spawn_blocking(move || win::collect_elements_sync(&meta))
@@ -394,11 +609,71 @@ pub async fn collect_ui_elements(meta: &ScreenshotMeta) -> SeeClawResult<Vec<UIE
         .map_err(|e| crate::errors::SeeClawError::Perception(format!("join: {e}")))?
 }
 
-#[cfg(not(target_os = "windows"))]
+#[cfg(all(target_os = "macos", feature = "uia"))]
+pub async fn collect_ui_elements(meta: &ScreenshotMeta) -> SeeClawResult<Vec<UIElement>> {
+    let meta = meta.clone();
+    tokio::task::spawn_blocking(move || mac::collect_elements_sync(&meta))
+        .await
+        .map_err(|e| crate::errors::SeeClawError::Perception(format!("join: {e}")))?
+}
+
+#[cfg(not(all(any(target_os = "windows", target_os = "macos"), feature = "uia")))]
 pub async fn collect_ui_elements(_meta: &ScreenshotMeta) -> SeeClawResult<Vec<UIElement>> {
     Ok(Vec::new())
 }
 
+/// The element that currently has keyboard focus.
+#[derive(Debug, Clone)]
+pub struct FocusedElement {
+    /// Accessible name (may be empty for unlabeled controls).
+    pub name: String,
+    pub node_type: ElementType,
+    /// Screen rectangle `[left, top, right, bottom]` in desktop coordinates.
+    pub rect: Option<[i32; 4]>,
+}
+
+/// Read the focused element from UIA. `Ok(None)` when nothing has focus;
+/// an error on platforms without UI Automation.
+#[cfg(all(target_os = "windows", feature = "uia"))]
+pub async fn focused_element() -> SeeClawResult<Option<FocusedElement>> {
+    tokio::task::spawn_blocking(win::focused_element_sync)
+        .await
+        .map_err(|e| crate::errors::SeeClawError::Perception(format!("join: {e}")))?
+}
+
+#[cfg(not(all(target_os = "windows", feature = "uia")))]
+pub async fn focused_element() -> SeeClawResult<Option<FocusedElement>> {
+    Err(crate::errors::SeeClawError::Perception(
+        "focused element requires Windows UI Automation".into(),
+    ))
+}
+
+/// [`VisionParser`] over the OS accessibility tree.
+pub struct UiaParser;
+
+#[async_trait::async_trait]
+impl VisionParser for UiaParser {
+    fn name(&self) -> &str {
+        "uia"
+    }
+
+    async fn parse(
+        &self,
+        _image_bytes: &[u8],
+        meta: &ScreenshotMeta,
+    ) -> SeeClawResult<PerceptionContext> {
+        let elements = collect_ui_elements(meta).await?;
+        Ok(PerceptionContext {
+            image_base64: None,
+            elements,
+            resolution: (meta.physical_width, meta.physical_height),
+            meta: meta.clone(),
+            source: PerceptionSource::Accessibility,
+            timings: Vec::new(),
+        })
+    }
+}
+
 // ── Merge YOLO + UIA ────────────────────────────────────────────────────────
 
 /// Merge YOLO detections with UIA elements.
@@ -425,6 +700,14 @@ pub fn merge_detections(
             if matched.content.is_none() {
                 matched.content = uia_elem.content.clone();
             }
+            for (source, conf) in uia_elem.source_confidence {
+                let entry = matched.source_confidence.entry(source).or_insert(conf);
+                *entry = entry.max(conf);
+            }
+            if matched.source_confidence.len() > 1 {
+                matched.source = ElementSource::Merged;
+            }
+            matched.confidence = matched.confidence.max(uia_elem.confidence);
         } else {
             // No YOLO match — add the UIA element
             yolo.push(uia_elem);
@@ -432,7 +715,7 @@ pub fn merge_detections(
     }
 }
 
-fn bbox_iou(a: &[f32; 4], b: &[f32; 4]) -> f32 {
+pub(crate) fn bbox_iou(a: &[f32; 4], b: &[f32; 4]) -> f32 {
     let ix1 = a[0].max(b[0]);
     let iy1 = a[1].max(b[1]);
     let ix2 = a[2].min(b[2]);
@@ -3,13 +3,26 @@
 /// Loads a YOLOv8 nano ONNX model and runs detection on screenshots.
 /// Falls back gracefully if the model file is missing.
 use crate::errors::{SeeClawError, SeeClawResult};
-use crate::perception::types::{ElementType, UIElement};
+use crate::perception::traits::VisionParser;
+use crate::perception::types::{
+    ElementSource, ElementType, PerceptionContext, PerceptionSource, ScreenshotMeta, UIElement,
+};
 
 use ndarray::Array4;
+#[cfg(feature = "coreml")]
+use ort::ep::CoreML;
+#[cfg(feature = "cuda")]
+use ort::ep::CUDA;
+#[cfg(feature = "directml")]
+use ort::ep::DirectML;
+#[cfg(any(feature = "cuda", feature = "directml", feature = "coreml"))]
+use ort::ep::ExecutionProvider;
+use ort::ep::ExecutionProviderDispatch;
 use ort::session::Session;
-use ort::session::builder::GraphOptimizationLevel;
+use ort::session::builder::{GraphOptimizationLevel, SessionBuilder};
 use ort::value::Tensor;
 use std::path::Path;
+use std::sync::{Arc, Mutex};
 
 /// Raw detection before NMS and ID assignment.
 #[derive(Debug, Clone)]
@@ -22,6 +35,8 @@ struct RawDetection {
 /// Holds the ONNX Runtime session and inference configuration.
 pub struct YoloDetector {
     session: Session,
+    /// Execution provider the session runs on ("cuda", "directml", "coreml", "cpu").
+    execution_provider: &'static str,
     input_size: u32,
     conf_threshold: f32,
     iou_threshold: f32,
@@ -35,14 +50,15 @@ impl YoloDetector {
         conf_threshold: f32,
         iou_threshold: f32,
         class_names: Vec<String>,
+        execution_providers: &[String],
     ) -> Option<Self> {
         if !Path::new(model_path).exists() {
             tracing::warn!(path = %model_path, "YOLO model not found — detection disabled");
             return None;
         }
-        match Self::build(model_path, conf_threshold, iou_threshold, class_names) {
+        match Self::build(model_path, conf_threshold, iou_threshold, class_names, execution_providers) {
             Ok(det) => {
-                tracing::info!(path = %model_path, "YOLO detector loaded");
+                tracing::info!(path = %model_path, execution_provider = det.execution_provider, "YOLO detector loaded");
                 Some(det)
             }
             Err(e) => {
@@ -52,14 +68,40 @@ impl YoloDetector {
         }
     }
 
+    /// Build the detector described by `[perception]`, or `None` when YOLO is
+    /// disabled or the model cannot be loaded (callers fall back to SoM grid).
+    pub fn from_config(cfg: &crate::config::PerceptionConfig) -> Option<Self> {
+        if !cfg.use_yolo {
+            return None;
+        }
+        let class_names = if cfg.class_names.is_empty() {
+            default_ui_class_names()
+        } else {
+            cfg.class_names.clone()
+        };
+        Self::try_new(
+            &cfg.yolo_model_path,
+            cfg.confidence_threshold,
+            cfg.iou_threshold,
+            class_names,
+            &cfg.execution_providers,
+        )
+    }
+
+    /// Execution provider the session runs on.
+    pub fn execution_provider(&self) -> &'static str {
+        self.execution_provider
+    }
+
     fn build(
         model_path: &str,
         conf_threshold: f32,
         iou_threshold: f32,
         class_names: Vec<String>,
+        execution_providers: &[String],
     ) -> SeeClawResult<Self> {
-        let session = Session::builder()
-            .map_err(|e| SeeClawError::Perception(format!("ort session builder: {e}")))?
+        let (builder, execution_provider) = session_builder(execution_providers)?;
+        let session = builder
             .with_optimization_level(GraphOptimizationLevel::Level3)
             .map_err(|e| SeeClawError::Perception(format!("ort opt-level: {e}")))?
             .commit_from_file(model_path)
@@ -67,6 +109,7 @@ impl YoloDetector {
 
         Ok(Self {
             session,
+            execution_provider,
             input_size: 640,
             conf_threshold,
             iou_threshold,
@@ -268,6 +311,8 @@ impl YoloDetector {
                 bbox: det.bbox,
                 content: None,
                 confidence: det.confidence,
+                source: ElementSource::Yolo,
+                source_confidence: UIElement::single_source(ElementSource::Yolo, det.confidence),
                 parent_id: None,
             });
         }
@@ -326,6 +371,52 @@ impl YoloDetector {
 
 // ── Utilities ────────────────────────────────────────────────────────────────
 
+/// A session builder on the first of `preferred` ("cuda", "directml",
+/// "coreml") that registers, or on CPU when none does. GPU providers need
+/// the matching crate feature and runtime libraries; anything missing is
+/// logged and skipped.
+fn session_builder(preferred: &[String]) -> SeeClawResult<(SessionBuilder, &'static str)> {
+    let new_builder = || {
+        Session::builder().map_err(|e| SeeClawError::Perception(format!("ort session builder: {e}")))
+    };
+    for name in preferred {
+        let (label, ep): (&'static str, Option<ExecutionProviderDispatch>) = match name.trim().to_lowercase().as_str() {
+            #[cfg(feature = "cuda")]
+            "cuda" => ("cuda", available(CUDA::default())),
+            #[cfg(feature = "directml")]
+            "directml" | "dml" => ("directml", available(DirectML::default())),
+            #[cfg(feature = "coreml")]
+            "coreml" => ("coreml", available(CoreML::default())),
+            #[cfg(not(feature = "cuda"))]
+            "cuda" => ("cuda", None),
+            #[cfg(not(feature = "directml"))]
+            "directml" | "dml" => ("directml", None),
+            #[cfg(not(feature = "coreml"))]
+            "coreml" => ("coreml", None),
+            "cpu" => break,
+            other => {
+                tracing::warn!(provider = %other, "unknown ONNX execution provider — skipped");
+                continue;
+            }
+        };
+        let Some(ep) = ep else {
+            tracing::warn!(provider = label, "ONNX execution provider not available in this build / on this machine — trying next");
+            continue;
+        };
+        match new_builder()?.with_execution_providers([ep.error_on_failure()]) {
+            Ok(builder) => return Ok((builder, label)),
+            Err(e) => tracing::warn!(provider = label, error = %e, "ONNX execution provider unavailable — trying next"),
+        }
+    }
+    Ok((new_builder()?, "cpu"))
+}
+
+/// `ep` ready to register, or `None` when this machine lacks it.
+#[cfg(any(feature = "cuda", feature = "directml", feature = "coreml"))]
+fn available<E: ExecutionProvider + Into<ExecutionProviderDispatch>>(ep: E) -> Option<ExecutionProviderDispatch> {
+    ep.is_available().unwrap_or(false).then(|| ep.into())
+}
+
 fn iou(a: &[f32; 4], b: &[f32; 4]) -> f32 {
     let ix1 = a[0].max(b[0]);
     let iy1 = a[1].max(b[1]);
@@ -381,3 +472,51 @@ pub fn coco_class_names() -> Vec<String> {
         "hair drier","toothbrush",
     ].into_iter().map(String::from).collect()
 }
+
+// ── VisionParser adapter ────────────────────────────────────────────────────
+
+/// [`VisionParser`] backed by a [`YoloDetector`].
+/// Inference runs on a blocking thread; the detector sits behind a mutex
+/// because `detect` needs `&mut self`.
+pub struct YoloParser {
+    detector: Arc<Mutex<YoloDetector>>,
+}
+
+impl YoloParser {
+    pub fn new(detector: YoloDetector) -> Self {
+        Self { detector: Arc::new(Mutex::new(detector)) }
+    }
+}
+
+#[async_trait::async_trait]
+impl VisionParser for YoloParser {
+    fn name(&self) -> &str {
+        "yolo"
+    }
+
+    async fn parse(
+        &self,
+        image_bytes: &[u8],
+        meta: &ScreenshotMeta,
+    ) -> SeeClawResult<PerceptionContext> {
+        let detector = self.detector.clone();
+        let bytes = image_bytes.to_vec();
+        let elements = tokio::task::spawn_blocking(move || {
+            let mut det = detector
+                .lock()
+                .map_err(|_| SeeClawError::Perception("YOLO detector mutex poisoned".into()))?;
+            det.detect(&bytes)
+        })
+        .await
+        .map_err(|e| SeeClawError::Perception(format!("join: {e}")))??;
+
+        Ok(PerceptionContext {
+            image_base64: None,
+            elements,
+            resolution: (meta.physical_width, meta.physical_height),
+            meta: meta.clone(),
+            source: PerceptionSource::Onnx,
+            timings: Vec::new(),
+        })
+    }
+}
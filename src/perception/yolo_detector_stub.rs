@@ -0,0 +1,64 @@
+/// YOLO detector stand-in used when the crate is built without the `onnx`
+/// feature. Mirrors the public API of `yolo_detector.rs` so callers compile
+/// unchanged; no detector can ever be constructed, so perception falls back
+/// to UI Automation and the SoM grid.
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::perception::traits::VisionParser;
+use crate::perception::types::{PerceptionContext, ScreenshotMeta, UIElement};
+
+pub struct YoloDetector {
+    _private: (),
+}
+
+impl YoloDetector {
+    pub fn try_new(
+        model_path: &str,
+        _conf_threshold: f32,
+        _iou_threshold: f32,
+        _class_names: Vec<String>,
+        _execution_providers: &[String],
+    ) -> Option<Self> {
+        tracing::warn!(path = %model_path, "built without the `onnx` feature — YOLO detection disabled");
+        None
+    }
+
+    pub fn from_config(cfg: &crate::config::PerceptionConfig) -> Option<Self> {
+        if cfg.use_yolo {
+            tracing::warn!("use_yolo is set but this build has no `onnx` feature — ignoring");
+        }
+        None
+    }
+
+    pub fn execution_provider(&self) -> &'static str {
+        "cpu"
+    }
+
+    pub fn detect(&mut self, _image_bytes: &[u8]) -> SeeClawResult<Vec<UIElement>> {
+        Err(SeeClawError::Perception("YOLO support not compiled in".into()))
+    }
+}
+
+pub struct YoloParser {
+    _detector: YoloDetector,
+}
+
+impl YoloParser {
+    pub fn new(detector: YoloDetector) -> Self {
+        Self { _detector: detector }
+    }
+}
+
+#[async_trait::async_trait]
+impl VisionParser for YoloParser {
+    fn name(&self) -> &str {
+        "yolo"
+    }
+
+    async fn parse(
+        &self,
+        _image_bytes: &[u8],
+        _meta: &ScreenshotMeta,
+    ) -> SeeClawResult<PerceptionContext> {
+        Err(SeeClawError::Perception("YOLO support not compiled in".into()))
+    }
+}
@@ -0,0 +1,89 @@
+//! Named config profiles.
+//!
+//! A profile is a partial config under `[profiles.<name>]` that is merged
+//! over the rest of config.toml, e.g.
+//!
+//! ```toml
+//! active_profile = "cheap"
+//!
+//! [profiles.cheap.llm.roles.vision]
+//! provider = "zhipu"
+//! model = "glm-4v-flash"
+//!
+//! [profiles.offline.llm]
+//! active_provider = "ollama"
+//! [profiles.offline.perception]
+//! grid_n = 8
+//! ```
+//!
+//! Tables merge key by key; any other value replaces the base one.
+//! `active_profile` applies to every task; `start_task` can name another
+//! profile for a single goal.
+
+use std::sync::RwLock;
+
+use serde::Serialize;
+
+use crate::config::AppConfig;
+use crate::errors::{SeeClawError, SeeClawResult};
+
+/// Profile the running engine was last switched to ("" = base config).
+static ACTIVE: RwLock<String> = RwLock::new(String::new());
+
+/// Record the active profile (on startup and by `set_active_profile`).
+pub fn set_active(name: &str) {
+    *ACTIVE.write().unwrap_or_else(|e| e.into_inner()) = name.to_string();
+}
+
+pub fn active() -> String {
+    ACTIVE.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileList {
+    /// Empty when no profile is applied.
+    pub active: String,
+    pub profiles: Vec<String>,
+}
+
+pub fn list(cfg: &AppConfig) -> ProfileList {
+    ProfileList { active: cfg.active_profile.clone(), profiles: cfg.profiles.keys().cloned().collect() }
+}
+
+/// `base` with its `active_profile` applied.
+pub fn effective(base: &AppConfig) -> SeeClawResult<AppConfig> {
+    apply(base, &base.active_profile)
+}
+
+/// `base` with profile `name` merged over it; `""` returns `base` unchanged.
+pub fn apply(base: &AppConfig, name: &str) -> SeeClawResult<AppConfig> {
+    if name.is_empty() {
+        return Ok(base.clone());
+    }
+    let overlay = base.profiles.get(name).ok_or_else(|| {
+        let known: Vec<&str> = base.profiles.keys().map(String::as_str).collect();
+        SeeClawError::Config(format!("unknown profile '{name}' (known: {})", known.join(", ")))
+    })?;
+    let mut merged = toml::Value::try_from(base)?;
+    merge(&mut merged, &toml::Value::Table(overlay.clone()));
+    let mut cfg: AppConfig = merged.try_into()?;
+    cfg.active_profile = name.to_string();
+    cfg.profiles = base.profiles.clone();
+    Ok(cfg)
+}
+
+fn merge(base: &mut toml::Value, overlay: &toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay.clone(),
+    }
+}
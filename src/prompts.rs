@@ -0,0 +1,93 @@
+//! Runtime prompt overrides.
+//!
+//! Every prompt is compiled in with `include_str!`. `[prompts] dir` names a
+//! folder whose files replace the built-in prompt of the same name
+//! (`planner.md`, `chat_agent.md`, `verifier.md`, …), and `tools_file` /
+//! `experience_summary_template` point at single files. A file is re-read
+//! whenever its modification time changes, so edits apply to the next model
+//! call without a restart; a missing or unreadable file falls back to the
+//! embedded default.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, RwLock};
+use std::time::SystemTime;
+
+use crate::config::PromptsConfig;
+
+static CONFIG: RwLock<Option<PromptsConfig>> = RwLock::new(None);
+/// Last read contents per file, keyed by path.
+static CACHE: Mutex<Option<HashMap<PathBuf, (SystemTime, String)>>> = Mutex::new(None);
+
+/// Apply the `[prompts]` settings (on startup and whenever config changes).
+pub fn configure(cfg: &PromptsConfig) {
+    *CONFIG.write().unwrap_or_else(|e| e.into_inner()) = Some(cfg.clone());
+}
+
+/// The prompt `name` (a file name such as `"planner.md"`) from the override
+/// folder, or `embedded` when there is none.
+pub fn get(name: &str, embedded: &'static str) -> Cow<'static, str> {
+    let path = {
+        let cfg = CONFIG.read().unwrap_or_else(|e| e.into_inner());
+        match cfg.as_ref().map(|c| c.dir.trim()).filter(|d| !d.is_empty()) {
+            Some(dir) => PathBuf::from(dir).join(name),
+            None => return Cow::Borrowed(embedded),
+        }
+    };
+    load(&path).map(Cow::Owned).unwrap_or(Cow::Borrowed(embedded))
+}
+
+/// Built-in tool definitions (`tools_file`), or `embedded`.
+pub fn tools(embedded: &'static str) -> Cow<'static, str> {
+    configured_file(|c| &c.tools_file, "builtin.json", embedded)
+}
+
+/// Experience summary template (`experience_summary_template`), or `embedded`.
+pub fn experience_summary(embedded: &'static str) -> Cow<'static, str> {
+    configured_file(|c| &c.experience_summary_template, "experience_summary.md", embedded)
+}
+
+/// A prompt with its own config key; the override folder still applies
+/// when the key is empty.
+fn configured_file(
+    key: impl Fn(&PromptsConfig) -> &String,
+    name: &str,
+    embedded: &'static str,
+) -> Cow<'static, str> {
+    let file = {
+        let cfg = CONFIG.read().unwrap_or_else(|e| e.into_inner());
+        cfg.as_ref().map(|c| key(c).trim().to_string()).unwrap_or_default()
+    };
+    if file.is_empty() {
+        return get(name, embedded);
+    }
+    load(&PathBuf::from(file)).map(Cow::Owned).unwrap_or(Cow::Borrowed(embedded))
+}
+
+/// File contents, re-read only when the modification time changed.
+fn load(path: &PathBuf) -> Option<String> {
+    let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let cache = cache.get_or_insert_with(HashMap::new);
+    if let Some((seen, text)) = cache.get(path) {
+        if *seen == modified {
+            return Some(text.clone());
+        }
+    }
+    match std::fs::read_to_string(path) {
+        Ok(text) if !text.trim().is_empty() => {
+            tracing::info!(path = %path.display(), "prompts: loaded override");
+            cache.insert(path.clone(), (modified, text.clone()));
+            Some(text)
+        }
+        Ok(_) => {
+            tracing::warn!(path = %path.display(), "prompts: override is empty — using built-in");
+            None
+        }
+        Err(e) => {
+            tracing::warn!(path = %path.display(), error = %e, "prompts: cannot read override — using built-in");
+            None
+        }
+    }
+}
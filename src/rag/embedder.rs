@@ -1,6 +1,292 @@
-// Text embedder for RAG — full implementation in Phase 9.
+//! Text embedder for RAG.
+//!
+//! Prefers a local ONNX sentence-embedding model (e.g. all-MiniLM-L6-v2
+//! exported with its `tokenizer.json`), run on the same ort stack as the
+//! YOLO detector. When no local model is present — or the crate is built
+//! without the `onnx` feature — texts are sent to an OpenAI-compatible
+//! `/embeddings` endpoint instead.
+use crate::config::AppConfig;
 use crate::errors::{SeeClawError, SeeClawResult};
 
-pub async fn embed(_text: &str) -> SeeClawResult<Vec<f32>> {
-    Err(SeeClawError::Rag("Embedder not implemented yet (Phase 9)".to_string()))
+pub enum Embedder {
+    #[cfg(feature = "onnx")]
+    Local(std::sync::Arc<local::LocalEmbedder>),
+    Api(ApiEmbedder),
+}
+
+impl Embedder {
+    /// Local model if it loads, otherwise the API fallback. `None` when
+    /// neither is available (no model and no provider with an endpoint).
+    pub fn from_config(config: &AppConfig) -> Option<Self> {
+        #[cfg(feature = "onnx")]
+        {
+            if let Some(local) = local::LocalEmbedder::try_new(&config.rag) {
+                return Some(Self::Local(std::sync::Arc::new(local)));
+            }
+        }
+        ApiEmbedder::from_config(config).map(Self::Api)
+    }
+
+    pub async fn embed(&self, text: &str) -> SeeClawResult<Vec<f32>> {
+        self.embed_batch(&[text.to_string()])
+            .await?
+            .pop()
+            .ok_or_else(|| SeeClawError::Rag("embedder returned no vector".into()))
+    }
+
+    /// Embed several texts; the result is in input order.
+    pub async fn embed_batch(&self, texts: &[String]) -> SeeClawResult<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        match self {
+            #[cfg(feature = "onnx")]
+            Self::Local(local) => {
+                let local = local.clone();
+                let texts = texts.to_vec();
+                tokio::task::spawn_blocking(move || local.embed_batch(&texts))
+                    .await
+                    .map_err(|e| SeeClawError::Rag(format!("embed task failed: {e}")))?
+            }
+            Self::Api(api) => api.embed_batch(texts).await,
+        }
+    }
+}
+
+// ── API fallback ───────────────────────────────────────────────────────────
+
+/// OpenAI-compatible `POST {api_base}/embeddings`.
+pub struct ApiEmbedder {
+    client: reqwest::Client,
+    api_base: String,
+    api_key: String,
+    model: String,
+    batch_size: usize,
+}
+
+impl ApiEmbedder {
+    /// Uses `[rag] embedding_provider`, or the active provider if unset.
+    pub fn from_config(config: &AppConfig) -> Option<Self> {
+        let id = if config.rag.embedding_provider.is_empty() {
+            &config.llm.active_provider
+        } else {
+            &config.rag.embedding_provider
+        };
+        let entry = config.llm.providers.get(id)?;
+        if entry.api_base.is_empty() {
+            return None;
+        }
+        Some(Self {
+            client: reqwest::Client::new(),
+            api_base: entry.api_base.trim_end_matches('/').to_string(),
+            api_key: entry.resolve_api_key(id),
+            model: config.rag.embedding_model.clone(),
+            batch_size: config.rag.batch_size.max(1),
+        })
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> SeeClawResult<Vec<Vec<f32>>> {
+        let mut out = Vec::with_capacity(texts.len());
+        for chunk in texts.chunks(self.batch_size) {
+            let url = format!("{}/embeddings", self.api_base);
+            crate::net_audit::record(&url, crate::net_audit::NetPurpose::Embeddings);
+            let resp = self
+                .client
+                .post(url)
+                .bearer_auth(&self.api_key)
+                .json(&serde_json::json!({ "model": self.model, "input": chunk }))
+                .send()
+                .await?;
+            let status = resp.status();
+            if !status.is_success() {
+                let body = resp.text().await.unwrap_or_default();
+                return Err(SeeClawError::Rag(format!("embeddings API {status}: {body}")));
+            }
+            let body: serde_json::Value = resp.json().await?;
+            let mut data: Vec<(u64, Vec<f32>)> = body["data"]
+                .as_array()
+                .ok_or_else(|| SeeClawError::Rag("embeddings response without data".into()))?
+                .iter()
+                .map(|d| {
+                    let vec = d["embedding"]
+                        .as_array()
+                        .map(|a| a.iter().filter_map(|v| v.as_f64()).map(|v| v as f32).collect())
+                        .unwrap_or_default();
+                    (d["index"].as_u64().unwrap_or(0), vec)
+                })
+                .collect();
+            if data.len() != chunk.len() {
+                return Err(SeeClawError::Rag(format!(
+                    "embeddings API returned {} vectors for {} inputs",
+                    data.len(),
+                    chunk.len()
+                )));
+            }
+            data.sort_by_key(|(i, _)| *i);
+            out.extend(data.into_iter().map(|(_, v)| v));
+        }
+        Ok(out)
+    }
+}
+
+// ── Local ONNX model ───────────────────────────────────────────────────────
+
+#[cfg(feature = "onnx")]
+mod local {
+    use std::sync::Mutex;
+
+    use ndarray::Array2;
+    use ort::session::builder::GraphOptimizationLevel;
+    use ort::session::Session;
+    use ort::value::Tensor;
+    use tokenizers::Tokenizer;
+
+    use crate::config::RagConfig;
+    use crate::errors::{SeeClawError, SeeClawResult};
+
+    pub struct LocalEmbedder {
+        session: Mutex<Session>,
+        tokenizer: Tokenizer,
+        max_seq_len: usize,
+        batch_size: usize,
+        /// BERT-style models take a third `token_type_ids` input.
+        wants_token_types: bool,
+    }
+
+    impl LocalEmbedder {
+        /// Load the model and tokenizer; `None` (with a log line) if either is missing.
+        pub fn try_new(cfg: &RagConfig) -> Option<Self> {
+            if !std::path::Path::new(&cfg.embedding_model_path).exists() {
+                tracing::info!(path = %cfg.embedding_model_path, "no local embedding model — using API embeddings");
+                return None;
+            }
+            match Self::build(cfg) {
+                Ok(e) => {
+                    tracing::info!(path = %cfg.embedding_model_path, "local embedding model loaded");
+                    Some(e)
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, "local embedding model failed to load — using API embeddings");
+                    None
+                }
+            }
+        }
+
+        fn build(cfg: &RagConfig) -> SeeClawResult<Self> {
+            let session = Session::builder()
+                .map_err(|e| SeeClawError::Rag(format!("ort session builder: {e}")))?
+                .with_optimization_level(GraphOptimizationLevel::Level3)
+                .map_err(|e| SeeClawError::Rag(format!("ort opt-level: {e}")))?
+                .commit_from_file(&cfg.embedding_model_path)
+                .map_err(|e| SeeClawError::Rag(format!("ort load model: {e}")))?;
+            let tokenizer = Tokenizer::from_file(&cfg.tokenizer_path)
+                .map_err(|e| SeeClawError::Rag(format!("load tokenizer '{}': {e}", cfg.tokenizer_path)))?;
+            let wants_token_types = session.inputs().iter().any(|i| i.name() == "token_type_ids");
+            Ok(Self {
+                session: Mutex::new(session),
+                tokenizer,
+                max_seq_len: cfg.max_seq_len.max(8),
+                batch_size: cfg.batch_size.max(1),
+                wants_token_types,
+            })
+        }
+
+        /// Blocking; call from `spawn_blocking`.
+        pub fn embed_batch(&self, texts: &[String]) -> SeeClawResult<Vec<Vec<f32>>> {
+            let mut out = Vec::with_capacity(texts.len());
+            for chunk in texts.chunks(self.batch_size) {
+                out.extend(self.embed_chunk(chunk)?);
+            }
+            Ok(out)
+        }
+
+        fn embed_chunk(&self, texts: &[String]) -> SeeClawResult<Vec<Vec<f32>>> {
+            let encodings = self
+                .tokenizer
+                .encode_batch(texts.to_vec(), true)
+                .map_err(|e| SeeClawError::Rag(format!("tokenize: {e}")))?;
+
+            // Truncate to max_seq_len and right-pad to the longest sequence.
+            let seq = encodings
+                .iter()
+                .map(|e| e.get_ids().len().min(self.max_seq_len))
+                .max()
+                .unwrap_or(1)
+                .max(1);
+            let batch = encodings.len();
+            let mut ids = Array2::<i64>::zeros((batch, seq));
+            let mut mask = Array2::<i64>::zeros((batch, seq));
+            let mut types = Array2::<i64>::zeros((batch, seq));
+            for (row, enc) in encodings.iter().enumerate() {
+                let n = enc.get_ids().len().min(seq);
+                for col in 0..n {
+                    ids[[row, col]] = enc.get_ids()[col] as i64;
+                    mask[[row, col]] = enc.get_attention_mask()[col] as i64;
+                    types[[row, col]] = enc.get_type_ids()[col] as i64;
+                }
+            }
+
+            let tensor = |a: Array2<i64>| {
+                Tensor::from_array(a).map_err(|e| SeeClawError::Rag(format!("ort tensor: {e}")))
+            };
+            let mut session = self
+                .session
+                .lock()
+                .map_err(|_| SeeClawError::Rag("embedding session poisoned".into()))?;
+            let hidden = {
+                let outputs = if self.wants_token_types {
+                    session.run(ort::inputs![
+                        "input_ids" => tensor(ids)?,
+                        "attention_mask" => tensor(mask.clone())?,
+                        "token_type_ids" => tensor(types)?,
+                    ])
+                } else {
+                    session.run(ort::inputs![
+                        "input_ids" => tensor(ids)?,
+                        "attention_mask" => tensor(mask.clone())?,
+                    ])
+                }
+                .map_err(|e| SeeClawError::Rag(format!("ort run: {e}")))?;
+                outputs[0]
+                    .try_extract_array::<f32>()
+                    .map_err(|e| SeeClawError::Rag(format!("extract tensor: {e}")))?
+                    .to_owned()
+            };
+
+            let vectors = match hidden.ndim() {
+                // Model already pools: [batch, dim].
+                2 => hidden.outer_iter().map(|row| row.iter().copied().collect::<Vec<f32>>()).collect::<Vec<_>>(),
+                // Token embeddings [batch, seq, dim]: attention-masked mean pooling.
+                3 => hidden
+                    .outer_iter()
+                    .enumerate()
+                    .map(|(row, tokens)| {
+                        let dim = tokens.shape()[1];
+                        let mut sum = vec![0f32; dim];
+                        let mut count = 0f32;
+                        for (t, token) in tokens.outer_iter().enumerate() {
+                            if mask[[row, t]] == 0 {
+                                continue;
+                            }
+                            count += 1.0;
+                            for (s, v) in sum.iter_mut().zip(token.iter()) {
+                                *s += v;
+                            }
+                        }
+                        sum.iter().map(|s| s / count.max(1.0)).collect()
+                    })
+                    .collect(),
+                n => return Err(SeeClawError::Rag(format!("unexpected embedding output rank {n}"))),
+            };
+            Ok(vectors.into_iter().map(l2_normalize).collect())
+        }
+    }
+
+    fn l2_normalize(mut v: Vec<f32>) -> Vec<f32> {
+        let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            v.iter_mut().for_each(|x| *x /= norm);
+        }
+        v
+    }
 }
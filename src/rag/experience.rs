@@ -1,6 +1,130 @@
-// Experience document writer — full implementation in Phase 9.
+//! Experience memory — task post-mortems written after each run and recalled
+//! into the planner prompt for similar goals.
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+
+use crate::config::AppConfig;
 use crate::errors::{SeeClawError, SeeClawResult};
+use crate::events::{NullSink, SharedEventSink};
+use crate::llm::registry::ProviderRegistry;
+use crate::llm::types::{ChatMessage, MessageContent};
+use crate::rag::embedder::Embedder;
+use crate::rag::index::{RagHit, RagIndex};
+
+const EXPERIENCE_SUMMARY_PROMPT: &str = include_str!("../../prompts/system/experience_summary.md");
+
+/// How many past experiences are injected into the planner prompt.
+pub const RECALL_TOP_K: usize = 3;
+/// Experiences below this cosine similarity are not worth the tokens.
+const MIN_RECALL_SCORE: f32 = 0.35;
+/// Only the tail of long execution logs goes into the summary prompt.
+const MAX_LOG_LINES: usize = 40;
+
+pub struct ExperienceStore {
+    index: RagIndex,
+    embedder: Embedder,
+}
+
+impl ExperienceStore {
+    /// `None` (logged) when no embedder is available or the index cannot open.
+    pub fn open(config: &AppConfig) -> Option<Self> {
+        let embedder = Embedder::from_config(config).or_else(|| {
+            tracing::info!("no embedder available — experience memory disabled");
+            None
+        })?;
+        match RagIndex::open_default() {
+            Ok(index) => Some(Self { index, embedder }),
+            Err(e) => {
+                tracing::warn!(error = %e, "experience index unavailable — memory disabled");
+                None
+            }
+        }
+    }
+
+    /// Summarise a finished task into an experience entry, embed and store it.
+    pub async fn append_experience(
+        &self,
+        registry: &Arc<Mutex<ProviderRegistry>>,
+        goal: &str,
+        steps_log: &[String],
+        final_result: &str,
+    ) -> SeeClawResult<()> {
+        let document = match summarise(registry, goal, steps_log, final_result).await {
+            Ok(doc) if !doc.trim().is_empty() => doc,
+            Ok(_) | Err(_) => fallback_document(goal, final_result),
+        };
+        let vector = self.embedder.embed(&format!("{goal}\n{document}")).await?;
+        let id = uuid::Uuid::new_v4().to_string();
+        self.index.insert(&id, &vector, &document).await?;
+        tracing::info!(id = %id, "experience stored");
+        Ok(())
+    }
+
+    /// Past experiences similar to `goal`, best first.
+    pub async fn recall(&self, goal: &str, top_k: usize) -> SeeClawResult<Vec<RagHit>> {
+        let vector = self.embedder.embed(goal).await?;
+        let mut hits = self.index.search(&vector, top_k).await?;
+        hits.retain(|h| h.score >= MIN_RECALL_SCORE);
+        Ok(hits)
+    }
+}
+
+/// Planner prompt section for recalled experiences; empty if there are none.
+pub fn format_for_planner(hits: &[RagHit]) -> String {
+    if hits.is_empty() {
+        return String::new();
+    }
+    let mut out = String::from(
+        "## Past Experience\nLessons from similar earlier tasks. Apply them where relevant; ignore them if the situation differs.\n\n",
+    );
+    for hit in hits {
+        out.push_str(hit.text.trim());
+        out.push_str("\n\n");
+    }
+    out.trim_end().to_string()
+}
+
+async fn summarise(
+    registry: &Arc<Mutex<ProviderRegistry>>,
+    goal: &str,
+    steps_log: &[String],
+    final_result: &str,
+) -> SeeClawResult<String> {
+    let history = steps_log[steps_log.len().saturating_sub(MAX_LOG_LINES)..].join("\n");
+    let prompt = crate::prompts::experience_summary(EXPERIENCE_SUMMARY_PROMPT)
+        .replace("{task_description}", goal)
+        .replace("{execution_history}", if history.is_empty() { "(none)" } else { &history })
+        .replace("{final_result}", final_result);
+
+    let (provider, mut cfg) = {
+        let reg = registry.lock().await;
+        reg.call_config_for_role("chat")?
+    };
+    cfg.silent = true;
+    cfg.stream = false;
+
+    let sink: SharedEventSink = Arc::new(NullSink);
+    let messages = vec![ChatMessage {
+        role: "user".into(),
+        content: MessageContent::Text(prompt),
+        tool_call_id: None,
+        tool_calls: None,
+    }];
+    let response = provider.chat(messages, Vec::new(), &cfg, &sink).await?;
+    let text = response.content.trim().trim_matches('`').trim().to_string();
+    if text.is_empty() {
+        return Err(SeeClawError::Rag("empty experience summary".into()));
+    }
+    Ok(text)
+}
 
-pub async fn append_experience(_title: &str, _content: &str) -> SeeClawResult<()> {
-    Err(SeeClawError::Rag("Experience writer not implemented yet (Phase 9)".to_string()))
+/// Used when the summary LLM call fails — still better than losing the run.
+fn fallback_document(goal: &str, final_result: &str) -> String {
+    format!(
+        "## {} — {}\n- **Result**: {}",
+        chrono::Local::now().format("%Y-%m-%d"),
+        goal,
+        final_result
+    )
 }
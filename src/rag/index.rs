@@ -1,24 +1,158 @@
-// Vector index for RAG — full implementation in Phase 9.
+//! Vector index for RAG — embeddings stored in a local SQLite file.
+//!
+//! Vectors are kept as little-endian `f32` BLOBs and searched by brute-force
+//! cosine similarity. The experience store holds at most a few thousand
+//! entries, so a full scan is cheaper than maintaining an ANN structure.
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use rusqlite::{params, Connection};
+
+use crate::agent_engine::history::data_dir_or_cwd;
 use crate::errors::{SeeClawError, SeeClawResult};
 
-pub struct RagIndex;
+/// One search result.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RagHit {
+    pub id: String,
+    pub text: String,
+    /// Cosine similarity in [-1, 1].
+    pub score: f32,
+}
+
+pub struct RagIndex {
+    conn: Arc<Mutex<Connection>>,
+    path: PathBuf,
+}
 
 impl RagIndex {
-    pub fn new() -> Self {
-        Self
+    /// Open (or create) the index at `SeeClaw/rag/index.sqlite`.
+    pub fn open_default() -> SeeClawResult<Self> {
+        Self::open(data_dir_or_cwd("rag").join("index.sqlite"))
+    }
+
+    /// Open (or create) an index file at `path`.
+    pub fn open(path: impl AsRef<Path>) -> SeeClawResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let conn = Connection::open(&path).map_err(rag_err)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS embeddings (
+                 id         TEXT PRIMARY KEY,
+                 text       TEXT NOT NULL,
+                 dim        INTEGER NOT NULL,
+                 vector     BLOB NOT NULL,
+                 created_at INTEGER NOT NULL
+             );",
+        )
+        .map_err(rag_err)?;
+        tracing::debug!(path = %path.display(), "RAG index opened");
+        Ok(Self { conn: Arc::new(Mutex::new(conn)), path })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Insert or replace the entry `id`.
+    pub async fn insert(&self, id: &str, vec: &[f32], text: &str) -> SeeClawResult<()> {
+        let (id, text, blob, dim) = (id.to_string(), text.to_string(), to_blob(vec), vec.len() as i64);
+        self.with_conn(move |conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO embeddings (id, text, dim, vector, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![id, text, dim, blob, chrono::Utc::now().timestamp()],
+            )
+            .map(|_| ())
+        })
+        .await
+    }
+
+    /// Remove `id`. Returns whether an entry was deleted.
+    pub async fn delete(&self, id: &str) -> SeeClawResult<bool> {
+        let id = id.to_string();
+        self.with_conn(move |conn| {
+            conn.execute("DELETE FROM embeddings WHERE id = ?1", params![id]).map(|n| n > 0)
+        })
+        .await
+    }
+
+    /// Top `top_k` entries by cosine similarity to `query_vec`, best first.
+    /// Entries whose dimension differs from the query are skipped.
+    pub async fn search(&self, query_vec: &[f32], top_k: usize) -> SeeClawResult<Vec<RagHit>> {
+        let query = query_vec.to_vec();
+        self.with_conn(move |conn| {
+            let mut stmt = conn.prepare("SELECT id, text, vector FROM embeddings WHERE dim = ?1")?;
+            let rows = stmt.query_map(params![query.len() as i64], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, Vec<u8>>(2)?))
+            })?;
+            let mut hits = Vec::new();
+            for row in rows {
+                let (id, text, blob) = row?;
+                let score = cosine_similarity(&query, &from_blob(&blob));
+                hits.push(RagHit { id, text, score });
+            }
+            hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+            hits.truncate(top_k);
+            Ok(hits)
+        })
+        .await
     }
 
-    pub async fn search(&self, _query_vec: &[f32], _top_k: usize) -> SeeClawResult<Vec<String>> {
-        Err(SeeClawError::Rag("RAG index not implemented yet (Phase 9)".to_string()))
+    /// Number of stored entries.
+    pub async fn len(&self) -> SeeClawResult<usize> {
+        self.with_conn(|conn| {
+            conn.query_row("SELECT COUNT(*) FROM embeddings", [], |row| row.get::<_, i64>(0))
+                .map(|n| n as usize)
+        })
+        .await
     }
 
-    pub async fn insert(&self, _id: &str, _vec: &[f32], _text: &str) -> SeeClawResult<()> {
-        Err(SeeClawError::Rag("RAG index not implemented yet (Phase 9)".to_string()))
+    pub async fn is_empty(&self) -> SeeClawResult<bool> {
+        Ok(self.len().await? == 0)
     }
+
+    /// Run a blocking SQLite operation off the async runtime.
+    async fn with_conn<T, F>(&self, f: F) -> SeeClawResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> rusqlite::Result<T> + Send + 'static,
+    {
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn
+                .lock()
+                .map_err(|_| SeeClawError::Rag("index connection poisoned".into()))?;
+            f(&conn).map_err(rag_err)
+        })
+        .await
+        .map_err(|e| SeeClawError::Rag(format!("index task failed: {e}")))?
+    }
+}
+
+fn rag_err(e: rusqlite::Error) -> SeeClawError {
+    SeeClawError::Rag(e.to_string())
+}
+
+fn to_blob(vec: &[f32]) -> Vec<u8> {
+    vec.iter().flat_map(|v| v.to_le_bytes()).collect()
 }
 
-impl Default for RagIndex {
-    fn default() -> Self {
-        Self::new()
+fn from_blob(blob: &[u8]) -> Vec<f32> {
+    blob.chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+/// Cosine similarity; 0.0 when either vector has zero norm.
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let (mut dot, mut na, mut nb) = (0.0f32, 0.0f32, 0.0f32);
+    for (x, y) in a.iter().zip(b) {
+        dot += x * y;
+        na += x * x;
+        nb += y * y;
+    }
+    if na == 0.0 || nb == 0.0 {
+        return 0.0;
     }
+    dot / (na.sqrt() * nb.sqrt())
 }
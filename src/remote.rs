@@ -0,0 +1,211 @@
+//! Remote control API — drive the agent over local HTTP and WebSocket.
+//!
+//! Enabled with `[remote] enabled = true` (and the `remote` cargo feature).
+//! Routes, all behind a bearer token (`Authorization: Bearer <token>` or
+//! `?token=<token>`):
+//!
+//! - `POST /tasks` `{"goal": "..."}` — same as `start_task` (queued while busy)
+//! - `POST /stop` — same as `stop_task`
+//! - `POST /approval` `{"approved": true}` — answer a pending approval
+//! - `GET /status` — last agent state, its goal and the task queue
+//! - `GET /events` — WebSocket; every agent event as `{"event", "payload"}`
+//!
+//! The server listens on 127.0.0.1 unless `bind` says otherwise.
+
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::Deserialize;
+use tokio::sync::broadcast;
+
+use crate::agent_engine::state::{AgentEvent, LoopOverrides};
+use crate::agent_engine::task_queue;
+use crate::config::RemoteConfig;
+use crate::events::{ChannelSink, SharedEventSink, TeeSink};
+use crate::AgentHandle;
+
+/// Events buffered per WebSocket client before the slowest ones skip ahead.
+const EVENT_BUFFER: usize = 512;
+
+struct Server {
+    handle: Arc<AgentHandle>,
+    events: Arc<ChannelSink>,
+    token: String,
+    /// Last `agent_state_changed` payload, with the goal of the current task.
+    status: Mutex<serde_json::Value>,
+}
+
+/// Start the server when `[remote]` enables it; returns the sink the agent
+/// loop should emit to (`sink` plus the server's event stream), or `sink`
+/// unchanged when the server is off.
+pub fn start(cfg: &RemoteConfig, handle: Arc<AgentHandle>, sink: SharedEventSink) -> SharedEventSink {
+    if !cfg.enabled {
+        return sink;
+    }
+    let token = cfg.resolve_token();
+    if token.is_empty() {
+        tracing::warn!("remote: enabled but no token set ([remote] token or SEECLAW_REMOTE_TOKEN); not starting");
+        return sink;
+    }
+    let addr: SocketAddr = match cfg.bind.parse() {
+        Ok(a) => a,
+        Err(e) => {
+            tracing::warn!(bind = %cfg.bind, error = %e, "remote: invalid bind address; not starting");
+            return sink;
+        }
+    };
+    if !addr.ip().is_loopback() {
+        tracing::warn!(%addr, "remote: listening on a non-loopback address; other machines can reach the API");
+    }
+
+    let (events, _) = ChannelSink::new(EVENT_BUFFER);
+    let server = Arc::new(Server {
+        handle,
+        events: Arc::new(events),
+        token,
+        status: Mutex::new(serde_json::json!({ "state": "idle" })),
+    });
+    track_status(server.clone());
+
+    let app = Router::new()
+        .route("/status", get(status))
+        .route("/tasks", post(start_task))
+        .route("/stop", post(stop_task))
+        .route("/approval", post(approval))
+        .route("/events", get(events_ws))
+        .layer(middleware::from_fn_with_state(server.clone(), authorize))
+        .with_state(server.clone());
+    tauri::async_runtime::spawn(async move {
+        let listener = match tokio::net::TcpListener::bind(addr).await {
+            Ok(l) => l,
+            Err(e) => {
+                tracing::warn!(%addr, error = %e, "remote: bind failed");
+                return;
+            }
+        };
+        tracing::info!(%addr, "remote: API listening");
+        if let Err(e) = axum::serve(listener, app).await {
+            tracing::warn!(error = %e, "remote: server stopped");
+        }
+    });
+
+    let tee: SharedEventSink = server.events.clone();
+    Arc::new(TeeSink(vec![sink, tee]))
+}
+
+/// Keep `status` in step with the agent's state events.
+fn track_status(server: Arc<Server>) {
+    let mut rx = server.events.subscribe();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            match rx.recv().await {
+                Ok(msg) if msg.event == "agent_state_changed" => {
+                    let mut status = server.status.lock().unwrap_or_else(|e| e.into_inner());
+                    let goal = msg.payload.get("goal").cloned().or_else(|| status.get("goal").cloned());
+                    *status = msg.payload;
+                    if let (Some(goal), Some(obj)) = (goal, status.as_object_mut()) {
+                        obj.entry("goal").or_insert(goal);
+                    }
+                }
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+}
+
+async fn authorize(State(server): State<Arc<Server>>, req: Request, next: Next) -> Response {
+    let header_token = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    let query_token = req
+        .uri()
+        .query()
+        .and_then(|q| q.split('&').find_map(|kv| kv.strip_prefix("token=")));
+    match header_token.or(query_token) {
+        Some(t) if constant_time_eq(t.as_bytes(), server.token.as_bytes()) => next.run(req).await,
+        _ => (StatusCode::UNAUTHORIZED, "missing or invalid token").into_response(),
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+async fn status(State(server): State<Arc<Server>>) -> Json<serde_json::Value> {
+    let status = server.status.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    Json(serde_json::json!({ "status": status, "queue": task_queue::list() }))
+}
+
+#[derive(Deserialize)]
+struct TaskRequest {
+    goal: String,
+    /// Loop mode / limits for this goal (same shape as `start_task`'s).
+    #[serde(default)]
+    loop_config: Option<LoopOverrides>,
+}
+
+async fn start_task(State(server): State<Arc<Server>>, Json(req): Json<TaskRequest>) -> Response {
+    if req.goal.trim().is_empty() {
+        return (StatusCode::BAD_REQUEST, "goal is empty").into_response();
+    }
+    tracing::info!(goal = %req.goal, "remote: start_task");
+    let event = match req.loop_config {
+        Some(l) => AgentEvent::GoalWithLoop(req.goal, l),
+        None => AgentEvent::GoalReceived(req.goal),
+    };
+    send(&server, event).await
+}
+
+async fn stop_task(State(server): State<Arc<Server>>) -> Response {
+    tracing::info!("remote: stop_task");
+    server.handle.stop_flag.set();
+    send(&server, AgentEvent::Stop).await
+}
+
+#[derive(Deserialize)]
+struct ApprovalRequest {
+    approved: bool,
+}
+
+async fn approval(State(server): State<Arc<Server>>, Json(req): Json<ApprovalRequest>) -> Response {
+    tracing::info!(approved = req.approved, "remote: approval");
+    let event = if req.approved { AgentEvent::UserApproved } else { AgentEvent::UserRejected };
+    send(&server, event).await
+}
+
+async fn send(server: &Server, event: AgentEvent) -> Response {
+    match server.handle.tx.send(event).await {
+        Ok(()) => StatusCode::ACCEPTED.into_response(),
+        Err(e) => (StatusCode::SERVICE_UNAVAILABLE, format!("agent channel closed: {e}")).into_response(),
+    }
+}
+
+async fn events_ws(State(server): State<Arc<Server>>, ws: WebSocketUpgrade) -> Response {
+    let rx = server.events.subscribe();
+    ws.on_upgrade(move |socket| stream_events(socket, rx))
+}
+
+async fn stream_events(mut socket: WebSocket, mut rx: broadcast::Receiver<crate::events::AgentEventMessage>) {
+    loop {
+        let text = match rx.recv().await {
+            Ok(msg) => serde_json::to_string(&msg).unwrap_or_default(),
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                serde_json::json!({ "event": "lagged", "payload": { "skipped": skipped } }).to_string()
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+        if socket.send(Message::Text(text)).await.is_err() {
+            break;
+        }
+    }
+}
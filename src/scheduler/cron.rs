@@ -0,0 +1,201 @@
+//! Five-field cron expressions (`minute hour day-of-month month day-of-week`),
+//! evaluated in local time.
+//!
+//! Each field takes `*`, a number, a range `a-b`, a step `*/n` or `a-b/n`,
+//! or a comma list of those. Day of week is 0-6 with 0 (or 7) = Sunday.
+//! When both day fields are restricted a day matching either one runs, as
+//! in classic cron. Shorthands: `@hourly`, `@daily`, `@weekly`, `@monthly`.
+
+use chrono::{DateTime, Datelike, Duration, Local, TimeZone, Timelike};
+
+use crate::errors::{SeeClawError, SeeClawResult};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpr {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    days_any: bool,
+    weekdays_any: bool,
+}
+
+impl CronExpr {
+    pub fn parse(expr: &str) -> SeeClawResult<Self> {
+        let expanded = match expr.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            other => other,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let &[min, hour, dom, month, dow] = fields.as_slice() else {
+            return Err(invalid(expr, "expected 5 fields: minute hour day month weekday"));
+        };
+        let mut weekdays = field(dow, 0, 7).map_err(|e| invalid(expr, &e))?;
+        if weekdays & (1 << 7) != 0 {
+            weekdays |= 1; // 7 is Sunday too
+        }
+        Ok(Self {
+            minutes: field(min, 0, 59).map_err(|e| invalid(expr, &e))?,
+            hours: field(hour, 0, 23).map_err(|e| invalid(expr, &e))?,
+            days: field(dom, 1, 31).map_err(|e| invalid(expr, &e))?,
+            months: field(month, 1, 12).map_err(|e| invalid(expr, &e))?,
+            weekdays,
+            days_any: dom == "*",
+            weekdays_any: dow == "*",
+        })
+    }
+
+    /// First matching minute strictly after `after`; `None` when nothing
+    /// matches within the next four years (e.g. 31 February).
+    pub fn next_after(&self, after: DateTime<Local>) -> Option<DateTime<Local>> {
+        let start = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit = start + Duration::days(4 * 366);
+        let mut t = start.naive_local();
+        while t < limit.naive_local() {
+            if !bit(self.months, t.month()) {
+                let (y, m) = if t.month() == 12 { (t.year() + 1, 1) } else { (t.year(), t.month() + 1) };
+                t = chrono::NaiveDate::from_ymd_opt(y, m, 1)?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !self.day_matches(t.date()) {
+                t = t.date().succ_opt()?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !bit(self.hours, t.hour()) {
+                t = t.date().and_hms_opt(t.hour(), 0, 0)? + Duration::hours(1);
+                continue;
+            }
+            if !bit(self.minutes, t.minute()) {
+                t += Duration::minutes(1);
+                continue;
+            }
+            // Skip times that do not exist locally (DST gaps).
+            if let Some(local) = Local.from_local_datetime(&t).earliest() {
+                return Some(local);
+            }
+            t += Duration::minutes(1);
+        }
+        None
+    }
+
+    fn day_matches(&self, date: chrono::NaiveDate) -> bool {
+        let dom = bit(self.days, date.day());
+        let dow = bit(self.weekdays, date.weekday().num_days_from_sunday());
+        match (self.days_any, self.weekdays_any) {
+            (true, true) => true,
+            (false, true) => dom,
+            (true, false) => dow,
+            (false, false) => dom || dow,
+        }
+    }
+}
+
+fn bit(mask: u64, n: u32) -> bool {
+    mask & (1 << n) != 0
+}
+
+fn invalid(expr: &str, why: &str) -> SeeClawError {
+    SeeClawError::Config(format!("invalid cron expression '{expr}': {why}"))
+}
+
+/// Bit mask of the values `spec` selects within `lo..=hi`.
+fn field(spec: &str, lo: u32, hi: u32) -> Result<u64, String> {
+    let mut mask = 0u64;
+    for part in spec.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((r, s)) => (r, s.parse::<u32>().map_err(|_| format!("bad step in '{part}'"))?),
+            None => (part, 1),
+        };
+        if step == 0 {
+            return Err(format!("zero step in '{part}'"));
+        }
+        let (from, to) = if range == "*" {
+            (lo, hi)
+        } else if let Some((a, b)) = range.split_once('-') {
+            (number(a, lo, hi)?, number(b, lo, hi)?)
+        } else {
+            let n = number(range, lo, hi)?;
+            // "5/15" means from 5 to the end in steps of 15.
+            (n, if part.contains('/') { hi } else { n })
+        };
+        if from > to {
+            return Err(format!("empty range '{range}'"));
+        }
+        for v in (from..=to).step_by(step as usize) {
+            mask |= 1 << v;
+        }
+    }
+    Ok(mask)
+}
+
+fn number(s: &str, lo: u32, hi: u32) -> Result<u32, String> {
+    match s.parse::<u32>() {
+        Ok(n) if (lo..=hi).contains(&n) => Ok(n),
+        _ => Err(format!("'{s}' is not in {lo}-{hi}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every test runs in the same zone, so setting it from parallel tests
+    /// is harmless; New York has a DST gap at 02:00 on 2026-03-08.
+    fn local(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Local> {
+        std::env::set_var("TZ", "America/New_York");
+        Local.with_ymd_and_hms(y, mo, d, h, mi, 0).single().unwrap()
+    }
+
+    fn next(expr: &str, after: DateTime<Local>) -> Option<DateTime<Local>> {
+        CronExpr::parse(expr).unwrap().next_after(after)
+    }
+
+    #[test]
+    fn test_seven_is_sunday() {
+        // 2026-10-14 is a Wednesday.
+        let after = local(2026, 10, 14, 12, 0);
+        assert_eq!(next("0 9 * * 7", after), Some(local(2026, 10, 18, 9, 0)));
+        assert_eq!(next("0 9 * * 0", after), next("0 9 * * 7", after));
+    }
+
+    #[test]
+    fn test_day_of_month_or_weekday() {
+        // "13th or any Friday": Friday the 2nd comes first, then the 13th.
+        assert_eq!(next("0 0 13 * 5", local(2026, 10, 1, 0, 0)), Some(local(2026, 10, 2, 0, 0)));
+        assert_eq!(next("0 0 13 * 5", local(2026, 10, 10, 0, 0)), Some(local(2026, 10, 13, 0, 0)));
+        // Only one day field restricted: the other one does not widen it.
+        assert_eq!(next("0 0 13 * *", local(2026, 10, 1, 0, 0)), Some(local(2026, 10, 13, 0, 0)));
+    }
+
+    #[test]
+    fn test_skips_dst_gap() {
+        // 02:30 does not exist on 2026-03-08 in New York.
+        assert_eq!(next("30 2 * * *", local(2026, 3, 8, 0, 0)), Some(local(2026, 3, 9, 2, 30)));
+        assert_eq!(next("0 3 * * *", local(2026, 3, 8, 0, 0)), Some(local(2026, 3, 8, 3, 0)));
+    }
+
+    #[test]
+    fn test_impossible_date_never_runs() {
+        assert_eq!(next("0 0 31 2 *", local(2026, 1, 1, 0, 0)), None);
+    }
+
+    #[test]
+    fn test_next_is_strictly_after() {
+        let at = local(2026, 10, 16, 10, 0);
+        assert_eq!(next("0 10 * * *", at), Some(local(2026, 10, 17, 10, 0)));
+        assert_eq!(next("*/15 * * * *", local(2026, 10, 16, 10, 7)), Some(local(2026, 10, 16, 10, 15)));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(CronExpr::parse("60 * * * *").is_err());
+        assert!(CronExpr::parse("* * *").is_err());
+        assert!(CronExpr::parse("*/0 * * * *").is_err());
+        assert!(CronExpr::parse("0 0 5-1 * *").is_err());
+        assert_eq!(CronExpr::parse("@daily").unwrap(), CronExpr::parse("0 0 * * *").unwrap());
+    }
+}
@@ -0,0 +1,212 @@
+//! Scheduled and recurring tasks.
+//!
+//! A schedule pairs a goal with a cron expression (see [`cron`]) or a single
+//! "run at" time. Schedules are kept in `SeeClaw/scheduler/schedules.json`
+//! under the data dir. [`spawn`] starts a runner that sends the goal to the
+//! agent as an ordinary `GoalReceived` when it is due, so a busy agent
+//! queues it like a typed goal; a firing is skipped while the same goal is
+//! still waiting in the queue. Runs missed while the app was closed are not
+//! caught up, except that an overdue "run at" schedule fires once on start.
+
+pub mod cron;
+
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use crate::agent_engine::history::data_dir_or_cwd;
+use crate::agent_engine::state::AgentEvent;
+use crate::agent_engine::task_queue;
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::events::SharedEventSink;
+
+use self::cron::CronExpr;
+
+const SCHEDULES_FILE: &str = "schedules.json";
+/// Longest sleep between due checks; also bounds clock-change drift.
+const MAX_POLL: Duration = Duration::from_secs(30);
+
+/// When a schedule runs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum When {
+    /// Recurring, e.g. `"0 9 * * 1-5"` (09:00 on weekdays).
+    Cron { expr: String },
+    /// Once, at an RFC 3339 time.
+    At { time: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Schedule {
+    pub id: String,
+    pub goal: String,
+    pub when: When,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// RFC 3339 time of the last firing.
+    #[serde(default)]
+    pub last_run: Option<String>,
+    /// RFC 3339 time of the next firing; `None` once a one-off has run.
+    #[serde(default)]
+    pub next_run: Option<String>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl Schedule {
+    /// Next firing strictly after `now`.
+    fn next_after(&self, now: DateTime<Local>) -> SeeClawResult<Option<DateTime<Local>>> {
+        match &self.when {
+            When::Cron { expr } => Ok(CronExpr::parse(expr)?.next_after(now)),
+            When::At { time } => {
+                let at = DateTime::parse_from_rfc3339(time)
+                    .map_err(|e| SeeClawError::Config(format!("invalid time '{time}': {e}")))?
+                    .with_timezone(&Local);
+                Ok(self.last_run.is_none().then_some(at))
+            }
+        }
+    }
+}
+
+fn store() -> &'static Mutex<Vec<Schedule>> {
+    static STORE: OnceLock<Mutex<Vec<Schedule>>> = OnceLock::new();
+    STORE.get_or_init(|| Mutex::new(load()))
+}
+
+fn lock() -> std::sync::MutexGuard<'static, Vec<Schedule>> {
+    store().lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn path() -> PathBuf {
+    data_dir_or_cwd("scheduler").join(SCHEDULES_FILE)
+}
+
+fn load() -> Vec<Schedule> {
+    match std::fs::read_to_string(path()) {
+        Ok(text) => serde_json::from_str(&text).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "scheduler: schedules file unreadable, starting empty");
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn save(schedules: &[Schedule]) {
+    let result = serde_json::to_string_pretty(schedules)
+        .map_err(SeeClawError::from)
+        .and_then(|text| Ok(std::fs::write(path(), text)?));
+    if let Err(e) = result {
+        tracing::warn!(error = %e, "scheduler: failed to save schedules");
+    }
+}
+
+pub fn list() -> Vec<Schedule> {
+    lock().clone()
+}
+
+/// Register `goal` to run `when`. Invalid expressions and times in the past
+/// are rejected.
+pub fn add(goal: String, when: When) -> SeeClawResult<Schedule> {
+    let goal = goal.trim().to_string();
+    if goal.is_empty() {
+        return Err(SeeClawError::Config("schedule goal is empty".into()));
+    }
+    let mut schedule = Schedule {
+        id: uuid::Uuid::new_v4().to_string(),
+        goal,
+        when,
+        enabled: true,
+        last_run: None,
+        next_run: None,
+    };
+    let now = Local::now();
+    let next = schedule
+        .next_after(now)?
+        .ok_or_else(|| SeeClawError::Config("the schedule never runs".into()))?;
+    if next <= now {
+        return Err(SeeClawError::Config("the run-at time is in the past".into()));
+    }
+    schedule.next_run = Some(next.to_rfc3339());
+    let mut schedules = lock();
+    schedules.push(schedule.clone());
+    save(&schedules);
+    tracing::info!(id = %schedule.id, goal = %schedule.goal, next = %next, "scheduler: schedule added");
+    Ok(schedule)
+}
+
+/// Delete schedule `id`. `false` when there is none.
+pub fn remove(id: &str) -> bool {
+    let mut schedules = lock();
+    let before = schedules.len();
+    schedules.retain(|s| s.id != id);
+    let removed = schedules.len() != before;
+    if removed {
+        save(&schedules);
+    }
+    removed
+}
+
+/// Start the runner. Due goals are sent on `tx`; every firing is announced
+/// as `schedule_fired`.
+pub fn spawn(tx: mpsc::Sender<AgentEvent>, sink: SharedEventSink) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let wait = fire_due(&tx, &sink).await;
+            tokio::time::sleep(wait).await;
+            if tx.is_closed() {
+                break;
+            }
+        }
+    });
+}
+
+/// Fire every due schedule; returns how long to sleep until the next check.
+async fn fire_due(tx: &mpsc::Sender<AgentEvent>, sink: &SharedEventSink) -> Duration {
+    let now = Local::now();
+    let mut due = Vec::new();
+    let mut wait = MAX_POLL;
+    {
+        let mut schedules = lock();
+        let mut changed = false;
+        for s in schedules.iter_mut().filter(|s| s.enabled) {
+            let Some(next) = s.next_run.as_deref().and_then(|t| DateTime::parse_from_rfc3339(t).ok()) else {
+                continue;
+            };
+            let next = next.with_timezone(&Local);
+            if next > now {
+                wait = wait.min((next - now).to_std().unwrap_or(MAX_POLL));
+                continue;
+            }
+            // Missed recurring runs are not caught up: a cron schedule more
+            // than one poll overdue (the app was closed) just moves on.
+            let overdue = (now - next).to_std().unwrap_or_default() > MAX_POLL * 2;
+            if !(overdue && matches!(s.when, When::Cron { .. })) {
+                due.push(s.goal.clone());
+                s.last_run = Some(now.to_rfc3339());
+            }
+            s.next_run = s.next_after(now).ok().flatten().map(|t| t.to_rfc3339());
+            changed = true;
+        }
+        if changed {
+            save(&schedules);
+        }
+    }
+    for goal in due {
+        if task_queue::list().iter().any(|q| q.goal == goal) {
+            tracing::info!(%goal, "scheduler: previous run still queued, skipping");
+            continue;
+        }
+        tracing::info!(%goal, "scheduler: schedule due, starting goal");
+        let _ = sink.emit("schedule_fired", serde_json::json!({ "goal": &goal }));
+        if tx.send(AgentEvent::GoalReceived(goal)).await.is_err() {
+            break;
+        }
+    }
+    wait.max(Duration::from_secs(1))
+}
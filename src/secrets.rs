@@ -0,0 +1,105 @@
+//! Provider API keys in the OS credential store.
+//!
+//! With `[llm] keyring = true`, keys are kept in Windows Credential Manager,
+//! the macOS Keychain or the Secret Service (GNOME Keyring / KWallet) under
+//! the service name `SeeClaw`, one entry per provider id, and config.toml
+//! holds none. Saving settings moves any key the file would contain into the
+//! store; startup does the same for keys written before the option was on.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::config::{save_config, AppConfig};
+use crate::errors::{SeeClawError, SeeClawResult};
+
+const SERVICE: &str = "SeeClaw";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Apply `[llm] keyring` (on startup and whenever config changes).
+pub fn configure(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+fn entry(provider_id: &str) -> SeeClawResult<keyring::Entry> {
+    keyring::Entry::new(SERVICE, provider_id)
+        .map_err(|e| SeeClawError::Config(format!("credential store unavailable: {e}")))
+}
+
+/// The stored key for `provider_id`; `None` when the option is off, there is
+/// no entry, or the store can't be reached.
+pub fn api_key(provider_id: &str) -> Option<String> {
+    if !enabled() {
+        return None;
+    }
+    let result = entry(provider_id).map(|e| e.get_password());
+    match result {
+        Ok(Ok(key)) if !key.is_empty() => Some(key),
+        Ok(Ok(_)) | Ok(Err(keyring::Error::NoEntry)) => None,
+        Ok(Err(e)) => {
+            tracing::warn!(provider = provider_id, error = %e, "keyring: cannot read API key");
+            None
+        }
+        Err(e) => {
+            tracing::warn!(provider = provider_id, error = %e, "keyring: cannot read API key");
+            None
+        }
+    }
+}
+
+/// Store `key` for `provider_id`, or delete the entry when `key` is empty.
+pub fn set_api_key(provider_id: &str, key: &str) -> SeeClawResult<()> {
+    let entry = entry(provider_id)?;
+    let result = if key.is_empty() {
+        match entry.delete_credential() {
+            Err(keyring::Error::NoEntry) => Ok(()),
+            other => other,
+        }
+    } else {
+        entry.set_password(key)
+    };
+    result.map_err(|e| {
+        SeeClawError::Config(format!(
+            "cannot store the API key for '{provider_id}' in the OS credential store: {e} \
+             (set [llm] keyring = false to keep keys in config.toml)"
+        ))
+    })
+}
+
+/// A copy of `cfg` with every provider key moved into the credential store,
+/// for writing to disk. An empty key deletes the stored one; an unset key
+/// (`None`) leaves it alone. Fails rather than fall back to plaintext.
+pub fn strip_api_keys(cfg: &AppConfig) -> SeeClawResult<AppConfig> {
+    let mut stripped = cfg.clone();
+    for (id, entry) in stripped.llm.providers.iter_mut() {
+        if let Some(key) = entry.api_key.take() {
+            set_api_key(id, key.trim())?;
+            tracing::debug!(provider = id, "keyring: API key stored");
+        }
+    }
+    Ok(stripped)
+}
+
+/// Move plaintext keys left in config.toml into the credential store.
+pub fn migrate(cfg: &AppConfig) {
+    if !cfg.llm.keyring {
+        return;
+    }
+    let plaintext: Vec<&str> = cfg
+        .llm
+        .providers
+        .iter()
+        .filter(|(_, p)| p.api_key.as_deref().is_some_and(|k| !k.is_empty()))
+        .map(|(id, _)| id.as_str())
+        .collect();
+    if plaintext.is_empty() {
+        return;
+    }
+    match save_config(cfg) {
+        Ok(()) => tracing::info!(providers = ?plaintext, "keyring: moved API keys out of config.toml"),
+        Err(e) => tracing::warn!(error = %e, "keyring: migration failed; keys stay in config.toml"),
+    }
+}
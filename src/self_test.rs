@@ -0,0 +1,171 @@
+//! Self-test — one pass over every layer a task depends on.
+//!
+//! Answers "nothing happens when I start a task" without reading logs: can we
+//! capture the screen, does the perception chain find elements, does input
+//! injection reach the OS, can the app open a window, and do the configured
+//! LLM endpoints answer. Every check runs even when an earlier one fails, so
+//! the report shows the whole picture at once.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, WebviewUrl, WebviewWindowBuilder};
+use tokio::sync::Mutex;
+
+use crate::config::AppConfig;
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::executor::input;
+use crate::llm::health::probe_endpoint;
+use crate::llm::registry::ProviderRegistry;
+use crate::perception::pipeline::PerceptionChain;
+use crate::perception::screenshot::capture_primary;
+use crate::perception::yolo_detector::YoloDetector;
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+const WINDOW_LABEL: &str = "self-test";
+
+/// Outcome of one check.
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    pub name: String,
+    pub passed: bool,
+    /// What was observed, or why it failed.
+    pub detail: String,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SelfTestReport {
+    /// True when every check passed.
+    pub passed: bool,
+    pub checks: Vec<CheckResult>,
+}
+
+/// Run all checks in order.
+pub async fn run(
+    app: &AppHandle,
+    config: &AppConfig,
+    registry: &Arc<Mutex<ProviderRegistry>>,
+) -> SelfTestReport {
+    let mut checks = Vec::new();
+
+    let started = Instant::now();
+    let shot = capture_primary().await;
+    let duration_ms = started.elapsed().as_millis() as u64;
+    checks.push(match &shot {
+        Ok(s) => CheckResult {
+            name: "screenshot".into(),
+            passed: true,
+            detail: format!(
+                "{}x{} at scale {}",
+                s.meta.physical_width, s.meta.physical_height, s.meta.scale_factor
+            ),
+            duration_ms,
+        },
+        Err(e) => CheckResult { duration_ms, ..failed("screenshot", e) },
+    });
+
+    checks.push(match &shot {
+        Ok(s) => {
+            check("detection", async {
+                let chain = PerceptionChain::from_config(
+                    &config.perception,
+                    YoloDetector::from_config(&config.perception),
+                );
+                let ctx = chain.run(&s.image_bytes, &s.meta).await?;
+                if ctx.elements.is_empty() {
+                    return Err(SeeClawError::Perception(format!(
+                        "no elements found (parsers: {})",
+                        chain.names().join(", ")
+                    )));
+                }
+                Ok(format!("{} elements via {:?}", ctx.elements.len(), ctx.source))
+            })
+            .await
+        }
+        Err(_) => skipped("detection", "needs a screenshot"),
+    });
+
+    checks.push(check("mouse", nudge_mouse()).await);
+    checks.push(check("window", open_and_close_window(app)).await);
+
+    let endpoints = registry.lock().await.endpoints();
+    if endpoints.is_empty() {
+        checks.push(skipped("providers", "no LLM provider configured"));
+    }
+    let client = reqwest::Client::builder().timeout(PROBE_TIMEOUT).build();
+    for (id, url) in endpoints {
+        let name = format!("provider:{id}");
+        checks.push(match &client {
+            Ok(client) => {
+                check(&name, async {
+                    if probe_endpoint(client, &url).await {
+                        Ok(format!("{url} answered"))
+                    } else {
+                        Err(SeeClawError::LlmProvider(format!("{url} is unreachable")))
+                    }
+                })
+                .await
+            }
+            Err(e) => skipped(&name, &e.to_string()),
+        });
+    }
+
+    let passed = checks.iter().all(|c| c.passed);
+    tracing::info!(passed, failed = ?checks.iter().filter(|c| !c.passed).map(|c| c.name.as_str()).collect::<Vec<_>>(), "self-test finished");
+    SelfTestReport { passed, checks }
+}
+
+/// Move the cursor one pixel and back, and confirm it actually moved.
+async fn nudge_mouse() -> SeeClawResult<String> {
+    let (x, y) = input::mouse_position().await?;
+    let nx = if x > 0 { x - 1 } else { x + 1 };
+    input::mouse_move(nx, y).await?;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let moved = input::mouse_position().await?;
+    input::mouse_move(x, y).await?;
+    if moved != (nx, y) {
+        return Err(SeeClawError::Executor(format!(
+            "cursor did not move: expected ({nx}, {y}), found ({}, {})",
+            moved.0, moved.1
+        )));
+    }
+    Ok(format!("cursor moved at ({x}, {y}) and restored"))
+}
+
+/// Open a tiny blank window and close it again.
+async fn open_and_close_window(app: &AppHandle) -> SeeClawResult<String> {
+    let blank = "about:blank".parse().map_err(|e| SeeClawError::Executor(format!("{e}")))?;
+    let window = WebviewWindowBuilder::new(app, WINDOW_LABEL, WebviewUrl::External(blank))
+        .title("SeeClaw self-test")
+        .inner_size(120.0, 60.0)
+        .skip_taskbar(true)
+        .focused(false)
+        .build()
+        .map_err(|e| SeeClawError::Executor(format!("open window: {e}")))?;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    window
+        .close()
+        .map_err(|e| SeeClawError::Executor(format!("close window: {e}")))?;
+    Ok("opened and closed a window".into())
+}
+
+async fn check(name: &str, work: impl Future<Output = SeeClawResult<String>>) -> CheckResult {
+    let started = Instant::now();
+    let result = work.await;
+    let duration_ms = started.elapsed().as_millis() as u64;
+    match result {
+        Ok(detail) => CheckResult { name: name.into(), passed: true, detail, duration_ms },
+        Err(e) => CheckResult { duration_ms, ..failed(name, &e) },
+    }
+}
+
+fn failed(name: &str, err: &SeeClawError) -> CheckResult {
+    CheckResult { name: name.into(), passed: false, detail: err.to_string(), duration_ms: 0 }
+}
+
+fn skipped(name: &str, reason: &str) -> CheckResult {
+    CheckResult { name: name.into(), passed: false, detail: format!("skipped: {reason}"), duration_ms: 0 }
+}
@@ -0,0 +1,228 @@
+//! SkillExecutor — runs a registered skill's combo steps inside the current plan.
+//!
+//! Used by `ActionExecNode` (when the LLM emits `invoke_skill`) and by
+//! `ComboExecNode` (when the planner assigns `mode: "combo"`). Each combo step
+//! is parsed into an `AgentAction` and dispatched through the same executor
+//! as a regular action, so clicks, scrolls and typing behave identically.
+//!
+//! Steps that would require user approval under the current safety config
+//! (terminal, MCP, anything in `require_approval_for`) or that terminate the graph (finish_task, get_viewport, nested invoke_skill) are
+//! refused — a skill must not bypass the safety gate.
+//!
+//! Consecutive type_text / hotkey / key_press steps run as one keyboard batch
+//! when `[executor] batch_keyboard_actions` is on.
+
+use crate::agent_engine::context::NodeContext;
+use crate::agent_engine::node::wait_stop;
+use crate::agent_engine::nodes::action_exec::{execute_action_impl, is_batchable, run_keyboard_batch};
+use crate::agent_engine::state::{AgentAction, SharedState};
+use crate::agent_engine::tool_parser::parse_action_by_name;
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::executor::safety;
+use crate::skills::registry::SkillRegistry;
+
+/// Result of a single combo step.
+#[derive(Debug, Clone)]
+pub struct SkillStepOutcome {
+    pub action: String,
+    pub ok: bool,
+    pub message: String,
+}
+
+/// Result of a whole skill invocation.
+#[derive(Debug)]
+pub struct SkillRun {
+    pub skill: String,
+    /// Total number of steps defined by the skill.
+    pub total: usize,
+    /// Outcomes of the steps that were attempted (stops at the first failure).
+    pub steps: Vec<SkillStepOutcome>,
+    pub stopped: bool,
+    /// Error of the step that aborted the run, if any.
+    pub error: Option<SeeClawError>,
+}
+
+impl SkillRun {
+    pub fn ok(&self) -> bool {
+        !self.stopped && self.error.is_none() && self.steps.len() == self.total
+    }
+
+    /// Collapse into an action outcome, keeping the failing step's error category.
+    pub fn into_result(self) -> SeeClawResult<String> {
+        if self.stopped {
+            return Err(SeeClawError::Cancelled);
+        }
+        let summary = self.summary();
+        match self.error {
+            Some(SeeClawError::GroundingFailed(_)) => Err(SeeClawError::GroundingFailed(summary)),
+            Some(SeeClawError::ExecutorDenied(_)) => Err(SeeClawError::ExecutorDenied(summary)),
+            Some(_) => Err(SeeClawError::Executor(summary)),
+            None => Ok(summary),
+        }
+    }
+
+    /// One-line summary suitable for a tool result.
+    pub fn summary(&self) -> String {
+        if self.stopped {
+            return "Stopped by user".into();
+        }
+        match self.steps.iter().find(|s| !s.ok) {
+            Some(failed) => format!(
+                "Skill '{}' failed at step {}/{} ({}): {}",
+                self.skill,
+                self.steps.len(),
+                self.total,
+                failed.action,
+                failed.message
+            ),
+            None => format!("Skill '{}' executed ({} combo steps)", self.skill, self.total),
+        }
+    }
+
+    /// Per-step lines for `SharedState::steps_log`, numbered under the plan step.
+    pub fn log_lines(&self, plan_step: usize) -> Vec<String> {
+        self.steps
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                format!(
+                    "Step {}.{}: [skill {}] {} - {}",
+                    plan_step,
+                    i + 1,
+                    self.skill,
+                    s.action,
+                    if s.ok { s.message.clone() } else { format!("FAILED: {}", s.message) }
+                )
+            })
+            .collect()
+    }
+}
+
+pub struct SkillExecutor<'a> {
+    registry: &'a SkillRegistry,
+}
+
+impl<'a> SkillExecutor<'a> {
+    pub fn new(registry: &'a SkillRegistry) -> Self {
+        Self { registry }
+    }
+
+    /// Expand and run `skill_name` with `inputs`. Returns `None` when the skill
+    /// is not registered (callers decide how to fall back).
+    pub async fn run(
+        &self,
+        skill_name: &str,
+        inputs: &serde_json::Value,
+        state: &SharedState,
+        ctx: &NodeContext,
+    ) -> Option<SkillRun> {
+        let combo_steps = self.registry.expand_combo(skill_name, inputs)?;
+        let mut run = SkillRun {
+            skill: skill_name.to_string(),
+            total: combo_steps.len(),
+            steps: Vec::with_capacity(combo_steps.len()),
+            stopped: false,
+            error: None,
+        };
+
+        tracing::info!(skill = %skill_name, steps = run.total, "SkillExecutor: running skill");
+
+        let mut i = 0;
+        while i < combo_steps.len() {
+            if state.is_stopped() {
+                run.stopped = true;
+                break;
+            }
+
+            let batch: Vec<AgentAction> = combo_steps[i..]
+                .iter()
+                .map_while(|s| parse_action_by_name(&s.action, &s.args).ok().filter(|a| is_batchable(a, ctx)))
+                .collect();
+            if batch.len() > 1 {
+                let results = run_keyboard_batch(&batch, state, ctx).await;
+                tracing::debug!(skill = %skill_name, combo_step = i, steps = results.len(), "SkillExecutor: keyboard batch done");
+                let mut failed = None;
+                for (combo_step, result) in combo_steps[i..].iter().zip(results) {
+                    let (ok, message) = match &result {
+                        Ok(msg) => (true, msg.clone()),
+                        Err(e) => (false, e.to_string()),
+                    };
+                    run.steps.push(SkillStepOutcome {
+                        action: combo_step.action.clone(),
+                        ok,
+                        message,
+                    });
+                    if let Err(e) = result {
+                        failed = Some(e);
+                    }
+                }
+                if let Some(e) = failed {
+                    if matches!(e, SeeClawError::Cancelled) {
+                        run.stopped = true;
+                    }
+                    run.error = Some(e);
+                    break;
+                }
+                i += batch.len();
+                continue;
+            }
+
+            let combo_step = &combo_steps[i];
+            let result = match parse_action_by_name(&combo_step.action, &combo_step.args) {
+                Ok(action) if !allowed_in_skill(&action, ctx) => Err(SeeClawError::ExecutorDenied(format!(
+                    "action '{}' is not allowed inside a skill",
+                    combo_step.action
+                ))),
+                Ok(AgentAction::Wait { milliseconds }) => {
+                    let flag = state.stop_flag.clone();
+                    let completed = tokio::select! {
+                        _ = tokio::time::sleep(std::time::Duration::from_millis(milliseconds as u64)) => true,
+                        _ = wait_stop(flag) => false,
+                    };
+                    if !completed {
+                        run.stopped = true;
+                        break;
+                    }
+                    Ok(format!("Waited {milliseconds}ms"))
+                }
+                Ok(action) => execute_action_impl(&action, state, ctx).await,
+                Err(e) => Err(SeeClawError::Skills(format!("invalid step: {e}"))),
+            };
+
+            let (ok, message) = match &result {
+                Ok(msg) => (true, msg.clone()),
+                Err(e) => (false, e.to_string()),
+            };
+            tracing::debug!(skill = %skill_name, combo_step = i, ok, message = %message, "SkillExecutor: step done");
+            run.steps.push(SkillStepOutcome {
+                action: combo_step.action.clone(),
+                ok,
+                message,
+            });
+            if let Err(e) = result {
+                if matches!(e, SeeClawError::Cancelled) {
+                    run.stopped = true;
+                }
+                run.error = Some(e);
+                break;
+            }
+            i += 1;
+        }
+
+        Some(run)
+    }
+}
+
+/// Only auto-approved, non-terminal actions may run inside a skill.
+fn allowed_in_skill(action: &AgentAction, ctx: &NodeContext) -> bool {
+    !safety::requires_approval(action, &ctx.safety)
+        && !matches!(
+            action,
+            AgentAction::FinishTask { .. }
+                | AgentAction::ReportFailure { .. }
+                | AgentAction::GetViewport { .. }
+                | AgentAction::SelectMonitor { .. }
+                | AgentAction::InvokeSkill { .. }
+                | AgentAction::DelegateSubtask { .. }
+        )
+}
@@ -1,6 +1,7 @@
 use std::path::Path;
 
-use crate::skills::registry::{SkillDefinition, SkillRegistry};
+use crate::config::SafetyConfig;
+use crate::skills::registry::{RejectedSkill, SkillDefinition, SkillRegistry};
 
 // ── Registry builder ───────────────────────────────────────────────────────
 
@@ -8,7 +9,9 @@ use crate::skills::registry::{SkillDefinition, SkillRegistry};
 ///
 /// Scans for `*.skill.json` files and populates the registry.
 /// Each file is a unified skill definition containing both metadata and combo steps.
-pub async fn load_skill_registry(skills_dir: &str) -> SkillRegistry {
+/// Skills whose declared or implied permissions exceed `safety` are not
+/// enabled; they are listed in `SkillRegistry::rejected_skills` instead.
+pub async fn load_skill_registry(skills_dir: &str, safety: &SafetyConfig) -> SkillRegistry {
     let mut registry = SkillRegistry::new();
     let dir = Path::new(skills_dir);
 
@@ -17,12 +20,13 @@ pub async fn load_skill_registry(skills_dir: &str) -> SkillRegistry {
         return registry;
     }
 
-    if let Err(e) = scan_skill_dir(dir, &mut registry).await {
+    if let Err(e) = scan_skill_dir(dir, safety, &mut registry).await {
         tracing::warn!(error = %e, "Failed to scan skill directory");
     }
 
     tracing::info!(
         skills = registry.skill_names().len(),
+        rejected = registry.rejected_skills().len(),
         "Skill registry loaded"
     );
     registry
@@ -31,6 +35,7 @@ pub async fn load_skill_registry(skills_dir: &str) -> SkillRegistry {
 /// Recursively scan a directory for `.skill.json` files.
 async fn scan_skill_dir(
     dir: &Path,
+    safety: &SafetyConfig,
     registry: &mut SkillRegistry,
 ) -> Result<(), String> {
     let mut entries = tokio::fs::read_dir(dir)
@@ -42,10 +47,19 @@ async fn scan_skill_dir(
             Ok(Some(entry)) => {
                 let path = entry.path();
                 if path.is_dir() {
-                    Box::pin(scan_skill_dir(&path, registry)).await?;
+                    Box::pin(scan_skill_dir(&path, safety, registry)).await?;
                 } else if let Some(fname) = path.file_name().and_then(|f| f.to_str()) {
                     if fname.ends_with(".skill.json") {
                         if let Some(skill) = parse_skill_file(&path).await {
+                            if let Some(reason) = skill.permission_error(safety) {
+                                tracing::warn!(name = %skill.name, path = %path.display(), reason = %reason, "skill not enabled");
+                                registry.reject(RejectedSkill {
+                                    name: skill.name,
+                                    path: path.display().to_string(),
+                                    reason,
+                                });
+                                continue;
+                            }
                             tracing::debug!(name = %skill.name, "loaded skill");
                             registry.add_skill(skill);
                         }
@@ -80,7 +94,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_load_skill_registry() {
-        let registry = load_skill_registry("prompts/skills").await;
+        let registry = load_skill_registry("prompts/skills", &SafetyConfig::default()).await;
         assert!(registry.skill_names().len() > 0);
     }
 }
@@ -1,5 +1,12 @@
+pub mod executor;
 pub mod manager;
+pub mod pack;
+pub mod recorder;
 pub mod registry;
 
+/// Directory scanned for `.skill.json` files (relative to the working directory).
+pub const SKILLS_DIR: &str = "prompts/skills";
+
+pub use executor::{SkillExecutor, SkillRun};
 pub use manager::load_skill_registry;
 pub use registry::{ComboStep, SkillDefinition, SkillRegistry};
@@ -0,0 +1,359 @@
+//! Skill packs — zip archives for sharing vetted skills between machines.
+//!
+//! Layout:
+//! - `skillpack.json` — [`PackManifest`]: pack metadata plus the SHA-256 of
+//!   every skill file.
+//! - `skillpack.sig` — optional base64 ed25519 signature over the exact bytes
+//!   of `skillpack.json`. Because the manifest pins each file's hash, the
+//!   signature covers the whole pack.
+//! - `skills/<file>.skill.json` — the skill definitions themselves.
+//!
+//! Imported packs are unpacked to `<skills_dir>/imported/<pack>/`, where
+//! `load_skill_registry` picks them up on the next start. Trust is decided by
+//! `[skills]` in config.toml: signatures from `trusted_keys` are trusted, and
+//! `require_signed_packs` rejects anything else.
+
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::config::{SafetyConfig, SkillsConfig};
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::skills::registry::{RejectedSkill, SkillDefinition};
+
+const MANIFEST_FILE: &str = "skillpack.json";
+const SIGNATURE_FILE: &str = "skillpack.sig";
+const SKILLS_PREFIX: &str = "skills/";
+/// Manifest format version written by this build.
+const PACK_FORMAT: u32 = 1;
+/// Skill files larger than this are rejected on import.
+const MAX_SKILL_FILE_BYTES: u64 = 1024 * 1024;
+
+// ── Manifest ────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackManifest {
+    pub format: u32,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub created_at: String,
+    /// Base64 ed25519 public key of the signer; empty for unsigned packs.
+    #[serde(default)]
+    pub public_key: String,
+    pub skills: Vec<PackEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackEntry {
+    /// File name inside `skills/`.
+    pub file: String,
+    /// Skill name from the definition.
+    pub name: String,
+    /// Lowercase hex SHA-256 of the file contents.
+    pub sha256: String,
+}
+
+/// How an imported pack's signature checked out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureStatus {
+    Unsigned,
+    /// Valid signature from a key in `trusted_keys`.
+    Trusted,
+    /// Valid signature from a key the user has not trusted.
+    UnknownSigner,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportReport {
+    pub path: String,
+    pub skills: Vec<String>,
+    pub signed: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportReport {
+    pub pack: String,
+    /// Directory the skills were written to.
+    pub dir: String,
+    pub skills: Vec<String>,
+    pub signature: SignatureStatus,
+    /// Base64 public key of the signer, when signed.
+    pub signer: Option<String>,
+    /// Imported skills that will not be enabled under the current `[safety]`.
+    pub not_enabled: Vec<RejectedSkill>,
+}
+
+// ── Export ──────────────────────────────────────────────────────────────────
+
+/// Write the skills named in `names` from `skills_dir` into a pack at `dest`.
+/// Signs the manifest when `cfg` resolves a signing key.
+pub fn export_skills(
+    skills_dir: &Path,
+    names: &[String],
+    pack_name: &str,
+    description: &str,
+    dest: &Path,
+    cfg: &SkillsConfig,
+) -> SeeClawResult<ExportReport> {
+    if names.is_empty() {
+        return Err(SeeClawError::Skills("no skills selected for export".into()));
+    }
+    let mut available = HashMap::new();
+    collect_skill_files(skills_dir, &mut available)?;
+
+    let mut files: Vec<(PackEntry, Vec<u8>)> = Vec::new();
+    for name in names {
+        let (path, bytes) = available
+            .remove(name)
+            .ok_or_else(|| SeeClawError::Skills(format!("skill not found: {name}")))?;
+        let file = unique_file_name(&path, &files);
+        let entry = PackEntry { file, name: name.clone(), sha256: sha256_hex(&bytes) };
+        files.push((entry, bytes));
+    }
+
+    let signing_key = cfg.resolved_signing_key().map(|k| parse_signing_key(&k)).transpose()?;
+    let manifest = PackManifest {
+        format: PACK_FORMAT,
+        name: pack_name.to_string(),
+        description: description.to_string(),
+        created_at: chrono::Local::now().to_rfc3339(),
+        public_key: signing_key
+            .as_ref()
+            .map(|k| STANDARD.encode(k.verifying_key().to_bytes()))
+            .unwrap_or_default(),
+        skills: files.iter().map(|(e, _)| e.clone()).collect(),
+    };
+    let manifest_bytes = serde_json::to_vec_pretty(&manifest)?;
+
+    if let Some(parent) = dest.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(dest)?);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    zip.start_file(MANIFEST_FILE, options).map_err(zip_err)?;
+    zip.write_all(&manifest_bytes)?;
+    if let Some(key) = &signing_key {
+        let sig = key.sign(&manifest_bytes);
+        zip.start_file(SIGNATURE_FILE, options).map_err(zip_err)?;
+        zip.write_all(STANDARD.encode(sig.to_bytes()).as_bytes())?;
+    }
+    for (entry, bytes) in &files {
+        zip.start_file(format!("{SKILLS_PREFIX}{}", entry.file), options).map_err(zip_err)?;
+        zip.write_all(bytes)?;
+    }
+    zip.finish().map_err(zip_err)?;
+
+    tracing::info!(pack = pack_name, skills = files.len(), signed = signing_key.is_some(), path = %dest.display(), "skill pack exported");
+    Ok(ExportReport {
+        path: dest.display().to_string(),
+        skills: manifest.skills.into_iter().map(|e| e.name).collect(),
+        signed: signing_key.is_some(),
+    })
+}
+
+// ── Import ──────────────────────────────────────────────────────────────────
+
+/// Verify the pack at `path` and unpack its skills under `<skills_dir>/imported/`.
+///
+/// Nothing is written unless every file matches its manifest hash and parses
+/// as a skill, and the signature satisfies `cfg`. Skills whose permissions
+/// exceed `safety` are still written but reported in `not_enabled`.
+pub fn import_skill_pack(
+    skills_dir: &Path,
+    path: &Path,
+    cfg: &SkillsConfig,
+    safety: &SafetyConfig,
+) -> SeeClawResult<ImportReport> {
+    let mut zip = zip::ZipArchive::new(std::fs::File::open(path)?).map_err(zip_err)?;
+
+    let manifest_bytes = read_entry(&mut zip, MANIFEST_FILE)?
+        .ok_or_else(|| SeeClawError::Skills(format!("{MANIFEST_FILE} missing from pack")))?;
+    let manifest: PackManifest = serde_json::from_slice(&manifest_bytes)
+        .map_err(|e| SeeClawError::Skills(format!("invalid {MANIFEST_FILE}: {e}")))?;
+    if manifest.format > PACK_FORMAT {
+        return Err(SeeClawError::Skills(format!(
+            "pack format {} is newer than supported ({PACK_FORMAT})",
+            manifest.format
+        )));
+    }
+
+    let signature = match read_entry(&mut zip, SIGNATURE_FILE)? {
+        Some(sig) => Some(verify_signature(&manifest, &manifest_bytes, &sig)?),
+        None => None,
+    };
+    let status = match &signature {
+        None => SignatureStatus::Unsigned,
+        Some(key) if cfg.trusted_keys.iter().any(|k| k.trim() == key) => SignatureStatus::Trusted,
+        Some(_) => SignatureStatus::UnknownSigner,
+    };
+    if cfg.require_signed_packs && status != SignatureStatus::Trusted {
+        return Err(SeeClawError::Skills(match status {
+            SignatureStatus::Unsigned => "pack is unsigned and require_signed_packs is enabled".to_string(),
+            _ => format!("pack is signed by an untrusted key: {}", manifest.public_key),
+        }));
+    }
+
+    let dir = skills_dir.join("imported").join(file_stem(&manifest.name));
+    let mut files: Vec<(String, Vec<u8>)> = Vec::new();
+    let mut not_enabled = Vec::new();
+    for entry in &manifest.skills {
+        let file = safe_file_name(&entry.file)?;
+        let bytes = read_entry(&mut zip, &format!("{SKILLS_PREFIX}{}", entry.file))?
+            .ok_or_else(|| SeeClawError::Skills(format!("{} listed in manifest but missing", entry.file)))?;
+        if sha256_hex(&bytes) != entry.sha256.to_ascii_lowercase() {
+            return Err(SeeClawError::Skills(format!("{}: hash does not match manifest", entry.file)));
+        }
+        let skill: SkillDefinition = serde_json::from_slice(&bytes)
+            .map_err(|e| SeeClawError::Skills(format!("{}: not a valid skill: {e}", entry.file)))?;
+        if skill.name != entry.name {
+            return Err(SeeClawError::Skills(format!(
+                "{}: defines '{}' but manifest says '{}'",
+                entry.file, skill.name, entry.name
+            )));
+        }
+        if let Some(reason) = skill.permission_error(safety) {
+            not_enabled.push(RejectedSkill {
+                name: skill.name,
+                path: dir.join(&file).display().to_string(),
+                reason,
+            });
+        }
+        files.push((file, bytes));
+    }
+
+    std::fs::create_dir_all(&dir)?;
+    for (file, bytes) in &files {
+        std::fs::write(dir.join(file), bytes)?;
+    }
+
+    tracing::info!(pack = %manifest.name, skills = files.len(), signature = ?status, dir = %dir.display(), "skill pack imported");
+    Ok(ImportReport {
+        pack: manifest.name,
+        dir: dir.display().to_string(),
+        skills: manifest.skills.into_iter().map(|e| e.name).collect(),
+        signature: status,
+        signer: signature,
+        not_enabled,
+    })
+}
+
+/// Check `sig` (base64) over the manifest bytes against the manifest's public
+/// key. Returns that key on success; a bad signature is always an error.
+fn verify_signature(manifest: &PackManifest, manifest_bytes: &[u8], sig: &[u8]) -> SeeClawResult<String> {
+    let key_bytes: [u8; 32] = STANDARD
+        .decode(manifest.public_key.trim())
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| SeeClawError::Skills("pack is signed but has no valid public_key".into()))?;
+    let key = VerifyingKey::from_bytes(&key_bytes)
+        .map_err(|e| SeeClawError::Skills(format!("invalid signer key: {e}")))?;
+    let sig_bytes: [u8; 64] = STANDARD
+        .decode(String::from_utf8_lossy(sig).trim())
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| SeeClawError::Skills(format!("malformed {SIGNATURE_FILE}")))?;
+    key.verify(manifest_bytes, &Signature::from_bytes(&sig_bytes))
+        .map_err(|_| SeeClawError::Skills("pack signature does not match its manifest".into()))?;
+    Ok(manifest.public_key.trim().to_string())
+}
+
+// ── Helpers ─────────────────────────────────────────────────────────────────
+
+fn parse_signing_key(encoded: &str) -> SeeClawResult<SigningKey> {
+    let seed: [u8; 32] = STANDARD
+        .decode(encoded.trim())
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| SeeClawError::Config("skill signing key must be a base64 32-byte seed".into()))?;
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+fn zip_err(e: zip::result::ZipError) -> SeeClawError {
+    SeeClawError::Skills(format!("zip: {e}"))
+}
+
+/// Read a whole archive entry, `Ok(None)` when it does not exist.
+fn read_entry(zip: &mut zip::ZipArchive<std::fs::File>, name: &str) -> SeeClawResult<Option<Vec<u8>>> {
+    let entry = match zip.by_name(name) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(zip_err(e)),
+    };
+    if entry.size() > MAX_SKILL_FILE_BYTES {
+        return Err(SeeClawError::Skills(format!("{name} is too large")));
+    }
+    let mut bytes = Vec::new();
+    entry.take(MAX_SKILL_FILE_BYTES).read_to_end(&mut bytes)?;
+    Ok(Some(bytes))
+}
+
+/// Only plain `*.skill.json` file names — no directories, so a pack can never
+/// write outside its own folder.
+fn safe_file_name(file: &str) -> SeeClawResult<String> {
+    let valid = !file.is_empty()
+        && file.ends_with(".skill.json")
+        && !file.contains(['/', '\\', ':'])
+        && !file.starts_with('.');
+    if valid {
+        Ok(file.to_string())
+    } else {
+        Err(SeeClawError::Skills(format!("invalid skill file name in pack: {file}")))
+    }
+}
+
+fn file_stem(name: &str) -> String {
+    let stem: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '_' || c == '-' { c } else { '_' })
+        .collect();
+    if stem.is_empty() { "pack".to_string() } else { stem }
+}
+
+/// The source file's name, suffixed if another exported skill already uses it.
+fn unique_file_name(path: &Path, taken: &[(PackEntry, Vec<u8>)]) -> String {
+    let base = path
+        .file_name()
+        .and_then(|f| f.to_str())
+        .and_then(|f| f.strip_suffix(".skill.json"))
+        .map(file_stem)
+        .unwrap_or_else(|| "skill".to_string());
+    let mut candidate = format!("{base}.skill.json");
+    let mut n = 2;
+    while taken.iter().any(|(e, _)| e.file == candidate) {
+        candidate = format!("{base}_{n}.skill.json");
+        n += 1;
+    }
+    candidate
+}
+
+/// Map skill name → (path, raw bytes) for every parseable `.skill.json` under `dir`.
+fn collect_skill_files(dir: &Path, out: &mut HashMap<String, (PathBuf, Vec<u8>)>) -> SeeClawResult<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_skill_files(&path, out)?;
+        } else if path.to_string_lossy().ends_with(".skill.json") {
+            let Ok(bytes) = std::fs::read(&path) else { continue };
+            match serde_json::from_slice::<SkillDefinition>(&bytes) {
+                Ok(skill) => {
+                    out.entry(skill.name).or_insert((path, bytes));
+                }
+                Err(e) => tracing::debug!(path = %path.display(), error = %e, "skipping unparseable skill file"),
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,405 @@
+//! Skill recorder — captures the user's mouse/keyboard input into a new skill.
+//!
+//! `start()` begins a recording session: a global input hook (rdev) appends
+//! normalised events to the session and saves a screenshot after every click
+//! so the recording can be reviewed later. `stop()` converts the captured
+//! events into `ComboStep`s and writes a `.skill.json` file under
+//! `<skills_dir>/recorded/`, where `load_skill_registry` picks it up.
+//!
+//! Clicks are stored as SoM grid labels (e.g. "C4") so they replay through the
+//! normal grounding path; typed characters are merged into `type_text`, chords
+//! become `hotkey`, and pauses longer than `MIN_WAIT_MS` become `wait` steps.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use serde::Serialize;
+
+use crate::errors::{SeeClawError, SeeClawResult};
+use crate::perception::som_grid::cell_label;
+use crate::skills::registry::{ComboStep, SkillDefinition};
+
+/// Pauses shorter than this are not recorded as `wait` steps.
+const MIN_WAIT_MS: u64 = 300;
+/// Longer pauses are clamped (the user was probably thinking, not waiting on the UI).
+const MAX_WAIT_MS: u64 = 5000;
+/// Two left clicks on the same cell within this window form a double-click.
+const DOUBLE_CLICK_MS: u64 = 400;
+
+// ── Recorded input ──────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClickButton {
+    Left,
+    Right,
+}
+
+/// A single user input, already normalised to the agent's action vocabulary.
+#[derive(Debug, Clone)]
+pub enum RecordedInput {
+    /// Mouse click at physical screen coordinates.
+    Click { x: f64, y: f64, button: ClickButton },
+    /// A non-text key, optionally with held modifiers ("enter", ["ctrl"] + "c").
+    Key { key: String, modifiers: Vec<String> },
+    /// Printable text produced by a key press.
+    Text(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct TimedInput {
+    /// Milliseconds since the recording started.
+    pub at_ms: u64,
+    pub input: RecordedInput,
+}
+
+/// Convert recorded input into replayable combo steps.
+///
+/// `screen` is the physical size of the primary monitor; clicks outside it
+/// are dropped.
+pub fn inputs_to_steps(inputs: &[TimedInput], screen: (u32, u32), grid_n: u32) -> Vec<ComboStep> {
+    let mut steps: Vec<ComboStep> = Vec::new();
+    let mut text = String::new();
+    let mut last_at: Option<u64> = None;
+    // (at_ms, cell) of the last single left click, for double-click folding
+    let mut last_left: Option<(u64, String)> = None;
+
+    let flush_text = |text: &mut String, steps: &mut Vec<ComboStep>| {
+        if !text.is_empty() {
+            steps.push(step("type_text", serde_json::json!({ "text": text.clone(), "clear_first": false })));
+            text.clear();
+        }
+    };
+
+    for ev in inputs {
+        let gap = last_at.map(|t| ev.at_ms.saturating_sub(t)).unwrap_or(0);
+        last_at = Some(ev.at_ms);
+
+        if let RecordedInput::Text(s) = &ev.input {
+            // Pauses inside a run of typing are not meaningful UI waits.
+            text.push_str(s);
+            last_left = None;
+            continue;
+        }
+        flush_text(&mut text, &mut steps);
+
+        match &ev.input {
+            RecordedInput::Click { x, y, button } => {
+                let Some(cell) = point_to_cell(*x, *y, screen, grid_n) else { continue };
+                if *button == ClickButton::Left {
+                    if let Some((prev_at, prev_cell)) = &last_left {
+                        if ev.at_ms - prev_at <= DOUBLE_CLICK_MS && *prev_cell == cell {
+                            if let Some(last) = steps.last_mut() {
+                                last.action = "mouse_double_click".into();
+                            }
+                            last_left = None;
+                            continue;
+                        }
+                    }
+                }
+                push_wait(&mut steps, gap);
+                let action = match button {
+                    ClickButton::Left => "mouse_click",
+                    ClickButton::Right => "mouse_right_click",
+                };
+                steps.push(step(action, serde_json::json!({ "element_id": cell })));
+                last_left = (*button == ClickButton::Left).then(|| (ev.at_ms, cell));
+            }
+            RecordedInput::Key { key, modifiers } => {
+                push_wait(&mut steps, gap);
+                if modifiers.is_empty() {
+                    steps.push(step("key_press", serde_json::json!({ "key": key })));
+                } else {
+                    let keys = format!("{}+{}", modifiers.join("+"), key);
+                    steps.push(step("hotkey", serde_json::json!({ "keys": keys })));
+                }
+                last_left = None;
+            }
+            RecordedInput::Text(_) => unreachable!(),
+        }
+    }
+    flush_text(&mut text, &mut steps);
+    steps
+}
+
+fn step(action: &str, args: serde_json::Value) -> ComboStep {
+    ComboStep { action: action.to_string(), args }
+}
+
+fn push_wait(steps: &mut Vec<ComboStep>, gap_ms: u64) {
+    if steps.is_empty() || gap_ms < MIN_WAIT_MS {
+        return;
+    }
+    let ms = (gap_ms.min(MAX_WAIT_MS) / 100) * 100;
+    steps.push(step("wait", serde_json::json!({ "milliseconds": ms })));
+}
+
+fn point_to_cell(x: f64, y: f64, (w, h): (u32, u32), grid_n: u32) -> Option<String> {
+    if x < 0.0 || y < 0.0 || x >= w as f64 || y >= h as f64 || grid_n == 0 {
+        return None;
+    }
+    let col = (x / w as f64 * grid_n as f64) as u32;
+    let row = (y / h as f64 * grid_n as f64) as u32;
+    Some(cell_label(col.min(grid_n - 1), row.min(grid_n - 1)))
+}
+
+// ── Recording session ───────────────────────────────────────────────────────
+
+struct Session {
+    started: Instant,
+    inputs: Vec<TimedInput>,
+    screen: (u32, u32),
+    shots_dir: PathBuf,
+    shots: usize,
+}
+
+static SESSION: Mutex<Option<Session>> = Mutex::new(None);
+
+/// Result of a finished recording, returned to the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordedSkill {
+    pub name: String,
+    pub path: String,
+    pub steps: usize,
+    /// Folder with one screenshot per recorded click.
+    pub screenshots_dir: String,
+}
+
+pub fn is_recording() -> bool {
+    SESSION.lock().map(|s| s.is_some()).unwrap_or(false)
+}
+
+/// Start a recording session. Fails if one is already running or the
+/// platform has no global input hook.
+pub async fn start() -> SeeClawResult<()> {
+    if is_recording() {
+        return Err(SeeClawError::Skills("a skill recording is already in progress".into()));
+    }
+
+    hook::ensure_listening()?;
+
+    let shot = crate::perception::screenshot::capture_primary().await?;
+    let shots_dir = crate::agent_engine::history::data_dir_or_cwd("recordings")
+        .join(chrono::Local::now().format("%Y%m%d-%H%M%S").to_string());
+    std::fs::create_dir_all(&shots_dir)?;
+    std::fs::write(shots_dir.join("start.jpg"), &shot.image_bytes)?;
+
+    let mut guard = SESSION.lock().map_err(|e| SeeClawError::Skills(e.to_string()))?;
+    if guard.is_some() {
+        return Err(SeeClawError::Skills("a skill recording is already in progress".into()));
+    }
+    *guard = Some(Session {
+        started: Instant::now(),
+        inputs: Vec::new(),
+        screen: (shot.meta.physical_width, shot.meta.physical_height),
+        shots_dir,
+        shots: 0,
+    });
+    tracing::info!("skill recording started");
+    Ok(())
+}
+
+/// Stop the current session and write it as `<skills_dir>/recorded/<name>.skill.json`.
+///
+/// The final click is discarded: it is the click on SeeClaw's own
+/// "stop recording" button.
+pub async fn stop(
+    skills_dir: &Path,
+    name: &str,
+    description: &str,
+    triggers: &str,
+    grid_n: u32,
+) -> SeeClawResult<RecordedSkill> {
+    let session = SESSION
+        .lock()
+        .map_err(|e| SeeClawError::Skills(e.to_string()))?
+        .take()
+        .ok_or_else(|| SeeClawError::Skills("no skill recording in progress".into()))?;
+
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(SeeClawError::Skills("skill name must not be empty".into()));
+    }
+
+    let mut inputs = session.inputs;
+    if matches!(inputs.last().map(|i| &i.input), Some(RecordedInput::Click { .. })) {
+        inputs.pop();
+    }
+    let steps = inputs_to_steps(&inputs, session.screen, grid_n);
+    if steps.is_empty() {
+        return Err(SeeClawError::Skills("nothing was recorded".into()));
+    }
+
+    let skill = SkillDefinition {
+        name: name.to_string(),
+        description: description.trim().to_string(),
+        params: Vec::new(),
+        triggers: triggers.trim().to_string(),
+        steps,
+        permissions: Vec::new(),
+    };
+
+    let dir = skills_dir.join("recorded");
+    tokio::fs::create_dir_all(&dir).await?;
+    let path = dir.join(format!("{}.skill.json", file_stem(name)));
+    if path.exists() {
+        return Err(SeeClawError::Skills(format!("skill file already exists: {}", path.display())));
+    }
+    tokio::fs::write(&path, serde_json::to_string_pretty(&skill)?).await?;
+
+    tracing::info!(skill = %skill.name, steps = skill.steps.len(), path = %path.display(), "skill recording saved");
+    Ok(RecordedSkill {
+        name: skill.name,
+        path: path.display().to_string(),
+        steps: skill.steps.len(),
+        screenshots_dir: session.shots_dir.display().to_string(),
+    })
+}
+
+fn file_stem(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_alphanumeric() || c == '_' || c == '-' { c } else { '_' })
+        .collect()
+}
+
+/// Called from the hook thread for every normalised input.
+fn record(input: RecordedInput) {
+    let Ok(mut guard) = SESSION.lock() else { return };
+    let Some(session) = guard.as_mut() else { return };
+    let at_ms = session.started.elapsed().as_millis() as u64;
+
+    if matches!(input, RecordedInput::Click { .. }) {
+        // Screenshot off the hook thread — low-level hooks must return quickly.
+        session.shots += 1;
+        let path = session.shots_dir.join(format!("click_{:03}.jpg", session.shots));
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(150)).await;
+            match crate::perception::screenshot::capture_primary().await {
+                Ok(shot) => {
+                    let _ = tokio::fs::write(&path, &shot.image_bytes).await;
+                }
+                Err(e) => tracing::debug!(error = %e, "recording screenshot failed"),
+            }
+        });
+    }
+    session.inputs.push(TimedInput { at_ms, input });
+}
+
+// ── Global input hook ───────────────────────────────────────────────────────
+
+#[cfg(feature = "recorder")]
+mod hook {
+    use std::sync::OnceLock;
+
+    use super::{record, ClickButton, RecordedInput};
+    use crate::errors::SeeClawResult;
+    use crate::input_hook::{self, Button, EventType, Key};
+
+    static STARTED: OnceLock<()> = OnceLock::new();
+
+    /// Subscribe to the global input hook once; the handler stays for the
+    /// rest of the process and only records while a session is active.
+    pub fn ensure_listening() -> SeeClawResult<()> {
+        if STARTED.set(()).is_err() {
+            return Ok(());
+        }
+        let mut pos = (0.0, 0.0);
+        let mut modifiers: Vec<&'static str> = Vec::new();
+        input_hook::subscribe(move |event| match event.event_type {
+            EventType::MouseMove { x, y } => pos = (x, y),
+            EventType::ButtonPress(button) => {
+                let button = match button {
+                    Button::Left => ClickButton::Left,
+                    Button::Right => ClickButton::Right,
+                    _ => return,
+                };
+                record(RecordedInput::Click { x: pos.0, y: pos.1, button });
+            }
+            EventType::KeyPress(key) => {
+                if let Some(m) = modifier_name(key) {
+                    if !modifiers.contains(&m) {
+                        modifiers.push(m);
+                    }
+                    return;
+                }
+                let chord = modifiers.iter().any(|m| *m != "shift");
+                if !chord {
+                    if let Some(text) = event.name.clone().filter(|n| n.chars().all(|c| !c.is_control())) {
+                        record(RecordedInput::Text(text));
+                        return;
+                    }
+                }
+                if let Some(name) = key_name(key) {
+                    let mods = if chord { modifiers.iter().map(|m| m.to_string()).collect() } else { Vec::new() };
+                    record(RecordedInput::Key { key: name, modifiers: mods });
+                }
+            }
+            EventType::KeyRelease(key) => {
+                if let Some(m) = modifier_name(key) {
+                    modifiers.retain(|x| *x != m);
+                }
+            }
+            _ => {}
+        })
+    }
+
+    fn modifier_name(key: Key) -> Option<&'static str> {
+        match key {
+            Key::ControlLeft | Key::ControlRight => Some("ctrl"),
+            Key::ShiftLeft | Key::ShiftRight => Some("shift"),
+            Key::Alt | Key::AltGr => Some("alt"),
+            Key::MetaLeft | Key::MetaRight => Some("win"),
+            _ => None,
+        }
+    }
+
+    /// Map to the key names accepted by `executor::input::press_hotkey`.
+    fn key_name(key: Key) -> Option<String> {
+        let name = match key {
+            Key::Return | Key::KpReturn => "enter",
+            Key::Escape => "escape",
+            Key::Tab => "tab",
+            Key::Space => "space",
+            Key::Backspace => "backspace",
+            Key::Delete => "delete",
+            Key::Home => "home",
+            Key::End => "end",
+            Key::PageUp => "pageup",
+            Key::PageDown => "pagedown",
+            Key::UpArrow => "up",
+            Key::DownArrow => "down",
+            Key::LeftArrow => "left",
+            Key::RightArrow => "right",
+            Key::F1 => "f1",
+            Key::F2 => "f2",
+            Key::F3 => "f3",
+            Key::F4 => "f4",
+            Key::F5 => "f5",
+            Key::F6 => "f6",
+            Key::F7 => "f7",
+            Key::F8 => "f8",
+            Key::F9 => "f9",
+            Key::F10 => "f10",
+            Key::F11 => "f11",
+            Key::F12 => "f12",
+            other => {
+                // KeyA..KeyZ → "a".."z", Num0..Num9 → "0".."9"
+                let debug = format!("{other:?}");
+                let c = debug.strip_prefix("Key").or_else(|| debug.strip_prefix("Num"))?;
+                return (c.len() == 1).then(|| c.to_lowercase());
+            }
+        };
+        Some(name.to_string())
+    }
+}
+
+#[cfg(not(feature = "recorder"))]
+mod hook {
+    use crate::errors::{SeeClawError, SeeClawResult};
+
+    pub fn ensure_listening() -> SeeClawResult<()> {
+        // Keep `record` referenced so non-recorder builds stay warning-free.
+        let _ = super::record;
+        Err(SeeClawError::Skills("skill recording requires the `recorder` feature".into()))
+    }
+}
@@ -12,6 +12,14 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::config::SafetyConfig;
+use crate::mcp::manager::split_namespaced_tool_name;
+
+/// Registries up to this size are listed in full in the planner prompt.
+const LIST_ALL_THRESHOLD: usize = 8;
+/// Maximum number of detailed skill entries in a goal-filtered prompt.
+const MAX_RELEVANT_SKILLS: usize = 6;
+
 // ── SkillDefinition — the unified .skill.json format ───────────────────────
 
 /// A complete skill loaded from a single `.skill.json` file.
@@ -27,6 +35,98 @@ pub struct SkillDefinition {
     pub triggers: String,
     /// Ordered action steps to execute (the combo sequence).
     pub steps: Vec<ComboStep>,
+    /// Capabilities beyond GUI input the skill needs, e.g. `["terminal"]`.
+    /// Checked against `[safety]` when the skill is loaded.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub permissions: Vec<SkillPermission>,
+}
+
+/// A capability a skill must declare before it may use it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillPermission {
+    Terminal,
+    FileWrite,
+    Network,
+}
+
+impl SkillPermission {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Terminal => "terminal",
+            Self::FileWrite => "file_write",
+            Self::Network => "network",
+        }
+    }
+
+    /// The `[safety]` switch that grants this permission.
+    fn setting(self) -> &'static str {
+        match self {
+            Self::Terminal => "allow_terminal_commands",
+            Self::FileWrite => "allow_file_operations",
+            Self::Network => "allow_network",
+        }
+    }
+
+    fn allowed_by(self, safety: &SafetyConfig) -> bool {
+        match self {
+            Self::Terminal => safety.allow_terminal_commands,
+            Self::FileWrite => safety.allow_file_operations,
+            Self::Network => safety.allow_network,
+        }
+    }
+
+    /// Permission implied by a combo step's action, if any. GUI input,
+    /// clipboard and read-only file tools need none.
+    fn for_action(action: &str) -> Option<Self> {
+        match action {
+            // Launching an app runs an arbitrary program, like a command.
+            "execute_terminal" | "open_app" => Some(Self::Terminal),
+            "write_file" | "move_file" => Some(Self::FileWrite),
+            "mcp_call" => Some(Self::Network),
+            name if split_namespaced_tool_name(name).is_some() => Some(Self::Network),
+            _ => None,
+        }
+    }
+}
+
+impl SkillDefinition {
+    /// Why the skill may not be enabled under `safety`, or `None` if it may.
+    ///
+    /// A step that needs a permission the skill does not declare is refused
+    /// too, so a skill cannot escalate by simply leaving `permissions` out.
+    pub fn permission_error(&self, safety: &SafetyConfig) -> Option<String> {
+        for step in &self.steps {
+            if let Some(needed) = SkillPermission::for_action(&step.action) {
+                if !self.permissions.contains(&needed) {
+                    return Some(format!(
+                        "step '{}' needs the '{}' permission, which the skill does not declare",
+                        step.action,
+                        needed.as_str()
+                    ));
+                }
+            }
+        }
+        let denied: Vec<String> = self
+            .permissions
+            .iter()
+            .filter(|p| !p.allowed_by(safety))
+            .map(|p| format!("'{}' (enable [safety] {})", p.as_str(), p.setting()))
+            .collect();
+        if denied.is_empty() {
+            None
+        } else {
+            Some(format!("requires {} permission not granted by the current safety settings", denied.join(", ")))
+        }
+    }
+}
+
+/// A skill that was found but not enabled.
+#[derive(Debug, Clone, Serialize)]
+pub struct RejectedSkill {
+    pub name: String,
+    pub path: String,
+    pub reason: String,
 }
 
 /// A single action inside a combo sequence.
@@ -38,6 +138,31 @@ pub struct ComboStep {
     pub args: serde_json::Value,
 }
 
+/// Render the planner skills section: full entries for `detailed`, names only for `others`.
+fn summary_for(detailed: &[&SkillDefinition], others: &[&str]) -> String {
+    if detailed.is_empty() && others.is_empty() {
+        return String::new();
+    }
+
+    let mut out = String::from("# Available Skills\n\n");
+    out.push_str("When a task matches a skill's triggers below, you MUST include it in `required_skills` and recommend `combo` mode.\n\n");
+
+    for skill in detailed {
+        out.push_str(&format!(
+            "- **{}**: {} | params: [{}] | triggers: {}\n",
+            skill.name,
+            skill.description,
+            skill.params.join(", "),
+            skill.triggers,
+        ));
+    }
+    if !others.is_empty() {
+        out.push_str(&format!("\nOther skills (less relevant to this goal): {}\n", others.join(", ")));
+    }
+
+    out
+}
+
 // ── Legacy type aliases (for backward compatibility during migration) ───────
 
 pub type SkillManifest = SkillDefinition;
@@ -49,15 +174,28 @@ pub type ComboDefinition = SkillDefinition;
 #[derive(Debug, Clone)]
 pub struct SkillRegistry {
     skills: HashMap<String, SkillDefinition>,
+    /// Skills refused at load time (e.g. permissions exceed `SafetyConfig`).
+    rejected: Vec<RejectedSkill>,
 }
 
 impl SkillRegistry {
     pub fn new() -> Self {
         Self {
             skills: HashMap::new(),
+            rejected: Vec::new(),
         }
     }
 
+    /// Record a skill that was not enabled, and why.
+    pub fn reject(&mut self, skill: RejectedSkill) {
+        self.rejected.push(skill);
+    }
+
+    /// Skills refused at load time.
+    pub fn rejected_skills(&self) -> &[RejectedSkill] {
+        &self.rejected
+    }
+
     /// Insert a skill definition.
     pub fn add_skill(&mut self, skill: SkillDefinition) {
         self.skills.insert(skill.name.clone(), skill);
@@ -89,24 +227,58 @@ impl SkillRegistry {
     /// minimal to keep token usage low. The Planner uses this to recommend
     /// combo mode and specify `required_skills` in its plan output.
     pub fn manifest_summary_for_planner(&self) -> String {
-        if self.skills.is_empty() {
-            return String::new();
-        }
+        let mut skills: Vec<&SkillDefinition> = self.skills.values().collect();
+        skills.sort_by(|a, b| a.name.cmp(&b.name));
+        summary_for(&skills, &[])
+    }
 
-        let mut out = String::from("# Available Skills\n\n");
-        out.push_str("When a task matches a skill's triggers below, you MUST include it in `required_skills` and recommend `combo` mode.\n\n");
+    /// Planner skills context filtered by relevance to `goal`.
+    ///
+    /// Small registries are listed in full. Larger ones list the best-matching
+    /// skills in detail (by triggers and name keywords) and only the names of
+    /// the rest, so the planner still knows they exist.
+    pub fn skills_context_for_goal(&self, goal: &str) -> String {
+        if self.skills.len() <= LIST_ALL_THRESHOLD {
+            return self.manifest_summary_for_planner();
+        }
 
+        let lower = goal.to_lowercase();
+        let mut scores: HashMap<&str, f32> = self
+            .match_triggers(goal)
+            .into_iter()
+            .filter_map(|(name, score)| self.skills.get_key_value(&name).map(|(k, _)| (k.as_str(), score)))
+            .collect();
         for skill in self.skills.values() {
-            out.push_str(&format!(
-                "- **{}**: {} | params: [{}] | triggers: {}\n",
-                skill.name,
-                skill.description,
-                skill.params.join(", "),
-                skill.triggers,
-            ));
+            let hits = skill
+                .name
+                .split('_')
+                .filter(|w| w.len() > 2 && lower.contains(w))
+                .count();
+            if hits > 0 {
+                *scores.entry(skill.name.as_str()).or_insert(0.0) += 0.5 * hits as f32;
+            }
         }
 
-        out
+        let mut ranked: Vec<(&str, f32)> = scores.into_iter().collect();
+        ranked.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.cmp(b.0))
+        });
+        ranked.truncate(MAX_RELEVANT_SKILLS);
+
+        let relevant: Vec<&SkillDefinition> = ranked
+            .iter()
+            .filter_map(|(name, _)| self.skills.get(*name))
+            .collect();
+        let mut others: Vec<&str> = self
+            .skills
+            .keys()
+            .map(|k| k.as_str())
+            .filter(|k| !relevant.iter().any(|s| s.name == *k))
+            .collect();
+        others.sort_unstable();
+        summary_for(&relevant, &others)
     }
 
     /// Find skills whose triggers match the given text.
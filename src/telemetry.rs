@@ -0,0 +1,86 @@
+//! Tracing subscriber setup shared by the desktop app and the CLI.
+//!
+//! Logs always go to the given writer, filtered by `RUST_LOG`. Builds with
+//! the `otel` feature also ship spans — `task`, one per graph `node`,
+//! `llm_call` and `perception_parser` — to an OTLP collector when
+//! `[telemetry] otlp_endpoint` (or `OTEL_EXPORTER_OTLP_ENDPOINT`) is set.
+
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+
+use crate::config::TelemetryConfig;
+
+/// Install the global subscriber. `default_filter` applies when `RUST_LOG` is
+/// unset. With an exporter configured this must run inside a Tokio runtime.
+pub fn init<W>(default_filter: &str, writer: W, cfg: &TelemetryConfig)
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_filter));
+    let registry = tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer().with_writer(writer));
+
+    #[cfg(feature = "otel")]
+    {
+        let endpoint = cfg.resolve_endpoint();
+        let otel = endpoint.as_deref().map(|ep| otlp_layer(ep, &cfg.service_name));
+        let (layer, error) = match otel {
+            Some(Ok(layer)) => (Some(layer), None),
+            Some(Err(e)) => (None, Some(e)),
+            None => (None, None),
+        };
+        let exporting = layer.is_some();
+        registry.with(layer).init();
+        match (endpoint, error) {
+            (Some(ep), None) if exporting => tracing::info!(endpoint = %ep, "telemetry: exporting spans over OTLP"),
+            (Some(ep), Some(e)) => tracing::warn!(endpoint = %ep, error = %e, "telemetry: OTLP exporter unavailable"),
+            _ => {}
+        }
+    }
+    #[cfg(not(feature = "otel"))]
+    {
+        registry.init();
+        if cfg.resolve_endpoint().is_some() {
+            tracing::warn!("telemetry: otlp_endpoint is set but this build has no `otel` feature");
+        }
+    }
+}
+
+/// Flush spans still queued for export. Call once before the process exits.
+pub fn shutdown() {
+    #[cfg(feature = "otel")]
+    opentelemetry::global::shutdown_tracer_provider();
+}
+
+#[cfg(feature = "otel")]
+fn otlp_layer<S>(
+    endpoint: &str,
+    service_name: &str,
+) -> Result<tracing_opentelemetry::OpenTelemetryLayer<S, opentelemetry_sdk::trace::Tracer>, String>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+{
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_otlp::WithExportConfig;
+
+    // The exporter connects on its own schedule; list the collector up front.
+    crate::net_audit::record(endpoint, crate::net_audit::NetPurpose::Telemetry);
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let provider = opentelemetry_sdk::trace::TracerProvider::builder()
+        .with_batch_exporter(exporter, opentelemetry_sdk::runtime::Tokio)
+        .with_resource(opentelemetry_sdk::Resource::new([opentelemetry::KeyValue::new(
+            "service.name",
+            service_name.to_string(),
+        )]))
+        .build();
+    let tracer = provider.tracer("seeclaw");
+    opentelemetry::global::set_tracer_provider(provider);
+    Ok(tracing_opentelemetry::layer().with_tracer(tracer))
+}
@@ -0,0 +1,185 @@
+//! System tray companion — agent status at a glance and a quick-goal launcher.
+//!
+//! The tray icon is a coloured dot that follows `agent_state_changed`:
+//! grey idle, blue working, amber waiting on the user (or queued offline),
+//! green done, red error. Left click opens the quick-goal prompt — a small
+//! always-on-top window that submits a goal without bringing up the main
+//! window. The menu offers the same plus open / pause-resume / stop / quit; "open"
+//! recreates the main window if it was closed in background mode. The
+//! quick-goal prompt and stop are also bound to global hotkeys (see
+//! [`crate::hotkeys`]).
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use tauri::image::Image;
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Listener, Manager, WebviewUrl, WebviewWindowBuilder};
+
+use crate::agent_engine::state::AgentEvent;
+use crate::background;
+use crate::AgentHandle;
+
+const TRAY_ID: &str = "seeclaw-tray";
+/// Label of the quick-goal window; the frontend renders the prompt when
+/// `window.__SEECLAW_VIEW__ === "quick"`.
+pub const QUICK_WINDOW: &str = "quick";
+const ICON_SIZE: u32 = 32;
+/// Whether the last `agent_state_changed` was "paused"; decides what the
+/// pause menu item does.
+static PAUSED: AtomicBool = AtomicBool::new(false);
+
+/// Coarse agent status shown by the tray icon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TrayStatus {
+    Idle,
+    Working,
+    Waiting,
+    Done,
+    Error,
+}
+
+impl TrayStatus {
+    /// Map an `agent_state_changed` state name.
+    fn from_state(state: &str) -> Self {
+        match state {
+            "idle" | "" => Self::Idle,
+            "waiting_for_user" | "deferred" | "paused" => Self::Waiting,
+            "done" => Self::Done,
+            "error" => Self::Error,
+            _ => Self::Working,
+        }
+    }
+
+    fn rgb(self) -> [u8; 3] {
+        match self {
+            Self::Idle => [140, 140, 150],
+            Self::Working => [52, 120, 246],
+            Self::Waiting => [245, 166, 35],
+            Self::Done => [46, 160, 67],
+            Self::Error => [220, 53, 69],
+        }
+    }
+
+    fn tooltip(self) -> &'static str {
+        match self {
+            Self::Idle => "SeeClaw — idle",
+            Self::Working => "SeeClaw — working…",
+            Self::Waiting => "SeeClaw — waiting for you",
+            Self::Done => "SeeClaw — task done",
+            Self::Error => "SeeClaw — task failed",
+        }
+    }
+}
+
+/// Create the tray icon and keep it in sync with the agent state.
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    let quick = MenuItem::with_id(app, "quick_goal", "Quick goal…", true, None::<&str>)?;
+    let open = MenuItem::with_id(app, "open", "Open SeeClaw", true, None::<&str>)?;
+    let pause = MenuItem::with_id(app, "pause", "Pause / resume task", true, None::<&str>)?;
+    let stop = MenuItem::with_id(app, "stop", "Stop current task", true, None::<&str>)?;
+    let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+    let separator = PredefinedMenuItem::separator(app)?;
+    let menu = Menu::with_items(app, &[&quick, &open, &pause, &stop, &separator, &quit])?;
+
+    TrayIconBuilder::with_id(TRAY_ID)
+        .icon(status_icon(TrayStatus::Idle))
+        .tooltip(TrayStatus::Idle.tooltip())
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(|app, event| match event.id.as_ref() {
+            "quick_goal" => show_quick_goal(app),
+            "open" => background::show_main_window(app),
+            "pause" => toggle_pause(app),
+            "stop" => stop_task(app),
+            "quit" => app.exit(0),
+            _ => {}
+        })
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_quick_goal(tray.app_handle());
+            }
+        })
+        .build(app)?;
+
+    let handle = app.clone();
+    app.listen_any("agent_state_changed", move |event| {
+        let state = serde_json::from_str::<serde_json::Value>(event.payload())
+            .ok()
+            .and_then(|v| v["state"].as_str().map(str::to_string))
+            .unwrap_or_default();
+        PAUSED.store(state == "paused", Ordering::Relaxed);
+        let status = TrayStatus::from_state(&state);
+        if let Some(tray) = handle.tray_by_id(TRAY_ID) {
+            let _ = tray.set_icon(Some(status_icon(status)));
+            let _ = tray.set_tooltip(Some(status.tooltip()));
+        }
+    });
+    Ok(())
+}
+
+/// Open (or focus) the quick-goal prompt.
+pub(crate) fn show_quick_goal(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(QUICK_WINDOW) {
+        let _ = window.show();
+        let _ = window.set_focus();
+        return;
+    }
+    let built = WebviewWindowBuilder::new(app, QUICK_WINDOW, WebviewUrl::App("index.html".into()))
+        .initialization_script("window.__SEECLAW_VIEW__ = 'quick';")
+        .title("SeeClaw")
+        .inner_size(460.0, 64.0)
+        .resizable(false)
+        .decorations(false)
+        .transparent(true)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .center()
+        .focused(true)
+        .build();
+    if let Err(e) = built {
+        tracing::warn!(error = %e, "tray: failed to open quick-goal window");
+    }
+}
+
+/// Pause the running task, or resume it when the last state seen was "paused".
+fn toggle_pause(app: &AppHandle) {
+    let handle = app.state::<Arc<AgentHandle>>().inner().clone();
+    let event = if PAUSED.load(Ordering::Relaxed) { AgentEvent::Resume } else { AgentEvent::Pause };
+    tauri::async_runtime::spawn(async move {
+        let _ = handle.tx.send(event).await;
+    });
+}
+
+/// Same as the `stop_task` command: flag first, channel event as backup.
+pub(crate) fn stop_task(app: &AppHandle) {
+    let handle = app.state::<Arc<AgentHandle>>().inner().clone();
+    handle.stop_flag.set();
+    tauri::async_runtime::spawn(async move {
+        let _ = handle.tx.send(AgentEvent::Stop).await;
+    });
+}
+
+/// A filled, anti-aliased dot in the status colour.
+fn status_icon(status: TrayStatus) -> Image<'static> {
+    let [r, g, b] = status.rgb();
+    let size = ICON_SIZE as f32;
+    let center = size / 2.0;
+    let radius = size / 2.0 - 3.0;
+    let mut rgba = Vec::with_capacity((ICON_SIZE * ICON_SIZE * 4) as usize);
+    for y in 0..ICON_SIZE {
+        for x in 0..ICON_SIZE {
+            let dx = x as f32 + 0.5 - center;
+            let dy = y as f32 + 0.5 - center;
+            let coverage = (radius + 0.5 - (dx * dx + dy * dy).sqrt()).clamp(0.0, 1.0);
+            rgba.extend_from_slice(&[r, g, b, (coverage * 255.0) as u8]);
+        }
+    }
+    Image::new_owned(rgba, ICON_SIZE, ICON_SIZE)
+}